@@ -0,0 +1,118 @@
+//! 加速结构基准：BVH、k-d 树与均匀网格的构建和遍历耗时
+//!
+//! 运行：`cargo run --release --example accel_bench`
+//!
+//! 场景：
+//! - 小球群：最终场景中 165³ 立方体内的 1000 个半径为 10 的球（物体密集，互相遮挡）
+//! - 稀疏球：200³ 范围内 20000 个小球（大部分光线穿过空白区域）
+//!
+//! 所有加速结构使用同一组光线，并核对最近交点一致。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::acceleration::Accelerator;
+use ray_tracing_rust::ray_tracing::utils::random::with_seeded_stream;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const RAYS: usize = 200_000;
+
+/// 在以 size 为边长的立方体内随机放置 count 个球
+fn sphere_cluster(count: usize, size: f64, radius: f64) -> HittableList {
+    let white = std::sync::Arc::new(Lambertian::new(Color::new(0.73, 0.73, 0.73)));
+    let mut list = HittableList::new();
+    for _ in 0..count {
+        let center = Point3::new(
+            random_double_range(0.0, size),
+            random_double_range(0.0, size),
+            random_double_range(0.0, size),
+        );
+        list.add(std::sync::Arc::new(Sphere::new(
+            center,
+            radius,
+            white.clone(),
+        )));
+    }
+    list
+}
+
+/// 从立方体外的球面射向立方体内随机点的光线
+fn rays_towards(size: f64) -> Vec<Ray> {
+    let center = Point3::new(size / 2.0, size / 2.0, size / 2.0);
+    (0..RAYS)
+        .map(|_| {
+            let origin = center + Vec3::random_unit_vector() * size * 1.5;
+            let target = Point3::new(
+                random_double_range(0.0, size),
+                random_double_range(0.0, size),
+                random_double_range(0.0, size),
+            );
+            Ray::new(origin, target - origin, 0.0)
+        })
+        .collect()
+}
+
+/// 最近交点遍历，返回 (耗时, 命中数, 交点距离之和)
+fn time_hit(world: &dyn Hittable, rays: &[Ray]) -> (Duration, usize, f64) {
+    let start = Instant::now();
+    let mut rec = HitRecord::default();
+    let (mut hits, mut t_sum) = (0, 0.0);
+    for r in rays {
+        if world.hit(r, Interval::new(0.001, f64::INFINITY), &mut rec) {
+            hits += 1;
+            t_sum += rec.t;
+        }
+    }
+    (start.elapsed(), black_box(hits), t_sum)
+}
+
+/// 遮挡测试遍历，返回 (耗时, 被遮挡的光线数)
+fn time_hit_any(world: &dyn Hittable, rays: &[Ray]) -> (Duration, usize) {
+    let start = Instant::now();
+    let blocked = rays
+        .iter()
+        .filter(|r| world.hit_any(r, Interval::new(0.001, 1.0)))
+        .count();
+    (start.elapsed(), black_box(blocked))
+}
+
+fn bench(name: &str, list: &HittableList, size: f64) {
+    let rays = rays_towards(size);
+    println!("{}（{} 个物体，{} 条光线）", name, list.len(), RAYS);
+
+    let mut reference: Option<(usize, f64)> = None;
+    for accelerator in [Accelerator::Bvh, Accelerator::KdTree, Accelerator::Grid] {
+        let start = Instant::now();
+        let world = accelerator.build(list);
+        let build = start.elapsed();
+        let (hit_time, hits, t_sum) = time_hit(world.as_ref(), &rays);
+        let (any_time, blocked) = time_hit_any(world.as_ref(), &rays);
+
+        let consistent = match reference {
+            None => {
+                reference = Some((hits, t_sum));
+                "参考"
+            }
+            Some((ref_hits, ref_t)) if ref_hits == hits && (ref_t - t_sum).abs() < 1e-6 * ref_t => {
+                "一致"
+            }
+            Some(_) => "不一致!",
+        };
+        println!(
+            "  {:<8} 构建 {:>7.1} ms  最近交点 {:>6.0} ns/光线  遮挡 {:>6.0} ns/光线  命中 {} / 遮挡 {}  {}",
+            format!("{:?}", accelerator),
+            build.as_secs_f64() * 1e3,
+            hit_time.as_secs_f64() * 1e9 / RAYS as f64,
+            any_time.as_secs_f64() * 1e9 / RAYS as f64,
+            hits,
+            blocked,
+            consistent
+        );
+    }
+}
+
+fn main() {
+    with_seeded_stream(7, || {
+        bench("小球群", &sphere_cluster(1000, 165.0, 10.0), 165.0);
+        bench("稀疏球", &sphere_cluster(20000, 200.0, 0.5), 200.0);
+    });
+}
@@ -0,0 +1,84 @@
+//! 输出辅助缓冲区（AOV）：最终图像之外，另存法线、深度和反照率
+//!
+//! 运行：`cargo run --release --example aov_output`
+//! 深度同时以线性 PFM 保存，便于后期合成或作为降噪器的引导输入。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::rendering::framebuffer::FrameBuffer;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::scenes::cornell_box::{CornellBoxParams, CornellContents, build_cornell_box};
+use std::sync::Arc;
+
+fn save(fb: &FrameBuffer, filename: &str) {
+    match save_framebuffer(fb, filename, OutputFormat::from_filename(filename)) {
+        Ok(()) => eprintln!("已保存 {}", filename),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
+
+fn main() {
+    let params =
+        CornellBoxParams::with_contents(&[CornellContents::Boxes, CornellContents::MetalSphere]);
+    let (world, lights) = build_cornell_box(&params);
+    let world = BvhNode::new(&world);
+    let lights: Arc<dyn Hittable> = Arc::new(lights);
+
+    let camera = Camera::builder()
+        .image_width(300)
+        .samples_per_pixel(64)
+        .max_depth(20)
+        .background_color(Color::zeros())
+        .vfov(40.0)
+        .lookfrom(Point3::new(278.0, 278.0, -800.0))
+        .lookat(Point3::new(278.0, 278.0, 0.0))
+        .build();
+
+    // 最终图像
+    let beauty = camera.render_to_buffer(&world, Some(lights));
+    save(&beauty, "aov_beauty.png");
+
+    // 辅助缓冲区：每个像素中心一条主光线
+    let (width, height) = (camera.image_width() as u32, camera.image_height() as u32);
+    let mut normal = FrameBuffer::new(width, height);
+    let mut depth = FrameBuffer::new(width, height);
+    let mut albedo = FrameBuffer::new(width, height);
+    let mut max_depth: f64 = 0.0;
+
+    for j in 0..height {
+        for i in 0..width {
+            let ray = camera.center_ray(i as i32, j as i32);
+            let mut rec = HitRecord::default();
+            if !world.hit(&ray, Interval::new(0.001, f64::INFINITY), &mut rec) {
+                continue;
+            }
+
+            let distance = rec.t * ray.dir.norm();
+            max_depth = max_depth.max(distance);
+            depth.set(i, j, Color::new(distance, distance, distance));
+            normal.set(i, j, (rec.normal + Vec3::new(1.0, 1.0, 1.0)) * 0.5);
+
+            // 反照率：散射衰减，发光体使用其发射颜色
+            let mut srec = ScatterRecord::new();
+            let color = if rec.mat.scatter(&ray, &rec, &mut srec) {
+                srec.attenuation
+            } else {
+                rec.mat.emitted(rec.u, rec.v, &rec.p)
+            };
+            albedo.set(i, j, color);
+        }
+    }
+
+    save(&depth, "aov_depth.pfm");
+    // 深度预览按最大深度归一化（近处亮）
+    let mut depth_preview = FrameBuffer::new(width, height);
+    for j in 0..height {
+        for i in 0..width {
+            let d = depth.get(i, j).x;
+            let v = if d > 0.0 { 1.0 - d / max_depth } else { 0.0 };
+            depth_preview.set(i, j, Color::new(v, v, v));
+        }
+    }
+    save(&depth_preview, "aov_depth.png");
+    save(&normal, "aov_normal.png");
+    save(&albedo, "aov_albedo.png");
+}
@@ -0,0 +1,78 @@
+//! 自动取景：同一个模型按相差五个数量级的尺度放在不同位置，相机由包围球自动取景
+//!
+//! 运行：`cargo run --release --example auto_framing`
+//!
+//! 模型分别以 0.01、1 和 1000 倍的尺度放在远离原点的不同位置（模拟单位不同的 OBJ/PLY 文件），
+//! 三次渲染使用相同的相机设置，只调用 `frame_scene` 取景，三幅画面的构图相同。输出 auto_framing.png。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::geometry::quad::box_new;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::scene::world::Scene;
+use ray_tracing_rust::ray_tracing::utils::image_compare::side_by_side;
+use std::sync::Arc;
+
+/// 一组球和一个盒子，整体按 scale 缩放后平移到 offset
+fn model(scale: f64, offset: Vec3) -> Scene {
+    let at = |x: f64, y: f64, z: f64| Point3::new(x, y, z) * scale + offset;
+    let mut scene = Scene::new();
+    scene.add(Arc::new(box_new(
+        at(-2.0, 0.0, -1.0),
+        at(2.0, 0.3, 1.0),
+        Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5))),
+    )));
+    for (x, radius, color) in [
+        (-1.2, 0.6, Color::new(0.8, 0.2, 0.2)),
+        (0.0, 0.8, Color::new(0.2, 0.7, 0.3)),
+        (1.3, 0.5, Color::new(0.2, 0.3, 0.8)),
+    ] {
+        scene.add(Arc::new(Sphere::new(
+            at(x, 0.3 + radius, 0.0),
+            radius * scale,
+            Arc::new(Lambertian::new(color)),
+        )));
+    }
+    scene
+}
+
+fn main() {
+    let mut images = Vec::new();
+    for (scale, offset) in [
+        (0.01, Vec3::new(3.0, -1.0, 2.0)),
+        (1.0, Vec3::zeros()),
+        (1000.0, Vec3::new(-2.0e4, 5.0e3, 1.0e4)),
+    ] {
+        let scene = model(scale, offset);
+        let camera = Camera::builder()
+            .aspect_ratio(4.0 / 3.0)
+            .image_width(240)
+            .samples_per_pixel(32)
+            .max_depth(6)
+            .vfov(35.0)
+            // 只给出视线方向，位置和距离由取景决定
+            .lookfrom(Point3::new(1.0, 1.2, 3.0))
+            .lookat(Point3::origin())
+            .frame_scene(&scene, 0.1)
+            .background_color(Color::new(0.7, 0.8, 1.0))
+            .seed(3)
+            .build();
+        let (center, radius) = scene.bounding_sphere().unwrap_or((Point3::origin(), 0.0));
+        println!(
+            "尺度 {:>7}: 包围球半径 {:.4}，相机位于 ({:.3}, {:.3}, {:.3})，距球心 {:.4}",
+            scale,
+            radius,
+            camera.lookfrom().x,
+            camera.lookfrom().y,
+            camera.lookfrom().z,
+            (camera.lookfrom() - center).norm()
+        );
+        images.push(camera.render_to_buffer(scene.build_world().as_ref(), scene.light_sampler()));
+    }
+
+    let filename = "auto_framing.png";
+    let refs: Vec<_> = images.iter().collect();
+    match save_framebuffer(&side_by_side(&refs), filename, OutputFormat::Png8) {
+        Ok(()) => println!("已保存 {}（尺度 0.01 | 1 | 1000）", filename),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
@@ -0,0 +1,89 @@
+//! 按色温指定光源：同一个房间分别用 2700K（暖白白炽灯）、4000K（中性白）和 6500K（日光）照明
+//!
+//! 运行：`cargo run --release --example blackbody_lights`
+//!
+//! 输出 blackbody_lights.png。三盏灯的亮度相同，只有色温不同。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::materials::blackbody_light::BlackbodyLight;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::utils::image_compare::side_by_side;
+use std::sync::Arc;
+
+const TEMPERATURES: [f64; 3] = [2700.0, 4000.0, 6500.0];
+
+fn room(kelvin: f64) -> (HittableList, Arc<dyn Hittable>) {
+    let white: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.73, 0.73, 0.73)));
+    let mut world = HittableList::new();
+    for (q, u, v) in [
+        (
+            Point3::origin(),
+            Vec3::new(4.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 4.0),
+        ),
+        (
+            Point3::new(0.0, 4.0, 0.0),
+            Vec3::new(4.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 4.0),
+        ),
+        (
+            Point3::new(0.0, 0.0, 4.0),
+            Vec3::new(4.0, 0.0, 0.0),
+            Vec3::new(0.0, 4.0, 0.0),
+        ),
+        (
+            Point3::origin(),
+            Vec3::new(0.0, 4.0, 0.0),
+            Vec3::new(0.0, 0.0, 4.0),
+        ),
+        (
+            Point3::new(4.0, 0.0, 0.0),
+            Vec3::new(0.0, 4.0, 0.0),
+            Vec3::new(0.0, 0.0, 4.0),
+        ),
+    ] {
+        world.add(Arc::new(Quad::new(q, u, v, white.clone())));
+    }
+    world.add(Arc::new(Sphere::new(
+        Point3::new(2.0, 0.9, 2.2),
+        0.9,
+        Arc::new(Metal::new(Color::new(0.9, 0.9, 0.9), 0.05)),
+    )));
+
+    let lamp: Arc<dyn Hittable> = Arc::new(Quad::new(
+        Point3::new(1.5, 3.99, 1.5),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.0),
+        Arc::new(BlackbodyLight::new(kelvin, 6.0)),
+    ));
+    world.add(lamp.clone());
+    (world, lamp)
+}
+
+fn main() {
+    let panels: Vec<_> = TEMPERATURES
+        .iter()
+        .map(|&kelvin| {
+            let (world, lamp) = room(kelvin);
+            Camera::builder()
+                .aspect_ratio(1.0)
+                .image_width(200)
+                .samples_per_pixel(128)
+                .max_depth(8)
+                .vfov(40.0)
+                .lookfrom(Point3::new(2.0, 2.0, -5.5))
+                .lookat(Point3::new(2.0, 2.0, 0.0))
+                .background_color(Color::zeros())
+                .seed(3)
+                .build()
+                .render_to_buffer(&BvhNode::new(&world), Some(lamp))
+        })
+        .collect();
+
+    let filename = "blackbody_lights.png";
+    let image = side_by_side(&panels.iter().collect::<Vec<_>>());
+    match save_framebuffer(&image, filename, OutputFormat::Png8) {
+        Ok(()) => println!("已保存 {}（{:?} K）", filename, TEMPERATURES),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
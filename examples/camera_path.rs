@@ -0,0 +1,55 @@
+//! 相机路径：用几个关键帧定义飞行路线，逐帧沿样条插值机位
+//!
+//! 运行：`cargo run --release --example camera_path [帧数]`
+//! 同样的路径也可以写在动画描述文件中交给 `render-anim` 渲染（见 `scenes::animation`）。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::scenes::cornell_box::{CornellBoxParams, CornellContents, build_cornell_box};
+use std::sync::Arc;
+
+fn main() {
+    let frames: u32 = std::env::args()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(24);
+
+    let params = CornellBoxParams::with_contents(&[CornellContents::Boxes]);
+    let (world, lights) = build_cornell_box(&params);
+    let world = BvhNode::new(&world);
+    let lights: Arc<dyn Hittable> = Arc::new(lights);
+
+    // 从盒子外推进到盒内，再转向高盒；起止处缓入缓出
+    let center = Point3::new(278.0, 278.0, 278.0);
+    let path = CameraPath::uniform(
+        SplineKind::CatmullRom,
+        &[
+            (Point3::new(278.0, 278.0, -900.0), center),
+            (Point3::new(278.0, 300.0, -300.0), center),
+            (
+                Point3::new(150.0, 320.0, 50.0),
+                Point3::new(300.0, 250.0, 400.0),
+            ),
+            (
+                Point3::new(100.0, 200.0, 150.0),
+                Point3::new(370.0, 200.0, 350.0),
+            ),
+        ],
+    )
+    .with_easing(Easing::EaseInOut);
+
+    let mut camera = Camera::builder()
+        .image_width(200)
+        .samples_per_pixel(32)
+        .max_depth(10)
+        .background_color(Color::zeros())
+        .vfov(40.0)
+        .build();
+
+    std::fs::create_dir_all("camera_path").expect("无法创建输出目录");
+    for frame in 0..frames {
+        path.apply(&mut camera, frame as f64 / (frames - 1).max(1) as f64);
+        camera.set_seed(Some(frame as u64));
+        camera.set_output_filename(format!("camera_path/frame_{:03}.png", frame));
+        camera.render(&world, Some(lights.clone()));
+    }
+}
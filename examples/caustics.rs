@@ -0,0 +1,98 @@
+//! 焦散光子图与路径追踪结合：康奈尔盒玻璃球下方的焦散
+//!
+//! 运行：`cargo run --release --example caustics [采样数] [参考采样数] [每轮光子数]`
+//!
+//! 输出 caustics_compare.png（纯路径追踪 | 加焦散光子图 | 高采样数参考图），以及玻璃球下方焦散区域
+//! 相对参考图的 PSNR/SSIM（整幅图的误差主要来自与焦散无关的漫反射噪声）。
+
+use image::Rgb32FImage;
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::rendering::caustics::{CausticMap, CausticSettings};
+use ray_tracing_rust::ray_tracing::rendering::framebuffer::FrameBuffer;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::utils::image_compare::{compare, display_image, side_by_side};
+use ray_tracing_rust::scenes::ao_compare::{CompareScene, build_compare_scene};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// 玻璃球下方焦散所在的区域
+fn caustic_region(fb: &FrameBuffer) -> Rgb32FImage {
+    let image = display_image(fb);
+    let (w, h) = image.dimensions();
+    image::imageops::crop_imm(
+        &image,
+        w * 45 / 100,
+        h * 75 / 100,
+        w * 35 / 100,
+        h * 22 / 100,
+    )
+    .to_image()
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let spp: i32 = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(64);
+    let reference_spp: i32 = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(512);
+    let photons: usize = args.get(3).and_then(|s| s.parse().ok()).unwrap_or(200_000);
+
+    let (world, lights, builder) = build_compare_scene(CompareScene::Cornell, 300);
+    let world = BvhNode::new(&world);
+    let lights: Arc<dyn Hittable> = Arc::new(lights);
+    let builder = builder.denoise(None).caustics(None).seed(11);
+    let settings = CausticSettings::with_photons(photons);
+
+    let start = Instant::now();
+    let path_traced = builder
+        .clone()
+        .samples_per_pixel(spp)
+        .build()
+        .render_to_buffer(&world, Some(lights.clone()));
+    let path_time = start.elapsed();
+
+    let start = Instant::now();
+    let map = CausticMap::build(&world, lights.as_ref(), &settings, 50, Some(11));
+    let photon_time = start.elapsed();
+
+    let start = Instant::now();
+    let hybrid = builder
+        .clone()
+        .samples_per_pixel(spp)
+        .caustics(Some(settings))
+        .build()
+        .render_to_buffer(&world, Some(lights.clone()));
+    let hybrid_time = start.elapsed();
+
+    eprintln!("渲染参考图（{} spp）...", reference_spp);
+    let reference = builder
+        .samples_per_pixel(reference_spp)
+        .build()
+        .render_to_buffer(&world, Some(lights));
+    let reference_region = caustic_region(&reference);
+
+    println!(
+        "{} 轮共保存 {} 个焦散光子（{:.2} s）",
+        settings.passes,
+        map.photon_count(),
+        photon_time.as_secs_f64()
+    );
+    let metrics = compare(&caustic_region(&path_traced), &reference_region).expect("尺寸一致");
+    println!(
+        "路径追踪 {} spp:   {}（{:.2} s）",
+        spp,
+        metrics,
+        path_time.as_secs_f64()
+    );
+    let metrics = compare(&caustic_region(&hybrid), &reference_region).expect("尺寸一致");
+    println!(
+        "加焦散光子图:      {}（{:.2} s，含光子发射）",
+        metrics,
+        hybrid_time.as_secs_f64()
+    );
+
+    let filename = "caustics_compare.png";
+    let image = side_by_side(&[&path_traced, &hybrid, &reference]);
+    match save_framebuffer(&image, filename, OutputFormat::Png8) {
+        Ok(()) => eprintln!("已保存 {}（路径追踪 | 加焦散光子图 | 参考图）", filename),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
@@ -0,0 +1,101 @@
+//! 单形噪声与 Worley（细胞）噪声纹理样板
+//!
+//! 运行：`cargo run --release --example cellular_noise`
+//!
+//! 输出 cellular_noise.png，直接在 XY 平面上采样纹理，从左到右为：单形噪声云、F1 细胞、
+//! F2 − F1 龟裂泥地、反相高次幂的焦散网纹、曼哈顿距离和切比雪夫距离的 F1。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::materials::texture::Texture;
+use ray_tracing_rust::ray_tracing::materials::texture::simplex::SimplexTexture;
+use ray_tracing_rust::ray_tracing::materials::texture::worley::WorleyTexture;
+use ray_tracing_rust::ray_tracing::procedural::worley::{DistanceMetric, Worley, WorleyFeature};
+use ray_tracing_rust::ray_tracing::rendering::framebuffer::FrameBuffer;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::utils::image_compare::side_by_side;
+
+/// 样板边长（像素）和覆盖的世界范围
+const SIZE: u32 = 160;
+const EXTENT: f64 = 4.0;
+
+fn swatch(texture: &dyn Texture) -> FrameBuffer {
+    let mut fb = FrameBuffer::new(SIZE, SIZE);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let p = Point3::new(
+                x as f64 / SIZE as f64 * EXTENT,
+                y as f64 / SIZE as f64 * EXTENT,
+                0.37,
+            );
+            fb.set(x, y, texture.value(0.0, 0.0, &p));
+        }
+    }
+    fb
+}
+
+fn main() {
+    let seed = 7;
+    let cells = Worley::new(seed);
+    let textures: Vec<Box<dyn Texture>> = vec![
+        Box::new(SimplexTexture::new(
+            seed,
+            0.8,
+            Color::new(0.25, 0.4, 0.75),
+            Color::new(0.95, 0.95, 1.0),
+        )),
+        Box::new(WorleyTexture::new(
+            cells,
+            WorleyFeature::F1,
+            1.5,
+            Color::new(0.9, 0.85, 0.7),
+            Color::new(0.2, 0.15, 0.1),
+        )),
+        Box::new(
+            WorleyTexture::new(
+                cells,
+                WorleyFeature::F2MinusF1,
+                1.5,
+                Color::new(0.12, 0.08, 0.05),
+                Color::new(0.7, 0.55, 0.35),
+            )
+            .with_gain(12.0),
+        ),
+        Box::new(
+            WorleyTexture::new(
+                cells.with_jitter(0.8),
+                WorleyFeature::F2MinusF1,
+                1.2,
+                Color::new(0.05, 0.25, 0.4),
+                Color::new(0.85, 1.0, 1.0),
+            )
+            .with_gain(2.5)
+            .inverted()
+            .with_exponent(6.0),
+        ),
+        Box::new(
+            WorleyTexture::new(
+                cells.with_metric(DistanceMetric::Manhattan),
+                WorleyFeature::F1,
+                1.5,
+                Color::new(0.95, 0.6, 0.2),
+                Color::new(0.25, 0.05, 0.1),
+            )
+            .with_gain(0.8),
+        ),
+        Box::new(WorleyTexture::new(
+            cells.with_metric(DistanceMetric::Chebyshev),
+            WorleyFeature::F1,
+            1.5,
+            Color::new(0.6, 0.9, 0.5),
+            Color::new(0.05, 0.2, 0.1),
+        )),
+    ];
+
+    let panels: Vec<FrameBuffer> = textures.iter().map(|t| swatch(t.as_ref())).collect();
+    let filename = "cellular_noise.png";
+    let image = side_by_side(&panels.iter().collect::<Vec<_>>());
+    match save_framebuffer(&image, filename, OutputFormat::Png8) {
+        Ok(()) => println!("已保存 {}", filename),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
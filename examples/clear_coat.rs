@@ -0,0 +1,88 @@
+//! 清漆层：同一底层材质有无清漆层的对比，以及车漆和上漆木材
+//!
+//! 运行：`cargo run --release --example clear_coat [每像素样本数]`
+//!
+//! 从左到右依次为：无清漆的红色漆面、加清漆的红色漆面、金属闪漆（粗糙金属上覆盖光滑清漆）、
+//! 上漆的木材（略粗糙的清漆）。输出 clear_coat.png。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::materials::clear_coat::ClearCoat;
+use std::sync::Arc;
+
+/// 木纹纹理：绕 y 轴的同心年轮，带少量扰动
+#[derive(Debug)]
+struct WoodGrain {
+    center: Point3,
+}
+
+impl Texture for WoodGrain {
+    fn value(&self, _u: f64, _v: f64, p: &Point3) -> Color {
+        let d = p - self.center;
+        let r = (d.x * d.x + d.z * d.z).sqrt() + 0.05 * (d.y * 9.0).sin();
+        let ring = 0.5 + 0.5 * (r * 40.0).sin();
+        let light = Color::new(0.62, 0.40, 0.20);
+        let dark = Color::new(0.32, 0.17, 0.07);
+        dark + ring.powf(3.0) * (light - dark)
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let spp: i32 = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(128);
+
+    let mut world = HittableList::new();
+    let mut lights = HittableList::new();
+
+    world.add(Arc::new(Quad::new(
+        Point3::new(-8.0, 0.0, -8.0),
+        Vec3::new(16.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 16.0),
+        Arc::new(Lambertian::new(Color::new(0.4, 0.4, 0.4))),
+    )));
+
+    let red: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.6, 0.05, 0.05)));
+    let flake: Arc<dyn Material> = Arc::new(Metal::new(Color::new(0.2, 0.35, 0.7), 0.6));
+    let center = Point3::new(3.3, 0.8, 0.0);
+    let wood: Arc<dyn Material> = Arc::new(Lambertian::new_texture(Arc::new(WoodGrain { center })));
+    let materials: [Arc<dyn Material>; 4] = [
+        red.clone(),
+        Arc::new(ClearCoat::new(red, 1.5, 0.0)),
+        Arc::new(ClearCoat::new(flake, 1.5, 0.02)),
+        Arc::new(ClearCoat::new(wood, 1.5, 0.15)),
+    ];
+    for (i, mat) in materials.into_iter().enumerate() {
+        world.add(Arc::new(Sphere::new(
+            Point3::new(2.2 * i as f64 - 3.3, 0.8, 0.0),
+            0.8,
+            mat,
+        )));
+    }
+
+    let (corner, u, v) = (
+        Point3::new(-3.0, 6.0, -2.0),
+        Vec3::new(6.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.5),
+    );
+    world.add(Arc::new(Quad::new(
+        corner,
+        u,
+        v,
+        Arc::new(DiffuseLight::new_color(Color::new(6.0, 6.0, 6.0))),
+    )));
+    lights.add(Arc::new(Quad::new(corner, u, v, Arc::new(NoMaterial))));
+
+    let mut camera = Camera::builder()
+        .aspect_ratio(2.0)
+        .image_width(640)
+        .samples_per_pixel(spp)
+        .max_depth(12)
+        .background_color(Color::new(0.15, 0.17, 0.2))
+        .vfov(32.0)
+        .lookfrom(Point3::new(0.0, 2.2, 9.0))
+        .lookat(Point3::new(0.0, 0.7, 0.0))
+        .output_filename("clear_coat.png")
+        .build();
+
+    let world = BvhNode::new(&world);
+    camera.render(&world, Some(Arc::new(lights)));
+}
@@ -0,0 +1,168 @@
+//! 在下游 crate 中定义自定义材质、纹理和PDF
+//!
+//! 运行：`cargo run --release --example custom_material`
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::utils::config;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// 条纹纹理：沿 u 方向交替的两种颜色
+#[derive(Debug)]
+struct Stripes {
+    count: f64,
+    a: Color,
+    b: Color,
+}
+
+impl Texture for Stripes {
+    fn value(&self, u: f64, _v: f64, _p: &Point3) -> Color {
+        if (u * self.count).floor() as i64 % 2 == 0 {
+            self.a
+        } else {
+            self.b
+        }
+    }
+}
+
+/// 以镜面反射方向为中心的 Phong 波瓣：pdf ∝ cos^n α
+#[derive(Debug)]
+struct PhongLobePDF {
+    frame: ONB,
+    exponent: f64,
+}
+
+impl PhongLobePDF {
+    fn new(reflected: &Vec3, exponent: f64) -> Self {
+        Self {
+            frame: ONB::new(reflected),
+            exponent,
+        }
+    }
+}
+
+impl PDF for PhongLobePDF {
+    fn value(&self, direction: &Vec3) -> f64 {
+        let cos_alpha = direction.normalize().dot(&self.frame.w());
+        if cos_alpha <= 0.0 {
+            return 0.0;
+        }
+        (self.exponent + 1.0) / (2.0 * PI) * cos_alpha.powf(self.exponent)
+    }
+
+    fn generate(&self) -> Vec3 {
+        let (r1, r2) = (random_double(), random_double());
+        let cos_alpha = r1.powf(1.0 / (self.exponent + 1.0));
+        let sin_alpha = (1.0 - cos_alpha * cos_alpha).sqrt();
+        let phi = 2.0 * PI * r2;
+        self.frame.local_to_world(&Vec3::new(
+            sin_alpha * phi.cos(),
+            sin_alpha * phi.sin(),
+            cos_alpha,
+        ))
+    }
+}
+
+/// 归一化的 Phong 光泽材质：f = albedo·(n+2)/(2π)·cos^n α
+#[derive(Debug)]
+struct Glossy {
+    albedo: TexturePtr,
+    exponent: f64,
+}
+
+impl Material for Glossy {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        let reflected = r_in.dir.normalize().reflect(&rec.normal);
+        let attenuation =
+            self.albedo
+                .value_filtered(rec.u, rec.v, &rec.p, &rec.normal, rec.footprint.as_ref());
+        srec.set_diffuse(
+            attenuation,
+            Arc::new(PhongLobePDF::new(&reflected, self.exponent)),
+        );
+        true
+    }
+
+    fn scattering_pdf(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        let direction = scattered.dir.normalize();
+        let cos_theta = rec.normal.dot(&direction);
+        let reflected = r_in.dir.normalize().reflect(&rec.normal);
+        let cos_alpha = reflected.dot(&direction);
+        if cos_theta <= 0.0 || cos_alpha <= 0.0 {
+            return 0.0;
+        }
+        (self.exponent + 2.0) / (2.0 * PI) * cos_alpha.powf(self.exponent) * cos_theta
+    }
+}
+
+fn main() {
+    let mut world = HittableList::new();
+    let mut lights = HittableList::new();
+
+    // 简单的程序化纹理可以直接用闭包定义：地面上间隔一个单位的网格线
+    let grid = ClosureTexture::new(|_u, _v, p| {
+        let line = |x: f64| (x - x.round()).abs() < 0.02;
+        if line(p.x) || line(p.z) {
+            Color::new(0.2, 0.2, 0.2)
+        } else {
+            Color::new(0.5, 0.5, 0.5)
+        }
+    });
+    let floor = Arc::new(Lambertian::new_texture(Arc::new(grid)));
+    world.add(Arc::new(Quad::new(
+        Point3::new(-10.0, 0.0, -10.0),
+        Vec3::new(20.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 20.0),
+        floor,
+    )));
+
+    let stripes = Arc::new(Stripes {
+        count: 12.0,
+        a: Color::new(0.8, 0.2, 0.1),
+        b: Color::new(0.9, 0.8, 0.6),
+    });
+    world.add(Arc::new(Sphere::new(
+        Point3::new(-1.1, 1.0, 0.0),
+        1.0,
+        Arc::new(Glossy {
+            albedo: stripes,
+            exponent: 40.0,
+        }),
+    )));
+    world.add(Arc::new(Sphere::new(
+        Point3::new(1.1, 1.0, 0.0),
+        1.0,
+        Arc::new(Glossy {
+            albedo: Arc::new(SolidColor::new(Color::new(0.2, 0.4, 0.8))),
+            exponent: 400.0,
+        }),
+    )));
+
+    let (corner, u, v) = (
+        Point3::new(-1.5, 5.0, -1.5),
+        Vec3::new(3.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 3.0),
+    );
+    world.add(Arc::new(Quad::new(
+        corner,
+        u,
+        v,
+        Arc::new(DiffuseLight::new_color(Color::new(8.0, 8.0, 8.0))),
+    )));
+    lights.add(Arc::new(Quad::new(corner, u, v, Arc::new(NoMaterial))));
+
+    let mut camera = Camera::builder()
+        .aspect_ratio(16.0 / 9.0)
+        .image_width(400)
+        .samples_per_pixel(64)
+        .max_depth(20)
+        .background_color(Color::new(0.05, 0.05, 0.08))
+        .vfov(35.0)
+        .lookfrom(Point3::new(0.0, 2.5, 8.0))
+        .lookat(Point3::new(0.0, 1.0, 0.0))
+        .output_filename(config::global().output_path("custom_material.png"))
+        .build();
+
+    let world = BvhNode::new(&world);
+    camera.render(&world, Some(Arc::new(lights)));
+}
@@ -0,0 +1,73 @@
+//! 辅助缓冲引导降噪的效果：低采样数渲染 → 降噪，与高采样数参考图比较
+//!
+//! 运行：`cargo run --release --example denoise [采样数] [参考采样数]`
+//!
+//! 输出 denoise_compare.png（噪声图 | 降噪后 | 参考图）和各强度下相对参考图的 PSNR/SSIM。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::rendering::denoise::{DenoiseSettings, denoise};
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::utils::image_compare::{compare, display_image, side_by_side};
+use ray_tracing_rust::scenes::ao_compare::{CompareScene, build_compare_scene};
+use std::sync::Arc;
+use std::time::Instant;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let spp: i32 = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(16);
+    let reference_spp: i32 = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(256);
+
+    let (world, lights, builder) = build_compare_scene(CompareScene::Cornell, 300);
+    let world = BvhNode::new(&world);
+    let lights: Arc<dyn Hittable> = Arc::new(lights);
+    let builder = builder.denoise(None).seed(7);
+
+    let start = Instant::now();
+    let camera = builder.clone().samples_per_pixel(spp).build();
+    let noisy = camera.render_to_buffer(&world, Some(lights.clone()));
+    let render_time = start.elapsed();
+
+    let start = Instant::now();
+    let aov = camera.render_aov(&world);
+    let aov_time = start.elapsed();
+
+    eprintln!("渲染参考图（{} spp）...", reference_spp);
+    let reference = builder
+        .samples_per_pixel(reference_spp)
+        .build()
+        .render_to_buffer(&world, Some(lights));
+    let reference_image = display_image(&reference);
+
+    println!(
+        "{} spp 渲染 {:.2} s，辅助缓冲 {:.2} s",
+        spp,
+        render_time.as_secs_f64(),
+        aov_time.as_secs_f64()
+    );
+    let metrics = compare(&display_image(&noisy), &reference_image).expect("尺寸一致");
+    println!("未降噪:     {}", metrics);
+
+    let mut shown = noisy.clone();
+    for strength in [0.5, 1.0, 2.0] {
+        let start = Instant::now();
+        let denoised = denoise(&noisy, &aov, &DenoiseSettings::with_strength(strength));
+        let elapsed = start.elapsed();
+        let metrics = compare(&display_image(&denoised), &reference_image).expect("尺寸一致");
+        println!(
+            "强度 {:<4}: {}（{:.0} ms）",
+            strength,
+            metrics,
+            elapsed.as_secs_f64() * 1e3
+        );
+        if strength == 1.0 {
+            shown = denoised;
+        }
+    }
+
+    let filename = "denoise_compare.png";
+    let image = side_by_side(&[&noisy, &shown, &reference]);
+    match save_framebuffer(&image, filename, OutputFormat::Png8) {
+        Ok(()) => eprintln!("已保存 {}（噪声图 | 降噪后 | 参考图）", filename),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
@@ -0,0 +1,72 @@
+//! 纹理分派方式的微基准：trait对象 vs 枚举分派
+//!
+//! 运行：`cargo run --release --example dispatch_bench`
+//!
+//! 对同一组命中记录重复调用朗伯材质的 `scatter`，比较纯色纹理经 `Arc<dyn Texture>` 取值与内联存放（`TextureKind`）。
+
+use ray_tracing_rust::prelude::*;
+use std::hint::black_box;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const RECORDS: usize = 4096;
+const ROUNDS: usize = 250;
+
+/// 以各材质对所有命中记录散射 ROUNDS 轮，返回耗时
+fn time_scatter<M: Material + ?Sized>(materials: &[&M], records: &[(Ray, HitRecord)]) -> Duration {
+    let start = Instant::now();
+    let mut srec = ScatterRecord::new();
+    let mut sum = Color::zeros();
+    for _ in 0..ROUNDS {
+        for (i, (r, rec)) in records.iter().enumerate() {
+            let mat = materials[i % materials.len()];
+            if mat.scatter(r, rec, &mut srec) {
+                sum += srec.attenuation;
+            }
+        }
+    }
+    black_box(sum);
+    start.elapsed()
+}
+
+fn report(name: &str, elapsed: Duration) {
+    let calls = (RECORDS * ROUNDS) as f64;
+    println!(
+        "{:<32} {:>8.1} ms  {:>6.2} ns/次",
+        name,
+        elapsed.as_secs_f64() * 1e3,
+        elapsed.as_secs_f64() * 1e9 / calls
+    );
+}
+
+fn main() {
+    let records: Vec<(Ray, HitRecord)> = (0..RECORDS)
+        .map(|_| {
+            let p = Point3::new(random_double(), random_double(), random_double());
+            let normal = Vec3::random_unit_vector();
+            let rec = HitRecord::new(
+                p,
+                normal,
+                Arc::new(NoMaterial),
+                1.0,
+                random_double(),
+                random_double(),
+                true,
+            );
+            (Ray::new(p + normal, -normal, 0.0), rec)
+        })
+        .collect();
+
+    // 纹理：同一种朗伯材质，反照率经trait对象或内联存放
+    let albedo = Color::new(0.7, 0.5, 0.3);
+    let shared_texture = Lambertian::new_texture(Arc::new(SolidColor::new(albedo)));
+    let inline_texture = Lambertian::new(albedo);
+    report(
+        "Lambertian + Arc<dyn Texture>",
+        time_scatter(&[&shared_texture], &records),
+    );
+    report(
+        "Lambertian + TextureKind",
+        time_scatter(&[&inline_texture], &records),
+    );
+}
@@ -0,0 +1,63 @@
+//! 位移球体：用地图的亮度作高度图，让地球的轮廓随地形起伏
+//!
+//! 运行：`cargo run --release --example displaced_planet [每像素样本数] [位移比例]`
+//!
+//! 输出 displaced_planet.png：左为普通球体，右为位移球体（默认最大位移为半径的 8%，
+//! 为了看清轮廓而远超真实比例）。光源从侧后方掠射，明暗交界线和边缘轮廓上能看到地形。
+//! 地图从纹理搜索路径中查找 earthmap.jpg。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::geometry::displaced_sphere::DisplacedSphere;
+use ray_tracing_rust::ray_tracing::materials::texture::image::ImageTexture;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::utils::image_compare::side_by_side;
+use std::sync::Arc;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let spp: i32 = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(64);
+    let displacement: f64 = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(0.08);
+
+    let map: TexturePtr = Arc::new(ImageTexture::new("earthmap.jpg"));
+    let surface: Arc<dyn Material> = Arc::new(Lambertian::new_texture(map.clone()));
+    let sun: Arc<dyn Hittable> = Arc::new(Sphere::new(
+        Point3::new(-30.0, 10.0, 8.0),
+        6.0,
+        Arc::new(DiffuseLight::new_color(Color::new(30.0, 29.0, 27.0))),
+    ));
+
+    let planets: [Arc<dyn Hittable>; 2] = [
+        Arc::new(Sphere::new(Point3::origin(), 1.0, surface.clone())),
+        Arc::new(
+            DisplacedSphere::new(Point3::origin(), 1.0, map, displacement, surface).with_steps(192),
+        ),
+    ];
+
+    let camera = Camera::builder()
+        .image_width(320)
+        .aspect_ratio(1.0)
+        .samples_per_pixel(spp)
+        .max_depth(4)
+        .background_color(Color::new(0.005, 0.005, 0.01))
+        .vfov(30.0)
+        .lookfrom(Point3::new(2.0, 1.0, 4.0))
+        .lookat(Point3::origin())
+        .seed(7)
+        .build();
+
+    let images: Vec<_> = planets
+        .into_iter()
+        .map(|planet| {
+            let mut world = HittableList::new();
+            world.add(planet);
+            world.add(sun.clone());
+            camera.render_to_buffer(&world, Some(sun.clone()))
+        })
+        .collect();
+
+    let combined = side_by_side(&[&images[0], &images[1]]);
+    match save_framebuffer(&combined, "displaced_planet.png", OutputFormat::Png8) {
+        Ok(()) => println!("已保存 displaced_planet.png"),
+        Err(e) => eprintln!("保存失败: {}", e),
+    }
+}
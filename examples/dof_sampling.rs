@@ -0,0 +1,144 @@
+//! 景深感知采样：失焦严重的散景区域分到更多样本
+//!
+//! 运行：`cargo run --release --example dof_sampling`
+//!
+//! 输出 dof_sampling.png，从左到右为：弥散圆直径（越亮越模糊）、每像素样本数相同、
+//! 按弥散圆分配样本（总样本数大致相同）、高样本数参考。终端打印两种方式相对参考的 PSNR，
+//! 散景区域和其余区域分开统计：额外样本集中在散景区域，其余区域的噪声相应变多。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::materials::texture::checker::CheckerTexture;
+use ray_tracing_rust::ray_tracing::rendering::dof_sampling::DofSampling;
+use ray_tracing_rust::ray_tracing::rendering::framebuffer::FrameBuffer;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::utils::image_compare::{display_image, mse, psnr, side_by_side};
+use std::sync::Arc;
+
+const WIDTH: i32 = 240;
+
+fn scene() -> BvhNode {
+    let mut world = HittableList::new();
+    let checker = |scale| {
+        Arc::new(Lambertian::new_texture(Arc::new(
+            CheckerTexture::new_colors(
+                scale,
+                Color::new(0.15, 0.15, 0.15),
+                Color::new(0.8, 0.8, 0.8),
+            ),
+        )))
+    };
+    world.add(Arc::new(Quad::new(
+        Point3::new(-20.0, 0.0, -3.0),
+        Vec3::new(40.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 20.0),
+        checker(0.5),
+    )));
+    world.add(Arc::new(Quad::new(
+        Point3::new(-20.0, 0.0, -3.0),
+        Vec3::new(40.0, 0.0, 0.0),
+        Vec3::new(0.0, 10.0, 0.0),
+        checker(0.3),
+    )));
+    // 对焦处的绿球和前景的红球
+    world.add(Arc::new(Sphere::new(
+        Point3::new(0.0, 0.8, 0.0),
+        0.8,
+        Arc::new(Lambertian::new(Color::new(0.2, 0.8, 0.3))),
+    )));
+    world.add(Arc::new(Sphere::new(
+        Point3::new(-1.0, 0.4, 5.5),
+        0.4,
+        Arc::new(Lambertian::new(Color::new(0.9, 0.2, 0.2))),
+    )));
+    // 前景的一串小灯在失焦处形成明显的散景
+    for k in 0..6 {
+        world.add(Arc::new(Sphere::new(
+            Point3::new(0.3 + 0.35 * k as f64, 0.3 + 0.12 * k as f64, 5.0),
+            0.04,
+            Arc::new(DiffuseLight::new_color(Color::new(40.0, 30.0, 15.0))),
+        )));
+    }
+    BvhNode::new(&world)
+}
+
+fn camera(samples: i32, dof_sampling: Option<DofSampling>) -> Camera {
+    Camera::builder()
+        .aspect_ratio(16.0 / 9.0)
+        .image_width(WIDTH)
+        .samples_per_pixel(samples)
+        .max_depth(8)
+        .vfov(35.0)
+        .lookfrom(Point3::new(0.0, 1.5, 8.0))
+        .lookat(Point3::new(0.0, 0.6, 0.0))
+        .focus_dist(8.0)
+        .defocus_angle(3.0)
+        .background_color(Color::new(0.25, 0.3, 0.4))
+        .dof_sampling(dof_sampling)
+        .seed(5)
+        .build()
+}
+
+fn main() {
+    let world = scene();
+    let weighted = DofSampling::with_weight(1.0);
+    let base = 16;
+
+    // 按弥散圆估计平均样本数倍数，让均匀采样使用大致相同的总样本数
+    let probe = camera(base, None);
+    let coc = probe.circle_of_confusion(&world);
+    let (width, height) = (WIDTH as usize, probe.image_height() as usize);
+    let multipliers = weighted.multipliers(&coc, width, height);
+    let average = multipliers.iter().sum::<u32>() as f64 / multipliers.len() as f64;
+    let uniform_samples = ((base as f64 * average).sqrt().round() as i32).pow(2);
+    println!(
+        "平均样本数倍数 {:.2}，均匀采样使用 {} spp，景深感知采样基础 {} spp",
+        average, uniform_samples, base
+    );
+
+    let uniform = camera(uniform_samples, None).render_to_buffer(&world, None);
+    let adaptive = camera(base, Some(weighted)).render_to_buffer(&world, None);
+    let reference = camera(1024, None).render_to_buffer(&world, None);
+
+    // 分别统计散景区域（弥散圆不小于 4 像素）和其余区域的误差
+    let target = display_image(&reference);
+    let bokeh: Vec<bool> = multipliers.iter().map(|&p| p > 1).collect();
+    for (name, fb) in [("均匀采样", &uniform), ("景深感知采样", &adaptive)] {
+        let image = display_image(fb);
+        let region = |inside: bool| {
+            let (mut sum, mut count) = (0.0, 0);
+            for (index, &flag) in bokeh.iter().enumerate() {
+                if flag != inside {
+                    continue;
+                }
+                let (x, y) = ((index % width) as u32, (index / width) as u32);
+                for c in 0..3 {
+                    let d = image.get_pixel(x, y)[c] as f64 - target.get_pixel(x, y)[c] as f64;
+                    sum += d * d;
+                }
+                count += 3;
+            }
+            psnr(sum / count.max(1) as f64)
+        };
+        println!(
+            "{}: 全图 PSNR {:.2} dB，散景区域 {:.2} dB，其余 {:.2} dB",
+            name,
+            psnr(mse(&image, &target)),
+            region(true),
+            region(false)
+        );
+    }
+
+    let max_coc = coc.iter().copied().fold(1e-9, f64::max);
+    let mut coc_image = FrameBuffer::new(width as u32, height as u32);
+    for (index, c) in coc.iter().enumerate() {
+        let (x, y) = (index % width, index / width);
+        coc_image.set(x as u32, y as u32, Color::repeat(c / max_coc));
+    }
+
+    let filename = "dof_sampling.png";
+    let image = side_by_side(&[&coc_image, &uniform, &adaptive, &reference]);
+    match save_framebuffer(&image, filename, OutputFormat::Png8) {
+        Ok(()) => println!("已保存 {}（弥散圆 | 均匀 | 景深感知 | 参考）", filename),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
@@ -0,0 +1,109 @@
+//! 环境光重要性采样：带明亮太阳的环境贴图和一盏面光源照明的室外场景
+//!
+//! 运行：`cargo run --release --example environment_light`
+//!
+//! 输出 environment_light.png，从左到右为：只采样面光源、环境光与面光源按功率统一采样（相同采样数）
+//! 和高采样参考图（512 spp），并打印两者相对参考图的 PSNR。只采样面光源时，太阳只能靠材质采样偶然命中，
+//! 受光面布满亮点，阴影也无法成形。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::materials::texture::ClosureTexture;
+use ray_tracing_rust::ray_tracing::rendering::background::{Background, EnvironmentMap};
+use ray_tracing_rust::ray_tracing::rendering::framebuffer::FrameBuffer;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::scene::world::Scene;
+use ray_tracing_rust::ray_tracing::utils::image_compare::{display_image, mse, psnr, side_by_side};
+use std::sync::Arc;
+
+/// 太阳方向
+const SUN: [f64; 3] = [0.5, 0.6, 0.35];
+/// 太阳的角半径（弧度）
+const SUN_RADIUS: f64 = 0.03;
+
+/// 蓝色渐变天空加上一个约占 0.003 sr 的太阳圆盘（纹理的位置参数即为方向）
+fn sky() -> Arc<dyn Background> {
+    let sun = Vec3::from(SUN).normalize();
+    let texture = ClosureTexture::new(move |_u, _v, p: &Point3| {
+        let d = p.coords.normalize();
+        if d.dot(&sun) > SUN_RADIUS.cos() {
+            return Color::new(1000.0, 900.0, 750.0);
+        }
+        let t = d.y.max(0.0);
+        Color::new(0.35, 0.45, 0.6) * (1.0 - t) + Color::new(0.1, 0.2, 0.45) * t
+    })
+    .with_name("sun_sky");
+    Arc::new(EnvironmentMap::new(Arc::new(texture), 1.0))
+}
+
+fn scene() -> Scene {
+    let mut scene = Scene::new();
+    scene.add(Arc::new(Quad::new(
+        Point3::new(-10.0, 0.0, -10.0),
+        Vec3::new(20.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 20.0),
+        Arc::new(Lambertian::new(Color::new(0.6, 0.6, 0.55))),
+    )));
+    scene.add(Arc::new(Sphere::new(
+        Point3::new(-1.2, 1.0, 0.0),
+        1.0,
+        Arc::new(Lambertian::new(Color::new(0.7, 0.2, 0.15))),
+    )));
+    scene.add(Arc::new(Sphere::new(
+        Point3::new(1.2, 1.0, -0.5),
+        1.0,
+        Arc::new(Metal::new(Color::new(0.9, 0.85, 0.8), 0.3)),
+    )));
+    // 球后方朝下的暖色面光源
+    scene.add_emitter(Arc::new(Quad::new(
+        Point3::new(-0.5, 3.0, -2.5),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.0),
+        Arc::new(DiffuseLight::new_color(Color::new(12.0, 9.0, 6.0))),
+    )));
+    scene
+}
+
+fn render(scene: &Scene, sky: &Arc<dyn Background>, samples: i32) -> FrameBuffer {
+    let camera = Camera::builder()
+        .aspect_ratio(4.0 / 3.0)
+        .image_width(200)
+        .samples_per_pixel(samples)
+        .max_depth(8)
+        .vfov(40.0)
+        .lookfrom(Point3::new(0.0, 2.5, 7.0))
+        .lookat(Point3::new(0.0, 0.8, 0.0))
+        .background(sky.clone())
+        .seed(5)
+        .build();
+    camera.render_to_buffer(scene.build_world().as_ref(), scene.light_sampler())
+}
+
+fn main() {
+    let sky = sky();
+
+    let area_only = scene();
+    let mut unified = scene();
+    unified.add_environment_light(sky.as_ref());
+    unified.set_light_weights_by_power();
+
+    let samples = 64;
+    let without = render(&area_only, &sky, samples);
+    let with = render(&unified, &sky, samples);
+    let reference = render(&unified, &sky, 512);
+
+    let target = display_image(&reference);
+    for (name, fb) in [("只采样面光源", &without), ("统一采样", &with)] {
+        println!(
+            "{}: PSNR {:.2} dB",
+            name,
+            psnr(mse(&display_image(fb), &target))
+        );
+    }
+
+    let filename = "environment_light.png";
+    let image = side_by_side(&[&without, &with, &reference]);
+    match save_framebuffer(&image, filename, OutputFormat::Png8) {
+        Ok(()) => println!("已保存 {}（只采样面光源 | 统一采样 | 参考）", filename),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
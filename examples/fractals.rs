@@ -0,0 +1,91 @@
+//! 分形距离场：Mandelbulb、Menger 海绵和谢尔宾斯基四面体，按轨道陷阱着色
+//!
+//! 运行：`cargo run --release --example fractals [每像素采样数]`
+//!
+//! 输出 fractals.png。分形用球体追踪求交，渲染比普通几何体慢得多，默认只用较少的采样。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::geometry::sdf::{DistanceField, SdfObject};
+use ray_tracing_rust::ray_tracing::geometry::transforms::scale::Scale;
+use ray_tracing_rust::ray_tracing::geometry::transforms::translate::Translate;
+use ray_tracing_rust::ray_tracing::materials::texture::checker::CheckerTexture;
+use ray_tracing_rust::ray_tracing::procedural::fractals::{
+    CosinePalette, Mandelbulb, MengerSponge, SierpinskiTetrahedron,
+};
+use ray_tracing_rust::ray_tracing::rendering::background::VerticalGradient;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use std::sync::Arc;
+
+/// 把距离场物体缩放后放到 position（物体原点处）
+fn place(
+    field: Arc<dyn DistanceField>,
+    palette: CosinePalette,
+    scale: f64,
+    position: Point3,
+) -> Arc<dyn Hittable> {
+    let material = Arc::new(Lambertian::new_texture(Arc::new(palette)));
+    let object = Arc::new(SdfObject::new(field, material));
+    Arc::new(Translate::new(
+        Arc::new(Scale::new(object, scale)),
+        position.coords,
+    ))
+}
+
+fn main() {
+    let samples: i32 = std::env::args()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(16);
+
+    let mut world = HittableList::new();
+    world.add(Arc::new(Quad::new(
+        Point3::new(-20.0, 0.0, -20.0),
+        Vec3::new(0.0, 0.0, 40.0),
+        Vec3::new(40.0, 0.0, 0.0),
+        Arc::new(Lambertian::new_texture(Arc::new(
+            CheckerTexture::new_colors(
+                0.5,
+                Color::new(0.8, 0.8, 0.8),
+                Color::new(0.35, 0.35, 0.38),
+            ),
+        ))),
+    )));
+    world.add(place(
+        Arc::new(Mandelbulb::default()),
+        CosinePalette::fire(),
+        1.1,
+        Point3::new(-2.7, 1.2, 0.0),
+    ));
+    world.add(place(
+        Arc::new(MengerSponge::default()),
+        CosinePalette::rainbow(),
+        1.0,
+        Point3::new(0.0, 1.0, 0.0),
+    ));
+    world.add(place(
+        Arc::new(SierpinskiTetrahedron::default()),
+        CosinePalette::ice(),
+        1.0,
+        Point3::new(2.7, 1.0, 0.0),
+    ));
+    let world = BvhNode::new(&world);
+
+    let camera = Camera::builder()
+        .aspect_ratio(16.0 / 9.0)
+        .image_width(480)
+        .samples_per_pixel(samples)
+        .max_depth(5)
+        .vfov(38.0)
+        .lookfrom(Point3::new(1.5, 3.5, 8.0))
+        .lookat(Point3::new(0.0, 1.0, 0.0))
+        .background(Arc::new(VerticalGradient::sky()))
+        .seed(5)
+        .build();
+    let fb = camera.render_to_buffer(&world, None);
+
+    let filename = "fractals.png";
+    match save_framebuffer(&fb, filename, OutputFormat::Png8) {
+        Ok(()) => println!("已保存 {}", filename),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
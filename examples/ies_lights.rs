@@ -0,0 +1,109 @@
+//! IES 配光曲线和距离衰减控制：墙边的三盏灯分别为均匀点光源、窄光束筒灯和蝙蝠翼灯具
+//!
+//! 运行：`cargo run --release --example ies_lights`
+//!
+//! 输出 ies_lights.png，左图为物理的平方反比衰减，右图为一次反比衰减（参考距离 1.5）。
+//! 配光数据读取自 `examples/ies/` 下的 LM-63 文件。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::lights::falloff::Falloff;
+use ray_tracing_rust::ray_tracing::lights::ies::IesProfile;
+use ray_tracing_rust::ray_tracing::materials::point_light::PointLight;
+use ray_tracing_rust::ray_tracing::rendering::framebuffer::FrameBuffer;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::utils::image_compare::side_by_side;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// 灯泡半径
+const BULB_RADIUS: f64 = 0.02;
+
+fn load_profile(path: &str) -> Arc<IesProfile> {
+    let profile = IesProfile::load(path).unwrap_or_else(|e| {
+        eprintln!("无法读取配光文件: {}", e);
+        std::process::exit(1);
+    });
+    eprintln!(
+        "{}: 最大强度 {:.0} cd，光通量 {:.0} lm",
+        path,
+        profile.max_candela(),
+        profile.lumens()
+    );
+    Arc::new(profile)
+}
+
+fn render(profiles: &[Arc<IesProfile>; 2], falloff: Falloff) -> FrameBuffer {
+    let mut world = HittableList::new();
+    let mut lights = HittableList::new();
+
+    let white = Arc::new(Lambertian::new(Color::new(0.75, 0.75, 0.75)));
+    world.add(Arc::new(Quad::new(
+        Point3::new(-4.0, 0.0, -1.0),
+        Vec3::new(8.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 6.0),
+        white.clone(),
+    )));
+    world.add(Arc::new(Quad::new(
+        Point3::new(-4.0, 0.0, -1.0),
+        Vec3::new(8.0, 0.0, 0.0),
+        Vec3::new(0.0, 4.0, 0.0),
+        white,
+    )));
+
+    // 最亮方向上的发光强度 I = π r² L
+    let intensity = 3.0;
+    let radiance = Color::repeat(intensity / (PI * BULB_RADIUS * BULB_RADIUS));
+    let down = Vec3::new(0.0, -1.0, 0.0);
+    let fixtures = [
+        (-2.0, PointLight::new_color(radiance * 0.3)),
+        (
+            0.0,
+            PointLight::new_color(radiance).with_profile(profiles[0].clone(), down),
+        ),
+        (
+            2.0,
+            PointLight::new_color(radiance).with_profile(profiles[1].clone(), down),
+        ),
+    ];
+    for (x, light) in fixtures {
+        let bulb: Arc<dyn Hittable> = Arc::new(Sphere::new(
+            Point3::new(x, 2.8, -0.7),
+            BULB_RADIUS,
+            Arc::new(light.with_falloff(falloff)),
+        ));
+        world.add(bulb.clone());
+        lights.add(bulb);
+    }
+
+    let world = BvhNode::new(&world);
+    let camera = Camera::builder()
+        .aspect_ratio(4.0 / 3.0)
+        .image_width(320)
+        .samples_per_pixel(64)
+        .max_depth(10)
+        .background_color(Color::zeros())
+        .vfov(55.0)
+        .lookfrom(Point3::new(0.0, 1.6, 4.5))
+        .lookat(Point3::new(0.0, 1.3, -1.0))
+        .seed(2)
+        .build();
+    camera.render_to_buffer(&world, Some(Arc::new(lights)))
+}
+
+fn main() {
+    let profiles = [
+        load_profile("examples/ies/downlight.ies"),
+        load_profile("examples/ies/batwing.ies"),
+    ];
+    let panels = [
+        render(&profiles, Falloff::Quadratic),
+        render(&profiles, Falloff::Linear { reference: 1.5 }),
+    ];
+
+    let filename = "ies_lights.png";
+    let image = side_by_side(&panels.iter().collect::<Vec<_>>());
+    match save_framebuffer(&image, filename, OutputFormat::Png8) {
+        Ok(()) => eprintln!("已保存 {}（平方反比 | 一次反比）", filename),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
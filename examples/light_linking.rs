@@ -0,0 +1,77 @@
+//! 光源链接：左侧的暖色光只照亮左边的球，右侧的冷色光不照亮地面
+//!
+//! 运行：`cargo run --release --example light_linking`
+//!
+//! 输出 light_linking.png，左图不使用链接，右图按规则链接。两盏光在相机中都可见，
+//! 也都照常投射阴影，链接只决定它们的光照落在哪些物体上。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::rendering::framebuffer::FrameBuffer;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::scene::world::{LinkRule, Scene};
+use ray_tracing_rust::ray_tracing::utils::image_compare::side_by_side;
+use std::sync::Arc;
+
+fn render(linked: bool) -> FrameBuffer {
+    let mut scene = Scene::new();
+    let white = Arc::new(Lambertian::new(Color::new(0.73, 0.73, 0.73)));
+    let floor = scene.add(Arc::new(Quad::new(
+        Point3::new(-4.0, 0.0, -4.0),
+        Vec3::new(8.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 8.0),
+        white.clone(),
+    )));
+    let left = scene.add(Arc::new(Sphere::new(
+        Point3::new(-0.8, 0.6, 0.0),
+        0.6,
+        white.clone(),
+    )));
+    scene.add(Arc::new(Sphere::new(
+        Point3::new(0.8, 0.6, 0.0),
+        0.6,
+        white,
+    )));
+
+    let (_, warm) = scene.add_emitter(Arc::new(Quad::new(
+        Point3::new(-2.5, 2.5, -0.5),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.0),
+        Arc::new(DiffuseLight::new_color(Color::new(15.0, 9.0, 4.0))),
+    )));
+    let (_, cool) = scene.add_emitter(Arc::new(Quad::new(
+        Point3::new(1.5, 2.5, -0.5),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.0),
+        Arc::new(DiffuseLight::new_color(Color::new(4.0, 8.0, 15.0))),
+    )));
+
+    if linked {
+        scene.set_light_links(warm, LinkRule::Only(vec![left]));
+        scene.set_object_links(floor, LinkRule::Except(vec![cool]));
+    }
+
+    let camera = Camera::builder()
+        .image_width(300)
+        .samples_per_pixel(64)
+        .max_depth(10)
+        .background_color(Color::new(0.02, 0.02, 0.03))
+        .vfov(40.0)
+        .lookfrom(Point3::new(0.0, 2.0, 5.0))
+        .lookat(Point3::new(0.0, 0.7, 0.0))
+        .seed(1)
+        .build();
+    camera.render_to_buffer(scene.build_world().as_ref(), scene.light_sampler())
+}
+
+fn main() {
+    let images = [render(false), render(true)];
+    let filename = "light_linking.png";
+    match save_framebuffer(
+        &side_by_side(&[&images[0], &images[1]]),
+        filename,
+        OutputFormat::Png8,
+    ) {
+        Ok(()) => eprintln!("已保存 {}（不链接 | 链接）", filename),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
@@ -0,0 +1,134 @@
+//! 光门自动检测：只靠窗外天空照明的房间，分别不用和使用自动检测的光门渲染
+//!
+//! 运行：`cargo run --release --example light_portals`
+//!
+//! 输出 light_portals.png，从左到右为：无光门、自动光门（相同采样数）和高采样参考图（512 spp），
+//! 并打印两者相对参考图的 PSNR。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::geometry::quad::box_new;
+use ray_tracing_rust::ray_tracing::rendering::background::VerticalGradient;
+use ray_tracing_rust::ray_tracing::rendering::framebuffer::FrameBuffer;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::sampling::portal::PortalSettings;
+use ray_tracing_rust::ray_tracing::scene::world::Scene;
+use ray_tracing_rust::ray_tracing::utils::image_compare::{display_image, mse, psnr, side_by_side};
+use std::sync::Arc;
+
+/// 房间边长
+const SIZE: f64 = 10.0;
+/// 左墙上窗洞的 (z, y) 范围
+const WINDOW: ((f64, f64), (f64, f64)) = ((3.5, 7.5), (3.0, 7.0));
+
+fn room() -> Scene {
+    let mut scene = Scene::new();
+    let white: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.75, 0.75, 0.75)));
+    let red: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.65, 0.1, 0.08)));
+    let green: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.15, 0.45, 0.12)));
+
+    let wall = |q: Point3, u: Vec3, v: Vec3, mat: &Arc<dyn Material>| -> Arc<dyn Hittable> {
+        Arc::new(Quad::new(q, u, v, mat.clone()))
+    };
+    // 地板、天花板、前后墙和右墙
+    scene.add(wall(
+        Point3::origin(),
+        Vec3::new(SIZE, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, SIZE),
+        &white,
+    ));
+    scene.add(wall(
+        Point3::new(0.0, SIZE, 0.0),
+        Vec3::new(SIZE, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, SIZE),
+        &white,
+    ));
+    scene.add(wall(
+        Point3::new(0.0, 0.0, SIZE),
+        Vec3::new(SIZE, 0.0, 0.0),
+        Vec3::new(0.0, SIZE, 0.0),
+        &white,
+    ));
+    scene.add(wall(
+        Point3::origin(),
+        Vec3::new(SIZE, 0.0, 0.0),
+        Vec3::new(0.0, SIZE, 0.0),
+        &white,
+    ));
+    scene.add(wall(
+        Point3::new(SIZE, 0.0, 0.0),
+        Vec3::new(0.0, SIZE, 0.0),
+        Vec3::new(0.0, 0.0, SIZE),
+        &green,
+    ));
+
+    // 左墙由窗洞四周的四块组成
+    let ((z0, z1), (y0, y1)) = WINDOW;
+    for (z, y) in [
+        ((0.0, SIZE), (0.0, y0)),
+        ((0.0, SIZE), (y1, SIZE)),
+        ((0.0, z0), (y0, y1)),
+        ((z1, SIZE), (y0, y1)),
+    ] {
+        scene.add(wall(
+            Point3::new(0.0, y.0, z.0),
+            Vec3::new(0.0, y.1 - y.0, 0.0),
+            Vec3::new(0.0, 0.0, z.1 - z.0),
+            &red,
+        ));
+    }
+
+    scene.add(Arc::new(box_new(
+        Point3::new(5.5, 0.0, 5.5),
+        Point3::new(8.0, 3.5, 8.0),
+        white.clone(),
+    )));
+    scene.add(Arc::new(Sphere::new(
+        Point3::new(3.5, 1.5, 6.5),
+        1.5,
+        white,
+    )));
+    scene
+}
+
+fn render(scene: &Scene, samples: i32) -> FrameBuffer {
+    let camera = Camera::builder()
+        .aspect_ratio(4.0 / 3.0)
+        .image_width(240)
+        .samples_per_pixel(samples)
+        .max_depth(8)
+        .vfov(70.0)
+        .lookfrom(Point3::new(8.5, 5.0, 0.8))
+        .lookat(Point3::new(3.0, 4.0, 7.0))
+        .background(Arc::new(VerticalGradient::sky()))
+        .seed(11)
+        .build();
+    camera.render_to_buffer(scene.build_world().as_ref(), scene.light_sampler())
+}
+
+fn main() {
+    let plain = room();
+    let mut portals = room();
+    let ids = portals.add_portals(&PortalSettings::default());
+    println!("检测到 {} 个光门", ids.len());
+
+    let samples = 32;
+    let without = render(&plain, samples);
+    let with = render(&portals, samples);
+    let reference = render(&portals, 512);
+
+    let target = display_image(&reference);
+    for (name, fb) in [("无光门", &without), ("自动光门", &with)] {
+        println!(
+            "{}: PSNR {:.2} dB",
+            name,
+            psnr(mse(&display_image(fb), &target))
+        );
+    }
+
+    let filename = "light_portals.png";
+    let image = side_by_side(&[&without, &with, &reference]);
+    match save_framebuffer(&image, filename, OutputFormat::Png8) {
+        Ok(()) => println!("已保存 {}（无光门 | 自动光门 | 参考）", filename),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
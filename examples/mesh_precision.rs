@@ -0,0 +1,154 @@
+//! 网格精度：同一块地形分别以 TriangleMesh、f64 和 f32 的紧凑网格渲染，比较内存、速度和画面误差
+//!
+//! 运行：`cargo run --release --example mesh_precision [每边格数] [平移距离]`
+//!
+//! 地形（默认 300×300 格，18 万个三角形）先放在原点附近渲染，再整体连同相机平移到远处（默认 1e5）重新渲染。
+//! 以 TriangleMesh 的图像为参考打印各紧凑网格的 PSNR：原点附近 f32 与参考几乎一致，
+//! 远处的 f32 顶点误差超过自相交偏移，阴影中出现痤疮。逐条光线的标量求交在 f32 下并不更快
+//!（光线和交点要在两种精度之间转换），f32 的收益主要是内存。
+//! 输出 mesh_precision.png，从左到右为：参考、原点附近的 f32、远处的 f32。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::geometry::compact_mesh::CompactMesh;
+use ray_tracing_rust::ray_tracing::geometry::mesh::{MeshData, TriangleMesh};
+use ray_tracing_rust::ray_tracing::geometry::triangle::Triangle;
+use ray_tracing_rust::ray_tracing::rendering::framebuffer::FrameBuffer;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::utils::image_compare::{display_image, mse, psnr, side_by_side};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// 地形边长
+const SIZE: f64 = 60.0;
+
+fn height(x: f64, z: f64) -> f64 {
+    2.0 * (x * 0.11).sin() * (z * 0.07).cos()
+        + 0.8 * (x * 0.37 + z * 0.23).sin()
+        + 0.25 * (x * 1.3).cos() * (z * 1.1).sin()
+}
+
+fn terrain(cells: usize, offset: Vec3) -> MeshData {
+    let mut data = MeshData::default();
+    let step = SIZE / cells as f64;
+    for j in 0..=cells {
+        for i in 0..=cells {
+            let (x, z) = (i as f64 * step - SIZE / 2.0, j as f64 * step - SIZE / 2.0);
+            data.positions
+                .push(Point3::new(x, height(x, z), z) + offset);
+        }
+    }
+    let row = cells + 1;
+    for j in 0..cells {
+        for i in 0..cells {
+            let v = j * row + i;
+            data.indices.push([v, v + row, v + 1]);
+            data.indices.push([v + 1, v + row, v + row + 1]);
+        }
+    }
+    data
+}
+
+fn render(mesh: Arc<dyn Hittable>, offset: Vec3) -> FrameBuffer {
+    let mut world = HittableList::new();
+    world.add(mesh);
+    let light = Arc::new(Quad::new(
+        Point3::new(-4.0, 20.0, -4.0) + offset,
+        Vec3::new(8.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 8.0),
+        Arc::new(DiffuseLight::new_color(Color::new(6.0, 6.0, 5.0))),
+    ));
+    world.add(light.clone());
+
+    let camera = Camera::builder()
+        .aspect_ratio(16.0 / 9.0)
+        .image_width(320)
+        .samples_per_pixel(16)
+        .max_depth(4)
+        .vfov(40.0)
+        .lookfrom(Point3::new(-14.0, 9.0, -12.0) + offset)
+        .lookat(Point3::new(0.0, 0.0, 0.0) + offset)
+        .background_color(Color::new(0.3, 0.4, 0.55))
+        .seed(1)
+        .build();
+    camera.render_to_buffer(&world, Some(light))
+}
+
+/// 构建网格并渲染，打印构建和渲染用时
+fn run<H: Hittable + 'static>(
+    name: &str,
+    build: impl FnOnce() -> H,
+    memory: impl FnOnce(&H) -> usize,
+    offset: Vec3,
+) -> FrameBuffer {
+    let start = Instant::now();
+    let mesh = build();
+    let build_time = start.elapsed().as_secs_f64();
+    let bytes = memory(&mesh);
+    let start = Instant::now();
+    let fb = render(Arc::new(mesh), offset);
+    println!(
+        "  {:<18} 内存 {:>6.1} MB  构建 {:.2} s  渲染 {:.2} s",
+        name,
+        bytes as f64 / 1e6,
+        build_time,
+        start.elapsed().as_secs_f64()
+    );
+    fb
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let cells: usize = args.first().and_then(|s| s.parse().ok()).unwrap_or(300);
+    let distance: f64 = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(1e5);
+    let ground: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.55, 0.5, 0.4)));
+
+    let mut panels = Vec::new();
+    for offset in [Vec3::zeros(), Vec3::new(distance, 0.0, distance)] {
+        let data = terrain(cells, offset);
+        println!(
+            "地形平移 ({:.0}, {:.0})，{} 个三角形：",
+            offset.x,
+            offset.z,
+            data.triangle_count()
+        );
+        // TriangleMesh 的内存只计三角形对象及其指针，不含 BVH 节点
+        let reference = run(
+            "TriangleMesh",
+            || TriangleMesh::new(&data, ground.clone()),
+            |mesh| mesh.len() * (std::mem::size_of::<Triangle>() + 3 * 8),
+            offset,
+        );
+        let f64_image = run(
+            "CompactMesh<f64>",
+            || CompactMesh::<f64>::new(&data, ground.clone()).expect("顶点数超出 u32"),
+            CompactMesh::memory_bytes,
+            offset,
+        );
+        let f32_image = run(
+            "CompactMesh<f32>",
+            || CompactMesh::<f32>::new(&data, ground.clone()).expect("顶点数超出 u32"),
+            CompactMesh::memory_bytes,
+            offset,
+        );
+
+        let target = display_image(&reference);
+        for (name, fb) in [("f64", &f64_image), ("f32", &f32_image)] {
+            println!(
+                "  {} 相对参考 PSNR {:.2} dB",
+                name,
+                psnr(mse(&display_image(fb), &target))
+            );
+        }
+        if panels.is_empty() {
+            panels.push(reference);
+        }
+        panels.push(f32_image);
+    }
+
+    let filename = "mesh_precision.png";
+    let refs: Vec<&FrameBuffer> = panels.iter().collect();
+    match save_framebuffer(&side_by_side(&refs), filename, OutputFormat::Png8) {
+        Ok(()) => println!("已保存 {}（参考 | 原点附近 f32 | 远处 f32）", filename),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
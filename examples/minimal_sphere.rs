@@ -0,0 +1,35 @@
+//! 最小示例：天空下的一个球体
+//!
+//! 运行：`cargo run --release --example minimal_sphere`
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::rendering::background::VerticalGradient;
+use std::sync::Arc;
+
+fn main() {
+    let mut world = HittableList::new();
+    world.add(Arc::new(Sphere::new(
+        Point3::new(0.0, 0.0, -1.0),
+        0.5,
+        Arc::new(Lambertian::new(Color::new(0.7, 0.3, 0.3))),
+    )));
+    world.add(Arc::new(Sphere::new(
+        Point3::new(0.0, -100.5, -1.0),
+        100.0,
+        Arc::new(Lambertian::new(Color::new(0.8, 0.8, 0.0))),
+    )));
+
+    let mut camera = Camera::builder()
+        .aspect_ratio(16.0 / 9.0)
+        .image_width(400)
+        .samples_per_pixel(50)
+        .max_depth(20)
+        .background(Arc::new(VerticalGradient::sky()))
+        .lookfrom(Point3::new(0.0, 0.0, 0.0))
+        .lookat(Point3::new(0.0, 0.0, -1.0))
+        .output_filename("minimal_sphere.png")
+        .build();
+
+    // 没有光源列表时只按材质采样
+    camera.render(&world, None);
+}
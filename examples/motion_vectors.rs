@@ -0,0 +1,123 @@
+//! 运动矢量辅助缓冲与后期运动模糊：康奈尔盒中的高盒在快门期间转动，矮盒和球向右平移
+//!
+//! 运行：`cargo run --release --example motion_vectors [每像素样本数]`
+//!
+//! 先渲染快门中点时刻的清晰帧和运动场景的运动矢量，再沿运动矢量做后期运动模糊，
+//! 与渲染时采样快门时间得到的运动模糊比较。输出：
+//! - motion_vectors.png：清晰帧 | 后期运动模糊 | 渲染的运动模糊 | 运动矢量可视化
+//! - motion_vectors.pfm：以像素为单位的运动矢量（红为 x、绿为 y），供合成软件或时域降噪使用
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::geometry::scene_graph::{Node, Transform};
+use ray_tracing_rust::ray_tracing::rendering::motion_blur::{MotionBlurSettings, motion_blur};
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::utils::image_compare::{compare, display_image, side_by_side};
+use ray_tracing_rust::scenes::cornell_box::{
+    CornellBoxConfig, CornellBoxParams, build_cornell_box, cornell_box_camera,
+};
+use std::sync::Arc;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let spp: i32 = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(64);
+
+    let params = CornellBoxParams::with_contents(&[]);
+    let white: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.73, 0.73, 0.73)));
+    let tall = Arc::new(box_new(
+        Point3::new(-82.5, 0.0, -82.5),
+        Point3::new(82.5, 330.0, 82.5),
+        white.clone(),
+    ));
+    let short = Arc::new(box_new(
+        Point3::new(-82.5, 0.0, -82.5),
+        Point3::new(82.5, 165.0, 82.5),
+        white,
+    ));
+    let ball: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.2, 0.3, 0.8)));
+
+    let tall_open = Transform {
+        rotate_y: 15.0,
+        translate: Vec3::new(347.5, 0.0, 377.5),
+    };
+    let tall_close = Transform {
+        rotate_y: 45.0,
+        ..tall_open
+    };
+    let short_open = Transform {
+        rotate_y: -18.0,
+        translate: Vec3::new(172.5, 0.0, 147.5),
+    };
+    let short_close = Transform {
+        translate: short_open.translate + Vec3::new(50.0, 0.0, 0.0),
+        ..short_open
+    };
+    let (ball_open, ball_close) = (
+        Point3::new(130.0, 360.0, 250.0),
+        Point3::new(230.0, 360.0, 250.0),
+    );
+
+    // time 为 None 时物体在快门期间运动，否则固定在该时刻
+    let build = |time: Option<f64>| {
+        let mut root = Node::default();
+        let (tall_node, short_node, sphere) = match time {
+            None => (
+                Node::with_geometry(tall_open, tall.clone()).with_motion(tall_close),
+                Node::with_geometry(short_open, short.clone()).with_motion(short_close),
+                Sphere::new_moving(ball_open, ball_close, 50.0, ball.clone()),
+            ),
+            Some(t) => (
+                Node::with_geometry(tall_open.lerp(&tall_close, t), tall.clone()),
+                Node::with_geometry(short_open.lerp(&short_close, t), short.clone()),
+                Sphere::new(ball_open + (ball_close - ball_open) * t, 50.0, ball.clone()),
+            ),
+        };
+        root.add_child(tall_node).add_child(short_node);
+
+        let (mut world, lights) = build_cornell_box(&params);
+        for object in root.flatten().objects {
+            world.add(object);
+        }
+        world.add(Arc::new(sphere));
+        (BvhNode::new(&world), lights)
+    };
+
+    let config = CornellBoxConfig {
+        image_width: 300,
+        samples_per_pixel: spp,
+        max_depth: 20,
+        ..CornellBoxConfig::default()
+    };
+    let camera = cornell_box_camera(&config, &params).seed(5).build();
+
+    let (sharp_world, lights) = build(Some(0.5));
+    let sharp = camera.render_to_buffer(&sharp_world, Some(Arc::new(lights)));
+
+    let (moving_world, lights) = build(None);
+    let aov = camera.render_aov(&moving_world);
+    let reference = camera.render_to_buffer(&moving_world, Some(Arc::new(lights)));
+
+    let post = motion_blur(&sharp, &aov, &MotionBlurSettings::default());
+
+    let longest = aov.motion.iter().map(|m| m.norm()).fold(0.0, f64::max);
+    println!("最大运动矢量长度: {:.1} 像素", longest);
+    let reference_image = display_image(&reference);
+    for (name, image) in [("清晰帧", &sharp), ("后期运动模糊", &post)] {
+        let metrics = compare(&display_image(image), &reference_image).expect("尺寸一致");
+        println!("{} 与渲染的运动模糊相比: {}", name, metrics);
+    }
+
+    let combined = side_by_side(&[&sharp, &post, &reference, &aov.motion_buffer()]);
+    for (fb, filename, format) in [
+        (&combined, "motion_vectors.png", OutputFormat::Png8),
+        (
+            &aov.motion_vectors(),
+            "motion_vectors.pfm",
+            OutputFormat::Pfm,
+        ),
+    ] {
+        match save_framebuffer(fb, filename, format) {
+            Ok(()) => eprintln!("已保存 {}", filename),
+            Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! 多边形光源示例：正六边形和三角形面光源按面积重要性采样
+//!
+//! 运行：`cargo run --release --example polygon_lights`
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::geometry::triangle::Triangle;
+use std::sync::Arc;
+
+fn main() {
+    let mut world = HittableList::new();
+    let mut lights = HittableList::new();
+
+    let white = Arc::new(Lambertian::new(Color::new(0.73, 0.73, 0.73)));
+    world.add(Arc::new(Quad::new(
+        Point3::new(-4.0, 0.0, -4.0),
+        Vec3::new(8.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 8.0),
+        white.clone(),
+    )));
+    world.add(Arc::new(Quad::new(
+        Point3::new(-4.0, 0.0, -2.0),
+        Vec3::new(8.0, 0.0, 0.0),
+        Vec3::new(0.0, 4.0, 0.0),
+        white.clone(),
+    )));
+    world.add(Arc::new(Polygon::regular(
+        Point3::new(0.0, 0.001, 0.3),
+        Vec3::new(0.0, 1.0, 0.0),
+        0.9,
+        5,
+        Arc::new(Lambertian::new(Color::new(0.2, 0.5, 0.8))),
+    )));
+    world.add(Arc::new(Sphere::new(
+        Point3::new(0.0, 0.5, 0.3),
+        0.5,
+        Arc::new(Dielectric::new(1.5)),
+    )));
+
+    let hexagon: Arc<dyn Hittable> = Arc::new(Polygon::regular(
+        Point3::new(-1.2, 2.6, 0.5),
+        Vec3::new(0.4, -1.0, 0.0),
+        0.5,
+        6,
+        Arc::new(DiffuseLight::new_color(Color::new(30.0, 22.0, 15.0))),
+    ));
+    let triangle: Arc<dyn Hittable> = Arc::new(Triangle::new(
+        Point3::new(1.0, 2.4, 0.0),
+        Point3::new(1.8, 2.4, 0.6),
+        Point3::new(1.2, 2.6, 1.0),
+        Arc::new(DiffuseLight::new_color(Color::new(12.0, 20.0, 35.0))),
+    ));
+    for light in [hexagon, triangle] {
+        world.add(light.clone());
+        lights.add(light);
+    }
+
+    let world = BvhNode::new(&world);
+    let mut camera = Camera::builder()
+        .aspect_ratio(16.0 / 9.0)
+        .image_width(400)
+        .samples_per_pixel(100)
+        .max_depth(20)
+        .background_color(Color::zeros())
+        .lookfrom(Point3::new(0.0, 1.6, 5.0))
+        .lookat(Point3::new(0.0, 0.8, 0.0))
+        .vfov(45.0)
+        .output_filename("polygon_lights.png")
+        .build();
+    camera.render(&world, Some(Arc::new(lights)));
+}
@@ -0,0 +1,58 @@
+//! Disney 原则化材质：同一组参数覆盖常见的几类外观
+//!
+//! 运行：`cargo run --release --example principled [每像素样本数]`
+//!
+//! 在测试球场景中从左到右依次渲染：塑料、拉丝铜、天鹅绒（光泽层）、车漆（金属 + 清漆层）、
+//! 玻璃和磨砂玻璃（透射）。输出 principled.png。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::materials::principled::PrincipledMaterial;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::scenes::material_ball::{MaterialBallConfig, material_ball_sheet};
+use std::sync::Arc;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let spp: i32 = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(128);
+
+    let materials: Vec<Arc<dyn Material>> = vec![
+        Arc::new(PrincipledMaterial::new_constant(
+            Color::new(0.1, 0.3, 0.8),
+            0.3,
+            0.0,
+        )),
+        Arc::new(PrincipledMaterial::new_constant(
+            Color::new(0.95, 0.64, 0.54),
+            0.35,
+            1.0,
+        )),
+        Arc::new(
+            PrincipledMaterial::new_constant(Color::new(0.35, 0.02, 0.1), 1.0, 0.0)
+                .with_specular(0.1, 0.0)
+                .with_sheen(1.0, 0.6),
+        ),
+        Arc::new(
+            PrincipledMaterial::new_constant(Color::new(0.5, 0.05, 0.05), 0.45, 0.7)
+                .with_clearcoat(1.0, 0.95),
+        ),
+        Arc::new(
+            PrincipledMaterial::new_constant(Color::new(0.9, 1.0, 0.95), 0.0, 0.0)
+                .with_transmission(1.0, 1.5),
+        ),
+        Arc::new(
+            PrincipledMaterial::new_constant(Color::new(0.8, 0.9, 1.0), 0.3, 0.0)
+                .with_transmission(1.0, 1.5),
+        ),
+    ];
+
+    let config = MaterialBallConfig {
+        image_width: 240,
+        samples_per_pixel: spp,
+        ..MaterialBallConfig::default()
+    };
+    let sheet = material_ball_sheet(&materials, &config);
+    match save_framebuffer(&sheet, "principled.png", OutputFormat::Png8) {
+        Ok(()) => eprintln!("已保存 principled.png"),
+        Err(e) => eprintln!("保存 principled.png 失败: {}", e),
+    }
+}
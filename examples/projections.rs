@@ -0,0 +1,103 @@
+//! 相机投影：同一个小镇分别用透视景深、移轴镜头（微缩模型效果）和小行星投影渲染
+//!
+//! 运行：`cargo run --release --example projections`
+//!
+//! 输出 projections.png，从左到右为：普通透视 + 景深、移轴镜头、小行星。
+//! 前两幅使用相同的光圈和对焦距离：透视的景深几乎察觉不到，移轴镜头倾斜 3° 后清晰平面
+//! 几乎沿视线方向，只有画面中部一条窄带清晰。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::geometry::quad::box_new;
+use ray_tracing_rust::ray_tracing::rendering::background::VerticalGradient;
+use ray_tracing_rust::ray_tracing::rendering::framebuffer::FrameBuffer;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::rendering::projection::{Projection, TiltShift};
+use ray_tracing_rust::ray_tracing::utils::image_compare::side_by_side;
+use ray_tracing_rust::ray_tracing::utils::random::{Pcg32, hash_seed};
+use std::sync::Arc;
+
+/// 街区网格的半边格数和格距
+const BLOCKS: i32 = 8;
+const SPACING: f64 = 6.0;
+
+fn town() -> BvhNode {
+    let mut world = HittableList::new();
+    world.add(Arc::new(Quad::new(
+        Point3::new(-200.0, 0.0, -200.0),
+        Vec3::new(0.0, 0.0, 400.0),
+        Vec3::new(400.0, 0.0, 0.0),
+        Arc::new(Lambertian::new(Color::new(0.35, 0.45, 0.3))),
+    )));
+
+    let palette = [
+        Color::new(0.8, 0.3, 0.25),
+        Color::new(0.85, 0.75, 0.5),
+        Color::new(0.3, 0.45, 0.75),
+        Color::new(0.9, 0.9, 0.85),
+    ];
+    for i in -BLOCKS..=BLOCKS {
+        for k in -BLOCKS..=BLOCKS {
+            let mut rng = Pcg32::new(hash_seed(&[i as u64, k as u64]));
+            let (x, z) = (i as f64 * SPACING, k as f64 * SPACING);
+            let height = 2.0 + 6.0 * rng.next_f64().powi(2);
+            let color = palette[(rng.next_u32() % palette.len() as u32) as usize];
+            world.add(Arc::new(box_new(
+                Point3::new(x - 1.8, 0.0, z - 1.8),
+                Point3::new(x + 1.8, height, z + 1.8),
+                Arc::new(Lambertian::new(color)),
+            )));
+        }
+    }
+    BvhNode::new(&world)
+}
+
+fn render(world: &BvhNode, builder: CameraBuilder) -> FrameBuffer {
+    let camera = builder
+        .aspect_ratio(4.0 / 3.0)
+        .image_width(300)
+        .samples_per_pixel(64)
+        .max_depth(6)
+        .background(Arc::new(VerticalGradient::sky()))
+        .seed(3)
+        .build();
+    camera.render_to_buffer(world, None)
+}
+
+fn main() {
+    let world = town();
+
+    let lookfrom = Point3::new(10.0, 32.0, 62.0);
+    let lookat = Point3::new(0.0, 0.0, 0.0);
+    let aerial = || {
+        Camera::builder()
+            .vfov(35.0)
+            .lookfrom(lookfrom)
+            .lookat(lookat)
+            .defocus_angle(0.1)
+            .focus_dist((lookfrom - lookat).norm())
+    };
+
+    let panels = [
+        render(&world, aerial()),
+        render(
+            &world,
+            aerial().projection(Projection::TiltShift(TiltShift::tilt(3.0))),
+        ),
+        render(
+            &world,
+            Camera::builder()
+                .vfov(90.0)
+                .lookfrom(Point3::new(0.0, 9.0, 0.0))
+                .lookat(Point3::new(0.0, 0.0, 0.0))
+                .vup(Vec3::new(0.0, 0.0, -1.0))
+                .projection(Projection::LittlePlanet { fov: 240.0 }),
+        ),
+    ];
+
+    let filename = "projections.png";
+    let image = side_by_side(&panels.iter().collect::<Vec<_>>());
+    match save_framebuffer(&image, filename, OutputFormat::Png8) {
+        Ok(()) => eprintln!("已保存 {}（透视 | 移轴 | 小行星）", filename),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
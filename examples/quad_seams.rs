@@ -0,0 +1,84 @@
+//! 四边形接缝的回归图：从斜放且远离原点的封闭盒子内部射向 12 条棱边，记录从缝隙漏出的光线
+//!
+//! 运行：`cargo run --release --example quad_seams`
+//!
+//! 输出 quad_seams.png：每条棱边占一条横带，横坐标为棱边上的位置，纵坐标为盒子内部不同的出发点；
+//! 命中盒子的光线为深灰色，漏出的光线为红色。接缝密封时整幅图没有红点。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::math::onb::ONB;
+use ray_tracing_rust::ray_tracing::rendering::framebuffer::FrameBuffer;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use std::sync::Arc;
+
+/// 棱边上的取样位置数（图像宽度）
+const POSITIONS: u32 = 512;
+/// 每条棱边的出发点数
+const ORIGINS: u32 = 24;
+
+fn main() {
+    let frame = ONB::new(&Vec3::new(0.3, 0.7, -0.4).normalize());
+    let corner = Point3::new(1234.5, -678.25, 4321.0);
+    let edges = [
+        frame.u() * 37.0,
+        frame.v() * 12.5 + frame.u() * 3.0,
+        frame.w() * 80.0,
+    ];
+    let far = corner + edges[0] + edges[1] + edges[2];
+
+    let mut sides = HittableList::new();
+    for (k, q) in [(0, corner), (1, far)] {
+        let sign = if k == 0 { 1.0 } else { -1.0 };
+        for i in 0..3 {
+            let (u, v) = (edges[i] * sign, edges[(i + 1) % 3] * sign);
+            sides.add(Arc::new(Quad::new(q, u, v, Arc::new(NoMaterial))));
+        }
+    }
+
+    // 12 条棱边：沿第 axis 条棱，另外两条棱方向各取 0 或 1
+    let mut seams = Vec::new();
+    for axis in 0..3 {
+        for offset in 0..4 {
+            let mut start = corner;
+            for (bit, k) in [(axis + 1) % 3, (axis + 2) % 3].into_iter().enumerate() {
+                if offset >> bit & 1 == 1 {
+                    start += edges[k];
+                }
+            }
+            seams.push((start, edges[axis]));
+        }
+    }
+
+    let mut fb = FrameBuffer::new(POSITIONS, seams.len() as u32 * ORIGINS);
+    let mut leaked = 0;
+    for (index, (start, edge)) in seams.iter().enumerate() {
+        for row in 0..ORIGINS {
+            // 内部出发点沿盒子对角线分布
+            let f = 0.15 + 0.7 * (row as f64 + 0.5) / ORIGINS as f64;
+            let origin = corner + f * edges[0] + (1.0 - f) * edges[1] + (0.3 + 0.4 * f) * edges[2];
+            for x in 0..POSITIONS {
+                let target = start + ((x as f64 + 0.5) / POSITIONS as f64) * edge;
+                let ray = Ray::new(origin, target - origin, 0.0);
+                let mut rec = HitRecord::default();
+                let hit = sides.hit(&ray, Interval::new(1e-9, f64::INFINITY), &mut rec);
+                let color = if hit {
+                    Color::new(0.1, 0.1, 0.1)
+                } else {
+                    leaked += 1;
+                    Color::new(1.0, 0.0, 0.0)
+                };
+                fb.set(x, index as u32 * ORIGINS + row, color);
+            }
+        }
+    }
+
+    println!(
+        "{} 条射向棱边的光线中 {} 条从缝隙漏出",
+        POSITIONS * ORIGINS * seams.len() as u32,
+        leaked
+    );
+    match save_framebuffer(&fb, "quad_seams.png", OutputFormat::Png8) {
+        Ok(()) => eprintln!("已保存 quad_seams.png"),
+        Err(e) => eprintln!("保存 quad_seams.png 失败: {}", e),
+    }
+}
@@ -0,0 +1,49 @@
+//! 二次曲面示例：椭球、抛物面和双曲面
+//!
+//! 运行：`cargo run --release --example quadrics`
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::rendering::background::VerticalGradient;
+use std::sync::Arc;
+
+fn main() {
+    let mut world = HittableList::new();
+    world.add(Arc::new(Quad::new(
+        Point3::new(-6.0, 0.0, -6.0),
+        Vec3::new(12.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 12.0),
+        Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5))),
+    )));
+    world.add(Arc::new(Quadric::ellipsoid(
+        Point3::new(-2.2, 0.6, 0.0),
+        Vec3::new(0.9, 0.6, 0.5),
+        Arc::new(Lambertian::new(Color::new(0.8, 0.3, 0.2))),
+    )));
+    world.add(Arc::new(Quadric::paraboloid(
+        Point3::new(0.0, 0.0, 0.0),
+        0.8,
+        1.4,
+        Arc::new(Metal::new(Color::new(0.9, 0.8, 0.5), 0.05)),
+    )));
+    world.add(Arc::new(Quadric::hyperboloid(
+        Point3::new(2.2, 0.8, 0.0),
+        0.35,
+        0.8,
+        1.6,
+        Arc::new(Lambertian::new(Color::new(0.2, 0.4, 0.8))),
+    )));
+
+    let world = BvhNode::new(&world);
+    let mut camera = Camera::builder()
+        .aspect_ratio(16.0 / 9.0)
+        .image_width(400)
+        .samples_per_pixel(64)
+        .max_depth(20)
+        .background(Arc::new(VerticalGradient::sky()))
+        .lookfrom(Point3::new(0.0, 2.5, 6.0))
+        .lookat(Point3::new(0.0, 0.6, 0.0))
+        .vfov(40.0)
+        .output_filename("quadrics.png")
+        .build();
+    camera.render(&world, None);
+}
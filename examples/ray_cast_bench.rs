@@ -0,0 +1,168 @@
+//! 光线查询接口的用法和基准：与相机和着色无关的最近交点、遮挡和可见性查询
+//!
+//! 运行：`cargo run --release --example ray_cast_bench`
+//!
+//! 场景为地面上随机摆放的约 1000 个球和盒子，依次测量：
+//! - 单线程最近交点和遮挡查询的耗时
+//! - 模拟旋转激光雷达（360×64 线束）的批量并行测距
+//! - 随机点对之间的可见性预计算
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::scene::ray_cast::RayCast;
+use ray_tracing_rust::ray_tracing::scene::world::Scene;
+use ray_tracing_rust::ray_tracing::utils::random::with_seeded_stream;
+use std::f64::consts::PI;
+use std::hint::black_box;
+use std::sync::Arc;
+use std::time::Instant;
+
+const RAYS: usize = 200_000;
+const PAIRS: usize = 100_000;
+
+fn build_scene() -> Scene {
+    let white: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.73, 0.73, 0.73)));
+    let mut scene = Scene::new();
+    scene.add(Arc::new(Quad::new(
+        Point3::new(-100.0, 0.0, -100.0),
+        Vec3::new(200.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 200.0),
+        white.clone(),
+    )));
+    for i in 0..1000 {
+        let x = random_double_range(-90.0, 90.0);
+        let z = random_double_range(-90.0, 90.0);
+        let size = random_double_range(0.5, 3.0);
+        // 激光雷达所在的原点附近留空
+        if x.abs() < 8.0 && z.abs() < 8.0 {
+            continue;
+        }
+        if i % 2 == 0 {
+            scene.add(Arc::new(Sphere::new(
+                Point3::new(x, size, z),
+                size,
+                white.clone(),
+            )));
+        } else {
+            scene.add(Arc::new(box_new(
+                Point3::new(x - size, 0.0, z - size),
+                Point3::new(x + size, 2.0 * size, z + size),
+                white.clone(),
+            )));
+        }
+    }
+    scene
+}
+
+/// 场景上方随机点射向地面附近随机点的光线
+fn random_rays() -> Vec<Ray> {
+    (0..RAYS)
+        .map(|_| {
+            let origin = Point3::new(
+                random_double_range(-100.0, 100.0),
+                random_double_range(5.0, 30.0),
+                random_double_range(-100.0, 100.0),
+            );
+            let target = Point3::new(
+                random_double_range(-100.0, 100.0),
+                0.0,
+                random_double_range(-100.0, 100.0),
+            );
+            Ray::new(origin, (target - origin).normalize(), 0.0)
+        })
+        .collect()
+}
+
+/// 位于 origin 的旋转激光雷达：水平 360 线、俯仰 -25°..15° 共 64 线
+fn lidar_rays(origin: Point3) -> Vec<Ray> {
+    let mut rays = Vec::with_capacity(360 * 64);
+    for ring in 0..64 {
+        let elevation = (-25.0 + 40.0 * ring as f64 / 63.0) * PI / 180.0;
+        for step in 0..360 {
+            let azimuth = step as f64 * PI / 180.0;
+            let dir = Vec3::new(
+                elevation.cos() * azimuth.cos(),
+                elevation.sin(),
+                elevation.cos() * azimuth.sin(),
+            );
+            rays.push(Ray::new(origin, dir, 0.0));
+        }
+    }
+    rays
+}
+
+fn main() {
+    with_seeded_stream(3, || {
+        let start = Instant::now();
+        let scene = build_scene();
+        let caster = scene.ray_caster();
+        println!(
+            "场景: {} 个物体，构建加速结构 {:.1} ms",
+            scene.len(),
+            start.elapsed().as_secs_f64() * 1e3
+        );
+
+        let rays = random_rays();
+
+        // 单线程最近交点：直接在加速结构上使用 RayCast
+        let start = Instant::now();
+        let hits = rays
+            .iter()
+            .filter(|r| caster.world().trace_nearest(r).is_some())
+            .count();
+        let nearest = start.elapsed();
+
+        // 单线程遮挡：只需判断 20 单位内是否被挡住
+        let start = Instant::now();
+        let blocked = rays
+            .iter()
+            .filter(|r| caster.trace_occluded(r, 20.0))
+            .count();
+        let occluded = start.elapsed();
+        println!(
+            "单线程: 最近交点 {:>6.0} ns/光线（命中 {}），遮挡 {:>6.0} ns/光线（20 单位内遮挡 {}）",
+            nearest.as_secs_f64() * 1e9 / RAYS as f64,
+            black_box(hits),
+            occluded.as_secs_f64() * 1e9 / RAYS as f64,
+            black_box(blocked)
+        );
+
+        // 批量并行：激光雷达扫描
+        let lidar = lidar_rays(Point3::new(0.0, 1.8, 0.0));
+        let start = Instant::now();
+        let distances = caster.trace_distance_batch(&lidar);
+        let scan = start.elapsed();
+        let returns: Vec<f64> = distances.iter().flatten().copied().collect();
+        let mean = returns.iter().sum::<f64>() / returns.len().max(1) as f64;
+        println!(
+            "激光雷达: {} 条光束，{:.2} ms（{:.0} ns/光束），回波 {}，平均距离 {:.2}",
+            lidar.len(),
+            scan.as_secs_f64() * 1e3,
+            scan.as_secs_f64() * 1e9 / lidar.len() as f64,
+            returns.len(),
+            mean
+        );
+
+        // 可见性预计算：随机点对
+        let points: Vec<(Point3, Point3)> = (0..PAIRS)
+            .map(|_| {
+                let p = |y| {
+                    Point3::new(
+                        random_double_range(-90.0, 90.0),
+                        y,
+                        random_double_range(-90.0, 90.0),
+                    )
+                };
+                (p(1.0), p(1.0))
+            })
+            .collect();
+        let start = Instant::now();
+        let visible = points.iter().filter(|(a, b)| caster.visible(a, b)).count();
+        let elapsed = start.elapsed();
+        println!(
+            "可见性: {} 个点对，{:>6.0} ns/对，互相可见 {:.1}%",
+            PAIRS,
+            elapsed.as_secs_f64() * 1e9 / PAIRS as f64,
+            100.0 * visible as f64 / PAIRS as f64
+        );
+    });
+}
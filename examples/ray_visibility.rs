@@ -0,0 +1,85 @@
+//! 按光线类型控制可见性：同一个橙色球分别设置为完全可见、对相机不可见（只留阴影和倒影）、
+//! 只对相机可见（没有阴影和倒影），三幅图从左到右拼接输出
+//!
+//! 运行：`cargo run --release --example ray_visibility`
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::rendering::framebuffer::FrameBuffer;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::utils::image_compare::side_by_side;
+use std::sync::Arc;
+
+fn render(flags: RayVisibility) -> FrameBuffer {
+    let mut world = HittableList::new();
+    let mut lights = HittableList::new();
+
+    let white = Arc::new(Lambertian::new(Color::new(0.73, 0.73, 0.73)));
+    world.add(Arc::new(Quad::new(
+        Point3::new(-4.0, 0.0, -4.0),
+        Vec3::new(8.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 8.0),
+        white.clone(),
+    )));
+    // 后方的镜子显示倒影
+    world.add(Arc::new(Quad::new(
+        Point3::new(-3.0, 0.0, -1.5),
+        Vec3::new(6.0, 0.0, 0.0),
+        Vec3::new(0.0, 3.0, 0.0),
+        Arc::new(Metal::new(Color::new(0.9, 0.9, 0.9), 0.0)),
+    )));
+    world.add(Arc::new(Visibility::new(
+        Arc::new(Sphere::new(
+            Point3::new(0.0, 0.6, 0.0),
+            0.6,
+            Arc::new(Lambertian::new(Color::new(0.9, 0.45, 0.1))),
+        )),
+        flags,
+    )));
+
+    let light: Arc<dyn Hittable> = Arc::new(Quad::new(
+        Point3::new(0.5, 3.0, 0.5),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.0),
+        Arc::new(DiffuseLight::new_color(Color::new(15.0, 15.0, 15.0))),
+    ));
+    world.add(light.clone());
+    lights.add(light);
+
+    let world = BvhNode::new(&world);
+    let camera = Camera::builder()
+        .image_width(300)
+        .samples_per_pixel(64)
+        .max_depth(10)
+        .background_color(Color::new(0.05, 0.05, 0.08))
+        .vfov(40.0)
+        .lookfrom(Point3::new(1.5, 2.0, 5.0))
+        .lookat(Point3::new(0.0, 0.6, 0.0))
+        .seed(1)
+        .build();
+    camera.render_to_buffer(&world, Some(Arc::new(lights)))
+}
+
+fn main() {
+    let panels = [
+        ("完全可见", RayVisibility::default()),
+        ("对相机不可见", RayVisibility::SHADOW_ONLY),
+        ("只对相机可见", RayVisibility::CAMERA_ONLY),
+    ];
+    let images: Vec<FrameBuffer> = panels
+        .iter()
+        .map(|(name, flags)| {
+            eprintln!("渲染: {}", name);
+            render(*flags)
+        })
+        .collect();
+
+    let filename = "ray_visibility.png";
+    let image = side_by_side(&images.iter().collect::<Vec<_>>());
+    match save_framebuffer(&image, filename, OutputFormat::Png8) {
+        Ok(()) => eprintln!(
+            "已保存 {}（完全可见 | 对相机不可见 | 只对相机可见）",
+            filename
+        ),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
@@ -0,0 +1,88 @@
+//! 嵌入式渲染接口：通过回调获取进度，用原子标志取消渲染
+//!
+//! 运行：`cargo run --release --example render_progress`
+//!
+//! 第一次渲染完整完成并每 10% 打印一次进度；第二次在完成一半时由另一个线程请求取消。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::rendering::progress::Progress;
+use ray_tracing_rust::scenes::cornell_box::build_cornell_box_scene;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+fn main() {
+    let (world, lights) = build_cornell_box_scene();
+    let world = BvhNode::new(&world);
+    let lights: Arc<dyn Hittable> = Arc::new(lights);
+    let camera = Camera::builder()
+        .image_width(200)
+        .samples_per_pixel(16)
+        .max_depth(10)
+        .background_color(Color::zeros())
+        .vfov(40.0)
+        .lookfrom(Point3::new(278.0, 278.0, -800.0))
+        .lookat(Point3::new(278.0, 278.0, 0.0))
+        .build();
+
+    // 完整渲染：回调在工作线程上并发调用，只在跨过 10% 的边界时打印
+    let last_decile = AtomicU64::new(0);
+    let start = Instant::now();
+    let fb = camera.render_with(
+        &world,
+        Some(lights.clone()),
+        |p: Progress| {
+            let decile = (p.fraction() * 10.0) as u64;
+            if last_decile.fetch_max(decile, Ordering::Relaxed) < decile {
+                println!(
+                    "进度 {:>3}%（{}/{} 像素）",
+                    decile * 10,
+                    p.completed,
+                    p.total
+                );
+            }
+        },
+        &AtomicBool::new(false),
+    );
+    match fb {
+        Some(fb) => println!(
+            "完成 {}x{}，用时 {:.2} s",
+            fb.width(),
+            fb.height(),
+            start.elapsed().as_secs_f64()
+        ),
+        None => println!("渲染被意外取消"),
+    }
+
+    // 取消：另一个线程观察进度，过半后置位取消标志
+    let cancel = AtomicBool::new(false);
+    let fraction = AtomicU64::new(0);
+    let start = Instant::now();
+    let result = std::thread::scope(|scope| {
+        scope.spawn(|| {
+            while fraction.load(Ordering::Relaxed) < 50 {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            println!("请求取消");
+            cancel.store(true, Ordering::Relaxed);
+        });
+        camera.render_with(
+            &world,
+            Some(lights),
+            |p| {
+                fraction.fetch_max((p.fraction() * 100.0) as u64, Ordering::Relaxed);
+            },
+            &cancel,
+        )
+    });
+    println!(
+        "{}，完成 {}% 时停止，用时 {:.2} s",
+        if result.is_none() {
+            "渲染已取消"
+        } else {
+            "渲染在取消前已完成"
+        },
+        fraction.load(Ordering::Relaxed),
+        start.elapsed().as_secs_f64()
+    );
+}
@@ -0,0 +1,38 @@
+//! 机器可读的渲染报告：渲染康奈尔盒并在输出图像旁写出 JSON 报告
+//!
+//! 运行：`cargo run --release --example render_report`
+//!
+//! 输出 render_report.png 和 render_report.png.json。报告包含渲染设置、各阶段耗时、
+//! 游程压缩的进度记录、按材质类型的着色统计和输出文件的哈希；命令行渲染加 `--report`
+//! 或在 raytracer.toml 中设置 `report = true` 得到同样的报告。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::rendering::report::report_path;
+use ray_tracing_rust::ray_tracing::rendering::stats::RenderStats;
+use ray_tracing_rust::scenes::cornell_box::build_cornell_box_scene;
+use std::sync::Arc;
+
+fn main() {
+    let (world, lights) = build_cornell_box_scene();
+    let world = BvhNode::new(&world);
+    let filename = "render_report.png";
+    let mut camera = Camera::builder()
+        .image_width(200)
+        .samples_per_pixel(16)
+        .max_depth(10)
+        .background_color(Color::zeros())
+        .vfov(40.0)
+        .lookfrom(Point3::new(278.0, 278.0, -800.0))
+        .lookat(Point3::new(278.0, 278.0, 0.0))
+        .output_filename(filename)
+        .stats(Arc::new(RenderStats::new()))
+        .report(true)
+        .seed(1)
+        .build();
+    camera.render(&world, Some(Arc::new(lights)));
+
+    match std::fs::read_to_string(report_path(filename)) {
+        Ok(text) => print!("{}", text),
+        Err(e) => eprintln!("读取渲染报告失败: {}", e),
+    }
+}
@@ -0,0 +1,170 @@
+//! 程序化散布：在起伏地形上按噪声密度图种树、撒石头，在平地四边形上排布楼块
+//!
+//! 运行：`cargo run --release --example scatter [种子]`
+//!
+//! 输出 scatter.png。所有实例共享三个原型的几何，只保存各自的缩放、旋转和平移。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::acceleration::Accelerator;
+use ray_tracing_rust::ray_tracing::geometry::mesh::{MeshData, TriangleMesh};
+use ray_tracing_rust::ray_tracing::geometry::quad::box_new;
+use ray_tracing_rust::ray_tracing::materials::texture::Texture;
+use ray_tracing_rust::ray_tracing::procedural::noise::Perlin;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::scene::scatter::{Scatter, ScatterSettings, ScatterSurface};
+use std::sync::Arc;
+
+/// 地形半边长
+const HALF: f64 = 40.0;
+
+fn height(x: f64, z: f64) -> f64 {
+    // 右侧平地留给城市
+    let hills = 2.5 * (x * 0.12).sin() * (z * 0.09).cos() + 1.2 * (x * 0.31 + z * 0.17).sin();
+    hills * ((10.0 - x) / 20.0).clamp(0.0, 1.0)
+}
+
+/// 成片的森林：低频噪声阈值化后作为种树的概率
+#[derive(Debug)]
+struct ForestDensity {
+    noise: Perlin,
+}
+
+impl Texture for ForestDensity {
+    fn value(&self, _u: f64, _v: f64, p: &Point3) -> Color {
+        let n = self.noise.noise(&Point3::new(p.x * 0.08, 0.0, p.z * 0.08));
+        Color::repeat((n * 3.0 + 0.2).clamp(0.0, 1.0))
+    }
+}
+
+fn terrain(cells: usize, mat: Arc<dyn Material>) -> TriangleMesh {
+    let mut data = MeshData::default();
+    let step = 2.0 * HALF / cells as f64;
+    for j in 0..=cells {
+        for i in 0..=cells {
+            let (x, z) = (i as f64 * step - HALF, j as f64 * step - HALF);
+            data.positions.push(Point3::new(x, height(x, z), z));
+        }
+    }
+    let row = cells + 1;
+    for j in 0..cells {
+        for i in 0..cells {
+            let v = j * row + i;
+            data.indices.push([v, v + row, v + 1]);
+            data.indices.push([v + 1, v + row, v + row + 1]);
+        }
+    }
+    TriangleMesh::new(&data, mat)
+}
+
+/// 原型：底部中心位于原点
+fn tree() -> Arc<dyn Hittable> {
+    let mut parts = box_new(
+        Point3::new(-0.12, 0.0, -0.12),
+        Point3::new(0.12, 1.2, 0.12),
+        Arc::new(Lambertian::new(Color::new(0.35, 0.22, 0.12))),
+    );
+    parts.add(Arc::new(Sphere::new(
+        Point3::new(0.0, 1.9, 0.0),
+        0.9,
+        Arc::new(Lambertian::new(Color::new(0.12, 0.38, 0.12))),
+    )));
+    Arc::new(BvhNode::new(&parts))
+}
+
+fn rock() -> Arc<dyn Hittable> {
+    Arc::new(Sphere::new(
+        Point3::new(0.0, 0.25, 0.0),
+        0.5,
+        Arc::new(Lambertian::new(Color::new(0.45, 0.45, 0.47))),
+    ))
+}
+
+fn building() -> Arc<dyn Hittable> {
+    Arc::new(BvhNode::new(&box_new(
+        Point3::new(-1.0, 0.0, -1.0),
+        Point3::new(1.0, 3.0, 1.0),
+        Arc::new(Lambertian::new(Color::new(0.75, 0.72, 0.68))),
+    )))
+}
+
+fn main() {
+    let seed: u64 = std::env::args()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(7);
+
+    let mut world = HittableList::new();
+    let ground = Arc::new(Lambertian::new(Color::new(0.42, 0.5, 0.3)));
+    world.add(Arc::new(terrain(160, ground)));
+
+    let hills = ScatterSurface::Heightfield {
+        min: (-HALF, -HALF),
+        max: (10.0, HALF),
+        height: Arc::new(height),
+    };
+    let forest = Scatter::new(ScatterSettings {
+        scale: (0.7, 1.4),
+        min_distance: 1.2,
+        max_slope: 25.0,
+        sink: 0.1,
+        ..ScatterSettings::new(2500, seed)
+    })
+    .with_prototype(tree(), 1.0)
+    .with_density(Arc::new(ForestDensity {
+        noise: Perlin::new(),
+    }));
+    let rocks = Scatter::new(ScatterSettings {
+        scale: (0.3, 1.2),
+        sink: 0.2,
+        ..ScatterSettings::new(300, seed + 1)
+    })
+    .with_prototype(rock(), 1.0);
+
+    // 城市：平地上的四边形区域（法线朝上），楼块高矮不一
+    let block = Arc::new(Quad::new(
+        Point3::new(14.0, 0.0, -30.0),
+        Vec3::new(0.0, 0.0, 60.0),
+        Vec3::new(24.0, 0.0, 0.0),
+        Arc::new(Lambertian::new(Color::new(0.3, 0.3, 0.3))),
+    ));
+    world.add(block.clone());
+    let city = Scatter::new(ScatterSettings {
+        scale: (0.6, 1.8),
+        rotation_jitter: 10.0,
+        min_distance: 3.2,
+        ..ScatterSettings::new(200, seed + 2)
+    })
+    .with_prototype(building(), 3.0)
+    .with_prototype(tree(), 1.0);
+
+    let mut instances = 0;
+    for (scatter, surface) in [
+        (&forest, &hills),
+        (&rocks, &hills),
+        (&city, &ScatterSurface::Surface(block)),
+    ] {
+        let objects = scatter.build(surface);
+        instances += objects.len();
+        world.add(Accelerator::Bvh.build(&objects));
+    }
+    println!("共 {} 个实例", instances);
+
+    let world = BvhNode::new(&world);
+    let camera = Camera::builder()
+        .aspect_ratio(16.0 / 9.0)
+        .image_width(400)
+        .samples_per_pixel(32)
+        .max_depth(6)
+        .vfov(40.0)
+        .lookfrom(Point3::new(-30.0, 35.0, 60.0))
+        .lookat(Point3::new(2.0, 0.0, 0.0))
+        .seed(seed)
+        .build();
+    let fb = camera.render_to_buffer(&world, None);
+
+    let filename = "scatter.png";
+    match save_framebuffer(&fb, filename, OutputFormat::Png8) {
+        Ok(()) => println!("已保存 {}", filename),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
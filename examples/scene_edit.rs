@@ -0,0 +1,68 @@
+//! 以编程方式修改场景：按类型查询物体、替换和移除物体，无需重新构建整个场景
+//!
+//! 运行：`cargo run --release --example scene_edit`
+//! 先渲染一排漫反射球，再把较大的球替换为玻璃球、移除最右侧的球后渲染第二张图。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::rendering::background::VerticalGradient;
+use ray_tracing_rust::ray_tracing::scene::world::Scene;
+use std::sync::Arc;
+
+fn render(scene: &Scene, filename: &str) {
+    let camera = Camera::builder()
+        .aspect_ratio(16.0 / 9.0)
+        .image_width(400)
+        .samples_per_pixel(50)
+        .max_depth(20)
+        .background(Arc::new(VerticalGradient::sky()))
+        .lookfrom(Point3::new(0.0, 1.0, 4.0))
+        .lookat(Point3::new(0.0, 0.2, 0.0))
+        .output_filename(filename)
+        .build();
+    camera.render_frame(scene.build_world().as_ref(), scene.light_sampler());
+}
+
+fn main() {
+    let mut scene = Scene::new();
+    scene.add(Arc::new(Quad::new(
+        Point3::new(-5.0, -0.5, -5.0),
+        Vec3::new(10.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 10.0),
+        Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5))),
+    )));
+    for (i, radius) in [0.3, 0.5, 0.3, 0.5, 0.3].into_iter().enumerate() {
+        scene.add(Arc::new(Sphere::new(
+            Point3::new(i as f64 - 2.0, radius - 0.5, 0.0),
+            radius,
+            Arc::new(Lambertian::new(Color::new(0.8, 0.3, 0.2))),
+        )));
+    }
+    render(&scene, "scene_edit_before.png");
+
+    // 按类型查询：较大的球换成玻璃球，标识不变
+    let glass: Arc<dyn Material> = Arc::new(Dielectric::new(1.5));
+    let large: Vec<_> = scene
+        .objects_of::<Sphere>()
+        .filter(|(_, sphere)| sphere.radius() > 0.4)
+        .map(|(id, sphere)| (id, sphere.center(0.0), sphere.radius()))
+        .collect();
+    for (id, center, radius) in large {
+        scene.replace(id, Arc::new(Sphere::new(center, radius, glass.clone())));
+    }
+
+    // 移除最右侧的球
+    if let Some(rightmost) = scene
+        .objects_of::<Sphere>()
+        .max_by(|(_, a), (_, b)| a.center(0.0).x.total_cmp(&b.center(0.0).x))
+        .map(|(id, _)| id)
+    {
+        scene.remove(rightmost);
+    }
+
+    println!(
+        "修改后: {} 个物体，其中 {} 个球体",
+        scene.len(),
+        scene.objects_of::<Sphere>().count()
+    );
+    render(&scene, "scene_edit_after.png");
+}
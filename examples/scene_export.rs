@@ -0,0 +1,89 @@
+//! 把程序构建的场景写成场景文件，再读回来渲染，比较两者是否一致
+//!
+//! 运行：`cargo run --release --example scene_export [--smoke] [输出文件]`
+//!
+//! 使用带旋转盒子和金属球的康奈尔盒：盒子被展开成世界坐标下的四边形，读回后应渲染出相同的图像。
+//! 加上参数 `--smoke` 时场景中还有烟雾，它无法用场景文件表示，会在跳过列表中列出。
+//! 输出 scene_export.png（原场景 | 读回的场景）。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::scene::scene_file::{CameraView, SceneFile, serialize_scene};
+use ray_tracing_rust::ray_tracing::scene::world::Scene;
+use ray_tracing_rust::ray_tracing::utils::image_compare::{compare, display_image, side_by_side};
+use ray_tracing_rust::scenes::cornell_box::{CornellBoxParams, CornellContents, build_cornell_box};
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let smoke = args.iter().any(|a| a == "--smoke");
+    let path = args
+        .iter()
+        .find(|a| !a.starts_with("--"))
+        .cloned()
+        .unwrap_or_else(|| "cornell_export.scene".to_string());
+
+    let mut contents = vec![CornellContents::Boxes, CornellContents::MetalSphere];
+    if smoke {
+        contents.push(CornellContents::Smoke);
+    }
+    let params = CornellBoxParams::with_contents(&contents);
+    let (world, lights) = build_cornell_box(&params);
+    let mut scene = Scene::new();
+    for object in &world {
+        scene.add(object.clone());
+    }
+    for light in &lights {
+        scene.add_light(light.clone());
+    }
+
+    let camera = Camera::builder()
+        .image_width(250)
+        .samples_per_pixel(64)
+        .max_depth(20)
+        .background_color(Color::zeros())
+        .vfov(40.0)
+        .lookfrom(Point3::new(278.0, 278.0, -800.0))
+        .lookat(Point3::new(278.0, 278.0, 0.0))
+        .seed(3)
+        .build();
+
+    let export = serialize_scene(&scene, Some(&CameraView::of(&camera)));
+    if let Err(e) = export.save(&path) {
+        eprintln!("保存 {} 失败: {}", path, e);
+        std::process::exit(1);
+    }
+    eprintln!("已写出 {}（{} 行）", path, export.text.lines().count());
+    for skipped in &export.skipped {
+        eprintln!("  跳过: {}", skipped);
+    }
+
+    let loaded = SceneFile::load(&path).unwrap_or_else(|e| {
+        eprintln!("无法读取场景文件 {}: {}", path, e);
+        std::process::exit(1);
+    });
+    eprintln!(
+        "读回 {} 个物体、{} 个光源",
+        loaded.scene.len(),
+        loaded.scene.lights().count()
+    );
+
+    let mut reloaded_camera = camera.clone();
+    reloaded_camera.set_view(loaded.view.lookfrom, loaded.view.lookat);
+    reloaded_camera.set_vfov(loaded.view.vfov);
+
+    let original = camera.render_to_buffer(scene.build_world().as_ref(), scene.light_sampler());
+    let reloaded = reloaded_camera.render_to_buffer(
+        loaded.scene.build_world().as_ref(),
+        loaded.scene.light_sampler(),
+    );
+    // 没有被跳过的物体时两幅图应当一致，只有展开变换带来的舍入差异
+    let metrics = compare(&display_image(&original), &display_image(&reloaded)).expect("尺寸一致");
+    println!("原场景 vs 读回的场景: {}", metrics);
+
+    let filename = "scene_export.png";
+    let image = side_by_side(&[&original, &reloaded]);
+    match save_framebuffer(&image, filename, OutputFormat::Png8) {
+        Ok(()) => eprintln!("已保存 {}（原场景 | 读回的场景）", filename),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
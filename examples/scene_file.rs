@@ -0,0 +1,39 @@
+//! 从简单的文本场景文件构建场景并渲染
+//!
+//! 运行：`cargo run --release --example scene_file [场景文件]`
+//! 默认读取 `examples/scenes/three_spheres.scene`，格式说明见 `scene::scene_file`。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::scene::scene_file::SceneFile;
+
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "examples/scenes/three_spheres.scene".to_string());
+    let loaded = SceneFile::load(&path).unwrap_or_else(|e| {
+        eprintln!("无法读取场景文件 {}: {}", path, e);
+        std::process::exit(1);
+    });
+
+    let mut camera = Camera::builder()
+        .aspect_ratio(16.0 / 9.0)
+        .image_width(400)
+        .samples_per_pixel(100)
+        .max_depth(20)
+        .background_color(Color::new(0.02, 0.02, 0.03))
+        .vfov(loaded.view.vfov)
+        .lookfrom(loaded.view.lookfrom)
+        .lookat(loaded.view.lookat)
+        .output_filename("scene_file.png")
+        .build();
+
+    eprintln!(
+        "已加载 {} 个物体、{} 个光源",
+        loaded.scene.len(),
+        loaded.scene.lights().count()
+    );
+    camera.render(
+        loaded.scene.build_world().as_ref(),
+        loaded.scene.light_sampler(),
+    );
+}
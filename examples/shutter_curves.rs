@@ -0,0 +1,86 @@
+//! 快门效率曲线：同一颗水平运动的发光球在不同快门曲线下的运动模糊拖影
+//!
+//! 运行：`cargo run --release --example shutter_curves [每像素样本数]`
+//!
+//! 输出 shutter_curves.png，从上到下为 box（均匀采样，拖影亮度处处相同、两端是硬边）、
+//! trapezoid:0.25（两端各四分之一的时间线性开合）、sine（中间最亮、两端平滑淡出）。
+//! 终端打印每种快门沿拖影中线的亮度剖面。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::rendering::framebuffer::FrameBuffer;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::rendering::shutter::Shutter;
+use std::sync::Arc;
+
+const WIDTH: i32 = 320;
+const HEIGHT: u32 = 80;
+
+/// 竖直拼接多幅同宽的图像
+fn stack(images: &[FrameBuffer]) -> FrameBuffer {
+    let width = images.iter().map(|fb| fb.width()).max().unwrap_or(0);
+    let mut out = FrameBuffer::new(width, images.iter().map(|fb| fb.height()).sum());
+    let mut top = 0;
+    for fb in images {
+        for y in 0..fb.height() {
+            for x in 0..fb.width() {
+                out.set(x, top + y, fb.get(x, y));
+            }
+        }
+        top += fb.height();
+    }
+    out
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let spp: i32 = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(64);
+
+    // 快门开启期间从左向右移动 6 个单位
+    let mut world = HittableList::new();
+    world.add(Arc::new(Sphere::new_moving(
+        Point3::new(-3.0, 0.0, 0.0),
+        Point3::new(3.0, 0.0, 0.0),
+        0.5,
+        Arc::new(DiffuseLight::new_color(Color::new(4.0, 3.2, 1.6))),
+    )));
+
+    let shutters = [Shutter::Box, Shutter::trapezoid(0.25), Shutter::Sinusoidal];
+    let mut images = Vec::new();
+    for shutter in shutters {
+        let fb = Camera::builder()
+            .image_width(WIDTH)
+            .aspect_ratio(WIDTH as f64 / HEIGHT as f64)
+            .samples_per_pixel(spp)
+            .max_depth(1)
+            .background_color(Color::zeros())
+            .vfov(20.0)
+            .lookfrom(Point3::new(0.0, 0.0, 12.0))
+            .lookat(Point3::origin())
+            .shutter(shutter.clone())
+            .seed(3)
+            .build()
+            .render_to_buffer(&world, None);
+
+        // 拖影范围内每 16 像素取一次中线附近 5 行的平均亮度
+        let y = fb.height() / 2;
+        let profile: Vec<String> = (96..fb.width() - 96)
+            .step_by(16)
+            .map(|x| {
+                let sum: f64 = (y - 2..=y + 2).map(|y| fb.get(x, y).x).sum();
+                format!("{:.2}", sum / 5.0)
+            })
+            .collect();
+        println!(
+            "{:<16} 等效开启比例 {:.3}，拖影亮度: {}",
+            shutter.to_string(),
+            shutter.efficiency_ratio(),
+            profile.join(" ")
+        );
+        images.push(fb);
+    }
+
+    match save_framebuffer(&stack(&images), "shutter_curves.png", OutputFormat::Png8) {
+        Ok(()) => println!("已保存 shutter_curves.png"),
+        Err(e) => eprintln!("保存失败: {}", e),
+    }
+}
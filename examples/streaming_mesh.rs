@@ -0,0 +1,143 @@
+//! 流式网格：把大网格写成分块文件，内存映射打开后只构建被光线触及的块
+//!
+//! 运行：`cargo run --release --example streaming_mesh [网格文件] [每边格数]`
+//!
+//! 生成一块起伏地形（默认 600×600 格，72 万个三角形）写入网格文件（默认在临时目录），
+//! 然后以流式网格打开并俯视渲染一小片地面，输出 streaming_mesh.png，
+//! 并与一次性构建整个网格的 TriangleMesh 比较准备时间。流式网格的已构建块限制在
+//! 较小的内存预算内，超出时最久未用的块被释放。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::geometry::mesh::{MeshData, TriangleMesh};
+use ray_tracing_rust::ray_tracing::geometry::streaming_mesh::{
+    StreamingMesh, write_streaming_mesh,
+};
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// 地形边长
+const SIZE: f64 = 100.0;
+
+/// 每块的三角形数，比默认值小，便于观察按需构建
+const CHUNK_TRIANGLES: usize = 4096;
+
+/// 已构建块的内存预算（字节）
+const MEMORY_BUDGET: usize = 64 << 20;
+
+fn height(x: f64, z: f64) -> f64 {
+    2.0 * (x * 0.11).sin() * (z * 0.07).cos()
+        + 0.8 * (x * 0.37 + z * 0.23).sin()
+        + 0.25 * (x * 1.3).cos() * (z * 1.1).sin()
+}
+
+fn terrain(cells: usize) -> MeshData {
+    let mut data = MeshData::default();
+    let step = SIZE / cells as f64;
+    for j in 0..=cells {
+        for i in 0..=cells {
+            let (x, z) = (i as f64 * step - SIZE / 2.0, j as f64 * step - SIZE / 2.0);
+            data.positions.push(Point3::new(x, height(x, z), z));
+        }
+    }
+    let row = cells + 1;
+    for j in 0..cells {
+        for i in 0..cells {
+            let v = j * row + i;
+            data.indices.push([v, v + row, v + 1]);
+            data.indices.push([v + 1, v + row, v + row + 1]);
+        }
+    }
+    data
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let path = args.first().cloned().unwrap_or_else(|| {
+        std::env::temp_dir()
+            .join("terrain.rtsmesh")
+            .display()
+            .to_string()
+    });
+    let cells: usize = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(600);
+
+    let data = terrain(cells);
+    let start = Instant::now();
+    let chunks = match write_streaming_mesh(&path, &data, CHUNK_TRIANGLES) {
+        Ok(chunks) => chunks,
+        Err(e) => {
+            eprintln!("写入 {} 失败: {}", path, e);
+            std::process::exit(1);
+        }
+    };
+    let bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    println!(
+        "写入 {}：{} 个三角形，{} 块，{:.1} MB，用时 {:.2} s",
+        path,
+        data.triangle_count(),
+        chunks,
+        bytes as f64 / 1e6,
+        start.elapsed().as_secs_f64()
+    );
+
+    let ground = Arc::new(Lambertian::new(Color::new(0.55, 0.5, 0.4)));
+    let start = Instant::now();
+    let full = TriangleMesh::new(&data, ground.clone());
+    println!(
+        "TriangleMesh 一次性构建 {} 个三角形：{:.2} s",
+        full.len(),
+        start.elapsed().as_secs_f64()
+    );
+    drop(full);
+    drop(data);
+
+    let start = Instant::now();
+    // SAFETY: 文件由本程序刚刚写出，渲染期间不会被截断或改写
+    let mesh = match unsafe { StreamingMesh::open_with_budget(&path, ground, MEMORY_BUDGET) } {
+        Ok(mesh) => Arc::new(mesh),
+        Err(e) => {
+            eprintln!("打开 {} 失败: {}", path, e);
+            std::process::exit(1);
+        }
+    };
+    println!(
+        "StreamingMesh 打开：{:.3} s（内存映射: {}）",
+        start.elapsed().as_secs_f64(),
+        if mesh.is_mapped() { "是" } else { "否" }
+    );
+
+    let mut world = HittableList::new();
+    world.add(mesh.clone());
+    world.add(Arc::new(Sphere::new(
+        Point3::new(-36.0, height(-36.0, -30.0) + 1.0, -30.0),
+        1.0,
+        Arc::new(Metal::new(Color::new(0.9, 0.9, 0.9), 0.05)),
+    )));
+
+    let camera = Camera::builder()
+        .aspect_ratio(16.0 / 9.0)
+        .image_width(320)
+        .samples_per_pixel(16)
+        .max_depth(4)
+        .vfov(35.0)
+        .lookfrom(Point3::new(-42.0, 14.0, -36.0))
+        .lookat(Point3::new(-36.0, 0.5, -30.0))
+        .seed(1)
+        .build();
+    let start = Instant::now();
+    let fb = camera.render_to_buffer(&world, None);
+    println!(
+        "渲染用时 {:.2} s，驻留 {}/{} 块（估计 {:.1}/{} MB）",
+        start.elapsed().as_secs_f64(),
+        mesh.resident_chunks(),
+        mesh.chunk_count(),
+        mesh.resident_bytes() as f64 / (1 << 20) as f64,
+        MEMORY_BUDGET >> 20
+    );
+
+    let filename = "streaming_mesh.png";
+    match save_framebuffer(&fb, filename, OutputFormat::Png8) {
+        Ok(()) => println!("已保存 {}", filename),
+        Err(e) => eprintln!("保存 {} 失败: {}", filename, e),
+    }
+}
@@ -0,0 +1,97 @@
+//! Loop 细分：低面数网格在导入时细分为光滑曲面
+//!
+//! 运行：`cargo run --release --example subdivision [每像素样本数]`
+//!
+//! 立方体按 glTF 的常见做法每个面使用独立的顶点（24 个顶点、12 个三角形），
+//! 从左到右依次细分 0～3 级，细分在焊接后的拓扑上进行，面与面之间不会开裂。
+//! 最后打印同一网格在不同相机距离下自适应选择的细分级数。输出 subdivision.png。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::geometry::mesh::{MeshData, TriangleMesh};
+use ray_tracing_rust::ray_tracing::geometry::subdivision::{ScreenSizeTarget, loop_subdivide};
+use ray_tracing_rust::ray_tracing::materials::principled::PrincipledMaterial;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::utils::image_compare::side_by_side;
+use std::sync::Arc;
+
+/// 每个面独立顶点的单位立方体（中心在原点）
+fn split_cube() -> MeshData {
+    let mut data = MeshData::default();
+    for axis in 0..3 {
+        for sign in [-1.0, 1.0] {
+            let mut n = Vec3::zeros();
+            n[axis] = sign;
+            let mut u = Vec3::zeros();
+            u[(axis + 1) % 3] = 1.0;
+            let v = sign * n.cross(&u);
+            let base = data.positions.len();
+            for (a, b) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+                data.positions.push(Point3::from(0.5 * (n + a * u + b * v)));
+                data.normals.push(n);
+                data.uvs.push((0.5 * (a + 1.0), 0.5 * (b + 1.0)));
+            }
+            data.indices.push([base, base + 1, base + 2]);
+            data.indices.push([base, base + 2, base + 3]);
+        }
+    }
+    data
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let spp: i32 = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(64);
+
+    let cube = split_cube();
+    let material = Arc::new(
+        PrincipledMaterial::new_constant(Color::new(0.8, 0.25, 0.1), 0.25, 0.0)
+            .with_clearcoat(1.0, 0.9),
+    );
+    let lookfrom = Point3::new(1.6, 1.3, 2.0);
+
+    let frames: Vec<_> = (0..4)
+        .map(|levels| {
+            let data = loop_subdivide(cube.clone(), levels);
+            println!("{} 级细分: {} 个三角形", levels, data.triangle_count());
+            let mut world = HittableList::new();
+            world.add(Arc::new(TriangleMesh::new(&data, material.clone())));
+            world.add(Arc::new(Sphere::new(
+                Point3::new(0.0, -100.6, 0.0),
+                100.0,
+                Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5))),
+            )));
+            Camera::builder()
+                .aspect_ratio(1.0)
+                .image_width(240)
+                .samples_per_pixel(spp)
+                .max_depth(8)
+                .vfov(35.0)
+                .lookfrom(lookfrom)
+                .lookat(Point3::new(0.0, -0.05, 0.0))
+                .background_color(Color::new(0.7, 0.8, 1.0))
+                .seed(1)
+                .build()
+                .render_to_buffer(&world, None)
+        })
+        .collect();
+
+    for distance in [5.0, 30.0, 150.0] {
+        let target = ScreenSizeTarget {
+            lookfrom: Point3::new(0.0, 0.0, distance),
+            vfov: 35.0,
+            image_height: 1080,
+            max_edge_pixels: 8.0,
+            max_levels: 6,
+        };
+        println!(
+            "相机距离 {}：自适应细分 {} 级（1080p，边长不超过 8 像素）",
+            distance,
+            target.levels_for(&cube)
+        );
+    }
+
+    let sheet = side_by_side(&frames.iter().collect::<Vec<_>>());
+    match save_framebuffer(&sheet, "subdivision.png", OutputFormat::Png8) {
+        Ok(()) => println!("已保存 subdivision.png"),
+        Err(e) => eprintln!("保存 subdivision.png 失败: {}", e),
+    }
+}
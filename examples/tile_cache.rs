@@ -0,0 +1,118 @@
+//! 分块缓存的增量重渲染：修改一个球的材质后只重新渲染光线经过它的分块
+//!
+//! 运行：`cargo run --release --example tile_cache [每像素样本数]`
+//!
+//! 依次进行四次渲染并打印耗时和复用的分块数：
+//! 1. 冷缓存，渲染全部分块；
+//! 2. 把中间的球换成金属，只有看到该球（包括地面上的反射和间接光）的分块重新渲染；
+//! 3. 不修改场景再次渲染，全部复用；
+//! 4. 把左侧的球换成玻璃并只渲染其周围的裁剪区域。
+//!
+//! 相机使用固定种子，每个像素的随机数只取决于像素位置，第 2 步的结果与不使用缓存的完整渲染逐像素相同，
+//! 示例会检查这一点。输出 tile_cache_before.png、tile_cache_after.png 和 tile_cache_crop.png。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::rendering::background::VerticalGradient;
+use ray_tracing_rust::ray_tracing::rendering::framebuffer::FrameBuffer;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::rendering::tile_cache::{Crop, TileCache};
+use ray_tracing_rust::ray_tracing::scene::world::{ObjectId, Scene};
+use std::sync::Arc;
+use std::time::Instant;
+
+fn render(scene: &Scene, camera: &Camera, cache: &TileCache, crop: Option<Crop>) -> FrameBuffer {
+    let start = Instant::now();
+    let fb = scene.render_cached(camera, cache, crop);
+    let stats = cache.stats();
+    println!(
+        "  耗时 {:>8.2?}，复用 {} 块，渲染 {} 块，留空 {} 块",
+        start.elapsed(),
+        stats.reused,
+        stats.rendered,
+        stats.skipped
+    );
+    fb
+}
+
+fn save(fb: &FrameBuffer, filename: &str) {
+    if let Err(e) = save_framebuffer(fb, filename, OutputFormat::Png8) {
+        eprintln!("保存 {} 失败: {}", filename, e);
+    }
+}
+
+fn replace_material(scene: &mut Scene, id: ObjectId, mat: Arc<dyn Material>) {
+    let (center, radius) = scene
+        .get(id)
+        .and_then(|object| object.downcast_ref::<Sphere>())
+        .map(|sphere| (sphere.center(0.0), sphere.radius()))
+        .expect("球体");
+    scene.replace(id, Arc::new(Sphere::new(center, radius, mat)));
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let spp: i32 = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(32);
+
+    let mut scene = Scene::new();
+    scene.add(Arc::new(Quad::new(
+        Point3::new(-6.0, -0.5, -6.0),
+        Vec3::new(12.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 12.0),
+        Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5))),
+    )));
+    let spheres: Vec<ObjectId> = (0..3)
+        .map(|i| {
+            scene.add(Arc::new(Sphere::new(
+                Point3::new(1.6 * (i as f64 - 1.0), 0.0, 0.0),
+                0.5,
+                Arc::new(Lambertian::new(Color::new(0.8, 0.3, 0.2))),
+            )))
+        })
+        .collect();
+
+    let camera = Camera::builder()
+        .aspect_ratio(16.0 / 9.0)
+        .image_width(480)
+        .samples_per_pixel(spp)
+        .max_depth(8)
+        .background(Arc::new(VerticalGradient::sky()))
+        .lookfrom(Point3::new(0.0, 0.8, 5.0))
+        .lookat(Point3::new(0.0, 0.0, 0.0))
+        .vfov(40.0)
+        .seed(11)
+        .build();
+    let cache = TileCache::new();
+
+    println!("1. 冷缓存");
+    save(
+        &render(&scene, &camera, &cache, None),
+        "tile_cache_before.png",
+    );
+
+    println!("2. 中间的球换成金属");
+    replace_material(
+        &mut scene,
+        spheres[1],
+        Arc::new(Metal::new(Color::new(0.8, 0.8, 0.9), 0.05)),
+    );
+    let incremental = render(&scene, &camera, &cache, None);
+    save(&incremental, "tile_cache_after.png");
+
+    let full = scene.render_cached(&camera, &TileCache::new(), None);
+    let max_difference = (0..full.height())
+        .flat_map(|y| (0..full.width()).map(move |x| (x, y)))
+        .map(|(x, y)| (full.get(x, y) - incremental.get(x, y)).abs().max())
+        .fold(0.0, f64::max);
+    println!("  与完整渲染的最大像素差: {:e}", max_difference);
+
+    println!("3. 场景不变");
+    render(&scene, &camera, &cache, None);
+
+    println!("4. 左侧的球换成玻璃，只渲染其周围");
+    replace_material(&mut scene, spheres[0], Arc::new(Dielectric::new(1.5)));
+    let crop = Crop::new(40, 80, 200, 240);
+    save(
+        &render(&scene, &camera, &cache, Some(crop)),
+        "tile_cache_crop.png",
+    );
+}
@@ -0,0 +1,161 @@
+//! 透明阴影：阴影光线穿过有色玻璃球，被菲涅尔透过率和 Beer-Lambert 吸收衰减，而不是被完全挡住
+//!
+//! 运行：`cargo run --release --example transparent_shadows [每像素阴影光线数]`
+//!
+//! 输出 transparent_shadows.png（左为完全遮挡的阴影，右为透明阴影的直接光照热度图，使用相同的色阶），
+//! 并打印玻璃球和漫反射球正下方阴影中心的直接光照：漫反射球的阴影两者相同，玻璃球的阴影只在右图中变亮。
+//! 再把光源换成小球面光源，用路径追踪分别关闭和开启相机的透明阴影渲染，输出 transparent_shadows_render.png：
+//! 关闭时玻璃球的阴影只有经折射偶然到达光源的焦散噪点，开启后与上面的热度图一样变亮并带有玻璃的颜色。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::rendering::color::luminance;
+use ray_tracing_rust::ray_tracing::rendering::light_probe::{
+    LightProbeSettings, light_contribution,
+};
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::utils::image_compare::side_by_side;
+use std::sync::Arc;
+
+/// 光源位置，在球的后上方，阴影落向相机一侧
+const LIGHT: Point3 = Point3::new(0.0, 6.0, -3.0);
+
+/// 路径追踪的每像素样本数
+const RENDER_SAMPLES: i32 = 256;
+
+/// 球心为 (x, 1, 0) 的球在地面上的阴影中心所在像素的值
+fn shadow_center(
+    camera: &Camera,
+    world: &dyn Hittable,
+    value: impl Fn(u32, u32) -> f64,
+    x: f64,
+) -> f64 {
+    // 从光源经过球心射到地面：y 从 6 降到 0 需要 1.2 倍的光源到球心距离
+    let target = LIGHT + 1.2 * (Point3::new(x, 1.0, 0.0) - LIGHT);
+    // 在画面中找离目标最近的可见地面点
+    let mut best = (f64::INFINITY, 0.0);
+    for j in 0..camera.image_height() {
+        for i in 0..camera.image_width() {
+            let ray = camera.center_ray(i, j);
+            let mut rec = HitRecord::default();
+            if world.hit(&ray, Interval::new(0.001, f64::INFINITY), &mut rec)
+                && rec.p.y.abs() < 1e-6
+            {
+                let distance = (rec.p - target).norm();
+                if distance < best.0 {
+                    best = (distance, value(i as u32, j as u32));
+                }
+            }
+        }
+    }
+    best.1
+}
+
+fn ground() -> Quad {
+    Quad::new(
+        Point3::new(-5.0, 0.0, -5.0),
+        Vec3::new(10.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 10.0),
+        Arc::new(Lambertian::new(Color::new(0.8, 0.8, 0.8))),
+    )
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let samples: u32 = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(16);
+
+    let mut world = HittableList::new();
+    world.add(Arc::new(ground()));
+    // 穿过直径（2 个单位）后剩下约 (0.3, 0.8, 0.9) 的青色玻璃
+    let glass = Dielectric::new(1.5).with_tint(Color::new(0.3, 0.8, 0.9), 2.0);
+    world.add(Arc::new(Sphere::new(
+        Point3::new(-1.4, 1.0, 0.0),
+        1.0,
+        Arc::new(glass),
+    )));
+    world.add(Arc::new(Sphere::new(
+        Point3::new(1.4, 1.0, 0.0),
+        1.0,
+        Arc::new(Lambertian::new(Color::new(0.7, 0.3, 0.2))),
+    )));
+    let world = Arc::new(BvhNode::new(&world));
+
+    let camera = Camera::builder()
+        .image_width(320)
+        .aspect_ratio(4.0 / 3.0)
+        .vfov(40.0)
+        .lookfrom(Point3::new(0.0, 7.0, 6.0))
+        .lookat(Point3::new(0.0, 0.5, 0.0))
+        .build();
+
+    let settings = LightProbeSettings::default()
+        .with_radius(0.3)
+        .with_shadow_samples(samples);
+    let opaque = light_contribution(
+        &camera,
+        world.as_ref(),
+        LIGHT,
+        &settings.with_transparent_shadows(false),
+    );
+    let transparent = light_contribution(&camera, world.as_ref(), LIGHT, &settings);
+
+    for (name, map) in [("完全遮挡", &opaque), ("透明阴影", &transparent)] {
+        println!(
+            "{}: 平均 {:.4}，玻璃球阴影中心 {:.4}，漫反射球阴影中心 {:.4}",
+            name,
+            map.mean(),
+            shadow_center(&camera, world.as_ref(), |i, j| map.get(i, j), -1.4),
+            shadow_center(&camera, world.as_ref(), |i, j| map.get(i, j), 1.4)
+        );
+    }
+
+    let scale = Some(opaque.max().max(transparent.max()));
+    let combined = side_by_side(&[&opaque.heatmap(scale), &transparent.heatmap(scale)]);
+    match save_framebuffer(&combined, "transparent_shadows.png", OutputFormat::Png8) {
+        Ok(()) => println!("已保存 transparent_shadows.png"),
+        Err(e) => eprintln!("保存失败: {}", e),
+    }
+
+    // 路径追踪：光源换成场景中的小球面光源，同时作为光源列表
+    let light: Arc<dyn Hittable> = Arc::new(Sphere::new(
+        LIGHT,
+        0.3,
+        Arc::new(DiffuseLight::new_color(Color::repeat(300.0))),
+    ));
+    let mut scene = HittableList::new();
+    scene.add(world.clone());
+    scene.add(light.clone());
+    let scene = BvhNode::new(&scene);
+    let mut renders = Vec::new();
+    for (name, transparent) in [("路径追踪", false), ("路径追踪 + 透明阴影", true)] {
+        let camera = Camera::builder()
+            .image_width(320)
+            .aspect_ratio(4.0 / 3.0)
+            .vfov(40.0)
+            .lookfrom(Point3::new(0.0, 7.0, 6.0))
+            .lookat(Point3::new(0.0, 0.5, 0.0))
+            .samples_per_pixel(RENDER_SAMPLES)
+            .max_depth(8)
+            .background_color(Color::zeros())
+            .transparent_shadows(transparent)
+            .seed(1)
+            .build();
+        let fb = camera.render_to_buffer(&scene, Some(light.clone()));
+        let value = |i, j| luminance(&fb.get(i, j));
+        println!(
+            "{}: 玻璃球阴影中心 {:.4}，漫反射球阴影中心 {:.4}",
+            name,
+            shadow_center(&camera, world.as_ref(), value, -1.4),
+            shadow_center(&camera, world.as_ref(), value, 1.4)
+        );
+        renders.push(fb);
+    }
+    let combined = side_by_side(&[&renders[0], &renders[1]]);
+    match save_framebuffer(
+        &combined,
+        "transparent_shadows_render.png",
+        OutputFormat::Png8,
+    ) {
+        Ok(()) => println!("已保存 transparent_shadows_render.png"),
+        Err(e) => eprintln!("保存失败: {}", e),
+    }
+}
@@ -0,0 +1,43 @@
+//! 转台动画：相机绕场景旋转一周，逐帧输出图像
+//!
+//! 运行：`cargo run --release --example turntable [帧数]`
+//! 每帧使用固定的随机种子，重新渲染同一帧得到完全相同的结果。
+//! 需要断点续渲和多进程并行时，请使用 `render-anim` 命令行子命令。
+
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::scenes::cornell_box::{CornellBoxParams, CornellContents, build_cornell_box};
+use std::sync::Arc;
+
+fn main() {
+    let frames: u32 = std::env::args()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(12);
+
+    let params = CornellBoxParams::with_contents(&[CornellContents::Boxes]);
+    let (world, lights) = build_cornell_box(&params);
+    let world = BvhNode::new(&world);
+    let lights: Arc<dyn Hittable> = Arc::new(lights);
+
+    let center = Point3::new(278.0, 278.0, 278.0);
+    let mut camera = Camera::builder()
+        .image_width(200)
+        .samples_per_pixel(32)
+        .max_depth(10)
+        .background_color(Color::zeros())
+        .vfov(40.0)
+        .lookat(center)
+        .orbit(0.0, 10.0, 1400.0)
+        .build();
+
+    std::fs::create_dir_all("turntable").expect("无法创建输出目录");
+    for frame in 0..frames {
+        // 方位角 180° 为盒子开口正前方，左右各摆动 25°
+        let azimuth =
+            180.0 + 25.0 * (2.0 * std::f64::consts::PI * frame as f64 / frames as f64).sin();
+        camera.orbit(azimuth, 10.0, 1400.0);
+        camera.set_seed(Some(frame as u64));
+        camera.set_output_filename(format!("turntable/frame_{:03}.png", frame));
+        camera.render(&world, Some(lights.clone()));
+    }
+}
@@ -0,0 +1,97 @@
+//! UDIM 多瓦片纹理：UV 跨越多个单位方格的网格按瓦片编号查找各自的图像
+//!
+//! 运行：`cargo run --release --example udim [瓦片目录]`
+//!
+//! 在瓦片目录（默认在临时目录）中生成 1001、1002、1011、1012 四张不同颜色的棋盘格图像，
+//! 把它们贴到一块 UV 范围为 [0,2]×[0,2] 的平面网格上，每个瓦片只在第一次被采样时加载。
+//! 输出 udim.png。
+
+use image::{Rgb, RgbImage};
+use ray_tracing_rust::prelude::*;
+use ray_tracing_rust::ray_tracing::geometry::mesh::{MeshData, TriangleMesh};
+use ray_tracing_rust::ray_tracing::materials::texture::udim::UdimTexture;
+use ray_tracing_rust::ray_tracing::rendering::color_space::TextureColorSpace;
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// 瓦片编号及其棋盘格的颜色
+const TILES: [(u32, [u8; 3]); 4] = [
+    (1001, [220, 60, 50]),
+    (1002, [60, 170, 70]),
+    (1011, [50, 90, 210]),
+    (1012, [230, 190, 40]),
+];
+
+/// 生成一张 8×8 格的棋盘格瓦片
+fn tile_image(color: [u8; 3]) -> RgbImage {
+    RgbImage::from_fn(256, 256, |x, y| {
+        if (x / 32 + y / 32) % 2 == 0 {
+            Rgb(color)
+        } else {
+            Rgb([235, 235, 235])
+        }
+    })
+}
+
+fn main() {
+    let dir = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| std::env::temp_dir().join("udim_tiles"));
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        eprintln!("创建 {} 失败: {}", dir.display(), e);
+        std::process::exit(1);
+    }
+    for (tile, color) in TILES {
+        let path = dir.join(format!("checker.{}.png", tile));
+        if let Err(e) = tile_image(color).save(&path) {
+            eprintln!("写入 {} 失败: {}", path.display(), e);
+            std::process::exit(1);
+        }
+    }
+
+    let pattern = dir.join("checker.<UDIM>.png").display().to_string();
+    let texture = Arc::new(UdimTexture::with_color_space(
+        &pattern,
+        TextureColorSpace::Srgb,
+    ));
+
+    // 边长 4 的地面，UV 覆盖 2×2 个瓦片
+    let mut data = MeshData::default();
+    for (x, z, u, v) in [
+        (-2.0, 2.0, 0.0, 0.0),
+        (2.0, 2.0, 2.0, 0.0),
+        (2.0, -2.0, 2.0, 2.0),
+        (-2.0, -2.0, 0.0, 2.0),
+    ] {
+        data.positions.push(Point3::new(x, 0.0, z));
+        data.uvs.push((u, v));
+    }
+    data.indices = vec![[0, 1, 2], [0, 2, 3]];
+
+    let mut world = HittableList::new();
+    world.add(Arc::new(TriangleMesh::new(
+        &data,
+        Arc::new(Lambertian::new_texture(texture.clone())),
+    )));
+
+    let camera = Camera::builder()
+        .aspect_ratio(4.0 / 3.0)
+        .image_width(400)
+        .samples_per_pixel(16)
+        .max_depth(4)
+        .vfov(40.0)
+        .lookfrom(Point3::new(0.0, 5.0, 4.5))
+        .lookat(Point3::new(0.0, 0.0, 0.0))
+        .background_color(Color::new(0.8, 0.8, 0.8))
+        .seed(1)
+        .build();
+    let fb = camera.render_to_buffer(&world, None);
+    println!("已加载的瓦片: {:?}", texture.loaded_tiles());
+
+    match save_framebuffer(&fb, "udim.png", OutputFormat::Png8) {
+        Ok(()) => println!("已保存 udim.png"),
+        Err(e) => eprintln!("保存 udim.png 失败: {}", e),
+    }
+}
@@ -9,5 +9,9 @@
 //! - 体积渲染
 //! - BVH加速结构
 
+//!
+//! 扩展自定义材质、纹理、PDF或几何体时，导入 [`prelude`] 即可获得所需的trait和常用类型。
+
+pub mod prelude;
 pub mod ray_tracing;
 pub mod scenes;
@@ -1,30 +1,309 @@
-mod ray_tracing;
-mod scenes;
-
-use scenes::cornell_box::{CornellBoxConfig, cornell_box_with_glass_sphere};
-use scenes::final_scene::{FinalSceneConfig, final_scene_next_week};
+use ray_tracing_rust::ray_tracing::geometry::curve::CurveType;
+use ray_tracing_rust::ray_tracing::geometry::point_cloud::SplatShape;
+use ray_tracing_rust::ray_tracing::rendering::bake::{BakeMode, BakeSettings};
+use ray_tracing_rust::ray_tracing::rendering::caustics::CausticSettings;
+use ray_tracing_rust::ray_tracing::rendering::denoise::DenoiseSettings;
+use ray_tracing_rust::ray_tracing::rendering::dof_sampling::DofSampling;
+use ray_tracing_rust::ray_tracing::rendering::halt::{HaltCondition, parse_duration};
+use ray_tracing_rust::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use ray_tracing_rust::ray_tracing::rendering::pdf_debug::PdfDebugConfig;
+use ray_tracing_rust::ray_tracing::rendering::probe_grid::ProbeFormat;
+use ray_tracing_rust::ray_tracing::rendering::wireframe::BoundsOverlay;
+use ray_tracing_rust::ray_tracing::utils::config::{self, RendererConfig};
+use ray_tracing_rust::ray_tracing::utils::image_compare::compare_files;
+use ray_tracing_rust::ray_tracing::validation::check::run_all;
+use ray_tracing_rust::scenes::animation::AnimationSpec;
+use ray_tracing_rust::scenes::ao_compare::{AoCompareConfig, CompareLayout, ao_compare_scene};
+use ray_tracing_rust::scenes::cornell_box::{CornellBoxConfig, cornell_box_with_glass_sphere};
+use ray_tracing_rust::scenes::final_scene::{FinalSceneConfig, MAIN_CAMERA, final_scene_next_week};
+use ray_tracing_rust::scenes::furnace::{FurnaceConfig, furnace_materials, render_furnace};
+use ray_tracing_rust::scenes::gltf_scene::{
+    GltfSceneConfig, bake_gltf_lightmap, render_gltf_scene, watch_gltf_scene,
+};
+use ray_tracing_rust::scenes::hair::{HairSceneConfig, render_hair_scene};
+use ray_tracing_rust::scenes::light_probe::{self, LightProbeSceneConfig};
+use ray_tracing_rust::scenes::material_ball::{
+    MaterialBallConfig, MaterialSweep, material_ball_sheet,
+};
+use ray_tracing_rust::scenes::onb_debug::{OnbDebugConfig, onb_debug_scene};
+use ray_tracing_rust::scenes::pdf_debug::{PdfDebugSceneConfig, pdf_debug_scene};
+use ray_tracing_rust::scenes::point_cloud::{PointCloudSceneConfig, render_point_cloud};
+use ray_tracing_rust::scenes::probe_grid::{self, ProbeGridSceneConfig};
+use ray_tracing_rust::scenes::render_farm::FarmManifest;
 use std::env;
+use std::ops::Range;
+use std::process::{Child, Command};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// 读取形如 `--name 值` 的命令行参数，值无法解析时退出
+fn flag_value<T: FromStr>(args: &[String], name: &str) -> Option<T> {
+    let index = args.iter().position(|a| a == name)?;
+    match args.get(index + 1).map(|v| v.parse()) {
+        Some(Ok(value)) => Some(value),
+        _ => {
+            eprintln!("参数 {} 需要一个有效的值", name);
+            std::process::exit(2);
+        }
+    }
+}
+
+/// 取正整数参数，与配置文件一样拒绝0和负数
+fn positive_flag(args: &[String], name: &str) -> Option<i32> {
+    let value = flag_value::<i32>(args, name)?;
+    if value <= 0 {
+        eprintln!("参数 {} 需要一个正整数", name);
+        std::process::exit(2);
+    }
+    Some(value)
+}
+
+/// 解析帧范围：`A..B`（不含B）、`A..=B` 或单个帧号；末帧为 u32::MAX 的闭区间无法表示，视为无效
+fn parse_frame_range(s: &str) -> Option<Range<u32>> {
+    if let Some((start, end)) = s.split_once("..=") {
+        return Some(start.parse().ok()?..end.parse::<u32>().ok()?.checked_add(1)?);
+    }
+    if let Some((start, end)) = s.split_once("..") {
+        return Some(start.parse().ok()?..end.parse().ok()?);
+    }
+    let frame: u32 = s.parse().ok()?;
+    Some(frame..frame.checked_add(1)?)
+}
+
+/// 以最多 jobs 个子进程并行渲染动画帧（每个子进程渲染一帧），返回失败的帧
+fn render_animation_jobs(
+    args: &[String],
+    spec_path: &str,
+    frames: &[u32],
+    jobs: usize,
+) -> Vec<u32> {
+    let exe = env::current_exe().unwrap_or_else(|e| {
+        eprintln!("无法确定可执行文件路径: {}", e);
+        std::process::exit(1);
+    });
+    // 子进程线程数：未指定时平分CPU核数
+    let threads = flag_value::<usize>(args, "--threads").unwrap_or_else(|| {
+        let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+        (cores / jobs).max(1)
+    });
+    let forwarded: Vec<&String> = [
+        "--spp",
+        "--max-depth",
+        "--termination",
+        "--denoise",
+        "--caustics",
+        "--projection",
+        "--dof-sampling",
+        "--shutter",
+        "--time-budget",
+        "--target-noise",
+        "--max-spp",
+        "--output-dir",
+    ]
+    .iter()
+    .filter_map(|name| args.iter().position(|a| a == name))
+    .flat_map(|index| &args[index..(index + 2).min(args.len())])
+    .chain(args.iter().filter(|a| *a == "--report"))
+    .collect();
+
+    let mut queue = frames.iter().copied();
+    let mut running: Vec<(u32, Child)> = Vec::new();
+    let mut failed = Vec::new();
+    let mut finished = 0;
+    loop {
+        while running.len() < jobs
+            && let Some(frame) = queue.next()
+        {
+            let child = Command::new(&exe)
+                .arg("render-anim")
+                .arg(spec_path)
+                .args(["--frame".to_string(), frame.to_string()])
+                .args(["--threads".to_string(), threads.to_string()])
+                .args(&forwarded)
+                .spawn();
+            match child {
+                Ok(child) => running.push((frame, child)),
+                Err(e) => {
+                    eprintln!("启动第{}帧的渲染进程失败: {}", frame, e);
+                    failed.push(frame);
+                }
+            }
+        }
+        if running.is_empty() {
+            break;
+        }
+
+        let mut index = 0;
+        while index < running.len() {
+            match running[index].1.try_wait() {
+                Ok(None) => index += 1,
+                result => {
+                    let (frame, _) = running.swap_remove(index);
+                    finished += 1;
+                    if matches!(result, Ok(Some(status)) if status.success()) {
+                        eprintln!("[{}/{}] 第{}帧完成", finished, frames.len(), frame);
+                    } else {
+                        eprintln!("[{}/{}] 第{}帧失败", finished, frames.len(), frame);
+                        failed.push(frame);
+                    }
+                }
+            }
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    failed.sort_unstable();
+    failed
+}
+
+/// 加载任务清单及其动画描述，出错时退出
+fn load_farm_manifest(path: &std::path::Path) -> (FarmManifest, AnimationSpec) {
+    let manifest = FarmManifest::load(path).unwrap_or_else(|e| {
+        eprintln!("读取任务清单时出错: {}", e);
+        std::process::exit(1);
+    });
+    let spec = manifest.animation().unwrap_or_else(|e| {
+        eprintln!("读取动画描述时出错: {}", e);
+        std::process::exit(1);
+    });
+    (manifest, spec)
+}
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
+    // 全局默认配置（raytracer.toml），命令行参数优先
+    let mut renderer_config = RendererConfig::load_default().unwrap_or_else(|e| {
+        eprintln!("读取配置文件时出错: {}", e);
+        std::process::exit(1);
+    });
+    if let Some(spp) = positive_flag(&args, "--spp") {
+        renderer_config.samples_per_pixel = Some(spp);
+    }
+    if let Some(depth) = positive_flag(&args, "--max-depth") {
+        renderer_config.max_depth = Some(depth);
+    }
+    if let Some(threads) = flag_value(&args, "--threads") {
+        renderer_config.threads = Some(threads);
+    }
+    if let Some(dir) = flag_value::<String>(&args, "--output-dir") {
+        renderer_config.output_dir = Some(dir.into());
+    }
+    if let Some(space) = flag_value(&args, "--color-space") {
+        renderer_config.output_color_space = space;
+    }
+    if let Some(policy) = flag_value(&args, "--termination") {
+        renderer_config.termination = policy;
+    }
+    if let Some(strength) = flag_value::<f64>(&args, "--denoise") {
+        renderer_config.denoise =
+            (strength > 0.0).then(|| DenoiseSettings::with_strength(strength));
+    }
+    if let Some(photons) = flag_value::<usize>(&args, "--caustics") {
+        renderer_config.caustics = (photons > 0).then(|| CausticSettings::with_photons(photons));
+    }
+    if let Some(projection) = flag_value(&args, "--projection") {
+        renderer_config.projection = projection;
+    }
+    if let Some(weight) = flag_value::<f64>(&args, "--dof-sampling") {
+        renderer_config.dof_sampling = (weight > 0.0).then(|| DofSampling::with_weight(weight));
+    }
+    if let Some(shutter) = flag_value(&args, "--shutter") {
+        renderer_config.shutter = shutter;
+    }
+    if args.iter().any(|a| a == "--report") {
+        renderer_config.report = true;
+    }
+    if let Some(budget) = flag_value::<String>(&args, "--time-budget") {
+        let budget = parse_duration(&budget).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        });
+        renderer_config
+            .halt
+            .get_or_insert_with(HaltCondition::default)
+            .time_budget = Some(budget);
+    }
+    if let Some(target) = flag_value::<f64>(&args, "--target-noise") {
+        renderer_config
+            .halt
+            .get_or_insert_with(HaltCondition::default)
+            .target_noise = Some(target.max(0.0));
+    }
+    if let Some(max) = flag_value::<u32>(&args, "--max-spp") {
+        renderer_config
+            .halt
+            .get_or_insert_with(HaltCondition::default)
+            .max_samples = Some(max.max(1));
+    }
+
+    if let Some(threads) = renderer_config.threads
+        && let Err(e) = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+    {
+        eprintln!("设置线程数失败: {}", e);
+    }
+    if let Some(dir) = &renderer_config.output_dir
+        && let Err(e) = std::fs::create_dir_all(dir)
+    {
+        eprintln!("创建输出目录 {} 失败: {}", dir.display(), e);
+        std::process::exit(1);
+    }
+    config::install(renderer_config.clone());
+    let spp = |default: i32| renderer_config.samples_per_pixel.unwrap_or(default);
+    let depth = |default: i32| renderer_config.max_depth.unwrap_or(default);
+
+    // 调试选项：--bounds 叠加叶子包围盒，--bounds-all 叠加全部BVH节点
+    let bounds_overlay = if args.iter().any(|a| a == "--bounds-all") {
+        BoundsOverlay::All
+    } else if args.iter().any(|a| a == "--bounds") {
+        BoundsOverlay::Leaves
+    } else {
+        BoundsOverlay::Off
+    };
+
+    // 透明背景：直接看到背景的像素输出为透明（PNG带alpha通道）
+    let transparent_background = args.iter().any(|a| a == "--transparent");
+
+    // 辐照度缓存预览（仅康奈尔盒场景）
+    let irradiance_cache = args.iter().any(|a| a == "--irradiance-cache");
+
+    // 渲染统计：按材质报告着色次数和反弹深度，用于找出开销最大的物体
+    let stats = args.iter().any(|a| a == "--stats");
+
+    // 最终场景的相机：--camera NAME 只渲染指定相机，--camera all 渲染全部相机
+    let final_camera = match flag_value::<String>(&args, "--camera") {
+        Some(name) if name == "all" => None,
+        Some(name) => Some(name),
+        None => Some(MAIN_CAMERA.to_string()),
+    };
+
     // 根据命令行参数选择场景
     match args.get(1).map(String::as_str) {
         Some("cornell") => {
             let config = CornellBoxConfig {
                 image_width: 600,
-                samples_per_pixel: 1000,
-                max_depth: 50,
-                output_filename: "cornell_box_glass.png".to_string(),
+                samples_per_pixel: spp(1000),
+                max_depth: depth(50),
+                output_filename: renderer_config.output_path("cornell_box_glass.png"),
+                bounds_overlay,
+                transparent_background,
+                irradiance_cache,
+                stats,
             };
             cornell_box_with_glass_sphere(config);
         }
         Some("final") => {
             let config = FinalSceneConfig {
                 image_width: 800,
-                samples_per_pixel: 5000,
-                max_depth: 75,
-                output_filename: "final_scene.png".to_string(),
+                samples_per_pixel: spp(5000),
+                max_depth: depth(75),
+                output_filename: renderer_config.output_path("final_scene.png"),
+                bounds_overlay,
+                transparent_background,
+                stats,
+                camera: final_camera.clone(),
             };
             final_scene_next_week(config);
         }
@@ -32,17 +311,780 @@ fn main() {
             // 快速测试版本
             let config = FinalSceneConfig {
                 image_width: 400,
-                samples_per_pixel: 100,
-                max_depth: 20,
-                output_filename: "quick_test.png".to_string(),
+                samples_per_pixel: spp(100),
+                max_depth: depth(20),
+                output_filename: renderer_config.output_path("quick_test.png"),
+                bounds_overlay,
+                transparent_background,
+                stats,
+                camera: final_camera,
             };
             final_scene_next_week(config);
         }
+        Some("furnace") => {
+            // 白炉测试：验证材质能量守恒
+            let config = FurnaceConfig {
+                samples_per_pixel: spp(256),
+                max_depth: depth(50),
+                output_filename: renderer_config.output_path("furnace.png"),
+                ..FurnaceConfig::default()
+            };
+            render_furnace(config);
+        }
+        Some("material-balls") => {
+            // 在相同的影棚布光下并排渲染白炉测试中的全部材质
+            let materials = furnace_materials();
+            let config = MaterialBallConfig {
+                samples_per_pixel: spp(256),
+                max_depth: depth(16),
+                ..MaterialBallConfig::default()
+            };
+            let sheet = material_ball_sheet(
+                &materials
+                    .iter()
+                    .map(|m| m.material.clone())
+                    .collect::<Vec<_>>(),
+                &config,
+            );
+            let filename = renderer_config.output_path("material_balls.png");
+            match save_framebuffer(&sheet, &filename, OutputFormat::from_filename(&filename)) {
+                Ok(()) => println!(
+                    "已保存 {}（从左到右: {}）",
+                    filename,
+                    materials
+                        .iter()
+                        .map(|m| m.name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                Err(e) => {
+                    eprintln!("保存 {} 时出错: {}", filename, e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some("material-sweep") => {
+            // 两个参数的网格扫描，每个格子是一个测试球
+            let sweep: MaterialSweep = match args.get(2).filter(|a| !a.starts_with("--")) {
+                Some(name) => name.parse().unwrap_or_else(|e| {
+                    eprintln!("{}", e);
+                    std::process::exit(2);
+                }),
+                None => Default::default(),
+            };
+            let rows = flag_value(&args, "--rows").unwrap_or(4usize).max(1);
+            let columns = flag_value(&args, "--cols").unwrap_or(5usize).max(1);
+            let config = MaterialBallConfig {
+                image_width: 160,
+                samples_per_pixel: spp(128),
+                max_depth: depth(16),
+                ..MaterialBallConfig::default()
+            };
+            let sheet = sweep.render(rows, columns, &config);
+            let (row_name, column_name) = sweep.axis_names();
+            let (row_values, column_values) = sweep.values(rows, columns);
+            let format_values = |values: &[f64]| {
+                values
+                    .iter()
+                    .map(|v| format!("{:.3}", v))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            let filename = renderer_config.output_path("material_sweep.png");
+            match save_framebuffer(&sheet, &filename, OutputFormat::from_filename(&filename)) {
+                Ok(()) => {
+                    println!("已保存 {}", filename);
+                    println!(
+                        "  行（从上到下）{}: {}",
+                        row_name,
+                        format_values(&row_values)
+                    );
+                    println!(
+                        "  列（从左到右）{}: {}",
+                        column_name,
+                        format_values(&column_values)
+                    );
+                }
+                Err(e) => {
+                    eprintln!("保存 {} 时出错: {}", filename, e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some("hair") => {
+            // 毛球场景：数万根贝塞尔曲线毛发
+            let config = HairSceneConfig {
+                samples_per_pixel: spp(64),
+                max_depth: depth(12),
+                output_filename: renderer_config.output_path("hair.png"),
+                strands: flag_value(&args, "--strands").unwrap_or(30000),
+                curve_type: if args.iter().any(|a| a == "--flat") {
+                    CurveType::Flat
+                } else {
+                    CurveType::Cylinder
+                },
+                bounds_overlay,
+                transparent_background,
+                stats,
+                ..HairSceneConfig::default()
+            };
+            render_hair_scene(config);
+        }
+        Some("points") => {
+            // 渲染点云文件（.xyz/.ply）
+            let Some(path) = args.get(2).filter(|a| !a.starts_with("--")) else {
+                eprintln!(
+                    "用法: {} points <文件.xyz|文件.ply> [--radius R] [--spheres]",
+                    args[0]
+                );
+                std::process::exit(2);
+            };
+            let config = PointCloudSceneConfig {
+                samples_per_pixel: spp(64),
+                max_depth: depth(10),
+                output_filename: renderer_config.output_path("point_cloud.png"),
+                radius: flag_value(&args, "--radius"),
+                shape: if args.iter().any(|a| a == "--spheres") {
+                    SplatShape::Sphere
+                } else {
+                    SplatShape::Disk
+                },
+                bounds_overlay,
+                transparent_background,
+                stats,
+                ..PointCloudSceneConfig::default()
+            };
+            if let Err(e) = render_point_cloud(path, config) {
+                eprintln!("渲染点云时出错: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some("gltf") => {
+            // 导入并渲染 glTF 2.0 场景（.gltf 或 .glb）
+            let Some(path) = args.get(2).filter(|a| !a.starts_with("--")) else {
+                eprintln!(
+                    "用法: {} gltf <文件.gltf|文件.glb> [--subdivide N] [--subdivide-pixels PX]",
+                    args[0]
+                );
+                std::process::exit(2);
+            };
+            // 指定像素数时按屏幕尺寸自适应细分，--subdivide 为最大级数
+            let subdivision_edge_pixels = flag_value(&args, "--subdivide-pixels");
+            let default_levels = if subdivision_edge_pixels.is_some() {
+                4
+            } else {
+                0
+            };
+            let config = GltfSceneConfig {
+                samples_per_pixel: spp(256),
+                max_depth: depth(30),
+                output_filename: renderer_config.output_path("gltf_scene.png"),
+                bounds_overlay,
+                transparent_background,
+                stats,
+                subdivision: flag_value(&args, "--subdivide").unwrap_or(default_levels),
+                subdivision_edge_pixels,
+                ..GltfSceneConfig::default()
+            };
+            if let Err(e) = render_gltf_scene(path, config) {
+                eprintln!("渲染 glTF 场景时出错: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some("watch") => {
+            // 监视 glTF 场景文件，修改后自动重新渐进渲染
+            let Some(path) = args.get(2).filter(|a| !a.starts_with("--")) else {
+                eprintln!("用法: {} watch <文件.gltf|文件.glb>", args[0]);
+                std::process::exit(2);
+            };
+            let config = GltfSceneConfig {
+                image_width: 400,
+                samples_per_pixel: spp(64),
+                max_depth: depth(10),
+                output_filename: renderer_config.output_path("watch_preview.png"),
+                transparent_background,
+                ..GltfSceneConfig::default()
+            };
+            if let Err(e) = watch_gltf_scene(path, config) {
+                eprintln!("监视 glTF 场景时出错: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some("bake") => {
+            // 烘焙 glTF 场景中某个物体的光照贴图
+            let Some(path) = args.get(2).filter(|a| !a.starts_with("--")) else {
+                eprintln!(
+                    "用法: {} bake <文件.gltf|文件.glb> [--object N] [--size N] [--radiance] [--hdr]",
+                    args[0]
+                );
+                std::process::exit(2);
+            };
+            let size = flag_value(&args, "--size").unwrap_or(512);
+            let settings = BakeSettings {
+                width: size,
+                height: size,
+                samples_per_texel: spp(64) as u32,
+                mode: if args.iter().any(|a| a == "--radiance") {
+                    BakeMode::Radiance
+                } else {
+                    BakeMode::Irradiance
+                },
+                ..BakeSettings::default()
+            };
+            let filename = if args.iter().any(|a| a == "--hdr") {
+                "lightmap.pfm"
+            } else {
+                "lightmap.png"
+            };
+            let config = GltfSceneConfig {
+                max_depth: depth(30),
+                output_filename: renderer_config.output_path(filename),
+                ..GltfSceneConfig::default()
+            };
+            let object = flag_value(&args, "--object").unwrap_or(0);
+            if let Err(e) = bake_gltf_lightmap(path, object, &config, &settings) {
+                eprintln!("烘焙光照贴图时出错: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some("ao-compare") => {
+            // 同一场景的环境光遮蔽与全局光照对比
+            let scene = match args.get(2).filter(|a| !a.starts_with("--")) {
+                Some(name) => name.parse().unwrap_or_else(|e| {
+                    eprintln!("{}", e);
+                    std::process::exit(2);
+                }),
+                None => Default::default(),
+            };
+            let config = AoCompareConfig {
+                scene,
+                samples_per_pixel: spp(256),
+                max_depth: depth(30),
+                ao_distance: flag_value(&args, "--ao-distance"),
+                ao_samples: flag_value(&args, "--ao-samples").unwrap_or(4),
+                layout: if args.iter().any(|a| a == "--diff") {
+                    CompareLayout::Difference
+                } else {
+                    CompareLayout::SideBySide
+                },
+                output_filename: renderer_config.output_path("ao_compare.png"),
+                ..AoCompareConfig::default()
+            };
+            match ao_compare_scene(config) {
+                Ok(metrics) => println!("AO 与全局光照（亮度对齐后）: {}", metrics),
+                Err(e) => {
+                    eprintln!("生成 AO 对比图时出错: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some("onb-debug") => {
+            // 按交点处的正交基底着色，检查翻转或退化的基底
+            let scene = match args.get(2).filter(|a| !a.starts_with("--")) {
+                Some(name) => name.parse().unwrap_or_else(|e| {
+                    eprintln!("{}", e);
+                    std::process::exit(2);
+                }),
+                None => Default::default(),
+            };
+            let config = OnbDebugConfig {
+                scene,
+                output_filename: renderer_config.output_path("onb_debug.png"),
+                ..OnbDebugConfig::default()
+            };
+            match onb_debug_scene(&config) {
+                Ok((hits, issues)) => {
+                    println!("检查了 {} 个像素的交点", hits);
+                    for (issue, count) in &issues {
+                        println!("  {}: {}", issue, count);
+                    }
+                    if issues.iter().any(|(_, count)| *count > 0) {
+                        std::process::exit(1);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("生成基底调试图时出错: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some("light-probe") => {
+            // 比较候选光源位置的直接光照贡献，帮助在大场景中摆放光源
+            let positions: Vec<_> = args[2..]
+                .iter()
+                .take_while(|a| !a.starts_with("--"))
+                .map(|a| light_probe::parse_position(a))
+                .collect::<Result<_, _>>()
+                .unwrap_or_else(|e| {
+                    eprintln!("{}", e);
+                    std::process::exit(2);
+                });
+            if positions.is_empty() {
+                eprintln!(
+                    "用法: {} light-probe <x,y,z>... [--scene cornell|final] [--radius R] [--samples N] [--opaque-shadows]",
+                    args[0]
+                );
+                std::process::exit(2);
+            }
+            let defaults = LightProbeSceneConfig::default();
+            let config = LightProbeSceneConfig {
+                scene: flag_value::<String>(&args, "--scene")
+                    .map(|name| {
+                        name.parse().unwrap_or_else(|e| {
+                            eprintln!("{}", e);
+                            std::process::exit(2);
+                        })
+                    })
+                    .unwrap_or_default(),
+                positions,
+                settings: defaults
+                    .settings
+                    .with_radius(flag_value(&args, "--radius").unwrap_or(0.0))
+                    .with_shadow_samples(flag_value(&args, "--samples").unwrap_or(4))
+                    .with_transparent_shadows(!args.iter().any(|a| a == "--opaque-shadows")),
+                output_filename: renderer_config.output_path("light_probe.png"),
+                ..defaults
+            };
+            match light_probe::light_probe_scene(&config) {
+                Ok(ranked) => {
+                    println!("候选光源位置（按平均直接光贡献排序）:");
+                    for (rank, contribution) in ranked.iter().enumerate() {
+                        println!("  {}. {}", rank + 1, contribution);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("光源摆放预估时出错: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some("probe-grid") => {
+            // 烘焙辐照度探针网格（二阶球谐系数），导出给实时引擎
+            let or_exit = |result: Result<_, String>| {
+                result.unwrap_or_else(|e| {
+                    eprintln!("{}", e);
+                    std::process::exit(2);
+                })
+            };
+            let scene = flag_value::<String>(&args, "--scene")
+                .map(|name| {
+                    name.parse().unwrap_or_else(|e| {
+                        eprintln!("{}", e);
+                        std::process::exit(2);
+                    })
+                })
+                .unwrap_or_default();
+            let min =
+                flag_value::<String>(&args, "--min").map(|s| or_exit(probe_grid::parse_corner(&s)));
+            let max =
+                flag_value::<String>(&args, "--max").map(|s| or_exit(probe_grid::parse_corner(&s)));
+            let bounds = match (min, max) {
+                (Some(min), Some(max)) => Some((min, max)),
+                (None, None) => None,
+                _ => {
+                    eprintln!("--min 和 --max 需要同时给出");
+                    std::process::exit(2);
+                }
+            };
+            let binary = args.iter().any(|a| a == "--binary");
+            let defaults = ProbeGridSceneConfig::default();
+            let config = ProbeGridSceneConfig {
+                scene,
+                bounds,
+                resolution: flag_value::<String>(&args, "--probes")
+                    .map(|s| {
+                        probe_grid::parse_resolution(&s).unwrap_or_else(|e| {
+                            eprintln!("{}", e);
+                            std::process::exit(2);
+                        })
+                    })
+                    .unwrap_or(defaults.resolution),
+                samples_per_probe: flag_value(&args, "--samples")
+                    .unwrap_or(defaults.samples_per_probe),
+                max_depth: depth(defaults.max_depth),
+                format: if binary {
+                    ProbeFormat::Binary
+                } else {
+                    ProbeFormat::Json
+                },
+                output_filename: renderer_config.output_path(if binary {
+                    "probes.bin"
+                } else {
+                    "probes.json"
+                }),
+                ..defaults
+            };
+            if let Err(e) = probe_grid::probe_grid_scene(&config) {
+                eprintln!("烘焙探针网格时出错: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some("render-anim") => {
+            // 批量渲染相机动画，已存在的帧文件视为完成的检查点
+            let Some(spec_path) = args.get(2).filter(|a| !a.starts_with("--")) else {
+                eprintln!(
+                    "用法: {} render-anim <动画描述> [--frames A..B] [--jobs N] [--force]",
+                    args[0]
+                );
+                std::process::exit(2);
+            };
+            let mut spec = AnimationSpec::load(spec_path).unwrap_or_else(|e| {
+                eprintln!("读取动画描述时出错: {}", e);
+                std::process::exit(1);
+            });
+            if let Some(spp) = flag_value(&args, "--spp") {
+                spec.samples_per_pixel = Some(spp);
+            }
+            if let Some(depth) = flag_value(&args, "--max-depth") {
+                spec.max_depth = Some(depth);
+            }
+
+            // 子进程：只渲染一帧
+            if let Some(frame) = flag_value::<u32>(&args, "--frame") {
+                match spec.render_frame(frame) {
+                    Ok(path) => eprintln!("第{}帧已保存为 {}", frame, path.display()),
+                    Err(e) => {
+                        eprintln!("渲染第{}帧时出错: {}", frame, e);
+                        std::process::exit(1);
+                    }
+                }
+                return;
+            }
+
+            let range = match flag_value::<String>(&args, "--frames") {
+                Some(s) => parse_frame_range(&s).unwrap_or_else(|| {
+                    eprintln!("无效的帧范围: {}", s);
+                    std::process::exit(2);
+                }),
+                None => 0..spec.frames,
+            };
+            let range = range.start..range.end.min(spec.frames);
+            let frames: Vec<u32> = if args.iter().any(|a| a == "--force") {
+                range.clone().collect()
+            } else {
+                spec.pending_frames(range.clone())
+            };
+            eprintln!(
+                "动画帧 {}..{}: 需要渲染 {} 帧，跳过 {} 帧已存在的输出",
+                range.start,
+                range.end,
+                frames.len(),
+                range.len() - frames.len()
+            );
+
+            let jobs = flag_value::<usize>(&args, "--jobs").unwrap_or(1).max(1);
+            let failed = render_animation_jobs(&args, spec_path, &frames, jobs);
+            if !failed.is_empty() {
+                eprintln!("以下帧渲染失败: {:?}", failed);
+                std::process::exit(1);
+            }
+        }
+        Some("farm-export") => {
+            // 把动画切分为任务清单，分发到多台机器渲染
+            let Some(spec_path) = args.get(2).filter(|a| !a.starts_with("--")) else {
+                eprintln!(
+                    "用法: {} farm-export <动画描述> [--frames A..B] [--frames-per-job N] [--bands N] [--manifest 文件]",
+                    args[0]
+                );
+                std::process::exit(2);
+            };
+            let mut spec = AnimationSpec::load(spec_path).unwrap_or_else(|e| {
+                eprintln!("读取动画描述时出错: {}", e);
+                std::process::exit(1);
+            });
+            if let Some(spp) = flag_value(&args, "--spp") {
+                spec.samples_per_pixel = Some(spp);
+            }
+            if let Some(depth) = flag_value(&args, "--max-depth") {
+                spec.max_depth = Some(depth);
+            }
+            let range = match flag_value::<String>(&args, "--frames") {
+                Some(s) => parse_frame_range(&s).unwrap_or_else(|| {
+                    eprintln!("无效的帧范围: {}", s);
+                    std::process::exit(2);
+                }),
+                None => 0..spec.frames,
+            };
+            let frames_per_job = flag_value(&args, "--frames-per-job").unwrap_or(1);
+            let bands = flag_value(&args, "--bands").unwrap_or(1);
+            if bands > 1 && renderer_config.denoise.is_some() {
+                eprintln!("警告: 分带渲染时不进行降噪");
+            }
+            let manifest_path = flag_value::<String>(&args, "--manifest").unwrap_or_else(|| {
+                std::path::Path::new(spec_path)
+                    .with_extension("farm.json")
+                    .to_string_lossy()
+                    .into_owned()
+            });
+            let manifest = FarmManifest::plan(spec_path, &spec, range, frames_per_job, bands)
+                .unwrap_or_else(|e| {
+                    eprintln!("规划任务时出错: {}", e);
+                    std::process::exit(1);
+                });
+            if let Err(e) = manifest.save(&manifest_path) {
+                eprintln!("写入任务清单 {} 失败: {}", manifest_path, e);
+                std::process::exit(1);
+            }
+            println!(
+                "已写出 {}：帧 {}..{}，{} 个任务（每任务最多 {} 帧，每帧 {} 个行带）",
+                manifest_path,
+                manifest.frames.start,
+                manifest.frames.end,
+                manifest.jobs.len(),
+                frames_per_job.max(1),
+                manifest.bands
+            );
+            println!(
+                "在每台机器上运行: {} farm-worker {}",
+                args[0], manifest_path
+            );
+            if manifest.bands > 1 {
+                println!("全部完成后拼合: {} farm-merge {}", args[0], manifest_path);
+            }
+        }
+        Some("farm-worker") => {
+            // 认领并渲染任务清单中尚未完成的任务
+            let Some(manifest_path) = args.get(2).filter(|a| !a.starts_with("--")) else {
+                eprintln!("用法: {} farm-worker <任务清单> [--job ID]", args[0]);
+                std::process::exit(2);
+            };
+            let manifest_path = std::path::Path::new(manifest_path);
+            let (manifest, spec) = load_farm_manifest(manifest_path);
+            let only = flag_value::<usize>(&args, "--job");
+            if only.is_some_and(|id| manifest.jobs.iter().all(|job| job.id != id)) {
+                eprintln!("清单中没有任务 {}", only.unwrap_or_default());
+                std::process::exit(2);
+            }
+            let mut failed = Vec::new();
+            for job in &manifest.jobs {
+                if only.is_some_and(|id| id != job.id) {
+                    continue;
+                }
+                // 指定任务时不经过认领，由外部调度器保证不重复运行
+                if only.is_none()
+                    && (manifest.is_done(&spec, job)
+                        || !FarmManifest::claim(manifest_path, job).unwrap_or_else(|e| {
+                            eprintln!("认领任务时出错: {}", e);
+                            std::process::exit(1);
+                        }))
+                {
+                    continue;
+                }
+                eprintln!(
+                    "任务 {}：帧 {}..{}，行 {}..{}",
+                    job.id, job.frames.start, job.frames.end, job.rows.start, job.rows.end
+                );
+                match manifest.run_job(&spec, job) {
+                    Ok(rendered) => eprintln!("任务 {} 完成，渲染了 {} 帧", job.id, rendered),
+                    Err(e) => {
+                        eprintln!("任务 {} 失败: {}", job.id, e);
+                        if only.is_none() {
+                            let _ = FarmManifest::release(manifest_path, job);
+                        }
+                        failed.push(job.id);
+                    }
+                }
+            }
+            if !failed.is_empty() {
+                eprintln!("以下任务失败: {:?}", failed);
+                std::process::exit(1);
+            }
+        }
+        Some("farm-merge") => {
+            // 把分带渲染的行带拼合为完整的帧
+            let Some(manifest_path) = args.get(2).filter(|a| !a.starts_with("--")) else {
+                eprintln!("用法: {} farm-merge <任务清单> [--keep-bands]", args[0]);
+                std::process::exit(2);
+            };
+            let (manifest, spec) = load_farm_manifest(std::path::Path::new(manifest_path));
+            let keep_bands = args.iter().any(|a| a == "--keep-bands");
+            let summary = manifest.merge(&spec, keep_bands).unwrap_or_else(|e| {
+                eprintln!("拼合时出错: {}", e);
+                std::process::exit(1);
+            });
+            println!(
+                "拼合 {} 帧，此前已完成 {} 帧，未完成 {} 帧",
+                summary.merged.len(),
+                summary.complete,
+                summary.incomplete.len()
+            );
+            for (frame, missing) in &summary.incomplete {
+                if missing.is_empty() {
+                    println!("  第{}帧尚未渲染", frame);
+                } else {
+                    println!("  第{}帧缺少行带 {:?}", frame, missing);
+                }
+            }
+            if !summary.incomplete.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Some("pdf-debug") => {
+            // 检查某个像素处材质的散射采样
+            let (Some(x), Some(y)) = (
+                args.get(2).and_then(|a| a.parse().ok()),
+                args.get(3).and_then(|a| a.parse().ok()),
+            ) else {
+                eprintln!(
+                    "用法: {} pdf-debug <x> <y> [cornell|final] [--samples N] [--lights]",
+                    args[0]
+                );
+                std::process::exit(2);
+            };
+            let scene = match args.get(4).filter(|a| !a.starts_with("--")) {
+                Some(name) => name.parse().unwrap_or_else(|e| {
+                    eprintln!("{}", e);
+                    std::process::exit(2);
+                }),
+                None => Default::default(),
+            };
+            let defaults = PdfDebugSceneConfig::default();
+            let config = PdfDebugSceneConfig {
+                scene,
+                pixel: (x, y),
+                light_sampling: args.iter().any(|a| a == "--lights"),
+                debug: PdfDebugConfig {
+                    samples: flag_value(&args, "--samples").unwrap_or(defaults.debug.samples),
+                    ..defaults.debug.clone()
+                },
+                csv_filename: renderer_config.output_path("pdf_debug.csv"),
+                image_filename: renderer_config.output_path("pdf_debug.png"),
+                ..defaults
+            };
+            match pdf_debug_scene(&config) {
+                Ok(report) => println!("{}", report),
+                Err(e) => {
+                    eprintln!("PDF调试时出错: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some("compare") => {
+            // 比较两幅渲染结果
+            let (Some(a), Some(b)) = (args.get(2), args.get(3)) else {
+                eprintln!("用法: {} compare <图像A> <图像B>", args[0]);
+                std::process::exit(2);
+            };
+            match compare_files(a, b) {
+                Ok(metrics) => println!("{}", metrics),
+                Err(e) => {
+                    eprintln!("比较图像时出错: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some("validate") => {
+            // 运行渲染器自检
+            let results = run_all();
+            for result in &results {
+                println!("{}", result);
+            }
+            if results.iter().any(|r| !r.passed) {
+                std::process::exit(1);
+            }
+        }
         _ => {
-            eprintln!("用法: {} [cornell|final|texture|quick]", args[0]);
+            eprintln!(
+                "用法: {} [cornell|final|texture|quick|furnace|material-balls|material-sweep|hair|points|gltf|watch|bake|ao-compare|pdf-debug|onb-debug|light-probe|probe-grid|render-anim|farm-export|farm-worker|farm-merge|compare|validate]",
+                args[0]
+            );
             eprintln!("  cornell - 康奈尔盒子场景");
             eprintln!("  final   - 最终复杂场景");
             eprintln!("  quick   - 快速测试场景");
+            eprintln!("  furnace - 白炉测试场景（反照率为1的材质置于均匀环境光中）");
+            eprintln!("  material-balls - 在相同的影棚布光下并排渲染各材质的测试球");
+            eprintln!(
+                "  material-sweep [coat|glass|noise|principled] - 两个材质参数的网格扫描印样（--rows N 行数, --cols N 列数）"
+            );
+            eprintln!(
+                "  hair    - 毛球场景（贝塞尔曲线毛发，--strands N 毛发根数, --flat 扁平条带）"
+            );
+            eprintln!(
+                "  points <文件> - 渲染点云（.xyz/.ply，--radius R 点半径, --spheres 绘制为球体）"
+            );
+            eprintln!(
+                "  gltf <文件> [--subdivide N] [--subdivide-pixels PX] - 导入并渲染 glTF 2.0 场景（.gltf/.glb），可细分低面数网格"
+            );
+            eprintln!("  watch <文件> - 监视 glTF 场景文件，修改后自动重新渐进渲染预览");
+            eprintln!("  bake <文件> - 烘焙 glTF 场景中物体的光照贴图（UV 空间）");
+            eprintln!(
+                "               --object N 物体序号, --size N 贴图尺寸, --radiance 烘焙出射辐射度, --hdr 输出 PFM"
+            );
+            eprintln!(
+                "  ao-compare [cornell|final] - 同一场景的环境光遮蔽与全局光照并排对比（AO | GI | 差异）"
+            );
+            eprintln!(
+                "               --ao-distance D 遮挡距离, --ao-samples N 每样本遮挡光线数, --diff 只输出差异图"
+            );
+            eprintln!(
+                "  pdf-debug <x> <y> [cornell|final] - 导出像素首个交点处的散射方向和PDF（CSV + 经纬度密度图）"
+            );
+            eprintln!("               --samples N 样本数, --lights 与渲染一样混合光源采样");
+            eprintln!(
+                "  onb-debug [cornell|final] - 按交点处的正交基底着色（u | v | w | 检查），统计翻转或退化的基底"
+            );
+            eprintln!("  render-anim <动画描述> - 批量渲染相机动画（跳过已存在的帧）");
+            eprintln!(
+                "               --frames A..B 帧范围, --jobs N 并行进程数, --force 重新渲染全部帧"
+            );
+            eprintln!("  farm-export <动画描述> - 把动画切分为分布式渲染的任务清单（JSON）");
+            eprintln!(
+                "               --frames A..B 帧范围, --frames-per-job N 每任务帧数, --bands N 每帧行带数, --manifest 文件"
+            );
+            eprintln!(
+                "  farm-worker <任务清单> - 认领并渲染尚未完成的任务（--job ID 只运行指定任务）"
+            );
+            eprintln!(
+                "  farm-merge <任务清单> - 把行带拼合为完整的帧（--keep-bands 保留行带文件）"
+            );
+            eprintln!("  compare <A> <B> - 比较两幅图像（MSE/PSNR/SSIM/FLIP）");
+            eprintln!(
+                "  light-probe <x,y,z>... - 比较候选光源位置的直接光照热度图（--scene cornell|final, --radius R, --samples N, --opaque-shadows 玻璃完全遮挡）"
+            );
+            eprintln!(
+                "  probe-grid - 烘焙球谐辐照度探针网格并导出（--scene cornell|final, --probes N|NX,NY,NZ, --samples N, --min x,y,z --max x,y,z, --binary）"
+            );
+            eprintln!("  validate - 运行渲染器自检");
+            eprintln!("选项:");
+            eprintln!("  --bounds     - 叠加叶子物体包围盒线框");
+            eprintln!("  --bounds-all - 叠加全部BVH节点包围盒线框");
+            eprintln!("  --transparent - 背景输出为透明（RGBA PNG）");
+            eprintln!("  --irradiance-cache - 康奈尔盒使用辐照度缓存快速预览（有偏）");
+            eprintln!("  --stats      - 渲染后输出按材质统计的着色开销和优化建议");
+            eprintln!(
+                "  --report     - 渲染结束时在输出文件旁写出 JSON 报告（设置、阶段耗时、统计、输出哈希）"
+            );
+            eprintln!(
+                "  --camera <名称> - final/quick 场景使用的相机（main|top|side|cluster，all 渲染全部，场景只构建一次）"
+            );
+            eprintln!("  --color-space <空间> - 输出色彩空间（gamma2 默认 | srgb | display-p3）");
+            eprintln!(
+                "  --termination <策略> - 路径终止策略（hybrid[:N] 默认 | fixed | roulette[:N]，N 为轮盘赌前的最少反弹次数）"
+            );
+            eprintln!(
+                "  --denoise <强度> - 以法线/反照率/深度辅助缓冲引导的降噪（1 为默认强度，0 关闭）"
+            );
+            eprintln!(
+                "  --caustics <光子数> - 焦散由光子图估计，与路径追踪结合（每轮光子数，如 200000；0 关闭）"
+            );
+            eprintln!(
+                "  --projection <投影> - 相机投影（perspective 默认 | little-planet[:视场角] | tilt-shift:倾角[:上移[:焦距]]）"
+            );
+            eprintln!(
+                "  --dof-sampling <权重> - 按弥散圆大小为失焦像素分配额外样本（1 为默认权重，0 关闭）"
+            );
+            eprintln!(
+                "  --shutter <曲线> - 快门效率曲线（box 默认 | trapezoid[:斜坡比例] | sine | curve:v0,v1,...），决定运动模糊拖影的亮度分布"
+            );
+            eprintln!(
+                "  --time-budget <时长> - 渐进渲染直到用完时间预算（如 90s、10m、1.5h），每轮结束后保存检查点"
+            );
+            eprintln!(
+                "  --target-noise <方差> - 渐进渲染直到每像素亮度估计值方差的平均值低于目标（如 1e-4）"
+            );
+            eprintln!("  --max-spp <N> - 渐进渲染的每像素样本数上限");
+            eprintln!("  --spp <N> / --max-depth <N> / --threads <N> / --output-dir <目录>");
+            eprintln!("               - 覆盖 raytracer.toml 中的默认值");
         }
     }
 }
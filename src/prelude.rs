@@ -0,0 +1,32 @@
+//! 常用类型和扩展用trait的统一导入：`use ray_tracing_rust::prelude::*;`
+
+pub use crate::ray_tracing::acceleration::bvh::BvhNode;
+pub use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+pub use crate::ray_tracing::geometry::hittable_list::HittableList;
+pub use crate::ray_tracing::geometry::light_link::LightLink;
+pub use crate::ray_tracing::geometry::planar::Planar;
+pub use crate::ray_tracing::geometry::polygon::Polygon;
+pub use crate::ray_tracing::geometry::quad::{Quad, box_new};
+pub use crate::ray_tracing::geometry::quadric::Quadric;
+pub use crate::ray_tracing::geometry::sphere::Sphere;
+pub use crate::ray_tracing::geometry::transforms::rotate_y::RotateY;
+pub use crate::ray_tracing::geometry::transforms::translate::Translate;
+pub use crate::ray_tracing::geometry::visibility::{RayVisibility, Visibility};
+pub use crate::ray_tracing::materials::dielectric::Dielectric;
+pub use crate::ray_tracing::materials::diffuse_light::DiffuseLight;
+pub use crate::ray_tracing::materials::lambertian::Lambertian;
+pub use crate::ray_tracing::materials::material::{Material, NoMaterial, ScatterRecord};
+pub use crate::ray_tracing::materials::metal::Metal;
+pub use crate::ray_tracing::materials::texture::{ClosureTexture, SolidColor, Texture, TexturePtr};
+pub use crate::ray_tracing::math::aabb::Aabb;
+pub use crate::ray_tracing::math::differential::TextureFootprint;
+pub use crate::ray_tracing::math::interval::Interval;
+pub use crate::ray_tracing::math::onb::ONB;
+pub use crate::ray_tracing::math::ray::{LightLinkTag, LinkSet, Ray, RayKind};
+pub use crate::ray_tracing::math::vec3::{Color, Point3, Vec3, Vec3Ext};
+pub use crate::ray_tracing::rendering::background::Background;
+pub use crate::ray_tracing::rendering::camera::{Camera, CameraBuilder};
+pub use crate::ray_tracing::rendering::camera_path::{CameraPath, Easing, SplineKind};
+pub use crate::ray_tracing::rendering::color::ColorExt;
+pub use crate::ray_tracing::sampling::pdf::{CosinePDF, HittablePDF, MixturePDF, PDF, SpherePDF};
+pub use crate::ray_tracing::utils::random::{random_double, random_double_range};
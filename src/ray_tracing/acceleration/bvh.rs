@@ -2,7 +2,7 @@ use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
 use crate::ray_tracing::geometry::hittable_list::HittableList;
 use crate::ray_tracing::math::aabb::Aabb;
 use crate::ray_tracing::math::interval::Interval;
-use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::ray::{LightLinkTag, Ray};
 use crate::ray_tracing::math::vec3::*;
 use crate::ray_tracing::utils::random::random_double;
 use std::cmp::Ordering;
@@ -90,8 +90,20 @@ impl Hittable for BvhNode {
             return false;
         }
 
+        // 子节点中未包装的物体不会写入光源链接标签，静止的物体不会写入运动信息，求交前重置，未命中时恢复
+        let mut link = rec.link;
+        let mut motion = rec.motion;
+        rec.link = LightLinkTag::DEFAULT;
+        rec.motion = None;
+
         // 检查左子树
         let hit_left = self.left.hit(r, ray_t, rec);
+        if hit_left {
+            link = rec.link;
+            motion = rec.motion;
+            rec.link = LightLinkTag::DEFAULT;
+            rec.motion = None;
+        }
 
         // 检查右子树，如果左子树命中则限制最大距离
         let right_interval = if hit_left {
@@ -100,28 +112,67 @@ impl Hittable for BvhNode {
             ray_t
         };
         let hit_right = self.right.hit(r, right_interval, rec);
+        if !hit_right {
+            rec.link = link;
+            rec.motion = motion;
+        }
 
         hit_left || hit_right
     }
 
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        // 找到任意遮挡即提前退出
+        self.bbox.hit(r, ray_t) && (self.left.hit_any(r, ray_t) || self.right.hit_any(r, ray_t))
+    }
+
     #[inline]
     fn bounding_box(&self) -> Option<Aabb> {
         Some(self.bbox)
     }
 
-    #[inline]
-    fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3, time: f64) -> f64 {
         // BVH节点的PDF值是左右子树的平均
-        0.5 * (self.left.pdf_value(origin, direction) + self.right.pdf_value(origin, direction))
+        0.5 * (self.left.pdf_value(origin, direction, time)
+            + self.right.pdf_value(origin, direction, time))
     }
 
     #[inline]
-    fn random(&self, origin: &Point3) -> Vec3 {
+    fn random(&self, origin: &Point3, time: f64) -> Vec3 {
         // 随机选择左右子树
         if random_double() < 0.5 {
-            self.left.random(origin)
+            self.left.random(origin, time)
+        } else {
+            self.right.random(origin, time)
+        }
+    }
+
+    /// 单对象节点的左右子节点相同，只计一次
+    fn area(&self) -> f64 {
+        if Arc::ptr_eq(&self.left, &self.right) {
+            self.left.area()
         } else {
-            self.right.random(origin)
+            self.left.area() + self.right.area()
+        }
+    }
+
+    fn power(&self) -> Color {
+        if Arc::ptr_eq(&self.left, &self.right) {
+            self.left.power()
+        } else {
+            self.left.power() + self.right.power()
+        }
+    }
+
+    fn collect_debug_boxes(&self, depth: usize, leaves_only: bool, boxes: &mut Vec<(Aabb, usize)>) {
+        if !leaves_only {
+            boxes.push((self.bbox, depth));
+        }
+
+        self.left.collect_debug_boxes(depth + 1, leaves_only, boxes);
+        // 单对象节点的左右子节点相同，避免重复绘制
+        if !Arc::ptr_eq(&self.left, &self.right) {
+            self.right
+                .collect_debug_boxes(depth + 1, leaves_only, boxes);
         }
     }
 }
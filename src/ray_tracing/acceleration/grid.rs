@@ -0,0 +1,291 @@
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::geometry::hittable_list::HittableList;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::{LightLinkTag, Ray};
+use crate::ray_tracing::math::vec3::*;
+use std::sync::Arc;
+
+/// 均匀网格加速结构，适合大量尺寸相近物体均匀分布的场景
+///
+/// 网格范围在构建时固定，之后插入物体为 O(1)（仅更新其覆盖的单元），
+/// 超出范围或没有包围盒的物体放入溢出列表，每条光线都会检测。
+pub struct UniformGrid {
+    bounds: Aabb,
+    resolution: [usize; 3],
+    cell_size: Vec3,
+    cells: Vec<Vec<usize>>,
+    objects: Vec<Arc<dyn Hittable>>,
+    overflow: Vec<usize>,
+    bbox: Aabb,
+}
+
+impl UniformGrid {
+    /// 从物体列表构建网格，每轴单元数按物体密度自动选择
+    pub fn new(list: &HittableList) -> Self {
+        let bounds = list.bounding_box().unwrap_or_else(Aabb::empty);
+        let resolution = Self::auto_resolution(&bounds, list.objects.len());
+        let mut grid = Self::with_bounds(bounds, resolution);
+        for object in &list.objects {
+            grid.insert(object.clone());
+        }
+        grid
+    }
+
+    /// 在固定范围和分辨率下创建空网格
+    pub fn with_bounds(bounds: Aabb, resolution: [usize; 3]) -> Self {
+        let resolution = resolution.map(|n| n.max(1));
+        let cell_size = if bounds.is_empty() {
+            Vec3::new(1.0, 1.0, 1.0)
+        } else {
+            Vec3::new(
+                bounds.x.size() / resolution[0] as f64,
+                bounds.y.size() / resolution[1] as f64,
+                bounds.z.size() / resolution[2] as f64,
+            )
+        };
+
+        Self {
+            bounds,
+            resolution,
+            cell_size,
+            cells: vec![Vec::new(); resolution[0] * resolution[1] * resolution[2]],
+            objects: Vec::new(),
+            overflow: Vec::new(),
+            bbox: Aabb::empty(),
+        }
+    }
+
+    /// 经验公式：单元总数约为物体数的 3 倍，按包围盒比例分配到各轴
+    fn auto_resolution(bounds: &Aabb, count: usize) -> [usize; 3] {
+        if bounds.is_empty() || count == 0 {
+            return [1, 1, 1];
+        }
+
+        let size = [bounds.x.size(), bounds.y.size(), bounds.z.size()];
+        let volume = size[0] * size[1] * size[2];
+        let cells_per_unit = (3.0 * count as f64 / volume).cbrt();
+        size.map(|s| ((s * cells_per_unit).round() as usize).clamp(1, 128))
+    }
+
+    /// 插入物体，只更新其包围盒覆盖的网格单元
+    pub fn insert(&mut self, object: Arc<dyn Hittable>) {
+        let index = self.objects.len();
+        let obj_bbox = object.bounding_box();
+        self.objects.push(object);
+
+        let Some(obj_bbox) = obj_bbox else {
+            self.overflow.push(index);
+            return;
+        };
+        self.bbox = self.bbox.merge(&obj_bbox);
+
+        let inside = (0..3).all(|axis| {
+            let b = self.bounds.axis_interval(axis);
+            let o = obj_bbox.axis_interval(axis);
+            b.min <= o.min && o.max <= b.max
+        });
+        if !inside {
+            self.overflow.push(index);
+            return;
+        }
+
+        let lo = self.cell_coords(&Point3::new(obj_bbox.x.min, obj_bbox.y.min, obj_bbox.z.min));
+        let hi = self.cell_coords(&Point3::new(obj_bbox.x.max, obj_bbox.y.max, obj_bbox.z.max));
+        for z in lo[2]..=hi[2] {
+            for y in lo[1]..=hi[1] {
+                for x in lo[0]..=hi[0] {
+                    let cell = self.cell_index(x, y, z);
+                    self.cells[cell].push(index);
+                }
+            }
+        }
+    }
+
+    /// 物体数量
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// 检查是否为空
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// 点所在的单元坐标（限制在网格内）
+    #[inline]
+    fn cell_coords(&self, p: &Point3) -> [usize; 3] {
+        let mut coords = [0; 3];
+        for (axis, coord) in coords.iter_mut().enumerate() {
+            let rel = (p[axis] - self.bounds.axis_interval(axis).min) / self.cell_size[axis];
+            *coord = (rel.floor().max(0.0) as usize).min(self.resolution[axis] - 1);
+        }
+        coords
+    }
+
+    #[inline]
+    fn cell_index(&self, x: usize, y: usize, z: usize) -> usize {
+        (z * self.resolution[1] + y) * self.resolution[0] + x
+    }
+
+    /// 光线进入和离开网格范围的参数区间
+    fn clip_to_bounds(&self, r: &Ray, ray_t: Interval) -> Option<Interval> {
+        let mut t = ray_t;
+        for axis in 0..3 {
+            let interval = self.bounds.axis_interval(axis);
+            let dir = r.dir[axis];
+            let orig = r.orig[axis];
+            if dir.abs() < 1e-12 {
+                if orig < interval.min || orig > interval.max {
+                    return None;
+                }
+                continue;
+            }
+
+            let inv = 1.0 / dir;
+            let t0 = (interval.min - orig) * inv;
+            let t1 = (interval.max - orig) * inv;
+            let (near, far) = if inv >= 0.0 { (t0, t1) } else { (t1, t0) };
+            t.min = t.min.max(near);
+            t.max = t.max.min(far);
+            if t.max <= t.min {
+                return None;
+            }
+        }
+        Some(t)
+    }
+
+    /// 按 3D-DDA（Amanatides & Woo）顺序访问光线在区间内穿过的单元
+    ///
+    /// `visit` 接收单元内的物体索引和光线离开该单元时的参数，返回 true 时停止遍历。
+    fn walk_cells(&self, r: &Ray, span: Interval, mut visit: impl FnMut(&[usize], f64) -> bool) {
+        let entry = r.at(span.min);
+        let mut cell = self.cell_coords(&entry);
+        let mut step = [0i64; 3];
+        let mut t_max = [f64::INFINITY; 3];
+        let mut t_delta = [f64::INFINITY; 3];
+        for axis in 0..3 {
+            let dir = r.dir[axis];
+            if dir.abs() < 1e-12 {
+                continue;
+            }
+            let axis_min = self.bounds.axis_interval(axis).min;
+            if dir > 0.0 {
+                step[axis] = 1;
+                let boundary = axis_min + (cell[axis] + 1) as f64 * self.cell_size[axis];
+                t_max[axis] = (boundary - r.orig[axis]) / dir;
+            } else {
+                step[axis] = -1;
+                let boundary = axis_min + cell[axis] as f64 * self.cell_size[axis];
+                t_max[axis] = (boundary - r.orig[axis]) / dir;
+            }
+            t_delta[axis] = self.cell_size[axis] / dir.abs();
+        }
+
+        loop {
+            // 选择最先离开的轴
+            let axis = if t_max[0] < t_max[1] {
+                if t_max[0] < t_max[2] { 0 } else { 2 }
+            } else if t_max[1] < t_max[2] {
+                1
+            } else {
+                2
+            };
+
+            let index = self.cell_index(cell[0], cell[1], cell[2]);
+            if visit(&self.cells[index], t_max[axis]) || t_max[axis] > span.max {
+                break;
+            }
+
+            let next = cell[axis] as i64 + step[axis];
+            if next < 0 || next >= self.resolution[axis] as i64 {
+                break;
+            }
+            cell[axis] = next as usize;
+            t_max[axis] += t_delta[axis];
+        }
+    }
+
+    /// 检测指定列表中的物体，更新最近交点
+    fn hit_indices(
+        &self,
+        indices: &[usize],
+        r: &Ray,
+        t_min: f64,
+        closest: &mut f64,
+        rec: &mut HitRecord,
+    ) -> bool {
+        let mut hit_anything = false;
+        let mut temp_rec = HitRecord::default();
+        for &index in indices {
+            if self.objects[index].hit(r, Interval::new(t_min, *closest), &mut temp_rec) {
+                hit_anything = true;
+                *closest = temp_rec.t;
+                std::mem::swap(rec, &mut temp_rec);
+                // 换出的旧记录可能带有光源链接标签和运动信息，未包装的静止物体不会覆盖它们
+                temp_rec.link = LightLinkTag::DEFAULT;
+                temp_rec.motion = None;
+            }
+        }
+        hit_anything
+    }
+}
+
+impl Hittable for UniformGrid {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let mut closest = ray_t.max;
+        let mut hit_anything = self.hit_indices(&self.overflow, r, ray_t.min, &mut closest, rec);
+
+        let Some(span) = self.clip_to_bounds(r, Interval::new(ray_t.min, closest)) else {
+            return hit_anything;
+        };
+
+        self.walk_cells(r, span, |indices, exit| {
+            if self.hit_indices(indices, r, ray_t.min, &mut closest, rec) {
+                hit_anything = true;
+            }
+            // 当前单元内已找到的交点必然比后续单元更近
+            closest <= exit
+        });
+        hit_anything
+    }
+
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        let any = |indices: &[usize]| indices.iter().any(|&i| self.objects[i].hit_any(r, ray_t));
+        if any(&self.overflow) {
+            return true;
+        }
+        let Some(span) = self.clip_to_bounds(r, ray_t) else {
+            return false;
+        };
+        // 找到任意遮挡即停止遍历
+        let mut found = false;
+        self.walk_cells(r, span, |indices, _| {
+            found = any(indices);
+            found
+        });
+        found
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.bbox)
+        }
+    }
+}
+
+impl std::fmt::Debug for UniformGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UniformGrid")
+            .field("bounds", &self.bounds)
+            .field("resolution", &self.resolution)
+            .field("objects", &format!("{} objects", self.objects.len()))
+            .field("overflow", &self.overflow.len())
+            .finish()
+    }
+}
@@ -0,0 +1,486 @@
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::geometry::hittable_list::HittableList;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::{LightLinkTag, Ray};
+use crate::ray_tracing::math::vec3::*;
+use std::sync::Arc;
+
+/// 遍历一个内部节点的相对开销
+const TRAVERSAL_COST: f64 = 1.0;
+/// 与一个物体求交的相对开销
+const INTERSECT_COST: f64 = 80.0;
+/// 划分出空节点时的开销折扣，鼓励尽早切掉空白区域
+const EMPTY_BONUS: f64 = 0.5;
+/// 叶子节点允许直接存放的物体数
+const MAX_LEAF_OBJECTS: usize = 1;
+/// 划分没有降低开销时，继续尝试的次数
+const MAX_BAD_REFINES: u32 = 3;
+/// 最大树深
+const MAX_TREE_DEPTH: u32 = 64;
+
+/// 展平存放的节点，内部节点的下方子节点紧跟在自身之后
+#[derive(Debug, Clone, Copy)]
+enum KdNode {
+    Interior { axis: u8, split: f64, above: u32 },
+    Leaf { first: u32, count: u32 },
+}
+
+/// 包围盒在某个轴上的起点或终点，排序后用于扫描所有候选划分平面
+#[derive(Debug, Clone, Copy)]
+struct BoundEdge {
+    t: f64,
+    start: bool,
+}
+
+/// k-d 树加速结构：用表面积启发式（SAH）选择轴对齐划分平面
+///
+/// 与BVH按物体划分不同，k-d 树按空间划分，跨越划分平面的物体会同时出现在两侧；
+/// 遍历时按光线方向由近到远访问节点，找到交点后即可跳过更远的节点，
+/// 因此在物体密集、互相遮挡的场景（如小球群）中往往比BVH更快。
+/// 没有包围盒的物体放入溢出列表，每条光线都会检测。
+pub struct KdTree {
+    objects: Vec<Arc<dyn Hittable>>,
+    nodes: Vec<KdNode>,
+    indices: Vec<u32>,
+    overflow: Vec<u32>,
+    bounds: Aabb,
+}
+
+impl KdTree {
+    /// 从物体列表构建 k-d 树
+    pub fn new(list: &HittableList) -> Self {
+        let objects = list.objects.clone();
+        let mut bounded = Vec::new();
+        let mut boxes = Vec::with_capacity(objects.len());
+        let mut overflow = Vec::new();
+        let mut bounds = Aabb::empty();
+        for (index, object) in objects.iter().enumerate() {
+            match object.bounding_box() {
+                Some(bbox) => {
+                    bounds = bounds.merge(&bbox);
+                    bounded.push(index as u32);
+                    boxes.push(bbox);
+                }
+                None => {
+                    overflow.push(index as u32);
+                    boxes.push(Aabb::empty());
+                }
+            }
+        }
+
+        let mut tree = Self {
+            objects,
+            nodes: Vec::new(),
+            indices: Vec::new(),
+            overflow,
+            bounds,
+        };
+        if !bounded.is_empty() {
+            let max_depth =
+                ((8.0 + 1.3 * (bounded.len() as f64).log2()).round() as u32).min(MAX_TREE_DEPTH);
+            tree.build(&boxes, bounds, bounded, max_depth, 0);
+        }
+        tree
+    }
+
+    /// 物体数量
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// 检查是否为空
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// 节点数量
+    #[inline]
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// 递归构建，返回节点序号
+    fn build(
+        &mut self,
+        boxes: &[Aabb],
+        node_bounds: Aabb,
+        objects: Vec<u32>,
+        depth: u32,
+        bad_refines: u32,
+    ) -> usize {
+        let node = self.nodes.len();
+        if objects.len() <= MAX_LEAF_OBJECTS || depth == 0 {
+            self.push_leaf(&objects);
+            return node;
+        }
+
+        let Some((axis, split, cost)) = best_split(boxes, &node_bounds, &objects) else {
+            self.push_leaf(&objects);
+            return node;
+        };
+
+        // 划分的开销高于直接作为叶子时，允许少量“坏”划分，之后可能出现更好的划分
+        let leaf_cost = INTERSECT_COST * objects.len() as f64;
+        let bad_refines = if cost > leaf_cost {
+            bad_refines + 1
+        } else {
+            bad_refines
+        };
+        if (cost > 4.0 * leaf_cost && objects.len() < 16) || bad_refines > MAX_BAD_REFINES {
+            self.push_leaf(&objects);
+            return node;
+        }
+
+        let mut below = Vec::new();
+        let mut above = Vec::new();
+        for &object in &objects {
+            let interval = boxes[object as usize].axis_interval(axis);
+            if interval.min < split {
+                below.push(object);
+            }
+            if interval.max > split {
+                above.push(object);
+            }
+            // 贴在划分平面上的扁平物体放在下方
+            if interval.min == split && interval.max == split {
+                below.push(object);
+            }
+        }
+        drop(objects);
+
+        let (mut below_bounds, mut above_bounds) = (node_bounds, node_bounds);
+        set_axis_max(&mut below_bounds, axis, split);
+        set_axis_min(&mut above_bounds, axis, split);
+
+        self.nodes.push(KdNode::Leaf { first: 0, count: 0 }); // 占位，子节点建好后替换
+        self.build(boxes, below_bounds, below, depth - 1, bad_refines);
+        let above_node = self.build(boxes, above_bounds, above, depth - 1, bad_refines);
+        self.nodes[node] = KdNode::Interior {
+            axis: axis as u8,
+            split,
+            above: above_node as u32,
+        };
+        node
+    }
+
+    fn push_leaf(&mut self, objects: &[u32]) {
+        self.nodes.push(KdNode::Leaf {
+            first: self.indices.len() as u32,
+            count: objects.len() as u32,
+        });
+        self.indices.extend_from_slice(objects);
+    }
+
+    /// 检测指定列表中的物体，更新最近交点
+    fn hit_objects(
+        &self,
+        objects: &[u32],
+        r: &Ray,
+        t_min: f64,
+        closest: &mut f64,
+        rec: &mut HitRecord,
+    ) -> bool {
+        let mut hit_anything = false;
+        let mut temp_rec = HitRecord::default();
+        for &index in objects {
+            let object = &self.objects[index as usize];
+            if object.hit(r, Interval::new(t_min, *closest), &mut temp_rec) {
+                hit_anything = true;
+                *closest = temp_rec.t;
+                std::mem::swap(rec, &mut temp_rec);
+                // 换出的旧记录可能带有光源链接标签和运动信息，未包装的静止物体不会覆盖它们
+                temp_rec.link = LightLinkTag::DEFAULT;
+                temp_rec.motion = None;
+            }
+        }
+        hit_anything
+    }
+
+    /// 叶子节点中的物体序号
+    #[inline]
+    fn leaf_objects(&self, first: u32, count: u32) -> &[u32] {
+        &self.indices[first as usize..(first + count) as usize]
+    }
+
+    /// 由近到远遍历与光线相交的叶子，visit 返回 true 时停止
+    ///
+    /// visit 的参数为叶子中的物体和光线在该叶子内的参数区间上界。
+    fn traverse(&self, r: &Ray, ray_t: Interval, mut visit: impl FnMut(&[u32], f64) -> bool) {
+        let Some(span) = clip_to_bounds(&self.bounds, r, ray_t) else {
+            return;
+        };
+        let inv_dir = Vec3::new(1.0 / r.dir.x, 1.0 / r.dir.y, 1.0 / r.dir.z);
+        // 树深不超过 MAX_TREE_DEPTH，待访问的远侧节点不会超过这个数量
+        let mut stack = [(0usize, 0.0, 0.0); MAX_TREE_DEPTH as usize];
+        let mut stack_len = 0;
+        let (mut node, mut t_min, mut t_max) = (0, span.min, span.max);
+
+        loop {
+            match self.nodes[node] {
+                KdNode::Interior { axis, split, above } => {
+                    let axis = axis as usize;
+                    let below = node + 1;
+                    let above = above as usize;
+                    let origin = r.orig[axis];
+                    let below_first = origin < split || (origin == split && r.dir[axis] <= 0.0);
+                    let (first, second) = if below_first {
+                        (below, above)
+                    } else {
+                        (above, below)
+                    };
+
+                    // 平行于划分平面的光线只经过一侧
+                    if r.dir[axis] == 0.0 {
+                        node = first;
+                        continue;
+                    }
+                    let t_plane = (split - origin) * inv_dir[axis];
+                    if t_plane > t_max || t_plane <= 0.0 {
+                        node = first;
+                    } else if t_plane < t_min {
+                        node = second;
+                    } else {
+                        stack[stack_len] = (second, t_plane, t_max);
+                        stack_len += 1;
+                        node = first;
+                        t_max = t_plane;
+                    }
+                }
+                KdNode::Leaf { first, count } => {
+                    if visit(self.leaf_objects(first, count), t_max) {
+                        return;
+                    }
+                    if stack_len == 0 {
+                        return;
+                    }
+                    stack_len -= 1;
+                    (node, t_min, t_max) = stack[stack_len];
+                }
+            }
+        }
+    }
+
+    /// 收集各节点的包围盒
+    fn collect_node_boxes(
+        &self,
+        node: usize,
+        bounds: Aabb,
+        depth: usize,
+        leaves_only: bool,
+        boxes: &mut Vec<(Aabb, usize)>,
+    ) {
+        match self.nodes[node] {
+            KdNode::Interior { axis, split, above } => {
+                if !leaves_only {
+                    boxes.push((bounds, depth));
+                }
+                let axis = axis as usize;
+                let (mut below_bounds, mut above_bounds) = (bounds, bounds);
+                set_axis_max(&mut below_bounds, axis, split);
+                set_axis_min(&mut above_bounds, axis, split);
+                self.collect_node_boxes(node + 1, below_bounds, depth + 1, leaves_only, boxes);
+                self.collect_node_boxes(
+                    above as usize,
+                    above_bounds,
+                    depth + 1,
+                    leaves_only,
+                    boxes,
+                );
+            }
+            KdNode::Leaf { count, .. } => {
+                if count > 0 {
+                    boxes.push((bounds, depth));
+                }
+            }
+        }
+    }
+}
+
+/// 用 SAH 在三个轴上寻找开销最低的划分平面，返回 (轴, 位置, 开销)
+fn best_split(boxes: &[Aabb], node_bounds: &Aabb, objects: &[u32]) -> Option<(usize, f64, f64)> {
+    let size = Vec3::new(
+        node_bounds.x.size(),
+        node_bounds.y.size(),
+        node_bounds.z.size(),
+    );
+    let total_area = 2.0 * (size.x * size.y + size.y * size.z + size.z * size.x);
+    if total_area <= 0.0 {
+        return None;
+    }
+    let inv_total_area = 1.0 / total_area;
+
+    let mut best: Option<(usize, f64, f64)> = None;
+    let mut edges = Vec::with_capacity(2 * objects.len());
+    for axis in 0..3 {
+        let node_interval = node_bounds.axis_interval(axis);
+        if node_interval.size() <= 0.0 {
+            continue;
+        }
+        edges.clear();
+        for &object in objects {
+            let interval = boxes[object as usize].axis_interval(axis);
+            edges.push(BoundEdge {
+                t: interval.min,
+                start: true,
+            });
+            edges.push(BoundEdge {
+                t: interval.max,
+                start: false,
+            });
+        }
+        // 位置相同时起点排在终点之前
+        edges.sort_by(|a, b| a.t.total_cmp(&b.t).then(b.start.cmp(&a.start)));
+
+        let (other0, other1) = ((axis + 1) % 3, (axis + 2) % 3);
+        let cross_area = size[other0] * size[other1];
+        let perimeter = size[other0] + size[other1];
+        let (mut below, mut above) = (0usize, objects.len());
+        for edge in &edges {
+            if !edge.start {
+                above -= 1;
+            }
+            if edge.t > node_interval.min && edge.t < node_interval.max {
+                let below_area = 2.0 * (cross_area + (edge.t - node_interval.min) * perimeter);
+                let above_area = 2.0 * (cross_area + (node_interval.max - edge.t) * perimeter);
+                let bonus = if below == 0 || above == 0 {
+                    EMPTY_BONUS
+                } else {
+                    0.0
+                };
+                let cost = TRAVERSAL_COST
+                    + INTERSECT_COST
+                        * (1.0 - bonus)
+                        * (below_area * inv_total_area * below as f64
+                            + above_area * inv_total_area * above as f64);
+                if best.is_none_or(|(_, _, best_cost)| cost < best_cost) {
+                    best = Some((axis, edge.t, cost));
+                }
+            }
+            if edge.start {
+                below += 1;
+            }
+        }
+    }
+    best
+}
+
+#[inline]
+fn set_axis_min(bbox: &mut Aabb, axis: usize, value: f64) {
+    match axis {
+        0 => bbox.x.min = value,
+        1 => bbox.y.min = value,
+        _ => bbox.z.min = value,
+    }
+}
+
+#[inline]
+fn set_axis_max(bbox: &mut Aabb, axis: usize, value: f64) {
+    match axis {
+        0 => bbox.x.max = value,
+        1 => bbox.y.max = value,
+        _ => bbox.z.max = value,
+    }
+}
+
+/// 光线进入和离开包围盒的参数区间
+fn clip_to_bounds(bounds: &Aabb, r: &Ray, ray_t: Interval) -> Option<Interval> {
+    let mut t = ray_t;
+    for axis in 0..3 {
+        let interval = bounds.axis_interval(axis);
+        let dir = r.dir[axis];
+        let orig = r.orig[axis];
+        if dir.abs() < 1e-12 {
+            if orig < interval.min || orig > interval.max {
+                return None;
+            }
+            continue;
+        }
+
+        let inv = 1.0 / dir;
+        let t0 = (interval.min - orig) * inv;
+        let t1 = (interval.max - orig) * inv;
+        let (near, far) = if inv >= 0.0 { (t0, t1) } else { (t1, t0) };
+        t.min = t.min.max(near);
+        t.max = t.max.min(far);
+        if t.max < t.min {
+            return None;
+        }
+    }
+    Some(t)
+}
+
+impl Hittable for KdTree {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let mut closest = ray_t.max;
+        let mut hit_anything = self.hit_objects(&self.overflow, r, ray_t.min, &mut closest, rec);
+
+        self.traverse(
+            r,
+            Interval::new(ray_t.min, closest),
+            |objects, leaf_t_max| {
+                if self.hit_objects(objects, r, ray_t.min, &mut closest, rec) {
+                    hit_anything = true;
+                }
+                // 交点在当前叶子之内时，后续叶子都更远
+                closest <= leaf_t_max
+            },
+        );
+        hit_anything
+    }
+
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        if self
+            .overflow
+            .iter()
+            .any(|&i| self.objects[i as usize].hit_any(r, ray_t))
+        {
+            return true;
+        }
+        let mut found = false;
+        self.traverse(r, ray_t, |objects, _| {
+            found = objects
+                .iter()
+                .any(|&i| self.objects[i as usize].hit_any(r, ray_t));
+            found
+        });
+        found
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        if self.nodes.is_empty() || !self.overflow.is_empty() {
+            None
+        } else {
+            Some(self.bounds)
+        }
+    }
+
+    fn area(&self) -> f64 {
+        self.objects.iter().map(|obj| obj.area()).sum()
+    }
+
+    fn power(&self) -> Color {
+        self.objects.iter().map(|obj| obj.power()).sum()
+    }
+
+    fn collect_debug_boxes(&self, depth: usize, leaves_only: bool, boxes: &mut Vec<(Aabb, usize)>) {
+        if !self.nodes.is_empty() {
+            self.collect_node_boxes(0, self.bounds, depth, leaves_only, boxes);
+        }
+    }
+}
+
+impl std::fmt::Debug for KdTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KdTree")
+            .field("bounds", &self.bounds)
+            .field("objects", &format!("{} objects", self.objects.len()))
+            .field("nodes", &self.nodes.len())
+            .field("references", &self.indices.len())
+            .field("overflow", &self.overflow.len())
+            .finish()
+    }
+}
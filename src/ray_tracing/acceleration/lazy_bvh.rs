@@ -0,0 +1,199 @@
+//! 延迟构建的 BVH：包围盒预先已知，内部物体在第一条光线进入包围盒时才生成并建树
+//!
+//! 用于流式加载的大场景：把几何分成若干块，顶层 BVH 只包含各块的包围盒，
+//! 从未被光线触及的块不会被读取和构建。多个块可以共享一个 [`ChunkBudget`]，
+//! 已构建块的估计内存超出预算时释放最久未被光线访问的块，之后再被触及时重新构建。
+
+use crate::ray_tracing::acceleration::bvh::BvhNode;
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::geometry::hittable_list::HittableList;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// 生成块内物体的函数
+pub type ChunkLoader = Box<dyn Fn() -> HittableList + Send + Sync>;
+
+/// 一个块的构建结果和访问时间
+#[derive(Default)]
+struct ChunkSlot {
+    bvh: RwLock<Option<Arc<BvhNode>>>,
+    /// 最近一次访问时预算的时钟值
+    last_used: AtomicU64,
+    /// 生成的物体为空，无需建树也不占预算
+    empty: AtomicBool,
+}
+
+/// 已构建块的内存预算，超出时按最近最少使用（LRU）释放块
+#[derive(Default)]
+pub struct ChunkBudget {
+    limit: usize,
+    /// 每次构建块时递增，块在被访问时记录当前值
+    clock: AtomicU64,
+    used: AtomicUsize,
+    resident: Mutex<Vec<(Arc<ChunkSlot>, usize)>>,
+}
+
+impl ChunkBudget {
+    /// limit 为已构建块的估计内存上限（字节），至少保留最近构建的一块
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            ..Self::default()
+        }
+    }
+
+    /// 内存上限（字节）
+    #[inline]
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// 当前已构建块的估计内存（字节）
+    #[inline]
+    pub fn used(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    /// 登记新构建的块，必要时释放其他最久未使用的块
+    fn admit(&self, slot: &Arc<ChunkSlot>, bytes: usize) {
+        let mut resident = self.resident.lock().unwrap();
+        resident.push((slot.clone(), bytes));
+        let mut used = self.used.load(Ordering::Relaxed) + bytes;
+        while used > self.limit && resident.len() > 1 {
+            let oldest = resident
+                .iter()
+                .enumerate()
+                .filter(|(_, (s, _))| !Arc::ptr_eq(s, slot))
+                .min_by_key(|(_, (s, _))| s.last_used.load(Ordering::Relaxed))
+                .map(|(i, _)| i)
+                .unwrap();
+            let (evicted, evicted_bytes) = resident.swap_remove(oldest);
+            // 正在遍历该块的光线持有自己的引用，树在它们结束后才真正释放
+            *evicted.bvh.write().unwrap() = None;
+            used -= evicted_bytes;
+        }
+        self.used.store(used, Ordering::Relaxed);
+    }
+}
+
+impl std::fmt::Debug for ChunkBudget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChunkBudget")
+            .field("limit", &self.limit)
+            .field("used", &self.used())
+            .finish()
+    }
+}
+
+/// 延迟构建的 BVH 块
+pub struct LazyBvh {
+    bbox: Aabb,
+    loader: ChunkLoader,
+    slot: Arc<ChunkSlot>,
+    /// 构建锁：并发触及未构建的块时只有一个线程构建，其余等待
+    building: Mutex<()>,
+    /// 共享的内存预算和本块构建后的估计内存，None 表示构建后一直保留
+    budget: Option<(Arc<ChunkBudget>, usize)>,
+}
+
+impl LazyBvh {
+    /// bbox 必须包含 loader 生成的全部物体，构建后一直保留
+    pub fn new(bbox: Aabb, loader: ChunkLoader) -> Self {
+        Self {
+            bbox,
+            loader,
+            slot: Arc::default(),
+            building: Mutex::new(()),
+            budget: None,
+        }
+    }
+
+    /// 受内存预算约束的块，bytes 为构建后（物体和块内 BVH）的估计内存
+    pub fn with_budget(
+        bbox: Aabb,
+        loader: ChunkLoader,
+        budget: Arc<ChunkBudget>,
+        bytes: usize,
+    ) -> Self {
+        Self {
+            budget: Some((budget, bytes)),
+            ..Self::new(bbox, loader)
+        }
+    }
+
+    /// 当前是否已构建（被预算释放后为 false）
+    #[inline]
+    pub fn is_resident(&self) -> bool {
+        self.slot.empty.load(Ordering::Acquire) || self.slot.bvh.read().unwrap().is_some()
+    }
+
+    /// 取得块内的 BVH，未构建或已被释放时构建
+    fn bvh(&self) -> Option<Arc<BvhNode>> {
+        if let Some((budget, _)) = &self.budget {
+            let now = budget.clock.load(Ordering::Relaxed);
+            if self.slot.last_used.load(Ordering::Relaxed) != now {
+                self.slot.last_used.store(now, Ordering::Relaxed);
+            }
+        }
+        if self.slot.empty.load(Ordering::Acquire) {
+            return None;
+        }
+        if let Some(bvh) = self.slot.bvh.read().unwrap().as_ref() {
+            return Some(bvh.clone());
+        }
+
+        let _guard = self.building.lock().unwrap();
+        if let Some(bvh) = self.slot.bvh.read().unwrap().as_ref() {
+            return Some(bvh.clone());
+        }
+        let list = (self.loader)();
+        if list.is_empty() {
+            self.slot.empty.store(true, Ordering::Release);
+            return None;
+        }
+        let bvh = Arc::new(BvhNode::new(&list));
+        *self.slot.bvh.write().unwrap() = Some(bvh.clone());
+        if let Some((budget, bytes)) = &self.budget {
+            let now = budget.clock.fetch_add(1, Ordering::Relaxed) + 1;
+            self.slot.last_used.store(now, Ordering::Relaxed);
+            budget.admit(&self.slot, *bytes);
+        }
+        Some(bvh)
+    }
+}
+
+impl Hittable for LazyBvh {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        // 先用已知的包围盒剔除，避免无谓地构建
+        self.bbox.hit(r, ray_t) && self.bvh().is_some_and(|bvh| bvh.hit(r, ray_t, rec))
+    }
+
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        self.bbox.hit(r, ray_t) && self.bvh().is_some_and(|bvh| bvh.hit_any(r, ray_t))
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.bbox)
+    }
+
+    /// 未构建的块只报告自身包围盒，不为调试显示而触发构建
+    fn collect_debug_boxes(&self, depth: usize, leaves_only: bool, boxes: &mut Vec<(Aabb, usize)>) {
+        match self.slot.bvh.read().unwrap().as_ref() {
+            Some(bvh) if !leaves_only => bvh.collect_debug_boxes(depth, leaves_only, boxes),
+            _ => boxes.push((self.bbox, depth)),
+        }
+    }
+}
+
+impl std::fmt::Debug for LazyBvh {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LazyBvh")
+            .field("bbox", &self.bbox)
+            .field("resident", &self.is_resident())
+            .finish()
+    }
+}
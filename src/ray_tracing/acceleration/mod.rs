@@ -1 +1,51 @@
 pub mod bvh;
+pub mod grid;
+pub mod kdtree;
+pub mod lazy_bvh;
+
+use crate::ray_tracing::geometry::hittable::Hittable;
+use crate::ray_tracing::geometry::hittable_list::HittableList;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// 场景顶层使用的加速结构
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Accelerator {
+    /// 按物体划分的包围盒层次结构，构建快，适用面最广
+    #[default]
+    Bvh,
+    /// 按空间划分的 k-d 树（SAH），物体密集、遮挡多的场景遍历更快
+    KdTree,
+    /// 均匀网格，适合大量尺寸相近、分布均匀的物体
+    Grid,
+}
+
+impl Accelerator {
+    /// 为物体列表构建加速结构
+    pub fn build(self, list: &HittableList) -> Arc<dyn Hittable> {
+        if list.is_empty() {
+            return Arc::new(HittableList::new());
+        }
+        match self {
+            Self::Bvh => Arc::new(bvh::BvhNode::new(list)),
+            Self::KdTree => Arc::new(kdtree::KdTree::new(list)),
+            Self::Grid => Arc::new(grid::UniformGrid::new(list)),
+        }
+    }
+}
+
+impl FromStr for Accelerator {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "bvh" => Ok(Self::Bvh),
+            "kdtree" | "kd-tree" | "kd" => Ok(Self::KdTree),
+            "grid" => Ok(Self::Grid),
+            other => Err(format!(
+                "未知的加速结构: {}（可选 bvh、kdtree、grid）",
+                other
+            )),
+        }
+    }
+}
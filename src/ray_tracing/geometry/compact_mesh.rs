@@ -0,0 +1,410 @@
+//! 紧凑三角网格：顶点、索引和 BVH 存放在连续的数组中，标量精度可选 f32 或 f64
+//!
+//! [`TriangleMesh`](super::mesh::TriangleMesh) 为每个三角形分配一个 `Triangle` 对象并建立通用的 BVH，
+//! 每个三角形约占数百字节；紧凑网格只保存顶点数组、u32 索引和扁平的 BVH 节点数组，
+//! 求交在所选精度下进行，命中后再换算为 f64 的命中记录。使用 f32 时内存约为 f64 的一半，
+//! 精度的取舍见 [`Scalar`]。
+//!
+//! 紧凑网格不作为面光源采样，也不支持烘焙光照贴图。
+
+use super::hittable::{HitRecord, Hittable};
+use super::mesh::MeshData;
+use crate::ray_tracing::materials::material::Material;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::scalar::Scalar;
+use crate::ray_tracing::math::vec3::*;
+use nalgebra::Vector3;
+use std::sync::Arc;
+
+/// 叶节点最多包含的三角形数
+const LEAF_TRIANGLES: usize = 4;
+/// 遍历栈的深度（按中位数划分时树高约为 log2(三角形数)）
+const STACK_DEPTH: usize = 64;
+
+/// 扁平 BVH 节点：内部节点的左子节点紧随其后，offset 为右子节点；叶节点的 offset 为首个三角形
+#[derive(Debug, Clone, Copy)]
+struct Node<S> {
+    min: [S; 3],
+    max: [S; 3],
+    offset: u32,
+    count: u32,
+}
+
+/// 最近的交点（三角形序号、t 和重心坐标）
+struct Closest<S> {
+    triangle: usize,
+    t: S,
+    b1: S,
+    b2: S,
+}
+
+/// 标量精度为 S 的紧凑三角网格
+#[derive(Clone)]
+pub struct CompactMesh<S: Scalar> {
+    positions: Arc<[[S; 3]]>,
+    normals: Arc<[[S; 3]]>,
+    uvs: Arc<[[S; 2]]>,
+    triangles: Arc<[[u32; 3]]>,
+    nodes: Arc<[Node<S>]>,
+    bbox: Option<Aabb>,
+    mat: Arc<dyn Material>,
+}
+
+impl<S: Scalar> CompactMesh<S> {
+    /// 从顶点数据创建网格，坐标舍入到精度 S，跳过越界索引和舍入后退化的三角形
+    ///
+    /// 顶点数超出 u32 索引范围时返回None。
+    pub fn new(data: &MeshData, mat: Arc<dyn Material>) -> Option<Self> {
+        let vertex_count = data.positions.len();
+        u32::try_from(vertex_count).ok()?;
+        let positions: Vec<[S; 3]> = data
+            .positions
+            .iter()
+            .map(|p| [p.x, p.y, p.z].map(S::narrow))
+            .collect();
+        let normals: Vec<[S; 3]> = if data.normals.len() == vertex_count {
+            data.normals
+                .iter()
+                .map(|n| {
+                    let n = n.normalize();
+                    [n.x, n.y, n.z].map(S::narrow)
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let uvs: Vec<[S; 2]> = if data.uvs.len() == vertex_count {
+            data.uvs
+                .iter()
+                .map(|&(u, v)| [S::narrow(u), S::narrow(v)])
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let vertex = |i: u32| -> Vec3 { to_vec3(&positions[i as usize]) };
+        let mut triangles: Vec<[u32; 3]> = data
+            .indices
+            .iter()
+            .filter(|face| face.iter().all(|&i| i < vertex_count))
+            .map(|face| face.map(|i| i as u32))
+            .filter(|&[a, b, c]| {
+                let (p0, p1, p2) = (vertex(a), vertex(b), vertex(c));
+                (p1 - p0).cross(&(p2 - p0)).norm_squared() > 0.0
+            })
+            .collect();
+
+        let mut nodes = Vec::with_capacity(2 * triangles.len() / LEAF_TRIANGLES + 1);
+        let bbox = if triangles.is_empty() {
+            None
+        } else {
+            let mut items: Vec<(u32, [u32; 3], Vec3)> = triangles
+                .iter()
+                .enumerate()
+                .map(|(k, &[a, b, c])| {
+                    let centroid = (vertex(a) + vertex(b) + vertex(c)) / 3.0;
+                    (k as u32, [a, b, c], centroid)
+                })
+                .collect();
+            build(&mut items, 0, &vertex, &mut nodes);
+            // 三角形按叶节点的顺序重新排列
+            triangles = items.into_iter().map(|(_, face, _)| face).collect();
+            let root = &nodes[0];
+            Some(Aabb::new_point(
+                Point3::from(to_vec3(&root.min)),
+                Point3::from(to_vec3(&root.max)),
+            ))
+        };
+
+        Some(Self {
+            positions: positions.into(),
+            normals: normals.into(),
+            uvs: uvs.into(),
+            triangles: triangles.into(),
+            nodes: nodes.into(),
+            bbox,
+            mat,
+        })
+    }
+
+    /// 共享几何和BVH、换用另一个材质的网格
+    pub fn with_material(&self, mat: Arc<dyn Material>) -> Self {
+        Self {
+            mat,
+            ..self.clone()
+        }
+    }
+
+    /// 三角形数量（不含被跳过的退化三角形）
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.triangles.len()
+    }
+
+    /// 检查是否为空
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+
+    /// 顶点、索引和 BVH 占用的字节数
+    pub fn memory_bytes(&self) -> usize {
+        std::mem::size_of_val(self.positions.as_ref())
+            + std::mem::size_of_val(self.normals.as_ref())
+            + std::mem::size_of_val(self.uvs.as_ref())
+            + std::mem::size_of_val(self.triangles.as_ref())
+            + std::mem::size_of_val(self.nodes.as_ref())
+    }
+
+    #[inline]
+    fn vertex(&self, i: u32) -> Vector3<S> {
+        let [x, y, z] = self.positions[i as usize];
+        Vector3::new(x, y, z)
+    }
+
+    /// 在 S 精度下遍历 BVH，any 为真时找到任意交点即返回
+    fn intersect(&self, r: &Ray, ray_t: Interval, any: bool) -> Option<Closest<S>> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let orig = Vector3::new(r.orig.x, r.orig.y, r.orig.z).map(S::narrow);
+        let dir = Vector3::new(r.dir.x, r.dir.y, r.dir.z).map(S::narrow);
+        let inv_dir = dir.map(|d| S::one() / d);
+        let t_min = S::narrow(ray_t.min);
+        let mut t_max = S::narrow(ray_t.max);
+        // 包围盒测试的远端按舍入误差放宽，避免漏掉擦边的交点
+        let slack = S::narrow(1.0 + 6.0 * S::UNIT_ROUNDOFF);
+
+        let mut closest = None;
+        let mut stack = [0u32; STACK_DEPTH];
+        let mut top = 1;
+        while top > 0 {
+            top -= 1;
+            let index = stack[top] as usize;
+            let node = &self.nodes[index];
+            if !slab_test(node, &orig, &inv_dir, t_min, t_max * slack) {
+                continue;
+            }
+            if node.count == 0 {
+                if top + 2 > STACK_DEPTH {
+                    continue;
+                }
+                stack[top] = node.offset;
+                stack[top + 1] = index as u32 + 1;
+                top += 2;
+                continue;
+            }
+            let first = node.offset as usize;
+            for k in first..first + node.count as usize {
+                if let Some((t, b1, b2)) = self.hit_triangle(k, &orig, &dir, t_min, t_max) {
+                    t_max = t;
+                    closest = Some(Closest {
+                        triangle: k,
+                        t,
+                        b1,
+                        b2,
+                    });
+                    if any {
+                        return closest;
+                    }
+                }
+            }
+        }
+        closest
+    }
+
+    /// Möller–Trumbore 求交，返回 (t, b1, b2)
+    #[inline]
+    fn hit_triangle(
+        &self,
+        k: usize,
+        orig: &Vector3<S>,
+        dir: &Vector3<S>,
+        t_min: S,
+        t_max: S,
+    ) -> Option<(S, S, S)> {
+        let [a, b, c] = self.triangles[k];
+        let p0 = self.vertex(a);
+        let e1 = self.vertex(b) - p0;
+        let e2 = self.vertex(c) - p0;
+        let pvec = dir.cross(&e2);
+        let det = e1.dot(&pvec);
+        if det == S::zero() {
+            return None;
+        }
+        let inv_det = S::one() / det;
+        let tvec = orig - p0;
+        let b1 = tvec.dot(&pvec) * inv_det;
+        if b1 < S::zero() || b1 > S::one() {
+            return None;
+        }
+        let qvec = tvec.cross(&e1);
+        let b2 = dir.dot(&qvec) * inv_det;
+        if b2 < S::zero() || b1 + b2 > S::one() {
+            return None;
+        }
+        let t = e2.dot(&qvec) * inv_det;
+        (t > t_min && t < t_max).then_some((t, b1, b2))
+    }
+
+    /// 由交点填写 f64 的命中记录
+    fn fill_record(&self, r: &Ray, hit: &Closest<S>, rec: &mut HitRecord) {
+        let [a, b, c] = self.triangles[hit.triangle];
+        let p0 = to_vec3(&self.positions[a as usize]);
+        let e1 = to_vec3(&self.positions[b as usize]) - p0;
+        let e2 = to_vec3(&self.positions[c as usize]) - p0;
+        let (b1, b2) = (hit.b1.widen(), hit.b2.widen());
+        let b0 = 1.0 - b1 - b2;
+
+        let uv = |i: u32, default: (f64, f64)| -> (f64, f64) {
+            self.uvs
+                .get(i as usize)
+                .map_or(default, |[u, v]| (u.widen(), v.widen()))
+        };
+        let uvs = [uv(a, (0.0, 0.0)), uv(b, (1.0, 0.0)), uv(c, (0.0, 1.0))];
+
+        rec.t = hit.t.widen();
+        rec.p = r.at(rec.t);
+        rec.u = b0 * uvs[0].0 + b1 * uvs[1].0 + b2 * uvs[2].0;
+        rec.v = b0 * uvs[0].1 + b1 * uvs[1].1 + b2 * uvs[2].1;
+        (rec.dpdu, rec.dpdv) = uv_derivatives(&uvs, &e1, &e2);
+        rec.mat = self.mat.clone();
+        rec.set_face_normal(r, &e1.cross(&e2).normalize());
+
+        // 平滑着色：插值法线翻到几何法线所在的一侧
+        if !self.normals.is_empty() {
+            let n = |i: u32| to_vec3(&self.normals[i as usize]);
+            let shading = b0 * n(a) + b1 * n(b) + b2 * n(c);
+            if shading.norm_squared() > 1e-12 {
+                let shading = shading.normalize();
+                rec.normal = if shading.dot(&rec.normal) < 0.0 {
+                    -shading
+                } else {
+                    shading
+                };
+            }
+        }
+    }
+}
+
+#[inline]
+fn to_vec3<S: Scalar>(v: &[S; 3]) -> Vec3 {
+    Vec3::new(v[0].widen(), v[1].widen(), v[2].widen())
+}
+
+/// 由纹理坐标求位置对 (u, v) 的偏导数，纹理坐标退化时为零（与 `Triangle` 一致）
+fn uv_derivatives(uvs: &[(f64, f64); 3], e1: &Vec3, e2: &Vec3) -> (Vec3, Vec3) {
+    let du1 = uvs[1].0 - uvs[0].0;
+    let dv1 = uvs[1].1 - uvs[0].1;
+    let du2 = uvs[2].0 - uvs[0].0;
+    let dv2 = uvs[2].1 - uvs[0].1;
+    let det = du1 * dv2 - dv1 * du2;
+    if det.abs() < 1e-12 {
+        return (Vec3::zeros(), Vec3::zeros());
+    }
+    let inv = 1.0 / det;
+    ((dv2 * e1 - dv1 * e2) * inv, (du1 * e2 - du2 * e1) * inv)
+}
+
+/// 光线与节点包围盒的区间是否与 (t_min, t_max) 重叠
+#[inline]
+fn slab_test<S: Scalar>(
+    node: &Node<S>,
+    orig: &Vector3<S>,
+    inv_dir: &Vector3<S>,
+    mut t_min: S,
+    mut t_max: S,
+) -> bool {
+    for axis in 0..3 {
+        let t0 = (node.min[axis] - orig[axis]) * inv_dir[axis];
+        let t1 = (node.max[axis] - orig[axis]) * inv_dir[axis];
+        let (near, far) = if t0 <= t1 { (t0, t1) } else { (t1, t0) };
+        // NaN（光线在包围盒面上且方向分量为0）时保持原区间
+        if near > t_min {
+            t_min = near;
+        }
+        if far < t_max {
+            t_max = far;
+        }
+        if t_max < t_min {
+            return false;
+        }
+    }
+    true
+}
+
+/// 按质心在最长轴上的中位数递归划分，节点包围盒向外舍入以包含 S 精度下的三角形
+fn build<S: Scalar>(
+    items: &mut [(u32, [u32; 3], Vec3)],
+    first: usize,
+    vertex: &impl Fn(u32) -> Vec3,
+    nodes: &mut Vec<Node<S>>,
+) {
+    let (mut min, mut max) = (Vec3::repeat(f64::INFINITY), Vec3::repeat(f64::NEG_INFINITY));
+    let (mut cmin, mut cmax) = (min, max);
+    for (_, face, centroid) in items.iter() {
+        for &i in face {
+            let p = vertex(i);
+            min = min.inf(&p);
+            max = max.sup(&p);
+        }
+        cmin = cmin.inf(centroid);
+        cmax = cmax.sup(centroid);
+    }
+    let pad = |x: f64| x.abs() * 4.0 * S::UNIT_ROUNDOFF;
+    let index = nodes.len();
+    nodes.push(Node {
+        min: [min.x, min.y, min.z].map(|x| S::narrow(x - pad(x))),
+        max: [max.x, max.y, max.z].map(|x| S::narrow(x + pad(x))),
+        offset: first as u32,
+        count: items.len() as u32,
+    });
+    if items.len() <= LEAF_TRIANGLES {
+        return;
+    }
+
+    let extent = cmax - cmin;
+    let axis = extent.imax();
+    let mid = items.len() / 2;
+    items.select_nth_unstable_by(mid, |a, b| a.2[axis].total_cmp(&b.2[axis]));
+    let (left, right) = items.split_at_mut(mid);
+    nodes[index].count = 0;
+    build(left, first, vertex, nodes);
+    nodes[index].offset = nodes.len() as u32;
+    build(right, first + mid, vertex, nodes);
+}
+
+impl<S: Scalar> Hittable for CompactMesh<S> {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        match self.intersect(r, ray_t, false) {
+            Some(hit) => {
+                self.fill_record(r, &hit, rec);
+                true
+            }
+            None => false,
+        }
+    }
+
+    #[inline]
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        self.intersect(r, ray_t, true).is_some()
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.bbox
+    }
+}
+
+impl<S: Scalar> std::fmt::Debug for CompactMesh<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompactMesh")
+            .field("scalar", &S::NAME)
+            .field("triangles", &self.triangles.len())
+            .field("nodes", &self.nodes.len())
+            .field("bbox", &self.bbox)
+            .finish()
+    }
+}
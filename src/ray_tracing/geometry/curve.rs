@@ -0,0 +1,294 @@
+use super::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::materials::material::Material;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::onb::ONB;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use std::sync::Arc;
+
+/// 最大细分深度，2^10 段足以覆盖任何实际的曲率
+const MAX_SUBDIVISION_DEPTH: u32 = 10;
+
+/// 曲线的横截面形状
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CurveType {
+    /// 始终正对光线的扁平条带，适合远处的细发丝和草叶
+    Flat,
+    /// 圆管，法线沿横截面变化，适合近处的粗发丝
+    #[default]
+    Cylinder,
+}
+
+/// 三次贝塞尔曲线（毛发、草叶等细长条带），宽度沿曲线从 width0 线性变化到 width1
+///
+/// 求交方法参照 pbrt-v3：把控制点变换到光线坐标系（光线沿 +z 轴），递归地用 de Casteljau 细分，
+/// 剔除扩展了半宽度的包围盒不包含光线的子段，在足够平直的子段上直接求最近点。
+/// 命中时 u 为曲线参数（根部为0），v 为横跨宽度的坐标，dpdu 为曲线切线。
+pub struct Curve {
+    cp: [Point3; 4],        // 控制点
+    width: [f64; 2],        // 两端宽度
+    kind: CurveType,        // 横截面形状
+    mat: Arc<dyn Material>, // 材质
+    bbox: Aabb,             // 包围盒
+    max_depth: u32,         // 细分深度，由曲率和宽度决定
+}
+
+impl Curve {
+    /// 创建曲线
+    pub fn new(
+        cp: [Point3; 4],
+        width0: f64,
+        width1: f64,
+        kind: CurveType,
+        mat: Arc<dyn Material>,
+    ) -> Self {
+        let width = [width0.max(0.0), width1.max(0.0)];
+        let max_width = width[0].max(width[1]);
+        let bbox = Aabb::new_point(cp[0], cp[1])
+            .merge(&Aabb::new_point(cp[2], cp[3]))
+            .expand(max_width);
+
+        // 细分到子段的控制多边形与弦的偏差小于宽度的 5%
+        let l0 = (0..2)
+            .map(|i| ((cp[i] - cp[i + 1]) + (cp[i + 2] - cp[i + 1])).amax())
+            .fold(0.0, f64::max);
+        let eps = (max_width * 0.05).max(1e-12);
+        let r0 = (std::f64::consts::SQRT_2 * 6.0 * l0 / (8.0 * eps)).log2() * 0.5;
+        let max_depth = if r0.is_finite() {
+            r0.round().clamp(0.0, MAX_SUBDIVISION_DEPTH as f64) as u32
+        } else {
+            0
+        };
+
+        Self {
+            cp,
+            width,
+            kind,
+            mat,
+            bbox,
+            max_depth,
+        }
+    }
+
+    /// 曲线参数 u 处的位置
+    #[inline]
+    pub fn point(&self, u: f64) -> Point3 {
+        eval_bezier(&self.cp, u).0
+    }
+
+    /// 曲线参数 u 处的宽度
+    #[inline]
+    pub fn width_at(&self, u: f64) -> f64 {
+        self.width[0] + (self.width[1] - self.width[0]) * u
+    }
+
+    /// 在光线坐标系中递归细分求交，找到更近的交点时更新 best 和 z_max
+    #[allow(clippy::too_many_arguments)]
+    fn recursive_intersect(
+        &self,
+        cp: &[Point3; 4],
+        u0: f64,
+        u1: f64,
+        depth: u32,
+        z_min: f64,
+        z_max: &mut f64,
+        best: &mut Option<CurveHit>,
+    ) {
+        // 子段包围盒（扩展半宽度）必须包含光线所在的 z 轴
+        let max_width = self.width_at(u0).max(self.width_at(u1));
+        let bounds = Aabb::new_point(cp[0], cp[1])
+            .merge(&Aabb::new_point(cp[2], cp[3]))
+            .expand(max_width);
+        if !bounds.x.contains(0.0)
+            || !bounds.y.contains(0.0)
+            || bounds.z.max < z_min
+            || bounds.z.min > *z_max
+        {
+            return;
+        }
+
+        if depth > 0 {
+            let (left, right) = split_bezier(cp);
+            let mid = 0.5 * (u0 + u1);
+            self.recursive_intersect(&left, u0, mid, depth - 1, z_min, z_max, best);
+            self.recursive_intersect(&right, mid, u1, depth - 1, z_min, z_max, best);
+            return;
+        }
+
+        // 光线必须落在子段两端的垂直平面之间
+        let edge = (cp[1].y - cp[0].y) * -cp[0].y + cp[0].x * (cp[0].x - cp[1].x);
+        if edge < 0.0 {
+            return;
+        }
+        let edge = (cp[2].y - cp[3].y) * -cp[3].y + cp[3].x * (cp[3].x - cp[2].x);
+        if edge < 0.0 {
+            return;
+        }
+
+        // 把子段近似为弦，求弦上离光线最近的参数
+        let (sx, sy) = (cp[3].x - cp[0].x, cp[3].y - cp[0].y);
+        let denom = sx * sx + sy * sy;
+        if denom == 0.0 {
+            return;
+        }
+        let w = ((-cp[0].x * sx - cp[0].y * sy) / denom).clamp(0.0, 1.0);
+        let u = (u0 + (u1 - u0) * w).clamp(u0, u1);
+        let hit_width = self.width_at(u);
+        let (pc, dpcdw) = eval_bezier(cp, w);
+        let dist2 = pc.x * pc.x + pc.y * pc.y;
+        if dist2 > hit_width * hit_width * 0.25 {
+            return;
+        }
+
+        let dist = dist2.sqrt();
+        let radius = 0.5 * hit_width;
+        let z = match self.kind {
+            CurveType::Flat => pc.z,
+            // 光线进入圆管的位置比中心线靠前
+            CurveType::Cylinder => pc.z - (radius * radius - dist2).max(0.0).sqrt(),
+        };
+        if z < z_min || z > *z_max {
+            return;
+        }
+
+        // 光线在曲线左侧时 v < 0.5
+        let side = dpcdw.x * -pc.y + pc.x * dpcdw.y;
+        let v = if hit_width > 0.0 {
+            if side > 0.0 {
+                0.5 + dist / hit_width
+            } else {
+                0.5 - dist / hit_width
+            }
+        } else {
+            0.5
+        };
+        *z_max = z;
+        *best = Some(CurveHit {
+            z,
+            u,
+            v,
+            offset: Vec3::new(-pc.x, -pc.y, z - pc.z),
+        });
+    }
+}
+
+/// 光线坐标系中的交点
+struct CurveHit {
+    z: f64,       // 沿光线的距离（光线方向已归一化）
+    u: f64,       // 曲线参数
+    v: f64,       // 横跨宽度的坐标
+    offset: Vec3, // 交点相对中心线的偏移（光线坐标系）
+}
+
+/// 三次贝塞尔曲线在 t 处的位置和导数
+#[inline]
+fn eval_bezier(cp: &[Point3; 4], t: f64) -> (Point3, Vec3) {
+    let lerp = |a: &Point3, b: &Point3| a + (b - a) * t;
+    let c1 = [
+        lerp(&cp[0], &cp[1]),
+        lerp(&cp[1], &cp[2]),
+        lerp(&cp[2], &cp[3]),
+    ];
+    let c2 = [lerp(&c1[0], &c1[1]), lerp(&c1[1], &c1[2])];
+    let derivative = c2[1] - c2[0];
+    let derivative = if derivative.norm_squared() > 0.0 {
+        3.0 * derivative
+    } else {
+        cp[3] - cp[0]
+    };
+    (lerp(&c2[0], &c2[1]), derivative)
+}
+
+/// de Casteljau 二分
+#[inline]
+fn split_bezier(cp: &[Point3; 4]) -> ([Point3; 4], [Point3; 4]) {
+    let mid = |a: &Point3, b: &Point3| a + (b - a) * 0.5;
+    let p01 = mid(&cp[0], &cp[1]);
+    let p12 = mid(&cp[1], &cp[2]);
+    let p23 = mid(&cp[2], &cp[3]);
+    let p012 = mid(&p01, &p12);
+    let p123 = mid(&p12, &p23);
+    let p0123 = mid(&p012, &p123);
+    ([cp[0], p01, p012, p0123], [p0123, p123, p23, cp[3]])
+}
+
+impl Hittable for Curve {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let length = r.dir.norm();
+        if length == 0.0 || !self.bbox.hit(r, ray_t) {
+            return false;
+        }
+
+        // 光线坐标系：z 沿光线方向，x 垂直于光线和曲线的弦，使曲线大致沿 y 方向
+        let dz = r.dir / length;
+        let dx = dz.cross(&(self.cp[3] - self.cp[0]));
+        let dx = if dx.norm_squared() > 1e-24 {
+            dx.normalize()
+        } else {
+            ONB::new(&dz).u()
+        };
+        let dy = dz.cross(&dx);
+        let to_ray = |p: &Point3| {
+            let d = p - r.orig;
+            Point3::new(d.dot(&dx), d.dot(&dy), d.dot(&dz))
+        };
+        let cp = self.cp.map(|p| to_ray(&p));
+
+        let mut z_max = ray_t.max * length;
+        let mut best = None;
+        self.recursive_intersect(
+            &cp,
+            0.0,
+            1.0,
+            self.max_depth,
+            ray_t.min * length,
+            &mut z_max,
+            &mut best,
+        );
+        let Some(hit) = best else {
+            return false;
+        };
+
+        let t = hit.z / length;
+        let tangent = eval_bezier(&self.cp, hit.u).1;
+        let tangent_dir = tangent.normalize();
+        let normal = match self.kind {
+            CurveType::Flat => -dz,
+            CurveType::Cylinder => dx * hit.offset.x + dy * hit.offset.y + dz * hit.offset.z,
+        };
+        // 去掉法线的切向分量
+        let normal = normal - tangent_dir * normal.dot(&tangent_dir);
+        let normal = if normal.norm_squared() > 1e-24 {
+            normal.normalize()
+        } else {
+            -dz
+        };
+
+        rec.t = t;
+        rec.p = r.at(t);
+        rec.u = hit.u;
+        rec.v = hit.v;
+        rec.dpdu = tangent;
+        rec.dpdv = normal.cross(&tangent_dir) * self.width_at(hit.u);
+        rec.mat = self.mat.clone();
+        rec.set_face_normal(r, &normal);
+        true
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.bbox)
+    }
+}
+
+impl std::fmt::Debug for Curve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Curve")
+            .field("cp", &self.cp)
+            .field("width", &self.width)
+            .field("kind", &self.kind)
+            .field("max_depth", &self.max_depth)
+            .finish()
+    }
+}
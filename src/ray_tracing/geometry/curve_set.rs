@@ -0,0 +1,80 @@
+use super::curve::Curve;
+use super::hittable::{HitRecord, Hittable};
+use super::hittable_list::HittableList;
+use crate::ray_tracing::acceleration::bvh::BvhNode;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use std::sync::Arc;
+
+/// 曲线集合（一整片毛发或草地）：内部用独立的BVH组织大量细长曲线
+///
+/// 作为一个物体加入场景，避免成千上万根曲线与其他物体混在同一棵BVH中。
+pub struct CurveSet {
+    curves: Vec<Arc<Curve>>,
+    bvh: Option<BvhNode>,
+}
+
+impl CurveSet {
+    /// 从曲线列表创建集合
+    pub fn new(curves: Vec<Curve>) -> Self {
+        let curves: Vec<Arc<Curve>> = curves.into_iter().map(Arc::new).collect();
+        let bvh = (!curves.is_empty()).then(|| {
+            let list: HittableList = curves
+                .iter()
+                .map(|c| c.clone() as Arc<dyn Hittable>)
+                .collect();
+            BvhNode::new(&list)
+        });
+        Self { curves, bvh }
+    }
+
+    /// 曲线数量
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.curves.len()
+    }
+
+    /// 检查是否为空
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.curves.is_empty()
+    }
+}
+
+impl Hittable for CurveSet {
+    #[inline]
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        self.bvh.as_ref().is_some_and(|bvh| bvh.hit(r, ray_t, rec))
+    }
+
+    #[inline]
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        self.bvh.as_ref().is_some_and(|bvh| bvh.hit_any(r, ray_t))
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.bvh.as_ref().and_then(|bvh| bvh.bounding_box())
+    }
+
+    fn collect_debug_boxes(&self, depth: usize, leaves_only: bool, boxes: &mut Vec<(Aabb, usize)>) {
+        match &self.bvh {
+            Some(bvh) if !leaves_only => bvh.collect_debug_boxes(depth, leaves_only, boxes),
+            _ => {
+                if let Some(bbox) = self.bounding_box() {
+                    boxes.push((bbox, depth));
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for CurveSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CurveSet")
+            .field("curves", &self.curves.len())
+            .field("bbox", &self.bounding_box())
+            .finish()
+    }
+}
@@ -0,0 +1,238 @@
+//! 位移球体：按高度图纹理沿径向抬高表面，得到有真实地形轮廓的星球
+//!
+//! 表面半径为 `radius + scale · h(u, v)`，h 是高度纹理在球面 UV 处的亮度（截断到 [0,1]），
+//! UV 参数化与 [`Sphere`] 相同，同一张地图既可以作颜色纹理也可以作高度图。
+//!
+//! 表面位于半径 radius 与 radius + scale 的两个球之间的壳层内。求交时在光线与壳层重叠的区间上
+//! 等距取样，检查点到球心的距离与该方向上表面半径之差的符号，找到第一次变号的区间后二分细化。
+//! 细化结果取在光线起点一侧，从交点出发的阴影光线不会立即再次命中自身。
+//! 法线由相邻 UV 处表面点的差分求出，包含地形的起伏。取样间隔大于地形细节时，掠射的细小凸起可能被跳过。
+
+use super::hittable::{HitRecord, Hittable};
+use super::sphere::Sphere;
+use crate::ray_tracing::materials::material::Material;
+use crate::ray_tracing::materials::texture::TexturePtr;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::rendering::color::luminance;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// 默认的壳层内取样数
+pub const DEFAULT_STEPS: usize = 128;
+/// 默认的二分细化次数
+pub const DEFAULT_REFINEMENT: usize = 20;
+/// 求法线时 UV 差分的步长
+const NORMAL_DELTA: f64 = 1e-3;
+
+/// 按高度图位移的球体
+#[derive(Debug)]
+pub struct DisplacedSphere {
+    center: Point3,
+    radius: f64,
+    height: TexturePtr,
+    scale: f64,
+    mat: Arc<dyn Material>,
+    bbox: Aabb,
+    steps: usize,
+    refinement: usize,
+}
+
+impl DisplacedSphere {
+    /// 创建位移球体：高度为0处半径为 radius，高度为1处为 radius + scale
+    pub fn new(
+        center: Point3,
+        radius: f64,
+        height: TexturePtr,
+        scale: f64,
+        mat: Arc<dyn Material>,
+    ) -> Self {
+        let scale = scale.max(0.0);
+        let outer = Vec3::repeat(radius + scale);
+        Self {
+            center,
+            radius,
+            height,
+            scale,
+            mat,
+            bbox: Aabb::new_point(center - outer, center + outer),
+            steps: DEFAULT_STEPS,
+            refinement: DEFAULT_REFINEMENT,
+        }
+    }
+
+    /// 设置壳层内的取样数，地形越陡峭、位移越大需要越多
+    pub fn with_steps(mut self, steps: usize) -> Self {
+        self.steps = steps.max(1);
+        self
+    }
+
+    /// 设置二分细化次数
+    pub fn with_refinement(mut self, refinement: usize) -> Self {
+        self.refinement = refinement;
+        self
+    }
+
+    /// 球心
+    #[inline]
+    pub fn center(&self) -> Point3 {
+        self.center
+    }
+
+    /// 高度为0处的半径
+    #[inline]
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// 最大位移
+    #[inline]
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// UV 处的单位方向，`Sphere::get_sphere_uv` 的逆映射
+    #[inline]
+    fn direction(u: f64, v: f64) -> Vec3 {
+        let theta = v * PI;
+        let phi = u * 2.0 * PI - PI;
+        Vec3::new(
+            theta.sin() * phi.cos(),
+            -theta.cos(),
+            -theta.sin() * phi.sin(),
+        )
+    }
+
+    /// UV 处的表面半径
+    fn surface_radius(&self, u: f64, v: f64, direction: &Vec3) -> f64 {
+        let p = self.center + self.radius * direction;
+        let h = luminance(&self.height.value(u, v, &p)).clamp(0.0, 1.0);
+        self.radius + self.scale * h
+    }
+
+    /// UV 处的表面点
+    fn surface_point(&self, u: f64, v: f64) -> Point3 {
+        let (u, v) = (u.rem_euclid(1.0), v.clamp(0.0, 1.0));
+        let direction = Self::direction(u, v);
+        self.center + self.surface_radius(u, v, &direction) * direction
+    }
+
+    /// 点到球心的距离减去该方向上的表面半径：表面外为正、内部为负
+    fn height_above_surface(&self, p: &Point3) -> f64 {
+        let offset = p - self.center;
+        let distance = offset.norm();
+        if distance == 0.0 {
+            return -self.radius;
+        }
+        let direction = offset / distance;
+        let (u, v) = Sphere::get_sphere_uv(&direction);
+        distance - self.surface_radius(u, v, &direction)
+    }
+
+    /// 光线与半径为 radius 的同心球的两个交点参数
+    fn sphere_roots(&self, r: &Ray, radius: f64) -> Option<(f64, f64)> {
+        let oc = r.orig - self.center;
+        let a = r.dir.norm_squared();
+        let half_b = oc.dot(&r.dir);
+        let c = oc.norm_squared() - radius * radius;
+        let discriminant = half_b * half_b - a * c;
+        if a == 0.0 || discriminant < 0.0 {
+            return None;
+        }
+        let sqrtd = discriminant.sqrt();
+        Some(((-half_b - sqrtd) / a, (-half_b + sqrtd) / a))
+    }
+
+    /// 交点处的外法线和 (∂p/∂u, ∂p/∂v)，由表面点的中心差分求出
+    fn frame(&self, u: f64, v: f64, radial: &Vec3) -> (Vec3, Vec3, Vec3) {
+        let (du, dv) = (NORMAL_DELTA, NORMAL_DELTA.min(v).min(1.0 - v).max(1e-6));
+        let dpdu = (self.surface_point(u + du, v) - self.surface_point(u - du, v)) / (2.0 * du);
+        let dpdv = (self.surface_point(u, v + dv) - self.surface_point(u, v - dv)) / (2.0 * dv);
+        let normal = match dpdv.cross(&dpdu).try_normalize(1e-12) {
+            // 两极附近 ∂p/∂u 退化，退回到径向
+            Some(n) if n.dot(radial) > 0.0 => n,
+            Some(n) if n.dot(radial) < 0.0 => -n,
+            _ => *radial,
+        };
+        (normal, dpdu, dpdv)
+    }
+}
+
+impl Hittable for DisplacedSphere {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let Some((outer_near, outer_far)) = self.sphere_roots(r, self.radius + self.scale) else {
+            return false;
+        };
+        let start = outer_near.max(ray_t.min);
+        let mut end = outer_far.min(ray_t.max);
+        // 高度不低于0，进入内球时一定已经穿过表面
+        if let Some((inner_near, _)) = self.sphere_roots(r, self.radius)
+            && inner_near > start
+        {
+            end = end.min(inner_near);
+        }
+        if start >= end {
+            return false;
+        }
+
+        let mut previous = (start, self.height_above_surface(&r.at(start)));
+        let outside = previous.1 > 0.0;
+        let step = (end - start) / self.steps as f64;
+        let mut bracket = None;
+        for i in 1..=self.steps {
+            let t = if i == self.steps {
+                end
+            } else {
+                start + step * i as f64
+            };
+            let h = self.height_above_surface(&r.at(t));
+            if (h > 0.0) != outside {
+                bracket = Some((previous.0, t));
+                break;
+            }
+            previous = (t, h);
+        }
+        let Some((mut near, mut far)) = bracket else {
+            return false;
+        };
+
+        // 二分细化，near 始终在光线起点一侧
+        for _ in 0..self.refinement {
+            let mid = 0.5 * (near + far);
+            if (self.height_above_surface(&r.at(mid)) > 0.0) == outside {
+                near = mid;
+            } else {
+                far = mid;
+            }
+        }
+        if !ray_t.surrounds(near) {
+            return false;
+        }
+
+        rec.t = near;
+        rec.p = r.at(near);
+        let radial = (rec.p - self.center).normalize();
+        let (u, v) = Sphere::get_sphere_uv(&radial);
+        let (normal, dpdu, dpdv) = self.frame(u, v, &radial);
+        rec.u = u;
+        rec.v = v;
+        rec.dpdu = dpdu;
+        rec.dpdv = dpdv;
+        rec.set_face_normal(r, &normal);
+        rec.mat = self.mat.clone();
+        true
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.bbox)
+    }
+
+    /// 按未位移的球面近似
+    #[inline]
+    fn area(&self) -> f64 {
+        4.0 * PI * self.radius * self.radius
+    }
+}
@@ -1,19 +1,27 @@
 use crate::ray_tracing::materials::material::{Material, NoMaterial};
 use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::differential::TextureFootprint;
 use crate::ray_tracing::math::interval::Interval;
-use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::ray::{LightLinkTag, Ray};
 use crate::ray_tracing::math::vec3::*;
+use std::any::Any;
 use std::sync::Arc;
 
 /// 命中记录，包含光线与物体交点的所有信息
 pub struct HitRecord {
-    pub p: Point3,              // 交点位置
-    pub normal: Vec3,           // 表面法线
-    pub mat: Arc<dyn Material>, // 材质
-    pub t: f64,                 // 光线参数t
-    pub u: f64,                 // 纹理坐标u
-    pub v: f64,                 // 纹理坐标v
-    pub front_face: bool,       // 是否为正面
+    pub p: Point3,                           // 交点位置
+    pub normal: Vec3,                        // 表面法线
+    pub mat: Arc<dyn Material>,              // 材质
+    pub t: f64,                              // 光线参数t
+    pub u: f64,                              // 纹理坐标u
+    pub v: f64,                              // 纹理坐标v
+    pub front_face: bool,                    // 是否为正面
+    pub dpdu: Vec3,                          // 位置对纹理坐标u的偏导数（不支持的几何体为零）
+    pub dpdv: Vec3,                          // 位置对纹理坐标v的偏导数
+    pub footprint: Option<TextureFootprint>, // 纹理足迹，由带微分的光线命中时计算
+    pub link: LightLinkTag,                  // 光源链接标签，未链接的物体为默认标签
+    pub motion: Option<[Point3; 2]>, // 交点处的表面点在快门打开和关闭时刻的位置，静止的物体为 None
+    pub weight: f64, // 采样权重，由 `Weighted` 材质读取（介质引导距离采样的修正），其余命中为1
 }
 
 impl HitRecord {
@@ -36,9 +44,23 @@ impl HitRecord {
             u,
             v,
             front_face,
+            dpdu: Vec3::zeros(),
+            dpdv: Vec3::zeros(),
+            footprint: None,
+            link: LightLinkTag::DEFAULT,
+            motion: None,
+            weight: 1.0,
         }
     }
 
+    /// 用光线微分计算交点的纹理足迹，光线没有微分时清除足迹
+    #[inline]
+    pub fn compute_footprint(&mut self, r: &Ray) {
+        self.footprint = r.differential.and_then(|differential| {
+            TextureFootprint::compute(&differential, &self.p, &self.normal, &self.dpdu, &self.dpdv)
+        });
+    }
+
     /// 根据光线方向设置正确的法线方向
     #[inline]
     pub fn set_face_normal(&mut self, r: &Ray, outward_normal: &Vec3) {
@@ -61,6 +83,10 @@ impl std::fmt::Debug for HitRecord {
             .field("u", &self.u)
             .field("v", &self.v)
             .field("front_face", &self.front_face)
+            .field("footprint", &self.footprint)
+            .field("link", &self.link)
+            .field("motion", &self.motion)
+            .field("weight", &self.weight)
             .finish()
     }
 }
@@ -75,16 +101,27 @@ impl Clone for HitRecord {
             u: self.u,
             v: self.v,
             front_face: self.front_face,
+            dpdu: self.dpdu,
+            dpdv: self.dpdv,
+            footprint: self.footprint,
+            link: self.link,
+            motion: self.motion,
+            weight: self.weight,
         }
     }
 }
 
+thread_local! {
+    /// 每个线程共享的空材质，避免每次创建默认命中记录时分配内存
+    static NO_MATERIAL: Arc<dyn Material> = Arc::new(NoMaterial);
+}
+
 impl Default for HitRecord {
     fn default() -> Self {
         Self::new(
             Point3::origin(),
             Vec3::new(0.0, 0.0, 0.0),
-            Arc::new(NoMaterial),
+            NO_MATERIAL.with(Arc::clone),
             0.0,
             0.0,
             0.0,
@@ -93,23 +130,155 @@ impl Default for HitRecord {
     }
 }
 
+/// 估计漫射发光表面的功率：Φ = sides·π·A·L̄，L̄ 为材质在给定表面点 (u, v, p) 上发射辐射度的平均值
+///
+/// sides 为发光的面数（`DiffuseLight` 不区分正反面，平面光源为2）。
+pub fn diffuse_emitter_power(
+    mat: &dyn Material,
+    area: f64,
+    sides: f64,
+    points: impl IntoIterator<Item = (f64, f64, Point3)>,
+) -> Color {
+    let mut sum = Color::zeros();
+    let mut count = 0;
+    for (u, v, p) in points {
+        sum += mat.emitted(u, v, &p);
+        count += 1;
+    }
+    if count == 0 {
+        return Color::zeros();
+    }
+    sum / count as f64 * (sides * std::f64::consts::PI * area)
+}
+
+/// 估计功率时每个参数方向上的采样点数
+pub const POWER_GRID: usize = 4;
+
+/// [0,1]² 上 POWER_GRID × POWER_GRID 网格的单元中心
+pub fn power_grid() -> impl Iterator<Item = (f64, f64)> {
+    let step = 1.0 / POWER_GRID as f64;
+    (0..POWER_GRID * POWER_GRID).map(move |k| {
+        (
+            ((k % POWER_GRID) as f64 + 0.5) * step,
+            ((k / POWER_GRID) as f64 + 0.5) * step,
+        )
+    })
+}
+
+/// 纹理空间中的表面三角形，用于把光照烘焙到纹理（光照贴图）
+///
+/// 纹理坐标在三角形内按重心坐标线性插值到位置和法线，法线指向烘焙的一侧。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UvTriangle {
+    pub uv: [(f64, f64); 3],
+    pub p: [Point3; 3],
+    pub n: [Vec3; 3],
+}
+
+impl UvTriangle {
+    /// 纹理坐标 (u, v) 的重心坐标，不在三角形内（允许 eps 的误差）时为None
+    pub fn barycentric(&self, u: f64, v: f64, eps: f64) -> Option<[f64; 3]> {
+        let [(u0, v0), (u1, v1), (u2, v2)] = self.uv;
+        let det = (u1 - u0) * (v2 - v0) - (u2 - u0) * (v1 - v0);
+        if det.abs() < 1e-18 {
+            return None;
+        }
+        let b1 = ((u - u0) * (v2 - v0) - (u2 - u0) * (v - v0)) / det;
+        let b2 = ((u1 - u0) * (v - v0) - (u - u0) * (v1 - v0)) / det;
+        let b0 = 1.0 - b1 - b2;
+        (b0 >= -eps && b1 >= -eps && b2 >= -eps).then_some([b0, b1, b2])
+    }
+
+    /// 重心坐标处的位置
+    #[inline]
+    pub fn point(&self, b: &[f64; 3]) -> Point3 {
+        Point3::from(b[0] * self.p[0].coords + b[1] * self.p[1].coords + b[2] * self.p[2].coords)
+    }
+
+    /// 重心坐标处的单位法线
+    #[inline]
+    pub fn normal(&self, b: &[f64; 3]) -> Vec3 {
+        (b[0] * self.n[0] + b[1] * self.n[1] + b[2] * self.n[2]).normalize()
+    }
+
+    /// 用给定的点和向量变换得到新的三角形（用于实例变换）
+    pub fn map(&self, point: impl Fn(&Point3) -> Point3, vector: impl Fn(&Vec3) -> Vec3) -> Self {
+        Self {
+            uv: self.uv,
+            p: self.p.map(|p| point(&p)),
+            n: self.n.map(|n| vector(&n)),
+        }
+    }
+}
+
 /// 可被光线击中的物体trait
-pub trait Hittable: Send + Sync + std::fmt::Debug {
+///
+/// 自定义几何体在 `hit` 中需要设置交点、t、纹理坐标、材质，并调用 `HitRecord::set_face_normal`；
+/// 提供 `bounding_box` 后才能放入BVH，作为光源参与重要性采样时还需实现 `pdf_value` 和 `random`。
+/// 物体可以通过 `downcast_ref` 从 `dyn Hittable` 取回具体类型。
+pub trait Hittable: Any + Send + Sync + std::fmt::Debug {
     /// 检测光线与物体的交点
     fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool;
 
+    /// 检测光线在区间内是否与物体有任意交点（阴影光线），无需求出最近交点
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        let mut rec = HitRecord::default();
+        self.hit(r, ray_t, &mut rec)
+    }
+
     /// 返回物体的包围盒
     fn bounding_box(&self) -> Option<Aabb> {
         None
     }
 
-    /// 计算从给定点向物体随机采样的概率密度
-    fn pdf_value(&self, _origin: &Point3, _direction: &Vec3) -> f64 {
+    /// 计算在给定时刻从某点向物体随机采样的概率密度（time 用于运动物体）
+    fn pdf_value(&self, _origin: &Point3, _direction: &Vec3, _time: f64) -> f64 {
         0.0
     }
 
-    /// 从给定点向物体生成随机方向
-    fn random(&self, _origin: &Point3) -> Vec3 {
+    /// 在给定时刻从某点向物体生成随机方向
+    fn random(&self, _origin: &Point3, _time: f64) -> Vec3 {
         Vec3::new(1.0, 0.0, 0.0) // 默认方向
     }
+
+    /// 表面积，用于按功率选择光源和验证采样PDF；不支持的物体（如体积）为0
+    fn area(&self) -> f64 {
+        0.0
+    }
+
+    /// 发射的辐射功率（辐射通量），不发光的物体为0
+    fn power(&self) -> Color {
+        Color::zeros()
+    }
+
+    /// 覆盖表面的纹理空间三角形，用于烘焙光照贴图；不支持的物体为空
+    fn uv_triangles(&self) -> Vec<UvTriangle> {
+        Vec::new()
+    }
+
+    /// 收集用于调试可视化的包围盒及其层级深度
+    fn collect_debug_boxes(
+        &self,
+        depth: usize,
+        _leaves_only: bool,
+        boxes: &mut Vec<(Aabb, usize)>,
+    ) {
+        if let Some(bbox) = self.bounding_box() {
+            boxes.push((bbox, depth));
+        }
+    }
+}
+
+impl dyn Hittable {
+    /// 尝试把物体转换为具体类型的引用
+    #[inline]
+    pub fn downcast_ref<T: Hittable>(&self) -> Option<&T> {
+        (self as &dyn Any).downcast_ref()
+    }
+
+    /// 物体是否为类型 T
+    #[inline]
+    pub fn is<T: Hittable>(&self) -> bool {
+        (self as &dyn Any).is::<T>()
+    }
 }
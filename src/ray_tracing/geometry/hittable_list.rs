@@ -1,7 +1,7 @@
-use super::hittable::{HitRecord, Hittable};
+use super::hittable::{HitRecord, Hittable, UvTriangle};
 use crate::ray_tracing::math::aabb::Aabb;
 use crate::ray_tracing::math::interval::Interval;
-use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::ray::{LightLinkTag, Ray};
 use crate::ray_tracing::math::vec3::*;
 use crate::ray_tracing::utils::random::random_int_range;
 use std::sync::Arc;
@@ -36,6 +36,70 @@ impl HittableList {
         self.objects.push(object);
     }
 
+    /// 移除并返回第 index 个物体，后面的物体前移；越界时返回None
+    pub fn remove(&mut self, index: usize) -> Option<Arc<dyn Hittable>> {
+        if index >= self.objects.len() {
+            return None;
+        }
+        let removed = self.objects.remove(index);
+        self.recompute_bbox();
+        Some(removed)
+    }
+
+    /// 替换第 index 个物体，返回原物体；越界时不做修改并返回None
+    pub fn replace(
+        &mut self,
+        index: usize,
+        object: Arc<dyn Hittable>,
+    ) -> Option<Arc<dyn Hittable>> {
+        let slot = self.objects.get_mut(index)?;
+        let old = std::mem::replace(slot, object);
+        self.recompute_bbox();
+        Some(old)
+    }
+
+    /// 只保留满足条件的物体
+    pub fn retain(&mut self, mut keep: impl FnMut(&Arc<dyn Hittable>) -> bool) {
+        self.objects.retain(|object| keep(object));
+        self.recompute_bbox();
+    }
+
+    /// 清空列表
+    pub fn clear(&mut self) {
+        self.objects.clear();
+        self.bbox = Aabb::empty();
+    }
+
+    /// 获取第 index 个物体
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&Arc<dyn Hittable>> {
+        self.objects.get(index)
+    }
+
+    /// 按添加顺序遍历物体
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<'_, Arc<dyn Hittable>> {
+        self.objects.iter()
+    }
+
+    /// 遍历类型为 T 的物体及其序号（不进入嵌套的列表或BVH）
+    pub fn iter_typed<T: Hittable>(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.objects
+            .iter()
+            .enumerate()
+            .filter_map(|(index, object)| object.downcast_ref::<T>().map(|o| (index, o)))
+    }
+
+    /// 移除物体后重新计算包围盒
+    fn recompute_bbox(&mut self) {
+        self.bbox = self
+            .objects
+            .iter()
+            .filter_map(|object| object.bounding_box())
+            .reduce(|a, b| a.merge(&b))
+            .unwrap_or_else(Aabb::empty);
+    }
+
     /// 获取物体数量
     #[inline]
     pub fn len(&self) -> usize {
@@ -59,13 +123,20 @@ impl Hittable for HittableList {
             if object.hit(r, Interval::new(ray_t.min, closest_so_far), &mut temp_rec) {
                 hit_anything = true;
                 closest_so_far = temp_rec.t;
-                *rec = temp_rec.clone();
+                std::mem::swap(rec, &mut temp_rec);
+                // 换出的旧记录可能带有光源链接标签和运动信息，未包装的静止物体不会覆盖它们
+                temp_rec.link = LightLinkTag::DEFAULT;
+                temp_rec.motion = None;
             }
         }
 
         hit_anything
     }
 
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        self.objects.iter().any(|object| object.hit_any(r, ray_t))
+    }
+
     #[inline]
     fn bounding_box(&self) -> Option<Aabb> {
         if self.is_empty() {
@@ -75,7 +146,7 @@ impl Hittable for HittableList {
         }
     }
 
-    fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3, time: f64) -> f64 {
         if self.is_empty() {
             return 0.0;
         }
@@ -83,17 +154,40 @@ impl Hittable for HittableList {
         let weight = 1.0 / self.objects.len() as f64;
         self.objects
             .iter()
-            .map(|obj| weight * obj.pdf_value(origin, direction))
+            .map(|obj| weight * obj.pdf_value(origin, direction, time))
             .sum()
     }
 
-    fn random(&self, origin: &Point3) -> Vec3 {
+    fn random(&self, origin: &Point3, time: f64) -> Vec3 {
         if self.is_empty() {
             return Vec3::new(1.0, 0.0, 0.0);
         }
 
         let random_index = random_int_range(0, self.objects.len() as i32 - 1) as usize;
-        self.objects[random_index].random(origin)
+        self.objects[random_index].random(origin, time)
+    }
+
+    fn area(&self) -> f64 {
+        self.objects.iter().map(|obj| obj.area()).sum()
+    }
+
+    fn power(&self) -> Color {
+        self.objects.iter().map(|obj| obj.power()).sum()
+    }
+
+    /// 各物体的纹理空间三角形（各物体的纹理坐标共用同一张贴图，可能互相重叠）
+    fn uv_triangles(&self) -> Vec<UvTriangle> {
+        self.objects.iter().flat_map(|o| o.uv_triangles()).collect()
+    }
+
+    fn collect_debug_boxes(&self, depth: usize, leaves_only: bool, boxes: &mut Vec<(Aabb, usize)>) {
+        if !leaves_only && !self.is_empty() {
+            boxes.push((self.bbox, depth));
+        }
+
+        for object in &self.objects {
+            object.collect_debug_boxes(depth + 1, leaves_only, boxes);
+        }
     }
 }
 
@@ -107,6 +201,16 @@ impl FromIterator<Arc<dyn Hittable>> for HittableList {
     }
 }
 
+impl<'a> IntoIterator for &'a HittableList {
+    type Item = &'a Arc<dyn Hittable>;
+    type IntoIter = std::slice::Iter<'a, Arc<dyn Hittable>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.objects.iter()
+    }
+}
+
 impl std::fmt::Debug for HittableList {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("HittableList")
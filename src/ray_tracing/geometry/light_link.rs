@@ -0,0 +1,110 @@
+//! 光源链接：指定光源只照亮部分物体，或物体只接受部分光源的照明
+//!
+//! 每个物体带一个链接标签：所属的链接组（0..64）和允许交互的组集合。着色点沿散射光线
+//! 命中发光体时，只有双方都允许对方的组，发光体的辐射才计入着色点的光照：
+//! 接收者的集合决定“接受哪些光源”，发光体的集合决定“照亮哪些物体”。
+//! 相机直接看到的发光体不受链接影响；镜面散射光线携带镜面交点自身的标签。
+//!
+//! 渲染器没有独立的直接光照阶段，光源采样和材质采样混合生成的散射光线命中发光体时才计入光照，
+//! 链接在这一步判断：被排除的光源照到的辐射按0计，估计仍然无偏，只是浪费了射向它的样本。
+//! 遮挡不受链接影响，被排除的光源仍被其他物体挡住。
+
+use super::hittable::{HitRecord, Hittable, UvTriangle};
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::{LightLinkTag, LinkSet, Ray};
+use crate::ray_tracing::math::vec3::{Color, Point3, Vec3};
+use std::sync::Arc;
+
+/// 为命中记录设置链接标签的包装物体
+///
+/// 嵌套包装时最外层的标签生效。
+pub struct LightLink {
+    object: Arc<dyn Hittable>,
+    tag: LightLinkTag,
+}
+
+impl LightLink {
+    /// 包装物体
+    #[inline]
+    pub fn new(object: Arc<dyn Hittable>, tag: LightLinkTag) -> Self {
+        Self { object, tag }
+    }
+
+    /// 属于 group 的发光体，只照亮 illuminates 中的组
+    #[inline]
+    pub fn emitter(object: Arc<dyn Hittable>, group: u8, illuminates: LinkSet) -> Self {
+        Self::new(object, LightLinkTag::new(group, illuminates))
+    }
+
+    /// 属于 group 的接收者，只接受 receives 中的组发出的光
+    #[inline]
+    pub fn receiver(object: Arc<dyn Hittable>, group: u8, receives: LinkSet) -> Self {
+        Self::new(object, LightLinkTag::new(group, receives))
+    }
+
+    /// 被包装的物体
+    #[inline]
+    pub fn object(&self) -> &Arc<dyn Hittable> {
+        &self.object
+    }
+
+    /// 链接标签
+    #[inline]
+    pub fn tag(&self) -> LightLinkTag {
+        self.tag
+    }
+}
+
+impl Hittable for LightLink {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        if !self.object.hit(r, ray_t, rec) {
+            return false;
+        }
+        rec.link = self.tag;
+        true
+    }
+
+    #[inline]
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        self.object.hit_any(r, ray_t)
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.object.bounding_box()
+    }
+
+    #[inline]
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3, time: f64) -> f64 {
+        self.object.pdf_value(origin, direction, time)
+    }
+
+    #[inline]
+    fn random(&self, origin: &Point3, time: f64) -> Vec3 {
+        self.object.random(origin, time)
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        self.object.area()
+    }
+
+    #[inline]
+    fn power(&self) -> Color {
+        self.object.power()
+    }
+
+    fn uv_triangles(&self) -> Vec<UvTriangle> {
+        self.object.uv_triangles()
+    }
+}
+
+impl std::fmt::Debug for LightLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LightLink")
+            .field("object", &"<Hittable>")
+            .field("tag", &self.tag)
+            .finish()
+    }
+}
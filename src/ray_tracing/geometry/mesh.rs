@@ -0,0 +1,205 @@
+use super::hittable::{HitRecord, Hittable, UvTriangle};
+use super::hittable_list::HittableList;
+use super::triangle::Triangle;
+use crate::ray_tracing::acceleration::bvh::BvhNode;
+use crate::ray_tracing::materials::material::Material;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::random::random_double;
+use std::sync::Arc;
+
+/// 索引三角网格的顶点数据
+///
+/// `normals` 和 `uvs` 为空表示没有该属性，否则长度必须与 `positions` 相同。
+#[derive(Debug, Clone, Default)]
+pub struct MeshData {
+    pub positions: Vec<Point3>,
+    pub normals: Vec<Vec3>,
+    pub uvs: Vec<(f64, f64)>,
+    pub indices: Vec<[usize; 3]>,
+}
+
+impl MeshData {
+    /// 三角形数量
+    #[inline]
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len()
+    }
+}
+
+/// 三角网格：内部用BVH组织三角形，作为光源时按面积选择三角形采样
+///
+/// 网格整体使用同一个材质，命中时覆盖三角形自身的材质，因此 `with_material` 可以在不重建BVH的情况下更换材质。
+#[derive(Clone)]
+pub struct TriangleMesh {
+    triangles: Arc<[Arc<Triangle>]>,
+    bvh: Option<Arc<BvhNode>>,
+    cdf: Arc<[f64]>, // 按面积累积的归一化分布，用于选择采样的三角形
+    area: f64,
+    mat: Arc<dyn Material>,
+}
+
+impl TriangleMesh {
+    /// 从顶点数据创建网格，跳过越界索引和退化三角形
+    pub fn new(data: &MeshData, mat: Arc<dyn Material>) -> Self {
+        let vertex_count = data.positions.len();
+        let has_normals = data.normals.len() == vertex_count;
+        let has_uvs = data.uvs.len() == vertex_count;
+
+        let triangles: Vec<Arc<Triangle>> = data
+            .indices
+            .iter()
+            .filter(|face| face.iter().all(|&i| i < vertex_count))
+            .map(|&[a, b, c]| {
+                let mut triangle = Triangle::new(
+                    data.positions[a],
+                    data.positions[b],
+                    data.positions[c],
+                    mat.clone(),
+                );
+                if has_normals {
+                    triangle =
+                        triangle.with_normals([data.normals[a], data.normals[b], data.normals[c]]);
+                }
+                if has_uvs {
+                    triangle = triangle.with_uvs([data.uvs[a], data.uvs[b], data.uvs[c]]);
+                }
+                triangle
+            })
+            .filter(|triangle| !triangle.is_degenerate())
+            .map(Arc::new)
+            .collect();
+
+        let mut cdf = Vec::with_capacity(triangles.len());
+        let mut area = 0.0;
+        for triangle in &triangles {
+            area += triangle.area();
+            cdf.push(area);
+        }
+        if area > 0.0 {
+            cdf.iter_mut().for_each(|c| *c /= area);
+        }
+
+        let bvh = (!triangles.is_empty()).then(|| {
+            let list: HittableList = triangles
+                .iter()
+                .map(|t| t.clone() as Arc<dyn Hittable>)
+                .collect();
+            Arc::new(BvhNode::new(&list))
+        });
+
+        Self {
+            triangles: triangles.into(),
+            bvh,
+            cdf: cdf.into(),
+            area,
+            mat,
+        }
+    }
+
+    /// 共享几何和BVH、换用另一个材质的网格
+    pub fn with_material(&self, mat: Arc<dyn Material>) -> Self {
+        Self {
+            mat,
+            ..self.clone()
+        }
+    }
+
+    /// 三角形数量（不含被跳过的退化三角形）
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.triangles.len()
+    }
+
+    /// 检查是否为空
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+}
+
+impl Hittable for TriangleMesh {
+    #[inline]
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        if !self.bvh.as_ref().is_some_and(|bvh| bvh.hit(r, ray_t, rec)) {
+            return false;
+        }
+        rec.mat = self.mat.clone();
+        true
+    }
+
+    #[inline]
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        self.bvh.as_ref().is_some_and(|bvh| bvh.hit_any(r, ray_t))
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.bvh.as_ref().and_then(|bvh| bvh.bounding_box())
+    }
+
+    /// 按面积加权的各三角形立体角密度之和
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3, time: f64) -> f64 {
+        if self.area <= 0.0 {
+            return 0.0;
+        }
+        self.triangles
+            .iter()
+            .map(|t| t.area() / self.area * t.pdf_value(origin, direction, time))
+            .sum()
+    }
+
+    fn random(&self, origin: &Point3, time: f64) -> Vec3 {
+        if self.triangles.is_empty() {
+            return Vec3::new(1.0, 0.0, 0.0);
+        }
+        let xi = random_double();
+        let index = self
+            .cdf
+            .partition_point(|&c| c < xi)
+            .min(self.triangles.len() - 1);
+        self.triangles[index].random(origin, time)
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        self.area
+    }
+
+    fn power(&self) -> Color {
+        self.triangles
+            .iter()
+            .map(|t| t.power_with(self.mat.as_ref()))
+            .sum()
+    }
+
+    fn uv_triangles(&self) -> Vec<UvTriangle> {
+        self.triangles
+            .iter()
+            .flat_map(|t| t.uv_triangles())
+            .collect()
+    }
+
+    fn collect_debug_boxes(&self, depth: usize, leaves_only: bool, boxes: &mut Vec<(Aabb, usize)>) {
+        match &self.bvh {
+            Some(bvh) if !leaves_only => bvh.collect_debug_boxes(depth, leaves_only, boxes),
+            _ => {
+                if let Some(bbox) = self.bounding_box() {
+                    boxes.push((bbox, depth));
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for TriangleMesh {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TriangleMesh")
+            .field("triangles", &self.triangles.len())
+            .field("area", &self.area)
+            .field("bbox", &self.bounding_box())
+            .finish()
+    }
+}
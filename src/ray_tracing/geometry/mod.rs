@@ -1,5 +1,21 @@
+pub mod compact_mesh;
+pub mod curve;
+pub mod curve_set;
+pub mod displaced_sphere;
 pub mod hittable;
 pub mod hittable_list;
+pub mod light_link;
+pub mod mesh;
+pub mod planar;
+pub mod point_cloud;
+pub mod polygon;
 pub mod quad;
+pub mod quadric;
+pub mod scene_graph;
+pub mod sdf;
 pub mod sphere;
+pub mod streaming_mesh;
+pub mod subdivision;
 pub mod transforms;
+pub mod triangle;
+pub mod visibility;
@@ -0,0 +1,51 @@
+//! 平面图元的公共接口：四边形、三角形和凸多边形
+//!
+//! 平面图元作为光源时都按面积均匀采样，立体角PDF由面积PDF换算：p = d² / (cosθ · A)。
+
+use super::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::random::random_double;
+
+/// 位于单个平面内的图元
+pub trait Planar: Hittable {
+    /// 平面的单位法线（按顶点逆时针顺序的右手方向）
+    fn plane_normal(&self) -> Vec3;
+
+    /// 边界顶点，按逆时针顺序
+    fn vertices(&self) -> Vec<Point3>;
+
+    /// 把 [0,1)² 上的均匀随机数映射为图元上按面积均匀分布的点
+    fn sample_point(&self, r1: f64, r2: f64) -> Point3;
+}
+
+/// 从 origin 沿 direction 采样到平面图元的立体角PDF，未命中时为0
+pub fn planar_pdf_value<P: Planar + ?Sized>(
+    shape: &P,
+    origin: &Point3,
+    direction: &Vec3,
+    time: f64,
+) -> f64 {
+    let mut rec = HitRecord::default();
+    if !shape.hit(
+        &Ray::new(*origin, *direction, time),
+        Interval::new(0.001, f64::INFINITY),
+        &mut rec,
+    ) {
+        return 0.0;
+    }
+
+    let distance_squared = rec.t * rec.t * direction.norm_squared();
+    let cosine = (direction.dot(&shape.plane_normal()) / direction.norm()).abs();
+
+    distance_squared / (cosine * shape.area())
+}
+
+/// 从 origin 指向平面图元上均匀采样点的方向
+#[inline]
+pub fn planar_random<P: Planar + ?Sized>(shape: &P, origin: &Point3) -> Vec3 {
+    let r1 = random_double();
+    let r2 = random_double();
+    shape.sample_point(r1, r2) - *origin
+}
@@ -0,0 +1,224 @@
+use super::hittable::{HitRecord, Hittable};
+use super::hittable_list::HittableList;
+use super::sphere::Sphere;
+use crate::ray_tracing::acceleration::bvh::BvhNode;
+use crate::ray_tracing::materials::lambertian::Lambertian;
+use crate::ray_tracing::materials::material::Material;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 点云中的一个点
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CloudPoint {
+    pub position: Point3,
+    /// 表面法线，扫描数据没有法线时为None
+    pub normal: Option<Vec3>,
+    /// 线性颜色，分量范围 [0,1]
+    pub color: Color,
+}
+
+/// 点的绘制形状
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SplatShape {
+    /// 垂直于点法线的圆盘（双面），相邻圆盘拼成连续的表面；没有法线的点退化为球体
+    #[default]
+    Disk,
+    /// 球体，不需要法线
+    Sphere,
+}
+
+/// 点云：每个点绘制为一个小圆盘或小球，内部用BVH组织
+///
+/// 颜色相同的点共享同一个材质，8位颜色的扫描数据通常只产生少量材质对象。
+pub struct PointCloud {
+    bvh: Option<BvhNode>,
+    len: usize,
+    radius: f64,
+    shape: SplatShape,
+}
+
+impl PointCloud {
+    /// 创建点云，material 根据点的颜色创建材质
+    pub fn new(
+        points: &[CloudPoint],
+        radius: f64,
+        shape: SplatShape,
+        material: impl Fn(Color) -> Arc<dyn Material>,
+    ) -> Self {
+        let mut materials: HashMap<[u64; 3], Arc<dyn Material>> = HashMap::new();
+        let splats: HittableList = points
+            .iter()
+            .map(|point| {
+                let c = point.color;
+                let mat = materials
+                    .entry([c.x.to_bits(), c.y.to_bits(), c.z.to_bits()])
+                    .or_insert_with(|| material(c))
+                    .clone();
+                let normal = point
+                    .normal
+                    .filter(|n| n.norm_squared() > 1e-12)
+                    .map(|n| n.normalize());
+                match (shape, normal) {
+                    (SplatShape::Disk, Some(normal)) => {
+                        Arc::new(SplatDisk::new(point.position, normal, radius, mat))
+                            as Arc<dyn Hittable>
+                    }
+                    _ => Arc::new(Sphere::new(point.position, radius, mat)),
+                }
+            })
+            .collect();
+
+        let bvh = (!splats.is_empty()).then(|| BvhNode::new(&splats));
+        Self {
+            bvh,
+            len: points.len(),
+            radius,
+            shape,
+        }
+    }
+
+    /// 使用朗伯材质（反照率为点的颜色）创建点云
+    pub fn lambertian(points: &[CloudPoint], radius: f64, shape: SplatShape) -> Self {
+        Self::new(points, radius, shape, |color| {
+            Arc::new(Lambertian::new(color))
+        })
+    }
+
+    /// 点的数量
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 检查是否为空
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 由点数和包围盒估计使相邻点大致相接的半径（假设点均匀分布在包围盒的表面上）
+    pub fn suggested_radius(points: &[CloudPoint]) -> f64 {
+        let Some(first) = points.first() else {
+            return 0.0;
+        };
+        let bbox = points
+            .iter()
+            .fold(Aabb::new_point(first.position, first.position), |b, p| {
+                b.merge(&Aabb::new_point(p.position, p.position))
+            });
+        let (x, y, z) = (bbox.x.size(), bbox.y.size(), bbox.z.size());
+        let area = 2.0 * (x * y + y * z + z * x);
+        (area / points.len() as f64).sqrt()
+    }
+}
+
+impl Hittable for PointCloud {
+    #[inline]
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        self.bvh.as_ref().is_some_and(|bvh| bvh.hit(r, ray_t, rec))
+    }
+
+    #[inline]
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        self.bvh.as_ref().is_some_and(|bvh| bvh.hit_any(r, ray_t))
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.bvh.as_ref().and_then(|bvh| bvh.bounding_box())
+    }
+
+    fn collect_debug_boxes(&self, depth: usize, leaves_only: bool, boxes: &mut Vec<(Aabb, usize)>) {
+        match &self.bvh {
+            Some(bvh) if !leaves_only => bvh.collect_debug_boxes(depth, leaves_only, boxes),
+            _ => {
+                if let Some(bbox) = self.bounding_box() {
+                    boxes.push((bbox, depth));
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for PointCloud {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PointCloud")
+            .field("points", &self.len)
+            .field("radius", &self.radius)
+            .field("shape", &self.shape)
+            .field("bbox", &self.bounding_box())
+            .finish()
+    }
+}
+
+/// 双面圆盘
+struct SplatDisk {
+    center: Point3,
+    normal: Vec3,
+    radius: f64,
+    mat: Arc<dyn Material>,
+    bbox: Aabb,
+}
+
+impl SplatDisk {
+    fn new(center: Point3, normal: Vec3, radius: f64, mat: Arc<dyn Material>) -> Self {
+        // 圆盘在每个轴上的半宽为 r·sqrt(1 - n²)
+        let extent = Vec3::new(
+            radius * (1.0 - normal.x * normal.x).max(0.0).sqrt(),
+            radius * (1.0 - normal.y * normal.y).max(0.0).sqrt(),
+            radius * (1.0 - normal.z * normal.z).max(0.0).sqrt(),
+        );
+        Self {
+            center,
+            normal,
+            radius,
+            mat,
+            bbox: Aabb::new_point(center - extent, center + extent),
+        }
+    }
+}
+
+impl Hittable for SplatDisk {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let denom = self.normal.dot(&r.dir);
+        if denom.abs() < 1e-12 {
+            return false;
+        }
+        let t = self.normal.dot(&(self.center - r.orig)) / denom;
+        if !ray_t.contains(t) {
+            return false;
+        }
+        let p = r.at(t);
+        if (p - self.center).norm_squared() > self.radius * self.radius {
+            return false;
+        }
+
+        // 点的颜色已经决定了材质，纹理坐标取圆盘中心
+        rec.t = t;
+        rec.p = p;
+        rec.u = 0.5;
+        rec.v = 0.5;
+        rec.mat = self.mat.clone();
+        rec.set_face_normal(r, &self.normal);
+        true
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.bbox)
+    }
+}
+
+impl std::fmt::Debug for SplatDisk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SplatDisk")
+            .field("center", &self.center)
+            .field("normal", &self.normal)
+            .field("radius", &self.radius)
+            .finish()
+    }
+}
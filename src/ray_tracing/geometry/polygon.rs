@@ -0,0 +1,259 @@
+//! 凸多边形：按以第一个顶点为中心的三角扇求交和采样
+//!
+//! 三角扇对凸多边形（以及相对第一个顶点星形的多边形）不重叠地覆盖整个面，
+//! 采样时先按面积的累积分布选择扇中的三角形，再在三角形内均匀采样。
+
+use super::hittable::{HitRecord, Hittable, UvTriangle, diffuse_emitter_power, power_grid};
+use super::planar::{Planar, planar_pdf_value, planar_random};
+use crate::ray_tracing::materials::material::Material;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::onb::ONB;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use std::sync::Arc;
+
+/// 三角扇中的一个三角形：顶点为 vertices[0]、p0 + e1、p0 + e2
+#[derive(Debug, Clone, Copy)]
+struct FanTriangle {
+    e1: Vec3,
+    e2: Vec3,
+    inv_det: f64, // 1 / (n · (e1 × e2))
+}
+
+/// 平面凸多边形
+pub struct Polygon {
+    vertices: Vec<Point3>,
+    fan: Vec<FanTriangle>,
+    cdf: Vec<f64>, // 扇中三角形面积的累积分布，最后一项为1
+    normal: Vec3,
+    d: f64,
+    uv_origin: Point3, // 纹理坐标的原点（平面内包围矩形的角点）
+    uv_u: Vec3,        // 纹理 u 方向的边（长度为包围矩形宽度）
+    uv_v: Vec3,        // 纹理 v 方向的边
+    mat: Arc<dyn Material>,
+    bbox: Aabb,
+    area: f64,
+}
+
+impl Polygon {
+    /// 由逆时针顺序的顶点创建凸多边形，顶点应位于同一平面内
+    ///
+    /// 平面法线用 Newell 方法估计，对略微不共面的顶点也稳定。
+    pub fn new(vertices: Vec<Point3>, mat: Arc<dyn Material>) -> Self {
+        assert!(vertices.len() >= 3, "多边形至少需要3个顶点");
+
+        let n = vertices.len();
+        let newell: Vec3 = (0..n)
+            .map(|i| {
+                let (a, b) = (vertices[i], vertices[(i + 1) % n]);
+                Vec3::new(
+                    (a.y - b.y) * (a.z + b.z),
+                    (a.z - b.z) * (a.x + b.x),
+                    (a.x - b.x) * (a.y + b.y),
+                )
+            })
+            .sum();
+        let normal = newell.normalize();
+        let centroid = Point3::from(vertices.iter().map(|p| p.coords).sum::<Vec3>() / n as f64);
+        let d = normal.dot(&centroid.coords);
+
+        let p0 = vertices[0];
+        let mut fan = Vec::with_capacity(n - 2);
+        let mut cdf = Vec::with_capacity(n - 2);
+        let mut area = 0.0;
+        for i in 1..n - 1 {
+            let e1 = vertices[i] - p0;
+            let e2 = vertices[i + 1] - p0;
+            let det = normal.dot(&e1.cross(&e2));
+            fan.push(FanTriangle {
+                e1,
+                e2,
+                inv_det: if det.abs() > 1e-18 { 1.0 / det } else { 0.0 },
+            });
+            area += 0.5 * det.abs();
+            cdf.push(area);
+        }
+        for c in &mut cdf {
+            *c /= area.max(1e-18);
+        }
+
+        // 纹理坐标：平面内以第一条边为 u 方向的包围矩形
+        let tangent = (vertices[1] - p0).normalize();
+        let bitangent = normal.cross(&tangent);
+        let (mut min_u, mut max_u) = (f64::INFINITY, f64::NEG_INFINITY);
+        let (mut min_v, mut max_v) = (f64::INFINITY, f64::NEG_INFINITY);
+        for p in &vertices {
+            let (u, v) = ((p - p0).dot(&tangent), (p - p0).dot(&bitangent));
+            (min_u, max_u) = (min_u.min(u), max_u.max(u));
+            (min_v, max_v) = (min_v.min(v), max_v.max(v));
+        }
+        let uv_origin = p0 + min_u * tangent + min_v * bitangent;
+        let uv_u = (max_u - min_u).max(1e-12) * tangent;
+        let uv_v = (max_v - min_v).max(1e-12) * bitangent;
+
+        let bbox = vertices.iter().fold(Aabb::new_point(p0, p0), |b, p| {
+            b.merge(&Aabb::new_point(*p, *p))
+        });
+
+        Self {
+            vertices,
+            fan,
+            cdf,
+            normal,
+            d,
+            uv_origin,
+            uv_u,
+            uv_v,
+            mat,
+            bbox,
+            area,
+        }
+    }
+
+    /// 正多边形：中心为 center、法线为 normal、外接圆半径为 radius、共 sides 条边
+    pub fn regular(
+        center: Point3,
+        normal: Vec3,
+        radius: f64,
+        sides: usize,
+        mat: Arc<dyn Material>,
+    ) -> Self {
+        let uvw = ONB::new(&normal);
+        let vertices = (0..sides.max(3))
+            .map(|k| {
+                let phi = 2.0 * std::f64::consts::PI * k as f64 / sides.max(3) as f64;
+                center + radius * (phi.cos() * uvw.u() + phi.sin() * uvw.v())
+            })
+            .collect();
+        Self::new(vertices, mat)
+    }
+
+    /// 点 p（位于平面内）的纹理坐标
+    #[inline]
+    fn uv(&self, p: &Point3) -> (f64, f64) {
+        let rel = p - self.uv_origin;
+        (
+            rel.dot(&self.uv_u) / self.uv_u.norm_squared(),
+            rel.dot(&self.uv_v) / self.uv_v.norm_squared(),
+        )
+    }
+}
+
+impl Hittable for Polygon {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let denom = self.normal.dot(&r.dir);
+        if denom.abs() < 1e-8 {
+            return false;
+        }
+
+        let t = (self.d - self.normal.dot(&r.orig.coords)) / denom;
+        if !ray_t.contains(t) {
+            return false;
+        }
+
+        // 交点在扇中任一三角形内即在多边形内
+        let intersection = r.at(t);
+        let w = intersection - self.vertices[0];
+        let inside = self.fan.iter().any(|tri| {
+            let b1 = self.normal.dot(&w.cross(&tri.e2)) * tri.inv_det;
+            let b2 = self.normal.dot(&tri.e1.cross(&w)) * tri.inv_det;
+            b1 >= 0.0 && b2 >= 0.0 && b1 + b2 <= 1.0
+        });
+        if !inside {
+            return false;
+        }
+
+        rec.t = t;
+        rec.p = intersection;
+        (rec.u, rec.v) = self.uv(&intersection);
+        rec.dpdu = self.uv_u;
+        rec.dpdv = self.uv_v;
+        rec.mat = self.mat.clone();
+        rec.set_face_normal(r, &self.normal);
+
+        true
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.bbox)
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3, time: f64) -> f64 {
+        planar_pdf_value(self, origin, direction, time)
+    }
+
+    fn random(&self, origin: &Point3, _time: f64) -> Vec3 {
+        planar_random(self, origin)
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        self.area
+    }
+
+    /// `DiffuseLight` 双面发光，按两面计算
+    fn power(&self) -> Color {
+        let points = power_grid().map(|(a, b)| {
+            let p = self.sample_point(a, b);
+            let (u, v) = self.uv(&p);
+            (u, v, p)
+        });
+        diffuse_emitter_power(self.mat.as_ref(), self.area, 2.0, points)
+    }
+
+    /// 烘焙法线所指的一侧，每个扇形三角形对应一个纹理空间三角形
+    fn uv_triangles(&self) -> Vec<UvTriangle> {
+        let p0 = self.vertices[0];
+        self.vertices
+            .windows(2)
+            .skip(1)
+            .map(|pair| {
+                let p = [p0, pair[0], pair[1]];
+                UvTriangle {
+                    uv: p.map(|q| self.uv(&q)),
+                    p,
+                    n: [self.normal; 3],
+                }
+            })
+            .collect()
+    }
+}
+
+impl Planar for Polygon {
+    #[inline]
+    fn plane_normal(&self) -> Vec3 {
+        self.normal
+    }
+
+    fn vertices(&self) -> Vec<Point3> {
+        self.vertices.clone()
+    }
+
+    /// r1 先按面积选择扇中的三角形，再重新缩放为三角形内的随机数
+    fn sample_point(&self, r1: f64, r2: f64) -> Point3 {
+        let index = self
+            .cdf
+            .partition_point(|&c| c <= r1)
+            .min(self.fan.len() - 1);
+        let lo = if index == 0 { 0.0 } else { self.cdf[index - 1] };
+        let r1 = ((r1 - lo) / (self.cdf[index] - lo).max(1e-18)).clamp(0.0, 1.0);
+
+        let tri = &self.fan[index];
+        let s = r1.sqrt();
+        self.vertices[0] + s * (1.0 - r2) * tri.e1 + s * r2 * tri.e2
+    }
+}
+
+impl std::fmt::Debug for Polygon {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Polygon")
+            .field("vertices", &self.vertices)
+            .field("normal", &self.normal)
+            .field("mat", &"<Material>")
+            .field("bbox", &self.bbox)
+            .field("area", &self.area)
+            .finish()
+    }
+}
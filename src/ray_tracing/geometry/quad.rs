@@ -1,11 +1,11 @@
-use super::hittable::{HitRecord, Hittable};
+use super::hittable::{HitRecord, Hittable, UvTriangle, diffuse_emitter_power, power_grid};
 use super::hittable_list::HittableList;
+use super::planar::{Planar, planar_pdf_value, planar_random};
 use crate::ray_tracing::materials::material::Material;
 use crate::ray_tracing::math::aabb::Aabb;
 use crate::ray_tracing::math::interval::Interval;
 use crate::ray_tracing::math::ray::Ray;
 use crate::ray_tracing::math::vec3::*;
-use crate::ray_tracing::utils::random::random_double;
 use std::sync::Arc;
 
 /// 四边形几何体
@@ -19,8 +19,21 @@ pub struct Quad {
     d: f64,                 // 平面方程常数项
     w: Vec3,                // 重心坐标计算辅助向量
     area: f64,              // 四边形面积
+    edge_scale: (f64, f64), // 交点的位置误差换算为 (alpha, beta) 误差的系数
 }
 
+/// 浮点运算单次舍入的相对误差上界
+const MACHINE_EPSILON: f64 = f64::EPSILON * 0.5;
+
+/// n 次浮点运算累积的相对误差上界 γn = nε/(1-nε)（Higham）
+#[inline]
+const fn gamma(n: i32) -> f64 {
+    (n as f64 * MACHINE_EPSILON) / (1.0 - n as f64 * MACHINE_EPSILON)
+}
+
+/// 光线方向与平面夹角的正弦低于此值时视为平行（与方向长度无关）
+const PARALLEL_SINE: f64 = 1e-12;
+
 impl Quad {
     /// 创建四边形
     #[inline]
@@ -30,6 +43,8 @@ impl Quad {
         let d = normal.dot(&(q.coords)); // 使用coords访问向量坐标
         let w = n / n.dot(&n);
         let area = n.norm();
+        // alpha = w·((P-Q)×v)，|P-Q| 的误差 δ 使 alpha 最多偏差 δ·|w|·|v|，beta 同理
+        let edge_scale = (w.norm() * v.norm(), w.norm() * u.norm());
 
         // 计算包围盒
         let bbox_diag1 = Aabb::new_point(q, q + u + v);
@@ -46,18 +61,56 @@ impl Quad {
             d,
             w,
             area,
+            edge_scale,
         }
     }
 
-    /// 检查点是否在四边形内部
+    /// 起始顶点
     #[inline]
-    fn is_interior(&self, a: f64, b: f64, rec: &mut HitRecord) -> bool {
-        if !(0.0..=1.0).contains(&a) || !(0.0..=1.0).contains(&b) {
-            return false;
+    pub fn q(&self) -> Point3 {
+        self.q
+    }
+
+    /// 第一条边
+    #[inline]
+    pub fn u(&self) -> Vec3 {
+        self.u
+    }
+
+    /// 第二条边
+    #[inline]
+    pub fn v(&self) -> Vec3 {
+        self.v
+    }
+
+    /// 材质
+    #[inline]
+    pub fn material(&self) -> &Arc<dyn Material> {
+        &self.mat
+    }
+
+    /// 检查点是否在四边形内部，edge_error 返回 (a, b) 的误差上界
+    ///
+    /// 落在 [0,1]² 之外但在误差范围内的点也算内部，共享一条边的两个四边形在舍入误差下
+    /// 不会同时拒绝边上的交点；纹理坐标截断到 [0,1]。误差上界只在边界附近才计算。
+    #[inline]
+    fn is_interior(
+        &self,
+        a: f64,
+        b: f64,
+        edge_error: impl FnOnce() -> (f64, f64),
+        rec: &mut HitRecord,
+    ) -> bool {
+        let unit = 0.0..=1.0;
+        if !unit.contains(&a) || !unit.contains(&b) {
+            let (tol_a, tol_b) = edge_error();
+            if !(-tol_a..=1.0 + tol_a).contains(&a) || !(-tol_b..=1.0 + tol_b).contains(&b) {
+                return false;
+            }
         }
 
-        rec.u = a;
-        rec.v = b;
+        rec.u = a.clamp(0.0, 1.0);
+        rec.v = b.clamp(0.0, 1.0);
         true
     }
 }
@@ -65,8 +118,9 @@ impl Quad {
 impl Hittable for Quad {
     fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
         let denom = self.normal.dot(&r.dir);
+        let dir_len2 = r.dir.norm_squared();
 
-        if denom.abs() < 1e-8 {
+        if denom * denom <= PARALLEL_SINE * PARALLEL_SINE * dir_len2 {
             return false;
         }
 
@@ -81,13 +135,27 @@ impl Hittable for Quad {
         let alpha = self.w.dot(&planar_hitpt_vector.cross(&self.v));
         let beta = self.w.dot(&self.u.cross(&planar_hitpt_vector));
 
-        if !self.is_interior(alpha, beta, rec) {
+        // 交点位置的误差上界：t 的求值误差沿光线方向放大 |t|·|dir| 倍，再加上 P - Q 的舍入误差
+        let edge_error = || {
+            let position_error = gamma(7)
+                * (r.orig.coords.norm()
+                    + t.abs() * dir_len2.sqrt()
+                    + self.q.coords.norm()
+                    + self.d.abs());
+            (
+                position_error * self.edge_scale.0,
+                position_error * self.edge_scale.1,
+            )
+        };
+        if !self.is_interior(alpha, beta, edge_error, rec) {
             return false;
         }
 
         // 设置命中记录
         rec.t = t;
         rec.p = intersection;
+        rec.dpdu = self.u;
+        rec.dpdv = self.v;
         rec.mat = self.mat.clone();
         rec.set_face_normal(r, &self.normal);
 
@@ -99,25 +167,63 @@ impl Hittable for Quad {
         Some(self.bbox)
     }
 
-    fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
-        let mut rec = HitRecord::default();
-        if !self.hit(
-            &Ray::new(*origin, *direction, 0.0),
-            Interval::new(0.001, f64::INFINITY),
-            &mut rec,
-        ) {
-            return 0.0;
-        }
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3, time: f64) -> f64 {
+        planar_pdf_value(self, origin, direction, time)
+    }
+
+    fn random(&self, origin: &Point3, _time: f64) -> Vec3 {
+        planar_random(self, origin)
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        self.area
+    }
 
-        let distance_squared = rec.t * rec.t * direction.norm_squared();
-        let cosine = (direction.dot(&rec.normal) / direction.norm()).abs();
+    /// `DiffuseLight` 双面发光，按两面计算
+    fn power(&self) -> Color {
+        let points = power_grid().map(|(a, b)| (a, b, self.q + a * self.u + b * self.v));
+        diffuse_emitter_power(self.mat.as_ref(), self.area, 2.0, points)
+    }
+
+    /// 烘焙 u × v 法线所指的一侧，纹理坐标覆盖整个 [0,1]²
+    fn uv_triangles(&self) -> Vec<UvTriangle> {
+        let (p00, p10) = (self.q, self.q + self.u);
+        let (p11, p01) = (p10 + self.v, self.q + self.v);
+        let n = [self.normal; 3];
+        vec![
+            UvTriangle {
+                uv: [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)],
+                p: [p00, p10, p11],
+                n,
+            },
+            UvTriangle {
+                uv: [(0.0, 0.0), (1.0, 1.0), (0.0, 1.0)],
+                p: [p00, p11, p01],
+                n,
+            },
+        ]
+    }
+}
 
-        distance_squared / (cosine * self.area)
+impl Planar for Quad {
+    #[inline]
+    fn plane_normal(&self) -> Vec3 {
+        self.normal
+    }
+
+    fn vertices(&self) -> Vec<Point3> {
+        vec![
+            self.q,
+            self.q + self.u,
+            self.q + self.u + self.v,
+            self.q + self.v,
+        ]
     }
 
-    fn random(&self, origin: &Point3) -> Vec3 {
-        let p = self.q + (random_double() * self.u) + (random_double() * self.v);
-        p - *origin
+    #[inline]
+    fn sample_point(&self, r1: f64, r2: f64) -> Point3 {
+        self.q + r1 * self.u + r2 * self.v
     }
 }
 
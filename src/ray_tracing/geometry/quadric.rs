@@ -0,0 +1,210 @@
+//! 二次曲面：由 4×4 对称矩阵 Q 定义的隐式曲面 xᵀQx = 0（x 为齐次坐标）
+//!
+//! 椭球、抛物面、双曲面、圆柱和圆锥都是二次曲面的特例。抛物面等曲面是无界的，
+//! 因此每个二次曲面都带一个裁剪包围盒，只有盒内的部分可见。
+
+use super::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::materials::material::Material;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use nalgebra::{Matrix4, Vector4};
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// 二次曲面几何体
+pub struct Quadric {
+    q: Matrix4<f64>,
+    clip: Aabb,
+    mat: Arc<dyn Material>,
+}
+
+impl Quadric {
+    /// 由矩阵和裁剪包围盒创建，非对称的矩阵会被对称化
+    ///
+    /// 法线取梯度方向，即指向 xᵀQx > 0 的一侧。
+    pub fn new(q: Matrix4<f64>, clip: Aabb, mat: Arc<dyn Material>) -> Self {
+        let mut clip = clip;
+        clip.pad_to_minimums();
+        Self {
+            q: (q + q.transpose()) * 0.5,
+            clip,
+            mat,
+        }
+    }
+
+    /// 由一般方程 Ax² + By² + Cz² + 2Dxy + 2Exz + 2Fyz + 2Gx + 2Hy + 2Iz + J = 0 的系数创建
+    pub fn from_coefficients(
+        [a, b, c, d, e, f, g, h, i, j]: [f64; 10],
+        clip: Aabb,
+        mat: Arc<dyn Material>,
+    ) -> Self {
+        #[rustfmt::skip]
+        let q = Matrix4::new(
+            a, d, e, g,
+            d, b, f, h,
+            e, f, c, i,
+            g, h, i, j,
+        );
+        Self::new(q, clip, mat)
+    }
+
+    /// 椭球：(x/rx)² + (y/ry)² + (z/rz)² = 1
+    pub fn ellipsoid(center: Point3, radii: Vec3, mat: Arc<dyn Material>) -> Self {
+        let q = Matrix4::from_diagonal(&Vector4::new(
+            1.0 / (radii.x * radii.x),
+            1.0 / (radii.y * radii.y),
+            1.0 / (radii.z * radii.z),
+            -1.0,
+        ));
+        let clip = Aabb::new_point(center - radii, center + radii);
+        Self::new(translated(&q, &center.coords), clip.expand(1e-6), mat)
+    }
+
+    /// 沿 +y 开口的旋转抛物面，顶点在 vertex，高度 height 处的半径为 radius
+    pub fn paraboloid(vertex: Point3, radius: f64, height: f64, mat: Arc<dyn Material>) -> Self {
+        // (x² + z²)·h/r² - y = 0
+        let k = height / (radius * radius);
+        let mut q = Matrix4::from_diagonal(&Vector4::new(k, 0.0, k, 0.0));
+        q[(1, 3)] = -0.5;
+        q[(3, 1)] = -0.5;
+        let extent = Vec3::new(radius, 0.0, radius);
+        let clip = Aabb::new_point(
+            vertex - extent,
+            vertex + extent + Vec3::new(0.0, height, 0.0),
+        );
+        Self::new(translated(&q, &vertex.coords), clip.expand(1e-6), mat)
+    }
+
+    /// 沿 y 轴的单叶旋转双曲面，腰部（y=center.y）半径为 waist_radius，
+    /// 上下两端（高度 ±height/2）半径为 end_radius，要求 end_radius > waist_radius
+    pub fn hyperboloid(
+        center: Point3,
+        waist_radius: f64,
+        end_radius: f64,
+        height: f64,
+        mat: Arc<dyn Material>,
+    ) -> Self {
+        // (x² + z²)/a² - y²/c² = 1，由 y = h/2 处半径为 end_radius 解出 c²
+        let half = 0.5 * height;
+        let a2 = waist_radius * waist_radius;
+        let c2 = half * half / ((end_radius * end_radius / a2) - 1.0).max(1e-12);
+        let q = Matrix4::from_diagonal(&Vector4::new(1.0 / a2, -1.0 / c2, 1.0 / a2, -1.0));
+        let extent = Vec3::new(end_radius, half, end_radius);
+        let clip = Aabb::new_point(center - extent, center + extent);
+        Self::new(translated(&q, &center.coords), clip.expand(1e-6), mat)
+    }
+
+    /// 定义曲面的对称矩阵
+    #[inline]
+    pub fn matrix(&self) -> &Matrix4<f64> {
+        &self.q
+    }
+
+    /// 裁剪包围盒
+    #[inline]
+    pub fn clip(&self) -> &Aabb {
+        &self.clip
+    }
+
+    /// 点 p 处的隐式函数值 xᵀQx
+    #[inline]
+    pub fn evaluate(&self, p: &Point3) -> f64 {
+        let x = p.to_homogeneous();
+        x.dot(&(self.q * x))
+    }
+
+    /// 点 p 处的（未归一化的）梯度，即外法线方向
+    #[inline]
+    pub fn gradient(&self, p: &Point3) -> Vec3 {
+        2.0 * (self.q * p.to_homogeneous()).xyz()
+    }
+}
+
+/// 把以原点为中心定义的矩阵平移 offset：Q' = Tᵀ Q T，T 为平移 -offset 的矩阵
+fn translated(q: &Matrix4<f64>, offset: &Vec3) -> Matrix4<f64> {
+    let t = Matrix4::new_translation(&-offset);
+    t.transpose() * q * t
+}
+
+/// 按从小到大的顺序返回 a·t² + b·t + c = 0 的实根（退化为一次方程时只有一个根）
+fn solve_quadratic(a: f64, b: f64, c: f64) -> Option<(f64, f64)> {
+    let scale = b.abs().max(c.abs()).max(1e-300);
+    if a.abs() <= 1e-12 * scale {
+        if b.abs() <= 1e-300 {
+            return None;
+        }
+        let t = -c / b;
+        return Some((t, t));
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    // 数值稳定的求根公式，避免 b 与根号相减时的抵消误差
+    let q = -0.5 * (b + b.signum() * discriminant.sqrt());
+    if q == 0.0 {
+        return Some((0.0, 0.0));
+    }
+    let (t0, t1) = (q / a, c / q);
+    Some((t0.min(t1), t0.max(t1)))
+}
+
+impl Hittable for Quadric {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let Some(ray_t) = self.clip.clip(r, ray_t) else {
+            return false;
+        };
+
+        let o = r.orig.to_homogeneous();
+        let d = r.dir.to_homogeneous();
+        let qd = self.q * d;
+        let a = d.dot(&qd);
+        let b = 2.0 * o.dot(&qd);
+        let c = o.dot(&(self.q * o));
+        let Some((t0, t1)) = solve_quadratic(a, b, c) else {
+            return false;
+        };
+
+        let Some(t) = [t0, t1].into_iter().find(|&t| ray_t.surrounds(t)) else {
+            return false;
+        };
+
+        rec.t = t;
+        rec.p = r.at(t);
+        let gradient = self.gradient(&rec.p);
+        // 奇异点（如圆锥顶点）梯度为零，取与光线相对的方向
+        let outward_normal = if gradient.norm_squared() > 1e-24 {
+            gradient.normalize()
+        } else {
+            -r.dir.normalize()
+        };
+
+        // 以裁剪盒中心为原点的柱面坐标
+        let local = rec.p - self.clip.center();
+        rec.u = ((-local.z).atan2(local.x) + PI) / (2.0 * PI);
+        rec.v = ((rec.p.y - self.clip.y.min) / self.clip.y.size()).clamp(0.0, 1.0);
+        rec.dpdu = Vec3::zeros();
+        rec.dpdv = Vec3::zeros();
+
+        rec.set_face_normal(r, &outward_normal);
+        rec.mat = self.mat.clone();
+        true
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.clip)
+    }
+}
+
+impl std::fmt::Debug for Quadric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Quadric")
+            .field("q", &self.q)
+            .field("clip", &self.clip)
+            .field("mat", &"<Material>")
+            .finish()
+    }
+}
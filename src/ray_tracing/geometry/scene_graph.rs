@@ -0,0 +1,182 @@
+use super::hittable::Hittable;
+use super::hittable_list::HittableList;
+use super::transforms::motion::MotionTransform;
+use super::transforms::rotate_y::RotateY;
+use super::transforms::translate::Translate;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::random::degrees_to_radians;
+use std::sync::Arc;
+
+/// 场景图节点的局部变换：先绕Y轴旋转，再平移
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub rotate_y: f64, // 绕Y轴旋转角度（度）
+    pub translate: Vec3,
+}
+
+impl Transform {
+    /// 单位变换
+    #[inline]
+    pub fn identity() -> Self {
+        Self {
+            rotate_y: 0.0,
+            translate: Vec3::zeros(),
+        }
+    }
+
+    /// 仅平移的变换
+    #[inline]
+    pub fn translation(offset: Vec3) -> Self {
+        Self {
+            rotate_y: 0.0,
+            translate: offset,
+        }
+    }
+
+    /// 仅绕Y轴旋转的变换
+    #[inline]
+    pub fn rotation_y(angle: f64) -> Self {
+        Self {
+            rotate_y: angle,
+            translate: Vec3::zeros(),
+        }
+    }
+
+    /// 复合变换：先应用 `child`，再应用 `self`
+    pub fn then(&self, child: &Transform) -> Transform {
+        Transform {
+            rotate_y: self.rotate_y + child.rotate_y,
+            translate: self.rotate_vec(&child.translate) + self.translate,
+        }
+    }
+
+    /// 与 other 之间按 t 线性插值（旋转角和平移分别插值，角度不取模）
+    #[inline]
+    pub fn lerp(&self, other: &Transform, t: f64) -> Transform {
+        Transform {
+            rotate_y: self.rotate_y + (other.rotate_y - self.rotate_y) * t,
+            translate: self.translate + (other.translate - self.translate) * t,
+        }
+    }
+
+    /// 用本变换的旋转部分旋转向量（与 RotateY 的局部到世界方向一致）
+    #[inline]
+    fn rotate_vec(&self, v: &Vec3) -> Vec3 {
+        let radians = degrees_to_radians(self.rotate_y);
+        let (sin_theta, cos_theta) = radians.sin_cos();
+        Vec3::new(
+            cos_theta * v.x + sin_theta * v.z,
+            v.y,
+            -sin_theta * v.x + cos_theta * v.z,
+        )
+    }
+
+    /// 用 RotateY/Translate 包装物体，恒等部分不会产生多余的包装层
+    pub fn apply(&self, object: Arc<dyn Hittable>) -> Arc<dyn Hittable> {
+        let rotated = if self.rotate_y == 0.0 {
+            object
+        } else {
+            Arc::new(RotateY::new(object, self.rotate_y))
+        };
+
+        if self.translate == Vec3::zeros() {
+            rotated
+        } else {
+            Arc::new(Translate::new(rotated, self.translate))
+        }
+    }
+}
+
+/// 快门打开时使用 open、关闭时使用 close 变换包装物体；两者相同时退化为静态变换
+pub fn apply_motion(
+    open: &Transform,
+    close: &Transform,
+    object: Arc<dyn Hittable>,
+) -> Arc<dyn Hittable> {
+    if open == close {
+        open.apply(object)
+    } else {
+        Arc::new(MotionTransform::new(object, *open, *close))
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// 场景图节点，子节点的变换相对于父节点
+#[derive(Debug, Default)]
+pub struct Node {
+    pub transform: Transform,
+    /// 快门关闭时的局部变换，None 表示节点在快门期间静止（始终为 transform）
+    pub motion: Option<Transform>,
+    pub children: Vec<Node>,
+    pub geometry: Option<Arc<dyn Hittable>>,
+}
+
+impl Node {
+    /// 创建空的分组节点
+    #[inline]
+    pub fn new(transform: Transform) -> Self {
+        Self {
+            transform,
+            motion: None,
+            children: Vec::new(),
+            geometry: None,
+        }
+    }
+
+    /// 创建包含几何体的叶子节点
+    #[inline]
+    pub fn with_geometry(transform: Transform, geometry: Arc<dyn Hittable>) -> Self {
+        Self {
+            transform,
+            motion: None,
+            children: Vec::new(),
+            geometry: Some(geometry),
+        }
+    }
+
+    /// 设置快门关闭时的局部变换，节点在快门期间从 transform 运动到 close
+    pub fn with_motion(mut self, close: Transform) -> Self {
+        self.motion = Some(close);
+        self
+    }
+
+    /// 添加子节点
+    pub fn add_child(&mut self, child: Node) -> &mut Self {
+        self.children.push(child);
+        self
+    }
+
+    /// 展开为世界空间中的物体列表
+    pub fn flatten(&self) -> HittableList {
+        let mut list = HittableList::new();
+        self.flatten_into(&Transform::identity(), &mut list);
+        list
+    }
+
+    /// 在父节点累积变换下展开到给定列表
+    pub fn flatten_into(&self, parent: &Transform, list: &mut HittableList) {
+        self.flatten_motion(parent, parent, list);
+    }
+
+    /// 在父节点快门打开、关闭时的累积变换下展开到给定列表
+    ///
+    /// 两个时刻分别复合出世界变换，物体在两者之间插值；父子节点同时运动时，
+    /// 中间时刻是复合结果之间的插值，而非逐层插值后的复合（旋转的父节点带动平移的子节点时有差别）。
+    pub fn flatten_motion(&self, open: &Transform, close: &Transform, list: &mut HittableList) {
+        let world_open = open.then(&self.transform);
+        let world_close = close.then(self.motion.as_ref().unwrap_or(&self.transform));
+
+        if let Some(geometry) = &self.geometry {
+            list.add(apply_motion(&world_open, &world_close, geometry.clone()));
+        }
+
+        for child in &self.children {
+            child.flatten_motion(&world_open, &world_close, list);
+        }
+    }
+}
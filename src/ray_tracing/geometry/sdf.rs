@@ -0,0 +1,143 @@
+//! 有向距离场（SDF）物体：用球体追踪（sphere tracing）求交
+//!
+//! 距离场给出空间中任意一点到表面的（下界）距离，光线每一步前进该距离，距离小于阈值时视为命中。
+//! 法线由距离场的梯度（四面体差分）估计。分形等没有解析求交的形状只需实现 [`DistanceField`]。
+//!
+//! 距离场还可以在命中点给出两个着色参数（如分形的轨道陷阱和迭代次数），写入命中记录的纹理坐标 (u, v)，
+//! 材质用纹理把它们映射为颜色。
+
+use super::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::materials::material::Material;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use std::sync::Arc;
+
+/// 默认的最大步数
+pub const DEFAULT_MAX_STEPS: usize = 256;
+/// 默认的命中阈值（场景单位）
+pub const DEFAULT_EPSILON: f64 = 1e-4;
+
+/// 有向距离场：表面外为正、内部为负
+pub trait DistanceField: Send + Sync + std::fmt::Debug {
+    /// 点到表面的距离（可以是保守的下界）
+    fn distance(&self, p: &Point3) -> f64;
+
+    /// 包含整个表面的包围盒，光线只在其中步进
+    fn bounds(&self) -> Aabb;
+
+    /// 表面点处的着色参数，作为纹理坐标 (u, v)，默认为 (0, 0)
+    fn coloring(&self, _p: &Point3) -> (f64, f64) {
+        (0.0, 0.0)
+    }
+}
+
+/// 球体追踪渲染的距离场物体
+#[derive(Debug)]
+pub struct SdfObject {
+    field: Arc<dyn DistanceField>,
+    mat: Arc<dyn Material>,
+    bbox: Aabb,
+    max_steps: usize,
+    epsilon: f64,
+}
+
+impl SdfObject {
+    /// 用默认步数和阈值创建距离场物体
+    pub fn new(field: Arc<dyn DistanceField>, mat: Arc<dyn Material>) -> Self {
+        let bbox = field.bounds();
+        Self {
+            field,
+            mat,
+            bbox,
+            max_steps: DEFAULT_MAX_STEPS,
+            epsilon: DEFAULT_EPSILON,
+        }
+    }
+
+    /// 设置最大步数，细节多的分形需要更多步
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps.max(1);
+        self
+    }
+
+    /// 设置命中阈值，越小细节越清晰、步数越多
+    pub fn with_epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = epsilon.max(1e-9);
+        self
+    }
+
+    /// 距离场
+    #[inline]
+    pub fn field(&self) -> &Arc<dyn DistanceField> {
+        &self.field
+    }
+
+    /// 距离场梯度方向（四面体差分），即表面外法线
+    fn normal(&self, p: &Point3) -> Vec3 {
+        let h = self.epsilon * 0.5;
+        let offsets = [
+            Vec3::new(1.0, -1.0, -1.0),
+            Vec3::new(-1.0, -1.0, 1.0),
+            Vec3::new(-1.0, 1.0, -1.0),
+            Vec3::new(1.0, 1.0, 1.0),
+        ];
+        let gradient: Vec3 = offsets
+            .iter()
+            .map(|k| k * self.field.distance(&(p + k * h)))
+            .sum();
+        gradient
+            .try_normalize(1e-30)
+            .unwrap_or(Vec3::new(0.0, 1.0, 0.0))
+    }
+}
+
+impl Hittable for SdfObject {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let Some(range) = self.bbox.clip(r, ray_t) else {
+            return false;
+        };
+        let length = r.dir.norm();
+        if length == 0.0 {
+            return false;
+        }
+        // 按世界空间距离步进；从表面出发的光线要先离开表面才能再次命中
+        let (start, end) = (range.min * length, range.max * length);
+        let dir = r.dir / length;
+        let mut s = start;
+        let mut left_surface = false;
+        for _ in 0..self.max_steps {
+            let p = r.orig + dir * s;
+            let d = self.field.distance(&p).abs();
+            if d < self.epsilon {
+                if left_surface {
+                    let outward = self.normal(&p);
+                    let (u, v) = self.field.coloring(&p);
+                    rec.t = s / length;
+                    rec.p = p;
+                    rec.u = u;
+                    rec.v = v;
+                    rec.dpdu = Vec3::zeros();
+                    rec.dpdv = Vec3::zeros();
+                    rec.mat = self.mat.clone();
+                    rec.set_face_normal(r, &outward);
+                    return true;
+                }
+                s += self.epsilon;
+            } else {
+                left_surface = true;
+                s += d;
+            }
+            if s > end {
+                return false;
+            }
+        }
+        false
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.bbox)
+    }
+}
@@ -1,4 +1,4 @@
-use super::hittable::{HitRecord, Hittable};
+use super::hittable::{HitRecord, Hittable, diffuse_emitter_power, power_grid};
 use crate::ray_tracing::materials::material::Material;
 use crate::ray_tracing::math::aabb::Aabb;
 use crate::ray_tracing::math::interval::Interval;
@@ -52,9 +52,33 @@ impl Sphere {
         }
     }
 
+    /// 时刻 time 的球心（静态球体与时间无关）
+    #[inline]
+    pub fn center(&self, time: f64) -> Point3 {
+        self.center.at(time)
+    }
+
+    /// 球心是否随时间运动
+    #[inline]
+    pub fn is_moving(&self) -> bool {
+        self.center.dir != Vec3::zeros()
+    }
+
+    /// 半径
+    #[inline]
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// 材质
+    #[inline]
+    pub fn material(&self) -> &Arc<dyn Material> {
+        &self.mat
+    }
+
     /// 获取球面UV坐标
     #[inline]
-    fn get_sphere_uv(p: &Vec3) -> (f64, f64) {
+    pub fn get_sphere_uv(p: &Vec3) -> (f64, f64) {
         // p: 单位球体表面上的点 (球心在原点)
         let theta = (-p.y).acos();
         let phi = (-p.z).atan2(p.x) + std::f64::consts::PI;
@@ -65,6 +89,17 @@ impl Sphere {
         (u, v)
     }
 
+    /// 球面上单位法线为 n 处的 (∂p/∂u, ∂p/∂v)，与 `get_sphere_uv` 的参数化一致
+    #[inline]
+    fn uv_derivatives(&self, n: &Vec3) -> (Vec3, Vec3) {
+        let sin_theta = (1.0 - n.y * n.y).max(0.0).sqrt().max(1e-6);
+        let dpdu = 2.0 * std::f64::consts::PI * self.radius * Vec3::new(n.z, 0.0, -n.x);
+        let dpdv = std::f64::consts::PI
+            * self.radius
+            * Vec3::new(-n.y * n.x / sin_theta, sin_theta, -n.y * n.z / sin_theta);
+        (dpdu, dpdv)
+    }
+
     /// 生成指向球体的随机方向
     fn random_to_sphere(&self, distance_squared: f64) -> Vec3 {
         let r1 = random_double();
@@ -107,9 +142,14 @@ impl Hittable for Sphere {
         rec.p = r.at(rec.t);
         // 计算 UV 坐标
         let outward_normal_vec = (rec.p - current_center) / self.radius;
+        if self.center.dir != Vec3::zeros() {
+            let offset = rec.p - current_center;
+            rec.motion = Some([self.center.at(0.0) + offset, self.center.at(1.0) + offset]);
+        }
         let (u, v) = Self::get_sphere_uv(&outward_normal_vec);
         rec.u = u;
         rec.v = v;
+        (rec.dpdu, rec.dpdv) = self.uv_derivatives(&outward_normal_vec);
 
         rec.set_face_normal(r, &outward_normal_vec);
         rec.mat = self.mat.clone();
@@ -122,17 +162,18 @@ impl Hittable for Sphere {
         Some(self.bbox)
     }
 
-    fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
+    /// 运动球体按给定时刻的球心位置采样
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3, time: f64) -> f64 {
         let mut rec = HitRecord::default();
         if !self.hit(
-            &Ray::new(*origin, *direction, 0.0),
+            &Ray::new(*origin, *direction, time),
             Interval::new(0.001, f64::INFINITY),
             &mut rec,
         ) {
             return 0.0;
         }
 
-        let current_center = self.center.at(0.0);
+        let current_center = self.center.at(time);
         let dist_squared = (current_center - *origin).norm_squared();
         let cos_theta_max = (1.0 - self.radius * self.radius / dist_squared).sqrt();
         let solid_angle = 2.0 * std::f64::consts::PI * (1.0 - cos_theta_max);
@@ -140,14 +181,35 @@ impl Hittable for Sphere {
         1.0 / solid_angle
     }
 
-    fn random(&self, origin: &Point3) -> Vec3 {
-        let current_center = self.center.at(0.0);
+    fn random(&self, origin: &Point3, time: f64) -> Vec3 {
+        let current_center = self.center.at(time);
         let direction = current_center - *origin;
         let distance_squared = direction.norm_squared();
 
         let onb = ONB::new(&direction);
         onb.local_to_world(&self.random_to_sphere(distance_squared))
     }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        4.0 * std::f64::consts::PI * self.radius * self.radius
+    }
+
+    /// 按 t=0 时刻的球心位置在 (u, v) 网格上平均发射辐射度
+    fn power(&self) -> Color {
+        let center = self.center.at(0.0);
+        let points = power_grid().map(|(u, v)| {
+            let theta = v * std::f64::consts::PI;
+            let phi = u * 2.0 * std::f64::consts::PI - std::f64::consts::PI;
+            let n = Vec3::new(
+                theta.sin() * phi.cos(),
+                -theta.cos(),
+                -theta.sin() * phi.sin(),
+            );
+            (u, v, center + self.radius * n)
+        });
+        diffuse_emitter_power(self.mat.as_ref(), self.area(), 1.0, points)
+    }
 }
 
 impl std::fmt::Debug for Sphere {
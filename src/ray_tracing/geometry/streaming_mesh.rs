@@ -0,0 +1,448 @@
+//! 流式三角网格：顶点和索引缓冲区通过内存映射按需读取，分块的 BVH 在光线首次进入时才构建
+//!
+//! 用于比内存更大的扫描模型。网格先用 [`write_streaming_mesh`] 转换为分块的二进制文件：
+//! 三角形按空间位置划分成若干块并连续存放，文件头之后是各块的包围盒。打开文件只读取块表，
+//! 顶层 BVH 由各块的包围盒组成，渲染时只有被光线触及的块才会读取顶点并建立块内 BVH。
+//! 已构建的块受内存预算约束，超出时释放最久未被光线访问的块（见 [`ChunkBudget`]）。
+//!
+//! 文件格式（小端序）：
+//! - 文件头 40 字节：魔数 `RTSMESH1`、属性标志 u32（1 = 法线，2 = 纹理坐标）、保留 u32、
+//!   顶点数 u64、三角形数 u64、块数 u64
+//! - 块表，每块 40 字节：包围盒最小点 3×f32、最大点 3×f32、首个三角形序号 u64、三角形数 u64
+//! - 顶点位置 3×f32，法线 3×f32（可选），纹理坐标 2×f32（可选）
+//! - 三角形索引 3×u32
+//!
+//! 流式网格不作为面光源采样。文件通过内存映射读取，打开函数是 `unsafe` 的：
+//! 网格存在期间文件不能被截断或改写（见 [`MappedFile::open`]）。
+
+use super::hittable::{HitRecord, Hittable};
+use super::hittable_list::HittableList;
+use super::mesh::MeshData;
+use super::triangle::Triangle;
+use crate::ray_tracing::acceleration::bvh::BvhNode;
+use crate::ray_tracing::acceleration::lazy_bvh::{ChunkBudget, LazyBvh};
+use crate::ray_tracing::materials::material::Material;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::mapped_file::MappedFile;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+const MAGIC: &[u8; 8] = b"RTSMESH1";
+const HEADER_SIZE: usize = 40;
+const CHUNK_ENTRY_SIZE: usize = 40;
+const HAS_NORMALS: u32 = 1;
+const HAS_UVS: u32 = 2;
+
+/// 每块默认的三角形数
+pub const DEFAULT_CHUNK_TRIANGLES: usize = 16384;
+/// 已构建块的默认内存预算（字节）
+pub const DEFAULT_MEMORY_BUDGET: usize = 1 << 30;
+
+/// 块构建后每个三角形的估计内存：三角形、块内 BVH 的一个节点以及两者的引用计数头
+fn resident_bytes_per_triangle() -> usize {
+    size_of::<Triangle>() + size_of::<BvhNode>() + 4 * size_of::<usize>()
+}
+
+fn invalid(source: &str, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", source, message),
+    )
+}
+
+/// 把网格写成分块的流式网格文件，每块最多 chunk_triangles 个三角形，跳过越界索引
+///
+/// 返回写入的块数。法线和纹理坐标按 [`MeshData`] 的约定，长度与顶点数相同时才写入。
+pub fn write_streaming_mesh(
+    path: impl AsRef<Path>,
+    data: &MeshData,
+    chunk_triangles: usize,
+) -> io::Result<usize> {
+    let path = path.as_ref();
+    let source = path.display().to_string();
+    let vertex_count = data.positions.len();
+    if u32::try_from(vertex_count).is_err() {
+        return Err(invalid(&source, "顶点数超出 u32 索引范围"));
+    }
+    let has_normals = vertex_count > 0 && data.normals.len() == vertex_count;
+    let has_uvs = vertex_count > 0 && data.uvs.len() == vertex_count;
+
+    // 位置按 f32 存储，包围盒也按存储后的值计算，保证包含块内三角形
+    let stored = |i: usize| data.positions[i].coords.map(|c| c as f32 as f64);
+    let faces: Vec<[usize; 3]> = data
+        .indices
+        .iter()
+        .filter(|face| face.iter().all(|&i| i < vertex_count))
+        .copied()
+        .collect();
+    let centroids: Vec<Vec3> = faces
+        .iter()
+        .map(|&[a, b, c]| (stored(a) + stored(b) + stored(c)) / 3.0)
+        .collect();
+
+    let mut order: Vec<usize> = (0..faces.len()).collect();
+    let mut ranges = Vec::new();
+    split_chunks(
+        &mut order,
+        0,
+        &centroids,
+        chunk_triangles.max(1),
+        &mut ranges,
+    );
+
+    let mut out = io::BufWriter::new(std::fs::File::create(path)?);
+    let flags = if has_normals { HAS_NORMALS } else { 0 } | if has_uvs { HAS_UVS } else { 0 };
+    out.write_all(MAGIC)?;
+    out.write_all(&flags.to_le_bytes())?;
+    out.write_all(&0u32.to_le_bytes())?;
+    for count in [vertex_count, faces.len(), ranges.len()] {
+        out.write_all(&(count as u64).to_le_bytes())?;
+    }
+
+    for &(start, len) in &ranges {
+        let mut min = Vec3::repeat(f64::INFINITY);
+        let mut max = Vec3::repeat(f64::NEG_INFINITY);
+        for &face in &order[start..start + len] {
+            for &v in &faces[face] {
+                let p = stored(v);
+                min = min.inf(&p);
+                max = max.sup(&p);
+            }
+        }
+        for c in min.iter().chain(max.iter()) {
+            out.write_all(&(*c as f32).to_le_bytes())?;
+        }
+        out.write_all(&(start as u64).to_le_bytes())?;
+        out.write_all(&(len as u64).to_le_bytes())?;
+    }
+
+    for p in &data.positions {
+        for c in p.coords.iter() {
+            out.write_all(&(*c as f32).to_le_bytes())?;
+        }
+    }
+    if has_normals {
+        for n in &data.normals {
+            for c in n.iter() {
+                out.write_all(&(*c as f32).to_le_bytes())?;
+            }
+        }
+    }
+    if has_uvs {
+        for &(u, v) in &data.uvs {
+            out.write_all(&(u as f32).to_le_bytes())?;
+            out.write_all(&(v as f32).to_le_bytes())?;
+        }
+    }
+    for &face in &order {
+        for &v in &faces[face] {
+            out.write_all(&(v as u32).to_le_bytes())?;
+        }
+    }
+    out.flush()?;
+    Ok(ranges.len())
+}
+
+/// 沿质心包围盒的最长轴按中位数递归划分，直到每块不超过 max 个三角形
+fn split_chunks(
+    order: &mut [usize],
+    offset: usize,
+    centroids: &[Vec3],
+    max: usize,
+    ranges: &mut Vec<(usize, usize)>,
+) {
+    if order.is_empty() {
+        return;
+    }
+    if order.len() <= max {
+        ranges.push((offset, order.len()));
+        return;
+    }
+    let mut lo = Vec3::repeat(f64::INFINITY);
+    let mut hi = Vec3::repeat(f64::NEG_INFINITY);
+    for &i in order.iter() {
+        lo = lo.inf(&centroids[i]);
+        hi = hi.sup(&centroids[i]);
+    }
+    let axis = (hi - lo).imax();
+    let mid = order.len() / 2;
+    order.select_nth_unstable_by(mid, |&a, &b| {
+        centroids[a][axis].total_cmp(&centroids[b][axis])
+    });
+    let (left, right) = order.split_at_mut(mid);
+    split_chunks(left, offset, centroids, max, ranges);
+    split_chunks(right, offset + mid, centroids, max, ranges);
+}
+
+/// 映射的流式网格文件
+#[derive(Debug)]
+struct MeshFile {
+    bytes: MappedFile,
+    vertex_count: usize,
+    positions: usize,
+    normals: Option<usize>,
+    uvs: Option<usize>,
+    indices: usize,
+}
+
+impl MeshFile {
+    #[inline]
+    fn f32_at(&self, offset: usize) -> f64 {
+        let bytes: [u8; 4] = self.bytes[offset..offset + 4].try_into().unwrap();
+        f32::from_le_bytes(bytes) as f64
+    }
+
+    #[inline]
+    fn u32_at(&self, offset: usize) -> usize {
+        let bytes: [u8; 4] = self.bytes[offset..offset + 4].try_into().unwrap();
+        u32::from_le_bytes(bytes) as usize
+    }
+
+    #[inline]
+    fn u64_at(&self, offset: usize) -> u64 {
+        let bytes: [u8; 8] = self.bytes[offset..offset + 8].try_into().unwrap();
+        u64::from_le_bytes(bytes)
+    }
+
+    #[inline]
+    fn vec3_at(&self, offset: usize) -> Vec3 {
+        Vec3::new(
+            self.f32_at(offset),
+            self.f32_at(offset + 4),
+            self.f32_at(offset + 8),
+        )
+    }
+
+    /// 读取一个三角形，索引越界或退化时返回 None
+    fn triangle(&self, index: usize, mat: &Arc<dyn Material>) -> Option<Triangle> {
+        let base = self.indices + index * 12;
+        let face = [
+            self.u32_at(base),
+            self.u32_at(base + 4),
+            self.u32_at(base + 8),
+        ];
+        if face.iter().any(|&v| v >= self.vertex_count) {
+            return None;
+        }
+        let [a, b, c] = face.map(|v| Point3::from(self.vec3_at(self.positions + v * 12)));
+        let mut triangle = Triangle::new(a, b, c, mat.clone());
+        if let Some(normals) = self.normals {
+            triangle = triangle.with_normals(face.map(|v| self.vec3_at(normals + v * 12)));
+        }
+        if let Some(uvs) = self.uvs {
+            triangle = triangle
+                .with_uvs(face.map(|v| (self.f32_at(uvs + v * 8), self.f32_at(uvs + v * 8 + 4))));
+        }
+        (!triangle.is_degenerate()).then_some(triangle)
+    }
+}
+
+/// 流式三角网格，整体使用同一个材质
+#[derive(Clone)]
+pub struct StreamingMesh {
+    file: Arc<MeshFile>,
+    chunks: Arc<[Arc<LazyBvh>]>,
+    root: Option<Arc<BvhNode>>,
+    triangle_count: usize,
+    budget: Arc<ChunkBudget>,
+}
+
+impl StreamingMesh {
+    /// 打开流式网格文件，只读取文件头和块表，已构建块使用默认内存预算
+    ///
+    /// # Safety
+    ///
+    /// 文件通过内存映射读取，返回的网格（及其克隆）存在期间文件不能被截断或改写，
+    /// 见 [`MappedFile::open`]。
+    pub unsafe fn open(path: impl AsRef<Path>, mat: Arc<dyn Material>) -> io::Result<Self> {
+        // SAFETY: 前提与本函数相同，由调用者保证
+        unsafe { Self::open_with_budget(path, mat, DEFAULT_MEMORY_BUDGET) }
+    }
+
+    /// 打开流式网格文件，已构建块的估计内存超过 budget 字节时按最近最少使用释放
+    ///
+    /// # Safety
+    ///
+    /// 与 [`StreamingMesh::open`] 相同：网格存在期间文件不能被截断或改写。
+    pub unsafe fn open_with_budget(
+        path: impl AsRef<Path>,
+        mat: Arc<dyn Material>,
+        budget: usize,
+    ) -> io::Result<Self> {
+        let budget = Arc::new(ChunkBudget::new(budget));
+        let path = path.as_ref();
+        let source = path.display().to_string();
+        // SAFETY: 文件在网格存在期间不被截断或改写，由调用者保证
+        let bytes = unsafe { MappedFile::open(path)? };
+        if bytes.len() < HEADER_SIZE || &bytes[..8] != MAGIC {
+            return Err(invalid(&source, "不是流式网格文件"));
+        }
+        let mut file = MeshFile {
+            bytes,
+            vertex_count: 0,
+            positions: 0,
+            normals: None,
+            uvs: None,
+            indices: 0,
+        };
+        let flags = file.u32_at(8) as u32;
+        let too_large = || invalid(&source, "文件头中的数量过大");
+        let count =
+            |file: &MeshFile, offset| usize::try_from(file.u64_at(offset)).map_err(|_| too_large());
+        let (vertex_count, triangle_count, chunk_count) =
+            (count(&file, 16)?, count(&file, 24)?, count(&file, 32)?);
+
+        // 依次计算各段的偏移，并检查文件长度足够
+        let mut end = HEADER_SIZE;
+        let mut section = |size: Option<usize>| -> io::Result<usize> {
+            let start = end;
+            end = size
+                .and_then(|size| end.checked_add(size))
+                .ok_or_else(too_large)?;
+            Ok(start)
+        };
+        let table = section(chunk_count.checked_mul(CHUNK_ENTRY_SIZE))?;
+        let positions = section(vertex_count.checked_mul(12))?;
+        let normals = if flags & HAS_NORMALS != 0 {
+            Some(section(vertex_count.checked_mul(12))?)
+        } else {
+            None
+        };
+        let uvs = if flags & HAS_UVS != 0 {
+            Some(section(vertex_count.checked_mul(8))?)
+        } else {
+            None
+        };
+        let indices = section(triangle_count.checked_mul(12))?;
+        if file.bytes.len() < end {
+            return Err(invalid(
+                &source,
+                &format!("文件被截断（需要 {} 字节，实际 {}）", end, file.bytes.len()),
+            ));
+        }
+        file.vertex_count = vertex_count;
+        file.positions = positions;
+        file.normals = normals;
+        file.uvs = uvs;
+        file.indices = indices;
+        let file = Arc::new(file);
+
+        let mut chunks = Vec::with_capacity(chunk_count);
+        for i in 0..chunk_count {
+            let entry = table + i * CHUNK_ENTRY_SIZE;
+            let bbox = Aabb::new_point(
+                Point3::from(file.vec3_at(entry)),
+                Point3::from(file.vec3_at(entry + 12)),
+            );
+            let first = count(&file, entry + 24)?;
+            let len = count(&file, entry + 32)?;
+            if first
+                .checked_add(len)
+                .is_none_or(|last| last > triangle_count)
+            {
+                return Err(invalid(&source, &format!("第 {} 块的三角形范围越界", i)));
+            }
+            let (file, mat) = (file.clone(), mat.clone());
+            let loader = Box::new(move || {
+                (first..first + len)
+                    .filter_map(|t| file.triangle(t, &mat))
+                    .map(|t| Arc::new(t) as Arc<dyn Hittable>)
+                    .collect::<HittableList>()
+            });
+            chunks.push(Arc::new(LazyBvh::with_budget(
+                bbox,
+                loader,
+                budget.clone(),
+                len * resident_bytes_per_triangle(),
+            )));
+        }
+
+        let root = (!chunks.is_empty()).then(|| {
+            let list: HittableList = chunks
+                .iter()
+                .map(|c| c.clone() as Arc<dyn Hittable>)
+                .collect();
+            Arc::new(BvhNode::new(&list))
+        });
+
+        Ok(Self {
+            file,
+            chunks: chunks.into(),
+            root,
+            triangle_count,
+            budget,
+        })
+    }
+
+    /// 文件中的三角形数量
+    #[inline]
+    pub fn triangle_count(&self) -> usize {
+        self.triangle_count
+    }
+
+    /// 块数
+    #[inline]
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// 当前已构建（未被预算释放）的块数
+    pub fn resident_chunks(&self) -> usize {
+        self.chunks.iter().filter(|c| c.is_resident()).count()
+    }
+
+    /// 当前已构建块的估计内存（字节）
+    #[inline]
+    pub fn resident_bytes(&self) -> usize {
+        self.budget.used()
+    }
+
+    /// 文件是否通过内存映射读取（不支持的平台上会整体读入内存）
+    #[inline]
+    pub fn is_mapped(&self) -> bool {
+        self.file.bytes.is_mapped()
+    }
+}
+
+impl Hittable for StreamingMesh {
+    #[inline]
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        self.root
+            .as_ref()
+            .is_some_and(|root| root.hit(r, ray_t, rec))
+    }
+
+    #[inline]
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        self.root
+            .as_ref()
+            .is_some_and(|root| root.hit_any(r, ray_t))
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.root.as_ref().and_then(|root| root.bounding_box())
+    }
+
+    fn collect_debug_boxes(&self, depth: usize, leaves_only: bool, boxes: &mut Vec<(Aabb, usize)>) {
+        if let Some(root) = &self.root {
+            root.collect_debug_boxes(depth, leaves_only, boxes);
+        }
+    }
+}
+
+impl std::fmt::Debug for StreamingMesh {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamingMesh")
+            .field("triangles", &self.triangle_count)
+            .field("chunks", &self.chunks.len())
+            .field("resident", &self.resident_chunks())
+            .field("budget", &self.budget)
+            .field("mapped", &self.is_mapped())
+            .finish()
+    }
+}
@@ -0,0 +1,270 @@
+//! Loop 细分曲面：把粗糙的三角网格细分为光滑曲面，导入低面数模型时无需外部预处理
+//!
+//! 每级细分把一个三角形分成四个，原顶点和新边点按 Loop 规则取邻域的加权平均，边界按三次 B 样条曲线细分。
+//! 导入的网格常在纹理接缝处复制顶点，拓扑按位置相同的顶点焊接后计算，纹理坐标按原顶点线性插值，
+//! 接缝两侧的顶点移动到相同位置，细分后不会开裂。细分后的法线按焊接后的邻接面积加权重新计算。
+
+use super::mesh::MeshData;
+use crate::ray_tracing::math::vec3::{Point3, Vec3};
+use std::collections::HashMap;
+
+/// 细分级别的选择方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Subdivision {
+    /// 每个网格细分固定的级数（0 为不细分）
+    Levels(u32),
+    /// 按屏幕尺寸自适应选择每个网格的级数
+    Adaptive(ScreenSizeTarget),
+}
+
+impl Default for Subdivision {
+    fn default() -> Self {
+        Self::Levels(0)
+    }
+}
+
+/// 自适应细分的目标：细分到网格最长的边投影到屏幕上不超过 max_edge_pixels 像素
+///
+/// 投影按网格包围盒上离相机最近的点估计，偏保守。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScreenSizeTarget {
+    /// 相机位置
+    pub lookfrom: Point3,
+    /// 垂直视场角（度）
+    pub vfov: f64,
+    /// 图像高度（像素）
+    pub image_height: u32,
+    /// 边的最大屏幕长度（像素）
+    pub max_edge_pixels: f64,
+    /// 最多细分的级数
+    pub max_levels: u32,
+}
+
+impl Subdivision {
+    /// 网格应细分的级数
+    pub fn levels_for(&self, data: &MeshData) -> u32 {
+        match self {
+            Self::Levels(levels) => *levels,
+            Self::Adaptive(target) => target.levels_for(data),
+        }
+    }
+}
+
+impl ScreenSizeTarget {
+    /// 网格应细分的级数：每级细分使边长减半
+    pub fn levels_for(&self, data: &MeshData) -> u32 {
+        let Some((min, max)) = bounds(&data.positions) else {
+            return 0;
+        };
+        let longest = data
+            .indices
+            .iter()
+            .filter(|face| face.iter().all(|&i| i < data.positions.len()))
+            .flat_map(|&[a, b, c]| [(a, b), (b, c), (c, a)])
+            .map(|(a, b)| (data.positions[a] - data.positions[b]).norm())
+            .fold(0.0, f64::max);
+
+        // 包围盒上离相机最近的点
+        let nearest = Point3::new(
+            self.lookfrom.x.clamp(min.x, max.x),
+            self.lookfrom.y.clamp(min.y, max.y),
+            self.lookfrom.z.clamp(min.z, max.z),
+        );
+        let distance = (nearest - self.lookfrom).norm().max(1e-6);
+        let pixels_per_unit =
+            self.image_height as f64 / (2.0 * distance * (self.vfov.to_radians() / 2.0).tan());
+        let ratio = longest * pixels_per_unit / self.max_edge_pixels.max(1e-6);
+        if ratio <= 1.0 {
+            return 0;
+        }
+        (ratio.log2().ceil() as u32).min(self.max_levels)
+    }
+}
+
+fn bounds(points: &[Point3]) -> Option<(Point3, Point3)> {
+    let first = *points.first()?;
+    Some(
+        points
+            .iter()
+            .fold((first, first), |(min, max), p| (min.inf(p), max.sup(p))),
+    )
+}
+
+/// 对网格做 levels 级 Loop 细分，跳过越界索引；levels 为 0 时原样返回
+pub fn loop_subdivide(data: MeshData, levels: u32) -> MeshData {
+    if levels == 0 {
+        return data;
+    }
+    let vertex_count = data.positions.len();
+    let mut mesh = MeshData {
+        normals: Vec::new(),
+        uvs: if data.uvs.len() == vertex_count {
+            data.uvs
+        } else {
+            Vec::new()
+        },
+        indices: data
+            .indices
+            .into_iter()
+            .filter(|face| face.iter().all(|&i| i < vertex_count))
+            .collect(),
+        positions: data.positions,
+    };
+    for _ in 0..levels {
+        mesh = subdivide_once(&mesh);
+    }
+    mesh.normals = smooth_normals(&mesh);
+    mesh
+}
+
+/// 按位置焊接顶点，返回每个顶点的焊接编号和焊接后的顶点数
+fn weld(positions: &[Point3]) -> (Vec<usize>, usize) {
+    let mut ids: HashMap<[u64; 3], usize> = HashMap::with_capacity(positions.len());
+    let welded = positions
+        .iter()
+        .map(|p| {
+            // +0.0 使 -0.0 与 0.0 焊接在一起
+            let key = [p.x + 0.0, p.y + 0.0, p.z + 0.0].map(f64::to_bits);
+            let next = ids.len();
+            *ids.entry(key).or_insert(next)
+        })
+        .collect();
+    (welded, ids.len())
+}
+
+#[inline]
+fn edge_key(a: usize, b: usize) -> (usize, usize) {
+    if a < b { (a, b) } else { (b, a) }
+}
+
+fn subdivide_once(mesh: &MeshData) -> MeshData {
+    let (weld_id, welded_count) = weld(&mesh.positions);
+    let mut welded_positions = vec![Point3::origin(); welded_count];
+    for (i, &w) in weld_id.iter().enumerate() {
+        welded_positions[w] = mesh.positions[i];
+    }
+
+    // 焊接后每条边的对顶点（一个为边界，两个为内部边）
+    let mut opposite: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for face in &mesh.indices {
+        let w = face.map(|i| weld_id[i]);
+        for k in 0..3 {
+            let (a, b, c) = (w[k], w[(k + 1) % 3], w[(k + 2) % 3]);
+            if a != b {
+                opposite.entry(edge_key(a, b)).or_default().push(c);
+            }
+        }
+    }
+
+    // 每个焊接顶点的邻居和边界邻居
+    let mut neighbors: Vec<Vec<usize>> = vec![Vec::new(); welded_count];
+    let mut boundary: Vec<Vec<usize>> = vec![Vec::new(); welded_count];
+    for (&(a, b), faces) in &opposite {
+        neighbors[a].push(b);
+        neighbors[b].push(a);
+        if faces.len() != 2 {
+            boundary[a].push(b);
+            boundary[b].push(a);
+        }
+    }
+
+    // 原顶点的新位置
+    let vertex_points: Vec<Point3> = (0..welded_count)
+        .map(|v| {
+            let p = welded_positions[v];
+            if !boundary[v].is_empty() {
+                // 边界顶点只受两个边界邻居影响，非流形的角点保持不动
+                return match boundary[v][..] {
+                    [b1, b2] => Point3::from(
+                        0.75 * p.coords
+                            + 0.125 * (welded_positions[b1].coords + welded_positions[b2].coords),
+                    ),
+                    _ => p,
+                };
+            }
+            let n = neighbors[v].len();
+            if n == 0 {
+                return p;
+            }
+            let beta = if n == 3 {
+                3.0 / 16.0
+            } else {
+                3.0 / (8.0 * n as f64)
+            };
+            let sum: Vec3 = neighbors[v]
+                .iter()
+                .map(|&u| welded_positions[u].coords)
+                .sum();
+            Point3::from((1.0 - n as f64 * beta) * p.coords + beta * sum)
+        })
+        .collect();
+
+    // 边点
+    let edge_point = |a: usize, b: usize| -> Point3 {
+        let (pa, pb) = (welded_positions[a].coords, welded_positions[b].coords);
+        match opposite.get(&edge_key(a, b)).map(Vec::as_slice) {
+            Some(&[c, d]) => Point3::from(
+                0.375 * (pa + pb)
+                    + 0.125 * (welded_positions[c].coords + welded_positions[d].coords),
+            ),
+            _ => Point3::from(0.5 * (pa + pb)),
+        }
+    };
+
+    // 原顶点保留编号，新边点按原顶点编号的边去重，纹理坐标在原顶点之间插值
+    let has_uvs = mesh.uvs.len() == mesh.positions.len();
+    let mut out = MeshData {
+        positions: weld_id.iter().map(|&w| vertex_points[w]).collect(),
+        normals: Vec::new(),
+        uvs: if has_uvs {
+            mesh.uvs.clone()
+        } else {
+            Vec::new()
+        },
+        indices: Vec::with_capacity(mesh.indices.len() * 4),
+    };
+    let mut edge_vertices: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut midpoint = |out: &mut MeshData, a: usize, b: usize| -> usize {
+        *edge_vertices.entry(edge_key(a, b)).or_insert_with(|| {
+            out.positions.push(edge_point(weld_id[a], weld_id[b]));
+            if has_uvs {
+                let (ua, ub) = (mesh.uvs[a], mesh.uvs[b]);
+                out.uvs.push((0.5 * (ua.0 + ub.0), 0.5 * (ua.1 + ub.1)));
+            }
+            out.positions.len() - 1
+        })
+    };
+    for &[a, b, c] in &mesh.indices {
+        let ab = midpoint(&mut out, a, b);
+        let bc = midpoint(&mut out, b, c);
+        let ca = midpoint(&mut out, c, a);
+        out.indices
+            .extend([[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]]);
+    }
+    out
+}
+
+/// 焊接后按面积加权平均的顶点法线
+fn smooth_normals(mesh: &MeshData) -> Vec<Vec3> {
+    let (weld_id, welded_count) = weld(&mesh.positions);
+    let mut normals = vec![Vec3::zeros(); welded_count];
+    for &[a, b, c] in &mesh.indices {
+        let (pa, pb, pc) = (mesh.positions[a], mesh.positions[b], mesh.positions[c]);
+        // 叉积的长度为面积的两倍，直接累加即为面积加权
+        let n = (pb - pa).cross(&(pc - pa));
+        for i in [a, b, c] {
+            normals[weld_id[i]] += n;
+        }
+    }
+    weld_id
+        .iter()
+        .map(|&w| {
+            let n = normals[w];
+            if n.norm_squared() > 0.0 {
+                n.normalize()
+            } else {
+                n
+            }
+        })
+        .collect()
+}
@@ -1,2 +1,4 @@
+pub mod motion;
 pub mod rotate_y;
+pub mod scale;
 pub mod translate;
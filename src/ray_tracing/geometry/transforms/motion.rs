@@ -0,0 +1,258 @@
+//! 快门期间变化的刚体变换：在快门打开和关闭时的两个变换之间按光线时间插值，
+//! 旋转的物体（如转动的盒子）也能得到正确的运动模糊，而不只是平移的球
+//!
+//! 变换由绕Y轴的旋转角和平移组成（见 [`Transform`]），两者分别线性插值；
+//! 旋转角不取模，从 0° 到 720° 表示快门期间转两圈。物体本身的形状不随时间变化（不支持变形模糊）。
+
+use super::super::hittable::{HitRecord, Hittable, UvTriangle};
+use super::super::scene_graph::Transform;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::random::degrees_to_radians;
+use std::sync::Arc;
+
+/// 计算包围盒时快门区间的分段数
+const BOUNDS_STEPS: usize = 32;
+
+/// 某一时刻的变换，预先计算旋转的正弦和余弦
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    sin_theta: f64,
+    cos_theta: f64,
+    offset: Vec3,
+}
+
+impl Frame {
+    fn new(transform: &Transform) -> Self {
+        let (sin_theta, cos_theta) = degrees_to_radians(transform.rotate_y).sin_cos();
+        Self {
+            sin_theta,
+            cos_theta,
+            offset: transform.translate,
+        }
+    }
+
+    /// 向量从局部坐标系旋转到世界坐标系（与 RotateY 一致）
+    #[inline]
+    fn local_to_world_vec(&self, v: &Vec3) -> Vec3 {
+        Vec3::new(
+            self.cos_theta * v.x + self.sin_theta * v.z,
+            v.y,
+            -self.sin_theta * v.x + self.cos_theta * v.z,
+        )
+    }
+
+    #[inline]
+    fn world_to_local_vec(&self, v: &Vec3) -> Vec3 {
+        Vec3::new(
+            self.cos_theta * v.x - self.sin_theta * v.z,
+            v.y,
+            self.sin_theta * v.x + self.cos_theta * v.z,
+        )
+    }
+
+    #[inline]
+    fn local_to_world(&self, p: &Point3) -> Point3 {
+        Point3::from(self.local_to_world_vec(&p.coords) + self.offset)
+    }
+
+    #[inline]
+    fn world_to_local(&self, p: &Point3) -> Point3 {
+        Point3::from(self.world_to_local_vec(&(p.coords - self.offset)))
+    }
+}
+
+/// 快门打开和关闭时分别使用 open、close 变换的物体
+pub struct MotionTransform {
+    object: Arc<dyn Hittable>,
+    open: Transform,
+    close: Transform,
+    bbox: Aabb,
+}
+
+impl MotionTransform {
+    /// 包装物体，快门时间 0 和 1 时分别处于 open 和 close 变换
+    pub fn new(object: Arc<dyn Hittable>, open: Transform, close: Transform) -> Self {
+        let bbox = match object.bounding_box() {
+            Some(local) => swept_bounds(&local, &open, &close),
+            None => Aabb::empty(),
+        };
+        Self {
+            object,
+            open,
+            close,
+            bbox,
+        }
+    }
+
+    /// 被变换的物体
+    #[inline]
+    pub fn object(&self) -> &Arc<dyn Hittable> {
+        &self.object
+    }
+
+    /// 快门打开和关闭时的变换
+    #[inline]
+    pub fn transforms(&self) -> (Transform, Transform) {
+        (self.open, self.close)
+    }
+
+    /// 时间 time 处的变换
+    #[inline]
+    pub fn at(&self, time: f64) -> Transform {
+        self.open.lerp(&self.close, time)
+    }
+
+    #[inline]
+    fn frame(&self, time: f64) -> Frame {
+        Frame::new(&self.at(time))
+    }
+}
+
+/// 局部包围盒在快门区间内扫过的世界空间包围盒
+///
+/// 把快门区间分成若干段，每段取两端旋转后包围盒的并集，再加上角点在这一段内沿圆弧偏离弦的最大距离
+/// （弓高 r·(1 - cos(Δθ/2))），最后按这一段的平移范围扩展，因此对任意时刻都是保守的。
+fn swept_bounds(local: &Aabb, open: &Transform, close: &Transform) -> Aabb {
+    let corners: Vec<Point3> = (0..8)
+        .map(|i| {
+            Point3::new(
+                if i & 1 == 0 { local.x.min } else { local.x.max },
+                if i & 2 == 0 { local.y.min } else { local.y.max },
+                if i & 4 == 0 { local.z.min } else { local.z.max },
+            )
+        })
+        .collect();
+    let radius = corners
+        .iter()
+        .map(|c| (c.x * c.x + c.z * c.z).sqrt())
+        .fold(0.0, f64::max);
+    let rotated = |angle: f64| {
+        let frame = Frame::new(&Transform::rotation_y(angle));
+        let points: Vec<Point3> = corners
+            .iter()
+            .map(|c| Point3::from(frame.local_to_world_vec(&c.coords)))
+            .collect();
+        points
+            .iter()
+            .skip(1)
+            .fold(Aabb::new_point(points[0], points[0]), |b, p| {
+                b.merge(&Aabb::new_point(*p, *p))
+            })
+    };
+
+    let mut bbox = Aabb::empty();
+    for step in 0..BOUNDS_STEPS {
+        let a = open.lerp(close, step as f64 / BOUNDS_STEPS as f64);
+        let b = open.lerp(close, (step + 1) as f64 / BOUNDS_STEPS as f64);
+        let half_angle = degrees_to_radians((b.rotate_y - a.rotate_y).abs()) * 0.5;
+        let sagitta = radius * (1.0 - half_angle.min(std::f64::consts::FRAC_PI_2).cos());
+        let segment = rotated(a.rotate_y).merge(&rotated(b.rotate_y));
+        let segment = Aabb::new_point(
+            Point3::new(
+                segment.x.min - sagitta + a.translate.x.min(b.translate.x),
+                segment.y.min + a.translate.y.min(b.translate.y),
+                segment.z.min - sagitta + a.translate.z.min(b.translate.z),
+            ),
+            Point3::new(
+                segment.x.max + sagitta + a.translate.x.max(b.translate.x),
+                segment.y.max + a.translate.y.max(b.translate.y),
+                segment.z.max + sagitta + a.translate.z.max(b.translate.z),
+            ),
+        );
+        bbox = bbox.merge(&segment);
+    }
+    bbox
+}
+
+impl Hittable for MotionTransform {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let frame = self.frame(r.time);
+        let local_r = Ray::new(
+            frame.world_to_local(&r.orig),
+            frame.world_to_local_vec(&r.dir),
+            r.time,
+        )
+        .with_kind(r.kind);
+        if !self.object.hit(&local_r, ray_t, rec) {
+            return false;
+        }
+
+        // 交点处的表面点在快门两端的位置：物体自身也在运动时使用其在两端的局部位置
+        let [open, close] = rec.motion.unwrap_or([rec.p, rec.p]);
+        rec.motion = Some([
+            self.frame(0.0).local_to_world(&open),
+            self.frame(1.0).local_to_world(&close),
+        ]);
+        rec.p = frame.local_to_world(&rec.p);
+        rec.normal = frame.local_to_world_vec(&rec.normal);
+        rec.dpdu = frame.local_to_world_vec(&rec.dpdu);
+        rec.dpdv = frame.local_to_world_vec(&rec.dpdv);
+        true
+    }
+
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        let frame = self.frame(r.time);
+        self.object.hit_any(
+            &Ray::new(
+                frame.world_to_local(&r.orig),
+                frame.world_to_local_vec(&r.dir),
+                r.time,
+            )
+            .with_kind(r.kind),
+            ray_t,
+        )
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.bbox)
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3, time: f64) -> f64 {
+        let frame = self.frame(time);
+        self.object.pdf_value(
+            &frame.world_to_local(origin),
+            &frame.world_to_local_vec(direction),
+            time,
+        )
+    }
+
+    fn random(&self, origin: &Point3, time: f64) -> Vec3 {
+        let frame = self.frame(time);
+        frame.local_to_world_vec(&self.object.random(&frame.world_to_local(origin), time))
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        self.object.area()
+    }
+
+    #[inline]
+    fn power(&self) -> Color {
+        self.object.power()
+    }
+
+    /// 快门打开时刻的三角形
+    fn uv_triangles(&self) -> Vec<UvTriangle> {
+        let frame = Frame::new(&self.open);
+        self.object
+            .uv_triangles()
+            .iter()
+            .map(|t| t.map(|p| frame.local_to_world(p), |n| frame.local_to_world_vec(n)))
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for MotionTransform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MotionTransform")
+            .field("object", &"<Hittable>")
+            .field("open", &self.open)
+            .field("close", &self.close)
+            .field("bbox", &self.bbox)
+            .finish()
+    }
+}
@@ -1,4 +1,4 @@
-use super::super::hittable::{HitRecord, Hittable};
+use super::super::hittable::{HitRecord, Hittable, UvTriangle};
 use crate::ray_tracing::math::aabb::Aabb;
 use crate::ray_tracing::math::interval::Interval;
 use crate::ray_tracing::math::ray::Ray;
@@ -66,6 +66,12 @@ impl RotateY {
         }
     }
 
+    /// 被旋转的物体
+    #[inline]
+    pub fn object(&self) -> &Arc<dyn Hittable> {
+        &self.object
+    }
+
     /// 将点从世界坐标系转换到对象的局部坐标系
     #[inline]
     fn world_to_local(&self, world_point: &Point3) -> Point3 {
@@ -78,7 +84,7 @@ impl RotateY {
 
     /// 将点从对象的局部坐标系转换到世界坐标系
     #[inline]
-    fn local_to_world(&self, local_point: &Point3) -> Point3 {
+    pub fn local_to_world(&self, local_point: &Point3) -> Point3 {
         Point3::new(
             self.cos_theta * local_point.x + self.sin_theta * local_point.z,
             local_point.y,
@@ -98,7 +104,7 @@ impl RotateY {
 
     /// 将向量从对象的局部坐标系转换到世界坐标系
     #[inline]
-    fn local_to_world_vec(&self, local_vec: &Vec3) -> Vec3 {
+    pub fn local_to_world_vec(&self, local_vec: &Vec3) -> Vec3 {
         Vec3::new(
             self.cos_theta * local_vec.x + self.sin_theta * local_vec.z,
             local_vec.y,
@@ -112,7 +118,7 @@ impl Hittable for RotateY {
         // 将光线从世界坐标系转换到对象的局部坐标系
         let origin = self.world_to_local(&r.orig);
         let direction = self.world_to_local_vec(&r.dir);
-        let rotated_r = Ray::new(origin, direction, r.time);
+        let rotated_r = Ray::new(origin, direction, r.time).with_kind(r.kind);
 
         // 在对象的局部坐标系中检测相交
         if !self.object.hit(&rotated_r, ray_t, rec) {
@@ -122,31 +128,62 @@ impl Hittable for RotateY {
         // 将交点和法线从对象的局部坐标系转换回世界坐标系
         rec.p = self.local_to_world(&rec.p);
         rec.normal = self.local_to_world_vec(&rec.normal);
+        rec.dpdu = self.local_to_world_vec(&rec.dpdu);
+        rec.dpdv = self.local_to_world_vec(&rec.dpdv);
+        if let Some(motion) = &mut rec.motion {
+            motion.iter_mut().for_each(|p| *p = self.local_to_world(p));
+        }
 
         true
     }
 
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        let origin = self.world_to_local(&r.orig);
+        let direction = self.world_to_local_vec(&r.dir);
+        self.object.hit_any(
+            &Ray::new(origin, direction, r.time).with_kind(r.kind),
+            ray_t,
+        )
+    }
+
     #[inline]
     fn bounding_box(&self) -> Option<Aabb> {
         Some(self.bbox)
     }
 
-    #[inline]
-    fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3, time: f64) -> f64 {
         // 将原点和方向转换到对象的局部坐标系
         let local_origin = self.world_to_local(origin);
         let local_direction = self.world_to_local_vec(direction);
-        self.object.pdf_value(&local_origin, &local_direction)
+        self.object.pdf_value(&local_origin, &local_direction, time)
     }
 
     #[inline]
-    fn random(&self, origin: &Point3) -> Vec3 {
+    fn random(&self, origin: &Point3, time: f64) -> Vec3 {
         // 将原点转换到对象的局部坐标系
         let local_origin = self.world_to_local(origin);
-        let local_direction = self.object.random(&local_origin);
+        let local_direction = self.object.random(&local_origin, time);
         // 将生成的方向转换回世界坐标系
         self.local_to_world_vec(&local_direction)
     }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        self.object.area()
+    }
+
+    #[inline]
+    fn power(&self) -> Color {
+        self.object.power()
+    }
+
+    fn uv_triangles(&self) -> Vec<UvTriangle> {
+        self.object
+            .uv_triangles()
+            .iter()
+            .map(|t| t.map(|p| self.local_to_world(p), |n| self.local_to_world_vec(n)))
+            .collect()
+    }
 }
 
 impl std::fmt::Debug for RotateY {
@@ -0,0 +1,118 @@
+use super::super::hittable::{HitRecord, Hittable, UvTriangle};
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use std::sync::Arc;
+
+/// 以原点为中心的均匀缩放变换
+///
+/// 局部光线的方向同样除以缩放系数，因此局部与世界坐标下的光线参数 t 相同；
+/// 均匀缩放不改变法线方向和立体角，光源采样的密度无需修正。
+pub struct Scale {
+    object: Arc<dyn Hittable>,
+    factor: f64,
+    bbox: Aabb,
+}
+
+impl Scale {
+    /// 创建缩放变换，factor 必须为正
+    pub fn new(object: Arc<dyn Hittable>, factor: f64) -> Self {
+        let factor = factor.max(1e-9);
+        let bbox = if let Some(b) = object.bounding_box() {
+            Aabb::new_point(
+                Point3::new(b.x.min, b.y.min, b.z.min) * factor,
+                Point3::new(b.x.max, b.y.max, b.z.max) * factor,
+            )
+        } else {
+            Aabb::empty()
+        };
+
+        Self {
+            object,
+            factor,
+            bbox,
+        }
+    }
+
+    /// 被缩放的物体
+    #[inline]
+    pub fn object(&self) -> &Arc<dyn Hittable> {
+        &self.object
+    }
+
+    /// 缩放系数
+    #[inline]
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+
+    #[inline]
+    fn local_ray(&self, r: &Ray) -> Ray {
+        let inv = 1.0 / self.factor;
+        Ray::new(r.orig * inv, r.dir * inv, r.time).with_kind(r.kind)
+    }
+}
+
+impl Hittable for Scale {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        if !self.object.hit(&self.local_ray(r), ray_t, rec) {
+            return false;
+        }
+
+        rec.p *= self.factor;
+        rec.dpdu *= self.factor;
+        rec.dpdv *= self.factor;
+        if let Some(motion) = &mut rec.motion {
+            motion.iter_mut().for_each(|p| *p *= self.factor);
+        }
+        true
+    }
+
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        self.object.hit_any(&self.local_ray(r), ray_t)
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.bbox)
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3, time: f64) -> f64 {
+        self.object
+            .pdf_value(&(origin / self.factor), direction, time)
+    }
+
+    #[inline]
+    fn random(&self, origin: &Point3, time: f64) -> Vec3 {
+        self.object.random(&(origin / self.factor), time)
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        self.object.area() * self.factor * self.factor
+    }
+
+    #[inline]
+    fn power(&self) -> Color {
+        self.object.power() * (self.factor * self.factor)
+    }
+
+    fn uv_triangles(&self) -> Vec<UvTriangle> {
+        self.object
+            .uv_triangles()
+            .iter()
+            .map(|t| t.map(|p| p * self.factor, |n| *n))
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for Scale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scale")
+            .field("object", &"<Hittable>")
+            .field("factor", &self.factor)
+            .field("bbox", &self.bbox)
+            .finish()
+    }
+}
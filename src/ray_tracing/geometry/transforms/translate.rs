@@ -1,4 +1,4 @@
-use super::super::hittable::{HitRecord, Hittable};
+use super::super::hittable::{HitRecord, Hittable, UvTriangle};
 use crate::ray_tracing::math::aabb::Aabb;
 use crate::ray_tracing::math::interval::Interval;
 use crate::ray_tracing::math::ray::Ray;
@@ -29,12 +29,24 @@ impl Translate {
             bbox,
         }
     }
+
+    /// 被平移的物体
+    #[inline]
+    pub fn object(&self) -> &Arc<dyn Hittable> {
+        &self.object
+    }
+
+    /// 平移量
+    #[inline]
+    pub fn offset(&self) -> Vec3 {
+        self.offset
+    }
 }
 
 impl Hittable for Translate {
     fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
         // 将光线向相反方向偏移到对象的局部坐标系
-        let offset_r = Ray::new(r.orig - self.offset, r.dir, r.time);
+        let offset_r = Ray::new(r.orig - self.offset, r.dir, r.time).with_kind(r.kind);
 
         // 检查偏移后的光线是否与原始物体相交
         if !self.object.hit(&offset_r, ray_t, rec) {
@@ -43,26 +55,51 @@ impl Hittable for Translate {
 
         // 将交点位置转换回世界坐标系
         rec.p += self.offset;
+        if let Some(motion) = &mut rec.motion {
+            motion.iter_mut().for_each(|p| *p += self.offset);
+        }
         true
     }
 
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        let offset_r = Ray::new(r.orig - self.offset, r.dir, r.time).with_kind(r.kind);
+        self.object.hit_any(&offset_r, ray_t)
+    }
+
     #[inline]
     fn bounding_box(&self) -> Option<Aabb> {
         Some(self.bbox)
     }
 
-    #[inline]
-    fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3, time: f64) -> f64 {
         // 将原点转换到对象的局部坐标系
         let local_origin = *origin - self.offset;
-        self.object.pdf_value(&local_origin, direction)
+        self.object.pdf_value(&local_origin, direction, time)
     }
 
     #[inline]
-    fn random(&self, origin: &Point3) -> Vec3 {
+    fn random(&self, origin: &Point3, time: f64) -> Vec3 {
         // 将原点转换到对象的局部坐标系
         let local_origin = *origin - self.offset;
-        self.object.random(&local_origin)
+        self.object.random(&local_origin, time)
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        self.object.area()
+    }
+
+    #[inline]
+    fn power(&self) -> Color {
+        self.object.power()
+    }
+
+    fn uv_triangles(&self) -> Vec<UvTriangle> {
+        self.object
+            .uv_triangles()
+            .iter()
+            .map(|t| t.map(|p| p + self.offset, |n| *n))
+            .collect()
     }
 }
 
@@ -0,0 +1,210 @@
+use super::hittable::{HitRecord, Hittable, UvTriangle, diffuse_emitter_power, power_grid};
+use super::planar::{Planar, planar_pdf_value, planar_random};
+use crate::ray_tracing::materials::material::Material;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use std::sync::Arc;
+
+/// 三角形几何体，可选顶点法线（平滑着色）和顶点纹理坐标
+pub struct Triangle {
+    p0: Point3,                 // 第一个顶点
+    e1: Vec3,                   // 边 p1 - p0
+    e2: Vec3,                   // 边 p2 - p0
+    normals: Option<[Vec3; 3]>, // 顶点法线
+    uvs: [(f64, f64); 3],       // 顶点纹理坐标
+    mat: Arc<dyn Material>,     // 材质
+    bbox: Aabb,                 // 包围盒
+    normal: Vec3,               // 几何法线（按顶点逆时针顺序）
+    area: f64,                  // 三角形面积
+}
+
+impl Triangle {
+    /// 创建三角形，纹理坐标默认为 (0,0)、(1,0)、(0,1)
+    #[inline]
+    pub fn new(p0: Point3, p1: Point3, p2: Point3, mat: Arc<dyn Material>) -> Self {
+        let e1 = p1 - p0;
+        let e2 = p2 - p0;
+        let n = e1.cross(&e2);
+        let area = 0.5 * n.norm();
+        let normal = if area > 0.0 { n.normalize() } else { n };
+        let bbox = Aabb::new_point(p0, p1).merge(&Aabb::new_point(p2, p2));
+
+        Self {
+            p0,
+            e1,
+            e2,
+            normals: None,
+            uvs: [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)],
+            mat,
+            bbox,
+            normal,
+            area,
+        }
+    }
+
+    /// 设置顶点法线，命中时按重心坐标插值作为着色法线
+    #[inline]
+    pub fn with_normals(mut self, normals: [Vec3; 3]) -> Self {
+        self.normals = Some(normals.map(|n| n.normalize()));
+        self
+    }
+
+    /// 设置顶点纹理坐标
+    #[inline]
+    pub fn with_uvs(mut self, uvs: [(f64, f64); 3]) -> Self {
+        self.uvs = uvs;
+        self
+    }
+
+    /// 面积为0的退化三角形
+    #[inline]
+    pub fn is_degenerate(&self) -> bool {
+        self.area <= 1e-12
+    }
+
+    /// 按给定材质估计发光功率（网格统一更换材质时使用），`DiffuseLight` 双面发光，按两面计算
+    pub fn power_with(&self, mat: &dyn Material) -> Color {
+        let points = power_grid()
+            .filter(|(a, b)| a + b <= 1.0)
+            .map(|(a, b)| (a, b, self.p0 + a * self.e1 + b * self.e2));
+        diffuse_emitter_power(mat, self.area, 2.0, points)
+    }
+
+    /// 由纹理坐标求位置对 (u, v) 的偏导数，纹理坐标退化时为零
+    fn uv_derivatives(&self) -> (Vec3, Vec3) {
+        let du1 = self.uvs[1].0 - self.uvs[0].0;
+        let dv1 = self.uvs[1].1 - self.uvs[0].1;
+        let du2 = self.uvs[2].0 - self.uvs[0].0;
+        let dv2 = self.uvs[2].1 - self.uvs[0].1;
+        let det = du1 * dv2 - dv1 * du2;
+        if det.abs() < 1e-12 {
+            return (Vec3::zeros(), Vec3::zeros());
+        }
+        let inv = 1.0 / det;
+        (
+            (dv2 * self.e1 - dv1 * self.e2) * inv,
+            (du1 * self.e2 - du2 * self.e1) * inv,
+        )
+    }
+}
+
+impl Hittable for Triangle {
+    /// Möller–Trumbore 求交
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let pvec = r.dir.cross(&self.e2);
+        let det = self.e1.dot(&pvec);
+        if det.abs() < 1e-12 {
+            return false;
+        }
+        let inv_det = 1.0 / det;
+
+        let tvec = r.orig - self.p0;
+        let b1 = tvec.dot(&pvec) * inv_det;
+        if !(0.0..=1.0).contains(&b1) {
+            return false;
+        }
+        let qvec = tvec.cross(&self.e1);
+        let b2 = r.dir.dot(&qvec) * inv_det;
+        if b2 < 0.0 || b1 + b2 > 1.0 {
+            return false;
+        }
+        let t = self.e2.dot(&qvec) * inv_det;
+        if !ray_t.contains(t) {
+            return false;
+        }
+
+        let b0 = 1.0 - b1 - b2;
+        rec.t = t;
+        rec.p = r.at(t);
+        rec.u = b0 * self.uvs[0].0 + b1 * self.uvs[1].0 + b2 * self.uvs[2].0;
+        rec.v = b0 * self.uvs[0].1 + b1 * self.uvs[1].1 + b2 * self.uvs[2].1;
+        (rec.dpdu, rec.dpdv) = self.uv_derivatives();
+        rec.mat = self.mat.clone();
+        rec.set_face_normal(r, &self.normal);
+
+        // 平滑着色：插值法线翻到几何法线所在的一侧，避免着色法线指向表面内部
+        if let Some([n0, n1, n2]) = self.normals {
+            let shading = b0 * n0 + b1 * n1 + b2 * n2;
+            if shading.norm_squared() > 1e-12 {
+                let shading = shading.normalize();
+                rec.normal = if shading.dot(&rec.normal) < 0.0 {
+                    -shading
+                } else {
+                    shading
+                };
+            }
+        }
+
+        true
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.bbox)
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3, time: f64) -> f64 {
+        planar_pdf_value(self, origin, direction, time)
+    }
+
+    fn random(&self, origin: &Point3, _time: f64) -> Vec3 {
+        planar_random(self, origin)
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        self.area
+    }
+
+    #[inline]
+    fn power(&self) -> Color {
+        self.power_with(self.mat.as_ref())
+    }
+
+    /// 烘焙几何法线（按顶点逆时针顺序）所指的一侧，有顶点法线时使用顶点法线
+    fn uv_triangles(&self) -> Vec<UvTriangle> {
+        if self.is_degenerate() {
+            return Vec::new();
+        }
+        vec![UvTriangle {
+            uv: self.uvs,
+            p: [self.p0, self.p0 + self.e1, self.p0 + self.e2],
+            n: self.normals.unwrap_or([self.normal; 3]),
+        }]
+    }
+}
+
+impl Planar for Triangle {
+    #[inline]
+    fn plane_normal(&self) -> Vec3 {
+        self.normal
+    }
+
+    fn vertices(&self) -> Vec<Point3> {
+        vec![self.p0, self.p0 + self.e1, self.p0 + self.e2]
+    }
+
+    /// 平方根映射把单位正方形均匀地映射到三角形上
+    #[inline]
+    fn sample_point(&self, r1: f64, r2: f64) -> Point3 {
+        let s = r1.sqrt();
+        self.p0 + s * (1.0 - r2) * self.e1 + s * r2 * self.e2
+    }
+}
+
+impl std::fmt::Debug for Triangle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Triangle")
+            .field("p0", &self.p0)
+            .field("e1", &self.e1)
+            .field("e2", &self.e2)
+            .field("normals", &self.normals)
+            .field("uvs", &self.uvs)
+            .field("mat", &"<Material>")
+            .field("bbox", &self.bbox)
+            .field("area", &self.area)
+            .finish()
+    }
+}
@@ -0,0 +1,160 @@
+//! 按光线类型控制物体的可见性：对相机不可见但投射阴影、不出现在反射中等常见的布光需求
+//!
+//! 包装后的物体根据光线的 [`RayKind`] 决定是否参与求交。路径追踪中阴影来自被物体挡住的散射光线，
+//! 因此“投射阴影”同时控制物体对散射光线（漫反射反弹）和遮挡测试光线的可见性，
+//! 关闭后物体也不再向周围反射间接光。发光体应保持投射阴影开启，否则散射光线会穿过它而照不亮场景。
+
+use super::hittable::{HitRecord, Hittable, UvTriangle};
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::{Ray, RayKind};
+use crate::ray_tracing::math::vec3::{Color, Point3, Vec3};
+use std::sync::Arc;
+
+/// 各类光线的可见性开关，默认全部可见
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RayVisibility {
+    /// 对相机主光线可见
+    pub camera: bool,
+    /// 出现在镜面反射和折射中
+    pub reflections: bool,
+    /// 投射阴影（对散射光线和遮挡测试光线可见）
+    pub shadows: bool,
+}
+
+impl Default for RayVisibility {
+    fn default() -> Self {
+        Self {
+            camera: true,
+            reflections: true,
+            shadows: true,
+        }
+    }
+}
+
+impl RayVisibility {
+    /// 对相机不可见，但投射阴影并出现在反射中
+    pub const SHADOW_ONLY: Self = Self {
+        camera: false,
+        reflections: true,
+        shadows: true,
+    };
+
+    /// 只对相机可见，不投射阴影、不出现在反射中
+    pub const CAMERA_ONLY: Self = Self {
+        camera: true,
+        reflections: false,
+        shadows: false,
+    };
+
+    /// 设置对相机的可见性
+    #[inline]
+    pub fn with_camera(mut self, visible: bool) -> Self {
+        self.camera = visible;
+        self
+    }
+
+    /// 设置在反射和折射中的可见性
+    #[inline]
+    pub fn with_reflections(mut self, visible: bool) -> Self {
+        self.reflections = visible;
+        self
+    }
+
+    /// 设置是否投射阴影
+    #[inline]
+    pub fn with_shadows(mut self, visible: bool) -> Self {
+        self.shadows = visible;
+        self
+    }
+
+    /// 对某类光线是否可见
+    #[inline]
+    pub fn visible_to(&self, kind: RayKind) -> bool {
+        match kind {
+            RayKind::Camera => self.camera,
+            RayKind::Specular | RayKind::Caustic => self.reflections,
+            RayKind::Diffuse | RayKind::Shadow => self.shadows,
+        }
+    }
+}
+
+/// 按光线类型决定是否参与求交的包装物体
+pub struct Visibility {
+    object: Arc<dyn Hittable>,
+    flags: RayVisibility,
+}
+
+impl Visibility {
+    /// 包装物体
+    #[inline]
+    pub fn new(object: Arc<dyn Hittable>, flags: RayVisibility) -> Self {
+        Self { object, flags }
+    }
+
+    /// 对相机不可见、但投射阴影并出现在反射中
+    #[inline]
+    pub fn shadow_only(object: Arc<dyn Hittable>) -> Self {
+        Self::new(object, RayVisibility::SHADOW_ONLY)
+    }
+
+    /// 被包装的物体
+    #[inline]
+    pub fn object(&self) -> &Arc<dyn Hittable> {
+        &self.object
+    }
+
+    /// 可见性开关
+    #[inline]
+    pub fn flags(&self) -> RayVisibility {
+        self.flags
+    }
+}
+
+impl Hittable for Visibility {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        self.flags.visible_to(r.kind) && self.object.hit(r, ray_t, rec)
+    }
+
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        self.flags.visible_to(r.kind) && self.object.hit_any(r, ray_t)
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.object.bounding_box()
+    }
+
+    #[inline]
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3, time: f64) -> f64 {
+        self.object.pdf_value(origin, direction, time)
+    }
+
+    #[inline]
+    fn random(&self, origin: &Point3, time: f64) -> Vec3 {
+        self.object.random(origin, time)
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        self.object.area()
+    }
+
+    #[inline]
+    fn power(&self) -> Color {
+        self.object.power()
+    }
+
+    fn uv_triangles(&self) -> Vec<UvTriangle> {
+        self.object.uv_triangles()
+    }
+}
+
+impl std::fmt::Debug for Visibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Visibility")
+            .field("object", &"<Hittable>")
+            .field("flags", &self.flags)
+            .finish()
+    }
+}
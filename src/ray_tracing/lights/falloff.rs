@@ -0,0 +1,73 @@
+//! 点光源和聚光灯的距离衰减控制
+//!
+//! 小球光源的照度天然按平方反比衰减。布光时有时希望光照传得更远，这里按着色点到光源的距离
+//! 给发光辐射度乘上修正系数，把衰减改为一次反比或不衰减；在参考距离处与物理衰减一致。
+//! 修正后的光源不再符合物理，也不再满足能量守恒。
+
+use std::fmt;
+use std::str::FromStr;
+
+/// 距离衰减方式
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Falloff {
+    /// 平方反比（物理正确）
+    #[default]
+    Quadratic,
+    /// 一次反比：照度在参考距离处与平方反比相同
+    Linear { reference: f64 },
+    /// 不随距离衰减：照度处处等于参考距离处的值
+    Constant { reference: f64 },
+}
+
+impl Falloff {
+    /// 距离为 distance 时发光辐射度的修正系数
+    #[inline]
+    pub fn factor(&self, distance: f64) -> f64 {
+        match *self {
+            Self::Quadratic => 1.0,
+            Self::Linear { reference } => distance / reference.max(1e-9),
+            Self::Constant { reference } => {
+                let ratio = distance / reference.max(1e-9);
+                ratio * ratio
+            }
+        }
+    }
+}
+
+impl FromStr for Falloff {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, reference) = match s.split_once(':') {
+            Some((name, value)) => {
+                let reference: f64 = value
+                    .parse()
+                    .map_err(|_| format!("无效的参考距离 `{}`", value))?;
+                if reference <= 0.0 {
+                    return Err(format!("参考距离必须大于0: `{}`", value));
+                }
+                (name, reference)
+            }
+            None => (s, 1.0),
+        };
+        match name.to_ascii_lowercase().as_str() {
+            "quadratic" | "inverse-square" => Ok(Self::Quadratic),
+            "linear" => Ok(Self::Linear { reference }),
+            "constant" | "none" => Ok(Self::Constant { reference }),
+            _ => Err(format!(
+                "未知的衰减方式 `{}`（可选: quadratic, linear[:参考距离], constant[:参考距离]）",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Falloff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Quadratic => f.write_str("quadratic"),
+            Self::Linear { reference } => write!(f, "linear:{}", reference),
+            Self::Constant { reference } => write!(f, "constant:{}", reference),
+        }
+    }
+}
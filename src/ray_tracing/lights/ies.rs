@@ -0,0 +1,226 @@
+//! IES LM-63 光度数据文件（`.ies`）的读取和插值
+//!
+//! 只支持灯具常用的 C 型光度数据：垂直角 0° 指向灯具正下方（天底），180° 指向正上方；
+//! 水平角绕垂直轴旋转，0° 为灯具的长度方向。按最后一个水平角识别对称性：
+//! 0° 为旋转对称，90° 为四象限对称，180° 为左右对称，360° 为完整数据。
+//! `TILT=INCLUDE` 的倾斜数据会被读取并忽略，`TILT=<文件名>` 按无倾斜处理。
+
+use std::io;
+use std::path::Path;
+
+/// C 型光度数据
+const PHOTOMETRIC_TYPE_C: i64 = 1;
+
+/// IES 配光曲线
+#[derive(Debug, Clone, PartialEq)]
+pub struct IesProfile {
+    /// 垂直角（度），递增
+    vertical: Vec<f64>,
+    /// 水平角（度），递增
+    horizontal: Vec<f64>,
+    /// 发光强度（坎德拉），按 [水平角][垂直角] 排列，已乘以倍率和镇流器系数
+    candela: Vec<Vec<f64>>,
+    max_candela: f64,
+    lumens: f64,
+}
+
+fn invalid(source: &str, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", source, message),
+    )
+}
+
+/// 按顺序读取空白分隔的数值
+struct Numbers<'a, I: Iterator<Item = &'a str>> {
+    tokens: I,
+    source: &'a str,
+}
+
+impl<'a, I: Iterator<Item = &'a str>> Numbers<'a, I> {
+    fn next(&mut self, what: &str) -> io::Result<f64> {
+        let token = self
+            .tokens
+            .next()
+            .ok_or_else(|| invalid(self.source, &format!("数据不完整，缺少{}", what)))?;
+        token
+            .parse()
+            .map_err(|_| invalid(self.source, &format!("{}不是数值: `{}`", what, token)))
+    }
+
+    fn count(&mut self, what: &str) -> io::Result<usize> {
+        let value = self.next(what)?;
+        if value < 0.0 || value.fract() != 0.0 {
+            return Err(invalid(self.source, &format!("{}无效: {}", what, value)));
+        }
+        Ok(value as usize)
+    }
+
+    fn list(&mut self, n: usize, what: &str) -> io::Result<Vec<f64>> {
+        (0..n).map(|_| self.next(what)).collect()
+    }
+}
+
+impl IesProfile {
+    /// 读取 IES 文件
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)?;
+        // 常见的 IES 文件使用 Latin-1 编码的关键字行，数值部分总是 ASCII
+        let text: String = bytes.iter().map(|&b| b as char).collect();
+        Self::parse(&text, &path.display().to_string())
+    }
+
+    /// 解析 IES 文本，source 用于错误信息
+    pub fn parse(text: &str, source: &str) -> io::Result<Self> {
+        let mut lines = text.lines();
+        let tilt = lines
+            .by_ref()
+            .find_map(|line| line.trim().strip_prefix("TILT="))
+            .ok_or_else(|| invalid(source, "缺少 TILT= 行"))?
+            .trim()
+            .to_string();
+
+        let rest: Vec<&str> = lines.collect();
+        let mut numbers = Numbers {
+            tokens: rest
+                .iter()
+                .flat_map(|line| line.split(|c: char| c.is_whitespace() || c == ','))
+                .filter(|t| !t.is_empty()),
+            source,
+        };
+
+        if tilt.eq_ignore_ascii_case("INCLUDE") {
+            numbers.next("灯具倾斜几何类型")?;
+            let pairs = numbers.count("倾斜数据个数")?;
+            numbers.list(2 * pairs, "倾斜数据")?;
+        }
+
+        let _lamps = numbers.next("灯数")?;
+        let lumens = numbers.next("光通量")?;
+        let multiplier = numbers.next("强度倍率")?;
+        let vertical_count = numbers.count("垂直角个数")?;
+        let horizontal_count = numbers.count("水平角个数")?;
+        let photometric_type = numbers.next("光度类型")? as i64;
+        let _units = numbers.next("单位类型")?;
+        numbers.list(3, "灯具尺寸")?;
+        let ballast = numbers.next("镇流器系数")?;
+        let ballast_lamp = numbers.next("镇流器-灯系数")?;
+        let _watts = numbers.next("输入功率")?;
+
+        if photometric_type != PHOTOMETRIC_TYPE_C {
+            return Err(invalid(
+                source,
+                &format!("只支持 C 型光度数据（类型 {}）", photometric_type),
+            ));
+        }
+        if vertical_count == 0 || horizontal_count == 0 {
+            return Err(invalid(source, "角度个数不能为0"));
+        }
+
+        let vertical = numbers.list(vertical_count, "垂直角")?;
+        let horizontal = numbers.list(horizontal_count, "水平角")?;
+        let increasing = |angles: &[f64]| angles.windows(2).all(|w| w[0] < w[1]);
+        if !increasing(&vertical) || !increasing(&horizontal) {
+            return Err(invalid(source, "角度必须严格递增"));
+        }
+
+        let scale = multiplier
+            * ballast
+            * if ballast_lamp > 0.0 {
+                ballast_lamp
+            } else {
+                1.0
+            };
+        let candela: Vec<Vec<f64>> = (0..horizontal_count)
+            .map(|_| {
+                numbers
+                    .list(vertical_count, "发光强度")
+                    .map(|row| row.into_iter().map(|c| (c * scale).max(0.0)).collect())
+            })
+            .collect::<io::Result<_>>()?;
+        let max_candela = candela.iter().flatten().copied().fold(0.0, f64::max);
+
+        Ok(Self {
+            vertical,
+            horizontal,
+            candela,
+            max_candela,
+            lumens,
+        })
+    }
+
+    /// 最大发光强度（坎德拉）
+    #[inline]
+    pub fn max_candela(&self) -> f64 {
+        self.max_candela
+    }
+
+    /// 文件中记录的每盏灯的光通量（流明，绝对光度数据为 -1）
+    #[inline]
+    pub fn lumens(&self) -> f64 {
+        self.lumens
+    }
+
+    /// 垂直角 theta、水平角 phi（度）方向上的发光强度（坎德拉），数据范围外为0
+    pub fn candela(&self, theta: f64, phi: f64) -> f64 {
+        let (first, last) = (self.vertical[0], self.vertical[self.vertical.len() - 1]);
+        if theta < first || theta > last {
+            return 0.0;
+        }
+        let phi = self.fold_horizontal(phi);
+
+        if self.horizontal.len() == 1 {
+            return interpolate(&self.vertical, &self.candela[0], theta);
+        }
+        let (h0, h1, t) = locate(&self.horizontal, phi);
+        let c0 = interpolate(&self.vertical, &self.candela[h0], theta);
+        let c1 = interpolate(&self.vertical, &self.candela[h1], theta);
+        c0 + (c1 - c0) * t
+    }
+
+    /// 相对最大强度归一化的发光强度，[0,1]
+    #[inline]
+    pub fn normalized(&self, theta: f64, phi: f64) -> f64 {
+        if self.max_candela > 0.0 {
+            self.candela(theta, phi) / self.max_candela
+        } else {
+            0.0
+        }
+    }
+
+    /// 按数据的对称性把水平角映射到数据覆盖的范围
+    fn fold_horizontal(&self, phi: f64) -> f64 {
+        let phi = phi.rem_euclid(360.0);
+        let last = self.horizontal[self.horizontal.len() - 1];
+        if self.horizontal.len() == 1 || last <= 0.0 {
+            0.0
+        } else if last <= 90.0 {
+            let phi = if phi > 180.0 { 360.0 - phi } else { phi };
+            if phi > 90.0 { 180.0 - phi } else { phi }
+        } else if last <= 180.0 {
+            if phi > 180.0 { 360.0 - phi } else { phi }
+        } else {
+            phi
+        }
+    }
+}
+
+/// x 在递增序列 xs 中所在的区间 (i, i+1) 和区间内的插值参数，超出两端时取端点
+fn locate(xs: &[f64], x: f64) -> (usize, usize, f64) {
+    let last = xs.len() - 1;
+    if x <= xs[0] {
+        return (0, 0, 0.0);
+    }
+    if x >= xs[last] {
+        return (last, last, 0.0);
+    }
+    let i = xs.partition_point(|&v| v <= x) - 1;
+    (i, i + 1, (x - xs[i]) / (xs[i + 1] - xs[i]))
+}
+
+/// 分段线性插值
+fn interpolate(xs: &[f64], ys: &[f64], x: f64) -> f64 {
+    let (i, j, t) = locate(xs, x);
+    ys[i] + (ys[j] - ys[i]) * t
+}
@@ -0,0 +1,7 @@
+//! 光源相关的辅助功能：点光源和聚光灯的距离衰减控制、IES 光度数据
+//!
+//! 光源本身仍然是带发光材质的几何体（见 `materials::spot_light` 和 `materials::point_light`），
+//! 这里提供的类型由这些材质使用。
+
+pub mod falloff;
+pub mod ies;
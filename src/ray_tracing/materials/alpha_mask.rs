@@ -0,0 +1,77 @@
+use super::material::{Material, ScatterRecord};
+use super::texture::TexturePtr;
+use crate::ray_tracing::geometry::hittable::HitRecord;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use std::sync::Arc;
+
+/// 透明度遮罩材质，用纹理控制表面哪些区域可见（树叶、铁丝网等镂空效果）
+pub struct AlphaMask {
+    base: Arc<dyn Material>,
+    mask: TexturePtr,
+}
+
+impl AlphaMask {
+    /// 用遮罩纹理包装基础材质，纹理亮度为不透明度（1为不透明，0为完全透明）
+    #[inline]
+    pub fn new(base: Arc<dyn Material>, mask: TexturePtr) -> Self {
+        Self { base, mask }
+    }
+}
+
+impl Material for AlphaMask {
+    #[inline]
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        self.base.scatter(r_in, rec, srec)
+    }
+
+    #[inline]
+    fn emitted(&self, u: f64, v: f64, p: &Point3) -> Color {
+        self.base.emitted(u, v, p)
+    }
+
+    #[inline]
+    fn emitted_towards(&self, r_in: &Ray, rec: &HitRecord) -> Color {
+        self.base.emitted_towards(r_in, rec)
+    }
+
+    #[inline]
+    fn scattering_pdf(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        self.base.scattering_pdf(r_in, rec, scattered)
+    }
+
+    #[inline]
+    fn bsdf_cos(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        srec: &ScatterRecord,
+        scattered: &Ray,
+    ) -> Color {
+        self.base.bsdf_cos(r_in, rec, srec, scattered)
+    }
+
+    #[inline]
+    fn alpha(&self, u: f64, v: f64, p: &Point3) -> f64 {
+        self.mask.scalar(u, v, p).clamp(0.0, 1.0) * self.base.alpha(u, v, p)
+    }
+
+    #[inline]
+    fn shadow_transmittance(&self, r: &Ray, rec: &HitRecord) -> Option<Color> {
+        self.base.shadow_transmittance(r, rec)
+    }
+
+    #[inline]
+    fn is_diffuse(&self) -> bool {
+        self.base.is_diffuse()
+    }
+}
+
+impl std::fmt::Debug for AlphaMask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AlphaMask")
+            .field("base", &self.base)
+            .field("mask", &"<Texture>")
+            .finish()
+    }
+}
@@ -0,0 +1,65 @@
+use super::diffuse_light::DiffuseLight;
+use super::material::{Material, ScatterRecord};
+use crate::ray_tracing::geometry::hittable::HitRecord;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::rendering::color::blackbody;
+use std::f64::consts::PI;
+
+/// 按色温指定颜色的漫射光源
+///
+/// 发光颜色为黑体辐射的颜色，强度以亮度（cd/m²）给出，色温和亮度互不影响。
+pub struct BlackbodyLight {
+    light: DiffuseLight,
+    kelvin: f64,
+    nits: f64,
+}
+
+impl BlackbodyLight {
+    /// 色温 kelvin、亮度 nits（cd/m²）的光源
+    pub fn new(kelvin: f64, nits: f64) -> Self {
+        Self {
+            light: DiffuseLight::new_color(blackbody(kelvin) * nits),
+            kelvin,
+            nits,
+        }
+    }
+
+    /// 按光通量（流明）和发光面积（m²）创建单面朗伯光源
+    pub fn from_lumens(kelvin: f64, lumens: f64, area: f64) -> Self {
+        Self::new(kelvin, lumens / (PI * area.max(1e-12)))
+    }
+
+    /// 色温（K）
+    #[inline]
+    pub fn kelvin(&self) -> f64 {
+        self.kelvin
+    }
+
+    /// 亮度（cd/m²）
+    #[inline]
+    pub fn nits(&self) -> f64 {
+        self.nits
+    }
+}
+
+impl Material for BlackbodyLight {
+    #[inline]
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        self.light.scatter(r_in, rec, srec)
+    }
+
+    #[inline]
+    fn emitted(&self, u: f64, v: f64, p: &Point3) -> Color {
+        self.light.emitted(u, v, p)
+    }
+}
+
+impl std::fmt::Debug for BlackbodyLight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlackbodyLight")
+            .field("kelvin", &self.kelvin)
+            .field("nits", &self.nits)
+            .finish()
+    }
+}
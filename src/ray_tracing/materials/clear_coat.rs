@@ -0,0 +1,188 @@
+use super::material::{Material, ScatterRecord};
+use super::microfacet::{fresnel_dielectric, ggx_d, roughness_to_alpha, smith_g};
+use crate::ray_tracing::geometry::hittable::HitRecord;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::rendering::color::luminance;
+use crate::ray_tracing::sampling::pdf::{ClearCoatPDF, ScatterPDF};
+use crate::ray_tracing::utils::random::random_double;
+use std::sync::Arc;
+
+/// 清漆层材质：任意底层材质上覆盖一层薄的光滑电介质（车漆、上漆的木材等）
+///
+/// 清漆层为 GGX 微表面反射，有独立的折射率和粗糙度；穿过清漆层到达底层并返回的光按两个方向的
+/// 菲涅尔透过率 (1 - F(θo))·(1 - F(θi)) 衰减，忽略层内的折射和多次反射。
+/// 底层为非镜面材质时两个波瓣的PDF按菲涅尔反射率和底层反照率加权混合（见 [`ClearCoatPDF`]），
+/// 底层为镜面材质时每次散射按相同的权重随机选择一个波瓣。只有正面有清漆层，背面直接使用底层材质。
+pub struct ClearCoat {
+    base: Arc<dyn Material>,
+    refraction_index: f64,
+    alpha: f64,
+}
+
+impl ClearCoat {
+    /// 在底层材质上覆盖折射率为 refraction_index、粗糙度为 roughness（0为光滑）的清漆层
+    #[inline]
+    pub fn new(base: Arc<dyn Material>, refraction_index: f64, roughness: f64) -> Self {
+        Self {
+            base,
+            refraction_index,
+            alpha: roughness_to_alpha(roughness.clamp(0.0, 1.0)),
+        }
+    }
+
+    /// 底层材质
+    #[inline]
+    pub fn base(&self) -> &Arc<dyn Material> {
+        &self.base
+    }
+
+    /// 清漆层在宏观表面上的菲涅尔反射率
+    #[inline]
+    fn fresnel(&self, cos_theta: f64) -> f64 {
+        fresnel_dielectric(cos_theta.abs(), self.refraction_index)
+    }
+
+    /// 选择清漆层波瓣的概率：清漆层反射率与到达底层后返回的能量之比
+    #[inline]
+    fn coat_probability(fresnel: f64, base_weight: f64) -> f64 {
+        let total = fresnel + base_weight;
+        if total > 0.0 { fresnel / total } else { 1.0 }
+    }
+
+    /// 清漆层反射波瓣的 BSDF·|cosθ|
+    fn coat_bsdf_cos(&self, wo: &Vec3, wi: &Vec3, n: &Vec3) -> f64 {
+        let cos_o = wo.dot(n);
+        if cos_o <= 0.0 || wi.dot(n) <= 0.0 {
+            return 0.0;
+        }
+        let h = wo + wi;
+        if h.norm_squared() < 1e-16 {
+            return 0.0;
+        }
+        let h = h.normalize();
+        let fresnel = fresnel_dielectric(wo.dot(&h), self.refraction_index);
+        fresnel * ggx_d(h.dot(n), self.alpha) * smith_g(wo, wi, n, &h, self.alpha) / (4.0 * cos_o)
+    }
+
+    /// 两个方向穿过清漆层的透过率
+    #[inline]
+    fn transmittance(&self, wo: &Vec3, wi: &Vec3, n: &Vec3) -> f64 {
+        (1.0 - self.fresnel(wo.dot(n))) * (1.0 - self.fresnel(wi.dot(n)))
+    }
+}
+
+impl Material for ClearCoat {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        if !rec.front_face {
+            return self.base.scatter(r_in, rec, srec);
+        }
+        let n = rec.normal;
+        let wo = -r_in.dir.normalize();
+        let fresnel = self.fresnel(wo.dot(&n));
+
+        if !self.base.scatter(r_in, rec, srec) {
+            // 底层吸收了光线，只剩清漆层的反射
+            let pdf = ClearCoatPDF::new(&wo, &n, self.alpha, 1.0, None);
+            srec.set_diffuse(Color::repeat(1.0), pdf);
+            return true;
+        }
+
+        if srec.skip_pdf {
+            // 镜面底层无法与清漆层的PDF混合，随机选择一个波瓣
+            let wi = srec.skip_pdf_ray.dir.normalize();
+            let transmittance = self.transmittance(&wo, &wi, &n);
+            let base_weight = transmittance * luminance(&srec.attenuation).clamp(0.0, 1.0);
+            let p = Self::coat_probability(fresnel, base_weight);
+            if random_double() < p {
+                let pdf = ClearCoatPDF::new(&wo, &n, self.alpha, 1.0, None);
+                srec.set_diffuse(Color::repeat(1.0 / p), pdf);
+            } else {
+                srec.attenuation *= transmittance / (1.0 - p);
+            }
+            return true;
+        }
+
+        let Some(base) = srec.pdf.take() else {
+            return false;
+        };
+        let base_weight = (1.0 - fresnel) * luminance(&srec.attenuation).clamp(0.0, 1.0);
+        let p = Self::coat_probability(fresnel, base_weight);
+        let pdf = ClearCoatPDF::new(&wo, &n, self.alpha, p, Some(base));
+        srec.set_diffuse(srec.attenuation, pdf);
+        true
+    }
+
+    #[inline]
+    fn emitted(&self, u: f64, v: f64, p: &Point3) -> Color {
+        self.base.emitted(u, v, p)
+    }
+
+    #[inline]
+    fn emitted_towards(&self, r_in: &Ray, rec: &HitRecord) -> Color {
+        self.base.emitted_towards(r_in, rec)
+    }
+
+    /// 清漆层与底层波瓣的标量和，底层按其 `scattering_pdf` 计入（不含颜色，供调试显示）
+    fn scattering_pdf(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        let base = self.base.scattering_pdf(r_in, rec, scattered);
+        if !rec.front_face {
+            return base;
+        }
+        let n = rec.normal;
+        let wo = -r_in.dir.normalize();
+        let wi = scattered.dir.normalize();
+        self.coat_bsdf_cos(&wo, &wi, &n) + self.transmittance(&wo, &wi, &n) * base
+    }
+
+    fn bsdf_cos(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        srec: &ScatterRecord,
+        scattered: &Ray,
+    ) -> Color {
+        let Some(ScatterPDF::ClearCoat(pdf)) = srec.pdf.as_ref().filter(|_| rec.front_face) else {
+            return self.base.bsdf_cos(r_in, rec, srec, scattered);
+        };
+        let n = rec.normal;
+        let wo = -r_in.dir.normalize();
+        let wi = scattered.dir.normalize();
+        let coat = self.coat_bsdf_cos(&wo, &wi, &n);
+
+        match pdf.base() {
+            Some(base) => {
+                let base_srec = ScatterRecord {
+                    pdf: Some(base.clone()),
+                    ..srec.clone()
+                };
+                let base = self.base.bsdf_cos(r_in, rec, &base_srec, scattered);
+                Color::repeat(coat) + self.transmittance(&wo, &wi, &n) * base
+            }
+            // 只有清漆层波瓣时，衰减为选择该波瓣的概率的倒数
+            None => srec.attenuation * coat,
+        }
+    }
+
+    #[inline]
+    fn alpha(&self, u: f64, v: f64, p: &Point3) -> f64 {
+        self.base.alpha(u, v, p)
+    }
+
+    /// 底层的透过率再乘以穿过清漆层的菲涅尔透过率
+    #[inline]
+    fn shadow_transmittance(&self, r: &Ray, rec: &HitRecord) -> Option<Color> {
+        let coat = 1.0 - self.fresnel(r.dir.normalize().dot(&rec.normal));
+        self.base.shadow_transmittance(r, rec).map(|t| t * coat)
+    }
+}
+
+impl std::fmt::Debug for ClearCoat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClearCoat")
+            .field("base", &self.base)
+            .field("refraction_index", &self.refraction_index)
+            .field("alpha", &self.alpha)
+            .finish()
+    }
+}
@@ -6,16 +6,59 @@ use crate::ray_tracing::utils::random::random_double;
 
 
 /// 电介质材质（玻璃等透明材质）
+///
+/// 可选的吸收系数按 Beer-Lambert 定律使穿过内部的光线衰减：光线从内部射到表面时，
+/// 乘以 exp(-σ·d)，d 为在内部走过的距离，得到有色玻璃和有色液体。
 #[derive(Debug)]
 pub struct Dielectric {
     refraction_index: f64, // 折射率
+    absorption: Color,     // 每单位长度的吸收系数 σ，0 为无色
 }
 
 impl Dielectric {
     /// 创建电介质材质
     #[inline]
     pub const fn new(refraction_index: f64) -> Self {
-        Self { refraction_index }
+        Self {
+            refraction_index,
+            absorption: Color::new(0.0, 0.0, 0.0),
+        }
+    }
+
+    /// 设置每单位长度的吸收系数
+    #[inline]
+    pub fn with_absorption(mut self, absorption: Color) -> Self {
+        self.absorption = absorption.map(|c| c.max(0.0));
+        self
+    }
+
+    /// 按穿过 distance 后剩余的颜色设置吸收系数：σ = -ln(color) / distance
+    #[inline]
+    pub fn with_tint(self, color: Color, distance: f64) -> Self {
+        let sigma = color.map(|c| -c.clamp(1e-6, 1.0).ln() / distance.max(1e-12));
+        self.with_absorption(sigma)
+    }
+
+    /// 折射率
+    #[inline]
+    pub const fn refraction_index(&self) -> f64 {
+        self.refraction_index
+    }
+
+    /// 每单位长度的吸收系数
+    #[inline]
+    pub fn absorption(&self) -> Color {
+        self.absorption
+    }
+
+    /// 从内部射到表面的光线在内部穿过的吸收，从外部射入时为1
+    #[inline]
+    fn interior_attenuation(&self, r_in: &Ray, rec: &HitRecord) -> Color {
+        if rec.front_face || self.absorption == Color::zeros() {
+            return Color::repeat(1.0);
+        }
+        let distance = rec.t * r_in.dir.norm();
+        (-self.absorption * distance).map(f64::exp)
     }
 
     /// Schlick近似计算反射率
@@ -50,7 +93,16 @@ impl Material for Dielectric {
         };
 
         let scattered_ray = Ray::new(rec.p, direction, r_in.time);
-        srec.set_specular(Color::new(1.0, 1.0, 1.0), scattered_ray);
+        srec.set_specular(self.interior_attenuation(r_in, rec), scattered_ray);
         true
     }
+
+    /// 阴影光线沿直线穿过表面（忽略折射造成的偏折），透过率为 1 减去菲涅尔反射率，
+    /// 从内部射出时再乘以内部的吸收
+    fn shadow_transmittance(&self, r: &Ray, rec: &HitRecord) -> Option<Color> {
+        // 两侧的 Schlick 反射率只取决于 r0，r0 对 n 和 1/n 相同
+        let cosine = r.dir.normalize().dot(&rec.normal).abs();
+        let surface = 1.0 - Self::reflectance(cosine, self.refraction_index);
+        Some(self.interior_attenuation(r, rec) * surface)
+    }
 }
@@ -1,29 +1,54 @@
 use super::material::{Material, ScatterRecord};
-use super::texture::{SolidColor, TexturePtr};
+use super::texture::{Texture, TextureKind, TexturePtr};
 use crate::ray_tracing::geometry::hittable::HitRecord;
 use crate::ray_tracing::math::ray::Ray;
 use crate::ray_tracing::math::vec3::*;
-use std::sync::Arc;
+use crate::ray_tracing::rendering::color::luminance;
+use crate::ray_tracing::rendering::exposure::LUMINOUS_EFFICACY;
+use std::f64::consts::PI;
 
 /// 漫射光源材质
 pub struct DiffuseLight {
-    emit: TexturePtr,
+    emit: TextureKind,
 }
 
 impl DiffuseLight {
     /// 从纹理创建光源
     #[inline]
     pub fn new(emit: TexturePtr) -> Self {
-        Self { emit }
+        Self { emit: emit.into() }
     }
 
     /// 从纯色创建光源
     #[inline]
     pub fn new_color(color: Color) -> Self {
         Self {
-            emit: Arc::new(SolidColor::new(color)),
+            emit: TextureKind::solid(color),
         }
     }
+
+    /// 发光纹理
+    #[inline]
+    pub fn emission(&self) -> &TextureKind {
+        &self.emit
+    }
+
+    /// 按亮度（cd/m²）创建光源，color 只决定色调，会按亮度归一化
+    pub fn from_nits(color: Color, nits: f64) -> Self {
+        let lum = luminance(&color);
+        let tint = if lum > 0.0 { color / lum } else { color };
+        Self::new_color(tint * nits)
+    }
+
+    /// 按光通量（流明）和发光面积（m²）创建单面朗伯光源：L = Φ / (π·A)
+    pub fn from_lumens(color: Color, lumens: f64, area: f64) -> Self {
+        Self::from_nits(color, lumens / (PI * area.max(1e-12)))
+    }
+
+    /// 按辐射功率（瓦特）和发光面积创建光源，按最大光视效能换算为流明
+    pub fn from_watts(color: Color, watts: f64, area: f64) -> Self {
+        Self::from_lumens(color, watts * LUMINOUS_EFFICACY, area)
+    }
 }
 
 impl Material for DiffuseLight {
@@ -0,0 +1,110 @@
+use super::material::{Material, ScatterRecord};
+use super::texture::{Texture, TextureKind, TexturePtr};
+use crate::ray_tracing::geometry::hittable::HitRecord;
+use crate::ray_tracing::math::onb::ONB;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::sampling::pdf::FiberPDF;
+use crate::ray_tracing::utils::random::random_double;
+use std::f64::consts::PI;
+
+/// Kajiya-Kay 毛发材质：绕纤维切线对称的漫反射加上沿反射锥的无色高光
+///
+/// 纤维切线取自命中记录的 dpdu（曲线几何体为曲线切线），没有切线的几何体上退化为以法线构造的切线。
+/// 每次散射以 `specular` 的概率选择高光分支，期望值即为两者的线性混合。
+pub struct HairMaterial {
+    color: TextureKind,
+    specular: f64,  // 高光分支的权重
+    roughness: f64, // 高光偏离反射锥的程度
+}
+
+impl HairMaterial {
+    /// 从纯色创建毛发材质
+    #[inline]
+    pub fn new(color: Color, specular: f64, roughness: f64) -> Self {
+        Self {
+            color: TextureKind::solid(color),
+            specular: specular.clamp(0.0, 1.0),
+            roughness: roughness.clamp(0.0, 1.0),
+        }
+    }
+
+    /// 从纹理创建毛发材质（曲线的纹理坐标 u 从根部的0变化到末端的1）
+    #[inline]
+    pub fn new_texture(color: TexturePtr, specular: f64, roughness: f64) -> Self {
+        Self {
+            color: color.into(),
+            specular: specular.clamp(0.0, 1.0),
+            roughness: roughness.clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// 命中点的纤维切线（单位向量）
+#[inline]
+fn fiber_tangent(rec: &HitRecord) -> Vec3 {
+    if rec.dpdu.norm_squared() > 1e-24 {
+        rec.dpdu.normalize()
+    } else {
+        ONB::new(&rec.normal).u()
+    }
+}
+
+impl Material for HairMaterial {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        let tangent = fiber_tangent(rec);
+
+        if random_double() < self.specular {
+            // 高光分支：出射方向在以切线为轴、与入射方向夹角相同的反射锥上，只取朝向入射一侧的半锥
+            let d = r_in.dir.normalize();
+            let cos_t = d.dot(&tangent);
+            let sin_t = (1.0 - cos_t * cos_t).max(0.0).sqrt();
+            let normal = rec.normal - tangent * rec.normal.dot(&tangent);
+            if normal.norm_squared() < 1e-24 {
+                return false;
+            }
+            let normal = normal.normalize();
+            let binormal = tangent.cross(&normal);
+            let phi = PI * (random_double() - 0.5);
+            let cone = cos_t * tangent + sin_t * (phi.cos() * normal + phi.sin() * binormal);
+            let scattered_dir = cone + self.roughness * Vec3::random_in_unit_sphere();
+            if scattered_dir.norm_squared() < 1e-12 {
+                return false;
+            }
+
+            srec.set_specular(
+                Color::repeat(1.0),
+                Ray::new(rec.p, scattered_dir, r_in.time),
+            );
+        } else {
+            let color = self.color.value_at_time(
+                rec.u,
+                rec.v,
+                &rec.p,
+                &rec.normal,
+                rec.footprint.as_ref(),
+                r_in.time,
+            );
+            srec.set_diffuse(color, FiberPDF::new(&tangent));
+        }
+        true
+    }
+
+    fn scattering_pdf(&self, _r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        // 仅漫反射分支会使用PDF
+        let cos_alpha = fiber_tangent(rec)
+            .dot(&scattered.dir.normalize())
+            .clamp(-1.0, 1.0);
+        (1.0 - cos_alpha * cos_alpha).sqrt() / (PI * PI)
+    }
+}
+
+impl std::fmt::Debug for HairMaterial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HairMaterial")
+            .field("color", &"<Texture>")
+            .field("specular", &self.specular)
+            .field("roughness", &self.roughness)
+            .finish()
+    }
+}
@@ -1,41 +1,64 @@
 use super::material::{Material, ScatterRecord};
-use super::texture::{SolidColor, TexturePtr};
+use super::texture::{Texture, TextureKind, TexturePtr};
 use crate::ray_tracing::geometry::hittable::HitRecord;
 use crate::ray_tracing::math::ray::Ray;
 use crate::ray_tracing::math::vec3::*;
 use crate::ray_tracing::sampling::pdf::SpherePDF;
-use std::sync::Arc;
 
 /// 各向同性散射材质，用于体积介质
 pub struct Isotropic {
-    albedo: TexturePtr,
+    albedo: TextureKind,
+    emit: Option<TextureKind>, // 自发光（发光雾、等离子体等）
 }
 
 impl Isotropic {
     /// 从纹理创建各向同性材质
     #[inline]
     pub fn new(texture: TexturePtr) -> Self {
-        Self { albedo: texture }
+        Self {
+            albedo: texture.into(),
+            emit: None,
+        }
     }
 
     /// 从颜色创建各向同性材质
     #[inline]
     pub fn new_color(color: Color) -> Self {
         Self {
-            albedo: Arc::new(SolidColor::new(color)),
+            albedo: TextureKind::solid(color),
+            emit: None,
+        }
+    }
+
+    /// 创建带自发光的各向同性材质，在每个散射点贡献发射辐射度
+    #[inline]
+    pub fn new_emissive(albedo: TexturePtr, emit: TexturePtr) -> Self {
+        Self {
+            albedo: albedo.into(),
+            emit: Some(emit.into()),
         }
     }
 }
 
 impl Material for Isotropic {
-    fn scatter(&self, _r_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
-        let attenuation = self.albedo.value(rec.u, rec.v, &rec.p);
-        let pdf = Arc::new(SpherePDF::new());
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        let attenuation =
+            self.albedo
+                .value_at_time(rec.u, rec.v, &rec.p, &rec.normal, None, r_in.time);
+        let pdf = SpherePDF::new();
 
         srec.set_diffuse(attenuation, pdf);
         true
     }
 
+    #[inline]
+    fn emitted(&self, u: f64, v: f64, p: &Point3) -> Color {
+        match &self.emit {
+            Some(emit) => emit.value(u, v, p),
+            None => Color::zeros(),
+        }
+    }
+
     #[inline]
     fn scattering_pdf(&self, _r_in: &Ray, _rec: &HitRecord, _scattered: &Ray) -> f64 {
         // 各向同性散射在所有方向的概率相等
@@ -47,6 +70,7 @@ impl std::fmt::Debug for Isotropic {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Isotropic")
             .field("albedo", &"<Texture>")
+            .field("emit", &self.emit.as_ref().map(|_| "<Texture>"))
             .finish()
     }
 }
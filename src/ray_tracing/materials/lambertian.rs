@@ -1,14 +1,13 @@
 use super::material::{Material, ScatterRecord};
-use super::texture::{SolidColor, TexturePtr};
+use super::texture::{Texture, TextureKind, TexturePtr};
 use crate::ray_tracing::geometry::hittable::HitRecord;
 use crate::ray_tracing::math::ray::Ray;
 use crate::ray_tracing::math::vec3::*;
 use crate::ray_tracing::sampling::pdf::CosinePDF;
-use std::sync::Arc;
 
 /// 朗伯材质（理想漫反射）
 pub struct Lambertian {
-    albedo: TexturePtr,
+    albedo: TextureKind,
 }
 
 impl Lambertian {
@@ -16,21 +15,36 @@ impl Lambertian {
     #[inline]
     pub fn new(albedo: Color) -> Self {
         Self {
-            albedo: Arc::new(SolidColor::new(albedo)),
+            albedo: TextureKind::solid(albedo),
         }
     }
 
     /// 从纹理创建朗伯材质
     #[inline]
     pub fn new_texture(albedo: TexturePtr) -> Self {
-        Self { albedo }
+        Self {
+            albedo: albedo.into(),
+        }
+    }
+
+    /// 反照率纹理
+    #[inline]
+    pub fn albedo(&self) -> &TextureKind {
+        &self.albedo
     }
 }
 
 impl Material for Lambertian {
-    fn scatter(&self, _r_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
-        let attenuation = self.albedo.value(rec.u, rec.v, &rec.p);
-        let pdf = Arc::new(CosinePDF::new(&rec.normal));
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        let attenuation = self.albedo.value_at_time(
+            rec.u,
+            rec.v,
+            &rec.p,
+            &rec.normal,
+            rec.footprint.as_ref(),
+            r_in.time,
+        );
+        let pdf = CosinePDF::new(&rec.normal);
 
         srec.set_diffuse(attenuation, pdf);
         true
@@ -44,6 +58,11 @@ impl Material for Lambertian {
             cos_theta / std::f64::consts::PI
         }
     }
+
+    #[inline]
+    fn is_diffuse(&self) -> bool {
+        true
+    }
 }
 
 impl std::fmt::Debug for Lambertian {
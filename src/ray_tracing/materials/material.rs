@@ -1,13 +1,14 @@
 use crate::ray_tracing::geometry::hittable::HitRecord;
 use crate::ray_tracing::math::ray::Ray;
 use crate::ray_tracing::math::vec3::*;
-use crate::ray_tracing::sampling::pdf::PDF;
-use std::sync::Arc;
+use crate::ray_tracing::sampling::pdf::ScatterPDF;
+use std::any::Any;
 
 /// 散射记录，包含材质散射的所有信息
+#[derive(Clone)]
 pub struct ScatterRecord {
     pub attenuation: Color,
-    pub pdf_ptr: Option<Arc<dyn PDF>>,
+    pub pdf: Option<ScatterPDF>,
     pub skip_pdf: bool,
     pub skip_pdf_ray: Ray,
 }
@@ -17,7 +18,7 @@ impl ScatterRecord {
     pub fn new() -> Self {
         Self {
             attenuation: Color::new(0.0, 0.0, 0.0),
-            pdf_ptr: None,
+            pdf: None,
             skip_pdf: false,
             skip_pdf_ray: Ray::default(),
         }
@@ -29,15 +30,17 @@ impl ScatterRecord {
         self.attenuation = attenuation;
         self.skip_pdf = true;
         self.skip_pdf_ray = ray;
-        self.pdf_ptr = None;
+        self.pdf = None;
     }
 
     /// 设置为使用PDF的散射（如漫反射）
+    ///
+    /// 内置的PDF按值传入，自定义PDF以 `Arc` 传入。
     #[inline]
-    pub fn set_diffuse(&mut self, attenuation: Color, pdf: Arc<dyn PDF>) {
+    pub fn set_diffuse(&mut self, attenuation: Color, pdf: impl Into<ScatterPDF>) {
         self.attenuation = attenuation;
         self.skip_pdf = false;
-        self.pdf_ptr = Some(pdf);
+        self.pdf = Some(pdf.into());
     }
 }
 
@@ -48,7 +51,14 @@ impl Default for ScatterRecord {
 }
 
 /// 材质trait，定义光线与表面的交互行为
-pub trait Material: Send + Sync + std::fmt::Debug {
+///
+/// 下游 crate 可以直接实现此 trait 定义自定义材质（见 `examples/custom_material.rs`）。积分器的约定：
+/// - `scatter` 返回false表示光线被吸收；镜面散射用 `ScatterRecord::set_specular` 给出确定的出射光线，
+///   其余散射用 `set_diffuse` 给出衰减和用于采样方向的PDF
+/// - 非镜面散射的贡献为 `attenuation * scattering_pdf / pdf`，pdf 是与光源采样混合后的采样密度，
+///   因此 `attenuation * scattering_pdf` 应等于 BSDF 乘以 |cosθ|
+/// - 由多个颜色不同的波瓣组成的材质（如清漆层）无法写成单一衰减与标量的乘积，覆盖 `bsdf_cos` 直接给出 BSDF·|cosθ|
+pub trait Material: Any + Send + Sync + std::fmt::Debug {
     /// 主要的散射方法
     fn scatter(&self, r_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool;
 
@@ -58,11 +68,65 @@ pub trait Material: Send + Sync + std::fmt::Debug {
         Color::new(0.0, 0.0, 0.0)
     }
 
+    /// 沿入射光线反方向发出的辐射度，方向相关的光源（如聚光灯）覆盖此方法
+    #[inline]
+    fn emitted_towards(&self, _r_in: &Ray, rec: &HitRecord) -> Color {
+        self.emitted(rec.u, rec.v, &rec.p)
+    }
+
     /// 散射PDF值（用于重要性采样）
     #[inline]
     fn scattering_pdf(&self, _r_in: &Ray, _rec: &HitRecord, _scattered: &Ray) -> f64 {
         0.0
     }
+
+    /// 散射方向上的 BSDF·|cosθ|，srec 为本次 `scatter` 得到的散射记录，默认为 `attenuation * scattering_pdf`
+    #[inline]
+    fn bsdf_cos(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        srec: &ScatterRecord,
+        scattered: &Ray,
+    ) -> Color {
+        srec.attenuation * self.scattering_pdf(r_in, rec, scattered)
+    }
+
+    /// 表面不透明度（1为完全不透明，0为光线直接穿过）
+    #[inline]
+    fn alpha(&self, _u: f64, _v: f64, _p: &Point3) -> f64 {
+        1.0
+    }
+
+    /// 阴影光线穿过此表面后剩余的比例；None 表示完全遮挡（默认）
+    ///
+    /// 透明材质（电介质）覆盖此方法，使直接光照的阴影光线穿过玻璃并被着色和衰减，而不是被完全挡住。
+    #[inline]
+    fn shadow_transmittance(&self, _r: &Ray, _rec: &HitRecord) -> Option<Color> {
+        None
+    }
+
+    /// 是否为纯漫反射（朗伯）材质，即出射辐射度只取决于辐照度
+    ///
+    /// 辐照度缓存等只对朗伯表面成立的近似据此判断，其余材质走完整的路径追踪。
+    #[inline]
+    fn is_diffuse(&self) -> bool {
+        false
+    }
+}
+
+impl dyn Material {
+    /// 尝试把材质转换为具体类型的引用
+    #[inline]
+    pub fn downcast_ref<T: Material>(&self) -> Option<&T> {
+        (self as &dyn Any).downcast_ref()
+    }
+
+    /// 材质是否为类型 T
+    #[inline]
+    pub fn is<T: Material>(&self) -> bool {
+        (self as &dyn Any).is::<T>()
+    }
 }
 
 /// 空材质，用作默认值或虚拟光源
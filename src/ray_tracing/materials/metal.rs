@@ -1,13 +1,13 @@
 use super::material::{Material, ScatterRecord};
+use super::texture::{Texture, TextureKind, TexturePtr};
 use crate::ray_tracing::geometry::hittable::HitRecord;
 use crate::ray_tracing::math::ray::Ray;
 use crate::ray_tracing::math::vec3::*;
 
 /// 金属材质
-#[derive(Debug)]
 pub struct Metal {
-    albedo: Color,
-    fuzz: f64, // 模糊度，0为完美镜面，1为完全模糊
+    albedo: TextureKind,
+    fuzz: TextureKind, // 模糊度（粗糙度），0为完美镜面，1为完全模糊
 }
 
 impl Metal {
@@ -15,16 +15,38 @@ impl Metal {
     #[inline]
     pub fn new(albedo: Color, fuzz: f64) -> Self {
         Self {
-            albedo,
-            fuzz: fuzz.clamp(0.0, 1.0), // 限制模糊度在合理范围内
+            albedo: TextureKind::solid(albedo),
+            fuzz: TextureKind::solid(Color::repeat(fuzz.clamp(0.0, 1.0))), // 限制模糊度在合理范围内
         }
     }
+
+    /// 从反照率纹理和粗糙度纹理创建金属材质
+    #[inline]
+    pub fn new_texture(albedo: TexturePtr, roughness: TexturePtr) -> Self {
+        Self {
+            albedo: albedo.into(),
+            fuzz: roughness.into(),
+        }
+    }
+
+    /// 反照率纹理
+    #[inline]
+    pub fn albedo(&self) -> &TextureKind {
+        &self.albedo
+    }
+
+    /// 模糊度纹理
+    #[inline]
+    pub fn fuzz(&self) -> &TextureKind {
+        &self.fuzz
+    }
 }
 
 impl Material for Metal {
     fn scatter(&self, r_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        let fuzz = self.fuzz.scalar(rec.u, rec.v, &rec.p).clamp(0.0, 1.0);
         let reflected = r_in.dir.normalize().reflect(&rec.normal);
-        let scattered_dir = reflected + self.fuzz * Vec3::random_in_unit_sphere();
+        let scattered_dir = reflected + fuzz * Vec3::random_in_unit_sphere();
 
         // 检查散射方向是否在表面上方
         if scattered_dir.dot(&rec.normal) <= 0.0 {
@@ -32,7 +54,24 @@ impl Material for Metal {
         }
 
         let scattered_ray = Ray::new(rec.p, scattered_dir, r_in.time);
-        srec.set_specular(self.albedo, scattered_ray);
+        let albedo = self.albedo.value_at_time(
+            rec.u,
+            rec.v,
+            &rec.p,
+            &rec.normal,
+            rec.footprint.as_ref(),
+            r_in.time,
+        );
+        srec.set_specular(albedo, scattered_ray);
         true
     }
 }
+
+impl std::fmt::Debug for Metal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Metal")
+            .field("albedo", &"<Texture>")
+            .field("fuzz", &"<Texture>")
+            .finish()
+    }
+}
@@ -0,0 +1,158 @@
+//! GGX（Trowbridge-Reitz）微表面模型的公共函数
+
+use crate::ray_tracing::math::onb::ONB;
+use crate::ray_tracing::math::vec3::{Color, Vec3};
+use crate::ray_tracing::sampling::pdf::GgxDielectricPDF;
+use crate::ray_tracing::utils::random::random_double;
+use std::f64::consts::PI;
+
+/// 最小粗糙度参数，避免接近镜面时数值发散
+pub const MIN_ALPHA: f64 = 1e-3;
+
+/// 由感知粗糙度计算 GGX 的 alpha 参数（alpha = roughness²）
+#[inline]
+pub fn roughness_to_alpha(roughness: f64) -> f64 {
+    (roughness * roughness).max(MIN_ALPHA)
+}
+
+/// GGX 法线分布函数 D(h)，cos_h 为微表面法线与宏观法线夹角的余弦
+#[inline]
+pub fn ggx_d(cos_h: f64, alpha: f64) -> f64 {
+    if cos_h <= 0.0 {
+        return 0.0;
+    }
+    let a2 = alpha * alpha;
+    let denom = cos_h * cos_h * (a2 - 1.0) + 1.0;
+    a2 / (PI * denom * denom)
+}
+
+/// Smith 单向遮蔽函数 G1，w 为方向，n 为宏观法线，h 为微表面法线
+#[inline]
+pub fn smith_g1(w: &Vec3, n: &Vec3, h: &Vec3, alpha: f64) -> f64 {
+    let cos_n = w.dot(n);
+    // 方向必须位于微表面的同一侧
+    if w.dot(h) * cos_n <= 0.0 {
+        return 0.0;
+    }
+    let cos2 = (cos_n * cos_n).min(1.0);
+    let tan2 = (1.0 - cos2) / cos2.max(1e-12);
+    2.0 / (1.0 + (1.0 + alpha * alpha * tan2).sqrt())
+}
+
+/// 双向遮蔽-阴影项 G = G1(v)·G1(l)
+#[inline]
+pub fn smith_g(v: &Vec3, l: &Vec3, n: &Vec3, h: &Vec3, alpha: f64) -> f64 {
+    smith_g1(v, n, h, alpha) * smith_g1(l, n, h, alpha)
+}
+
+/// 电介质精确菲涅尔反射率，cos_i 为入射方向与法线夹角余弦，eta 为透射侧/入射侧折射率之比
+pub fn fresnel_dielectric(cos_i: f64, eta: f64) -> f64 {
+    let cos_i = cos_i.clamp(0.0, 1.0);
+    let sin2_t = (1.0 - cos_i * cos_i) / (eta * eta);
+    if sin2_t >= 1.0 {
+        return 1.0; // 全内反射
+    }
+    let cos_t = (1.0 - sin2_t).sqrt();
+    let rs = (cos_i - eta * cos_t) / (cos_i + eta * cos_t);
+    let rp = (eta * cos_i - cos_t) / (eta * cos_i + cos_t);
+    0.5 * (rs * rs + rp * rp)
+}
+
+/// Schlick 菲涅尔近似，f0 为垂直入射时的反射率
+#[inline]
+pub fn fresnel_schlick(f0: Color, cos_theta: f64) -> Color {
+    let weight = (1.0 - cos_theta.clamp(0.0, 1.0)).powi(5);
+    f0 + (Color::repeat(1.0) - f0) * weight
+}
+
+/// 电介质 GGX 微表面反射与透射的 BSDF·|cosθ|，eta 为透射侧/入射侧折射率之比
+///
+/// 折射项不做 η² 缩放（与光滑的 Dielectric 一致）。
+pub fn ggx_dielectric_bsdf_cos(wo: &Vec3, wi: &Vec3, n: &Vec3, eta: f64, alpha: f64) -> f64 {
+    let Some((h, reflect)) = GgxDielectricPDF::half_vector(wo, wi, n, eta) else {
+        return 0.0;
+    };
+
+    let cos_o = wo.dot(n).abs().max(1e-8);
+    let cos_vh = wo.dot(&h);
+    let cos_lh = wi.dot(&h);
+    if cos_vh <= 0.0 {
+        return 0.0;
+    }
+
+    let fresnel = fresnel_dielectric(cos_vh, eta);
+    let d = ggx_d(h.dot(n), alpha);
+    let g = smith_g(wo, wi, n, &h, alpha);
+
+    if reflect {
+        fresnel * d * g / (4.0 * cos_o)
+    } else {
+        let denom = cos_vh + eta * cos_lh;
+        (1.0 - fresnel) * d * g * eta * eta * cos_vh * cos_lh.abs() / (cos_o * denom * denom)
+    }
+}
+
+/// GTR1（Berry）法线分布，拖尾比 GGX 更长，用于 Disney 模型的清漆层
+#[inline]
+pub fn gtr1_d(cos_h: f64, alpha: f64) -> f64 {
+    if cos_h <= 0.0 {
+        return 0.0;
+    }
+    let a2 = alpha * alpha;
+    if a2 >= 1.0 {
+        return 1.0 / PI;
+    }
+    (a2 - 1.0) / (PI * a2.ln() * (1.0 + (a2 - 1.0) * cos_h * cos_h))
+}
+
+/// 按 D(h)·cosθh 采样 GTR1 分布的微表面法线，返回世界坐标
+pub fn sample_gtr1(uvw: &ONB, alpha: f64) -> Vec3 {
+    let a2 = alpha * alpha;
+    let u = random_double();
+    let cos2 = if a2 >= 1.0 {
+        1.0 - u
+    } else {
+        (1.0 - a2.powf(1.0 - u)) / (1.0 - a2)
+    };
+    let cos_theta = cos2.clamp(0.0, 1.0).sqrt();
+    let sin_theta = (1.0 - cos2).max(0.0).sqrt();
+    let phi = 2.0 * PI * random_double();
+    uvw.local_to_world(&Vec3::new(
+        sin_theta * phi.cos(),
+        sin_theta * phi.sin(),
+        cos_theta,
+    ))
+}
+
+/// 可见法线分布（VNDF）的概率密度：G1(wo)·max(0, wo·h)·D(h) / |wo·n|
+#[inline]
+pub fn vndf_pdf(wo: &Vec3, n: &Vec3, h: &Vec3, alpha: f64) -> f64 {
+    let cos_o = wo.dot(n).abs().max(1e-8);
+    smith_g1(wo, n, h, alpha) * wo.dot(h).max(0.0) * ggx_d(h.dot(n), alpha) / cos_o
+}
+
+/// 按可见法线分布采样微表面法线（Heitz 2018），wo 为朝向法线一侧的观察方向，返回世界坐标
+pub fn sample_ggx_vndf(uvw: &ONB, wo: &Vec3, alpha: f64) -> Vec3 {
+    let local = uvw.world_to_local(wo);
+    // 拉伸到单位粗糙度的半球
+    let vh = Vec3::new(alpha * local.x, alpha * local.y, local.z.max(0.0)).normalize();
+
+    let lensq = vh.x * vh.x + vh.y * vh.y;
+    let t1_axis = if lensq > 0.0 {
+        Vec3::new(-vh.y, vh.x, 0.0) / lensq.sqrt()
+    } else {
+        Vec3::new(1.0, 0.0, 0.0)
+    };
+    let t2_axis = vh.cross(&t1_axis);
+
+    let r = random_double().sqrt();
+    let phi = 2.0 * PI * random_double();
+    let t1 = r * phi.cos();
+    let s = 0.5 * (1.0 + vh.z);
+    let t2 = (1.0 - s) * (1.0 - t1 * t1).sqrt() + s * r * phi.sin();
+
+    let nh = t1 * t1_axis + t2 * t2_axis + (1.0 - t1 * t1 - t2 * t2).max(0.0).sqrt() * vh;
+    // 还原拉伸
+    let ne = Vec3::new(alpha * nh.x, alpha * nh.y, nh.z.max(1e-6)).normalize();
+    uvw.local_to_world(&ne)
+}
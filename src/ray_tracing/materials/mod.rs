@@ -1,7 +1,18 @@
+pub mod alpha_mask;
+pub mod blackbody_light;
+pub mod clear_coat;
 pub mod dielectric;
 pub mod diffuse_light;
+pub mod hair;
 pub mod isotropic;
 pub mod lambertian;
 pub mod material;
 pub mod metal;
+pub mod microfacet;
+pub mod pbr;
+pub mod point_light;
+pub mod principled;
+pub mod rough_dielectric;
+pub mod scaled;
+pub mod spot_light;
 pub mod texture;
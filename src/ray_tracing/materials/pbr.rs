@@ -0,0 +1,133 @@
+use super::material::{Material, ScatterRecord};
+use super::texture::{Texture, TextureKind, TexturePtr};
+use crate::ray_tracing::geometry::hittable::HitRecord;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::sampling::pdf::CosinePDF;
+use crate::ray_tracing::utils::random::random_double;
+
+/// PBR纹理集材质：由反照率、粗糙度、金属度纹理共同驱动外观
+///
+/// 每次散射按金属度随机选择金属反射或漫反射分支，期望值即为两者的线性混合。
+/// 可选的切线空间法线贴图沿交点的 dpdu/dpdv 扰动着色法线；不提供这两个偏导数的几何体上法线贴图不起作用。
+pub struct PbrMaterial {
+    albedo: TextureKind,
+    roughness: TextureKind,
+    metalness: TextureKind,
+    normal_map: Option<TextureKind>,
+}
+
+impl PbrMaterial {
+    /// 从三张纹理创建PBR材质
+    #[inline]
+    pub fn new(albedo: TexturePtr, roughness: TexturePtr, metalness: TexturePtr) -> Self {
+        Self {
+            albedo: albedo.into(),
+            roughness: roughness.into(),
+            metalness: metalness.into(),
+            normal_map: None,
+        }
+    }
+
+    /// 从常量参数创建PBR材质
+    #[inline]
+    pub fn new_constant(albedo: Color, roughness: f64, metalness: f64) -> Self {
+        Self {
+            albedo: TextureKind::solid(albedo),
+            roughness: TextureKind::solid(Color::repeat(roughness)),
+            metalness: TextureKind::solid(Color::repeat(metalness)),
+            normal_map: None,
+        }
+    }
+
+    /// 设置切线空间法线贴图（RGB 按 2c - 1 解码为切线、副切线、法线方向的分量，(0.5, 0.5, 1) 为不扰动）
+    #[inline]
+    pub fn with_normal_map(mut self, normal_map: TexturePtr) -> Self {
+        self.normal_map = Some(normal_map.into());
+        self
+    }
+
+    /// 交点处的着色法线：切线取 dpdu 在法线平面上的投影，副切线的朝向与 dpdv 一致
+    ///
+    /// 没有法线贴图、交点没有偏导数或扰动后的法线翻到表面背面时返回几何法线。
+    fn shading_normal(&self, rec: &HitRecord) -> Vec3 {
+        let Some(normal_map) = &self.normal_map else {
+            return rec.normal;
+        };
+        let tangent = rec.dpdu - rec.normal * rec.normal.dot(&rec.dpdu);
+        if tangent.norm_squared() < 1e-16 {
+            return rec.normal;
+        }
+        let tangent = tangent.normalize();
+        let mut bitangent = rec.normal.cross(&tangent);
+        if bitangent.dot(&rec.dpdv) < 0.0 {
+            bitangent = -bitangent;
+        }
+
+        let encoded =
+            normal_map.value_filtered(rec.u, rec.v, &rec.p, &rec.normal, rec.footprint.as_ref());
+        let local = encoded * 2.0 - Vec3::repeat(1.0);
+        let normal = local.x * tangent + local.y * bitangent + local.z * rec.normal;
+        if normal.dot(&rec.normal) <= 1e-6 {
+            return rec.normal;
+        }
+        normal.normalize()
+    }
+}
+
+impl Material for PbrMaterial {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        let albedo = self.albedo.value_at_time(
+            rec.u,
+            rec.v,
+            &rec.p,
+            &rec.normal,
+            rec.footprint.as_ref(),
+            r_in.time,
+        );
+        let metalness = self.metalness.scalar(rec.u, rec.v, &rec.p).clamp(0.0, 1.0);
+        let normal = self.shading_normal(rec);
+
+        if random_double() < metalness {
+            // 金属分支：粗糙度控制反射模糊
+            let roughness = self.roughness.scalar(rec.u, rec.v, &rec.p).clamp(0.0, 1.0);
+            let reflected = r_in.dir.normalize().reflect(&normal);
+            let scattered_dir = reflected + roughness * Vec3::random_in_unit_sphere();
+            // 反射方向既要在着色法线一侧，也不能穿入几何表面
+            if scattered_dir.dot(&normal) <= 0.0 || scattered_dir.dot(&rec.normal) <= 0.0 {
+                return false;
+            }
+
+            srec.set_specular(albedo, Ray::new(rec.p, scattered_dir, r_in.time));
+        } else {
+            // 电介质分支：漫反射
+            srec.set_diffuse(albedo, CosinePDF::new(&normal));
+        }
+        true
+    }
+
+    fn scattering_pdf(&self, _r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        // 仅漫反射分支会使用PDF；穿入几何表面的方向没有贡献
+        let direction = scattered.dir.normalize();
+        if direction.dot(&rec.normal) <= 0.0 {
+            return 0.0;
+        }
+        let cos_theta = self.shading_normal(rec).dot(&direction);
+        if cos_theta < 0.0 {
+            0.0
+        } else {
+            cos_theta / std::f64::consts::PI
+        }
+    }
+}
+
+impl std::fmt::Debug for PbrMaterial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PbrMaterial")
+            .field("albedo", &"<Texture>")
+            .field("roughness", &"<Texture>")
+            .field("metalness", &"<Texture>")
+            .field("normal_map", &self.normal_map.as_ref().map(|_| "<Texture>"))
+            .finish()
+    }
+}
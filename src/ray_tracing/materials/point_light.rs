@@ -0,0 +1,90 @@
+use super::material::{Material, ScatterRecord};
+use super::texture::{SolidColor, TexturePtr};
+use crate::ray_tracing::geometry::hittable::HitRecord;
+use crate::ray_tracing::lights::falloff::Falloff;
+use crate::ray_tracing::lights::ies::IesProfile;
+use crate::ray_tracing::math::onb::ONB;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use std::sync::Arc;
+
+/// 点光源材质：放在小球上使用，默认向各方向均匀发光，可选 IES 配光曲线和距离衰减控制
+///
+/// 使用配光曲线时，发光辐射度按曲线相对最大强度的比例缩放，即 emit 为最亮方向上的辐射度。
+pub struct PointLight {
+    emit: TexturePtr,
+    profile: Option<(Arc<IesProfile>, ONB)>,
+    falloff: Falloff,
+}
+
+impl PointLight {
+    /// 创建点光源
+    #[inline]
+    pub fn new(emit: TexturePtr) -> Self {
+        Self {
+            emit,
+            profile: None,
+            falloff: Falloff::Quadratic,
+        }
+    }
+
+    /// 从纯色创建点光源
+    #[inline]
+    pub fn new_color(color: Color) -> Self {
+        Self::new(Arc::new(SolidColor::new(color)))
+    }
+
+    /// 设置配光曲线，nadir 为灯具的正下方（垂直角 0°）方向
+    ///
+    /// 水平角 0° 对应以 nadir 建立的正交基的 u 轴。
+    #[inline]
+    pub fn with_profile(mut self, profile: Arc<IesProfile>, nadir: Vec3) -> Self {
+        self.profile = Some((profile, ONB::new(&nadir)));
+        self
+    }
+
+    /// 设置距离衰减方式
+    #[inline]
+    pub fn with_falloff(mut self, falloff: Falloff) -> Self {
+        self.falloff = falloff;
+        self
+    }
+}
+
+impl Material for PointLight {
+    #[inline]
+    fn scatter(&self, _r_in: &Ray, _rec: &HitRecord, _srec: &mut ScatterRecord) -> bool {
+        false
+    }
+
+    #[inline]
+    fn emitted(&self, u: f64, v: f64, p: &Point3) -> Color {
+        self.emit.value(u, v, p)
+    }
+
+    fn emitted_towards(&self, r_in: &Ray, rec: &HitRecord) -> Color {
+        let mut factor = self.falloff.factor(rec.t * r_in.dir.norm());
+        if let Some((profile, frame)) = &self.profile {
+            factor *= profile_factor(profile, frame, &-r_in.dir);
+        }
+        self.emitted(rec.u, rec.v, &rec.p) * factor
+    }
+}
+
+/// 配光曲线在出射方向上的归一化强度，frame 的 w 轴为天底方向
+pub(crate) fn profile_factor(profile: &IesProfile, frame: &ONB, direction: &Vec3) -> f64 {
+    let local = frame.world_to_local(&direction.normalize());
+    let theta = local.z.clamp(-1.0, 1.0).acos().to_degrees();
+    let phi = local.y.atan2(local.x).to_degrees();
+    profile.normalized(theta, phi)
+}
+
+impl std::fmt::Debug for PointLight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PointLight")
+            .field("emit", &"<Texture>")
+            .field("nadir", &self.profile.as_ref().map(|(_, frame)| frame.w()))
+            .field("falloff", &self.falloff)
+            .finish()
+    }
+}
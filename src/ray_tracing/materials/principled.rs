@@ -0,0 +1,311 @@
+use super::material::{Material, ScatterRecord};
+use super::microfacet::{
+    fresnel_dielectric, fresnel_schlick, ggx_d, ggx_dielectric_bsdf_cos, gtr1_d,
+    roughness_to_alpha, smith_g,
+};
+use super::texture::{Texture, TextureKind, TexturePtr};
+use crate::ray_tracing::geometry::hittable::HitRecord;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::rendering::color::luminance;
+use crate::ray_tracing::sampling::pdf::principled_pdf::LobeWeights;
+use crate::ray_tracing::sampling::pdf::{GgxDielectricPDF, PrincipledPDF};
+use std::f64::consts::PI;
+
+/// 清漆层遮蔽项使用的固定粗糙度（Disney 模型的取值）
+const CLEARCOAT_G_ALPHA: f64 = 0.25;
+/// 清漆层的垂直入射反射率（折射率 1.5）
+const CLEARCOAT_F0: f64 = 0.04;
+
+/// Disney 原则化材质（Burley 2012/2015）：一组直观参数覆盖塑料、金属、织物、车漆和玻璃
+///
+/// 由 Burley 漫反射、光泽层（sheen）、GGX 镜面反射、GTR1 清漆层和 GGX 微表面透射组成，
+/// 各波瓣按其在观察方向上的反射率加权混合采样（见 [`PrincipledPDF`]）。
+/// 基础色、粗糙度和金属度可以是纹理，其余参数为常量。透射的颜色在每次穿过界面时乘以
+/// 基础色的平方根，穿过物体（进出两次）后整体乘以基础色；透射物体内部的背面按粗糙电介质处理。
+pub struct PrincipledMaterial {
+    base_color: TextureKind,
+    roughness: TextureKind,
+    metallic: TextureKind,
+    specular: f64,
+    specular_tint: f64,
+    sheen: f64,
+    sheen_tint: f64,
+    clearcoat: f64,
+    clearcoat_gloss: f64,
+    transmission: f64,
+    refraction_index: f64,
+}
+
+/// 交点处求值后的参数
+struct Lobes {
+    base: Color,
+    roughness: f64,
+    alpha: f64,
+    dielectric_f0: Color,
+    specular_f0: Color,
+    sheen: Color,
+    diffuse_weight: f64,
+    transmission_weight: f64,
+}
+
+impl PrincipledMaterial {
+    /// 从基础色、粗糙度和金属度纹理创建，其余参数取 Disney 模型的默认值
+    #[inline]
+    pub fn new(base_color: TexturePtr, roughness: TexturePtr, metallic: TexturePtr) -> Self {
+        Self::from_kinds(base_color.into(), roughness.into(), metallic.into())
+    }
+
+    /// 从常量参数创建
+    #[inline]
+    pub fn new_constant(base_color: Color, roughness: f64, metallic: f64) -> Self {
+        Self::from_kinds(
+            TextureKind::solid(base_color),
+            TextureKind::solid(Color::repeat(roughness)),
+            TextureKind::solid(Color::repeat(metallic)),
+        )
+    }
+
+    fn from_kinds(base_color: TextureKind, roughness: TextureKind, metallic: TextureKind) -> Self {
+        Self {
+            base_color,
+            roughness,
+            metallic,
+            specular: 0.5,
+            specular_tint: 0.0,
+            sheen: 0.0,
+            sheen_tint: 0.5,
+            clearcoat: 0.0,
+            clearcoat_gloss: 1.0,
+            transmission: 0.0,
+            refraction_index: 1.5,
+        }
+    }
+
+    /// 设置非金属的镜面反射强度（0.5 对应 4% 的垂直入射反射率）和向基础色着色的程度
+    #[inline]
+    pub fn with_specular(mut self, specular: f64, tint: f64) -> Self {
+        self.specular = specular.max(0.0);
+        self.specular_tint = tint.clamp(0.0, 1.0);
+        self
+    }
+
+    /// 设置光泽层（掠射角处的绒面反光）强度和向基础色着色的程度
+    #[inline]
+    pub fn with_sheen(mut self, sheen: f64, tint: f64) -> Self {
+        self.sheen = sheen.max(0.0);
+        self.sheen_tint = tint.clamp(0.0, 1.0);
+        self
+    }
+
+    /// 设置清漆层强度（0~1）和光泽度（1 为最光滑）
+    #[inline]
+    pub fn with_clearcoat(mut self, clearcoat: f64, gloss: f64) -> Self {
+        self.clearcoat = clearcoat.clamp(0.0, 1.0);
+        self.clearcoat_gloss = gloss.clamp(0.0, 1.0);
+        self
+    }
+
+    /// 设置透射比例（0 为不透明，1 为完全透射的玻璃）和折射率
+    #[inline]
+    pub fn with_transmission(mut self, transmission: f64, refraction_index: f64) -> Self {
+        self.transmission = transmission.clamp(0.0, 1.0);
+        self.refraction_index = refraction_index;
+        self
+    }
+
+    /// 折射率
+    #[inline]
+    pub fn refraction_index(&self) -> f64 {
+        self.refraction_index
+    }
+
+    /// 清漆层 GTR1 分布的粗糙度参数
+    #[inline]
+    fn clearcoat_alpha(&self) -> f64 {
+        0.1 + (0.001 - 0.1) * self.clearcoat_gloss
+    }
+
+    /// 是否从透射物体的内部击中背面
+    #[inline]
+    fn inside(&self, rec: &HitRecord) -> bool {
+        !rec.front_face && self.transmission > 0.0
+    }
+
+    /// 透射侧与入射侧折射率之比
+    #[inline]
+    fn eta(&self, rec: &HitRecord) -> f64 {
+        if self.inside(rec) {
+            1.0 / self.refraction_index
+        } else {
+            self.refraction_index
+        }
+    }
+
+    /// 在交点处求纹理参数
+    fn lobes(&self, r_in: &Ray, rec: &HitRecord) -> Lobes {
+        let base = self.base_color.value_at_time(
+            rec.u,
+            rec.v,
+            &rec.p,
+            &rec.normal,
+            rec.footprint.as_ref(),
+            r_in.time,
+        );
+        let roughness = self.roughness.scalar(rec.u, rec.v, &rec.p).clamp(0.0, 1.0);
+        let metallic = self.metallic.scalar(rec.u, rec.v, &rec.p).clamp(0.0, 1.0);
+
+        // 基础色按亮度归一化后的色调，用于镜面反射和光泽层的着色
+        let lum = luminance(&base);
+        let tint = if lum > 0.0 {
+            base / lum
+        } else {
+            Color::repeat(1.0)
+        };
+        let white = Color::repeat(1.0);
+        let dielectric_f0 = self.specular * 0.08 * (white + self.specular_tint * (tint - white));
+        Lobes {
+            base,
+            roughness,
+            alpha: roughness_to_alpha(roughness),
+            dielectric_f0,
+            specular_f0: dielectric_f0 + metallic * (base - dielectric_f0),
+            sheen: self.sheen * (white + self.sheen_tint * (tint - white)),
+            diffuse_weight: (1.0 - metallic) * (1.0 - self.transmission),
+            transmission_weight: (1.0 - metallic) * self.transmission,
+        }
+    }
+
+    /// 正面（或不透射物体）的 BSDF·|cosθ|
+    fn eval(&self, lobes: &Lobes, wo: &Vec3, wi: &Vec3, n: &Vec3) -> Color {
+        let cos_o = wo.dot(n);
+        let cos_i = wi.dot(n);
+        if cos_o <= 0.0 {
+            return Color::zeros();
+        }
+
+        if cos_i < 0.0 {
+            if lobes.transmission_weight <= 0.0 {
+                return Color::zeros();
+            }
+            let transmitted =
+                ggx_dielectric_bsdf_cos(wo, wi, n, self.refraction_index, lobes.alpha);
+            return lobes.transmission_weight * transmitted * lobes.base.map(f64::sqrt);
+        }
+
+        let h = wo + wi;
+        if h.norm_squared() < 1e-16 {
+            return Color::zeros();
+        }
+        let h = h.normalize();
+        let cos_d = wi.dot(&h);
+        let cos_h = h.dot(n);
+
+        // Burley 漫反射和光泽层
+        let schlick_weight = |cos: f64| (1.0 - cos.clamp(0.0, 1.0)).powi(5);
+        let fd90 = 0.5 + 2.0 * lobes.roughness * cos_d * cos_d;
+        let retro = (1.0 + (fd90 - 1.0) * schlick_weight(cos_i))
+            * (1.0 + (fd90 - 1.0) * schlick_weight(cos_o));
+        let diffuse = lobes.base * (retro / PI) + lobes.sheen * schlick_weight(cos_d);
+        // 镜面反射掉的能量不再进入漫反射层（Disney 原模型不做此修正，白色材质会略亮于入射光）
+        let diffuse = diffuse * (1.0 - luminance(&fresnel_schlick(lobes.dielectric_f0, cos_d)));
+
+        // GGX 镜面反射
+        let g = smith_g(wo, wi, n, &h, lobes.alpha);
+        let specular = fresnel_schlick(lobes.specular_f0, cos_d)
+            * (ggx_d(cos_h, lobes.alpha) * g / (4.0 * cos_o));
+
+        // GTR1 清漆层
+        let coat = if self.clearcoat > 0.0 {
+            let f = fresnel_schlick(Color::repeat(CLEARCOAT_F0), cos_d).x;
+            let g = smith_g(wo, wi, n, &h, CLEARCOAT_G_ALPHA);
+            0.25 * self.clearcoat * f * gtr1_d(cos_h, self.clearcoat_alpha()) * g / (4.0 * cos_o)
+        } else {
+            0.0
+        };
+
+        lobes.diffuse_weight * diffuse * cos_i + specular + Color::repeat(coat)
+    }
+
+    /// 按各波瓣在观察方向上的反射率分配采样概率
+    fn lobe_weights(&self, lobes: &Lobes, cos_o: f64) -> LobeWeights {
+        let specular = luminance(&fresnel_schlick(lobes.specular_f0, cos_o));
+        LobeWeights {
+            diffuse: lobes.diffuse_weight * (luminance(&lobes.base) + luminance(&lobes.sheen)),
+            specular,
+            clearcoat: 0.25
+                * self.clearcoat
+                * fresnel_schlick(Color::repeat(CLEARCOAT_F0), cos_o).x,
+            transmission: lobes.transmission_weight
+                * (1.0 - fresnel_dielectric(cos_o, self.refraction_index)),
+        }
+    }
+}
+
+impl Material for PrincipledMaterial {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        let lobes = self.lobes(r_in, rec);
+        let wo = -r_in.dir.normalize();
+        if self.inside(rec) {
+            let pdf = GgxDielectricPDF::new(&wo, &rec.normal, lobes.alpha, self.eta(rec));
+            srec.set_diffuse(lobes.base, pdf);
+            return true;
+        }
+
+        let weights = self.lobe_weights(&lobes, wo.dot(&rec.normal));
+        let pdf = PrincipledPDF::new(
+            &wo,
+            &rec.normal,
+            lobes.alpha,
+            self.clearcoat_alpha(),
+            self.refraction_index,
+            weights,
+        );
+        srec.set_diffuse(lobes.base, pdf);
+        true
+    }
+
+    /// 各波瓣之和的亮度（供调试显示）
+    fn scattering_pdf(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        luminance(&self.bsdf_cos(r_in, rec, &ScatterRecord::default(), scattered))
+    }
+
+    fn bsdf_cos(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        _srec: &ScatterRecord,
+        scattered: &Ray,
+    ) -> Color {
+        let lobes = self.lobes(r_in, rec);
+        let wo = -r_in.dir.normalize();
+        let wi = scattered.dir.normalize();
+        if self.inside(rec) {
+            let value = ggx_dielectric_bsdf_cos(&wo, &wi, &rec.normal, self.eta(rec), lobes.alpha);
+            return if wi.dot(&rec.normal) < 0.0 {
+                value * lobes.base.map(f64::sqrt)
+            } else {
+                Color::repeat(value)
+            };
+        }
+        self.eval(&lobes, &wo, &wi, &rec.normal)
+    }
+}
+
+impl std::fmt::Debug for PrincipledMaterial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrincipledMaterial")
+            .field("base_color", &"<Texture>")
+            .field("roughness", &"<Texture>")
+            .field("metallic", &"<Texture>")
+            .field("specular", &self.specular)
+            .field("specular_tint", &self.specular_tint)
+            .field("sheen", &self.sheen)
+            .field("sheen_tint", &self.sheen_tint)
+            .field("clearcoat", &self.clearcoat)
+            .field("clearcoat_gloss", &self.clearcoat_gloss)
+            .field("transmission", &self.transmission)
+            .field("refraction_index", &self.refraction_index)
+            .finish()
+    }
+}
@@ -0,0 +1,50 @@
+use super::material::{Material, ScatterRecord};
+use super::microfacet::{ggx_dielectric_bsdf_cos, roughness_to_alpha};
+use crate::ray_tracing::geometry::hittable::HitRecord;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::sampling::pdf::GgxDielectricPDF;
+
+/// 粗糙电介质材质（磨砂玻璃），GGX 微表面反射与透射
+#[derive(Debug)]
+pub struct RoughDielectric {
+    refraction_index: f64,
+    alpha: f64,
+}
+
+impl RoughDielectric {
+    /// 从折射率和粗糙度（0为光滑，1为非常粗糙）创建
+    #[inline]
+    pub fn new(refraction_index: f64, roughness: f64) -> Self {
+        Self {
+            refraction_index,
+            alpha: roughness_to_alpha(roughness.clamp(0.0, 1.0)),
+        }
+    }
+
+    /// 透射侧与入射侧折射率之比
+    #[inline]
+    fn eta(&self, rec: &HitRecord) -> f64 {
+        if rec.front_face {
+            self.refraction_index
+        } else {
+            1.0 / self.refraction_index
+        }
+    }
+}
+
+impl Material for RoughDielectric {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        let wo = -r_in.dir.normalize();
+        let pdf = GgxDielectricPDF::new(&wo, &rec.normal, self.alpha, self.eta(rec));
+        srec.set_diffuse(Color::new(1.0, 1.0, 1.0), pdf);
+        true
+    }
+
+    /// 返回 BSDF·|cos θ|（与光滑的 Dielectric 一致，折射项不做 η² 缩放）
+    fn scattering_pdf(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        let wo = -r_in.dir.normalize();
+        let wi = scattered.dir.normalize();
+        ggx_dielectric_bsdf_cos(&wo, &wi, &rec.normal, self.eta(rec), self.alpha)
+    }
+}
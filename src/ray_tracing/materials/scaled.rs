@@ -0,0 +1,173 @@
+use super::material::{Material, ScatterRecord};
+use crate::ray_tracing::geometry::hittable::HitRecord;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use std::sync::Arc;
+
+/// 按常数缩放基础材质的散射衰减和发射辐射度
+///
+/// 用于重要性采样中的权重修正，或整体调节光源强度。
+pub struct Scaled {
+    base: Arc<dyn Material>,
+    scale: f64,
+}
+
+impl Scaled {
+    #[inline]
+    pub fn new(base: Arc<dyn Material>, scale: f64) -> Self {
+        Self { base, scale }
+    }
+}
+
+impl Material for Scaled {
+    #[inline]
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        if !self.base.scatter(r_in, rec, srec) {
+            return false;
+        }
+        srec.attenuation *= self.scale;
+        true
+    }
+
+    #[inline]
+    fn emitted(&self, u: f64, v: f64, p: &Point3) -> Color {
+        self.scale * self.base.emitted(u, v, p)
+    }
+
+    #[inline]
+    fn emitted_towards(&self, r_in: &Ray, rec: &HitRecord) -> Color {
+        self.scale * self.base.emitted_towards(r_in, rec)
+    }
+
+    #[inline]
+    fn scattering_pdf(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        self.base.scattering_pdf(r_in, rec, scattered)
+    }
+
+    /// 基础材质按缩放前的衰减求值后再整体缩放，使自行组合多个波瓣的基础材质也被正确缩放
+    fn bsdf_cos(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        srec: &ScatterRecord,
+        scattered: &Ray,
+    ) -> Color {
+        if self.scale == 0.0 {
+            return Color::zeros();
+        }
+        let base = ScatterRecord {
+            attenuation: srec.attenuation / self.scale,
+            ..srec.clone()
+        };
+        self.scale * self.base.bsdf_cos(r_in, rec, &base, scattered)
+    }
+
+    #[inline]
+    fn alpha(&self, u: f64, v: f64, p: &Point3) -> f64 {
+        self.base.alpha(u, v, p)
+    }
+
+    #[inline]
+    fn shadow_transmittance(&self, r: &Ray, rec: &HitRecord) -> Option<Color> {
+        self.base
+            .shadow_transmittance(r, rec)
+            .map(|t| t * self.scale)
+    }
+
+    #[inline]
+    fn is_diffuse(&self) -> bool {
+        self.base.is_diffuse()
+    }
+}
+
+/// 按命中记录中的 `weight` 缩放基础材质的散射衰减和发射辐射度
+///
+/// 权重随每次命中变化时（如介质的引导距离采样）使用：包装材质只创建一次，
+/// 命中时写入权重即可，不必为每次散射分配新的 `Scaled`。没有命中记录的 `emitted` 不缩放。
+pub struct Weighted {
+    base: Arc<dyn Material>,
+}
+
+impl Weighted {
+    #[inline]
+    pub fn new(base: Arc<dyn Material>) -> Self {
+        Self { base }
+    }
+}
+
+impl Material for Weighted {
+    #[inline]
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        if !self.base.scatter(r_in, rec, srec) {
+            return false;
+        }
+        srec.attenuation *= rec.weight;
+        true
+    }
+
+    #[inline]
+    fn emitted(&self, u: f64, v: f64, p: &Point3) -> Color {
+        self.base.emitted(u, v, p)
+    }
+
+    #[inline]
+    fn emitted_towards(&self, r_in: &Ray, rec: &HitRecord) -> Color {
+        rec.weight * self.base.emitted_towards(r_in, rec)
+    }
+
+    #[inline]
+    fn scattering_pdf(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        self.base.scattering_pdf(r_in, rec, scattered)
+    }
+
+    fn bsdf_cos(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        srec: &ScatterRecord,
+        scattered: &Ray,
+    ) -> Color {
+        if rec.weight == 0.0 {
+            return Color::zeros();
+        }
+        let base = ScatterRecord {
+            attenuation: srec.attenuation / rec.weight,
+            ..srec.clone()
+        };
+        rec.weight * self.base.bsdf_cos(r_in, rec, &base, scattered)
+    }
+
+    #[inline]
+    fn alpha(&self, u: f64, v: f64, p: &Point3) -> f64 {
+        self.base.alpha(u, v, p)
+    }
+
+    #[inline]
+    fn shadow_transmittance(&self, r: &Ray, rec: &HitRecord) -> Option<Color> {
+        self.base
+            .shadow_transmittance(r, rec)
+            .map(|t| t * rec.weight)
+    }
+
+    #[inline]
+    fn is_diffuse(&self) -> bool {
+        self.base.is_diffuse()
+    }
+}
+
+impl std::fmt::Debug for Weighted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Weighted")
+            .field("base", &self.base)
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for Scaled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scaled")
+            .field("base", &self.base)
+            .field("scale", &self.scale)
+            .finish()
+    }
+}
@@ -0,0 +1,136 @@
+use super::material::{Material, ScatterRecord};
+use super::point_light::profile_factor;
+use super::texture::{SolidColor, TexturePtr};
+use crate::ray_tracing::geometry::hittable::HitRecord;
+use crate::ray_tracing::lights::falloff::Falloff;
+use crate::ray_tracing::lights::ies::IesProfile;
+use crate::ray_tracing::math::onb::ONB;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::random::degrees_to_radians;
+use std::sync::Arc;
+
+/// 聚光灯材质：只向主方向的圆锥内发光，可选用图案纹理（gobo）调制投射的光
+///
+/// 图案纹理按出射方向投影：圆锥外边缘对应纹理坐标 [0,1]² 的内切圆。
+/// 设置 IES 配光曲线后，圆锥内的强度再按曲线（以光照主方向为天底）调制。
+pub struct SpotLight {
+    emit: TexturePtr,
+    frame: ONB,
+    cos_inner: f64,
+    cos_outer: f64,
+    tan_outer: f64,
+    gobo: Option<TexturePtr>,
+    profile: Option<Arc<IesProfile>>,
+    falloff: Falloff,
+}
+
+impl SpotLight {
+    /// 创建聚光灯，direction 为光照主方向，inner/outer 为全亮区与截止区的半角（度）
+    #[inline]
+    pub fn new(emit: TexturePtr, direction: Vec3, inner_angle: f64, outer_angle: f64) -> Self {
+        let outer = degrees_to_radians(outer_angle.clamp(0.01, 89.0));
+        let inner = degrees_to_radians(inner_angle).clamp(0.0, outer);
+        Self {
+            emit,
+            frame: ONB::new(&direction),
+            cos_inner: inner.cos(),
+            cos_outer: outer.cos(),
+            tan_outer: outer.tan(),
+            gobo: None,
+            profile: None,
+            falloff: Falloff::Quadratic,
+        }
+    }
+
+    /// 从纯色创建聚光灯
+    #[inline]
+    pub fn new_color(color: Color, direction: Vec3, inner_angle: f64, outer_angle: f64) -> Self {
+        Self::new(
+            Arc::new(SolidColor::new(color)),
+            direction,
+            inner_angle,
+            outer_angle,
+        )
+    }
+
+    /// 设置图案纹理（如百叶窗、彩色玻璃）
+    #[inline]
+    pub fn with_gobo(mut self, gobo: TexturePtr) -> Self {
+        self.gobo = Some(gobo);
+        self
+    }
+
+    /// 设置 IES 配光曲线，垂直角 0° 为光照主方向
+    #[inline]
+    pub fn with_profile(mut self, profile: Arc<IesProfile>) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+
+    /// 设置距离衰减方式
+    #[inline]
+    pub fn with_falloff(mut self, falloff: Falloff) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    /// 出射方向上的强度系数（含边缘平滑衰减和图案调制）
+    fn directional_factor(&self, direction: &Vec3, p: &Point3) -> Color {
+        let local = self.frame.world_to_local(&direction.normalize());
+        let cos_theta = local.z;
+        if cos_theta <= self.cos_outer {
+            return Color::zeros();
+        }
+
+        let t = ((cos_theta - self.cos_outer) / (self.cos_inner - self.cos_outer).max(1e-9))
+            .clamp(0.0, 1.0);
+        let mut falloff = t * t * (3.0 - 2.0 * t);
+        if let Some(profile) = &self.profile {
+            falloff *= profile_factor(profile, &self.frame, direction);
+        }
+
+        match &self.gobo {
+            Some(gobo) => {
+                let u = 0.5 + 0.5 * local.x / (cos_theta * self.tan_outer);
+                let v = 0.5 + 0.5 * local.y / (cos_theta * self.tan_outer);
+                falloff * gobo.value(u, v, p)
+            }
+            None => Color::new(falloff, falloff, falloff),
+        }
+    }
+}
+
+impl Material for SpotLight {
+    #[inline]
+    fn scatter(&self, _r_in: &Ray, _rec: &HitRecord, _srec: &mut ScatterRecord) -> bool {
+        false
+    }
+
+    #[inline]
+    fn emitted(&self, u: f64, v: f64, p: &Point3) -> Color {
+        self.emit.value(u, v, p)
+    }
+
+    fn emitted_towards(&self, r_in: &Ray, rec: &HitRecord) -> Color {
+        let outgoing = -r_in.dir;
+        let distance = rec.t * r_in.dir.norm();
+        self.emitted(rec.u, rec.v, &rec.p)
+            .component_mul(&self.directional_factor(&outgoing, &rec.p))
+            * self.falloff.factor(distance)
+    }
+}
+
+impl std::fmt::Debug for SpotLight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpotLight")
+            .field("emit", &"<Texture>")
+            .field("direction", &self.frame.w())
+            .field("cos_inner", &self.cos_inner)
+            .field("cos_outer", &self.cos_outer)
+            .field("gobo", &self.gobo.as_ref().map(|_| "<Texture>"))
+            .field("profile", &self.profile.is_some())
+            .field("falloff", &self.falloff)
+            .finish()
+    }
+}
@@ -1,10 +1,12 @@
 use super::{SolidColor, Texture};
-use crate::ray_tracing::math::vec3::{Color, Point3};
+use crate::ray_tracing::math::differential::TextureFootprint;
+use crate::ray_tracing::math::vec3::{Color, Point3, Vec3};
 use std::sync::Arc;
 
 /// 棋盘格纹理
 #[derive(Debug)]
 pub struct CheckerTexture {
+    scale: f64,
     inv_scale: f64,
     even: Arc<dyn Texture>,
     odd: Arc<dyn Texture>,
@@ -15,6 +17,7 @@ impl CheckerTexture {
     #[inline]
     pub fn new(scale: f64, even: Arc<dyn Texture>, odd: Arc<dyn Texture>) -> Self {
         Self {
+            scale,
             inv_scale: 1.0 / scale,
             even,
             odd,
@@ -24,11 +27,11 @@ impl CheckerTexture {
     /// 从两个颜色创建棋盘格纹理
     #[inline]
     pub fn new_colors(scale: f64, c1: Color, c2: Color) -> Self {
-        Self {
-            inv_scale: 1.0 / scale,
-            even: Arc::new(SolidColor::new(c1)),
-            odd: Arc::new(SolidColor::new(c2)),
-        }
+        Self::new(
+            scale,
+            Arc::new(SolidColor::new(c1)),
+            Arc::new(SolidColor::new(c2)),
+        )
     }
 
     /// 从RGB值创建棋盘格纹理
@@ -36,6 +39,39 @@ impl CheckerTexture {
     pub fn new_rgb(scale: f64, r1: f64, g1: f64, b1: f64, r2: f64, g2: f64, b2: f64) -> Self {
         Self::new_colors(scale, Color::new(r1, g1, b1), Color::new(r2, g2, b2))
     }
+
+    /// 格子边长
+    #[inline]
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// 坐标和为偶数的格子使用的纹理
+    #[inline]
+    pub fn even(&self) -> &Arc<dyn Texture> {
+        &self.even
+    }
+
+    /// 坐标和为奇数的格子使用的纹理
+    #[inline]
+    pub fn odd(&self) -> &Arc<dyn Texture> {
+        &self.odd
+    }
+
+    /// 方波 (-1)^floor(x) 在以 x 为中心、宽度为 width 的区间上的盒式滤波平均值
+    #[inline]
+    fn filtered_square_wave(x: f64, width: f64) -> f64 {
+        if width < 1e-6 {
+            return if x.floor().rem_euclid(2.0) == 0.0 {
+                1.0
+            } else {
+                -1.0
+            };
+        }
+        // 方波的积分是周期为2的三角波
+        let integral = |x: f64| 1.0 - (x.rem_euclid(2.0) - 1.0).abs();
+        (integral(x + 0.5 * width) - integral(x - 0.5 * width)) / width
+    }
 }
 
 impl Texture for CheckerTexture {
@@ -54,4 +90,35 @@ impl Texture for CheckerTexture {
             self.odd.value(u, v, p)
         }
     }
+
+    /// 三个轴向的方波分别盒式滤波后相乘，得到偶数格的覆盖比例
+    fn value_filtered(
+        &self,
+        u: f64,
+        v: f64,
+        p: &Point3,
+        normal: &Vec3,
+        footprint: Option<&TextureFootprint>,
+    ) -> Color {
+        let Some(fp) = footprint else {
+            return self.value_with_normal(u, v, p, normal);
+        };
+
+        let sign: f64 = (0..3)
+            .map(|axis| {
+                let width = 2.0 * fp.dpdx[axis].abs().max(fp.dpdy[axis].abs()) * self.inv_scale;
+                Self::filtered_square_wave(self.inv_scale * p[axis], width)
+            })
+            .product();
+        let even_weight = 0.5 * (1.0 + sign);
+
+        let mut color = Color::zeros();
+        if even_weight > 0.0 {
+            color += even_weight * self.even.value_filtered(u, v, p, normal, footprint);
+        }
+        if even_weight < 1.0 {
+            color += (1.0 - even_weight) * self.odd.value_filtered(u, v, p, normal, footprint);
+        }
+        color
+    }
 }
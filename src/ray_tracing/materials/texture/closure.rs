@@ -0,0 +1,49 @@
+use super::Texture;
+use crate::ray_tracing::math::vec3::{Color, Point3};
+
+/// 由闭包定义的程序化纹理，无需定义新类型并实现 `Texture` 即可试验纹理
+///
+/// 闭包接收纹理坐标 (u, v) 和交点位置，返回颜色（见 `examples/custom_material.rs` 中的地面网格）。
+pub struct ClosureTexture<F> {
+    f: F,
+    name: &'static str,
+}
+
+impl<F> ClosureTexture<F>
+where
+    F: Fn(f64, f64, &Point3) -> Color + Send + Sync + 'static,
+{
+    /// 从闭包创建纹理
+    #[inline]
+    pub fn new(f: F) -> Self {
+        Self {
+            f,
+            name: "<closure>",
+        }
+    }
+
+    /// 设置调试输出中显示的名称
+    #[inline]
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+}
+
+impl<F> Texture for ClosureTexture<F>
+where
+    F: Fn(f64, f64, &Point3) -> Color + Send + Sync + 'static,
+{
+    #[inline]
+    fn value(&self, u: f64, v: f64, p: &Point3) -> Color {
+        (self.f)(u, v, p)
+    }
+}
+
+impl<F> std::fmt::Debug for ClosureTexture<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClosureTexture")
+            .field("name", &self.name)
+            .finish()
+    }
+}
@@ -1,58 +1,196 @@
 use super::Texture;
-use crate::ray_tracing::math::vec3::{Color, Point3};
-use image::{DynamicImage, GenericImageView};
+use crate::ray_tracing::math::differential::TextureFootprint;
+use crate::ray_tracing::math::vec3::{Color, Point3, Vec3};
+use crate::ray_tracing::rendering::color_space::TextureColorSpace;
+use crate::ray_tracing::utils::config;
+use image::{DynamicImage, ImageDecoder, ImageReader};
+use std::path::Path;
+
+/// mip 级别（线性 [0,1] 颜色）
+#[derive(Debug)]
+struct MipLevel {
+    width: usize,
+    height: usize,
+    data: Vec<Color>,
+}
+
+impl MipLevel {
+    /// 2×2 盒式滤波缩小一级，奇数尺寸时边缘纹素复用
+    fn downsample(&self) -> Self {
+        let width = (self.width / 2).max(1);
+        let height = (self.height / 2).max(1);
+        let mut data = Vec::with_capacity(width * height);
+        for j in 0..height {
+            for i in 0..width {
+                let (x0, y0) = ((2 * i).min(self.width - 1), (2 * j).min(self.height - 1));
+                let (x1, y1) = ((x0 + 1).min(self.width - 1), (y0 + 1).min(self.height - 1));
+                let sum = self.texel(x0, y0)
+                    + self.texel(x1, y0)
+                    + self.texel(x0, y1)
+                    + self.texel(x1, y1);
+                data.push(sum * 0.25);
+            }
+        }
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    #[inline]
+    fn texel(&self, i: usize, j: usize) -> Color {
+        self.data[j * self.width + i]
+    }
+
+    /// 双线性采样，(x, y) 为图像坐标（v 已翻转），边缘截断
+    fn bilinear(&self, x: f64, y: f64) -> Color {
+        let x = (x * self.width as f64 - 0.5).clamp(0.0, (self.width - 1) as f64);
+        let y = (y * self.height as f64 - 0.5).clamp(0.0, (self.height - 1) as f64);
+        let (i0, j0) = (x.floor() as usize, y.floor() as usize);
+        let (i1, j1) = ((i0 + 1).min(self.width - 1), (j0 + 1).min(self.height - 1));
+        let (fx, fy) = (x - i0 as f64, y - j0 as f64);
+
+        let top = self.texel(i0, j0) * (1.0 - fx) + self.texel(i1, j0) * fx;
+        let bottom = self.texel(i0, j1) * (1.0 - fx) + self.texel(i1, j1) * fx;
+        top * (1.0 - fy) + bottom * fy
+    }
+}
+
 /// 图像纹理
+///
+/// 加载时按纹理的色彩空间把像素转换为线性颜色并生成 mip 链：没有纹理足迹时按最近邻采样原图，
+/// 有足迹时在相邻 mip 级别间三线性插值。
 #[derive(Debug)]
 pub struct ImageTexture {
-    image: Option<DynamicImage>,
     width: u32,
     height: u32,
+    color_space: TextureColorSpace,
+    mips: Vec<MipLevel>,
+    // 加载时给出的文件名（未经搜索路径解析），从内存图像创建时为None
+    source: Option<String>,
 }
 
 impl ImageTexture {
-    /// 从文件加载图像纹理
+    /// 从文件加载图像纹理，按全局配置的纹理搜索路径查找，使用配置中的默认纹理色彩空间
     #[inline]
     pub fn new(image_filename: &str) -> Self {
-        // 尝试多个可能的路径
-        let paths = [
-            image_filename,
-            &format!("textures/{}", image_filename),
-            &format!("../textures/{}", image_filename),
-        ];
-
-        for path in &paths {
-            if let Ok(img) = image::open(path) {
-                return Self::from_image(img);
-            }
-        }
+        Self::with_color_space(image_filename, config::global().texture_color_space)
+    }
 
-        // 检查环境变量
-        if let Ok(image_dir) = std::env::var("RTW_IMAGES") {
-            let full_path = format!("{}/{}", image_dir, image_filename);
-            if let Ok(img) = image::open(&full_path) {
-                return Self::from_image(img);
-            }
+    /// 从文件加载图像纹理并声明其色彩空间
+    ///
+    /// 声明为 sRGB 的图像若嵌入了 Display P3 的 ICC 配置文件，按 Display P3 转换。
+    pub fn with_color_space(image_filename: &str, color_space: TextureColorSpace) -> Self {
+        if let Some(path) = config::global().resolve_texture(image_filename)
+            && let Ok((img, icc)) = load_with_icc(&path)
+        {
+            let detected = icc.as_deref().and_then(TextureColorSpace::from_icc_profile);
+            let color_space = match (color_space, detected) {
+                (TextureColorSpace::Srgb, Some(TextureColorSpace::DisplayP3)) => {
+                    eprintln!(
+                        "纹理 '{}' 嵌入了 Display P3 配置文件，按 Display P3 转换",
+                        image_filename
+                    );
+                    TextureColorSpace::DisplayP3
+                }
+                _ => color_space,
+            };
+            return Self {
+                source: Some(image_filename.to_string()),
+                ..Self::from_image_with_color_space(img, color_space)
+            };
         }
 
         eprintln!("ERROR: Could not load image file '{}'.", image_filename);
         Self {
-            image: None,
             width: 0,
             height: 0,
+            color_space,
+            mips: Vec::new(),
+            source: Some(image_filename.to_string()),
         }
     }
 
-    /// 从图像对象创建纹理
+    /// 从图像对象创建纹理（像素值按线性颜色使用）
     #[inline]
-    fn from_image(img: DynamicImage) -> Self {
+    pub fn from_image(img: DynamicImage) -> Self {
+        Self::from_image_with_color_space(img, TextureColorSpace::Linear)
+    }
+
+    /// 从图像对象创建纹理，像素按指定色彩空间转换为线性颜色
+    pub fn from_image_with_color_space(img: DynamicImage, color_space: TextureColorSpace) -> Self {
         let width = img.width();
         let height = img.height();
+        let mips = Self::build_mips(&img, color_space);
         Self {
-            image: Some(img),
             width,
             height,
+            color_space,
+            mips,
+            source: None,
         }
     }
+
+    /// 纹理数据的色彩空间
+    #[inline]
+    pub fn color_space(&self) -> TextureColorSpace {
+        self.color_space
+    }
+
+    /// 加载时给出的文件名，从内存图像创建的纹理为None
+    #[inline]
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    /// 生成完整的 mip 链（直到 1×1），在线性空间中滤波
+    fn build_mips(img: &DynamicImage, color_space: TextureColorSpace) -> Vec<MipLevel> {
+        let rgb = img.to_rgb8();
+        // 8位数据只有256个取值，预先计算解码表
+        let decoded: Vec<f64> = (0..=255u8)
+            .map(|v| color_space.to_linear(Color::repeat(v as f64 / 255.0)).x)
+            .collect();
+        let base = MipLevel {
+            width: rgb.width() as usize,
+            height: rgb.height() as usize,
+            data: rgb
+                .pixels()
+                .map(|p| match color_space {
+                    TextureColorSpace::DisplayP3 => color_space
+                        .to_linear(Color::new(p[0] as f64, p[1] as f64, p[2] as f64) / 255.0)
+                        .map(|c| c.max(0.0)),
+                    _ => Color::new(
+                        decoded[p[0] as usize],
+                        decoded[p[1] as usize],
+                        decoded[p[2] as usize],
+                    ),
+                })
+                .collect(),
+        };
+        if base.data.is_empty() {
+            return Vec::new();
+        }
+
+        let mut mips = vec![base];
+        while let Some(last) = mips.last()
+            && (last.width > 1 || last.height > 1)
+        {
+            let next = last.downsample();
+            mips.push(next);
+        }
+        mips
+    }
+}
+
+/// 读取图像和嵌入的 ICC 配置文件
+fn load_with_icc(path: &Path) -> image::ImageResult<(DynamicImage, Option<Vec<u8>>)> {
+    let mut decoder = ImageReader::open(path)?
+        .with_guessed_format()?
+        .into_decoder()?;
+    let icc = decoder.icc_profile().ok().flatten();
+    let img = DynamicImage::from_decoder(decoder)?;
+    Ok((img, icc))
 }
 
 impl Texture for ImageTexture {
@@ -73,17 +211,42 @@ impl Texture for ImageTexture {
         let i = i.min(self.width - 1);
         let j = j.min(self.height - 1);
 
-        if let Some(img) = &self.image {
-            let pixel = img.get_pixel(i, j);
-            let color_scale = 1.0 / 255.0;
-
-            Color::new(
-                color_scale * pixel[0] as f64,
-                color_scale * pixel[1] as f64,
-                color_scale * pixel[2] as f64,
-            )
-        } else {
-            Color::new(0.0, 1.0, 1.0) // 默认青色
+        // 最近邻采样原图（已转换为线性颜色）
+        match self.mips.first() {
+            Some(base) => base.texel(i as usize, j as usize),
+            None => Color::new(0.0, 1.0, 1.0), // 默认青色
+        }
+    }
+
+    fn value_filtered(
+        &self,
+        u: f64,
+        v: f64,
+        p: &Point3,
+        _normal: &Vec3,
+        footprint: Option<&TextureFootprint>,
+    ) -> Color {
+        let lod = match footprint {
+            Some(fp) if !self.mips.is_empty() => fp
+                .texel_width(self.width as f64, self.height as f64)
+                .max(1e-12)
+                .log2()
+                .min((self.mips.len() - 1) as f64),
+            _ => 0.0,
+        };
+        // 足迹小于一个纹素时保持原有的最近邻采样
+        if lod <= 0.0 {
+            return self.value(u, v, p);
+        }
+
+        let x = u.clamp(0.0, 1.0);
+        let y = 1.0 - v.clamp(0.0, 1.0);
+        let level = lod.floor() as usize;
+        let t = lod - level as f64;
+        let fine = self.mips[level].bilinear(x, y);
+        match self.mips.get(level + 1) {
+            Some(coarse) if t > 0.0 => fine * (1.0 - t) + coarse.bilinear(x, y) * t,
+            _ => fine,
         }
     }
 }
@@ -0,0 +1,101 @@
+use super::{SolidColor, Texture, TexturePtr};
+use crate::ray_tracing::math::differential::TextureFootprint;
+use crate::ray_tracing::math::vec3::{Color, Point3, Vec3};
+
+/// 内置材质持有的纹理：纯色按值内联存放，其余纹理通过trait对象共享
+///
+/// 场景中绝大多数纹理是纯色，内联后着色时不再经过指针和虚函数调用。
+/// 构造材质时仍然传入 `TexturePtr`，此类型只在材质内部使用。
+#[derive(Debug, Clone)]
+pub enum TextureKind {
+    Solid(SolidColor),
+    Shared(TexturePtr),
+}
+
+impl TextureKind {
+    /// 纯色纹理
+    #[inline]
+    pub const fn solid(color: Color) -> Self {
+        Self::Solid(SolidColor::new(color))
+    }
+
+    /// 纯色纹理的颜色，其他纹理为None
+    #[inline]
+    pub fn solid_color(&self) -> Option<Color> {
+        match self {
+            Self::Solid(solid) => Some(solid.value(0.0, 0.0, &Point3::origin())),
+            Self::Shared(_) => None,
+        }
+    }
+}
+
+impl From<TexturePtr> for TextureKind {
+    #[inline]
+    fn from(texture: TexturePtr) -> Self {
+        Self::Shared(texture)
+    }
+}
+
+impl From<SolidColor> for TextureKind {
+    #[inline]
+    fn from(texture: SolidColor) -> Self {
+        Self::Solid(texture)
+    }
+}
+
+impl Texture for TextureKind {
+    #[inline]
+    fn value(&self, u: f64, v: f64, p: &Point3) -> Color {
+        match self {
+            Self::Solid(texture) => texture.value(u, v, p),
+            Self::Shared(texture) => texture.value(u, v, p),
+        }
+    }
+
+    #[inline]
+    fn value_with_normal(&self, u: f64, v: f64, p: &Point3, normal: &Vec3) -> Color {
+        match self {
+            Self::Solid(texture) => texture.value(u, v, p),
+            Self::Shared(texture) => texture.value_with_normal(u, v, p, normal),
+        }
+    }
+
+    #[inline]
+    fn value_filtered(
+        &self,
+        u: f64,
+        v: f64,
+        p: &Point3,
+        normal: &Vec3,
+        footprint: Option<&TextureFootprint>,
+    ) -> Color {
+        match self {
+            Self::Solid(texture) => texture.value(u, v, p),
+            Self::Shared(texture) => texture.value_filtered(u, v, p, normal, footprint),
+        }
+    }
+
+    #[inline]
+    fn value_at_time(
+        &self,
+        u: f64,
+        v: f64,
+        p: &Point3,
+        normal: &Vec3,
+        footprint: Option<&TextureFootprint>,
+        time: f64,
+    ) -> Color {
+        match self {
+            Self::Solid(texture) => texture.value(u, v, p),
+            Self::Shared(texture) => texture.value_at_time(u, v, p, normal, footprint, time),
+        }
+    }
+
+    #[inline]
+    fn scalar(&self, u: f64, v: f64, p: &Point3) -> f64 {
+        match self {
+            Self::Solid(texture) => texture.scalar(u, v, p),
+            Self::Shared(texture) => texture.scalar(u, v, p),
+        }
+    }
+}
@@ -1,18 +1,85 @@
 pub mod checker;
+pub mod closure;
 pub mod image;
+pub mod kind;
 pub mod noise;
+pub mod simplex;
 pub mod solid_color;
+pub mod triplanar;
+pub mod udim;
+pub mod worley;
 
-use crate::ray_tracing::math::vec3::{Color, Point3};
+use crate::ray_tracing::math::differential::TextureFootprint;
+use crate::ray_tracing::math::vec3::{Color, Point3, Vec3};
+use std::any::Any;
 use std::sync::Arc;
 
 /// 纹理trait - 定义纹理的基本接口
-pub trait Texture: Send + Sync + std::fmt::Debug {
+///
+/// 自定义纹理只需实现 `value`；需要法线（如三平面映射）或支持滤波的纹理再覆盖对应的方法。
+/// 纹理可以通过 `downcast_ref` 从 `dyn Texture` 取回具体类型。
+pub trait Texture: Any + Send + Sync + std::fmt::Debug {
     fn value(&self, u: f64, v: f64, p: &Point3) -> Color;
+
+    /// 带表面法线的采样，供三平面映射等需要法线的纹理使用
+    #[inline]
+    fn value_with_normal(&self, u: f64, v: f64, p: &Point3, _normal: &Vec3) -> Color {
+        self.value(u, v, p)
+    }
+
+    /// 按纹理足迹滤波的采样，用于消除远处和掠射角处的走样；没有足迹时等同于 `value_with_normal`
+    #[inline]
+    fn value_filtered(
+        &self,
+        u: f64,
+        v: f64,
+        p: &Point3,
+        normal: &Vec3,
+        _footprint: Option<&TextureFootprint>,
+    ) -> Color {
+        self.value_with_normal(u, v, p, normal)
+    }
+
+    /// 带光线时刻的采样，供随时间变化的纹理（如动画噪声）使用；默认忽略时刻，等同于 `value_filtered`
+    #[inline]
+    fn value_at_time(
+        &self,
+        u: f64,
+        v: f64,
+        p: &Point3,
+        normal: &Vec3,
+        footprint: Option<&TextureFootprint>,
+        _time: f64,
+    ) -> Color {
+        self.value_filtered(u, v, p, normal, footprint)
+    }
+
+    /// 标量采样（RGB平均值），用于粗糙度、金属度、遮罩等单通道参数
+    #[inline]
+    fn scalar(&self, u: f64, v: f64, p: &Point3) -> f64 {
+        let c = self.value(u, v, p);
+        (c.x + c.y + c.z) / 3.0
+    }
+}
+
+impl dyn Texture {
+    /// 尝试把纹理转换为具体类型的引用
+    #[inline]
+    pub fn downcast_ref<T: Texture>(&self) -> Option<&T> {
+        (self as &dyn Any).downcast_ref()
+    }
+
+    /// 纹理是否为类型 T
+    #[inline]
+    pub fn is<T: Texture>(&self) -> bool {
+        (self as &dyn Any).is::<T>()
+    }
 }
 
 /// 纹理指针类型别名
 pub type TexturePtr = Arc<dyn Texture>;
 
 // 重新导出所有纹理类型
+pub use closure::ClosureTexture;
+pub use kind::TextureKind;
 pub use solid_color::SolidColor;
@@ -1,36 +1,112 @@
 use super::Texture;
-use crate::ray_tracing::math::vec3::{Color, Point3};
-use crate::ray_tracing::procedural::noise::Perlin;
+use crate::ray_tracing::math::differential::TextureFootprint;
+use crate::ray_tracing::math::vec3::{Color, Point3, Vec3};
+use crate::ray_tracing::procedural::noise::{Perlin, frame_time};
 
 /// 噪声纹理，基于Perlin噪声生成程序化纹理
+///
+/// 设置演变速度后改用四维噪声，图案随时间（全局帧时间 + 光线时刻）平滑变化。
 #[derive(Debug)]
 pub struct NoiseTexture {
     noise: Perlin,
     scale: f64,
+    speed: f64,
+    depth: i32,
 }
 
+/// 湍流默认叠加的噪声层数
+pub const DEFAULT_TURBULENCE_DEPTH: i32 = 7;
+
 impl NoiseTexture {
     /// 创建新的噪声纹理
     #[inline]
     pub fn new(scale: f64) -> Self {
+        Self::new_with_noise(Perlin::new(), scale)
+    }
+
+    /// 用种子创建噪声纹理，相同种子生成相同的图案
+    #[inline]
+    pub fn with_seed(seed: u64, scale: f64) -> Self {
+        Self::new_with_noise(Perlin::with_seed(seed), scale)
+    }
+
+    /// 创建带自定义Perlin噪声的纹理
+    #[inline]
+    pub fn new_with_noise(noise: Perlin, scale: f64) -> Self {
         Self {
-            noise: Perlin::new(),
+            noise,
             scale,
+            speed: 0.0,
+            depth: DEFAULT_TURBULENCE_DEPTH,
         }
     }
 
-    /// 创建带自定义Perlin噪声的纹理
+    /// 设置演变速度（每帧在噪声时间轴上前进的距离），0 为静止
     #[inline]
-    pub fn new_with_noise(noise: Perlin, scale: f64) -> Self {
-        Self { noise, scale }
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
     }
-}
 
-impl Texture for NoiseTexture {
-    fn value(&self, _u: f64, _v: f64, p: &Point3) -> Color {
+    /// 设置湍流叠加的噪声层数，层数越多细节越丰富（至少为 1）
+    #[inline]
+    pub fn with_turbulence_depth(mut self, depth: i32) -> Self {
+        self.depth = depth.max(1);
+        self
+    }
+
+    /// 噪声的种子，由系统随机数生成时为None
+    #[inline]
+    pub fn seed(&self) -> Option<u64> {
+        self.noise.seed()
+    }
+
+    /// 条纹的空间频率
+    #[inline]
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// 演变速度
+    #[inline]
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// 湍流叠加的噪声层数
+    #[inline]
+    pub fn turbulence_depth(&self) -> i32 {
+        self.depth
+    }
+
+    /// 时刻 time（帧）的颜色
+    pub fn value_at(&self, p: &Point3, time: f64) -> Color {
         // 使用正弦函数创建大理石纹理效果
         // turb函数添加湍流细节
-        let noise_value = 1.0 + (self.scale * p.z + 10.0 * self.noise.turb(p, 7)).sin();
+        let turbulence = if self.speed == 0.0 {
+            self.noise.turb(p, self.depth)
+        } else {
+            self.noise.turb4(p, self.speed * time, self.depth)
+        };
+        let noise_value = 1.0 + (self.scale * p.z + 10.0 * turbulence).sin();
         Color::new(0.5, 0.5, 0.5) * noise_value
     }
 }
+
+impl Texture for NoiseTexture {
+    fn value(&self, _u: f64, _v: f64, p: &Point3) -> Color {
+        self.value_at(p, frame_time())
+    }
+
+    fn value_at_time(
+        &self,
+        _u: f64,
+        _v: f64,
+        p: &Point3,
+        _normal: &Vec3,
+        _footprint: Option<&TextureFootprint>,
+        time: f64,
+    ) -> Color {
+        self.value_at(p, frame_time() + time)
+    }
+}
@@ -0,0 +1,62 @@
+use super::Texture;
+use crate::ray_tracing::math::vec3::{Color, Point3};
+use crate::ray_tracing::procedural::simplex::Simplex;
+
+/// 单形噪声纹理：分形噪声的值在两种颜色之间插值（云、污渍、岩石斑纹）
+#[derive(Debug, Clone)]
+pub struct SimplexTexture {
+    seed: u64,
+    noise: Simplex,
+    scale: f64,
+    octaves: u32,
+    low: Color,
+    high: Color,
+}
+
+impl SimplexTexture {
+    /// 创建单形噪声纹理，scale 为世界坐标到噪声坐标的缩放
+    pub fn new(seed: u64, scale: f64, low: Color, high: Color) -> Self {
+        Self {
+            seed,
+            noise: Simplex::new(seed),
+            scale,
+            octaves: 5,
+            low,
+            high,
+        }
+    }
+
+    /// 设置叠加的倍频层数
+    pub fn with_octaves(mut self, octaves: u32) -> Self {
+        self.octaves = octaves.max(1);
+        self
+    }
+
+    /// 噪声的种子
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// 世界坐标到噪声坐标的缩放
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// 叠加的倍频层数
+    pub fn octaves(&self) -> u32 {
+        self.octaves
+    }
+
+    /// 噪声值为 -1 和 1 处的颜色
+    pub fn colors(&self) -> (Color, Color) {
+        (self.low, self.high)
+    }
+}
+
+impl Texture for SimplexTexture {
+    fn value(&self, _u: f64, _v: f64, p: &Point3) -> Color {
+        let n = self.noise.fbm(&(p * self.scale), self.octaves);
+        let t = (0.5 + 0.5 * n).clamp(0.0, 1.0);
+        self.low.lerp(&self.high, t)
+    }
+}
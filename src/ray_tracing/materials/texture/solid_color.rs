@@ -13,6 +13,12 @@ impl SolidColor {
     pub const fn new(albedo: Color) -> Self {
         Self { albedo }
     }
+
+    /// 纹理的颜色
+    #[inline]
+    pub fn color(&self) -> Color {
+        self.albedo
+    }
 }
 
 impl Texture for SolidColor {
@@ -0,0 +1,75 @@
+use super::{Texture, TexturePtr};
+use crate::ray_tracing::math::vec3::{Color, Point3, Vec3};
+
+/// 三平面映射纹理：沿三个坐标轴投影子纹理，按法线方向混合
+///
+/// 不依赖物体自身的UV，可避免盒子、高度场等UV较差的物体上出现接缝。
+#[derive(Debug)]
+pub struct TriplanarTexture {
+    texture: TexturePtr,
+    scale: f64,     // 世界单位到纹理重复次数的比例
+    sharpness: f64, // 混合锐度，越大过渡越窄
+}
+
+impl TriplanarTexture {
+    /// 创建三平面映射纹理，scale 为世界单位到纹理重复次数的比例
+    #[inline]
+    pub fn new(texture: TexturePtr, scale: f64, sharpness: f64) -> Self {
+        Self {
+            texture,
+            scale,
+            sharpness: sharpness.max(1.0),
+        }
+    }
+
+    /// 被投影的子纹理
+    #[inline]
+    pub fn texture(&self) -> &TexturePtr {
+        &self.texture
+    }
+
+    /// 世界单位到纹理重复次数的比例
+    #[inline]
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// 混合锐度
+    #[inline]
+    pub fn sharpness(&self) -> f64 {
+        self.sharpness
+    }
+
+    /// 将平面坐标映射到 [0, 1) 的重复UV
+    #[inline]
+    fn planar_uv(&self, a: f64, b: f64) -> (f64, f64) {
+        let u = (a * self.scale).rem_euclid(1.0);
+        let v = (b * self.scale).rem_euclid(1.0);
+        (u, v)
+    }
+}
+
+impl Texture for TriplanarTexture {
+    fn value(&self, _u: f64, _v: f64, p: &Point3) -> Color {
+        // 无法线信息时退化为俯视（XZ平面）投影
+        let (u, v) = self.planar_uv(p.x, p.z);
+        self.texture.value(u, v, p)
+    }
+
+    fn value_with_normal(&self, _u: f64, _v: f64, p: &Point3, normal: &Vec3) -> Color {
+        let mut weights = normal.map(|n| n.abs().powf(self.sharpness));
+        let total = weights.x + weights.y + weights.z;
+        if total <= 0.0 {
+            return self.value(0.0, 0.0, p);
+        }
+        weights /= total;
+
+        let (ux, vx) = self.planar_uv(p.z, p.y);
+        let (uy, vy) = self.planar_uv(p.x, p.z);
+        let (uz, vz) = self.planar_uv(p.x, p.y);
+
+        weights.x * self.texture.value(ux, vx, p)
+            + weights.y * self.texture.value(uy, vy, p)
+            + weights.z * self.texture.value(uz, vz, p)
+    }
+}
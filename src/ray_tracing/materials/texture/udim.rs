@@ -0,0 +1,139 @@
+//! UDIM 多瓦片图像纹理：UV 跨越多个单位方格的网格，每个方格使用一张独立的图像
+//!
+//! 瓦片编号为 `1001 + ⌊u⌋ + 10⌊v⌋`（u 方向每行 10 个瓦片），文件名中的 `<UDIM>` 替换为编号，
+//! 例如 `skin.<UDIM>.png` 对应 `skin.1001.png`、`skin.1002.png`…。
+//! 瓦片在第一次被采样时才加载（按纹理搜索路径查找），之后缓存在纹理中；不存在的瓦片只提示一次。
+
+use super::Texture;
+use super::image::ImageTexture;
+use crate::ray_tracing::math::differential::TextureFootprint;
+use crate::ray_tracing::math::vec3::{Color, Point3, Vec3};
+use crate::ray_tracing::rendering::color_space::TextureColorSpace;
+use crate::ray_tracing::utils::config;
+use std::sync::OnceLock;
+
+/// 文件名中代表瓦片编号的占位符
+pub const UDIM_TOKEN: &str = "<UDIM>";
+/// 第一个瓦片的编号
+pub const FIRST_TILE: u32 = 1001;
+/// u 方向的瓦片数
+const TILES_PER_ROW: u32 = 10;
+/// v 方向的瓦片数（编号 1001～1999）
+const TILE_ROWS: u32 = 100;
+
+/// UDIM 多瓦片图像纹理
+#[derive(Debug)]
+pub struct UdimTexture {
+    pattern: String,
+    color_space: TextureColorSpace,
+    // 按 (编号 - 1001) 索引，None 表示瓦片文件不存在
+    tiles: Vec<OnceLock<Option<ImageTexture>>>,
+}
+
+impl UdimTexture {
+    /// 文件名是否包含 UDIM 占位符
+    #[inline]
+    pub fn is_pattern(filename: &str) -> bool {
+        filename.contains(UDIM_TOKEN)
+    }
+
+    /// 使用配置中的默认纹理色彩空间
+    #[inline]
+    pub fn new(pattern: &str) -> Self {
+        Self::with_color_space(pattern, config::global().texture_color_space)
+    }
+
+    /// pattern 为包含 `<UDIM>` 的文件名，瓦片按声明的色彩空间转换为线性颜色
+    pub fn with_color_space(pattern: &str, color_space: TextureColorSpace) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            color_space,
+            tiles: (0..TILES_PER_ROW * TILE_ROWS)
+                .map(|_| OnceLock::new())
+                .collect(),
+        }
+    }
+
+    /// 加载时给出的文件名模式
+    #[inline]
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// 纹理数据的色彩空间
+    #[inline]
+    pub fn color_space(&self) -> TextureColorSpace {
+        self.color_space
+    }
+
+    /// 瓦片编号对应的文件名
+    pub fn tile_filename(&self, tile: u32) -> String {
+        self.pattern.replace(UDIM_TOKEN, &tile.to_string())
+    }
+
+    /// 已加载（存在）的瓦片编号
+    pub fn loaded_tiles(&self) -> Vec<u32> {
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| matches!(slot.get(), Some(Some(_))))
+            .map(|(i, _)| FIRST_TILE + i as u32)
+            .collect()
+    }
+
+    /// 纹理坐标所在的瓦片编号及瓦片内的局部坐标，超出 1001～1999 范围时返回 None
+    pub fn locate(u: f64, v: f64) -> Option<(u32, f64, f64)> {
+        let (fu, fv) = (u.floor(), v.floor());
+        if !(0.0..TILES_PER_ROW as f64).contains(&fu) || !(0.0..TILE_ROWS as f64).contains(&fv) {
+            return None;
+        }
+        let tile = FIRST_TILE + fu as u32 + TILES_PER_ROW * fv as u32;
+        Some((tile, u - fu, v - fv))
+    }
+
+    /// 取瓦片，第一次访问时加载
+    fn tile(&self, tile: u32) -> Option<&ImageTexture> {
+        self.tiles[(tile - FIRST_TILE) as usize]
+            .get_or_init(|| {
+                let filename = self.tile_filename(tile);
+                if config::global().resolve_texture(&filename).is_none() {
+                    eprintln!("警告: UDIM 瓦片 '{}' 不存在", filename);
+                    return None;
+                }
+                Some(ImageTexture::with_color_space(&filename, self.color_space))
+            })
+            .as_ref()
+    }
+
+    /// 纹理坐标所在的瓦片及局部坐标
+    #[inline]
+    fn lookup(&self, u: f64, v: f64) -> Option<(&ImageTexture, f64, f64)> {
+        let (tile, u, v) = Self::locate(u, v)?;
+        Some((self.tile(tile)?, u, v))
+    }
+}
+
+impl Texture for UdimTexture {
+    /// 没有对应瓦片时返回青色（与加载失败的图像纹理一致）
+    fn value(&self, u: f64, v: f64, p: &Point3) -> Color {
+        match self.lookup(u, v) {
+            Some((tile, u, v)) => tile.value(u, v, p),
+            None => Color::new(0.0, 1.0, 1.0),
+        }
+    }
+
+    /// 每个瓦片覆盖一个单位方格，纹理足迹按瓦片自身的分辨率换算，滤波不跨越瓦片边界
+    fn value_filtered(
+        &self,
+        u: f64,
+        v: f64,
+        p: &Point3,
+        normal: &Vec3,
+        footprint: Option<&TextureFootprint>,
+    ) -> Color {
+        match self.lookup(u, v) {
+            Some((tile, u, v)) => tile.value_filtered(u, v, p, normal, footprint),
+            None => Color::new(0.0, 1.0, 1.0),
+        }
+    }
+}
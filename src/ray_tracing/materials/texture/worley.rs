@@ -0,0 +1,102 @@
+use super::Texture;
+use crate::ray_tracing::math::vec3::{Color, Point3};
+use crate::ray_tracing::procedural::worley::{Worley, WorleyFeature};
+
+/// Worley 噪声纹理：所选特征值经增益、反相和幂次映射到 [0,1] 后在两种颜色之间插值
+///
+/// 常用组合：F1 得到细胞和石子；F2 − F1 配合较大增益得到龟裂的泥地；
+/// F2 − F1 反相后取高次幂，只在细胞边界处留下亮线，类似水底焦散。
+#[derive(Debug, Clone)]
+pub struct WorleyTexture {
+    noise: Worley,
+    feature: WorleyFeature,
+    scale: f64,
+    gain: f64,
+    invert: bool,
+    exponent: f64,
+    low: Color,
+    high: Color,
+}
+
+impl WorleyTexture {
+    /// 创建 Worley 噪声纹理，scale 为世界坐标到噪声坐标的缩放
+    pub fn new(noise: Worley, feature: WorleyFeature, scale: f64, low: Color, high: Color) -> Self {
+        Self {
+            noise,
+            feature,
+            scale,
+            gain: 1.0,
+            invert: false,
+            exponent: 1.0,
+            low,
+            high,
+        }
+    }
+
+    /// 设置特征值的增益
+    pub fn with_gain(mut self, gain: f64) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    /// 反相（1 − t）
+    pub fn inverted(mut self) -> Self {
+        self.invert = true;
+        self
+    }
+
+    /// 设置幂次，大于 1 时图案更集中在 t 接近 1 的区域
+    pub fn with_exponent(mut self, exponent: f64) -> Self {
+        self.exponent = exponent.max(1e-3);
+        self
+    }
+
+    /// 噪声生成器
+    pub fn noise(&self) -> &Worley {
+        &self.noise
+    }
+
+    /// 使用的特征值
+    pub fn feature(&self) -> WorleyFeature {
+        self.feature
+    }
+
+    /// 世界坐标到噪声坐标的缩放
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// 特征值的增益
+    pub fn gain(&self) -> f64 {
+        self.gain
+    }
+
+    /// 是否反相
+    pub fn is_inverted(&self) -> bool {
+        self.invert
+    }
+
+    /// 幂次
+    pub fn exponent(&self) -> f64 {
+        self.exponent
+    }
+
+    /// t 为 0 和 1 处的颜色
+    pub fn colors(&self) -> (Color, Color) {
+        (self.low, self.high)
+    }
+
+    /// 点p处映射后的标量值，[0,1]
+    pub fn intensity(&self, p: &Point3) -> f64 {
+        let value = self.noise.evaluate(&(p * self.scale), self.feature);
+        let t = (value * self.gain).clamp(0.0, 1.0);
+        let t = if self.invert { 1.0 - t } else { t };
+        t.powf(self.exponent)
+    }
+}
+
+impl Texture for WorleyTexture {
+    fn value(&self, _u: f64, _v: f64, p: &Point3) -> Color {
+        self.low.lerp(&self.high, self.intensity(p))
+    }
+}
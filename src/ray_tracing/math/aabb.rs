@@ -108,6 +108,28 @@ impl Aabb {
         self.x.is_empty() || self.y.is_empty() || self.z.is_empty()
     }
 
+    /// 检查两个 AABB 是否重叠
+    #[inline]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.x.overlaps(&other.x) && self.y.overlaps(&other.y) && self.z.overlaps(&other.z)
+    }
+
+    /// 检查点是否在 AABB 内
+    #[inline]
+    pub fn contains(&self, p: &Point3) -> bool {
+        self.x.contains(p.x) && self.y.contains(p.y) && self.z.contains(p.z)
+    }
+
+    /// AABB 中心点
+    #[inline]
+    pub fn center(&self) -> Point3 {
+        Point3::new(
+            0.5 * (self.x.min + self.x.max),
+            0.5 * (self.y.min + self.y.max),
+            0.5 * (self.z.min + self.z.max),
+        )
+    }
+
     /// 合并两个 AABB
     #[inline]
     pub fn merge(&self, other: &Self) -> Self {
@@ -165,6 +187,30 @@ impl Aabb {
         }
         true
     }
+
+    /// 把光线区间裁剪到 AABB 内部，光线不经过 AABB 时为None
+    ///
+    /// 与 `hit` 不同，平行于某个轴的光线只有原点位于该轴的区间内时才算经过。
+    pub fn clip(&self, ray: &Ray, mut ray_t: Interval) -> Option<Interval> {
+        for axis in 0..3 {
+            let ax = self.axis_interval(axis);
+            let (orig, dir) = (ray.orig[axis], ray.dir[axis]);
+            if dir.abs() < 1e-12 {
+                if !ax.contains(orig) {
+                    return None;
+                }
+                continue;
+            }
+            let t0 = (ax.min - orig) / dir;
+            let t1 = (ax.max - orig) / dir;
+            ray_t.min = ray_t.min.max(t0.min(t1));
+            ray_t.max = ray_t.max.min(t0.max(t1));
+            if ray_t.max < ray_t.min {
+                return None;
+            }
+        }
+        Some(ray_t)
+    }
 }
 
 impl Default for Aabb {
@@ -0,0 +1,135 @@
+//! 光线微分（Igehy 1999）与纹理足迹，用于纹理滤波
+//!
+//! 相机为每条主光线附带相邻像素（x、y方向）的偏移光线，命中时与切平面相交得到交点在屏幕空间的偏导数，
+//! 进而得到纹理坐标的偏导数，纹理据此选择 mip 级别或滤波宽度。
+
+use super::ray::Ray;
+use super::vec3::*;
+
+/// 纹理坐标导数的上限，避免退化几何产生无穷大的滤波宽度
+const MAX_DERIVATIVE: f64 = 1e8;
+
+/// 光线微分：x、y 方向相邻像素光线的起点和方向
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayDifferential {
+    pub rx_origin: Point3,
+    pub rx_direction: Vec3,
+    pub ry_origin: Point3,
+    pub ry_direction: Vec3,
+}
+
+impl RayDifferential {
+    #[inline]
+    pub const fn new(
+        rx_origin: Point3,
+        rx_direction: Vec3,
+        ry_origin: Point3,
+        ry_direction: Vec3,
+    ) -> Self {
+        Self {
+            rx_origin,
+            rx_direction,
+            ry_origin,
+            ry_direction,
+        }
+    }
+
+    /// 镜面散射后的微分（按局部平面近似，忽略法线的变化）
+    ///
+    /// 散射方向与入射方向位于表面同侧时视为反射，偏移方向按法线镜像；否则视为透射，偏移方向保持不变。
+    pub fn scatter(
+        &self,
+        r_in: &Ray,
+        scattered: &Ray,
+        normal: &Vec3,
+        footprint: &TextureFootprint,
+    ) -> Self {
+        let scale = scattered.dir.norm() / r_in.dir.norm().max(1e-12);
+        let reflected = scattered.dir.dot(normal) * r_in.dir.dot(normal) < 0.0;
+
+        let offset = |d: &Vec3| {
+            if reflected {
+                (d.reflect(normal) - r_in.dir.reflect(normal)) * scale
+            } else {
+                (d - r_in.dir) * scale
+            }
+        };
+
+        Self {
+            rx_origin: scattered.orig + footprint.dpdx,
+            rx_direction: scattered.dir + offset(&self.rx_direction),
+            ry_origin: scattered.orig + footprint.dpdy,
+            ry_direction: scattered.dir + offset(&self.ry_direction),
+        }
+    }
+}
+
+/// 交点处的纹理足迹：位置和纹理坐标对屏幕 x、y 的偏导数
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TextureFootprint {
+    pub dpdx: Vec3,
+    pub dpdy: Vec3,
+    pub dudx: f64,
+    pub dudy: f64,
+    pub dvdx: f64,
+    pub dvdy: f64,
+}
+
+impl TextureFootprint {
+    /// 将微分光线与交点切平面相交计算足迹；dpdu、dpdv 为零时纹理坐标导数为零
+    ///
+    /// 微分光线与切平面平行时返回None。
+    pub fn compute(
+        differential: &RayDifferential,
+        p: &Point3,
+        normal: &Vec3,
+        dpdu: &Vec3,
+        dpdv: &Vec3,
+    ) -> Option<Self> {
+        let plane = |origin: &Point3, direction: &Vec3| -> Option<Vec3> {
+            let denom = normal.dot(direction);
+            if denom.abs() < 1e-12 {
+                return None;
+            }
+            let t = normal.dot(&(p - origin)) / denom;
+            t.is_finite().then(|| origin + direction * t - p)
+        };
+        let dpdx = plane(&differential.rx_origin, &differential.rx_direction)?;
+        let dpdy = plane(&differential.ry_origin, &differential.ry_direction)?;
+
+        // 最小二乘求解 dp = dpdu·du + dpdv·dv
+        let (a00, a01, a11) = (dpdu.dot(dpdu), dpdu.dot(dpdv), dpdv.dot(dpdv));
+        let det = a00 * a11 - a01 * a01;
+        let solve = |dp: &Vec3| -> (f64, f64) {
+            if det.abs() < 1e-20 {
+                return (0.0, 0.0);
+            }
+            let (b0, b1) = (dpdu.dot(dp), dpdv.dot(dp));
+            let du = (a11 * b0 - a01 * b1) / det;
+            let dv = (a00 * b1 - a01 * b0) / det;
+            (
+                du.clamp(-MAX_DERIVATIVE, MAX_DERIVATIVE),
+                dv.clamp(-MAX_DERIVATIVE, MAX_DERIVATIVE),
+            )
+        };
+        let (dudx, dvdx) = solve(&dpdx);
+        let (dudy, dvdy) = solve(&dpdy);
+
+        Some(Self {
+            dpdx,
+            dpdy,
+            dudx,
+            dudy,
+            dvdx,
+            dvdy,
+        })
+    }
+
+    /// 纹理空间中的足迹宽度（以纹素计），用于选择 mip 级别
+    #[inline]
+    pub fn texel_width(&self, width: f64, height: f64) -> f64 {
+        let x = (self.dudx * width).hypot(self.dvdx * height);
+        let y = (self.dudy * width).hypot(self.dvdy * height);
+        x.max(y)
+    }
+}
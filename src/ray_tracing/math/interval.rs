@@ -76,6 +76,12 @@ impl Interval {
         self.min >= self.max
     }
 
+    /// 检查两个区间是否重叠（包括边界接触）
+    #[inline]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.min <= other.max && other.min <= self.max
+    }
+
     /// 合并两个区间
     #[inline]
     pub fn merge(&self, other: &Self) -> Self {
@@ -1,5 +1,7 @@
 pub mod aabb;
+pub mod differential;
 pub mod interval;
 pub mod onb;
 pub mod ray;
+pub mod scalar;
 pub mod vec3;
@@ -1,16 +1,158 @@
+use super::differential::RayDifferential;
 use super::vec3::{Point3, Vec3};
 
+/// 光线的用途，供按光线类型控制可见性的物体（见 `geometry::visibility`）区分
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RayKind {
+    /// 从相机发出的主光线（包括穿过透明度遮罩后继续前进的部分）
+    Camera,
+    /// 按PDF采样的散射光线（漫反射、粗糙反射等），也是路径追踪中产生阴影的光线
+    #[default]
+    Diffuse,
+    /// 镜面反射和折射光线
+    Specular,
+    /// 从非镜面交点出发后只经过镜面散射的光线（焦散路径），可见性上按镜面光线处理
+    Caustic,
+    /// 只判断遮挡的光线（环境光遮蔽、直接光照采样、可见性查询）
+    Shadow,
+}
+
+/// 链接组的集合（位集，最多64个组）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LinkSet(u64);
+
+impl LinkSet {
+    /// 所有组
+    pub const ALL: Self = Self(u64::MAX);
+    /// 空集
+    pub const NONE: Self = Self(0);
+
+    /// 只含指定的组
+    pub fn only(groups: &[u8]) -> Self {
+        groups.iter().fold(Self::NONE, |set, &g| set.with(g))
+    }
+
+    /// 除指定的组以外的所有组
+    pub fn all_except(groups: &[u8]) -> Self {
+        groups.iter().fold(Self::ALL, |set, &g| set.without(g))
+    }
+
+    /// 加入一个组（超出范围的组号忽略）
+    #[inline]
+    pub const fn with(self, group: u8) -> Self {
+        if group < 64 {
+            Self(self.0 | 1 << group)
+        } else {
+            self
+        }
+    }
+
+    /// 移除一个组
+    #[inline]
+    pub const fn without(self, group: u8) -> Self {
+        if group < 64 {
+            Self(self.0 & !(1 << group))
+        } else {
+            self
+        }
+    }
+
+    /// 是否包含某个组
+    #[inline]
+    pub const fn contains(&self, group: u8) -> bool {
+        group < 64 && self.0 & (1 << group) != 0
+    }
+}
+
+/// 物体的光源链接标签（见 `geometry::light_link`），默认属于组0并与所有组交互
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LightLinkTag {
+    /// 所属的链接组
+    pub group: u8,
+    /// 允许交互的组：作为接收者时接受其光照的组，作为发光体时照亮的组
+    pub links: LinkSet,
+}
+
+impl Default for LightLinkTag {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl LightLinkTag {
+    /// 未链接物体的标签
+    pub const DEFAULT: Self = Self {
+        group: 0,
+        links: LinkSet::ALL,
+    };
+
+    #[inline]
+    pub const fn new(group: u8, links: LinkSet) -> Self {
+        Self { group, links }
+    }
+
+    /// 以 self 为接收者时，标签为 emitter 的发光体能否照亮它
+    #[inline]
+    pub const fn receives_from(&self, emitter: &LightLinkTag) -> bool {
+        self.links.contains(emitter.group) && emitter.links.contains(self.group)
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 pub struct Ray {
     pub orig: Point3,
     pub dir: Vec3,
     pub time: f64,
+    /// 光线微分，仅相机光线及其镜面散射光线携带，用于纹理滤波
+    pub differential: Option<RayDifferential>,
+    /// 光线的用途，默认为散射光线
+    pub kind: RayKind,
+    /// 发出光线的着色点的光源链接标签，相机光线为None（不受链接限制）
+    pub link: Option<LightLinkTag>,
+    /// 发出光线的着色点已向光源列表显式采样（透明阴影），光线到达的列表内发光体不再计入
+    pub direct_sampled: bool,
 }
 
 impl Ray {
     #[inline]
     pub const fn new(orig: Point3, dir: Vec3, time: f64) -> Self {
-        Self { orig, dir, time }
+        Self {
+            orig,
+            dir,
+            time,
+            differential: None,
+            kind: RayKind::Diffuse,
+            link: None,
+            direct_sampled: false,
+        }
+    }
+
+    /// 设置光线的用途
+    #[inline]
+    pub const fn with_kind(mut self, kind: RayKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// 设置发出光线的着色点的链接标签
+    #[inline]
+    pub const fn with_link(mut self, link: Option<LightLinkTag>) -> Self {
+        self.link = link;
+        self
+    }
+
+    /// 标记发出光线的着色点是否已向光源显式采样
+    #[inline]
+    pub const fn with_direct_sampled(mut self, direct_sampled: bool) -> Self {
+        self.direct_sampled = direct_sampled;
+        self
+    }
+
+    /// 附带光线微分
+    #[inline]
+    pub const fn with_differential(mut self, differential: Option<RayDifferential>) -> Self {
+        self.differential = differential;
+        self
     }
 
     #[inline]
@@ -0,0 +1,51 @@
+//! 几何数据的标量精度：渲染器整体使用 f64，大网格可以选择以 f32 存储和求交
+//!
+//! f32 使顶点、索引和 BVH 的内存减半，一次缓存行装下更多数据；代价是只有约 7 位有效数字。
+//! 交点位置的误差约为 `坐标量级 × 6e-8`，超过积分器的自相交偏移（0.001）时会出现表面痤疮，
+//! 因此 f32 适合坐标量级在 1e4 以内的网格；远离原点的大场景应先平移到原点附近或保持 f64。
+
+use nalgebra::RealField;
+
+/// 几何数据的标量类型（f32 或 f64）
+pub trait Scalar: RealField + Copy {
+    /// 类型名称，用于调试输出和报告
+    const NAME: &'static str;
+    /// 单位舍入误差（机器精度的一半）
+    const UNIT_ROUNDOFF: f64;
+
+    /// 从 f64 舍入到本精度
+    fn narrow(x: f64) -> Self;
+
+    /// 扩展为 f64（无损）
+    fn widen(self) -> f64;
+}
+
+impl Scalar for f32 {
+    const NAME: &'static str = "f32";
+    const UNIT_ROUNDOFF: f64 = f32::EPSILON as f64 * 0.5;
+
+    #[inline]
+    fn narrow(x: f64) -> Self {
+        x as f32
+    }
+
+    #[inline]
+    fn widen(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Scalar for f64 {
+    const NAME: &'static str = "f64";
+    const UNIT_ROUNDOFF: f64 = f64::EPSILON * 0.5;
+
+    #[inline]
+    fn narrow(x: f64) -> Self {
+        x
+    }
+
+    #[inline]
+    fn widen(self) -> f64 {
+        self
+    }
+}
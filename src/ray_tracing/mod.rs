@@ -1,9 +1,12 @@
 pub mod acceleration;
 pub mod geometry;
+pub mod lights;
 pub mod materials;
 pub mod math;
 pub mod procedural;
 pub mod rendering;
 pub mod sampling;
+pub mod scene;
 pub mod utils;
+pub mod validation;
 pub mod volumes;
@@ -0,0 +1,266 @@
+//! 现成的分形距离场：Mandelbulb、Menger 海绵和谢尔宾斯基四面体
+//!
+//! 分形用 [`SdfObject`] 包装后即可像普通物体一样放入场景（用 `Scale`、`Translate` 等变换摆放）。
+//! 命中点的纹理坐标携带着色参数：u 为轨道陷阱（迭代过程中离原点最近的距离，归一化到 [0,1]），
+//! v 为逃逸或被挖空时的迭代次数占比。[`CosinePalette`] 把 u 映射为连续的调色板颜色。
+//!
+//! [`SdfObject`]: crate::ray_tracing::geometry::sdf::SdfObject
+
+use crate::ray_tracing::geometry::sdf::DistanceField;
+use crate::ray_tracing::materials::texture::Texture;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::vec3::*;
+use std::f64::consts::TAU;
+
+/// Mandelbulb：三维球坐标下的 z ↦ z^n + c 迭代，包围在半径约 1.2 的球内
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mandelbulb {
+    /// 幂次，经典形态为 8
+    pub power: f64,
+    /// 迭代次数
+    pub iterations: usize,
+    /// 逃逸半径
+    pub bailout: f64,
+}
+
+impl Default for Mandelbulb {
+    fn default() -> Self {
+        Self {
+            power: 8.0,
+            iterations: 12,
+            bailout: 2.0,
+        }
+    }
+}
+
+impl Mandelbulb {
+    /// 指定幂次
+    pub fn new(power: f64) -> Self {
+        Self {
+            power,
+            ..Self::default()
+        }
+    }
+
+    /// 迭代：返回距离估计、轨道陷阱和逃逸时的迭代次数占比
+    fn iterate(&self, p: &Point3) -> (f64, f64, f64) {
+        let n = self.power;
+        let mut z = p.coords;
+        let mut dr = 1.0;
+        let mut r = z.norm();
+        let mut trap = f64::INFINITY;
+        let mut escaped = self.iterations;
+        for i in 0..self.iterations {
+            r = z.norm();
+            if r > self.bailout {
+                escaped = i;
+                break;
+            }
+            trap = trap.min(r);
+            if r < 1e-12 {
+                z = p.coords;
+                continue;
+            }
+            let theta = (z.y / r).clamp(-1.0, 1.0).acos() * n;
+            let phi = z.z.atan2(z.x) * n;
+            dr = r.powf(n - 1.0) * n * dr + 1.0;
+            let (sin_theta, cos_theta) = theta.sin_cos();
+            let (sin_phi, cos_phi) = phi.sin_cos();
+            z = r.powf(n) * Vec3::new(sin_theta * cos_phi, cos_theta, sin_theta * sin_phi)
+                + p.coords;
+        }
+        let distance = if r > 1e-12 {
+            0.5 * r.ln() * r / dr
+        } else {
+            0.0
+        };
+        (
+            distance,
+            (trap / self.bailout).clamp(0.0, 1.0),
+            escaped as f64 / self.iterations.max(1) as f64,
+        )
+    }
+}
+
+impl DistanceField for Mandelbulb {
+    fn distance(&self, p: &Point3) -> f64 {
+        self.iterate(p).0
+    }
+
+    fn bounds(&self) -> Aabb {
+        let r = Vec3::repeat(1.25);
+        Aabb::new_point(Point3::from(-r), Point3::from(r))
+    }
+
+    fn coloring(&self, p: &Point3) -> (f64, f64) {
+        let (_, trap, iterations) = self.iterate(p);
+        (trap, iterations)
+    }
+}
+
+/// Menger 海绵：边长为 2、中心在原点的立方体，每层挖去中心十字
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MengerSponge {
+    /// 挖空的层数
+    pub iterations: usize,
+}
+
+impl Default for MengerSponge {
+    fn default() -> Self {
+        Self { iterations: 4 }
+    }
+}
+
+impl MengerSponge {
+    /// 距离估计、轨道陷阱和决定表面的层数占比（0 为外侧立方体）
+    fn iterate(&self, p: &Point3) -> (f64, f64, f64) {
+        let q = p.coords.abs() - Vec3::repeat(1.0);
+        let mut d = q.max().min(0.0) + q.map(|c| c.max(0.0)).norm();
+        let mut scale = 1.0;
+        let mut trap = f64::INFINITY;
+        let mut level = 0;
+        for m in 0..self.iterations {
+            let a = (p.coords * scale).map(|c| (c + 1.0).rem_euclid(2.0) - 1.0);
+            scale *= 3.0;
+            let r = a.map(|c| (1.0 - 3.0 * c.abs()).abs());
+            let (da, db, dc) = (r.x.max(r.y), r.y.max(r.z), r.z.max(r.x));
+            let c = (da.min(db).min(dc) - 1.0) / scale;
+            trap = trap.min(a.norm());
+            if c > d {
+                d = c;
+                level = m + 1;
+            }
+        }
+        (
+            d,
+            (trap / 3f64.sqrt()).clamp(0.0, 1.0),
+            level as f64 / self.iterations.max(1) as f64,
+        )
+    }
+}
+
+impl DistanceField for MengerSponge {
+    fn distance(&self, p: &Point3) -> f64 {
+        self.iterate(p).0
+    }
+
+    fn bounds(&self) -> Aabb {
+        let r = Vec3::repeat(1.0 + 1e-3);
+        Aabb::new_point(Point3::from(-r), Point3::from(r))
+    }
+
+    fn coloring(&self, p: &Point3) -> (f64, f64) {
+        let (_, trap, level) = self.iterate(p);
+        (trap, level)
+    }
+}
+
+/// 谢尔宾斯基四面体：顶点为 (1,1,1)、(-1,-1,1)、(1,-1,-1)、(-1,1,-1) 的四面体反复折叠
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SierpinskiTetrahedron {
+    /// 折叠次数
+    pub iterations: usize,
+}
+
+impl Default for SierpinskiTetrahedron {
+    fn default() -> Self {
+        Self { iterations: 10 }
+    }
+}
+
+impl SierpinskiTetrahedron {
+    /// 距离估计和轨道陷阱
+    fn iterate(&self, p: &Point3) -> (f64, f64) {
+        let mut z = p.coords;
+        let mut trap = f64::INFINITY;
+        for _ in 0..self.iterations {
+            if z.x + z.y < 0.0 {
+                (z.x, z.y) = (-z.y, -z.x);
+            }
+            if z.x + z.z < 0.0 {
+                (z.x, z.z) = (-z.z, -z.x);
+            }
+            if z.y + z.z < 0.0 {
+                (z.y, z.z) = (-z.z, -z.y);
+            }
+            z = 2.0 * z - Vec3::repeat(1.0);
+            trap = trap.min(z.norm());
+        }
+        let distance = (z.norm() - 2.0) * 2f64.powi(-(self.iterations as i32));
+        (distance, (trap / 2.0).clamp(0.0, 1.0))
+    }
+}
+
+impl DistanceField for SierpinskiTetrahedron {
+    fn distance(&self, p: &Point3) -> f64 {
+        self.iterate(p).0
+    }
+
+    fn bounds(&self) -> Aabb {
+        let r = Vec3::repeat(1.05);
+        Aabb::new_point(Point3::from(-r), Point3::from(r))
+    }
+
+    fn coloring(&self, p: &Point3) -> (f64, f64) {
+        (self.iterate(p).1, 0.0)
+    }
+}
+
+/// 余弦调色板：color(t) = a + b·cos(2π(c·t + d))，t 取纹理坐标 u
+///
+/// 与分形的轨道陷阱配合，得到随结构层次连续变化的颜色。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CosinePalette {
+    pub a: Color,
+    pub b: Color,
+    pub c: Color,
+    pub d: Color,
+}
+
+impl CosinePalette {
+    /// 彩虹色
+    pub fn rainbow() -> Self {
+        Self {
+            a: Color::repeat(0.5),
+            b: Color::repeat(0.5),
+            c: Color::repeat(1.0),
+            d: Color::new(0.0, 0.33, 0.67),
+        }
+    }
+
+    /// 暖色：t 从 0 到 0.5 由暗红变为橙黄
+    pub fn fire() -> Self {
+        Self {
+            a: Color::new(0.55, 0.3, 0.12),
+            b: Color::new(0.45, 0.35, 0.12),
+            c: Color::repeat(1.0),
+            d: Color::new(0.5, 0.55, 0.6),
+        }
+    }
+
+    /// 冷色：从深蓝到青白
+    pub fn ice() -> Self {
+        Self {
+            a: Color::new(0.35, 0.5, 0.65),
+            b: Color::new(0.3, 0.3, 0.3),
+            c: Color::new(1.0, 1.0, 1.0),
+            d: Color::new(0.5, 0.45, 0.4),
+        }
+    }
+
+    /// t 处的颜色
+    pub fn at(&self, t: f64) -> Color {
+        Color::new(
+            self.a.x + self.b.x * (TAU * (self.c.x * t + self.d.x)).cos(),
+            self.a.y + self.b.y * (TAU * (self.c.y * t + self.d.y)).cos(),
+            self.a.z + self.b.z * (TAU * (self.c.z * t + self.d.z)).cos(),
+        )
+        .map(|c| c.clamp(0.0, 1.0))
+    }
+}
+
+impl Texture for CosinePalette {
+    fn value(&self, u: f64, _v: f64, _p: &Point3) -> Color {
+        self.at(u)
+    }
+}
@@ -1 +1,4 @@
+pub mod fractals;
 pub mod noise;
+pub mod simplex;
+pub mod worley;
@@ -1,5 +1,21 @@
 use crate::ray_tracing::math::vec3::{Point3, Vec3, Vec3Ext};
-use crate::ray_tracing::utils::random::random_int_range;
+use crate::ray_tracing::utils::random::{random_int_range, with_seeded_stream};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// 全局帧时间（f64 的位模式），动画噪声纹理在此基础上加上光线时刻
+static FRAME_TIME: AtomicU64 = AtomicU64::new(0);
+
+/// 设置全局帧时间（以帧为单位），批量渲染动画时在每帧开始前设置
+///
+/// 光线时刻在快门区间 [0, 1) 内取值，恰好覆盖一帧，因此纹理看到的时间为 帧时间 + 光线时刻。
+pub fn set_frame_time(time: f64) {
+    FRAME_TIME.store(time.to_bits(), Ordering::Relaxed);
+}
+
+/// 当前的全局帧时间
+pub fn frame_time() -> f64 {
+    f64::from_bits(FRAME_TIME.load(Ordering::Relaxed))
+}
 
 /// Perlin噪声生成器，用于程序化纹理
 #[derive(Debug)]
@@ -8,12 +24,36 @@ pub struct Perlin {
     perm_x: Vec<i32>,
     perm_y: Vec<i32>,
     perm_z: Vec<i32>,
+    // 四维噪声的时间轴：由前三个置换表和梯度表派生，不额外消耗随机数
+    ranvec4: Vec<[f64; 4]>,
+    perm_w: Vec<i32>,
+    // 生成梯度表和置换表的种子，None 表示由系统随机数生成、无法复现
+    seed: Option<u64>,
 }
 
 impl Perlin {
     /// 创建新的Perlin噪声生成器
     #[inline]
     pub fn new() -> Self {
+        Self::generate()
+    }
+
+    /// 用种子创建，相同种子生成相同的噪声
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            seed: Some(seed),
+            ..with_seeded_stream(seed, Self::generate)
+        }
+    }
+
+    /// 生成时使用的种子，由系统随机数生成时为None
+    #[inline]
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// 从当前线程的随机数流生成梯度表和置换表
+    fn generate() -> Self {
         const POINT_COUNT: usize = 256;
 
         // 生成随机梯度向量
@@ -22,11 +62,29 @@ impl Perlin {
             ranvec.push(Vec3::random_range(-1.0, 1.0).normalize());
         }
 
+        let perm_x = Self::generate_perm(POINT_COUNT);
+        let perm_y = Self::generate_perm(POINT_COUNT);
+        let perm_z = Self::generate_perm(POINT_COUNT);
+
+        // 置换的复合仍是置换；时间分量取另一个梯度的分量，再把四维梯度归一化
+        let perm_w = perm_y.iter().map(|&i| perm_x[i as usize]).collect();
+        let ranvec4 = (0..POINT_COUNT)
+            .map(|i| {
+                let g = ranvec[i];
+                let w = ranvec[perm_z[i] as usize].x;
+                let norm = (g.norm_squared() + w * w).sqrt();
+                [g.x / norm, g.y / norm, g.z / norm, w / norm]
+            })
+            .collect();
+
         Self {
             ranvec,
-            perm_x: Self::generate_perm(POINT_COUNT),
-            perm_y: Self::generate_perm(POINT_COUNT),
-            perm_z: Self::generate_perm(POINT_COUNT),
+            perm_x,
+            perm_y,
+            perm_z,
+            ranvec4,
+            perm_w,
+            seed: None,
         }
     }
 
@@ -75,6 +133,52 @@ impl Perlin {
         accum.abs()
     }
 
+    /// 计算点p在时刻t的四维噪声值，t 连续变化时图案平滑演变
+    pub fn noise4(&self, p: &Point3, t: f64) -> f64 {
+        let f = [p.x, p.y, p.z, t];
+        let cell = f.map(|c| c.floor() as i32);
+        let frac: [f64; 4] = std::array::from_fn(|a| f[a] - f[a].floor());
+        // Hermite平滑
+        let smooth = frac.map(|c| c * c * (3.0 - 2.0 * c));
+
+        let mut accum = 0.0;
+        for corner in 0..16 {
+            let d: [i32; 4] = std::array::from_fn(|a| (corner >> a) & 1);
+            let idx = self.perm_x[((cell[0] + d[0]) & 255) as usize]
+                ^ self.perm_y[((cell[1] + d[1]) & 255) as usize]
+                ^ self.perm_z[((cell[2] + d[2]) & 255) as usize]
+                ^ self.perm_w[((cell[3] + d[3]) & 255) as usize];
+            let g = &self.ranvec4[idx as usize];
+
+            let mut weight = 1.0;
+            let mut dot = 0.0;
+            for a in 0..4 {
+                let da = d[a] as f64;
+                weight *= da * smooth[a] + (1.0 - da) * (1.0 - smooth[a]);
+                dot += g[a] * (frac[a] - da);
+            }
+            accum += weight * dot;
+        }
+        accum
+    }
+
+    /// 四维湍流函数，时间轴与空间一起按倍频缩放
+    pub fn turb4(&self, p: &Point3, t: f64, depth: i32) -> f64 {
+        let mut accum = 0.0;
+        let mut temp_p = *p;
+        let mut temp_t = t;
+        let mut weight = 1.0;
+
+        for _ in 0..depth {
+            accum += weight * self.noise4(&temp_p, temp_t);
+            weight *= 0.5;
+            temp_p *= 2.0;
+            temp_t *= 2.0;
+        }
+
+        accum.abs()
+    }
+
     /// 生成置换表
     #[inline]
     fn generate_perm(point_count: usize) -> Vec<i32> {
@@ -0,0 +1,112 @@
+//! 三维单形（simplex）噪声
+//!
+//! 与 Perlin 噪声相比，单形噪声只在包含点的四面体的 4 个顶点上求和（Perlin 为立方体的 8 个角），
+//! 没有明显的轴向纹理，结果范围约为 [-1, 1]。相同的种子总是得到相同的噪声。
+
+use crate::ray_tracing::math::vec3::Point3;
+use crate::ray_tracing::utils::random::Pcg32;
+
+/// 立方体 12 条棱的中点方向，作为梯度
+const GRADIENTS: [[f64; 3]; 12] = [
+    [1.0, 1.0, 0.0],
+    [-1.0, 1.0, 0.0],
+    [1.0, -1.0, 0.0],
+    [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0],
+    [-1.0, 0.0, 1.0],
+    [1.0, 0.0, -1.0],
+    [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0],
+    [0.0, -1.0, 1.0],
+    [0.0, 1.0, -1.0],
+    [0.0, -1.0, -1.0],
+];
+
+/// 斜切系数：把立方体网格变换为单形网格
+const SKEW: f64 = 1.0 / 3.0;
+const UNSKEW: f64 = 1.0 / 6.0;
+
+/// 单形噪声生成器
+#[derive(Debug, Clone)]
+pub struct Simplex {
+    perm: [u8; 512],
+}
+
+impl Simplex {
+    /// 用种子生成置换表
+    pub fn new(seed: u64) -> Self {
+        let mut rng = Pcg32::new(seed);
+        let mut table: [u8; 256] = std::array::from_fn(|i| i as u8);
+        for i in (1..256).rev() {
+            let j = (rng.next_u32() % (i as u32 + 1)) as usize;
+            table.swap(i, j);
+        }
+        Self {
+            perm: std::array::from_fn(|i| table[i & 255]),
+        }
+    }
+
+    /// 点p处的噪声值，约在 [-1, 1] 内
+    pub fn noise(&self, p: &Point3) -> f64 {
+        // 找到点所在的单形网格单元
+        let s = (p.x + p.y + p.z) * SKEW;
+        let (i, j, k) = ((p.x + s).floor(), (p.y + s).floor(), (p.z + s).floor());
+        let t = (i + j + k) * UNSKEW;
+        let x0 = [p.x - (i - t), p.y - (j - t), p.z - (k - t)];
+
+        // 按坐标大小次序确定所在的四面体
+        let (o1, o2) = if x0[0] >= x0[1] {
+            if x0[1] >= x0[2] {
+                ([1, 0, 0], [1, 1, 0])
+            } else if x0[0] >= x0[2] {
+                ([1, 0, 0], [1, 0, 1])
+            } else {
+                ([0, 0, 1], [1, 0, 1])
+            }
+        } else if x0[1] < x0[2] {
+            ([0, 0, 1], [0, 1, 1])
+        } else if x0[0] < x0[2] {
+            ([0, 1, 0], [0, 1, 1])
+        } else {
+            ([0, 1, 0], [1, 1, 0])
+        };
+
+        let cell = [i as i64 & 255, j as i64 & 255, k as i64 & 255].map(|c| c as usize);
+        let corners = [[0, 0, 0], o1, o2, [1, 1, 1]];
+        corners
+            .iter()
+            .enumerate()
+            .map(|(n, o)| {
+                let offset = n as f64 * UNSKEW;
+                let d: [f64; 3] = std::array::from_fn(|a| x0[a] - o[a] as f64 + offset);
+                let falloff = 0.6 - d[0] * d[0] - d[1] * d[1] - d[2] * d[2];
+                if falloff <= 0.0 {
+                    return 0.0;
+                }
+                let gi = self.perm[cell[0]
+                    + o[0]
+                    + self.perm[cell[1] + o[1] + self.perm[cell[2] + o[2]] as usize] as usize]
+                    as usize
+                    % 12;
+                let g = GRADIENTS[gi];
+                falloff.powi(4) * (g[0] * d[0] + g[1] * d[1] + g[2] * d[2])
+            })
+            .sum::<f64>()
+            * 32.0
+    }
+
+    /// 分形布朗运动：octaves 层噪声叠加，每层频率加倍、振幅减半，结果约在 [-1, 1] 内
+    pub fn fbm(&self, p: &Point3, octaves: u32) -> f64 {
+        let mut sum = 0.0;
+        let mut amplitude = 1.0;
+        let mut total = 0.0;
+        let mut q = *p;
+        for _ in 0..octaves.max(1) {
+            sum += amplitude * self.noise(&q);
+            total += amplitude;
+            amplitude *= 0.5;
+            q *= 2.0;
+        }
+        sum / total
+    }
+}
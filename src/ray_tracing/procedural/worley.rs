@@ -0,0 +1,179 @@
+//! Worley（细胞）噪声
+//!
+//! 空间划分为单位立方体网格，每格有一个随机特征点；点的噪声值取它到最近特征点的距离 F1
+//! 和到次近特征点的距离 F2。F1 得到石子、细胞状的图案，F2 − F1 在细胞边界处为零，
+//! 得到龟裂的泥地、鳞片和水下焦散般的网纹。
+
+use crate::ray_tracing::math::vec3::{Point3, Vec3};
+use crate::ray_tracing::utils::random::{Pcg32, hash_seed};
+use std::fmt;
+use std::str::FromStr;
+
+/// 到特征点距离的度量方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DistanceMetric {
+    /// 欧氏距离，圆润的细胞
+    #[default]
+    Euclidean,
+    /// 曼哈顿距离，菱形的细胞
+    Manhattan,
+    /// 切比雪夫距离，方块状的细胞
+    Chebyshev,
+}
+
+impl DistanceMetric {
+    /// 向量的长度
+    #[inline]
+    pub fn length(&self, d: &Vec3) -> f64 {
+        match self {
+            Self::Euclidean => d.norm(),
+            Self::Manhattan => d.x.abs() + d.y.abs() + d.z.abs(),
+            Self::Chebyshev => d.x.abs().max(d.y.abs()).max(d.z.abs()),
+        }
+    }
+}
+
+impl FromStr for DistanceMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "euclidean" => Ok(Self::Euclidean),
+            "manhattan" => Ok(Self::Manhattan),
+            "chebyshev" => Ok(Self::Chebyshev),
+            _ => Err(format!(
+                "无效的距离度量 `{}`（可选: euclidean, manhattan, chebyshev）",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for DistanceMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Euclidean => "euclidean",
+            Self::Manhattan => "manhattan",
+            Self::Chebyshev => "chebyshev",
+        })
+    }
+}
+
+/// 由 F1、F2 组合出的噪声值
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WorleyFeature {
+    /// 到最近特征点的距离
+    #[default]
+    F1,
+    /// 到次近特征点的距离
+    F2,
+    /// F2 − F1，细胞边界处为零
+    F2MinusF1,
+}
+
+impl FromStr for WorleyFeature {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "f1" => Ok(Self::F1),
+            "f2" => Ok(Self::F2),
+            "f2-f1" => Ok(Self::F2MinusF1),
+            _ => Err(format!("无效的 Worley 特征 `{}`（可选: f1, f2, f2-f1）", s)),
+        }
+    }
+}
+
+impl fmt::Display for WorleyFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::F1 => "f1",
+            Self::F2 => "f2",
+            Self::F2MinusF1 => "f2-f1",
+        })
+    }
+}
+
+/// Worley 噪声生成器
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Worley {
+    seed: u64,
+    /// 特征点在格内的随机偏移幅度，0 为规则网格，1 为完全随机
+    pub jitter: f64,
+    /// 距离度量
+    pub metric: DistanceMetric,
+}
+
+impl Worley {
+    /// 用种子创建，特征点完全随机、使用欧氏距离
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            jitter: 1.0,
+            metric: DistanceMetric::Euclidean,
+        }
+    }
+
+    /// 生成特征点的种子
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// 设置距离度量
+    pub fn with_metric(mut self, metric: DistanceMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// 设置特征点的随机偏移幅度
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// 格 (i, j, k) 的特征点
+    fn feature_point(&self, cell: [i64; 3]) -> Point3 {
+        let mut rng = Pcg32::new(hash_seed(&[
+            self.seed,
+            cell[0] as u64,
+            cell[1] as u64,
+            cell[2] as u64,
+        ]));
+        let mut offset = || 0.5 + self.jitter * (rng.next_f64() - 0.5);
+        Point3::new(
+            cell[0] as f64 + offset(),
+            cell[1] as f64 + offset(),
+            cell[2] as f64 + offset(),
+        )
+    }
+
+    /// 点p到最近和次近特征点的距离 (F1, F2)
+    pub fn distances(&self, p: &Point3) -> (f64, f64) {
+        let base = [p.x.floor() as i64, p.y.floor() as i64, p.z.floor() as i64];
+        let (mut f1, mut f2) = (f64::INFINITY, f64::INFINITY);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let feature = self.feature_point([base[0] + dx, base[1] + dy, base[2] + dz]);
+                    let d = self.metric.length(&(feature - p));
+                    if d < f1 {
+                        (f1, f2) = (d, f1);
+                    } else if d < f2 {
+                        f2 = d;
+                    }
+                }
+            }
+        }
+        (f1, f2)
+    }
+
+    /// 点p处所选特征的值
+    pub fn evaluate(&self, p: &Point3, feature: WorleyFeature) -> f64 {
+        let (f1, f2) = self.distances(p);
+        match feature {
+            WorleyFeature::F1 => f1,
+            WorleyFeature::F2 => f2,
+            WorleyFeature::F2MinusF1 => f2 - f1,
+        }
+    }
+}
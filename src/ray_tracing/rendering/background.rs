@@ -0,0 +1,194 @@
+use super::environment_bake::EnvironmentBake;
+use crate::ray_tracing::geometry::sphere::Sphere;
+use crate::ray_tracing::materials::texture::TexturePtr;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+
+/// 场景背景：光线未命中任何物体时返回的辐射度
+pub trait Background: Send + Sync + std::fmt::Debug {
+    fn value(&self, r: &Ray) -> Color;
+
+    /// 预滤波的背景查询，lod 为 mip 级别（0为原始分辨率）；没有烘焙数据时返回未滤波的值
+    #[inline]
+    fn value_lod(&self, r: &Ray, _lod: f64) -> Color {
+        self.value(r)
+    }
+
+    /// 预烘焙的环境辐照度（法线为n、不考虑遮挡），没有烘焙数据时返回None
+    #[inline]
+    fn irradiance(&self, _n: &Vec3) -> Option<Color> {
+        None
+    }
+}
+
+/// 纯色背景
+#[derive(Debug, Clone)]
+pub struct ConstantColor {
+    color: Color,
+}
+
+impl ConstantColor {
+    #[inline]
+    pub const fn new(color: Color) -> Self {
+        Self { color }
+    }
+}
+
+impl Background for ConstantColor {
+    #[inline]
+    fn value(&self, _r: &Ray) -> Color {
+        self.color
+    }
+}
+
+/// 垂直渐变天空（《一周末》中的白-蓝渐变）
+#[derive(Debug, Clone)]
+pub struct VerticalGradient {
+    bottom: Color,
+    top: Color,
+}
+
+impl VerticalGradient {
+    #[inline]
+    pub const fn new(bottom: Color, top: Color) -> Self {
+        Self { bottom, top }
+    }
+
+    /// 书中的默认天空颜色
+    #[inline]
+    pub fn sky() -> Self {
+        Self::new(Color::new(1.0, 1.0, 1.0), Color::new(0.5, 0.7, 1.0))
+    }
+}
+
+impl Background for VerticalGradient {
+    #[inline]
+    fn value(&self, r: &Ray) -> Color {
+        let unit_direction = r.dir.normalize();
+        let a = 0.5 * (unit_direction.y + 1.0);
+        (1.0 - a) * self.bottom + a * self.top
+    }
+}
+
+/// 经纬度环境贴图，方向到UV的映射与球体纹理坐标一致
+///
+/// 创建时预烘焙 mip 链和辐照度图，供相机的环境光快速模式使用。
+#[derive(Debug)]
+pub struct EnvironmentMap {
+    texture: TexturePtr,
+    intensity: f64,
+    bake: EnvironmentBake,
+}
+
+impl EnvironmentMap {
+    pub fn new(texture: TexturePtr, intensity: f64) -> Self {
+        let bake = EnvironmentBake::new(|d| Self::lookup(&texture, intensity, d));
+        Self {
+            texture,
+            intensity,
+            bake,
+        }
+    }
+
+    /// 烘焙数据
+    #[inline]
+    pub fn bake(&self) -> &EnvironmentBake {
+        &self.bake
+    }
+
+    #[inline]
+    fn lookup(texture: &TexturePtr, intensity: f64, d: &Vec3) -> Color {
+        let (u, v) = Sphere::get_sphere_uv(d);
+        intensity * texture.value(u, v, &Point3::from(*d))
+    }
+}
+
+impl Background for EnvironmentMap {
+    fn value(&self, r: &Ray) -> Color {
+        Self::lookup(&self.texture, self.intensity, &r.dir.normalize())
+    }
+
+    #[inline]
+    fn value_lod(&self, r: &Ray, lod: f64) -> Color {
+        if lod <= 0.0 {
+            self.value(r)
+        } else {
+            self.bake.radiance(&r.dir, lod)
+        }
+    }
+
+    #[inline]
+    fn irradiance(&self, n: &Vec3) -> Option<Color> {
+        Some(self.bake.irradiance(n))
+    }
+}
+
+/// 简化的物理天空模型：瑞利散射天空 + 米氏散射太阳光晕 + 太阳圆盘
+///
+/// 光学厚度用 Kasten-Young 大气质量公式近似，适合快速获得随太阳高度变化的自然天色。
+#[derive(Debug, Clone)]
+pub struct PhysicalSky {
+    sun_direction: Vec3,
+    sun_intensity: f64,
+    turbidity: f64,
+}
+
+impl PhysicalSky {
+    /// 瑞利散射系数（对应 680/550/440nm，按 λ^-4 归一化）
+    const RAYLEIGH: [f64; 3] = [0.1, 0.23, 0.55];
+    /// 太阳角半径的余弦
+    const SUN_COS_RADIUS: f64 = 0.99996;
+
+    /// 从太阳方向、强度和浑浊度（1为晴朗）创建天空
+    #[inline]
+    pub fn new(sun_direction: Vec3, sun_intensity: f64, turbidity: f64) -> Self {
+        Self {
+            sun_direction: sun_direction.normalize(),
+            sun_intensity,
+            turbidity: turbidity.max(1.0),
+        }
+    }
+
+    /// 沿天顶角余弦为 cos_zenith 的方向穿过大气的相对质量
+    #[inline]
+    fn air_mass(cos_zenith: f64) -> f64 {
+        let cos_zenith = cos_zenith.max(0.0);
+        let zenith_deg = cos_zenith.acos().to_degrees();
+        1.0 / (cos_zenith + 0.50572 * (96.07995 - zenith_deg).max(1e-3).powf(-1.6364))
+    }
+}
+
+impl Background for PhysicalSky {
+    fn value(&self, r: &Ray) -> Color {
+        let d = r.dir.normalize();
+        let cos_gamma = d.dot(&self.sun_direction).clamp(-1.0, 1.0);
+
+        // 地平线以下逐渐变暗
+        let view_mass = Self::air_mass(d.y.max(0.02));
+        let sun_mass = Self::air_mass(self.sun_direction.y);
+
+        let rayleigh_phase = 3.0 / (16.0 * std::f64::consts::PI) * (1.0 + cos_gamma * cos_gamma);
+        let g = 0.76;
+        let mie_phase = (1.0 - g * g)
+            / (4.0 * std::f64::consts::PI * (1.0 + g * g - 2.0 * g * cos_gamma).powf(1.5));
+        let mie = 0.02 * self.turbidity;
+
+        let mut color = Color::zeros();
+        for (c, beta) in Self::RAYLEIGH.iter().enumerate() {
+            let sun_transmittance = (-(beta + mie) * sun_mass).exp();
+            let in_scatter = 1.0 - (-(beta + mie) * view_mass).exp();
+            let scatter = (beta * rayleigh_phase + mie * mie_phase) / (beta + mie);
+            let mut value = self.sun_intensity * sun_transmittance * in_scatter * scatter;
+
+            if cos_gamma > Self::SUN_COS_RADIUS && d.y > 0.0 {
+                value += self.sun_intensity * sun_transmittance * 100.0;
+            }
+            color[c] = value;
+        }
+
+        if d.y < 0.0 {
+            color *= (1.0 + d.y * 4.0).max(0.0);
+        }
+        color
+    }
+}
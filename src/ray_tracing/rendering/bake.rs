@@ -0,0 +1,260 @@
+//! 光照贴图烘焙：在物体的纹理空间而不是相机画面中积分光照
+//!
+//! 把物体的纹理空间三角形（[`Hittable::uv_triangles`]）光栅化到贴图上，每个纹素在对应的表面点
+//! 上使用相机的积分器（[`Camera::trace_radiance`]）采样，生成可供实时引擎使用的光照贴图。
+//! 贴图的行方向与 `ImageTexture` 一致：第0行对应 v = 1。
+
+use super::camera::Camera;
+use super::framebuffer::FrameBuffer;
+use crate::ray_tracing::geometry::hittable::{Hittable, UvTriangle};
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::sampling::pdf::{CosinePDF, HittablePDF, MixturePDF, PDF};
+use crate::ray_tracing::utils::random::{hash_seed, random_double, with_seeded_stream};
+use rayon::prelude::*;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// 判断样本是否落在三角形内时允许的重心坐标误差，避免相邻三角形的公共边上出现缝隙
+const EDGE_EPSILON: f64 = 1e-6;
+/// 出射辐射度模式下光线起点沿法线抬高的距离（须大于积分器的最小命中距离 0.001）
+const RADIANCE_OFFSET: f64 = 2e-3;
+
+/// 烘焙的物理量
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BakeMode {
+    /// 入射辐照度 E/π：与表面材质无关，乘以反照率即得漫反射表面的出射辐射度
+    #[default]
+    Irradiance,
+    /// 沿法线方向的完整出射辐射度（包含材质颜色、自发光和镜面反射）
+    Radiance,
+}
+
+/// 烘焙设置
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BakeSettings {
+    pub width: u32,
+    pub height: u32,
+    pub samples_per_texel: u32,
+    pub mode: BakeMode,
+    /// 向未覆盖的纹素扩展的像素数，避免双线性过滤时 UV 岛边缘混入背景色
+    pub dilation: u32,
+}
+
+impl Default for BakeSettings {
+    fn default() -> Self {
+        Self {
+            width: 512,
+            height: 512,
+            samples_per_texel: 64,
+            mode: BakeMode::Irradiance,
+            dilation: 2,
+        }
+    }
+}
+
+/// 烘焙结果
+#[derive(Debug, Clone)]
+pub struct Lightmap {
+    /// 线性 HDR 光照贴图
+    pub image: FrameBuffer,
+    /// 被物体表面覆盖的纹素数（不含扩展的边缘）
+    pub covered_texels: usize,
+}
+
+/// 烘焙 target 的光照贴图，world 和 lights 为完整场景（通常包含 target 本身）
+///
+/// target 不提供纹理空间三角形（如球体、体积）时返回None。相机只提供积分器设置
+/// （背景、最大深度、随机种子等），其位置和画面参数不影响结果。
+pub fn bake_lightmap(
+    camera: &Camera,
+    target: &dyn Hittable,
+    world: &dyn Hittable,
+    lights: Option<Arc<dyn Hittable>>,
+    settings: &BakeSettings,
+) -> Option<Lightmap> {
+    let triangles = target.uv_triangles();
+    if triangles.is_empty() || settings.width == 0 || settings.height == 0 {
+        return None;
+    }
+
+    let (width, height) = (settings.width as usize, settings.height as usize);
+    let candidates = rasterize(&triangles, width, height);
+    let spp = settings.samples_per_texel.max(1);
+    let lights = lights.as_ref();
+
+    // 逐行并行烘焙，每个纹素返回 (颜色, 是否被覆盖)
+    let texels: Vec<(Color, bool)> = (0..height)
+        .into_par_iter()
+        .flat_map_iter(|y| {
+            let candidates = &candidates;
+            let triangles = &triangles;
+            (0..width).map(move |x| {
+                let list = &candidates[y * width + x];
+                if list.is_empty() {
+                    return (Color::zeros(), false);
+                }
+                let bake =
+                    || bake_texel(camera, world, lights, settings, triangles, list, x, y, spp);
+                match camera.seed() {
+                    Some(seed) => with_seeded_stream(hash_seed(&[seed, x as u64, y as u64]), bake),
+                    None => bake(),
+                }
+            })
+        })
+        .collect();
+
+    let mut colors: Vec<Color> = texels.iter().map(|t| t.0).collect();
+    let mut covered: Vec<bool> = texels.iter().map(|t| t.1).collect();
+    let covered_texels = covered.iter().filter(|&&c| c).count();
+    dilate(&mut colors, &mut covered, width, height, settings.dilation);
+
+    let mut image = FrameBuffer::new(settings.width, settings.height);
+    for y in 0..height {
+        for x in 0..width {
+            image.set(x as u32, y as u32, colors[y * width + x]);
+        }
+    }
+    Some(Lightmap {
+        image,
+        covered_texels,
+    })
+}
+
+/// 纹理坐标 (u, v) 对应的贴图连续坐标（第0行为 v = 1）
+#[inline]
+fn uv_to_texel(u: f64, v: f64, width: usize, height: usize) -> (f64, f64) {
+    (u * width as f64, (1.0 - v) * height as f64)
+}
+
+/// 列出与每个纹素的 UV 包围盒重叠的三角形
+fn rasterize(triangles: &[UvTriangle], width: usize, height: usize) -> Vec<Vec<u32>> {
+    let mut candidates = vec![Vec::new(); width * height];
+    for (index, triangle) in triangles.iter().enumerate() {
+        let corners = triangle.uv.map(|(u, v)| uv_to_texel(u, v, width, height));
+        let (mut x0, mut y0) = (f64::INFINITY, f64::INFINITY);
+        let (mut x1, mut y1) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for (x, y) in corners {
+            x0 = x0.min(x);
+            y0 = y0.min(y);
+            x1 = x1.max(x);
+            y1 = y1.max(y);
+        }
+        // 只烘焙落在 [0,1]² 内的部分
+        let xs = x0.floor().max(0.0) as usize..(x1.ceil().min(width as f64).max(0.0) as usize);
+        let ys = y0.floor().max(0.0) as usize..(y1.ceil().min(height as f64).max(0.0) as usize);
+        for y in ys {
+            for x in xs.clone() {
+                candidates[y * width + x].push(index as u32);
+            }
+        }
+    }
+    candidates
+}
+
+/// 在纹素内抖动采样，返回 (平均颜色, 是否至少有一个样本落在表面上)
+#[allow(clippy::too_many_arguments)]
+fn bake_texel(
+    camera: &Camera,
+    world: &dyn Hittable,
+    lights: Option<&Arc<dyn Hittable>>,
+    settings: &BakeSettings,
+    triangles: &[UvTriangle],
+    candidates: &[u32],
+    x: usize,
+    y: usize,
+    spp: u32,
+) -> (Color, bool) {
+    let (width, height) = (settings.width as f64, settings.height as f64);
+    let mut sum = Color::zeros();
+    let mut count = 0;
+    for _ in 0..spp {
+        let u = (x as f64 + random_double()) / width;
+        let v = 1.0 - (y as f64 + random_double()) / height;
+        let Some((triangle, b)) = candidates.iter().find_map(|&i| {
+            let triangle = &triangles[i as usize];
+            triangle
+                .barycentric(u, v, EDGE_EPSILON)
+                .map(|b| (triangle, b))
+        }) else {
+            continue;
+        };
+
+        let p = triangle.point(&b);
+        let n = triangle.normal(&b);
+        let radiance = match settings.mode {
+            // 与积分器相同，混合光源和余弦采样：E/π 的估计为 L·cosθ/(π·pdf)
+            BakeMode::Irradiance => {
+                let cosine_pdf = CosinePDF::new(&n);
+                let (direction, pdf) = match lights {
+                    Some(light_objects) => {
+                        let light_pdf = HittablePDF::new(light_objects.as_ref(), &p, 0.0);
+                        let mixture = MixturePDF::new(&light_pdf, &cosine_pdf);
+                        let direction = mixture.generate();
+                        (direction, mixture.value(&direction))
+                    }
+                    None => {
+                        let direction = cosine_pdf.generate();
+                        (direction, cosine_pdf.value(&direction))
+                    }
+                };
+                let cos_theta = direction.normalize().dot(&n);
+                if cos_theta <= 0.0 || pdf < 1e-6 || !pdf.is_finite() {
+                    count += 1;
+                    continue;
+                }
+                camera.trace_radiance(&Ray::new(p, direction, 0.0), world, lights) * cos_theta
+                    / (PI * pdf)
+            }
+            // 从法线方向略高处向表面发射光线，得到表面朝该方向的出射辐射度
+            BakeMode::Radiance => {
+                let origin = p + n * RADIANCE_OFFSET;
+                camera.trace_radiance(&Ray::new(origin, -n, 0.0), world, lights)
+            }
+        };
+        if radiance.iter().all(|c| c.is_finite()) {
+            sum += radiance;
+            count += 1;
+        }
+    }
+
+    if count == 0 {
+        (Color::zeros(), false)
+    } else {
+        (sum / count as f64, true)
+    }
+}
+
+/// 用已覆盖的相邻纹素的平均值逐圈填充未覆盖的纹素
+fn dilate(colors: &mut [Color], covered: &mut [bool], width: usize, height: usize, passes: u32) {
+    for _ in 0..passes {
+        let mut filled = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                if covered[y * width + x] {
+                    continue;
+                }
+                let mut sum = Color::zeros();
+                let mut count = 0;
+                for ny in y.saturating_sub(1)..(y + 2).min(height) {
+                    for nx in x.saturating_sub(1)..(x + 2).min(width) {
+                        if covered[ny * width + nx] {
+                            sum += colors[ny * width + nx];
+                            count += 1;
+                        }
+                    }
+                }
+                if count > 0 {
+                    filled.push((y * width + x, sum / count as f64));
+                }
+            }
+        }
+        if filled.is_empty() {
+            break;
+        }
+        for (index, color) in filled {
+            colors[index] = color;
+            covered[index] = true;
+        }
+    }
+}
@@ -1,42 +1,120 @@
-use super::color::color_to_rgb_with_samples;
+use super::background::{Background, ConstantColor};
+use super::caustics::{CausticMap, CausticSettings};
+use super::color::luminance;
+use super::color_space::OutputColorSpace;
+use super::denoise::{AovBuffers, DenoiseSettings, denoise};
+use super::dof_sampling::DofSampling;
+use super::exposure::PhysicalExposure;
+use super::filter::{Filter, SplatTile};
+use super::framebuffer::FrameBuffer;
+use super::halt::{HaltCondition, PassAccumulator, PassInfo};
+use super::integrator::{BasisView, Integrator, ambient_occlusion, basis_color};
+use super::irradiance_cache::{IrradianceCache, IrradianceCacheSettings};
+use super::output::{OutputFormat, save_framebuffer};
+use super::progress::{Progress, ProgressSink};
+use super::projection::Projection;
+use super::report::{self, ProgressLog, RenderReport};
+use super::shutter::Shutter;
+use super::stats::RenderStats;
+use super::termination::TerminationPolicy;
+use super::tile_cache::{self, Crop, TileCache, TileLookup};
+use super::wireframe::{BoundsOverlay, box_edges, depth_color, draw_line};
 use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
 use crate::ray_tracing::materials::material::ScatterRecord;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::differential::RayDifferential;
 use crate::ray_tracing::math::interval::Interval;
-use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::ray::{Ray, RayKind};
 use crate::ray_tracing::math::vec3::*;
-use crate::ray_tracing::sampling::pdf::{HittablePDF, MixturePDF, PDF};
-use crate::ray_tracing::utils::random::{degrees_to_radians, random_double, random_double_range};
-use image::RgbImage;
+use crate::ray_tracing::sampling::pdf::{CosinePDF, HittablePDF, MixturePDF, PDF};
+use crate::ray_tracing::scene::ray_cast::{RAY_EPSILON, RayCast};
+use crate::ray_tracing::scene::world::Scene;
+use crate::ray_tracing::utils::config;
+use crate::ray_tracing::utils::random::{
+    degrees_to_radians, hash_seed, random_double, with_seeded_stream,
+};
 use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
+use std::f64::consts::PI;
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
 
 /// 相机配置和渲染器
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Camera {
-    // 公共配置参数
-    pub aspect_ratio: f64,
-    pub image_width: i32,
-    pub samples_per_pixel: i32,
-    pub max_depth: i32,
-    pub background: Color,
-    pub output_filename: String,
+    // 配置参数（私有，通过同名访问器和 set_ 方法读写）
+    aspect_ratio: f64,
+    image_width: i32,
+    samples_per_pixel: i32,
+    /// 最大反弹深度，纯轮盘赌终止策略下不生效
+    max_depth: i32,
+    background: Arc<dyn Background>,
+    output_filename: String,
+    /// 输出格式，为None时根据文件扩展名推断
+    output_format: Option<OutputFormat>,
+    /// 输出色彩空间（原色和传递函数），默认取全局配置
+    output_color_space: OutputColorSpace,
 
     // 相机位置和方向
-    pub vfov: f64,
-    pub lookfrom: Point3,
-    pub lookat: Point3,
-    pub vup: Vec3,
+    vfov: f64,
+    lookfrom: Point3,
+    lookat: Point3,
+    vup: Vec3,
 
     // 景深参数
-    pub defocus_angle: f64,
-    pub focus_dist: f64,
+    defocus_angle: f64,
+    focus_dist: f64,
+    /// 投影方式：透视、小行星或移轴镜头，默认取全局配置
+    projection: Projection,
+    /// 快门效率曲线：运动模糊的时间采样分布，默认取全局配置
+    shutter: Shutter,
+
+    // 像素重建滤波器
+    filter: Filter,
+    // 曝光倍数（线性缩放）
+    exposure: f64,
+    /// 随机种子：设置后每个 (像素, 样本) 使用独立的可复现随机数流，结果与线程数无关
+    seed: Option<u64>,
+
+    /// 辐照度缓存预览（有偏）：设置后主光线命中处的漫反射光照由缓存插值，最终渲染应保持为None
+    irradiance_cache: Option<IrradianceCacheSettings>,
+    /// 透明背景：直接看到背景的像素输出为透明（RGBA）
+    transparent_background: bool,
+    /// 环境光快速模式（有偏）：漫反射光线逃逸到背景时使用预烘焙的辐照度，背景不支持时无效
+    baked_environment: bool,
+    /// 透明阴影（有偏）：有光源列表时非镜面交点向光源显式采样，阴影光线沿直线穿过玻璃等透明表面并按透过率衰减，
+    /// 代替经折射到达光源的焦散路径
+    transparent_shadows: bool,
+
+    /// 积分器：路径追踪或环境光遮蔽
+    integrator: Integrator,
+    /// 路径终止策略：固定深度、轮盘赌或两者结合，默认取全局配置
+    termination: TerminationPolicy,
+    /// 降噪：设置后渲染结束时以法线/反照率/深度辅助缓冲引导滤波，默认取全局配置
+    denoise: Option<DenoiseSettings>,
+    /// 焦散光子映射：设置且有光源列表时，镜面到漫反射的焦散由光子估计，默认取全局配置
+    caustics: Option<CausticSettings>,
+    /// 景深感知采样：设置且有景深时，弥散圆大的像素使用更多样本，默认取全局配置
+    dof_sampling: Option<DofSampling>,
+
+    /// 停止条件：设置时间预算、目标噪声或样本数上限后 `render()` 改为渐进渲染，忽略 samples_per_pixel，默认取全局配置
+    halt: Option<HaltCondition>,
+
+    // 调试：包围盒线框叠加
+    bounds_overlay: BoundsOverlay,
+    /// 渲染统计：设置后按材质累计着色次数和反弹深度（有额外开销，仅用于分析场景）
+    stats: Option<Arc<RenderStats>>,
+    /// 渲染报告：`render()` 结束时在输出文件旁写出 JSON 报告（设置、阶段耗时、统计、输出哈希），默认取全局配置
+    report: bool,
 
     // 私有计算参数
+    initialized: bool,
     image_height: i32,
     pixel_samples_scale: f64,
     sqrt_spp: i32,
     recip_sqrt_spp: f64,
+    lens_stride: i32,
     center: Point3,
     pixel00_loc: Point3,
     pixel_delta_u: Vec3,
@@ -46,6 +124,13 @@ pub struct Camera {
     w: Vec3,
     defocus_disk_u: Vec3,
     defocus_disk_v: Vec3,
+    /// 移轴镜头的清晰平面（平面上一点和法线），不倾斜时为None
+    focus_plane: Option<(Point3, Vec3)>,
+    ir_cache: Option<Arc<IrradianceCache>>,
+    caustic_map: Option<Arc<CausticMap>>,
+    tile_cache: Option<TileCache>,
+    /// 只渲染与此区域重叠的分块（分布式渲染的分带任务），None 为整幅图像
+    region: Option<Crop>,
 }
 
 impl Camera {
@@ -57,8 +142,10 @@ impl Camera {
             image_width: 100,
             samples_per_pixel: 10,
             max_depth: 10,
-            background: Color::new(0.7, 0.8, 1.0),
+            background: Arc::new(ConstantColor::new(Color::new(0.7, 0.8, 1.0))),
             output_filename: "output.png".to_string(),
+            output_format: None,
+            output_color_space: config::global().output_color_space,
 
             vfov: 90.0,
             lookfrom: Point3::origin(),
@@ -67,12 +154,34 @@ impl Camera {
 
             defocus_angle: 0.0,
             focus_dist: 10.0,
+            projection: config::global().projection,
+            shutter: config::global().shutter.clone(),
+
+            filter: Filter::Box,
+            exposure: 1.0,
+            seed: None,
+
+            irradiance_cache: None,
+            transparent_background: false,
+            baked_environment: false,
+            transparent_shadows: false,
+            integrator: Integrator::default(),
+            termination: config::global().termination,
+            denoise: config::global().denoise,
+            caustics: config::global().caustics,
+            dof_sampling: config::global().dof_sampling,
+            halt: config::global().halt,
+            bounds_overlay: BoundsOverlay::Off,
+            stats: None,
+            report: config::global().report,
 
             // 私有参数在initialize中设置
+            initialized: false,
             image_height: 0,
             pixel_samples_scale: 0.0,
             sqrt_spp: 0,
             recip_sqrt_spp: 0.0,
+            lens_stride: 1,
             center: Point3::origin(),
             pixel00_loc: Point3::origin(),
             pixel_delta_u: Vec3::zeros(),
@@ -82,11 +191,24 @@ impl Camera {
             w: Vec3::zeros(),
             defocus_disk_u: Vec3::zeros(),
             defocus_disk_v: Vec3::zeros(),
+            focus_plane: None,
+            ir_cache: None,
+            caustic_map: None,
+            tile_cache: None,
+            region: None,
         }
     }
 
+    /// 创建相机构建器
+    #[inline]
+    pub fn builder() -> CameraBuilder {
+        CameraBuilder::new()
+    }
+
     /// 初始化相机参数
     fn initialize(&mut self) {
+        self.initialized = true;
+
         // 计算图像高度
         self.image_height = ((self.image_width as f64) / self.aspect_ratio) as i32;
         self.image_height = self.image_height.max(1);
@@ -95,6 +217,7 @@ impl Camera {
         self.sqrt_spp = (self.samples_per_pixel as f64).sqrt() as i32;
         self.pixel_samples_scale = 1.0 / (self.sqrt_spp * self.sqrt_spp) as f64;
         self.recip_sqrt_spp = 1.0 / (self.sqrt_spp as f64);
+        self.lens_stride = Self::coprime_stride(self.sqrt_spp * self.sqrt_spp);
 
         self.center = self.lookfrom;
 
@@ -117,21 +240,55 @@ impl Camera {
         self.pixel_delta_u = viewport_u / (self.image_width as f64);
         self.pixel_delta_v = viewport_v / (self.image_height as f64);
 
-        // 计算左上角像素位置
+        // 计算左上角像素位置，移轴镜头平移视口
+        let shift = match self.projection {
+            Projection::TiltShift(lens) => lens.shift,
+            _ => (0.0, 0.0),
+        };
         let viewport_upper_left =
-            self.center - (self.focus_dist * self.w) - viewport_u / 2.0 - viewport_v / 2.0;
+            self.center - (self.focus_dist * self.w) - viewport_u / 2.0 - viewport_v / 2.0
+                + shift.0 * viewport_u
+                - shift.1 * viewport_v;
         self.pixel00_loc = viewport_upper_left + 0.5 * (self.pixel_delta_u + self.pixel_delta_v);
 
         // 计算散焦光圈参数
         let defocus_radius = self.focus_dist * degrees_to_radians(self.defocus_angle / 2.0).tan();
         self.defocus_disk_u = self.u * defocus_radius;
         self.defocus_disk_v = self.v * defocus_radius;
+
+        self.focus_plane = match self.projection {
+            Projection::TiltShift(lens) if lens.tilt != 0.0 => Some(lens.focus_plane(
+                &self.center,
+                &-self.w,
+                &self.v,
+                &self.u,
+                self.focus_dist,
+                self.vfov,
+            )),
+            _ => None,
+        };
+    }
+
+    /// 选取与样本总数互质、约为黄金分割比例的步长，用于打乱镜头分层
+    pub(crate) fn coprime_stride(total: i32) -> i32 {
+        fn gcd(a: i32, b: i32) -> i32 {
+            if b == 0 { a } else { gcd(b, a % b) }
+        }
+
+        let mut stride = ((total as f64) * 0.618).round().max(1.0) as i32;
+        while gcd(stride, total) != 1 {
+            stride += 1;
+        }
+        stride
     }
 
-    /// 生成光线
+    /// 生成光线，offset 为样本相对像素中心的偏移，lens 为单位圆盘上的镜头采样点
     #[inline]
-    fn get_ray(&self, i: i32, j: i32, s_i: i32, s_j: i32) -> Ray {
-        let offset = self.sample_square_stratified(s_i, s_j);
+    fn get_ray(&self, i: i32, j: i32, offset: &Vec3, lens: &Vec3) -> Ray {
+        if let Projection::LittlePlanet { fov } = self.projection {
+            return self.get_ray_stereographic(fov, i, j, offset);
+        }
+
         let pixel_sample = self.pixel00_loc
             + ((i as f64 + offset.x) * self.pixel_delta_u)
             + ((j as f64 + offset.y) * self.pixel_delta_v);
@@ -139,35 +296,214 @@ impl Camera {
         let ray_origin = if self.defocus_angle <= 0.0 {
             self.center
         } else {
-            self.defocus_disk_sample()
+            self.defocus_disk_sample(lens)
+        };
+
+        // 移轴镜头：针孔光线与倾斜的清晰平面的交点是各镜头采样点共同的对焦点；
+        // 交点在镜头后方时为虚焦点，光线从虚焦点经镜头采样点发散。
+        // 方向长度保持与针孔光线相同，光线微分依赖这一尺度
+        let ray_direction = match self.focus_plane {
+            Some((point, normal)) => {
+                let pinhole = pixel_sample - self.center;
+                let t = (point - self.center).dot(&normal) / pinhole.dot(&normal);
+                let towards = (self.center + t * pinhole - ray_origin) * t.signum();
+                if t.is_finite() && towards.norm_squared() > 0.0 {
+                    towards.normalize() * pinhole.norm()
+                } else {
+                    pinhole
+                }
+            }
+            None => pixel_sample - ray_origin,
         };
+        let ray_time = self.shutter.sample(random_double());
 
-        let ray_direction = pixel_sample - ray_origin;
-        let ray_time = random_double_range(0.0, 1.0);
+        // 相邻像素方向的光线微分，按每像素样本间距缩小（不小于1/8像素），与 pbrt 的做法一致
+        let spacing = self.recip_sqrt_spp.max(0.125);
+        let differential = RayDifferential::new(
+            ray_origin,
+            ray_direction + self.pixel_delta_u * spacing,
+            ray_origin,
+            ray_direction + self.pixel_delta_v * spacing,
+        );
 
         Ray::new(ray_origin, ray_direction, ray_time)
+            .with_differential(Some(differential))
+            .with_kind(RayKind::Camera)
+    }
+
+    /// 小行星投影的光线，从镜头中心出发（不支持景深）
+    fn get_ray_stereographic(&self, fov: f64, i: i32, j: i32, offset: &Vec3) -> Ray {
+        let width = self.image_width as f64;
+        let direction = |px: f64, py: f64| {
+            let x = 2.0 * px / width - 1.0;
+            let y = (2.0 * py - self.image_height as f64) / width;
+            let local = Projection::stereographic_direction(fov, x, y);
+            local.x * (-self.w) + local.y * self.u - local.z * self.v
+        };
+        let (px, py) = (i as f64 + 0.5 + offset.x, j as f64 + 0.5 + offset.y);
+        let ray_direction = direction(px, py);
+        let spacing = self.recip_sqrt_spp.max(0.125);
+        let differential = RayDifferential::new(
+            self.center,
+            direction(px + spacing, py),
+            self.center,
+            direction(px, py + spacing),
+        );
+
+        Ray::new(
+            self.center,
+            ray_direction,
+            self.shutter.sample(random_double()),
+        )
+        .with_differential(Some(differential))
+        .with_kind(RayKind::Camera)
+    }
+
+    /// 图像高度（由宽度和宽高比计算，相机初始化后有效）
+    #[inline]
+    pub fn image_height(&self) -> i32 {
+        self.image_height
+    }
+
+    /// 穿过像素 (i, j) 中心、从镜头中心出发的主光线，用于拾取物体或生成辅助缓冲区
+    pub fn center_ray(&self, i: i32, j: i32) -> Ray {
+        assert!(
+            self.initialized,
+            "相机未初始化：请使用 CameraBuilder::build() 创建相机或调用 render()"
+        );
+        self.get_ray(i, j, &Vec3::zeros(), &Vec3::zeros())
     }
 
     /// 分层采样
     #[inline]
     fn sample_square_stratified(&self, s_i: i32, s_j: i32) -> Vec3 {
-        let x = (s_i as f64 + random_double()) * self.recip_sqrt_spp - 0.5;
-        let y = (s_j as f64 + random_double()) * self.recip_sqrt_spp - 0.5;
+        Self::sample_square_grid(s_i, s_j, self.recip_sqrt_spp)
+    }
+
+    /// 格宽为 recip 的网格上的分层采样
+    #[inline]
+    fn sample_square_grid(s_i: i32, s_j: i32, recip: f64) -> Vec3 {
+        let x = (s_i as f64 + random_double()) * recip - 0.5;
+        let y = (s_j as f64 + random_double()) * recip - 0.5;
         Vec3::new(x, y, 0.0)
     }
 
+    /// 镜头分层采样：将像素样本序号置换后映射到镜头网格，避免与像素分层相关
+    #[inline]
+    fn sample_lens_stratified(&self, sample_idx: i32) -> Vec3 {
+        Self::sample_lens_grid(sample_idx, self.sqrt_spp, self.lens_stride)
+    }
+
+    /// 边长为 sqrt_n 的镜头网格上的分层采样，stride 与 sqrt_n² 互质
+    #[inline]
+    pub(crate) fn sample_lens_grid(sample_idx: i32, sqrt_n: i32, stride: i32) -> Vec3 {
+        let recip = 1.0 / sqrt_n as f64;
+        let (cell_u, cell_v) = Self::lens_grid_cell(sample_idx, sqrt_n, stride);
+        let u = (cell_u as f64 + random_double()) * recip;
+        let v = (cell_v as f64 + random_double()) * recip;
+        Self::concentric_disk(u, v)
+    }
+
+    /// 样本序号置换后对应的镜头网格单元（行, 列）
+    ///
+    /// 乘积在 i64 中计算：步长约为样本总数的 0.618 倍，每像素约 6 万样本以上时 i32 乘积会溢出。
+    #[inline]
+    pub(crate) fn lens_grid_cell(sample_idx: i32, sqrt_n: i32, stride: i32) -> (i32, i32) {
+        let total = sqrt_n as i64 * sqrt_n as i64;
+        let lens_idx = (sample_idx as i64 * stride as i64).rem_euclid(total);
+        (
+            (lens_idx / sqrt_n as i64) as i32,
+            (lens_idx % sqrt_n as i64) as i32,
+        )
+    }
+
+    /// Shirley-Chiu 同心映射：将单位正方形保面积地映射到单位圆盘，保持分层结构
+    #[inline]
+    fn concentric_disk(u: f64, v: f64) -> Vec3 {
+        let a = 2.0 * u - 1.0;
+        let b = 2.0 * v - 1.0;
+        if a == 0.0 && b == 0.0 {
+            return Vec3::zeros();
+        }
+
+        let (r, theta) = if a.abs() > b.abs() {
+            (a, std::f64::consts::FRAC_PI_4 * (b / a))
+        } else {
+            (
+                b,
+                std::f64::consts::FRAC_PI_2 - std::f64::consts::FRAC_PI_4 * (a / b),
+            )
+        };
+        Vec3::new(r * theta.cos(), r * theta.sin(), 0.0)
+    }
+
     /// 散焦光圈采样
     #[inline]
-    fn defocus_disk_sample(&self) -> Point3 {
-        let p = Vec3::random_in_unit_disk();
-        self.center + (p.x * self.defocus_disk_u) + (p.y * self.defocus_disk_v)
+    fn defocus_disk_sample(&self, lens: &Vec3) -> Point3 {
+        self.center + (lens.x * self.defocus_disk_u) + (lens.y * self.defocus_disk_v)
+    }
+
+    /// 将相机空间中的点投影为像素坐标
+    #[inline]
+    fn project_to_pixel(&self, p: &Point3, depth: f64) -> (f64, f64) {
+        let on_focus_plane = self.center + (p - self.center) * (self.focus_dist / depth);
+        let rel = on_focus_plane - self.pixel00_loc;
+        let x = rel.dot(&self.pixel_delta_u) / self.pixel_delta_u.norm_squared() + 0.5;
+        let y = rel.dot(&self.pixel_delta_v) / self.pixel_delta_v.norm_squared() + 0.5;
+        (x, y)
+    }
+
+    /// 在渲染结果上叠加场景包围盒线框
+    fn draw_bounds_overlay(&self, fb: &mut FrameBuffer, world: &dyn Hittable) {
+        // 线框按透视投影绘制，小行星投影下不叠加
+        if matches!(self.projection, Projection::LittlePlanet { .. }) {
+            return;
+        }
+        let leaves_only = match self.bounds_overlay {
+            BoundsOverlay::Off => return,
+            BoundsOverlay::Leaves => true,
+            BoundsOverlay::All => false,
+        };
+
+        let mut boxes = Vec::new();
+        world.collect_debug_boxes(0, leaves_only, &mut boxes);
+
+        // 近裁剪面，防止相机后方的点被投影到图像上
+        let near = 1e-3 * self.focus_dist;
+        for (bbox, depth) in boxes {
+            let color = depth_color(depth);
+            for (a, b) in box_edges(&bbox) {
+                let mut za = -(a - self.center).dot(&self.w);
+                let mut zb = -(b - self.center).dot(&self.w);
+                if za < near && zb < near {
+                    continue;
+                }
+
+                // 裁剪穿过近平面的线段
+                let (mut a, mut b) = (a, b);
+                if za < near {
+                    a = a + (b - a) * ((near - za) / (zb - za));
+                    za = near;
+                } else if zb < near {
+                    b = b + (a - b) * ((near - zb) / (za - zb));
+                    zb = near;
+                }
+
+                let (x0, y0) = self.project_to_pixel(&a, za);
+                let (x1, y1) = self.project_to_pixel(&b, zb);
+                draw_line(fb, x0, y0, x1, y1, color);
+            }
+        }
     }
 
-    /// 计算光线颜色，使用重要性采样和俄罗斯轮盘赌
+    /// 计算光线颜色，使用重要性采样，按终止策略结束路径
+    ///
+    /// depth 为剩余的反弹次数，throughput 为到达此光线之前累积的路径通量（用于轮盘赌）。
     fn ray_color(
         &self,
         r: &Ray,
         depth: i32,
+        throughput: &Color,
         world: &dyn Hittable,
         lights: Option<&Arc<dyn Hittable>>,
     ) -> Color {
@@ -177,101 +513,535 @@ impl Camera {
 
         let mut rec = HitRecord::default();
         if !world.hit(r, Interval::new(0.001, f64::INFINITY), &mut rec) {
-            return self.background;
+            return self.background.value(r);
+        }
+        rec.compute_footprint(r);
+
+        // 透明度遮罩：被遮罩的区域视为未命中，光线从交点继续前进（微分光线不变）
+        if Self::masked_out(&rec) {
+            let continued = Ray { orig: rec.p, ..*r };
+            return self.ray_color(&continued, depth, throughput, world, lights);
+        }
+
+        self.shade(r, &rec, depth, throughput, world, lights)
+    }
+
+    /// 沿任意光线追踪一条完整路径，返回到达光线起点的辐射度
+    ///
+    /// 使用相机的背景、最大深度和统计等积分器设置，供光照贴图烘焙等不从相机出发的光线使用。
+    #[inline]
+    pub fn trace_radiance(
+        &self,
+        r: &Ray,
+        world: &dyn Hittable,
+        lights: Option<&Arc<dyn Hittable>>,
+    ) -> Color {
+        self.ray_color(r, self.depth_limit(), &Color::repeat(1.0), world, lights)
+    }
+
+    /// 终止策略下的深度上限
+    #[inline]
+    fn depth_limit(&self) -> i32 {
+        self.termination.depth_limit(self.max_depth)
+    }
+
+    /// 按终止策略决定路径是否在第 bounce 次反弹后继续，返回存活概率（终止时为None）
+    #[inline]
+    fn survive(&self, bounce: u32, throughput: &Color) -> Option<f64> {
+        let survival = self.termination.survival(bounce, throughput);
+        (survival > 0.0 && random_double() < survival).then_some(survival)
+    }
+
+    /// 按材质透明度随机决定交点是否被遮罩（光线应穿过）
+    #[inline]
+    fn masked_out(rec: &HitRecord) -> bool {
+        let alpha = rec.mat.alpha(rec.u, rec.v, &rec.p);
+        alpha < 1.0 && random_double() >= alpha
+    }
+
+    /// 追踪相机光线，返回 (颜色, 覆盖度)
+    ///
+    /// 启用透明背景时，直接逃逸到背景的相机光线颜色和覆盖度均为0；次级光线仍照常采样背景。
+    fn trace_primary(
+        &self,
+        r: &Ray,
+        world: &dyn Hittable,
+        lights: Option<&Arc<dyn Hittable>>,
+    ) -> (Color, f64) {
+        if !self.transparent_background {
+            return (
+                self.ray_color(r, self.depth_limit(), &Color::repeat(1.0), world, lights),
+                1.0,
+            );
+        }
+
+        let mut ray = *r;
+        loop {
+            let mut rec = HitRecord::default();
+            if !world.hit(&ray, Interval::new(0.001, f64::INFINITY), &mut rec) {
+                return (Color::zeros(), 0.0);
+            }
+            rec.compute_footprint(&ray);
+            if !Self::masked_out(&rec) {
+                let radiance = self.shade(
+                    &ray,
+                    &rec,
+                    self.depth_limit(),
+                    &Color::repeat(1.0),
+                    world,
+                    lights,
+                );
+                return (radiance, 1.0);
+            }
+            ray.orig = rec.p;
+        }
+    }
+
+    /// 追踪相机光线并估计首个交点的环境光遮蔽，返回 (颜色, 覆盖度)
+    ///
+    /// 未命中任何物体的光线视为完全未遮挡（白色），启用透明背景时覆盖度为0。
+    fn trace_ambient_occlusion(
+        &self,
+        r: &Ray,
+        world: &dyn Hittable,
+        (distance, samples): (f64, u32),
+        pixel: (i32, i32),
+        sample_index: u32,
+    ) -> (Color, f64) {
+        let mut ray = *r;
+        loop {
+            let mut rec = HitRecord::default();
+            if !world.hit(&ray, Interval::new(0.001, f64::INFINITY), &mut rec) {
+                return if self.transparent_background {
+                    (Color::zeros(), 0.0)
+                } else {
+                    (Color::repeat(1.0), 1.0)
+                };
+            }
+            if !Self::masked_out(&rec) {
+                let ao =
+                    ambient_occlusion(world, &rec, r.time, distance, samples, pixel, sample_index);
+                return (Color::repeat(ao), 1.0);
+            }
+            ray.orig = rec.p;
         }
+    }
+
+    /// 追踪相机光线，按首个交点处的正交基底着色，返回 (颜色, 覆盖度)
+    ///
+    /// 未命中任何物体的光线显示为黑色，启用透明背景时覆盖度为0。
+    fn trace_basis(&self, r: &Ray, world: &dyn Hittable, view: BasisView) -> (Color, f64) {
+        let mut ray = *r;
+        loop {
+            let mut rec = HitRecord::default();
+            if !world.hit(&ray, Interval::new(0.001, f64::INFINITY), &mut rec) {
+                let coverage = if self.transparent_background {
+                    0.0
+                } else {
+                    1.0
+                };
+                return (Color::zeros(), coverage);
+            }
+            if !Self::masked_out(&rec) {
+                return (basis_color(&rec, view), 1.0);
+            }
+            ray.orig = rec.p;
+        }
+    }
 
-        // 材质发射的光
-        let emission = rec.mat.emitted(rec.u, rec.v, &rec.p);
+    /// 计算交点处的出射辐射度（发光 + 散射）
+    fn shade(
+        &self,
+        r: &Ray,
+        rec: &HitRecord,
+        depth: i32,
+        throughput: &Color,
+        world: &dyn Hittable,
+        lights: Option<&Arc<dyn Hittable>>,
+    ) -> Color {
+        // 材质发射的光；启用焦散光子图时，焦散路径到达的光由光子估计；
+        // 光源链接排除了发出光线的着色点时，发光体的辐射不计入；
+        // 上一个交点已向光源列表显式采样时，列表内发光体的辐射已在那里计入
+        let linked = r.link.is_none_or(|source| source.receives_from(&rec.link));
+        let emission = if !linked || (r.kind == RayKind::Caustic && self.caustic_map.is_some()) {
+            Color::zeros()
+        } else {
+            let emission = rec.mat.emitted_towards(r, rec);
+            match lights {
+                Some(light_objects)
+                    if r.direct_sampled
+                        && emission != Color::zeros()
+                        && Self::in_lights(r, rec, light_objects) =>
+                {
+                    Color::zeros()
+                }
+                _ => emission,
+            }
+        };
 
         // 散射计算
         let mut srec = ScatterRecord::new();
-        if !rec.mat.scatter(r, &rec, &mut srec) {
+        let did_scatter = rec.mat.scatter(r, rec, &mut srec);
+        let bounce = (self.depth_limit() - depth).max(0) as u32;
+        if let Some(stats) = &self.stats {
+            stats.record(&rec.mat, bounce, did_scatter);
+        }
+        if !did_scatter {
             return emission;
         }
 
-        // 镜面反射跳过PDF
+        // 镜面反射跳过PDF，微分光线随镜面散射传递；其余散射不再跟踪微分
         if srec.skip_pdf {
+            let differential = r
+                .differential
+                .zip(rec.footprint)
+                .map(|(d, fp)| d.scatter(r, &srec.skip_pdf_ray, &rec.normal, &fp));
+            // 非镜面交点之后的镜面链属于焦散路径
+            let kind = match r.kind {
+                RayKind::Diffuse | RayKind::Caustic => RayKind::Caustic,
+                _ => RayKind::Specular,
+            };
+            // 透明阴影已近似了穿过透明表面到达光源的光，经折射的焦散路径不再重复计入
+            let direct_sampled = r.direct_sampled && rec.mat.shadow_transmittance(r, rec).is_some();
+            let scattered = srec
+                .skip_pdf_ray
+                .with_differential(differential)
+                .with_kind(kind)
+                .with_link(Some(rec.link))
+                .with_direct_sampled(direct_sampled);
+            let throughput = throughput.component_mul(&srec.attenuation);
+            let Some(survival) = self.survive(bounce, &throughput) else {
+                return emission;
+            };
             return emission
                 + srec.attenuation.component_mul(&self.ray_color(
-                    &srec.skip_pdf_ray,
+                    &scattered,
                     depth - 1,
+                    &(throughput / survival),
                     world,
                     lights,
-                ));
+                )) / survival;
         }
 
-        // 重要性采样：混合光源和BRDF采样
-        let (scattered_direction, pdf_value) = if let Some(light_objects) = lights {
-            let light_pdf = Arc::new(HittablePDF::new(light_objects.clone(), &rec.p));
-            let mixture_pdf = MixturePDF::new(light_pdf, srec.pdf_ptr.expect("材质必须提供PDF"));
+        // 辐照度缓存预览：主光线命中的纯漫反射表面按朗伯反射使用插值辐照度，其余材质走完整的路径追踪
+        if depth == self.depth_limit()
+            && rec.mat.is_diffuse()
+            && let Some(cache) = &self.ir_cache
+        {
+            let irradiance = self.cached_irradiance(cache, r, rec, world, lights);
+            return emission + srec.attenuation.component_mul(&irradiance) / PI;
+        }
 
-            let direction = mixture_pdf.generate();
-            let pdf = mixture_pdf.value(&direction);
-            (direction, pdf)
-        } else {
-            let pdf = srec.pdf_ptr.expect("材质必须提供PDF");
-            let direction = pdf.generate();
-            let pdf_val = pdf.value(&direction);
-            (direction, pdf_val)
+        let emission = match &self.caustic_map {
+            Some(map) => emission + map.estimate(r, rec, &srec),
+            None => emission,
+        };
+
+        // 透明阴影：向光源显式采样一次，续行光线只按材质采样
+        let next_event = lights.filter(|_| self.transparent_shadows);
+        let emission = match next_event {
+            Some(light_objects) => match self.sample_light(r, rec, world, light_objects) {
+                Some((shadow_ray, radiance, pdf)) => {
+                    emission
+                        + rec
+                            .mat
+                            .bsdf_cos(r, rec, &srec, &shadow_ray)
+                            .component_mul(&radiance)
+                            / pdf
+                }
+                None => emission,
+            },
+            None => emission,
         };
 
+        // 重要性采样：混合光源和BRDF采样
+        let (scattered_direction, pdf_value) =
+            if let Some(light_objects) = lights.filter(|_| next_event.is_none()) {
+                let light_pdf = HittablePDF::new(light_objects.as_ref(), &rec.p, r.time);
+                let material_pdf = srec.pdf.as_ref().expect("材质必须提供PDF");
+                let mixture_pdf = MixturePDF::new(&light_pdf, material_pdf);
+
+                let direction = mixture_pdf.generate();
+                let pdf = mixture_pdf.value(&direction);
+                (direction, pdf)
+            } else {
+                let pdf = srec.pdf.as_ref().expect("材质必须提供PDF");
+                let direction = pdf.generate();
+                let pdf_val = pdf.value(&direction);
+                (direction, pdf_val)
+            };
+
         // 避免除零和无效PDF
         if pdf_value < 1e-6 || !pdf_value.is_finite() {
             return emission;
         }
 
-        let scattered = Ray::new(rec.p, scattered_direction, r.time);
-        let scattering_pdf = rec.mat.scattering_pdf(r, &rec, &scattered);
+        let scattered = Ray::new(rec.p, scattered_direction, r.time)
+            .with_link(Some(rec.link))
+            .with_direct_sampled(next_event.is_some());
+        let bsdf_cos = rec.mat.bsdf_cos(r, rec, &srec, &scattered);
 
-        // 俄罗斯轮盘赌优化
-        if depth > 3 {
-            let rr_prob = 0.8;
-            if random_double() > rr_prob {
-                return emission;
+        // 本次散射的权重 f·cosθ/pdf，并入路径通量后由终止策略决定是否继续
+        let weight = bsdf_cos / pdf_value;
+        let throughput = throughput.component_mul(&weight);
+        let Some(survival) = self.survive(bounce, &throughput) else {
+            return emission;
+        };
+        let incoming = self.trace_scattered(
+            &scattered,
+            rec,
+            depth - 1,
+            &(throughput / survival),
+            world,
+            lights,
+        );
+        emission + weight.component_mul(&incoming) / survival
+    }
+
+    /// 追踪非镜面散射光线
+    ///
+    /// 环境光快速模式下，逃逸的光线不再查询完整的背景，而是返回交点法线方向的烘焙辐照度 E(n)/π，
+    /// 即把环境看作在半球上按余弦加权平均后的均匀光源。
+    fn trace_scattered(
+        &self,
+        scattered: &Ray,
+        rec: &HitRecord,
+        depth: i32,
+        throughput: &Color,
+        world: &dyn Hittable,
+        lights: Option<&Arc<dyn Hittable>>,
+    ) -> Color {
+        if !self.baked_environment || depth <= 0 {
+            return self.ray_color(scattered, depth, throughput, world, lights);
+        }
+        let Some(irradiance) = self.background.irradiance(&rec.normal) else {
+            return self.ray_color(scattered, depth, throughput, world, lights);
+        };
+
+        let mut hit = HitRecord::default();
+        if !world.hit(scattered, Interval::new(0.001, f64::INFINITY), &mut hit) {
+            return irradiance / PI;
+        }
+        if Self::masked_out(&hit) {
+            let continued = Ray {
+                orig: hit.p,
+                ..*scattered
+            };
+            return self.ray_color(&continued, depth, throughput, world, lights);
+        }
+        self.shade(scattered, &hit, depth, throughput, world, lights)
+    }
+
+    /// 估计交点处的辐照度：直接光照逐样本按光源采样，间接光照由缓存插值
+    ///
+    /// 没有光源列表时缓存保存完整的辐照度（包括直接光照）；有光源列表时，未列入其中的发光体的直接光照会丢失。
+    fn cached_irradiance(
+        &self,
+        cache: &IrradianceCache,
+        r: &Ray,
+        rec: &HitRecord,
+        world: &dyn Hittable,
+        lights: Option<&Arc<dyn Hittable>>,
+    ) -> Color {
+        let indirect = match cache.lookup(&rec.p, &rec.normal) {
+            Some(irradiance) => irradiance,
+            None => {
+                let (irradiance, harmonic_distance) = self.sample_irradiance(r, rec, world, lights);
+                cache.insert(rec.p, rec.normal, irradiance, harmonic_distance);
+                irradiance
             }
+        };
 
-            let rr_scale = 1.0 / rr_prob;
-            return emission
-                + rr_scale
-                    * (srec.attenuation.component_mul(
-                        &(scattering_pdf * self.ray_color(&scattered, depth - 1, world, lights)),
-                    ))
-                    / pdf_value;
+        match lights {
+            Some(light_objects) => indirect + self.direct_irradiance(r, rec, world, light_objects),
+            None => indirect,
+        }
+    }
+
+    /// 按余弦加权在半球上采样辐照度，返回 (辐照度, 命中距离的调和平均)
+    fn sample_irradiance(
+        &self,
+        r: &Ray,
+        rec: &HitRecord,
+        world: &dyn Hittable,
+        lights: Option<&Arc<dyn Hittable>>,
+    ) -> (Color, f64) {
+        let sample_count = self
+            .ir_cache
+            .as_ref()
+            .map_or(1, |c| c.settings().samples.max(1));
+        let pdf = CosinePDF::new(&rec.normal);
+
+        let mut irradiance = Color::zeros();
+        let mut inv_distance_sum = 0.0;
+        for _ in 0..sample_count {
+            let direction = pdf.generate();
+            let ray = Ray::new(rec.p, direction, r.time);
+            let mut hit = HitRecord::default();
+            let radiance = if world.hit(&ray, Interval::new(0.001, f64::INFINITY), &mut hit) {
+                inv_distance_sum += 1.0 / (hit.t * direction.norm()).max(1e-9);
+                let radiance = self.shade(
+                    &ray,
+                    &hit,
+                    self.depth_limit() - 1,
+                    &Color::repeat(1.0),
+                    world,
+                    lights,
+                );
+                if lights.is_some() {
+                    radiance - hit.mat.emitted_towards(&ray, &hit)
+                } else {
+                    radiance
+                }
+            } else {
+                self.background.value(&ray)
+            };
+            // 余弦加权采样时 L·cosθ/pdf = π·L
+            irradiance += radiance * PI;
+        }
+
+        let harmonic_distance = if inv_distance_sum > 0.0 {
+            sample_count as f64 / inv_distance_sum
+        } else {
+            f64::INFINITY
+        };
+        (irradiance / sample_count as f64, harmonic_distance)
+    }
+
+    /// 向光源采样一次，估计直接光照产生的辐照度
+    fn direct_irradiance(
+        &self,
+        r: &Ray,
+        rec: &HitRecord,
+        world: &dyn Hittable,
+        lights: &Arc<dyn Hittable>,
+    ) -> Color {
+        match self.sample_light(r, rec, world, lights) {
+            Some((shadow_ray, radiance, pdf)) => {
+                let cos_theta = shadow_ray.dir.dot(&rec.normal);
+                if cos_theta <= 0.0 {
+                    return Color::zeros();
+                }
+                radiance * cos_theta / pdf
+            }
+            None => Color::zeros(),
+        }
+    }
+
+    /// 按光源列表的PDF采样一个方向，返回 (指向光源的单位方向阴影光线, 经透过率衰减后到达的辐射度, 方向的PDF)
+    ///
+    /// 沿光线在光源列表中逐个查找交点，取第一个发光的表面；光源列表中的几何通常只带占位材质，
+    /// 辐射度取自场景中位于同一处的物体。光源列表也可能包含玻璃球等不发光的采样目标，遇到时继续向前。
+    /// 两点之间按透过率衰减：玻璃等透明表面投下带颜色的阴影，不透明表面完全遮挡。
+    fn sample_light(
+        &self,
+        r: &Ray,
+        rec: &HitRecord,
+        world: &dyn Hittable,
+        lights: &Arc<dyn Hittable>,
+    ) -> Option<(Ray, Color, f64)> {
+        let pdf = HittablePDF::new(lights.as_ref(), &rec.p, r.time);
+        let direction = pdf.generate();
+        let pdf_value = pdf.value(&direction);
+        if pdf_value < 1e-6 || !pdf_value.is_finite() || direction.norm_squared() == 0.0 {
+            return None;
+        }
+
+        // 方向归一化后光线参数即世界空间距离，自相交偏移和光源附近的查找窗口都按距离计
+        let ray = Ray::new(rec.p, direction.normalize(), r.time)
+            .with_kind(RayKind::Shadow)
+            .with_link(Some(rec.link));
+        let mut t_min = RAY_EPSILON;
+        loop {
+            let mut light = HitRecord::default();
+            if !lights.hit(&ray, Interval::new(t_min, f64::INFINITY), &mut light) {
+                return None;
+            }
+            let mut hit = HitRecord::default();
+            let span = Interval::new(light.t - RAY_EPSILON, light.t + RAY_EPSILON);
+            if world.hit(&ray, span, &mut hit) && rec.link.receives_from(&hit.link) {
+                let emission = hit.mat.emitted_towards(&ray, &hit);
+                if emission != Color::zeros() {
+                    let transmittance = world.trace_transmittance(&ray, light.t - RAY_EPSILON);
+                    return Some((ray, transmittance.component_mul(&emission), pdf_value));
+                }
+            }
+            t_min = light.t + RAY_EPSILON;
         }
+    }
 
-        // 正常递归
-        emission
-            + (srec.attenuation.component_mul(
-                &(scattering_pdf * self.ray_color(&scattered, depth - 1, world, lights)),
-            )) / pdf_value
+    /// 光线命中的发光点是否属于光源列表（即光源采样能采到它）
+    fn in_lights(r: &Ray, rec: &HitRecord, lights: &Arc<dyn Hittable>) -> bool {
+        let window = RAY_EPSILON / r.dir.norm().max(1e-12);
+        let mut light = HitRecord::default();
+        lights.hit(r, Interval::new(rec.t - window, rec.t + window), &mut light)
     }
 
-    /// 计算单个像素的颜色
-    fn calculate_pixel_color(
+    /// 计算单个像素的所有样本，返回 (样本偏移, 颜色, 覆盖度)
+    ///
+    /// sqrt_n 为该像素分层网格的边长（通常为每像素样本数的平方根，景深感知采样时可能更大）。
+    fn calculate_pixel_samples(
         &self,
         i: i32,
         j: i32,
         world: &dyn Hittable,
         lights: Option<&Arc<dyn Hittable>>,
-    ) -> Color {
-        let total_samples = self.sqrt_spp * self.sqrt_spp;
+        sqrt_n: i32,
+    ) -> Vec<(Vec3, Color, f64)> {
+        let total_samples = sqrt_n * sqrt_n;
+        let (recip, lens_stride) = if sqrt_n == self.sqrt_spp {
+            (self.recip_sqrt_spp, self.lens_stride)
+        } else {
+            (1.0 / sqrt_n as f64, Self::coprime_stride(total_samples))
+        };
 
         (0..total_samples)
             .into_par_iter()
             .map(|sample_idx| {
-                let s_i = sample_idx / self.sqrt_spp;
-                let s_j = sample_idx % self.sqrt_spp;
-                let ray = self.get_ray(i, j, s_i, s_j);
-                self.ray_color(&ray, self.max_depth, world, lights)
+                let trace = || {
+                    let s_i = sample_idx / sqrt_n;
+                    let s_j = sample_idx % sqrt_n;
+                    let offset = Self::sample_square_grid(s_i, s_j, recip);
+                    let lens = Self::sample_lens_grid(sample_idx, sqrt_n, lens_stride);
+                    let ray = self.get_ray(i, j, &offset, &lens);
+                    let (color, coverage) = match self.integrator {
+                        Integrator::PathTracing => self.trace_primary(&ray, world, lights),
+                        Integrator::AmbientOcclusion { distance, samples } => self
+                            .trace_ambient_occlusion(
+                                &ray,
+                                world,
+                                (distance, samples),
+                                (i, j),
+                                sample_idx as u32,
+                            ),
+                        Integrator::Basis(view) => self.trace_basis(&ray, world, view),
+                    };
+                    (offset, color, coverage)
+                };
+
+                match self.seed {
+                    Some(seed) => with_seeded_stream(
+                        hash_seed(&[seed, i as u64, j as u64, sample_idx as u64]),
+                        trace,
+                    ),
+                    None => trace(),
+                }
             })
-            .reduce(Color::zeros, |acc, color| acc + color)
+            .collect()
     }
 
-    /// 主渲染方法
+    /// 主渲染方法（先完成初始化再渲染，适用于 `Camera::new()` 创建的相机）
     pub fn render(&mut self, world: &dyn Hittable, lights: Option<Arc<dyn Hittable>>) {
         self.initialize();
+        self.render_frame(world, lights);
+    }
 
-        let mut img = RgbImage::new(self.image_width as u32, self.image_height as u32);
+    /// 使用已初始化的相机渲染，适用于 `CameraBuilder::build()` 返回的相机
+    pub fn render_frame(&self, world: &dyn Hittable, lights: Option<Arc<dyn Hittable>>) {
+        assert!(
+            self.initialized,
+            "相机未初始化：请使用 CameraBuilder::build() 创建相机或调用 render()"
+        );
 
         // 进度条设置
         let progress_bar = ProgressBar::new((self.image_height * self.image_width) as u64);
@@ -284,52 +1054,1421 @@ impl Camera {
                 .progress_chars("#>-"),
         );
 
-        // 设置块大小 - 通常16x16或32x32效果较好
-        let tile_size = 16;
-        let num_tiles_x = (self.image_width + tile_size - 1) / tile_size;
-        let num_tiles_y = (self.image_height + tile_size - 1) / tile_size;
-        let total_tiles = num_tiles_x * num_tiles_y;
-
-        // 并行渲染分块
-        let pixel_colors: Vec<(i32, i32, Color)> = (0..total_tiles)
-            .into_par_iter()
-            .flat_map(|tile_idx| {
-                let tile_x = (tile_idx % num_tiles_x) * tile_size;
-                let tile_y = (tile_idx / num_tiles_x) * tile_size;
+        let start = Instant::now();
+        let progress_log = ProgressLog::new();
+        let format = self
+            .output_format
+            .unwrap_or_else(|| OutputFormat::from_filename(&self.output_filename));
+        let (saved, stages) = report::collect_stages(|| {
+            let mut fb = match self.halt.filter(HaltCondition::is_active) {
+                // 渐进渲染：每轮结束后把当前结果写入输出文件作为检查点，中断时保留最近的结果
+                Some(halt) => self.render_progressive(
+                    world,
+                    lights,
+                    &halt,
+                    |p| progress_bar.set_position(p.completed),
+                    |image, info| {
+                        progress_bar.suspend(|| {
+                            eprintln!("{}", info);
+                            if let Err(e) = save_framebuffer(image, &self.output_filename, format) {
+                                eprintln!("保存检查点时出错: {}", e);
+                            }
+                        });
+                    },
+                ),
+                None => self.render_with(
+                    world,
+                    lights,
+                    |p| {
+                        progress_bar.set_position(p.completed);
+                        if self.report {
+                            progress_log.record(p.completed, p.total);
+                        }
+                    },
+                    &AtomicBool::new(false),
+                )?,
+            };
 
-                let mut tile_results = Vec::with_capacity((tile_size * tile_size) as usize);
+            let overlay_start = Instant::now();
+            self.draw_bounds_overlay(&mut fb, world);
+            report::record_stage("overlay", overlay_start.elapsed());
 
-                // 处理这个块内的所有像素
-                for j in tile_y..std::cmp::min(tile_y + tile_size, self.image_height) {
-                    for i in tile_x..std::cmp::min(tile_x + tile_size, self.image_width) {
-                        let pixel_color = self.calculate_pixel_color(i, j, world, lights.as_ref());
-                        tile_results.push((i, j, pixel_color));
-                        progress_bar.inc(1);
-                    }
+            // 保存图像
+            let save_start = Instant::now();
+            let saved = match save_framebuffer(&fb, &self.output_filename, format) {
+                Ok(_) => {
+                    eprintln!("图像已保存为 {}", self.output_filename);
+                    true
+                }
+                Err(e) => {
+                    eprintln!("保存图像时出错: {}", e);
+                    false
                 }
+            };
+            report::record_stage("save", save_start.elapsed());
+            Some(saved)
+        });
 
-                tile_results
-            })
-            .collect();
+        progress_bar.finish_and_clear();
 
-        // 填充图像缓冲区
-        for (i, j, color) in pixel_colors {
-            let rgb = color_to_rgb_with_samples(&color, self.samples_per_pixel);
-            img.put_pixel(i as u32, j as u32, rgb);
+        if self.report
+            && let Some(saved) = saved
+        {
+            let mut report = self.report_settings();
+            report.stages = stages;
+            report.progress = progress_log.into_marks();
+            report.total_seconds = start.elapsed().as_secs_f64();
+            if saved {
+                report.set_output(&self.output_filename);
+            }
+            let path = report::report_path(&self.output_filename);
+            match report.save(&path) {
+                Ok(_) => eprintln!("渲染报告已保存为 {}", path),
+                Err(e) => eprintln!("保存渲染报告 {} 时出错: {}", path, e),
+            }
         }
+    }
 
-        // 保存图像
-        match img.save(&self.output_filename) {
-            Ok(_) => eprintln!("图像已保存为 {}", self.output_filename),
-            Err(e) => eprintln!("保存图像时出错: {}", e),
+    /// 只含渲染设置和统计的报告，供 `render()` 填入耗时和输出后写出
+    pub fn report_settings(&self) -> RenderReport {
+        let mut report = RenderReport::default();
+        report.setting("width", self.image_width);
+        report.setting("height", self.image_height);
+        report.setting("samples_per_pixel", self.sqrt_spp * self.sqrt_spp);
+        report.setting("max_depth", self.max_depth);
+        report.setting("seed", self.seed.map(|s| s.to_string()));
+        report.setting(
+            "integrator",
+            match self.integrator {
+                Integrator::PathTracing => "path".to_string(),
+                Integrator::AmbientOcclusion { distance, samples } => {
+                    format!("ao:{}:{}", distance, samples)
+                }
+                Integrator::Basis(view) => format!("basis:{}", view),
+            },
+        );
+        report.setting("termination", self.termination.to_string());
+        report.setting("projection", self.projection.to_string());
+        report.setting("shutter", self.shutter.to_string());
+        report.setting("output_color_space", self.output_color_space.to_string());
+        report.setting("exposure", self.exposure);
+        report.setting("transparent_shadows", self.transparent_shadows);
+        report.setting("denoise", self.denoise.map(|d| d.strength));
+        report.setting("caustic_photons", self.caustics.map(|c| c.photons));
+        report.setting("dof_sampling", self.dof_sampling.map(|d| d.weight));
+        report.setting(
+            "halt",
+            self.halt
+                .filter(HaltCondition::is_active)
+                .map(|h| h.to_string()),
+        );
+        report.setting("threads", rayon::current_num_threads());
+        if let Some(stats) = &self.stats {
+            report.stats = stats.per_type();
         }
+        report
+    }
 
-        progress_bar.finish_and_clear();
+    /// 使用分块缓存渲染的副本，见 [`Scene::render_cached`](crate::ray_tracing::scene::world::Scene::render_cached)
+    pub(crate) fn with_tile_cache(mut self, cache: TileCache) -> Self {
+        self.tile_cache = Some(cache);
+        self
     }
-}
 
-impl Default for Camera {
-    fn default() -> Self {
-        Self::new()
+    /// 渲染到帧缓冲区而不保存文件（不显示进度条）
+    pub fn render_to_buffer(
+        &self,
+        world: &dyn Hittable,
+        lights: Option<Arc<dyn Hittable>>,
+    ) -> FrameBuffer {
+        assert!(
+            self.initialized,
+            "相机未初始化：请使用 CameraBuilder::build() 创建相机或调用 render()"
+        );
+        self.render_with(world, lights, |_| {}, &AtomicBool::new(false))
+            .expect("渲染未被取消")
+    }
+
+    /// 只渲染区域内的像素，区域外的像素留空
+    ///
+    /// 设置了种子时每个像素的样本与整幅渲染相同，各区域的结果可以逐像素拼接为完整的图像。
+    /// 降噪需要整幅图像的辅助缓冲，区域渲染时不进行。
+    pub fn render_region(
+        &self,
+        world: &dyn Hittable,
+        lights: Option<Arc<dyn Hittable>>,
+        region: Crop,
+    ) -> FrameBuffer {
+        assert!(
+            self.initialized,
+            "相机未初始化：请使用 CameraBuilder::build() 创建相机或调用 render()"
+        );
+        let mut camera = self.clone();
+        camera.region = Some(region);
+        camera.denoise = None;
+        camera.render_to_buffer(world, lights)
+    }
+
+    /// 渲染到帧缓冲区，通过回调报告进度，并在 cancel 被置位后尽快停止
+    ///
+    /// 回调在工作线程上按像素并发调用，应保持轻量（如更新原子变量或发送到通道）。
+    /// 渲染被取消时返回None。
+    pub fn render_with(
+        &self,
+        world: &dyn Hittable,
+        lights: Option<Arc<dyn Hittable>>,
+        progress: impl Fn(Progress) + Sync,
+        cancel: &AtomicBool,
+    ) -> Option<FrameBuffer> {
+        assert!(
+            self.initialized,
+            "相机未初始化：请使用 CameraBuilder::build() 创建相机或调用 render()"
+        );
+        let total = (self.image_width as u64) * (self.image_height as u64);
+        let sink = ProgressSink::new(total, &progress, cancel);
+        self.render_pixels(world, lights.as_ref(), &sink)
+    }
+
+    /// 渐进渲染：每轮用新的随机种子渲染 `halt.pass_samples` 个每像素样本并累加，直到满足停止条件
+    ///
+    /// progress 报告每一轮内的像素进度；每轮结束后以当前的平均图像（未降噪）调用 on_pass。
+    /// 降噪在全部轮次结束后对最终结果进行一次。
+    pub fn render_progressive(
+        &self,
+        world: &dyn Hittable,
+        lights: Option<Arc<dyn Hittable>>,
+        halt: &HaltCondition,
+        progress: impl Fn(Progress) + Sync,
+        mut on_pass: impl FnMut(&FrameBuffer, &PassInfo),
+    ) -> FrameBuffer {
+        assert!(
+            self.initialized,
+            "相机未初始化：请使用 CameraBuilder::build() 创建相机或调用 render()"
+        );
+        let mut pass_camera = self.clone();
+        pass_camera.samples_per_pixel = halt.pass_samples.max(1) as i32;
+        pass_camera.denoise = None;
+        pass_camera.initialize();
+        let pass_samples = (pass_camera.sqrt_spp * pass_camera.sqrt_spp) as u32;
+
+        let total = (self.image_width as u64) * (self.image_height as u64);
+        let cancel = AtomicBool::new(false);
+        let mut accumulator =
+            PassAccumulator::new(self.image_width as u32, self.image_height as u32);
+        let mut image = FrameBuffer::new(self.image_width as u32, self.image_height as u32);
+        let start = Instant::now();
+        let mut passes = 0;
+        let mut last_pass = Duration::ZERO;
+        while !halt.should_stop(
+            passes,
+            passes * pass_samples,
+            start.elapsed(),
+            last_pass,
+            accumulator.noise(),
+        ) {
+            let pass_start = Instant::now();
+            pass_camera.seed = self.seed.map(|seed| hash_seed(&[seed, passes as u64]));
+            let sink = ProgressSink::new(total, &progress, &cancel);
+            let Some(pass) = pass_camera.render_pixels(world, lights.as_ref(), &sink) else {
+                break;
+            };
+            accumulator.add(&pass);
+            image = accumulator.image(&pass);
+            passes += 1;
+            last_pass = pass_start.elapsed();
+            on_pass(
+                &image,
+                &PassInfo {
+                    passes,
+                    samples: passes * pass_samples,
+                    elapsed: start.elapsed(),
+                    noise: accumulator.noise(),
+                },
+            );
+        }
+
+        if let Some(settings) = &self.denoise {
+            let start = Instant::now();
+            let aov = self.render_aov(world);
+            image = denoise(&image, &aov, settings);
+            report::record_stage("denoise", start.elapsed());
+        }
+        image
+    }
+
+    /// 并行渲染所有像素，返回应用曝光后的线性帧缓冲区；被取消时返回None
+    fn render_pixels(
+        &self,
+        world: &dyn Hittable,
+        lights: Option<&Arc<dyn Hittable>>,
+        progress: &ProgressSink,
+    ) -> Option<FrameBuffer> {
+        // 辐照度缓存预览：在带缓存的副本上渲染，缓存仅在本次渲染中有效
+        if let Some(settings) = self.irradiance_cache
+            && self.ir_cache.is_none()
+        {
+            let mut preview = self.clone();
+            preview.ir_cache = Some(Arc::new(IrradianceCache::new(
+                &world.bounding_box().unwrap_or_else(Aabb::empty),
+                settings,
+            )));
+            return preview.render_pixels(world, lights, progress);
+        }
+
+        // 焦散光子图：在带光子图的副本上渲染，光子图仅在本次渲染中有效
+        if let Some(settings) = &self.caustics
+            && let Some(light_objects) = lights
+            && self.caustic_map.is_none()
+            && self.integrator == Integrator::PathTracing
+        {
+            let mut hybrid = self.clone();
+            let start = Instant::now();
+            hybrid.caustic_map = Some(Arc::new(CausticMap::build(
+                world,
+                light_objects.as_ref(),
+                settings,
+                self.depth_limit(),
+                self.seed,
+            )));
+            report::record_stage("caustics", start.elapsed());
+            return hybrid.render_pixels(world, lights, progress);
+        }
+
+        let start = Instant::now();
+        let mut fb = FrameBuffer::new(self.image_width as u32, self.image_height as u32);
+        fb.set_color_space(self.output_color_space);
+        if self.transparent_background {
+            fb.enable_alpha();
+        }
+
+        // 景深感知采样：每像素的分层网格边长
+        let grids = self.dof_sample_grids(world);
+
+        // 辐照度缓存和焦散光子图依赖整个场景，此时不复用分块
+        let cache = self
+            .tile_cache
+            .as_ref()
+            .filter(|_| self.ir_cache.is_none() && self.caustic_map.is_none());
+
+        // 设置块大小 - 通常16x16或32x32效果较好
+        let tile_size = 16;
+        let num_tiles_x = (self.image_width + tile_size - 1) / tile_size;
+        let num_tiles_y = (self.image_height + tile_size - 1) / tile_size;
+        let total_tiles = num_tiles_x * num_tiles_y;
+
+        // 并行渲染分块，每块将样本溅射到自己的累积缓冲区
+        let tiles: Vec<SplatTile> = (0..total_tiles)
+            .into_par_iter()
+            .map(|tile_idx| {
+                let tile_x = (tile_idx % num_tiles_x) * tile_size;
+                let tile_y = (tile_idx / num_tiles_x) * tile_size;
+                let tile_x1 = std::cmp::min(tile_x + tile_size, self.image_width);
+                let tile_y1 = std::cmp::min(tile_y + tile_size, self.image_height);
+
+                let mut tile = SplatTile::new(tile_x, tile_y, tile_x1, tile_y1, &self.filter);
+
+                // 区域渲染：跳过样本溅射不到区域内的分块
+                let margin = self.filter.radius().ceil() as i32;
+                if let Some(region) = &self.region
+                    && !region.overlaps(
+                        tile_x - margin,
+                        tile_y - margin,
+                        tile_x1 + margin,
+                        tile_y1 + margin,
+                    )
+                {
+                    for _ in 0..(tile_x1 - tile_x) * (tile_y1 - tile_y) {
+                        progress.pixel_done();
+                    }
+                    return tile;
+                }
+
+                // 分块缓存：依赖未变化的分块直接复用，景深网格随场景变化，计入分块键
+                let cached = cache.map(|cache| {
+                    let grid_hash = grids.as_ref().map_or(0, |g| {
+                        let rows = (tile_y..tile_y1).map(|j| {
+                            let row = (j * self.image_width) as usize;
+                            &g[row + tile_x as usize..row + tile_x1 as usize]
+                        });
+                        tile_cache::debug_hash(&rows.collect::<Vec<_>>())
+                    });
+                    cache.lookup(tile_x, tile_y, tile_x1, tile_y1, grid_hash)
+                });
+                let key = match cached {
+                    Some(TileLookup::Render(key)) => cache.map(|cache| (cache, key)),
+                    Some(lookup) => {
+                        for _ in 0..(tile_x1 - tile_x) * (tile_y1 - tile_y) {
+                            progress.pixel_done();
+                        }
+                        return match lookup {
+                            TileLookup::Cached(cached) => cached,
+                            _ => tile,
+                        };
+                    }
+                    None => None,
+                };
+
+                // 处理这个块内的所有像素，取消后跳过剩余像素
+                let dependencies = tile_cache::record_dependencies(|| {
+                    for j in tile_y..tile_y1 {
+                        for i in tile_x..tile_x1 {
+                            if progress.cancelled() {
+                                return;
+                            }
+                            let sqrt_n = grids
+                                .as_ref()
+                                .map_or(self.sqrt_spp, |g| g[(j * self.image_width + i) as usize]);
+                            for (offset, color, coverage) in
+                                self.calculate_pixel_samples(i, j, world, lights, sqrt_n)
+                            {
+                                let sx = i as f64 + 0.5 + offset.x;
+                                let sy = j as f64 + 0.5 + offset.y;
+                                tile.add_sample(&self.filter, sx, sy, &color, coverage);
+                            }
+                            progress.pixel_done();
+                        }
+                    }
+                });
+                if let Some((cache, key)) = key
+                    && !progress.cancelled()
+                {
+                    cache.store(tile_x, tile_y, key, dependencies, tile.clone());
+                }
+
+                tile
+            })
+            .collect();
+        if progress.cancelled() {
+            return None;
+        }
+
+        // 合并各块的加权和，归一化后填充帧缓冲区
+        let pixel_count = (self.image_width * self.image_height) as usize;
+        let mut sum = vec![Color::zeros(); pixel_count];
+        let mut weight = vec![0.0; pixel_count];
+        let mut coverage = vec![0.0; pixel_count];
+        for tile in &tiles {
+            tile.merge_into(
+                self.image_width,
+                self.image_height,
+                &mut sum,
+                &mut weight,
+                &mut coverage,
+            );
+        }
+
+        for j in 0..self.image_height {
+            for i in 0..self.image_width {
+                let idx = (j * self.image_width + i) as usize;
+                if weight[idx].abs() > 1e-12 {
+                    fb.set(i as u32, j as u32, self.exposure * sum[idx] / weight[idx]);
+                    if self.transparent_background {
+                        fb.set_alpha(i as u32, j as u32, coverage[idx] / weight[idx]);
+                    }
+                }
+            }
+        }
+
+        report::record_stage("render", start.elapsed());
+
+        if let Some(settings) = &self.denoise {
+            let start = Instant::now();
+            let aov = self.render_aov(world);
+            fb = denoise(&fb, &aov, settings);
+            report::record_stage("denoise", start.elapsed());
+        }
+        Some(fb)
+    }
+
+    /// 渲染主光线首个交点的法线、反照率、深度和运动矢量缓冲，供降噪引导和后期合成使用
+    ///
+    /// 每像素最多取16个与颜色渲染相同分布的样本；透明度遮罩与渲染时一样随机穿过。
+    /// 运动矢量由交点处的表面点在快门打开和关闭时刻的位置投影得到（见 [`HitRecord::motion`]），
+    /// 小行星投影下全为零。
+    pub fn render_aov(&self, world: &dyn Hittable) -> AovBuffers {
+        assert!(
+            self.initialized,
+            "相机未初始化：请使用 CameraBuilder::build() 创建相机或调用 render()"
+        );
+        let total_samples = self.sqrt_spp * self.sqrt_spp;
+        let count = total_samples.min(16);
+
+        let pixels: Vec<(Vec3, Color, f64, Vec3)> = (0..self.image_width * self.image_height)
+            .into_par_iter()
+            .map(|index| {
+                let (i, j) = (index % self.image_width, index / self.image_width);
+                let trace = || {
+                    let mut normal = Vec3::zeros();
+                    let mut albedo = Color::zeros();
+                    let mut depth = 0.0;
+                    let mut motion = Vec3::zeros();
+                    let mut hits = 0;
+                    for k in 0..count {
+                        let sample_idx = k * total_samples / count;
+                        let offset = self.sample_square_stratified(
+                            sample_idx / self.sqrt_spp,
+                            sample_idx % self.sqrt_spp,
+                        );
+                        let lens = self.sample_lens_stratified(sample_idx);
+                        let (n, a, d, m) =
+                            self.first_hit_aov(&self.get_ray(i, j, &offset, &lens), world);
+                        albedo += a;
+                        if d.is_finite() {
+                            normal += n;
+                            depth += d;
+                            motion += m;
+                            hits += 1;
+                        }
+                    }
+                    // 只要有样本命中就视为命中，法线、深度和运动矢量取命中样本的平均
+                    if hits == 0 {
+                        (
+                            Vec3::zeros(),
+                            albedo / count as f64,
+                            f64::INFINITY,
+                            Vec3::zeros(),
+                        )
+                    } else {
+                        let normal = normal.try_normalize(1e-9).unwrap_or_else(Vec3::zeros);
+                        let hits = hits as f64;
+                        (normal, albedo / count as f64, depth / hits, motion / hits)
+                    }
+                };
+                match self.seed {
+                    Some(seed) => with_seeded_stream(hash_seed(&[seed, i as u64, j as u64]), trace),
+                    None => trace(),
+                }
+            })
+            .collect();
+
+        let mut aov = AovBuffers::new(self.image_width as u32, self.image_height as u32);
+        for (index, (normal, albedo, depth, motion)) in pixels.into_iter().enumerate() {
+            aov.normal[index] = normal;
+            aov.albedo[index] = albedo;
+            aov.depth[index] = depth;
+            aov.motion[index] = motion;
+        }
+        aov
+    }
+
+    /// 单条主光线的 (法线, 反照率, 距离, 运动矢量)；未命中时距离为无穷大，反照率为1
+    fn first_hit_aov(&self, r: &Ray, world: &dyn Hittable) -> (Vec3, Color, f64, Vec3) {
+        let mut ray = *r;
+        loop {
+            let mut rec = HitRecord::default();
+            if !world.hit(&ray, Interval::new(0.001, f64::INFINITY), &mut rec) {
+                return (
+                    Vec3::zeros(),
+                    Color::repeat(1.0),
+                    f64::INFINITY,
+                    Vec3::zeros(),
+                );
+            }
+            if Self::masked_out(&rec) {
+                ray.orig = rec.p;
+                continue;
+            }
+            let mut srec = ScatterRecord::new();
+            // 不散射的材质（发光体）不做解调
+            let albedo = if rec.mat.scatter(&ray, &rec, &mut srec) {
+                srec.attenuation.map(|c| c.clamp(0.0, 1.0))
+            } else {
+                Color::repeat(1.0)
+            };
+            let motion = self.motion_vector(&rec);
+            return (rec.normal, albedo, (rec.p - r.orig).norm(), motion);
+        }
+    }
+
+    /// 交点处的表面点从快门打开到关闭在图像上的位移（像素），静止或位于相机后方时为零
+    fn motion_vector(&self, rec: &HitRecord) -> Vec3 {
+        let Some([open, close]) = rec.motion else {
+            return Vec3::zeros();
+        };
+        if matches!(self.projection, Projection::LittlePlanet { .. }) {
+            return Vec3::zeros();
+        }
+        let near = 1e-3 * self.focus_dist;
+        let depth_open = -(open - self.center).dot(&self.w);
+        let depth_close = -(close - self.center).dot(&self.w);
+        if depth_open < near || depth_close < near {
+            return Vec3::zeros();
+        }
+        let (x0, y0) = self.project_to_pixel(&open, depth_open);
+        let (x1, y1) = self.project_to_pixel(&close, depth_close);
+        Vec3::new(x1 - x0, y1 - y0, 0.0)
+    }
+
+    /// 每个像素的弥散圆直径估计（像素），按行存储
+    ///
+    /// 用穿过像素中心的针孔光线求首个交点的深度，按薄透镜模型计算弥散圆在对焦平面上的直径，
+    /// 再换算为像素。未命中的像素按无穷远处计算；没有景深或使用小行星投影时全为0。
+    /// 移轴镜头的倾斜对焦平面按不倾斜处理。
+    pub fn circle_of_confusion(&self, world: &dyn Hittable) -> Vec<f64> {
+        assert!(
+            self.initialized,
+            "相机未初始化：请使用 CameraBuilder::build() 创建相机或调用 render()"
+        );
+        let pixel_count = (self.image_width * self.image_height) as usize;
+        if self.defocus_angle <= 0.0 || matches!(self.projection, Projection::LittlePlanet { .. }) {
+            return vec![0.0; pixel_count];
+        }
+
+        // 光圈直径换算为对焦平面上的像素数
+        let aperture = 2.0 * self.defocus_disk_u.norm() / self.pixel_delta_u.norm();
+        (0..self.image_width * self.image_height)
+            .into_par_iter()
+            .map(|index| {
+                let (i, j) = (index % self.image_width, index / self.image_width);
+                let ray = self.center_ray(i, j);
+                let mut rec = HitRecord::default();
+                if !world.hit(&ray, Interval::new(0.001, f64::INFINITY), &mut rec) {
+                    return aperture;
+                }
+                let depth = (rec.p - self.center).dot(&-self.w);
+                if depth <= 1e-9 {
+                    return aperture;
+                }
+                aperture * (depth - self.focus_dist).abs() / depth
+            })
+            .collect()
+    }
+
+    /// 景深感知采样的每像素分层网格边长，样本数按倍数增加；未启用或没有景深时为None
+    fn dof_sample_grids(&self, world: &dyn Hittable) -> Option<Vec<i32>> {
+        let settings = self.dof_sampling?;
+        if self.defocus_angle <= 0.0 || settings.weight <= 0.0 {
+            return None;
+        }
+        let coc = self.circle_of_confusion(world);
+        let multipliers =
+            settings.multipliers(&coc, self.image_width as usize, self.image_height as usize);
+        Some(
+            multipliers
+                .into_iter()
+                .map(|m| ((self.sqrt_spp as f64) * (m as f64).sqrt()).round() as i32)
+                .collect(),
+        )
+    }
+
+    /// 自动对焦：沿图像中心方向投射光线，将对焦距离设为命中点沿视线方向的距离
+    ///
+    /// 未命中任何物体时保持原值并返回None。
+    pub fn auto_focus(&mut self, world: &dyn Hittable) -> Option<f64> {
+        let direction = (self.lookat - self.lookfrom).normalize();
+        let ray = Ray::new(self.lookfrom, direction, 0.0);
+
+        let mut rec = HitRecord::default();
+        if !world.hit(&ray, Interval::new(0.001, f64::INFINITY), &mut rec) {
+            return None;
+        }
+
+        self.focus_dist = rec.t;
+        self.refresh();
+        Some(self.focus_dist)
+    }
+
+    /// 自动曝光：以低分辨率、低采样数渲染探测图像，按对数平均亮度将中灰映射到 0.18
+    pub fn auto_exposure(
+        &mut self,
+        world: &dyn Hittable,
+        lights: Option<Arc<dyn Hittable>>,
+    ) -> f64 {
+        let mut probe = self.clone();
+        probe.image_width = self.image_width.clamp(1, 64);
+        probe.samples_per_pixel = 16;
+        probe.max_depth = self.max_depth.min(8);
+        probe.exposure = 1.0;
+        probe.denoise = None;
+        probe.caustics = None;
+        probe.initialize();
+
+        let fb = probe.render_to_buffer(world, lights);
+
+        // 对数平均亮度（Reinhard 曝光键值）
+        let delta = 1e-4;
+        let log_sum: f64 = fb
+            .pixels()
+            .iter()
+            .map(|c| (delta + luminance(c).max(0.0)).ln())
+            .sum();
+        let log_average = (log_sum / fb.pixels().len().max(1) as f64).exp();
+
+        self.exposure = if log_average > delta {
+            0.18 / log_average
+        } else {
+            1.0
+        };
+        self.exposure
+    }
+
+    /// 同时设置相机位置和观察目标
+    pub fn set_view(&mut self, lookfrom: Point3, lookat: Point3) {
+        self.lookfrom = lookfrom;
+        self.lookat = lookat;
+        self.refresh();
+    }
+
+    /// 以观察目标为球心，按方位角、仰角（度）和距离放置相机
+    ///
+    /// 方位角从 +z 轴开始向 +x 轴旋转，仰角以 xz 平面为 0、向 +y 为正。
+    pub fn orbit(&mut self, azimuth: f64, elevation: f64, distance: f64) {
+        let (az, el) = (degrees_to_radians(azimuth), degrees_to_radians(elevation));
+        let direction = Vec3::new(el.cos() * az.sin(), el.sin(), el.cos() * az.cos());
+        self.lookfrom = self.lookat + distance.max(1e-6) * direction;
+        self.refresh();
+    }
+
+    /// 返回相机相对观察目标的球坐标 (方位角, 仰角, 距离)，角度单位为度
+    pub fn spherical_coords(&self) -> (f64, f64, f64) {
+        let offset = self.lookfrom - self.lookat;
+        let distance = offset.norm();
+        if distance < 1e-12 {
+            return (0.0, 0.0, 0.0);
+        }
+        let azimuth = offset.x.atan2(offset.z).to_degrees();
+        let elevation = (offset.y / distance).clamp(-1.0, 1.0).asin().to_degrees();
+        (azimuth, elevation, distance)
+    }
+
+    /// 自动取景：看向场景包围球的球心，沿当前视线方向后退到包围球恰好充满画面，返回是否有可取景的物体
+    ///
+    /// margin 为画面边缘留白占包围球半径的比例（如 0.1）。视场角保持不变，
+    /// 超出 [1°, 160°] 时改为 40°；对焦距离设为相机到球心的距离。适合为尺度差异很大的导入模型生成预览。
+    pub fn frame_scene(&mut self, scene: &Scene, margin: f64) -> bool {
+        match scene.bounding_sphere() {
+            Some((center, radius)) => {
+                self.frame_sphere(center, radius * (1.0 + margin.max(0.0)));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 取景使以 center 为球心、radius 为半径的球恰好充满画面，见 [`Camera::frame_scene`]
+    pub fn frame_sphere(&mut self, center: Point3, radius: f64) {
+        if !(1.0..=160.0).contains(&self.vfov) {
+            self.vfov = 40.0;
+        }
+        let direction = self.lookfrom - self.lookat;
+        let direction = if direction.norm_squared() > 1e-24 {
+            direction.normalize()
+        } else {
+            Vec3::new(0.0, 0.0, 1.0)
+        };
+        // 画面较窄一侧的半视场角决定距离
+        let half_vertical = degrees_to_radians(self.vfov) / 2.0;
+        let half_horizontal = (half_vertical.tan() * self.aspect_ratio).atan();
+        let half = half_vertical.min(half_horizontal);
+        let distance = radius.max(1e-9) / half.sin();
+
+        self.lookat = center;
+        self.lookfrom = center + distance * direction;
+        self.focus_dist = distance;
+        self.refresh();
+    }
+
+    /// 推拉：沿视线方向移动相机，正值靠近目标（不会越过目标点）
+    pub fn dolly(&mut self, amount: f64) {
+        let offset = self.lookfrom - self.lookat;
+        let distance = offset.norm();
+        if distance < 1e-12 {
+            return;
+        }
+        let new_distance = (distance - amount).max(1e-6);
+        self.lookfrom = self.lookat + offset * (new_distance / distance);
+        self.refresh();
+    }
+
+    /// 平移：相机和目标一起沿画面的右方向和上方向移动
+    pub fn track(&mut self, right: f64, up: f64) {
+        let w = (self.lookfrom - self.lookat).normalize();
+        let u = self.vup.cross(&w).normalize();
+        let v = w.cross(&u);
+        let delta = right * u + up * v;
+        self.lookfrom += delta;
+        self.lookat += delta;
+        self.refresh();
+    }
+
+    /// 摇镜：相机位置不变，绕向上方向旋转视线（度，正值向左）
+    pub fn pan(&mut self, degrees: f64) {
+        let axis = nalgebra::Unit::new_normalize(self.vup);
+        let rotation = nalgebra::Rotation3::from_axis_angle(&axis, degrees_to_radians(degrees));
+        self.lookat = self.lookfrom + rotation * (self.lookat - self.lookfrom);
+        self.refresh();
+    }
+
+    /// 修改相机参数后，若已初始化则重新计算派生参数
+    fn refresh(&mut self) {
+        if self.initialized {
+            self.initialize();
+        }
+    }
+}
+
+/// 参数访问：相机的配置字段是私有的，setter 在相机已初始化时会重新计算像素网格、散焦盘等派生参数
+impl Camera {
+    /// 宽高比
+    #[inline]
+    pub fn aspect_ratio(&self) -> f64 {
+        self.aspect_ratio
+    }
+
+    /// 设置宽高比
+    pub fn set_aspect_ratio(&mut self, aspect_ratio: f64) {
+        self.aspect_ratio = aspect_ratio;
+        self.refresh();
+    }
+
+    /// 图像宽度（像素）
+    #[inline]
+    pub fn image_width(&self) -> i32 {
+        self.image_width
+    }
+
+    /// 设置图像宽度（像素）
+    pub fn set_image_width(&mut self, image_width: i32) {
+        self.image_width = image_width;
+        self.refresh();
+    }
+
+    /// 每像素采样数
+    #[inline]
+    pub fn samples_per_pixel(&self) -> i32 {
+        self.samples_per_pixel
+    }
+
+    /// 设置每像素采样数
+    pub fn set_samples_per_pixel(&mut self, samples_per_pixel: i32) {
+        self.samples_per_pixel = samples_per_pixel;
+        self.refresh();
+    }
+
+    /// 最大反射深度
+    #[inline]
+    pub fn max_depth(&self) -> i32 {
+        self.max_depth
+    }
+
+    /// 设置最大反射深度
+    pub fn set_max_depth(&mut self, max_depth: i32) {
+        self.max_depth = max_depth;
+        self.refresh();
+    }
+
+    /// 背景
+    #[inline]
+    pub fn background(&self) -> &Arc<dyn Background> {
+        &self.background
+    }
+
+    /// 设置背景
+    pub fn set_background(&mut self, background: Arc<dyn Background>) {
+        self.background = background;
+        self.refresh();
+    }
+
+    /// 输出文件名
+    #[inline]
+    pub fn output_filename(&self) -> &str {
+        &self.output_filename
+    }
+
+    /// 设置输出文件名
+    pub fn set_output_filename(&mut self, output_filename: impl Into<String>) {
+        self.output_filename = output_filename.into();
+        self.refresh();
+    }
+
+    /// 输出格式，None 表示根据文件扩展名推断
+    #[inline]
+    pub fn output_format(&self) -> Option<OutputFormat> {
+        self.output_format
+    }
+
+    /// 设置输出格式，None 表示根据文件扩展名推断
+    pub fn set_output_format(&mut self, output_format: Option<OutputFormat>) {
+        self.output_format = output_format;
+        self.refresh();
+    }
+
+    /// 输出色彩空间
+    #[inline]
+    pub fn output_color_space(&self) -> OutputColorSpace {
+        self.output_color_space
+    }
+
+    /// 设置输出色彩空间
+    pub fn set_output_color_space(&mut self, output_color_space: OutputColorSpace) {
+        self.output_color_space = output_color_space;
+        self.refresh();
+    }
+
+    /// 垂直视场角（度）
+    #[inline]
+    pub fn vfov(&self) -> f64 {
+        self.vfov
+    }
+
+    /// 设置垂直视场角（度）
+    pub fn set_vfov(&mut self, vfov: f64) {
+        self.vfov = vfov;
+        self.refresh();
+    }
+
+    /// 相机位置
+    #[inline]
+    pub fn lookfrom(&self) -> Point3 {
+        self.lookfrom
+    }
+
+    /// 设置相机位置
+    pub fn set_lookfrom(&mut self, lookfrom: Point3) {
+        self.lookfrom = lookfrom;
+        self.refresh();
+    }
+
+    /// 观察目标点
+    #[inline]
+    pub fn lookat(&self) -> Point3 {
+        self.lookat
+    }
+
+    /// 设置观察目标点
+    pub fn set_lookat(&mut self, lookat: Point3) {
+        self.lookat = lookat;
+        self.refresh();
+    }
+
+    /// 相机向上方向
+    #[inline]
+    pub fn vup(&self) -> Vec3 {
+        self.vup
+    }
+
+    /// 设置相机向上方向
+    pub fn set_vup(&mut self, vup: Vec3) {
+        self.vup = vup;
+        self.refresh();
+    }
+
+    /// 散焦角（度），0表示无景深
+    #[inline]
+    pub fn defocus_angle(&self) -> f64 {
+        self.defocus_angle
+    }
+
+    /// 设置散焦角（度），0表示无景深
+    pub fn set_defocus_angle(&mut self, defocus_angle: f64) {
+        self.defocus_angle = defocus_angle;
+        self.refresh();
+    }
+
+    /// 对焦距离
+    #[inline]
+    pub fn focus_dist(&self) -> f64 {
+        self.focus_dist
+    }
+
+    /// 设置对焦距离
+    pub fn set_focus_dist(&mut self, focus_dist: f64) {
+        self.focus_dist = focus_dist;
+        self.refresh();
+    }
+
+    /// 投影方式
+    #[inline]
+    pub fn projection(&self) -> Projection {
+        self.projection
+    }
+
+    /// 设置投影方式
+    pub fn set_projection(&mut self, projection: Projection) {
+        self.projection = projection;
+        self.refresh();
+    }
+
+    /// 快门效率曲线
+    #[inline]
+    pub fn shutter(&self) -> &Shutter {
+        &self.shutter
+    }
+
+    /// 设置快门效率曲线
+    pub fn set_shutter(&mut self, shutter: Shutter) {
+        self.shutter = shutter;
+        self.refresh();
+    }
+
+    /// 像素重建滤波器
+    #[inline]
+    pub fn filter(&self) -> Filter {
+        self.filter
+    }
+
+    /// 设置像素重建滤波器
+    pub fn set_filter(&mut self, filter: Filter) {
+        self.filter = filter;
+        self.refresh();
+    }
+
+    /// 曝光倍数
+    #[inline]
+    pub fn exposure(&self) -> f64 {
+        self.exposure
+    }
+
+    /// 设置曝光倍数
+    pub fn set_exposure(&mut self, exposure: f64) {
+        self.exposure = exposure;
+        self.refresh();
+    }
+
+    /// 随机种子
+    #[inline]
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// 设置随机种子，None 表示不可复现的线程随机数
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+        self.refresh();
+    }
+
+    /// 辐照度缓存预览参数
+    #[inline]
+    pub fn irradiance_cache(&self) -> Option<IrradianceCacheSettings> {
+        self.irradiance_cache
+    }
+
+    /// 设置辐照度缓存预览参数（None 关闭）
+    pub fn set_irradiance_cache(&mut self, irradiance_cache: Option<IrradianceCacheSettings>) {
+        self.irradiance_cache = irradiance_cache;
+        self.refresh();
+    }
+
+    /// 是否输出透明背景
+    #[inline]
+    pub fn transparent_background(&self) -> bool {
+        self.transparent_background
+    }
+
+    /// 设置是否输出透明背景
+    pub fn set_transparent_background(&mut self, transparent_background: bool) {
+        self.transparent_background = transparent_background;
+        self.refresh();
+    }
+
+    /// 是否启用环境光快速模式
+    #[inline]
+    pub fn baked_environment(&self) -> bool {
+        self.baked_environment
+    }
+
+    /// 设置是否启用环境光快速模式
+    pub fn set_baked_environment(&mut self, baked_environment: bool) {
+        self.baked_environment = baked_environment;
+        self.refresh();
+    }
+
+    /// 是否启用透明阴影
+    #[inline]
+    pub fn transparent_shadows(&self) -> bool {
+        self.transparent_shadows
+    }
+
+    /// 设置是否启用透明阴影
+    pub fn set_transparent_shadows(&mut self, transparent_shadows: bool) {
+        self.transparent_shadows = transparent_shadows;
+        self.refresh();
+    }
+
+    /// 积分器
+    #[inline]
+    pub fn integrator(&self) -> Integrator {
+        self.integrator
+    }
+
+    /// 设置积分器
+    pub fn set_integrator(&mut self, integrator: Integrator) {
+        self.integrator = integrator;
+        self.refresh();
+    }
+
+    /// 路径终止策略
+    #[inline]
+    pub fn termination(&self) -> TerminationPolicy {
+        self.termination
+    }
+
+    /// 设置路径终止策略
+    pub fn set_termination(&mut self, termination: TerminationPolicy) {
+        self.termination = termination;
+        self.refresh();
+    }
+
+    /// 降噪参数
+    #[inline]
+    pub fn denoise(&self) -> Option<DenoiseSettings> {
+        self.denoise
+    }
+
+    /// 设置降噪参数，None 表示不降噪
+    pub fn set_denoise(&mut self, denoise: Option<DenoiseSettings>) {
+        self.denoise = denoise;
+        self.refresh();
+    }
+
+    /// 焦散光子映射参数
+    #[inline]
+    pub fn caustics(&self) -> Option<CausticSettings> {
+        self.caustics
+    }
+
+    /// 设置焦散光子映射参数（None 关闭）
+    pub fn set_caustics(&mut self, caustics: Option<CausticSettings>) {
+        self.caustics = caustics;
+        self.refresh();
+    }
+
+    /// 景深感知采样参数
+    #[inline]
+    pub fn dof_sampling(&self) -> Option<DofSampling> {
+        self.dof_sampling
+    }
+
+    /// 设置景深感知采样参数（None 为每像素样本数相同）
+    pub fn set_dof_sampling(&mut self, dof_sampling: Option<DofSampling>) {
+        self.dof_sampling = dof_sampling;
+        self.refresh();
+    }
+
+    /// 渐进渲染的停止条件
+    #[inline]
+    pub fn halt(&self) -> Option<HaltCondition> {
+        self.halt
+    }
+
+    /// 设置渐进渲染的停止条件
+    pub fn set_halt(&mut self, halt: Option<HaltCondition>) {
+        self.halt = halt;
+        self.refresh();
+    }
+
+    /// 包围盒线框叠加模式
+    #[inline]
+    pub fn bounds_overlay(&self) -> BoundsOverlay {
+        self.bounds_overlay
+    }
+
+    /// 设置包围盒线框叠加模式
+    pub fn set_bounds_overlay(&mut self, bounds_overlay: BoundsOverlay) {
+        self.bounds_overlay = bounds_overlay;
+        self.refresh();
+    }
+
+    /// 渲染统计收集器
+    #[inline]
+    pub fn stats(&self) -> Option<&Arc<RenderStats>> {
+        self.stats.as_ref()
+    }
+
+    /// 设置渲染统计收集器（None 关闭）
+    pub fn set_stats(&mut self, stats: Option<Arc<RenderStats>>) {
+        self.stats = stats;
+        self.refresh();
+    }
+
+    /// 是否在渲染结束时写出 JSON 渲染报告
+    #[inline]
+    pub fn report(&self) -> bool {
+        self.report
+    }
+
+    /// 设置是否在渲染结束时写出 JSON 渲染报告
+    pub fn set_report(&mut self, report: bool) {
+        self.report = report;
+        self.refresh();
+    }
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 相机构建器，链式设置参数后由 `build()` 一次性完成初始化计算
+#[derive(Debug, Clone, Default)]
+pub struct CameraBuilder {
+    camera: Camera,
+}
+
+impl CameraBuilder {
+    /// 从默认相机参数开始构建
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            camera: Camera::new(),
+        }
+    }
+
+    /// 设置宽高比
+    #[inline]
+    pub fn aspect_ratio(mut self, aspect_ratio: f64) -> Self {
+        self.camera.aspect_ratio = aspect_ratio;
+        self
+    }
+
+    /// 设置图像宽度（像素）
+    #[inline]
+    pub fn image_width(mut self, image_width: i32) -> Self {
+        self.camera.image_width = image_width;
+        self
+    }
+
+    /// 设置每像素采样数
+    #[inline]
+    pub fn samples_per_pixel(mut self, samples_per_pixel: i32) -> Self {
+        self.camera.samples_per_pixel = samples_per_pixel;
+        self
+    }
+
+    /// 设置最大反射深度
+    #[inline]
+    pub fn max_depth(mut self, max_depth: i32) -> Self {
+        self.camera.max_depth = max_depth;
+        self
+    }
+
+    /// 设置背景
+    #[inline]
+    pub fn background(mut self, background: Arc<dyn Background>) -> Self {
+        self.camera.background = background;
+        self
+    }
+
+    /// 设置纯色背景
+    #[inline]
+    pub fn background_color(mut self, color: Color) -> Self {
+        self.camera.background = Arc::new(ConstantColor::new(color));
+        self
+    }
+
+    /// 设置输出文件名
+    #[inline]
+    pub fn output_filename(mut self, output_filename: impl Into<String>) -> Self {
+        self.camera.output_filename = output_filename.into();
+        self
+    }
+
+    /// 设置输出格式（默认根据文件扩展名推断）
+    #[inline]
+    pub fn output_format(mut self, output_format: OutputFormat) -> Self {
+        self.camera.output_format = Some(output_format);
+        self
+    }
+
+    /// 设置垂直视场角（度）
+    #[inline]
+    pub fn vfov(mut self, vfov: f64) -> Self {
+        self.camera.vfov = vfov;
+        self
+    }
+
+    /// 设置相机位置
+    #[inline]
+    pub fn lookfrom(mut self, lookfrom: Point3) -> Self {
+        self.camera.lookfrom = lookfrom;
+        self
+    }
+
+    /// 设置观察目标点
+    #[inline]
+    pub fn lookat(mut self, lookat: Point3) -> Self {
+        self.camera.lookat = lookat;
+        self
+    }
+
+    /// 以当前观察目标为球心放置相机（方位角、仰角单位为度），需在 `lookat` 之后调用
+    #[inline]
+    pub fn orbit(mut self, azimuth: f64, elevation: f64, distance: f64) -> Self {
+        self.camera.orbit(azimuth, elevation, distance);
+        self
+    }
+
+    /// 自动取景，见 [`Camera::frame_scene`]；应在设置宽高比、视场角和视线方向之后调用
+    pub fn frame_scene(mut self, scene: &Scene, margin: f64) -> Self {
+        self.camera.frame_scene(scene, margin);
+        self
+    }
+
+    /// 设置相机向上方向
+    #[inline]
+    pub fn vup(mut self, vup: Vec3) -> Self {
+        self.camera.vup = vup;
+        self
+    }
+
+    /// 设置散焦角（度），0表示无景深
+    #[inline]
+    pub fn defocus_angle(mut self, defocus_angle: f64) -> Self {
+        self.camera.defocus_angle = defocus_angle;
+        self
+    }
+
+    /// 设置对焦距离
+    #[inline]
+    pub fn focus_dist(mut self, focus_dist: f64) -> Self {
+        self.camera.focus_dist = focus_dist;
+        self
+    }
+
+    /// 设置投影方式
+    #[inline]
+    pub fn projection(mut self, projection: Projection) -> Self {
+        self.camera.projection = projection;
+        self
+    }
+
+    /// 设置快门效率曲线
+    #[inline]
+    pub fn shutter(mut self, shutter: Shutter) -> Self {
+        self.camera.shutter = shutter;
+        self
+    }
+
+    /// 设置像素重建滤波器
+    #[inline]
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.camera.filter = filter;
+        self
+    }
+
+    /// 设置曝光倍数
+    #[inline]
+    pub fn exposure(mut self, exposure: f64) -> Self {
+        self.camera.exposure = exposure;
+        self
+    }
+
+    /// 按 ISO、快门和光圈计算曝光倍数（场景辐射度按尼特解释）
+    #[inline]
+    pub fn physical_exposure(mut self, physical: PhysicalExposure) -> Self {
+        self.camera.exposure = physical.exposure();
+        self
+    }
+
+    /// 设置随机种子，使渲染结果可复现
+    #[inline]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.camera.seed = Some(seed);
+        self
+    }
+
+    /// 启用辐照度缓存预览模式（有偏，适合快速迭代室内场景）
+    #[inline]
+    pub fn irradiance_cache(mut self, settings: IrradianceCacheSettings) -> Self {
+        self.camera.irradiance_cache = Some(settings);
+        self
+    }
+
+    /// 设置输出色彩空间
+    #[inline]
+    pub fn output_color_space(mut self, color_space: OutputColorSpace) -> Self {
+        self.camera.output_color_space = color_space;
+        self
+    }
+
+    /// 设置是否输出透明背景
+    #[inline]
+    pub fn transparent_background(mut self, transparent: bool) -> Self {
+        self.camera.transparent_background = transparent;
+        self
+    }
+
+    /// 启用环境光快速模式（有偏，漫反射使用预烘焙的环境辐照度）
+    #[inline]
+    pub fn baked_environment(mut self, enabled: bool) -> Self {
+        self.camera.baked_environment = enabled;
+        self
+    }
+
+    /// 启用透明阴影（有偏，玻璃等透明物体投下带颜色的明亮阴影，需要光源列表）
+    #[inline]
+    pub fn transparent_shadows(mut self, enabled: bool) -> Self {
+        self.camera.transparent_shadows = enabled;
+        self
+    }
+
+    /// 设置积分器
+    #[inline]
+    pub fn integrator(mut self, integrator: Integrator) -> Self {
+        self.camera.integrator = integrator;
+        self
+    }
+
+    /// 设置降噪参数，None 表示不降噪
+    #[inline]
+    pub fn denoise(mut self, denoise: Option<DenoiseSettings>) -> Self {
+        self.camera.denoise = denoise;
+        self
+    }
+
+    /// 设置焦散光子映射参数（None 关闭）
+    #[inline]
+    pub fn caustics(mut self, caustics: Option<CausticSettings>) -> Self {
+        self.camera.caustics = caustics;
+        self
+    }
+
+    /// 设置路径终止策略
+    #[inline]
+    pub fn termination(mut self, termination: TerminationPolicy) -> Self {
+        self.camera.termination = termination;
+        self
+    }
+
+    /// 设置包围盒线框叠加模式
+    #[inline]
+    pub fn bounds_overlay(mut self, bounds_overlay: BoundsOverlay) -> Self {
+        self.camera.bounds_overlay = bounds_overlay;
+        self
+    }
+
+    /// 设置渐进渲染的停止条件，None 或未设置任何条件时按固定样本数渲染
+    #[inline]
+    pub fn halt(mut self, halt: Option<HaltCondition>) -> Self {
+        self.camera.halt = halt;
+        self
+    }
+
+    /// 设置景深感知采样参数（None 为每像素样本数相同）
+    #[inline]
+    pub fn dof_sampling(mut self, dof_sampling: Option<DofSampling>) -> Self {
+        self.camera.dof_sampling = dof_sampling;
+        self
+    }
+
+    /// 设置渲染统计收集器
+    #[inline]
+    pub fn stats(mut self, stats: Arc<RenderStats>) -> Self {
+        self.camera.stats = Some(stats);
+        self
+    }
+
+    /// 设置是否在渲染结束时写出 JSON 渲染报告
+    #[inline]
+    pub fn report(mut self, report: bool) -> Self {
+        self.camera.report = report;
+        self
+    }
+
+    /// 完成初始化计算并返回相机
+    pub fn build(mut self) -> Camera {
+        self.camera.initialize();
+        self.camera
     }
 }
@@ -0,0 +1,245 @@
+//! 相机路径：由关键帧的 lookfrom/lookat 插值出任意时刻的机位
+//!
+//! 支持经过所有关键帧的 Catmull-Rom 样条，以及把关键帧当作控制点的 Bezier 曲线（只经过首尾）。
+//! 整段路径的时间可以先经过缓动函数，实现起步加速、结束减速。
+
+use super::camera::Camera;
+use crate::ray_tracing::math::vec3::Point3;
+use std::fmt;
+use std::str::FromStr;
+
+/// 样条类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SplineKind {
+    /// 均匀 Catmull-Rom：经过每个关键帧，相邻段之间切线连续
+    #[default]
+    CatmullRom,
+    /// Bezier：关键帧为控制点，曲线只经过首尾两个关键帧，忽略关键帧时间
+    Bezier,
+}
+
+impl FromStr for SplineKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "catmull-rom" | "catmullrom" | "catmull_rom" => Ok(Self::CatmullRom),
+            "bezier" => Ok(Self::Bezier),
+            _ => Err(format!(
+                "未知的样条类型 `{}`（可选: catmull-rom, bezier）",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for SplineKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::CatmullRom => "catmull-rom",
+            Self::Bezier => "bezier",
+        })
+    }
+}
+
+/// 时间缓动函数，把 [0,1] 映射到 [0,1]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Easing {
+    /// 匀速
+    #[default]
+    Linear,
+    /// 从静止加速
+    EaseIn,
+    /// 减速到静止
+    EaseOut,
+    /// 两端都平滑（smoothstep）
+    EaseInOut,
+}
+
+impl Easing {
+    /// 缓动后的时间
+    #[inline]
+    pub fn apply(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => t * (2.0 - t),
+            Self::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+impl FromStr for Easing {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "linear" => Ok(Self::Linear),
+            "ease-in" | "ease_in" => Ok(Self::EaseIn),
+            "ease-out" | "ease_out" => Ok(Self::EaseOut),
+            "ease-in-out" | "ease_in_out" | "smooth" => Ok(Self::EaseInOut),
+            _ => Err(format!(
+                "未知的缓动函数 `{}`（可选: linear, ease-in, ease-out, ease-in-out）",
+                s
+            )),
+        }
+    }
+}
+
+/// 相机关键帧
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraKey {
+    /// 关键帧在整段路径中的时间，[0,1]
+    pub time: f64,
+    pub lookfrom: Point3,
+    pub lookat: Point3,
+}
+
+/// 由关键帧插值的相机路径
+#[derive(Debug, Clone, PartialEq)]
+pub struct CameraPath {
+    keys: Vec<CameraKey>,
+    spline: SplineKind,
+    easing: Easing,
+}
+
+impl CameraPath {
+    /// 由关键帧创建路径，关键帧按时间排序
+    ///
+    /// # Panics
+    /// 没有关键帧时 panic。
+    pub fn new(spline: SplineKind, mut keys: Vec<CameraKey>) -> Self {
+        assert!(!keys.is_empty(), "相机路径至少需要一个关键帧");
+        keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Self {
+            keys,
+            spline,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// 关键帧在时间上均匀分布的路径，views 为 (lookfrom, lookat)
+    pub fn uniform(spline: SplineKind, views: &[(Point3, Point3)]) -> Self {
+        let last = views.len().saturating_sub(1).max(1) as f64;
+        let keys = views
+            .iter()
+            .enumerate()
+            .map(|(i, &(lookfrom, lookat))| CameraKey {
+                time: i as f64 / last,
+                lookfrom,
+                lookat,
+            })
+            .collect();
+        Self::new(spline, keys)
+    }
+
+    /// 设置整段路径的缓动函数
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// 关键帧
+    #[inline]
+    pub fn keys(&self) -> &[CameraKey] {
+        &self.keys
+    }
+
+    /// 样条类型
+    #[inline]
+    pub fn spline(&self) -> SplineKind {
+        self.spline
+    }
+
+    /// 缓动函数
+    #[inline]
+    pub fn easing(&self) -> Easing {
+        self.easing
+    }
+
+    /// 时刻 t ∈ [0,1] 的 (lookfrom, lookat)
+    pub fn sample(&self, t: f64) -> (Point3, Point3) {
+        let t = self.easing.apply(t);
+        let lookfrom: Vec<Point3> = self.keys.iter().map(|k| k.lookfrom).collect();
+        let lookat: Vec<Point3> = self.keys.iter().map(|k| k.lookat).collect();
+        match self.spline {
+            SplineKind::Bezier => (bezier(&lookfrom, t), bezier(&lookat, t)),
+            SplineKind::CatmullRom => {
+                let (segment, u) = self.locate(t);
+                (
+                    catmull_rom(&lookfrom, segment, u),
+                    catmull_rom(&lookat, segment, u),
+                )
+            }
+        }
+    }
+
+    /// 把相机放到时刻 t 的机位
+    pub fn apply(&self, camera: &mut Camera, t: f64) {
+        let (lookfrom, lookat) = self.sample(t);
+        camera.set_view(lookfrom, lookat);
+    }
+
+    /// 时刻 t 所在的关键帧段及段内的参数
+    fn locate(&self, t: f64) -> (usize, f64) {
+        let keys = &self.keys;
+        if keys.len() < 2 || t <= keys[0].time {
+            return (0, 0.0);
+        }
+        let last = keys.len() - 1;
+        if t >= keys[last].time {
+            return (last - 1, 1.0);
+        }
+        let segment = keys.partition_point(|k| k.time <= t).clamp(1, last) - 1;
+        let span = keys[segment + 1].time - keys[segment].time;
+        let u = if span > 0.0 {
+            (t - keys[segment].time) / span
+        } else {
+            0.0
+        };
+        (segment, u)
+    }
+}
+
+/// 均匀 Catmull-Rom 在第 segment 段（points[segment] 到 points[segment+1]）参数 u 处的点
+///
+/// 首尾段缺少的外侧控制点由端点镜像得到，使路径在端点处沿相邻关键帧方向出发。
+fn catmull_rom(points: &[Point3], segment: usize, u: f64) -> Point3 {
+    let n = points.len();
+    if n == 1 {
+        return points[0];
+    }
+    let p1 = points[segment].coords;
+    let p2 = points[segment + 1].coords;
+    let p0 = if segment > 0 {
+        points[segment - 1].coords
+    } else {
+        2.0 * p1 - p2
+    };
+    let p3 = if segment + 2 < n {
+        points[segment + 2].coords
+    } else {
+        2.0 * p2 - p1
+    };
+
+    let u2 = u * u;
+    let u3 = u2 * u;
+    Point3::from(
+        0.5 * (2.0 * p1
+            + (p2 - p0) * u
+            + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * u2
+            + (3.0 * p1 - p0 - 3.0 * p2 + p3) * u3),
+    )
+}
+
+/// 以 points 为控制点的 Bezier 曲线（de Casteljau 算法）
+fn bezier(points: &[Point3], t: f64) -> Point3 {
+    let mut work: Vec<_> = points.iter().map(|p| p.coords).collect();
+    for level in (1..work.len()).rev() {
+        for i in 0..level {
+            work[i] = work[i] * (1.0 - t) + work[i + 1] * t;
+        }
+    }
+    Point3::from(work[0])
+}
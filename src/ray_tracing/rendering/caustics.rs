@@ -0,0 +1,306 @@
+//! 光子映射焦散层（概率渐进光子映射，Knaus & Zwicker 2011），与路径追踪结合
+//!
+//! 从光源列表发射光子，只保存经过至少一次镜面散射后落在非镜面表面上的光子（L S+ D 路径）。
+//! 路径追踪在每个非镜面交点上用附近的光子估计这部分光照，同时丢弃从非镜面交点出发、
+//! 只经过镜面散射到达发光体的贡献（[`RayKind::Caustic`] 光线），避免重复计算。
+//!
+//! 光子分多轮发射，第 i 轮的收集半径按 r²ᵢ = r²ᵢ₋₁·(i+α)/(i+1) 缩小；每次查询随机选取一轮，
+//! 对像素样本取平均后偏差随轮数增加而减小。光子从光源列表中形状的表面三角形发射，
+//! 不提供三角形的光源（如球体）和未列入光源列表的发光体不产生焦散。
+//!
+//! [`RayKind::Caustic`]: crate::ray_tracing::math::ray::RayKind::Caustic
+
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable, UvTriangle};
+use crate::ray_tracing::materials::material::ScatterRecord;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::{Ray, RayKind};
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::sampling::pdf::{CosinePDF, PDF};
+use crate::ray_tracing::utils::random::{hash_seed, random_double, with_seeded_stream};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+/// 查询时接受的光子法线与表面法线的最小夹角余弦，避免薄物体两侧的光子互相泄漏
+const NORMAL_COSINE: f64 = 0.5;
+
+/// 发射点到查询发光材质的射线偏移
+const EMIT_OFFSET: f64 = 0.01;
+
+/// 焦散光子映射参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CausticSettings {
+    /// 每轮发射的光子数
+    pub photons: usize,
+    /// 轮数
+    pub passes: u32,
+    /// 第一轮的收集半径，0 为自动（场景包围盒对角线的 0.5%）
+    pub radius: f64,
+    /// 半径缩小速度 α ∈ (0,1)，越小缩得越快（偏差小、噪声大）
+    pub alpha: f64,
+}
+
+impl Default for CausticSettings {
+    fn default() -> Self {
+        Self {
+            photons: 200_000,
+            passes: 8,
+            radius: 0.0,
+            alpha: 2.0 / 3.0,
+        }
+    }
+}
+
+impl CausticSettings {
+    /// 每轮发射 photons 个光子，其余参数取默认值
+    pub fn with_photons(photons: usize) -> Self {
+        Self {
+            photons,
+            ..Self::default()
+        }
+    }
+}
+
+/// 落在表面上的光子
+#[derive(Debug, Clone, Copy)]
+struct Photon {
+    p: Point3,
+    /// 表面法线（朝向光子到来的一侧）
+    normal: Vec3,
+    /// 指向光子来向的单位向量
+    wi: Vec3,
+    power: Color,
+}
+
+/// 单轮光子及其均匀网格索引，网格边长为收集直径，查询只需检查 2×2×2 个单元
+#[derive(Debug)]
+struct PhotonPass {
+    photons: Vec<Photon>,
+    cells: HashMap<[i64; 3], Vec<u32>>,
+    radius: f64,
+}
+
+impl PhotonPass {
+    fn new(photons: Vec<Photon>, radius: f64) -> Self {
+        let mut cells: HashMap<[i64; 3], Vec<u32>> = HashMap::new();
+        for (index, photon) in photons.iter().enumerate() {
+            cells
+                .entry(cell_of(&photon.p, 2.0 * radius))
+                .or_default()
+                .push(index as u32);
+        }
+        Self {
+            photons,
+            cells,
+            radius,
+        }
+    }
+
+    /// 交点处由焦散光子估计的出射辐射度
+    fn estimate(&self, r: &Ray, rec: &HitRecord, srec: &ScatterRecord) -> Color {
+        // 以 p - r 所在单元为起点的 2×2×2 个单元覆盖整个收集球
+        let corner = rec.p - Vec3::repeat(self.radius);
+        let [cx, cy, cz] = cell_of(&corner, 2.0 * self.radius);
+        let radius_squared = self.radius * self.radius;
+        let mut flux = Color::zeros();
+        for dx in 0..=1 {
+            for dy in 0..=1 {
+                for dz in 0..=1 {
+                    let Some(indices) = self.cells.get(&[cx + dx, cy + dy, cz + dz]) else {
+                        continue;
+                    };
+                    for &index in indices {
+                        let photon = &self.photons[index as usize];
+                        if (photon.p - rec.p).norm_squared() > radius_squared
+                            || photon.normal.dot(&rec.normal) < NORMAL_COSINE
+                        {
+                            continue;
+                        }
+                        // BRDF f = bsdf_cos/cosθ（朗伯材质为 albedo/π）
+                        let cos_theta = photon.wi.dot(&rec.normal);
+                        if cos_theta <= 0.0 {
+                            continue;
+                        }
+                        let incoming = Ray::new(rec.p, photon.wi, r.time);
+                        let bsdf_cos = rec.mat.bsdf_cos(r, rec, srec, &incoming);
+                        flux += photon.power.component_mul(&bsdf_cos) / cos_theta;
+                    }
+                }
+            }
+        }
+        flux / (PI * radius_squared)
+    }
+}
+
+fn cell_of(p: &Point3, size: f64) -> [i64; 3] {
+    [
+        (p.x / size).floor() as i64,
+        (p.y / size).floor() as i64,
+        (p.z / size).floor() as i64,
+    ]
+}
+
+/// 多轮焦散光子图
+#[derive(Debug)]
+pub struct CausticMap {
+    passes: Vec<PhotonPass>,
+}
+
+impl CausticMap {
+    /// 从光源列表发射光子并建立光子图
+    ///
+    /// max_depth 限制光子的镜面散射次数；seed 设置后结果可复现。
+    pub fn build(
+        world: &dyn Hittable,
+        lights: &dyn Hittable,
+        settings: &CausticSettings,
+        max_depth: i32,
+        seed: Option<u64>,
+    ) -> Self {
+        let emitters = Emitters::new(lights.uv_triangles());
+        let mut radius = if settings.radius > 0.0 {
+            settings.radius
+        } else {
+            world.bounding_box().map_or(1.0, |b| {
+                let diagonal = Vec3::new(b.x.size(), b.y.size(), b.z.size()).norm();
+                if diagonal.is_finite() && diagonal > 0.0 {
+                    diagonal * 0.005
+                } else {
+                    1.0
+                }
+            })
+        };
+        let alpha = settings.alpha.clamp(0.01, 0.99);
+
+        let mut passes = Vec::with_capacity(settings.passes as usize);
+        for pass in 0..settings.passes.max(1) {
+            if pass > 0 {
+                let i = pass as f64;
+                radius *= ((i + alpha) / (i + 1.0)).sqrt();
+            }
+            let photons: Vec<Photon> = if emitters.is_empty() {
+                Vec::new()
+            } else {
+                (0..settings.photons)
+                    .into_par_iter()
+                    .filter_map(|k| {
+                        let trace = || emitters.trace(world, settings.photons, max_depth);
+                        match seed {
+                            Some(seed) => {
+                                with_seeded_stream(hash_seed(&[seed, pass as u64, k as u64]), trace)
+                            }
+                            None => trace(),
+                        }
+                    })
+                    .collect()
+            };
+            passes.push(PhotonPass::new(photons, radius));
+        }
+        Self { passes }
+    }
+
+    /// 各轮保存的焦散光子总数
+    pub fn photon_count(&self) -> usize {
+        self.passes.iter().map(|p| p.photons.len()).sum()
+    }
+
+    /// 随机选取一轮，估计交点处焦散光照的出射辐射度
+    ///
+    /// srec 为交点处材质的散射记录，用于求光子方向上的 BRDF 值。
+    pub fn estimate(&self, r: &Ray, rec: &HitRecord, srec: &ScatterRecord) -> Color {
+        if self.passes.is_empty() {
+            return Color::zeros();
+        }
+        let index =
+            ((random_double() * self.passes.len() as f64) as usize).min(self.passes.len() - 1);
+        self.passes[index].estimate(r, rec, srec)
+    }
+}
+
+/// 按面积采样的发光表面
+struct Emitters {
+    triangles: Vec<UvTriangle>,
+    /// 面积的累积和
+    cdf: Vec<f64>,
+}
+
+impl Emitters {
+    fn new(triangles: Vec<UvTriangle>) -> Self {
+        let mut total = 0.0;
+        let cdf = triangles
+            .iter()
+            .map(|t| {
+                total += 0.5 * (t.p[1] - t.p[0]).cross(&(t.p[2] - t.p[0])).norm();
+                total
+            })
+            .collect();
+        Self { triangles, cdf }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.cdf.last().is_none_or(|&area| area <= 0.0)
+    }
+
+    /// 发射一个光子并追踪，落在非镜面表面且经过镜面散射时返回该光子
+    fn trace(&self, world: &dyn Hittable, photon_count: usize, max_depth: i32) -> Option<Photon> {
+        let area = *self.cdf.last()?;
+        let target = random_double() * area;
+        let index = self
+            .cdf
+            .partition_point(|&c| c <= target)
+            .min(self.cdf.len() - 1);
+        let triangle = &self.triangles[index];
+
+        // 三角形内均匀采样
+        let (r1, r2) = (random_double().sqrt(), random_double());
+        let b = [1.0 - r1, r1 * (1.0 - r2), r1 * r2];
+        let p = triangle.point(&b);
+        // 正反两面各以一半概率发射
+        let side = if random_double() < 0.5 { 1.0 } else { -1.0 };
+        let normal = triangle.normal(&b) * side;
+        let direction = CosinePDF::new(&normal).generate().normalize();
+
+        // 沿发射方向回看光源表面，得到发光材质在该方向上的辐射度
+        let probe = Ray::new(p + direction * EMIT_OFFSET, -direction, 0.0);
+        let mut rec = HitRecord::default();
+        if !world.hit(&probe, Interval::new(0.0, 2.0 * EMIT_OFFSET), &mut rec) {
+            return None;
+        }
+        let radiance = rec.mat.emitted_towards(&probe, &rec);
+        if radiance == Color::zeros() {
+            return None;
+        }
+
+        // 余弦采样方向、按面积采样位置、两面各半：Φ = L·π·A·2 / N
+        let mut power = radiance * (2.0 * PI * area / photon_count as f64);
+        let mut ray = Ray::new(p, direction, random_double());
+        let mut specular = false;
+        for _ in 0..max_depth.max(1) {
+            let mut rec = HitRecord::default();
+            if !world.hit(&ray, Interval::new(0.001, f64::INFINITY), &mut rec) {
+                return None;
+            }
+            let alpha = rec.mat.alpha(rec.u, rec.v, &rec.p);
+            if alpha < 1.0 && random_double() >= alpha {
+                ray.orig = rec.p;
+                continue;
+            }
+            let mut srec = ScatterRecord::new();
+            if !rec.mat.scatter(&ray, &rec, &mut srec) {
+                return None;
+            }
+            if !srec.skip_pdf {
+                return specular.then(|| Photon {
+                    p: rec.p,
+                    normal: rec.normal,
+                    wi: -ray.dir.normalize(),
+                    power,
+                });
+            }
+            power = power.component_mul(&srec.attenuation);
+            specular = true;
+            ray = srec.skip_pdf_ray.with_kind(RayKind::Specular);
+        }
+        None
+    }
+}
@@ -51,6 +51,20 @@ pub fn color_to_rgb_with_samples(pixel_color: &Color, samples_per_pixel: i32) ->
     Rgb([r_byte, g_byte, b_byte])
 }
 
+/// 将线性HDR颜色转换为伽马校正后的显示值，各分量限制在 [0, 1]
+pub fn color_to_display(pixel_color: &Color) -> Color {
+    let display = |c: f64| {
+        let c = if c.is_nan() { 0.0 } else { c };
+        linear_to_gamma(c).clamp(0.0, 1.0)
+    };
+
+    Color::new(
+        display(pixel_color.x),
+        display(pixel_color.y),
+        display(pixel_color.z),
+    )
+}
+
 // HSV到RGB转换辅助函数
 pub fn hsv_to_rgb(h: f64, s: f64, v: f64) -> (f64, f64, f64) {
     let h = h - h.floor(); // 归一化到[0,1]
@@ -72,3 +86,59 @@ pub fn hsv_to_rgb(h: f64, s: f64, v: f64) -> (f64, f64, f64) {
         _ => (v, p, q),
     }
 }
+
+/// 线性颜色的相对亮度（Rec.709 权重）
+#[inline]
+pub fn luminance(color: &Color) -> f64 {
+    0.2126 * color.x + 0.7152 * color.y + 0.0722 * color.z
+}
+
+/// 黑体辐射的颜色（线性 sRGB），按亮度归一化为 1
+///
+/// 普朗克光谱在 380–780nm 上对 CIE 1931 配色函数（Wyman 等人的多高斯拟合）积分得到 XYZ，
+/// 再转换到 sRGB 原色；超出色域的负分量截断为 0。2700K 为暖白的白炽灯，6500K 接近日光白。
+pub fn blackbody(kelvin: f64) -> Color {
+    // 第二辐射常数 c2 = hc/k（m·K）
+    const C2: f64 = 1.4388e-2;
+    let t = kelvin.max(100.0);
+    let lobe = |x: f64, mu: f64, s1: f64, s2: f64| {
+        let s = if x < mu { s1 } else { s2 };
+        (-0.5 * ((x - mu) / s).powi(2)).exp()
+    };
+
+    let mut xyz = Color::zeros();
+    for step in 0..=80 {
+        let nm = 380.0 + 5.0 * step as f64;
+        let lambda = nm * 1e-9;
+        let radiance = 1.0 / (lambda.powi(5) * ((C2 / (lambda * t)).exp() - 1.0));
+        let cmf = Color::new(
+            1.056 * lobe(nm, 599.8, 37.9, 31.0) + 0.362 * lobe(nm, 442.0, 16.0, 26.7)
+                - 0.065 * lobe(nm, 501.1, 20.4, 26.2),
+            0.821 * lobe(nm, 568.8, 46.9, 40.5) + 0.286 * lobe(nm, 530.9, 16.3, 31.1),
+            1.217 * lobe(nm, 437.0, 11.8, 36.0) + 0.681 * lobe(nm, 459.0, 26.0, 13.8),
+        );
+        xyz += radiance * cmf;
+    }
+
+    let rgb = Color::new(
+        3.2404542 * xyz.x - 1.5371385 * xyz.y - 0.4985314 * xyz.z,
+        -0.9692660 * xyz.x + 1.8760108 * xyz.y + 0.0415560 * xyz.z,
+        0.0556434 * xyz.x - 0.2040259 * xyz.y + 1.0572252 * xyz.z,
+    )
+    .map(|c| c.max(0.0));
+    let lum = luminance(&rgb);
+    if lum > 0.0 { rgb / lum } else { Color::zeros() }
+}
+
+/// 颜色的构造扩展，`Color` 是 nalgebra 向量的别名，无法直接添加关联函数
+pub trait ColorExt {
+    /// 色温为 kelvin 的黑体颜色，亮度为 1，见 [`blackbody`]
+    fn from_blackbody(kelvin: f64) -> Self;
+}
+
+impl ColorExt for Color {
+    #[inline]
+    fn from_blackbody(kelvin: f64) -> Self {
+        blackbody(kelvin)
+    }
+}
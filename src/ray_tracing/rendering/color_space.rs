@@ -0,0 +1,240 @@
+//! 最小的颜色管理层：纹理输入色彩空间的解码，以及输出色域和传递函数的编码
+//!
+//! 渲染器内部始终使用线性 Rec.709 原色（与 sRGB 相同）计算。纹理在加载时按声明的色彩空间
+//! 转换为线性值；输出时再按输出色彩空间转换原色并编码。
+
+use crate::ray_tracing::math::vec3::Color;
+use std::fmt;
+use std::str::FromStr;
+
+/// sRGB 传递函数的解码：编码值 → 线性值
+#[inline]
+pub fn srgb_to_linear(c: f64) -> f64 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// sRGB 传递函数的编码：线性值 → 编码值
+#[inline]
+pub fn linear_to_srgb(c: f64) -> f64 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// 线性 Rec.709 → 线性 Display P3（D65 白点）
+const REC709_TO_P3: [[f64; 3]; 3] = [
+    [0.822_462_1, 0.177_538_0, 0.0],
+    [0.033_194_2, 0.966_805_8, 0.0],
+    [0.017_082_6, 0.072_397_4, 0.910_520_0],
+];
+
+/// 线性 Display P3 → 线性 Rec.709（D65 白点）
+const P3_TO_REC709: [[f64; 3]; 3] = [
+    [1.224_940_2, -0.224_940_2, 0.0],
+    [-0.042_056_9, 1.042_056_9, 0.0],
+    [-0.019_637_6, -0.078_636_1, 1.098_273_7],
+];
+
+#[inline]
+fn transform(m: &[[f64; 3]; 3], c: &Color) -> Color {
+    Color::new(
+        m[0][0] * c.x + m[0][1] * c.y + m[0][2] * c.z,
+        m[1][0] * c.x + m[1][1] * c.y + m[1][2] * c.z,
+        m[2][0] * c.x + m[2][1] * c.y + m[2][2] * c.z,
+    )
+}
+
+/// 纹理数据的色彩空间
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TextureColorSpace {
+    /// 数值直接作为线性 Rec.709 颜色（历史行为；法线、粗糙度等数据纹理也应使用此项）
+    #[default]
+    Linear,
+    /// sRGB 编码（普通照片和颜色贴图的常见情况）
+    Srgb,
+    /// Display P3 原色加 sRGB 传递函数（较新设备拍摄的照片）
+    DisplayP3,
+}
+
+impl TextureColorSpace {
+    /// 将 [0,1] 范围的编码值转换为渲染器使用的线性 Rec.709 颜色
+    pub fn to_linear(self, encoded: Color) -> Color {
+        let decode = |c: Color| c.map(srgb_to_linear);
+        match self {
+            Self::Linear => encoded,
+            Self::Srgb => decode(encoded),
+            Self::DisplayP3 => transform(&P3_TO_REC709, &decode(encoded)),
+        }
+    }
+
+    /// 根据嵌入的 ICC 配置文件推断色彩空间
+    ///
+    /// 不做完整的色彩管理，只识别配置文件描述中的常见名称（Display P3 / sRGB），其他配置文件返回None。
+    pub fn from_icc_profile(icc: &[u8]) -> Option<Self> {
+        // v2 配置文件的描述为 ASCII，v4 为 UTF-16BE
+        let contains = |name: &str| {
+            let ascii = name.as_bytes();
+            let utf16: Vec<u8> = name.bytes().flat_map(|b| [0, b]).collect();
+            icc.windows(ascii.len()).any(|w| w == ascii)
+                || icc.windows(utf16.len()).any(|w| w == utf16.as_slice())
+        };
+        if contains("P3") {
+            Some(Self::DisplayP3)
+        } else if contains("sRGB") {
+            Some(Self::Srgb)
+        } else {
+            None
+        }
+    }
+}
+
+impl FromStr for TextureColorSpace {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "linear" | "raw" => Ok(Self::Linear),
+            "srgb" => Ok(Self::Srgb),
+            "display-p3" | "displayp3" | "p3" => Ok(Self::DisplayP3),
+            _ => Err(format!(
+                "未知的纹理色彩空间 `{}`（可选: linear, srgb, display-p3）",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for TextureColorSpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Linear => "linear",
+            Self::Srgb => "srgb",
+            Self::DisplayP3 => "display-p3",
+        })
+    }
+}
+
+/// 输出图像的色彩空间
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OutputColorSpace {
+    /// 书中的约定：Rec.709 原色，伽马 2.0（与历史输出逐字节一致）
+    #[default]
+    Gamma2,
+    /// 标准 sRGB：Rec.709 原色，sRGB 传递函数
+    Srgb,
+    /// Display P3：P3 原色，sRGB 传递函数，适合广色域显示器
+    DisplayP3,
+}
+
+impl OutputColorSpace {
+    /// 线性 Rec.709 颜色转换到输出原色（仍为线性，用于 PFM 等线性格式）
+    pub fn to_output_primaries(self, c: &Color) -> Color {
+        match self {
+            Self::Gamma2 | Self::Srgb => *c,
+            Self::DisplayP3 => transform(&REC709_TO_P3, c),
+        }
+    }
+
+    /// 线性 Rec.709 颜色编码为显示值，各分量限制在 [0,1]，NaN 视为0
+    pub fn encode(self, c: &Color) -> Color {
+        let c = self.to_output_primaries(c);
+        c.map(|v| {
+            let v = if v.is_nan() { 0.0 } else { v.max(0.0) };
+            let encoded = match self {
+                Self::Gamma2 => v.sqrt(),
+                Self::Srgb | Self::DisplayP3 => linear_to_srgb(v),
+            };
+            encoded.clamp(0.0, 1.0)
+        })
+    }
+
+    /// PNG cICP 块的 (原色, 传递函数, 矩阵, 全范围) 代码（ITU-T H.273），伽马 2.0 没有对应代码
+    pub fn cicp(self) -> Option<[u8; 4]> {
+        match self {
+            Self::Gamma2 => None,
+            Self::Srgb => Some([1, 13, 0, 1]),
+            Self::DisplayP3 => Some([12, 13, 0, 1]),
+        }
+    }
+}
+
+impl FromStr for OutputColorSpace {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "gamma2" | "book" => Ok(Self::Gamma2),
+            "srgb" | "rec709" => Ok(Self::Srgb),
+            "display-p3" | "displayp3" | "p3" => Ok(Self::DisplayP3),
+            _ => Err(format!(
+                "未知的输出色彩空间 `{}`（可选: gamma2, srgb, display-p3）",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OutputColorSpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Gamma2 => "gamma2",
+            Self::Srgb => "srgb",
+            Self::DisplayP3 => "display-p3",
+        })
+    }
+}
+
+/// 把 [0,1] 的显示值量化为8位，与书中 write_color 的量化方式一致
+#[inline]
+pub fn quantize_8bit(v: f64) -> u8 {
+    (256.0 * v.clamp(0.0, 0.999)) as u8
+}
+
+/// 在 PNG 数据的 IHDR 块之后插入 cICP 块，标记图像的色彩空间
+///
+/// 数据不是以 IHDR 开头的 PNG 时原样返回。
+pub fn tag_png_color_space(png: Vec<u8>, space: OutputColorSpace) -> Vec<u8> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    let Some(cicp) = space.cicp() else {
+        return png;
+    };
+    if png.len() < 33 || !png.starts_with(SIGNATURE) || &png[12..16] != b"IHDR" {
+        return png;
+    }
+
+    // 签名(8) + IHDR(长度4 + 类型4 + 数据13 + CRC4)
+    let insert_at = 33;
+    let mut chunk = Vec::with_capacity(16);
+    chunk.extend_from_slice(&4u32.to_be_bytes());
+    chunk.extend_from_slice(b"cICP");
+    chunk.extend_from_slice(&cicp);
+    chunk.extend_from_slice(&crc32(&chunk[4..]).to_be_bytes());
+
+    let mut tagged = Vec::with_capacity(png.len() + chunk.len());
+    tagged.extend_from_slice(&png[..insert_at]);
+    tagged.extend_from_slice(&chunk);
+    tagged.extend_from_slice(&png[insert_at..]);
+    tagged
+}
+
+/// PNG 使用的 CRC-32（多项式 0xEDB88320）
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
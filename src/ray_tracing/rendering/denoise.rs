@@ -0,0 +1,308 @@
+//! 不依赖外部库的降噪：以法线、反照率和深度辅助缓冲（AOV）为引导的交叉双边滤波
+//!
+//! 使用边缘保持的 À-Trous 小波滤波（Dammertz 等，2010）：每轮以 5×5 的 B3 样条核、间隔加倍地采样邻域，
+//! 邻域像素的权重由几何引导（法线、反照率、深度）和颜色差异共同决定，几轮即可覆盖较大半径。
+//! 颜色先除以反照率（解调）再滤波，滤波后乘回，纹理细节不会被模糊。
+//! 孤立的极亮像素（萤火虫）在滤波前被限制到邻域的亮度水平，否则它们会被颜色权重当作边缘保留下来。
+
+use super::color::luminance;
+use super::framebuffer::FrameBuffer;
+use crate::ray_tracing::math::vec3::{Color, Vec3};
+use rayon::prelude::*;
+
+/// B3 样条核的一维权重（偏移 0、±1、±2）
+const KERNEL: [f64; 3] = [3.0 / 8.0, 1.0 / 4.0, 1.0 / 16.0];
+
+/// 反照率低于此值时不做解调（如发光体、黑色表面）
+const MIN_ALBEDO: f64 = 0.01;
+
+/// 萤火虫抑制的亮度上限取邻域中第几大的亮度（从0开始），允许最多这么多个相邻的亮像素成簇
+const FIREFLY_RANK: usize = 3;
+
+/// 主光线首个交点的辅助缓冲，每个像素为样本平均值
+///
+/// 未命中任何物体的像素法线为零向量、反照率为1、深度为无穷大、运动矢量为零。
+#[derive(Debug, Clone)]
+pub struct AovBuffers {
+    width: u32,
+    height: u32,
+    /// 朝向相机一侧的着色法线
+    pub normal: Vec<Vec3>,
+    /// 首个交点的材质衰减（漫反射颜色）
+    pub albedo: Vec<Color>,
+    /// 相机到交点的距离
+    pub depth: Vec<f64>,
+    /// 首个交点在快门区间内的屏幕空间位移（像素，x 向右、y 向下，z 为0），从快门打开到关闭
+    pub motion: Vec<Vec3>,
+}
+
+impl AovBuffers {
+    /// 创建全为“未命中”的缓冲
+    pub fn new(width: u32, height: u32) -> Self {
+        let count = (width * height) as usize;
+        Self {
+            width,
+            height,
+            normal: vec![Vec3::zeros(); count],
+            albedo: vec![Color::repeat(1.0); count],
+            depth: vec![f64::INFINITY; count],
+            motion: vec![Vec3::zeros(); count],
+        }
+    }
+
+    /// 宽度
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// 高度
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// 法线缓冲的可视化（[-1,1] 映射到 [0,1]）
+    pub fn normal_buffer(&self) -> FrameBuffer {
+        self.to_framebuffer(|i| (self.normal[i] + Vec3::repeat(1.0)) * 0.5)
+    }
+
+    /// 反照率缓冲
+    pub fn albedo_buffer(&self) -> FrameBuffer {
+        self.to_framebuffer(|i| self.albedo[i])
+    }
+
+    /// 深度缓冲的可视化（按最远有限深度归一化，越近越亮）
+    pub fn depth_buffer(&self) -> FrameBuffer {
+        let far = self
+            .depth
+            .iter()
+            .copied()
+            .filter(|d| d.is_finite())
+            .fold(0.0, f64::max);
+        self.to_framebuffer(|i| {
+            let d = self.depth[i];
+            Color::repeat(if d.is_finite() && far > 0.0 {
+                1.0 - d / far
+            } else {
+                0.0
+            })
+        })
+    }
+
+    /// 运动矢量缓冲：像素位移存放在 x、y 分量中，保存为 PFM 供后期运动模糊或时域降噪使用
+    pub fn motion_vectors(&self) -> FrameBuffer {
+        self.to_framebuffer(|i| self.motion[i])
+    }
+
+    /// 运动矢量的可视化：位移按最大长度归一化后映射到红（x）和绿（y）通道，静止处为 (0.5, 0.5, 0.5)
+    pub fn motion_buffer(&self) -> FrameBuffer {
+        let longest = self.motion.iter().map(|m| m.norm()).fold(0.0, f64::max);
+        let scale = if longest > 0.0 { 0.5 / longest } else { 0.0 };
+        self.to_framebuffer(|i| {
+            let m = self.motion[i] * scale;
+            Color::new(0.5 + m.x, 0.5 + m.y, 0.5)
+        })
+    }
+
+    fn to_framebuffer(&self, color: impl Fn(usize) -> Color) -> FrameBuffer {
+        let mut fb = FrameBuffer::new(self.width, self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                fb.set(x, y, color((y * self.width + x) as usize));
+            }
+        }
+        fb
+    }
+}
+
+/// 降噪参数
+///
+/// 各项 sigma 是对应差异的容忍度：越大越平滑，越小越保留边缘。
+/// strength 统一缩放所有 sigma，为0时不降噪。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DenoiseSettings {
+    /// 整体强度
+    pub strength: f64,
+    /// À-Trous 迭代次数，滤波半径约为 2^(iterations+1) 像素
+    pub iterations: u32,
+    /// 颜色差异（按亮度压缩后）的容忍度，每轮迭代减半
+    pub sigma_color: f64,
+    /// 法线差异的容忍度
+    pub sigma_normal: f64,
+    /// 反照率差异的容忍度
+    pub sigma_albedo: f64,
+    /// 相对深度差异（每像素距离）的容忍度
+    pub sigma_depth: f64,
+    /// 滤波前用反照率解调颜色，保留纹理细节
+    pub demodulate_albedo: bool,
+    /// 滤波前把孤立亮像素的亮度限制到 5×5 邻域的水平（会损失少量焦散等高亮处的能量）
+    pub clamp_fireflies: bool,
+}
+
+impl Default for DenoiseSettings {
+    fn default() -> Self {
+        Self {
+            strength: 1.0,
+            iterations: 5,
+            sigma_color: 0.5,
+            sigma_normal: 0.3,
+            sigma_albedo: 0.1,
+            sigma_depth: 0.05,
+            demodulate_albedo: true,
+            clamp_fireflies: true,
+        }
+    }
+}
+
+impl DenoiseSettings {
+    /// 默认参数，指定整体强度
+    pub fn with_strength(strength: f64) -> Self {
+        Self {
+            strength,
+            ..Self::default()
+        }
+    }
+}
+
+/// 以辅助缓冲为引导对帧缓冲区降噪，返回新的帧缓冲区（alpha 和色彩空间不变）
+pub fn denoise(fb: &FrameBuffer, aov: &AovBuffers, settings: &DenoiseSettings) -> FrameBuffer {
+    assert!(
+        fb.width() == aov.width && fb.height() == aov.height,
+        "辅助缓冲尺寸与帧缓冲区不一致"
+    );
+    if settings.strength <= 0.0 || settings.iterations == 0 {
+        return fb.clone();
+    }
+
+    let width = fb.width() as i32;
+    let height = fb.height() as i32;
+    let demodulation: Vec<Color> = aov
+        .albedo
+        .iter()
+        .map(|a| {
+            if settings.demodulate_albedo {
+                a.map(|c| if c < MIN_ALBEDO { 1.0 } else { c })
+            } else {
+                Color::repeat(1.0)
+            }
+        })
+        .collect();
+    let mut current: Vec<Color> = fb
+        .pixels()
+        .iter()
+        .zip(&demodulation)
+        .map(|(c, a)| c.component_div(a))
+        .collect();
+    if settings.clamp_fireflies {
+        current = clamp_fireflies(&current, width, height);
+    }
+
+    let inv_sq = |sigma: f64| {
+        let s = sigma * settings.strength;
+        1.0 / (s * s).max(1e-12)
+    };
+    let w_normal = inv_sq(settings.sigma_normal);
+    let w_albedo = inv_sq(settings.sigma_albedo);
+    let w_depth = inv_sq(settings.sigma_depth);
+
+    for iteration in 0..settings.iterations {
+        let step = 1i32 << iteration;
+        let w_color = inv_sq(settings.sigma_color / step as f64);
+        let compressed: Vec<Color> = current.iter().map(compress).collect();
+
+        current = (0..width * height)
+            .into_par_iter()
+            .map(|index| {
+                let (x, y) = (index % width, index / width);
+                let p = index as usize;
+                let mut sum = Color::zeros();
+                let mut weight_sum = 0.0;
+                for dy in -2i32..=2 {
+                    let qy = y + dy * step;
+                    if qy < 0 || qy >= height {
+                        continue;
+                    }
+                    for dx in -2i32..=2 {
+                        let qx = x + dx * step;
+                        if qx < 0 || qx >= width {
+                            continue;
+                        }
+                        let q = (qy * width + qx) as usize;
+                        let distance = step as f64 * ((dx * dx + dy * dy) as f64).sqrt();
+                        let Some(dz) = depth_difference(aov.depth[p], aov.depth[q], distance)
+                        else {
+                            continue;
+                        };
+                        let exponent = (aov.normal[p] - aov.normal[q]).norm_squared() * w_normal
+                            + (aov.albedo[p] - aov.albedo[q]).norm_squared() * w_albedo
+                            + dz * dz * w_depth
+                            + (compressed[p] - compressed[q]).norm_squared() * w_color;
+                        let weight = KERNEL[dx.unsigned_abs() as usize]
+                            * KERNEL[dy.unsigned_abs() as usize]
+                            * (-exponent).exp();
+                        sum += current[q] * weight;
+                        weight_sum += weight;
+                    }
+                }
+                // 中心像素的权重恒为正
+                sum / weight_sum
+            })
+            .collect();
+    }
+
+    let mut out = fb.clone();
+    for y in 0..fb.height() {
+        for x in 0..fb.width() {
+            let index = (y * fb.width() + x) as usize;
+            out.set(x, y, current[index].component_mul(&demodulation[index]));
+        }
+    }
+    out
+}
+
+/// 把每个像素的亮度限制到 5×5 邻域中其他像素的第 FIREFLY_RANK 大的亮度
+fn clamp_fireflies(pixels: &[Color], width: i32, height: i32) -> Vec<Color> {
+    (0..width * height)
+        .into_par_iter()
+        .map(|index| {
+            let (x, y) = (index % width, index / width);
+            let color = pixels[index as usize];
+            let mut neighbours = Vec::with_capacity(24);
+            for qy in (y - 2).max(0)..=(y + 2).min(height - 1) {
+                for qx in (x - 2).max(0)..=(x + 2).min(width - 1) {
+                    if (qx, qy) != (x, y) {
+                        neighbours.push(luminance(&pixels[(qy * width + qx) as usize]));
+                    }
+                }
+            }
+            neighbours.sort_unstable_by(|a, b| b.total_cmp(a));
+            let limit = neighbours
+                .get(FIREFLY_RANK.min(neighbours.len().saturating_sub(1)))
+                .copied()
+                .unwrap_or(f64::INFINITY);
+            let lum = luminance(&color);
+            if lum > limit && lum > 0.0 {
+                color * (limit.max(0.0) / lum)
+            } else {
+                color
+            }
+        })
+        .collect()
+}
+
+/// 按亮度压缩 HDR 颜色，避免高亮像素主导颜色差异
+#[inline]
+fn compress(color: &Color) -> Color {
+    color / (1.0 + luminance(color).max(0.0))
+}
+
+/// 每像素距离上的相对深度差；一个命中一个未命中时返回None（不混合）
+#[inline]
+fn depth_difference(a: f64, b: f64, distance: f64) -> Option<f64> {
+    match (a.is_finite(), b.is_finite()) {
+        (true, true) => Some((a - b).abs() / (a.max(b).max(1e-9) * distance.max(1.0))),
+        (false, false) => Some(0.0),
+        _ => None,
+    }
+}
@@ -0,0 +1,81 @@
+//! 景深感知的采样分配：弥散圆大的像素（强烈失焦的散景区域）收敛最慢，分配更多样本
+//!
+//! 相机先用每像素一条主光线估计弥散圆直径（像素），再按直径为每个像素确定样本数的整数倍数，
+//! 像素和镜头的分层网格随之加密。前景物体的散景会溢出到相邻像素，因此弥散圆按其半径向邻域膨胀。
+
+/// 弥散圆直径达到此值（像素）且权重为1时样本数加倍
+const REFERENCE_COC: f64 = 4.0;
+/// 弥散圆膨胀的最大半径（像素），限制预处理的开销
+const MAX_SPREAD: i32 = 8;
+
+/// 景深感知采样参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DofSampling {
+    /// 额外样本的权重：弥散圆直径每增加 4 像素，样本数增加 weight 倍（向下取整），为0时不分配额外样本
+    pub weight: f64,
+    /// 每像素样本数倍数的上限
+    pub max_multiplier: u32,
+}
+
+impl Default for DofSampling {
+    fn default() -> Self {
+        Self {
+            weight: 1.0,
+            max_multiplier: 4,
+        }
+    }
+}
+
+impl DofSampling {
+    /// 指定权重，其余参数取默认值
+    pub fn with_weight(weight: f64) -> Self {
+        Self {
+            weight: weight.max(0.0),
+            ..Self::default()
+        }
+    }
+
+    /// 设置每像素样本数倍数的上限
+    pub fn with_max_multiplier(mut self, max_multiplier: u32) -> Self {
+        self.max_multiplier = max_multiplier.max(1);
+        self
+    }
+
+    /// 弥散圆直径为 coc 像素时的样本数倍数
+    pub fn multiplier(&self, coc: f64) -> u32 {
+        let extra = self.weight * coc.max(0.0) / REFERENCE_COC;
+        (1.0 + extra)
+            .floor()
+            .clamp(1.0, self.max_multiplier.max(1) as f64) as u32
+    }
+
+    /// 每像素的样本数倍数；coc 为按行存储的弥散圆直径，先膨胀再换算
+    pub fn multipliers(&self, coc: &[f64], width: usize, height: usize) -> Vec<u32> {
+        spread_coc(coc, width, height)
+            .iter()
+            .map(|&c| self.multiplier(c))
+            .collect()
+    }
+}
+
+/// 每个像素的弥散圆向半径范围内的邻域扩散，取最大值
+pub fn spread_coc(coc: &[f64], width: usize, height: usize) -> Vec<f64> {
+    let mut out = coc.to_vec();
+    for y in 0..height {
+        for x in 0..width {
+            let c = coc[y * width + x];
+            let radius = ((c * 0.5) as i32).min(MAX_SPREAD);
+            for dy in -radius..=radius {
+                for dx in -radius..=radius {
+                    let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+                    if nx < 0 || ny < 0 || nx >= width as i32 || ny >= height as i32 {
+                        continue;
+                    }
+                    let slot = &mut out[ny as usize * width + nx as usize];
+                    *slot = slot.max(c);
+                }
+            }
+        }
+    }
+    out
+}
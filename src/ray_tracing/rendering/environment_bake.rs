@@ -0,0 +1,189 @@
+//! 环境贴图预烘焙：经纬度 mip 链和余弦卷积的辐照度图
+//!
+//! 在场景构建时对环境光采样一次，渲染时漫反射光线可以直接查询烘焙结果（有偏的快速模式），
+//! 不必每次弹射都采样完整的 HDR 环境。纹理坐标与 `Sphere::get_sphere_uv` 一致。
+
+use crate::ray_tracing::geometry::sphere::Sphere;
+use crate::ray_tracing::math::vec3::*;
+use std::f64::consts::PI;
+
+/// mip 链第0级的默认宽度（高度为宽度的一半）
+pub const DEFAULT_BASE_WIDTH: usize = 256;
+/// 辐照度图的默认宽度
+pub const DEFAULT_IRRADIANCE_WIDTH: usize = 32;
+/// 卷积辐照度时使用的 mip 级别的最大宽度
+const CONVOLUTION_WIDTH: usize = 64;
+
+/// 经纬度图像（u 方向环绕，v 方向截断）
+#[derive(Debug, Clone)]
+struct LatLongImage {
+    width: usize,
+    height: usize,
+    data: Vec<Color>,
+}
+
+impl LatLongImage {
+    /// 按纹素中心方向逐个求值
+    fn from_fn(width: usize, height: usize, f: impl Fn(&Vec3) -> Color) -> Self {
+        let mut data = Vec::with_capacity(width * height);
+        for j in 0..height {
+            for i in 0..width {
+                data.push(f(&texel_direction(i, j, width, height)));
+            }
+        }
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    #[inline]
+    fn texel(&self, i: usize, j: usize) -> Color {
+        self.data[j * self.width + i]
+    }
+
+    /// 2×2 盒式滤波缩小一级
+    fn downsample(&self) -> Self {
+        let width = (self.width / 2).max(1);
+        let height = (self.height / 2).max(1);
+        let (sx, sy) = (self.width / width, self.height / height);
+
+        let mut data = Vec::with_capacity(width * height);
+        for j in 0..height {
+            for i in 0..width {
+                let mut sum = Color::zeros();
+                for dy in 0..sy {
+                    for dx in 0..sx {
+                        sum += self.texel(i * sx + dx, j * sy + dy);
+                    }
+                }
+                data.push(sum / (sx * sy) as f64);
+            }
+        }
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    /// 双线性插值查询
+    fn sample(&self, u: f64, v: f64) -> Color {
+        let x = u * self.width as f64 - 0.5;
+        let y = (v * self.height as f64 - 0.5).clamp(0.0, (self.height - 1) as f64);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+
+        let wrap = |i: f64| (i as i64).rem_euclid(self.width as i64) as usize;
+        let (i0, i1) = (wrap(x0), wrap(x0 + 1.0));
+        let j0 = y0 as usize;
+        let j1 = (j0 + 1).min(self.height - 1);
+
+        let top = self.texel(i0, j0) * (1.0 - fx) + self.texel(i1, j0) * fx;
+        let bottom = self.texel(i0, j1) * (1.0 - fx) + self.texel(i1, j1) * fx;
+        top * (1.0 - fy) + bottom * fy
+    }
+}
+
+/// 烘焙后的环境光照
+#[derive(Debug, Clone)]
+pub struct EnvironmentBake {
+    /// 预滤波的 mip 链，第0级分辨率最高
+    mips: Vec<LatLongImage>,
+    /// 余弦卷积后的辐照度图
+    irradiance: LatLongImage,
+}
+
+impl EnvironmentBake {
+    /// 使用默认分辨率烘焙，radiance 给出单位方向上的环境辐射度
+    pub fn new(radiance: impl Fn(&Vec3) -> Color) -> Self {
+        Self::with_resolution(radiance, DEFAULT_BASE_WIDTH, DEFAULT_IRRADIANCE_WIDTH)
+    }
+
+    /// 指定 mip 链第0级宽度和辐照度图宽度烘焙
+    pub fn with_resolution(
+        radiance: impl Fn(&Vec3) -> Color,
+        base_width: usize,
+        irradiance_width: usize,
+    ) -> Self {
+        let base_width = base_width.max(2);
+        let mut mips = vec![LatLongImage::from_fn(base_width, base_width / 2, radiance)];
+        while let Some(last) = mips.last()
+            && (last.width > 1 || last.height > 1)
+        {
+            let next = last.downsample();
+            mips.push(next);
+        }
+
+        let source = mips
+            .iter()
+            .find(|mip| mip.width <= CONVOLUTION_WIDTH)
+            .unwrap_or(&mips[mips.len() - 1]);
+        let irradiance_width = irradiance_width.max(2);
+        let irradiance = LatLongImage::from_fn(irradiance_width, irradiance_width / 2, |n| {
+            convolve_cosine(source, n)
+        });
+
+        Self { mips, irradiance }
+    }
+
+    /// mip 级别数
+    #[inline]
+    pub fn mip_levels(&self) -> usize {
+        self.mips.len()
+    }
+
+    /// 预滤波的辐射度查询，lod 为 mip 级别（可为小数，在相邻两级之间线性插值）
+    pub fn radiance(&self, direction: &Vec3, lod: f64) -> Color {
+        let (u, v) = Sphere::get_sphere_uv(&direction.normalize());
+        let lod = lod.clamp(0.0, (self.mips.len() - 1) as f64);
+        let level = lod.floor() as usize;
+        let t = lod - level as f64;
+
+        let fine = self.mips[level].sample(u, v);
+        if t <= 0.0 || level + 1 >= self.mips.len() {
+            return fine;
+        }
+        fine * (1.0 - t) + self.mips[level + 1].sample(u, v) * t
+    }
+
+    /// 法线为 n 的表面接收的环境辐照度（不考虑遮挡）
+    #[inline]
+    pub fn irradiance(&self, n: &Vec3) -> Color {
+        let (u, v) = Sphere::get_sphere_uv(&n.normalize());
+        self.irradiance.sample(u, v)
+    }
+}
+
+/// 纹素 (i, j) 中心对应的单位方向（`Sphere::get_sphere_uv` 的逆映射）
+fn texel_direction(i: usize, j: usize, width: usize, height: usize) -> Vec3 {
+    let u = (i as f64 + 0.5) / width as f64;
+    let v = (j as f64 + 0.5) / height as f64;
+    let theta = v * PI;
+    let phi = u * 2.0 * PI - PI;
+    Vec3::new(
+        theta.sin() * phi.cos(),
+        -theta.cos(),
+        -theta.sin() * phi.sin(),
+    )
+}
+
+/// 对整幅环境图做余弦加权积分：E(n) = ∫ L(ω) max(0, n·ω) dω
+fn convolve_cosine(image: &LatLongImage, n: &Vec3) -> Color {
+    let d_phi = 2.0 * PI / image.width as f64;
+    let d_theta = PI / image.height as f64;
+
+    let mut irradiance = Color::zeros();
+    for j in 0..image.height {
+        let theta = (j as f64 + 0.5) * d_theta;
+        let solid_angle = theta.sin() * d_theta * d_phi;
+        for i in 0..image.width {
+            let cos = n.dot(&texel_direction(i, j, image.width, image.height));
+            if cos > 0.0 {
+                irradiance += image.texel(i, j) * (cos * solid_angle);
+            }
+        }
+    }
+    irradiance
+}
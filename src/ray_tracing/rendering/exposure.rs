@@ -0,0 +1,50 @@
+//! 基于物理的相机曝光模型
+//!
+//! 使用物理曝光时，场景中的辐射度按亮度单位（cd/m²，尼特）解释，
+//! 光源应通过 `DiffuseLight::from_watts` / `from_lumens` / `from_nits` 以物理单位指定。
+
+/// 明视觉最大光视效能（lm/W），用于辐射通量与光通量之间的换算
+pub const LUMINOUS_EFFICACY: f64 = 683.0;
+
+/// 物理相机曝光参数：感光度、快门时间和光圈F值
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhysicalExposure {
+    pub iso: f64,
+    /// 快门时间（秒）
+    pub shutter: f64,
+    pub f_number: f64,
+}
+
+impl PhysicalExposure {
+    #[inline]
+    pub fn new(iso: f64, shutter: f64, f_number: f64) -> Self {
+        Self {
+            iso,
+            shutter,
+            f_number,
+        }
+    }
+
+    /// "阳光16法则"：ISO 100、1/100 秒、f/16，适合日光下的室外场景
+    #[inline]
+    pub fn sunny_16() -> Self {
+        Self::new(100.0, 1.0 / 100.0, 16.0)
+    }
+
+    /// ISO 100 下的曝光值 EV100 = log2(N² / t · 100 / S)
+    pub fn ev100(&self) -> f64 {
+        (self.f_number * self.f_number / self.shutter.max(1e-12) * 100.0 / self.iso.max(1e-12))
+            .log2()
+    }
+
+    /// 线性曝光倍数（基于饱和度的传感器模型，亮度为 1.2·2^EV100 尼特时达到饱和）
+    pub fn exposure(&self) -> f64 {
+        1.0 / (1.2 * self.ev100().exp2())
+    }
+}
+
+impl Default for PhysicalExposure {
+    fn default() -> Self {
+        Self::sunny_16()
+    }
+}
@@ -0,0 +1,165 @@
+use crate::ray_tracing::math::vec3::Color;
+
+/// 像素重建滤波器
+///
+/// 每个样本按滤波器权重溅射到半径内的所有像素，最终像素值为加权平均。
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Filter {
+    /// 盒式滤波：样本只贡献给所在像素（原有行为）
+    #[default]
+    Box,
+    /// 三角（帐篷）滤波
+    Triangle { radius: f64 },
+    /// 截断高斯滤波，alpha 控制衰减速度
+    Gaussian { radius: f64, alpha: f64 },
+    /// Mitchell-Netravali 滤波，常用参数 b = c = 1/3
+    Mitchell { radius: f64, b: f64, c: f64 },
+}
+
+impl Filter {
+    /// 默认参数的高斯滤波
+    #[inline]
+    pub fn gaussian(radius: f64) -> Self {
+        Self::Gaussian { radius, alpha: 2.0 }
+    }
+
+    /// 默认参数（b = c = 1/3）的 Mitchell-Netravali 滤波
+    #[inline]
+    pub fn mitchell(radius: f64) -> Self {
+        Self::Mitchell {
+            radius,
+            b: 1.0 / 3.0,
+            c: 1.0 / 3.0,
+        }
+    }
+
+    /// 滤波器支撑半径（像素）
+    #[inline]
+    pub fn radius(&self) -> f64 {
+        match *self {
+            Self::Box => 0.5,
+            Self::Triangle { radius }
+            | Self::Gaussian { radius, .. }
+            | Self::Mitchell { radius, .. } => radius.max(0.5),
+        }
+    }
+
+    /// 计算相对像素中心偏移 (dx, dy) 处的滤波权重
+    pub fn evaluate(&self, dx: f64, dy: f64) -> f64 {
+        let r = self.radius();
+        if dx.abs() > r || dy.abs() > r {
+            return 0.0;
+        }
+
+        match *self {
+            Self::Box => 1.0,
+            Self::Triangle { .. } => (r - dx.abs()) * (r - dy.abs()),
+            Self::Gaussian { alpha, .. } => {
+                let g = |d: f64| ((-alpha * d * d).exp() - (-alpha * r * r).exp()).max(0.0);
+                g(dx) * g(dy)
+            }
+            Self::Mitchell { b, c, .. } => {
+                Self::mitchell_1d(2.0 * dx / r, b, c) * Self::mitchell_1d(2.0 * dy / r, b, c)
+            }
+        }
+    }
+
+    /// 一维 Mitchell-Netravali 核，x ∈ [-2, 2]
+    fn mitchell_1d(x: f64, b: f64, c: f64) -> f64 {
+        let x = x.abs();
+        if x < 1.0 {
+            ((12.0 - 9.0 * b - 6.0 * c) * x * x * x
+                + (-18.0 + 12.0 * b + 6.0 * c) * x * x
+                + (6.0 - 2.0 * b))
+                / 6.0
+        } else if x < 2.0 {
+            ((-b - 6.0 * c) * x * x * x
+                + (6.0 * b + 30.0 * c) * x * x
+                + (-12.0 * b - 48.0 * c) * x
+                + (8.0 * b + 24.0 * c))
+                / 6.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// 分块溅射累积缓冲区，在块边界外留出滤波半径的边距
+#[derive(Debug, Clone)]
+pub struct SplatTile {
+    x0: i32,
+    y0: i32,
+    width: i32,
+    height: i32,
+    sum: Vec<Color>,
+    weight: Vec<f64>,
+    coverage: Vec<f64>,
+}
+
+impl SplatTile {
+    /// 为 [x0, x1) × [y0, y1) 的像素块创建累积缓冲区
+    pub fn new(x0: i32, y0: i32, x1: i32, y1: i32, filter: &Filter) -> Self {
+        let margin = (filter.radius() - 0.5).ceil() as i32;
+        let width = x1 - x0 + 2 * margin;
+        let height = y1 - y0 + 2 * margin;
+        Self {
+            x0: x0 - margin,
+            y0: y0 - margin,
+            width,
+            height,
+            sum: vec![Color::zeros(); (width * height) as usize],
+            weight: vec![0.0; (width * height) as usize],
+            coverage: vec![0.0; (width * height) as usize],
+        }
+    }
+
+    /// 将图像坐标 (sx, sy) 处的样本按滤波器权重溅射到相邻像素，coverage 为样本的不透明度
+    pub fn add_sample(&mut self, filter: &Filter, sx: f64, sy: f64, color: &Color, coverage: f64) {
+        let r = filter.radius();
+        let px_min = ((sx - r).floor() as i32).max(self.x0);
+        let px_max = ((sx + r).floor() as i32).min(self.x0 + self.width - 1);
+        let py_min = ((sy - r).floor() as i32).max(self.y0);
+        let py_max = ((sy + r).floor() as i32).min(self.y0 + self.height - 1);
+
+        for py in py_min..=py_max {
+            for px in px_min..=px_max {
+                let w = filter.evaluate(px as f64 + 0.5 - sx, py as f64 + 0.5 - sy);
+                if w == 0.0 {
+                    continue;
+                }
+                let idx = ((py - self.y0) * self.width + (px - self.x0)) as usize;
+                self.sum[idx] += color * w;
+                self.weight[idx] += w;
+                self.coverage[idx] += coverage * w;
+            }
+        }
+    }
+
+    /// 将累积结果合并到整幅图像的加权和缓冲区
+    pub fn merge_into(
+        &self,
+        image_width: i32,
+        image_height: i32,
+        sum: &mut [Color],
+        weight: &mut [f64],
+        coverage: &mut [f64],
+    ) {
+        for ty in 0..self.height {
+            let y = self.y0 + ty;
+            if y < 0 || y >= image_height {
+                continue;
+            }
+            for tx in 0..self.width {
+                let x = self.x0 + tx;
+                if x < 0 || x >= image_width {
+                    continue;
+                }
+                let src = (ty * self.width + tx) as usize;
+                let dst = (y * image_width + x) as usize;
+                sum[dst] += self.sum[src];
+                weight[dst] += self.weight[src];
+                coverage[dst] += self.coverage[src];
+            }
+        }
+    }
+}
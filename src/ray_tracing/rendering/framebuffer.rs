@@ -0,0 +1,166 @@
+use super::color_space::{OutputColorSpace, quantize_8bit};
+use crate::ray_tracing::math::vec3::Color;
+use image::{ImageBuffer, Rgb, RgbImage, Rgba, RgbaImage};
+
+/// 16位RGB图像类型
+pub type Rgb16Image = ImageBuffer<Rgb<u16>, Vec<u16>>;
+
+/// 16位RGBA图像类型
+pub type Rgba16Image = ImageBuffer<Rgba<u16>, Vec<u16>>;
+
+/// 帧缓冲区，保存按样本数平均后的线性HDR像素颜色
+///
+/// 可选的alpha通道保存像素覆盖度，此时颜色为预乘alpha的值。
+#[derive(Debug, Clone)]
+pub struct FrameBuffer {
+    width: u32,
+    height: u32,
+    pixels: Vec<Color>,
+    alpha: Option<Vec<f64>>,
+    color_space: OutputColorSpace,
+}
+
+impl FrameBuffer {
+    /// 创建全黑的帧缓冲区
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![Color::zeros(); (width * height) as usize],
+            alpha: None,
+            color_space: OutputColorSpace::default(),
+        }
+    }
+
+    /// 启用alpha通道（初始为完全不透明）
+    pub fn enable_alpha(&mut self) {
+        if self.alpha.is_none() {
+            self.alpha = Some(vec![1.0; self.pixels.len()]);
+        }
+    }
+
+    /// 是否带有alpha通道
+    #[inline]
+    pub fn has_alpha(&self) -> bool {
+        self.alpha.is_some()
+    }
+
+    /// 获取像素覆盖度，无alpha通道时为1
+    #[inline]
+    pub fn alpha(&self, x: u32, y: u32) -> f64 {
+        self.alpha
+            .as_ref()
+            .map_or(1.0, |a| a[(y * self.width + x) as usize])
+    }
+
+    /// 设置像素覆盖度（未启用alpha通道时忽略）
+    #[inline]
+    pub fn set_alpha(&mut self, x: u32, y: u32, alpha: f64) {
+        let idx = (y * self.width + x) as usize;
+        if let Some(a) = self.alpha.as_mut() {
+            a[idx] = alpha;
+        }
+    }
+
+    /// 取消预乘后的颜色和限制到 [0,1] 的alpha
+    fn straight_rgba(&self, x: u32, y: u32) -> (Color, f64) {
+        let alpha = self.alpha(x, y).clamp(0.0, 1.0);
+        let color = self.get(x, y);
+        if alpha > 1e-6 {
+            (color / alpha, alpha)
+        } else {
+            (Color::zeros(), 0.0)
+        }
+    }
+
+    /// 转换为8位/16位图像和保存时使用的输出色彩空间
+    #[inline]
+    pub fn color_space(&self) -> OutputColorSpace {
+        self.color_space
+    }
+
+    /// 设置输出色彩空间（像素数据始终为线性 Rec.709）
+    #[inline]
+    pub fn set_color_space(&mut self, color_space: OutputColorSpace) {
+        self.color_space = color_space;
+    }
+
+    /// 按输出色彩空间编码的8位像素
+    #[inline]
+    pub fn encoded_rgb8(&self, color: &Color) -> Rgb<u8> {
+        let c = self.color_space.encode(color);
+        Rgb([quantize_8bit(c.x), quantize_8bit(c.y), quantize_8bit(c.z)])
+    }
+
+    /// 图像宽度
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// 图像高度
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// 获取像素颜色
+    #[inline]
+    pub fn get(&self, x: u32, y: u32) -> Color {
+        self.pixels[(y * self.width + x) as usize]
+    }
+
+    /// 设置像素颜色
+    #[inline]
+    pub fn set(&mut self, x: u32, y: u32, color: Color) {
+        self.pixels[(y * self.width + x) as usize] = color;
+    }
+
+    /// 按行优先顺序访问所有像素
+    #[inline]
+    pub fn pixels(&self) -> &[Color] {
+        &self.pixels
+    }
+
+    /// 转换为8位图像（按输出色彩空间编码）
+    pub fn to_rgb8(&self) -> RgbImage {
+        RgbImage::from_fn(self.width, self.height, |x, y| {
+            self.encoded_rgb8(&self.get(x, y))
+        })
+    }
+
+    /// 转换为16位图像（按输出色彩空间编码），减少暗部色带
+    pub fn to_rgb16(&self) -> Rgb16Image {
+        Rgb16Image::from_fn(self.width, self.height, |x, y| {
+            let c = self.color_space.encode(&self.get(x, y));
+            Rgb([
+                (c.x * 65535.0).round() as u16,
+                (c.y * 65535.0).round() as u16,
+                (c.z * 65535.0).round() as u16,
+            ])
+        })
+    }
+
+    /// 转换为8位RGBA图像（非预乘alpha）
+    pub fn to_rgba8(&self) -> RgbaImage {
+        RgbaImage::from_fn(self.width, self.height, |x, y| {
+            let (color, alpha) = self.straight_rgba(x, y);
+            let rgb = self.encoded_rgb8(&color);
+            Rgba([rgb[0], rgb[1], rgb[2], (alpha * 255.0).round() as u8])
+        })
+    }
+
+    /// 转换为16位RGBA图像（非预乘alpha）
+    pub fn to_rgba16(&self) -> Rgba16Image {
+        Rgba16Image::from_fn(self.width, self.height, |x, y| {
+            let (color, alpha) = self.straight_rgba(x, y);
+            let c = self.color_space.encode(&color);
+            Rgba([
+                (c.x * 65535.0).round() as u16,
+                (c.y * 65535.0).round() as u16,
+                (c.z * 65535.0).round() as u16,
+                (alpha * 65535.0).round() as u16,
+            ])
+        })
+    }
+}
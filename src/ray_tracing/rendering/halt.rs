@@ -0,0 +1,254 @@
+//! 渐进渲染的停止条件：时间预算或目标噪声水平，取代固定的每像素样本数
+//!
+//! 渐进渲染按轮进行，每轮用新的随机种子渲染 `pass_samples` 个样本并累加到每像素的平均值中。
+//! 噪声由各轮结果之间的差异估计：每像素亮度估计值的方差（样本方差除以轮数），对所有像素取平均。
+//! 任一条件满足即停止：时间预算不足以再完成一轮、平均方差低于目标，或达到样本数上限。
+
+use super::color::luminance;
+use super::framebuffer::FrameBuffer;
+use crate::ray_tracing::math::vec3::Color;
+use std::fmt;
+use std::time::Duration;
+
+/// 渐进渲染的停止条件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HaltCondition {
+    /// 墙钟时间预算，预计下一轮会超出预算时停止
+    pub time_budget: Option<Duration>,
+    /// 目标噪声：每像素亮度估计值方差的平均值
+    pub target_noise: Option<f64>,
+    /// 每像素样本数上限，None 时只由其他条件决定
+    pub max_samples: Option<u32>,
+    /// 每轮的每像素样本数（向下取整为平方数）
+    pub pass_samples: u32,
+}
+
+impl Default for HaltCondition {
+    fn default() -> Self {
+        Self {
+            time_budget: None,
+            target_noise: None,
+            max_samples: None,
+            pass_samples: 16,
+        }
+    }
+}
+
+impl HaltCondition {
+    /// 渲染指定时长后停止
+    pub fn time_budget(budget: Duration) -> Self {
+        Self {
+            time_budget: Some(budget),
+            ..Self::default()
+        }
+    }
+
+    /// 平均方差低于 target 后停止
+    pub fn target_noise(target: f64) -> Self {
+        Self {
+            target_noise: Some(target.max(0.0)),
+            ..Self::default()
+        }
+    }
+
+    /// 设置每像素样本数上限
+    pub fn with_max_samples(mut self, max_samples: u32) -> Self {
+        self.max_samples = Some(max_samples.max(1));
+        self
+    }
+
+    /// 设置每轮的每像素样本数
+    pub fn with_pass_samples(mut self, pass_samples: u32) -> Self {
+        self.pass_samples = pass_samples.max(1);
+        self
+    }
+
+    /// 是否设置了任何停止条件；都未设置时渲染器按固定样本数渲染
+    pub fn is_active(&self) -> bool {
+        self.time_budget.is_some() || self.target_noise.is_some() || self.max_samples.is_some()
+    }
+
+    /// 已完成 passes 轮（共 samples 个每像素样本）、耗时 elapsed、最近一轮耗时 last_pass 时是否停止
+    ///
+    /// 至少渲染一轮；有噪声目标时至少两轮才能估计方差。
+    pub fn should_stop(
+        &self,
+        passes: u32,
+        samples: u32,
+        elapsed: Duration,
+        last_pass: Duration,
+        noise: Option<f64>,
+    ) -> bool {
+        if passes == 0 {
+            return false;
+        }
+        if self.max_samples.is_some_and(|max| samples >= max) {
+            return true;
+        }
+        if self
+            .time_budget
+            .is_some_and(|budget| elapsed + last_pass > budget)
+        {
+            return true;
+        }
+        match (self.target_noise, noise) {
+            (Some(target), Some(noise)) => noise <= target,
+            (Some(_), None) => false,
+            // 只有样本数上限时在上面判断，没有任何条件时只渲染一轮
+            (None, _) => self.time_budget.is_none() && self.max_samples.is_none(),
+        }
+    }
+}
+
+impl fmt::Display for HaltCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(budget) = self.time_budget {
+            parts.push(format!("time:{}", format_duration(budget)));
+        }
+        if let Some(target) = self.target_noise {
+            parts.push(format!("noise:{}", target));
+        }
+        if let Some(max) = self.max_samples {
+            parts.push(format!("spp:{}", max));
+        }
+        parts.push(format!("pass:{}", self.pass_samples));
+        f.write_str(&parts.join(","))
+    }
+}
+
+/// 解析时长：`90`、`90s`、`10m`、`1.5h`（无单位时为秒）
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let (number, scale) = match s.char_indices().last() {
+        Some((i, 's')) => (&s[..i], 1.0),
+        Some((i, 'm')) => (&s[..i], 60.0),
+        Some((i, 'h')) => (&s[..i], 3600.0),
+        _ => (s, 1.0),
+    };
+    number
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite() && *v > 0.0)
+        .map(|v| Duration::from_secs_f64(v * scale))
+        .ok_or_else(|| format!("无效的时长 `{}`（如 90s、10m、1.5h）", s))
+}
+
+/// 按最大的整单位输出时长
+pub fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs_f64();
+    if secs >= 3600.0 && secs % 3600.0 == 0.0 {
+        format!("{}h", secs / 3600.0)
+    } else if secs >= 60.0 && secs % 60.0 == 0.0 {
+        format!("{}m", secs / 60.0)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// 一轮渐进渲染后的状态
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PassInfo {
+    /// 已完成的轮数
+    pub passes: u32,
+    /// 累计的每像素样本数
+    pub samples: u32,
+    /// 累计耗时
+    pub elapsed: Duration,
+    /// 当前的平均方差（至少两轮后才有）
+    pub noise: Option<f64>,
+}
+
+impl fmt::Display for PassInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "第{}轮: {} spp, 耗时 {:.1}s",
+            self.passes,
+            self.samples,
+            self.elapsed.as_secs_f64()
+        )?;
+        if let Some(noise) = self.noise {
+            write!(f, ", 平均方差 {:.3e}", noise)?;
+        }
+        Ok(())
+    }
+}
+
+/// 逐轮累加的像素平均值和亮度二阶矩
+#[derive(Debug, Clone)]
+pub(crate) struct PassAccumulator {
+    width: u32,
+    height: u32,
+    passes: u32,
+    sum: Vec<Color>,
+    alpha: Vec<f64>,
+    luminance_sq: Vec<f64>,
+    has_alpha: bool,
+}
+
+impl PassAccumulator {
+    pub(crate) fn new(width: u32, height: u32) -> Self {
+        let count = (width * height) as usize;
+        Self {
+            width,
+            height,
+            passes: 0,
+            sum: vec![Color::zeros(); count],
+            alpha: vec![0.0; count],
+            luminance_sq: vec![0.0; count],
+            has_alpha: false,
+        }
+    }
+
+    /// 累加一轮的结果（各轮样本数相同，按等权平均）
+    pub(crate) fn add(&mut self, pass: &FrameBuffer) {
+        self.passes += 1;
+        self.has_alpha = pass.has_alpha();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let index = (y * self.width + x) as usize;
+                let color = pass.get(x, y);
+                let l = luminance(&color);
+                self.sum[index] += color;
+                self.luminance_sq[index] += l * l;
+                self.alpha[index] += pass.alpha(x, y);
+            }
+        }
+    }
+
+    /// 每像素亮度估计值方差的平均值，少于两轮时为None
+    pub(crate) fn noise(&self) -> Option<f64> {
+        if self.passes < 2 {
+            return None;
+        }
+        let n = self.passes as f64;
+        let total: f64 = self
+            .sum
+            .iter()
+            .zip(&self.luminance_sq)
+            .map(|(sum, sq)| {
+                let mean = luminance(sum) / n;
+                let sample_variance = ((sq - n * mean * mean) / (n - 1.0)).max(0.0);
+                sample_variance / n
+            })
+            .sum();
+        Some(total / self.sum.len().max(1) as f64)
+    }
+
+    /// 当前的平均图像
+    pub(crate) fn image(&self, template: &FrameBuffer) -> FrameBuffer {
+        let mut fb = template.clone();
+        let scale = 1.0 / self.passes.max(1) as f64;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let index = (y * self.width + x) as usize;
+                fb.set(x, y, self.sum[index] * scale);
+                if self.has_alpha {
+                    fb.set_alpha(x, y, self.alpha[index] * scale);
+                }
+            }
+        }
+        fb
+    }
+}
@@ -0,0 +1,187 @@
+//! 积分器选择：完整的路径追踪、环境光遮蔽（AO）或正交基底调试视图
+
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::math::onb::ONB;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::{Color, Vec3};
+use crate::ray_tracing::sampling::blue_noise::{blue_noise, r2};
+use crate::ray_tracing::scene::ray_cast::RayCast;
+use std::f64::consts::PI;
+use std::fmt;
+use std::str::FromStr;
+
+/// 相机使用的积分器
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Integrator {
+    /// 带重要性采样的路径追踪（全局光照）
+    #[default]
+    PathTracing,
+    /// 环境光遮蔽：主光线交点处半球内在 distance 范围内未被遮挡的比例（余弦加权）
+    ///
+    /// 每个相机样本发出 samples 条遮挡光线，方向取 R2 序列并按像素的蓝噪声值旋转。
+    AmbientOcclusion { distance: f64, samples: u32 },
+    /// 正交基底调试：按主光线交点处由法线构建的 [`ONB`] 着色，不追踪任何散射
+    Basis(BasisView),
+}
+
+/// 正交基底调试视图显示的内容
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BasisView {
+    /// u 轴，分量从 [-1, 1] 映射到 RGB 的 [0, 1]
+    U,
+    /// v 轴
+    V,
+    /// w 轴（应与表面法线一致）
+    W,
+    /// 按 [`BasisIssue`] 分类着色：正常为绿色，各类问题用不同的颜色标出
+    #[default]
+    Check,
+}
+
+impl FromStr for BasisView {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "u" => Ok(Self::U),
+            "v" => Ok(Self::V),
+            "w" => Ok(Self::W),
+            "check" => Ok(Self::Check),
+            _ => Err(format!("未知的基底视图 `{}`（可选: u, v, w, check）", s)),
+        }
+    }
+}
+
+impl fmt::Display for BasisView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::U => "u",
+            Self::V => "v",
+            Self::W => "w",
+            Self::Check => "check",
+        })
+    }
+}
+
+/// 正交基底的问题，按严重程度从高到低检查
+///
+/// 任何一种问题都会让 `CosinePDF` 等在局部坐标中采样的PDF把方向映射错：
+/// 退化或不正交的基底使采样密度不再是余弦分布，w 轴翻转则把方向采到表面下方。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BasisIssue {
+    /// 基向量含 NaN 或无穷（通常来自长度为0的法线），品红
+    Degenerate,
+    /// w 轴与法线不一致（翻转或偏离），红色
+    Misaligned,
+    /// 基向量不是单位长度或互不正交，黄色
+    NotOrthonormal,
+    /// 左手系（u × v = -w），蓝色；采样仍然正确，但依赖手性的各向异性材质会镜像
+    LeftHanded,
+}
+
+impl BasisIssue {
+    /// 所有问题，按检查顺序排列
+    pub const ALL: [Self; 4] = [
+        Self::Degenerate,
+        Self::Misaligned,
+        Self::NotOrthonormal,
+        Self::LeftHanded,
+    ];
+
+    /// 检查视图中的颜色
+    pub fn color(&self) -> Color {
+        match self {
+            Self::Degenerate => Color::new(1.0, 0.0, 1.0),
+            Self::Misaligned => Color::new(1.0, 0.0, 0.0),
+            Self::NotOrthonormal => Color::new(1.0, 1.0, 0.0),
+            Self::LeftHanded => Color::new(0.0, 0.3, 1.0),
+        }
+    }
+}
+
+impl fmt::Display for BasisIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Degenerate => "退化",
+            Self::Misaligned => "w 轴与法线不一致",
+            Self::NotOrthonormal => "不正交",
+            Self::LeftHanded => "左手系",
+        })
+    }
+}
+
+/// 基底检查的容差
+const BASIS_TOLERANCE: f64 = 1e-6;
+
+/// 检查由法线 normal 构建的基底，没有问题时返回None
+pub fn basis_issue(uvw: &ONB, normal: &Vec3) -> Option<BasisIssue> {
+    let axes = [uvw.u(), uvw.v(), uvw.w()];
+    if axes.iter().any(|a| !a.iter().all(|c| c.is_finite())) {
+        return Some(BasisIssue::Degenerate);
+    }
+    if uvw.w().dot(&normal.normalize()) < 1.0 - BASIS_TOLERANCE {
+        return Some(BasisIssue::Misaligned);
+    }
+    let unit = axes
+        .iter()
+        .any(|a| (a.norm() - 1.0).abs() > BASIS_TOLERANCE);
+    let orthogonal = [(0, 1), (1, 2), (0, 2)]
+        .iter()
+        .all(|&(a, b)| axes[a].dot(&axes[b]).abs() <= BASIS_TOLERANCE);
+    if unit || !orthogonal {
+        return Some(BasisIssue::NotOrthonormal);
+    }
+    if uvw.u().cross(&uvw.v()).dot(&uvw.w()) < 0.0 {
+        return Some(BasisIssue::LeftHanded);
+    }
+    None
+}
+
+/// 交点处基底调试视图的颜色
+pub fn basis_color(rec: &HitRecord, view: BasisView) -> Color {
+    let uvw = ONB::new(&rec.normal);
+    let encode = |a: Vec3| (a + Vec3::repeat(1.0)) * 0.5;
+    match view {
+        BasisView::U => encode(uvw.u()),
+        BasisView::V => encode(uvw.v()),
+        BasisView::W => encode(uvw.w()),
+        BasisView::Check => match basis_issue(&uvw, &rec.normal) {
+            Some(issue) => issue.color(),
+            None => Color::new(0.1, 0.6, 0.2),
+        },
+    }
+}
+
+/// 估计交点处的环境光遮蔽（1为完全未遮挡）
+///
+/// (pixel_x, pixel_y) 选取蓝噪声遮罩中的旋转量，sample_index 为像素内的样本序号。
+pub fn ambient_occlusion(
+    world: &dyn Hittable,
+    rec: &HitRecord,
+    time: f64,
+    distance: f64,
+    samples: u32,
+    (pixel_x, pixel_y): (i32, i32),
+    sample_index: u32,
+) -> f64 {
+    let samples = samples.max(1);
+    let uvw = ONB::new(&rec.normal);
+    let shift = (
+        blue_noise(pixel_x, pixel_y, 0),
+        blue_noise(pixel_x, pixel_y, 1),
+    );
+
+    let unoccluded = (0..samples)
+        .filter(|k| {
+            let (u, v) = r2(sample_index * samples + k);
+            let (u, v) = ((u + shift.0).fract(), (v + shift.1).fract());
+            // 余弦加权半球采样
+            let r = u.sqrt();
+            let phi = 2.0 * PI * v;
+            let local = Vec3::new(r * phi.cos(), r * phi.sin(), (1.0 - u).max(0.0).sqrt());
+            let ray = Ray::new(rec.p, uvw.local_to_world(&local), time);
+            !world.trace_occluded(&ray, distance)
+        })
+        .count();
+    unoccluded as f64 / samples as f64
+}
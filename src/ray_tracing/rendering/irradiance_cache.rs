@@ -0,0 +1,190 @@
+//! 辐照度缓存（Ward 1988），用于漫反射全局光照的快速（有偏）预览
+//!
+//! 在主光线命中的漫反射表面上稀疏地计算半球辐照度，存入八叉树，其余位置在
+//! 有效半径内的缓存记录之间加权插值。
+
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::vec3::*;
+use std::sync::RwLock;
+
+/// 八叉树最大深度
+const MAX_DEPTH: usize = 20;
+
+/// 辐照度缓存参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IrradianceCacheSettings {
+    /// 精度参数 a：越小记录越密、质量越高（Ward 建议 0.1 ~ 0.3）
+    pub accuracy: f64,
+    /// 每条记录的半球采样数
+    pub samples: usize,
+    /// 记录有效半径下限（相对场景包围盒对角线长度）
+    pub min_spacing: f64,
+    /// 记录有效半径上限（相对场景包围盒对角线长度）
+    pub max_spacing: f64,
+}
+
+impl Default for IrradianceCacheSettings {
+    fn default() -> Self {
+        Self {
+            accuracy: 0.25,
+            samples: 256,
+            min_spacing: 0.005,
+            max_spacing: 0.25,
+        }
+    }
+}
+
+/// 单条缓存记录
+#[derive(Debug, Clone, Copy)]
+struct Record {
+    p: Point3,
+    n: Vec3,
+    irradiance: Color,
+    radius: f64, // 到周围表面的调和平均距离
+}
+
+#[derive(Debug)]
+struct Node {
+    center: Point3,
+    half_size: f64,
+    records: Vec<Record>,
+    children: [Option<Box<Node>>; 8],
+}
+
+impl Node {
+    fn new(center: Point3, half_size: f64) -> Self {
+        Self {
+            center,
+            half_size,
+            records: Vec::new(),
+            children: Default::default(),
+        }
+    }
+
+    #[inline]
+    fn child_index(&self, p: &Point3) -> usize {
+        (p.x > self.center.x) as usize
+            | ((p.y > self.center.y) as usize) << 1
+            | ((p.z > self.center.z) as usize) << 2
+    }
+
+    fn child_center(&self, idx: usize) -> Point3 {
+        let q = self.half_size * 0.5;
+        Point3::new(
+            self.center.x + if idx & 1 != 0 { q } else { -q },
+            self.center.y + if idx & 2 != 0 { q } else { -q },
+            self.center.z + if idx & 4 != 0 { q } else { -q },
+        )
+    }
+
+    /// 记录存放在边长不小于其影响直径的最深节点
+    fn insert(&mut self, record: Record, influence: f64, depth: usize) {
+        if depth >= MAX_DEPTH || self.half_size * 0.5 < influence {
+            self.records.push(record);
+            return;
+        }
+        let idx = self.child_index(&record.p);
+        let center = self.child_center(idx);
+        let half_size = self.half_size * 0.5;
+        self.children[idx]
+            .get_or_insert_with(|| Box::new(Node::new(center, half_size)))
+            .insert(record, influence, depth + 1);
+    }
+
+    /// 遍历所有可能影响 p 的记录（节点向外扩展半个边长后包含 p）
+    fn for_each_near(&self, p: &Point3, f: &mut impl FnMut(&Record)) {
+        self.records.iter().for_each(&mut *f);
+        for child in self.children.iter().flatten() {
+            let reach = child.half_size * 2.0;
+            if (p.x - child.center.x).abs() <= reach
+                && (p.y - child.center.y).abs() <= reach
+                && (p.z - child.center.z).abs() <= reach
+            {
+                child.for_each_near(p, f);
+            }
+        }
+    }
+}
+
+/// 线程安全的辐照度缓存
+#[derive(Debug)]
+pub struct IrradianceCache {
+    settings: IrradianceCacheSettings,
+    scene_size: f64,
+    root: RwLock<Node>,
+}
+
+impl IrradianceCache {
+    /// 为给定场景包围盒创建空缓存
+    pub fn new(bounds: &Aabb, settings: IrradianceCacheSettings) -> Self {
+        let extent = Vec3::new(bounds.x.size(), bounds.y.size(), bounds.z.size());
+        let scene_size = if extent.iter().all(|e| e.is_finite()) {
+            extent.norm().max(1e-6)
+        } else {
+            1.0
+        };
+        let half_size = 0.5 * extent.max().clamp(1e-6, f64::MAX) * 1.01;
+        let center = if bounds.is_empty() || !half_size.is_finite() {
+            Point3::origin()
+        } else {
+            bounds.center()
+        };
+
+        Self {
+            settings,
+            scene_size,
+            root: RwLock::new(Node::new(center, half_size.min(1e12))),
+        }
+    }
+
+    /// 缓存参数
+    #[inline]
+    pub fn settings(&self) -> &IrradianceCacheSettings {
+        &self.settings
+    }
+
+    /// 按 Ward 权重插值 p 处（法线 n）的辐照度，没有足够近的记录时返回None
+    pub fn lookup(&self, p: &Point3, n: &Vec3) -> Option<Color> {
+        let threshold = 1.0 / self.settings.accuracy;
+        let mut sum = Color::zeros();
+        let mut weight_sum = 0.0;
+
+        let root = self.root.read().unwrap_or_else(|e| e.into_inner());
+        root.for_each_near(p, &mut |rec| {
+            let offset = p - rec.p;
+            // 排除位于记录点前方的查询点（可能存在记录未见到的遮挡）
+            if offset.dot(&(n + rec.n)) < -0.1 * rec.radius {
+                return;
+            }
+            let normal_term = (1.0 - n.dot(&rec.n)).max(0.0).sqrt();
+            let error = offset.norm() / rec.radius + normal_term;
+            if error < 1e-9 {
+                sum += rec.irradiance * 1e9;
+                weight_sum += 1e9;
+            } else if 1.0 / error > threshold {
+                let w = 1.0 / error;
+                sum += rec.irradiance * w;
+                weight_sum += w;
+            }
+        });
+
+        (weight_sum > 0.0).then(|| sum / weight_sum)
+    }
+
+    /// 插入新记录，harmonic_distance 为采样光线命中距离的调和平均
+    pub fn insert(&self, p: Point3, n: Vec3, irradiance: Color, harmonic_distance: f64) {
+        let radius = harmonic_distance.clamp(
+            self.settings.min_spacing * self.scene_size,
+            self.settings.max_spacing * self.scene_size,
+        );
+        let record = Record {
+            p,
+            n,
+            irradiance,
+            radius,
+        };
+        let influence = radius * self.settings.accuracy;
+        let mut root = self.root.write().unwrap_or_else(|e| e.into_inner());
+        root.insert(record, influence, 0);
+    }
+}
@@ -0,0 +1,155 @@
+//! 关键帧参数通道：光源强度、颜色和材质参数（如粗糙度）随动画时间变化
+//!
+//! 通道在关键帧之间插值，时间在 [0,1] 内（整段动画），首个关键帧之前和最后一个之后保持端点的值。
+//! 每段内的插值可以经过缓动函数，让脉动的发光体平滑地亮起和熄灭。
+//! [`Flicker`] 在此基础上叠加按帧号确定的随机闪烁，模拟烛光。
+
+use super::camera_path::Easing;
+use crate::ray_tracing::math::vec3::Color;
+use crate::ray_tracing::utils::random::hash_seed;
+
+/// 可以在关键帧之间线性插值的值
+pub trait Keyable: Copy {
+    fn lerp(a: Self, b: Self, t: f64) -> Self;
+}
+
+impl Keyable for f64 {
+    #[inline]
+    fn lerp(a: Self, b: Self, t: f64) -> Self {
+        a + (b - a) * t
+    }
+}
+
+impl Keyable for Color {
+    #[inline]
+    fn lerp(a: Self, b: Self, t: f64) -> Self {
+        a + (b - a) * t
+    }
+}
+
+/// 参数关键帧
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Key<T> {
+    /// 关键帧在整段动画中的时间，[0,1]
+    pub time: f64,
+    pub value: T,
+}
+
+/// 由关键帧插值的参数通道
+#[derive(Debug, Clone, PartialEq)]
+pub struct Channel<T> {
+    keys: Vec<Key<T>>,
+    easing: Easing,
+}
+
+impl<T: Keyable> Channel<T> {
+    /// 由关键帧创建通道，关键帧按时间排序
+    ///
+    /// # Panics
+    /// 没有关键帧时 panic。
+    pub fn new(mut keys: Vec<Key<T>>) -> Self {
+        assert!(!keys.is_empty(), "参数通道至少需要一个关键帧");
+        keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Self {
+            keys,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// 全程不变的通道
+    pub fn constant(value: T) -> Self {
+        Self::new(vec![Key { time: 0.0, value }])
+    }
+
+    /// 关键帧在时间上均匀分布的通道
+    pub fn uniform(values: &[T]) -> Self {
+        let last = values.len().saturating_sub(1).max(1) as f64;
+        let keys = values
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| Key {
+                time: i as f64 / last,
+                value,
+            })
+            .collect();
+        Self::new(keys)
+    }
+
+    /// 设置每段内的缓动函数
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// 关键帧
+    pub fn keys(&self) -> &[Key<T>] {
+        &self.keys
+    }
+
+    /// 时刻 t 的值
+    pub fn evaluate(&self, t: f64) -> T {
+        let next = self.keys.partition_point(|k| k.time <= t);
+        if next == 0 {
+            return self.keys[0].value;
+        }
+        if next == self.keys.len() {
+            return self.keys[next - 1].value;
+        }
+        let (a, b) = (&self.keys[next - 1], &self.keys[next]);
+        let local = (t - a.time) / (b.time - a.time);
+        T::lerp(a.value, b.value, self.easing.apply(local))
+    }
+}
+
+/// 随机闪烁：按帧号确定的一维值噪声，结果与渲染顺序无关
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Flicker {
+    /// 亮度相对变化的幅度，0.3 表示在约 ±30% 之间波动（不低于0）
+    pub amplitude: f64,
+    /// 每帧经过的噪声格数：1 为每帧一个新的随机值，小于1时变化更缓慢
+    pub rate: f64,
+    pub seed: u64,
+}
+
+impl Flicker {
+    /// 指定幅度，每帧一个新的随机值
+    pub fn new(amplitude: f64) -> Self {
+        Self {
+            amplitude: amplitude.max(0.0),
+            rate: 1.0,
+            seed: 0,
+        }
+    }
+
+    /// 设置每帧经过的噪声格数
+    pub fn with_rate(mut self, rate: f64) -> Self {
+        self.rate = rate.max(0.0);
+        self
+    }
+
+    /// 设置随机种子
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// 第 frame 帧的亮度倍数
+    pub fn factor(&self, frame: f64) -> f64 {
+        let x = frame * self.rate;
+        // 两个频率叠加，慢变化上带有快速的抖动
+        let noise = 0.7 * self.value_noise(x, 0) + 0.3 * self.value_noise(x * 2.7 + 13.0, 1);
+        (1.0 + self.amplitude * noise).max(0.0)
+    }
+
+    /// 整数格点上为 [-1, 1] 的随机值，格点之间平滑插值
+    fn value_noise(&self, x: f64, octave: u64) -> f64 {
+        let cell = x.floor();
+        let lattice = |i: f64| {
+            let h = hash_seed(&[self.seed, octave, i as i64 as u64]);
+            (h >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+        };
+        let f = x - cell;
+        let f = f * f * (3.0 - 2.0 * f);
+        f64::lerp(lattice(cell), lattice(cell + 1.0), f)
+    }
+}
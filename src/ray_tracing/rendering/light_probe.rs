@@ -0,0 +1,245 @@
+//! 光源摆放预估：候选光源位置对画面的直接光照贡献热度图
+//!
+//! 每个像素只投射一条主光线，在首个交点处向候选位置（点光源或小球光源）发阴影光线，
+//! 按漫反射估计单次反弹的贡献 `albedo · cosθ / (π·d²)`，不追踪间接光。
+//! 比完整渲染快几个数量级，适合在 final_scene 这样的大场景中反复比较多个候选位置。
+//! 阴影光线默认穿过玻璃等透明材质（见 [`RayCast::trace_transmittance`]），玻璃球投下明亮、带颜色的阴影。
+
+use super::camera::Camera;
+use super::color::luminance;
+use super::framebuffer::FrameBuffer;
+use super::pdf_debug::heat;
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::materials::material::ScatterRecord;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::scene::ray_cast::RayCast;
+use crate::ray_tracing::utils::random::{hash_seed, random_double, with_seeded_stream};
+use rayon::prelude::*;
+use std::f64::consts::PI;
+use std::fmt;
+
+/// 光源预估参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LightProbeSettings {
+    /// 每像素的阴影光线数（半径为0时只需1条）
+    pub shadow_samples: u32,
+    /// 候选光源的半径，0 为点光源；大于0时阴影光线射向球面上的随机点，得到软阴影
+    pub radius: f64,
+    /// 光源强度（每单位立体角的辐射强度），只缩放结果，不影响候选位置之间的比较
+    pub intensity: f64,
+    /// 随机种子
+    pub seed: u64,
+    /// 阴影光线穿过透明材质；为 false 时任何物体都完全遮挡
+    pub transparent_shadows: bool,
+}
+
+impl Default for LightProbeSettings {
+    fn default() -> Self {
+        Self {
+            shadow_samples: 4,
+            radius: 0.0,
+            intensity: 1.0,
+            seed: 0,
+            transparent_shadows: true,
+        }
+    }
+}
+
+impl LightProbeSettings {
+    /// 半径为 radius 的球形候选光源
+    pub fn with_radius(mut self, radius: f64) -> Self {
+        self.radius = radius.max(0.0);
+        self
+    }
+
+    /// 设置每像素的阴影光线数
+    pub fn with_shadow_samples(mut self, samples: u32) -> Self {
+        self.shadow_samples = samples.max(1);
+        self
+    }
+
+    /// 设置阴影光线是否穿过透明材质
+    pub fn with_transparent_shadows(mut self, transparent: bool) -> Self {
+        self.transparent_shadows = transparent;
+        self
+    }
+}
+
+/// 一个候选位置的贡献图（每像素的亮度）
+#[derive(Debug, Clone, PartialEq)]
+pub struct LightContribution {
+    pub position: Point3,
+    width: u32,
+    height: u32,
+    values: Vec<f64>,
+}
+
+impl LightContribution {
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// 像素 (x, y) 的贡献
+    #[inline]
+    pub fn get(&self, x: u32, y: u32) -> f64 {
+        self.values[(y * self.width + x) as usize]
+    }
+
+    /// 全图平均贡献，用于比较候选位置
+    pub fn mean(&self) -> f64 {
+        self.values.iter().sum::<f64>() / self.values.len().max(1) as f64
+    }
+
+    /// 最大贡献
+    pub fn max(&self) -> f64 {
+        self.values.iter().copied().fold(0.0, f64::max)
+    }
+
+    /// 被照亮（贡献大于0）的像素比例
+    pub fn coverage(&self) -> f64 {
+        let lit = self.values.iter().filter(|&&v| v > 0.0).count();
+        lit as f64 / self.values.len().max(1) as f64
+    }
+
+    /// 黑-红-黄-白热度图，scale 对应白色；为None时用本图的最大值
+    ///
+    /// 贡献随距离平方衰减，动态范围很大，按平方根压缩后着色。多个候选位置之间比较时应传入相同的 scale。
+    pub fn heatmap(&self, scale: Option<f64>) -> FrameBuffer {
+        let scale = scale.unwrap_or_else(|| self.max()).max(1e-12);
+        let mut fb = FrameBuffer::new(self.width, self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                fb.set(x, y, heat((self.get(x, y) / scale).sqrt()));
+            }
+        }
+        fb
+    }
+}
+
+impl fmt::Display for LightContribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "({:.1}, {:.1}, {:.1}): 平均 {:.4e}，最大 {:.4e}，照亮 {:.1}% 的像素",
+            self.position.x,
+            self.position.y,
+            self.position.z,
+            self.mean(),
+            self.max(),
+            self.coverage() * 100.0
+        )
+    }
+}
+
+/// 估计候选位置 position 处的光源对相机画面的直接光照贡献
+pub fn light_contribution(
+    camera: &Camera,
+    world: &dyn Hittable,
+    position: Point3,
+    settings: &LightProbeSettings,
+) -> LightContribution {
+    let width = camera.image_width();
+    let height = camera.image_height();
+    let samples = if settings.radius > 0.0 {
+        settings.shadow_samples.max(1)
+    } else {
+        1
+    };
+
+    let values = (0..width * height)
+        .into_par_iter()
+        .map(|index| {
+            let (i, j) = (index % width, index / width);
+            with_seeded_stream(hash_seed(&[settings.seed, i as u64, j as u64]), || {
+                let Some((ray, rec)) = first_hit(&camera.center_ray(i, j), world) else {
+                    return 0.0;
+                };
+                let mut srec = ScatterRecord::new();
+                if !rec.mat.scatter(&ray, &rec, &mut srec) {
+                    return 0.0;
+                }
+                let albedo = luminance(&srec.attenuation.map(|c| c.clamp(0.0, 1.0)));
+
+                let total: f64 = (0..samples)
+                    .map(|_| {
+                        let target = position + settings.radius * Vec3::random_unit_vector();
+                        direct(world, &rec, &target, settings.transparent_shadows)
+                    })
+                    .sum();
+                settings.intensity * albedo * total / (samples as f64 * PI)
+            })
+        })
+        .collect();
+
+    LightContribution {
+        position,
+        width: width as u32,
+        height: height as u32,
+        values,
+    }
+}
+
+/// 依次估计多个候选位置，按平均贡献从大到小排序
+pub fn rank_positions(
+    camera: &Camera,
+    world: &dyn Hittable,
+    positions: &[Point3],
+    settings: &LightProbeSettings,
+) -> Vec<LightContribution> {
+    let mut results: Vec<LightContribution> = positions
+        .iter()
+        .map(|&p| light_contribution(camera, world, p, settings))
+        .collect();
+    results.sort_by(|a, b| b.mean().total_cmp(&a.mean()));
+    results
+}
+
+/// 主光线的首个不透明交点（随机穿过透明度遮罩）
+fn first_hit(r: &Ray, world: &dyn Hittable) -> Option<(Ray, HitRecord)> {
+    let mut ray = *r;
+    loop {
+        let mut rec = HitRecord::default();
+        if !world.hit(&ray, Interval::new(0.001, f64::INFINITY), &mut rec) {
+            return None;
+        }
+        let alpha = rec.mat.alpha(rec.u, rec.v, &rec.p);
+        if alpha < 1.0 && random_double() >= alpha {
+            ray.orig = rec.p;
+            continue;
+        }
+        return Some((ray, rec));
+    }
+}
+
+/// 交点处来自 target 的直接光 cosθ / d² 乘以阴影光线的透过率（亮度），在背面时为0
+fn direct(world: &dyn Hittable, rec: &HitRecord, target: &Point3, transparent: bool) -> f64 {
+    let to_light = target - rec.p;
+    let distance_squared = to_light.norm_squared();
+    if distance_squared < 1e-12 {
+        return 0.0;
+    }
+    let distance = distance_squared.sqrt();
+    let direction = to_light / distance;
+    let cosine = rec.normal.dot(&direction);
+    if cosine <= 0.0 {
+        return 0.0;
+    }
+    let shadow = Ray::new(rec.p, direction, 0.0);
+    let max_dist = distance * (1.0 - 1e-6);
+    let visibility = if transparent {
+        luminance(&world.trace_transmittance(&shadow, max_dist))
+    } else if world.trace_occluded(&shadow, max_dist) {
+        0.0
+    } else {
+        1.0
+    };
+    visibility * cosine / distance_squared
+}
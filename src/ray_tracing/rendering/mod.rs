@@ -1,2 +1,30 @@
+pub mod background;
+pub mod bake;
 pub mod camera;
+pub mod camera_path;
+pub mod caustics;
 pub mod color;
+pub mod color_space;
+pub mod denoise;
+pub mod dof_sampling;
+pub mod environment_bake;
+pub mod exposure;
+pub mod filter;
+pub mod framebuffer;
+pub mod halt;
+pub mod integrator;
+pub mod irradiance_cache;
+pub mod keyframes;
+pub mod light_probe;
+pub mod motion_blur;
+pub mod output;
+pub mod pdf_debug;
+pub mod probe_grid;
+pub mod progress;
+pub mod projection;
+pub mod report;
+pub mod shutter;
+pub mod stats;
+pub mod termination;
+pub mod tile_cache;
+pub mod wireframe;
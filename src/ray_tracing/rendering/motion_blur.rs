@@ -0,0 +1,81 @@
+//! 后期运动模糊：沿辅助缓冲中的运动矢量对图像取样平均，为渲染时没有采样快门时间的动画帧补上运动模糊
+//!
+//! 每个像素沿自身的运动矢量（以像素为中心、从 -v/2 到 +v/2）等间距地双线性取样后平均，是最简单的收集式实现：
+//! 运动物体内部被正确地拉出拖影，但静止的背景不会被模糊到运动物体外侧，物体的轮廓比真实的运动模糊更清晰。
+
+use super::denoise::AovBuffers;
+use super::framebuffer::FrameBuffer;
+use crate::ray_tracing::math::vec3::Color;
+use rayon::prelude::*;
+
+/// 后期运动模糊参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotionBlurSettings {
+    /// 运动矢量的缩放：1 为整个快门区间的位移，0.5 相当于 180° 快门
+    pub scale: f64,
+    /// 每像素沿运动矢量的最大取样数，实际取样数随位移长度增加
+    pub max_samples: u32,
+}
+
+impl Default for MotionBlurSettings {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            max_samples: 32,
+        }
+    }
+}
+
+/// 按辅助缓冲的运动矢量对帧缓冲区做运动模糊，返回新的帧缓冲区（alpha 和色彩空间不变）
+pub fn motion_blur(
+    fb: &FrameBuffer,
+    aov: &AovBuffers,
+    settings: &MotionBlurSettings,
+) -> FrameBuffer {
+    assert!(
+        fb.width() == aov.width() && fb.height() == aov.height(),
+        "辅助缓冲尺寸与帧缓冲区不一致"
+    );
+    let width = fb.width();
+    let height = fb.height();
+    let pixels = fb.pixels();
+
+    // 双线性取样，超出图像的位置取边缘像素
+    let sample = |x: f64, y: f64| -> Color {
+        let x = x.clamp(0.0, (width - 1) as f64);
+        let y = y.clamp(0.0, (height - 1) as f64);
+        let (x0, y0) = (x.floor() as u32, y.floor() as u32);
+        let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+        let (fx, fy) = (x - x0 as f64, y - y0 as f64);
+        let at = |x: u32, y: u32| pixels[(y * width + x) as usize];
+        let top = at(x0, y0) * (1.0 - fx) + at(x1, y0) * fx;
+        let bottom = at(x0, y1) * (1.0 - fx) + at(x1, y1) * fx;
+        top * (1.0 - fy) + bottom * fy
+    };
+
+    let blurred: Vec<Color> = (0..width * height)
+        .into_par_iter()
+        .map(|index| {
+            let motion = aov.motion[index as usize] * settings.scale;
+            let length = motion.norm();
+            if length < 0.5 {
+                return pixels[index as usize];
+            }
+            let (x, y) = ((index % width) as f64, (index / width) as f64);
+            let count = (length.ceil() as u32).clamp(2, settings.max_samples.max(2));
+            let sum = (0..count).fold(Color::zeros(), |sum, k| {
+                let t = (k as f64 + 0.5) / count as f64 - 0.5;
+                sum + sample(x + motion.x * t, y + motion.y * t)
+            });
+            sum / count as f64
+        })
+        .collect();
+
+    let mut out = fb.clone();
+    for y in 0..height {
+        for x in 0..width {
+            out.set(x, y, blurred[(y * width + x) as usize]);
+        }
+    }
+    out
+}
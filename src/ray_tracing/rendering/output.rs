@@ -0,0 +1,102 @@
+use super::color_space::tag_png_color_space;
+use super::framebuffer::FrameBuffer;
+use image::{DynamicImage, ImageFormat};
+use std::fs::File;
+use std::io::{self, BufWriter, Cursor, Write};
+use std::path::Path;
+
+/// 图像输出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// 8位PNG（或image库根据扩展名支持的其他8位格式）
+    #[default]
+    Png8,
+    /// 16位PNG，减少渐变区域的色带
+    Png16,
+    /// 书中使用的ASCII PPM（P3）格式，便于与其他实现逐字节比较
+    Ppm,
+    /// 线性浮点PFM格式，保留完整HDR数据
+    Pfm,
+}
+
+impl OutputFormat {
+    /// 根据文件扩展名推断输出格式
+    pub fn from_filename(filename: &str) -> Self {
+        let ext = Path::new(filename)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+
+        match ext.as_deref() {
+            Some("ppm") => Self::Ppm,
+            Some("pfm") => Self::Pfm,
+            _ => Self::Png8,
+        }
+    }
+}
+
+/// 按指定格式保存帧缓冲区
+///
+/// 帧缓冲区带alpha通道时，PNG格式写入RGBA；PPM和PFM不支持透明度，忽略alpha。
+/// 输出色彩空间不是默认的伽马 2.0 时，PNG 文件带有标记色彩空间的 cICP 块。
+pub fn save_framebuffer(fb: &FrameBuffer, filename: &str, format: OutputFormat) -> io::Result<()> {
+    let image: DynamicImage = match format {
+        OutputFormat::Png8 if fb.has_alpha() => fb.to_rgba8().into(),
+        OutputFormat::Png16 if fb.has_alpha() => fb.to_rgba16().into(),
+        OutputFormat::Png8 => fb.to_rgb8().into(),
+        OutputFormat::Png16 => fb.to_rgb16().into(),
+        OutputFormat::Ppm => return write_ppm(fb, filename),
+        OutputFormat::Pfm => return write_pfm(fb, filename),
+    };
+
+    let is_png = matches!(ImageFormat::from_path(filename), Ok(ImageFormat::Png));
+    if !is_png || fb.color_space().cicp().is_none() {
+        return image.save(filename).map_err(io::Error::other);
+    }
+    let mut png = Cursor::new(Vec::new());
+    image
+        .write_to(&mut png, ImageFormat::Png)
+        .map_err(io::Error::other)?;
+    std::fs::write(
+        filename,
+        tag_png_color_space(png.into_inner(), fb.color_space()),
+    )
+}
+
+/// 写入ASCII PPM（P3），量化方式与书中 write_color 一致
+fn write_ppm(fb: &FrameBuffer, filename: &str) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(filename)?);
+    writeln!(out, "P3\n{} {}\n255", fb.width(), fb.height())?;
+
+    for y in 0..fb.height() {
+        for x in 0..fb.width() {
+            let rgb = fb.encoded_rgb8(&fb.get(x, y));
+            writeln!(out, "{} {} {}", rgb[0], rgb[1], rgb[2])?;
+        }
+    }
+
+    out.flush()
+}
+
+/// 写入PFM（小端32位浮点，行从下到上存储），保存未经伽马校正、已转换到输出原色的线性值
+fn write_pfm(fb: &FrameBuffer, filename: &str) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(filename)?);
+    // 负比例因子表示小端字节序
+    write!(out, "PF\n{} {}\n-1.0\n", fb.width(), fb.height())?;
+
+    for y in (0..fb.height()).rev() {
+        for x in 0..fb.width() {
+            let c = fb.color_space().to_output_primaries(&fb.get(x, y));
+            for component in [c.x, c.y, c.z] {
+                let value = if component.is_finite() {
+                    component
+                } else {
+                    0.0
+                };
+                out.write_all(&(value as f32).to_le_bytes())?;
+            }
+        }
+    }
+
+    out.flush()
+}
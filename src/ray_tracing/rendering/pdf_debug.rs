@@ -0,0 +1,394 @@
+//! 散射PDF调试：在某个像素的首个交点处反复调用材质的 `scatter`，导出采样方向及其PDF
+//!
+//! 输出 CSV（每个样本一行）和三联的经纬度密度图，坐标系以表面法线为天顶：
+//! 采样方向的直方图密度 | 采样所用PDF的取值 | 材质的 `scattering_pdf`（BRDF·cosθ 的归一化波瓣）。
+//! 采样正确时前两幅应一致；重要性采样与BRDF匹配时后两幅也应一致（未混合光源采样时）。
+
+use super::camera::Camera;
+use super::color::luminance;
+use super::framebuffer::FrameBuffer;
+use super::output::{OutputFormat, save_framebuffer};
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::materials::material::ScatterRecord;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::onb::ONB;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::sampling::pdf::{HittablePDF, MixturePDF, PDF};
+use crate::ray_tracing::utils::image_compare::side_by_side;
+use crate::ray_tracing::utils::random::with_seeded_stream;
+use std::f64::consts::PI;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::sync::Arc;
+
+/// PDF调试配置
+#[derive(Debug, Clone)]
+pub struct PdfDebugConfig {
+    /// 散射样本数
+    pub samples: usize,
+    /// 每幅经纬度密度图的宽度（高度为宽度的一半）
+    pub image_width: u32,
+    /// 随机种子，相同种子得到相同的样本
+    pub seed: u64,
+}
+
+impl Default for PdfDebugConfig {
+    fn default() -> Self {
+        Self {
+            samples: 200_000,
+            image_width: 128,
+            seed: 0,
+        }
+    }
+}
+
+/// 一次散射采样
+#[derive(Debug, Clone, Copy)]
+pub struct ScatterSample {
+    /// 世界空间中的单位方向
+    pub direction: Vec3,
+    /// 以法线为 z 轴的局部坐标系中的单位方向
+    pub local: Vec3,
+    /// 生成该方向的PDF的取值（有光源时为光源与材质的混合PDF），镜面散射为无穷大
+    pub pdf: f64,
+    /// 材质自身PDF的取值
+    pub material_pdf: f64,
+    /// 材质的 `scattering_pdf`
+    pub scattering_pdf: f64,
+    /// 路径权重 bsdf_cos / pdf（默认即 attenuation · scattering_pdf / pdf，镜面散射为 attenuation）
+    pub weight: Color,
+    /// 是否为跳过PDF的镜面散射
+    pub specular: bool,
+}
+
+/// 某个交点处的散射采样结果
+pub struct PdfDebugReport {
+    /// 入射光线
+    pub ray: Ray,
+    /// 交点
+    pub hit: HitRecord,
+    /// 材质的调试描述
+    pub material: String,
+    /// 材质未散射（如光源）的次数
+    pub absorbed: usize,
+    /// 散射样本
+    pub samples: Vec<ScatterSample>,
+    frame: ONB,
+    lights: Option<Arc<dyn Hittable>>,
+}
+
+/// 在给定交点处采样散射方向
+///
+/// 没有场景时也可以手工构造 `HitRecord` 单独检查新材质；有光源列表时与渲染一样按光源和材质各一半混合采样。
+pub fn probe_hit(
+    ray: &Ray,
+    rec: &HitRecord,
+    lights: Option<Arc<dyn Hittable>>,
+    config: &PdfDebugConfig,
+) -> PdfDebugReport {
+    let frame = ONB::new(&rec.normal);
+    let mut samples = Vec::with_capacity(config.samples);
+    let mut absorbed = 0;
+
+    with_seeded_stream(config.seed, || {
+        for _ in 0..config.samples {
+            let mut srec = ScatterRecord::new();
+            if !rec.mat.scatter(ray, rec, &mut srec) {
+                absorbed += 1;
+                continue;
+            }
+
+            if srec.skip_pdf {
+                let direction = srec.skip_pdf_ray.dir.normalize();
+                samples.push(ScatterSample {
+                    direction,
+                    local: frame.world_to_local(&direction),
+                    pdf: f64::INFINITY,
+                    material_pdf: f64::INFINITY,
+                    scattering_pdf: 0.0,
+                    weight: srec.attenuation,
+                    specular: true,
+                });
+                continue;
+            }
+
+            let Some(material_pdf) = srec.pdf.as_ref() else {
+                absorbed += 1;
+                continue;
+            };
+            let (direction, pdf) = match &lights {
+                Some(lights) => {
+                    let light_pdf = HittablePDF::new(lights.as_ref(), &rec.p, ray.time);
+                    let mixture = MixturePDF::new(&light_pdf, material_pdf);
+                    let direction = mixture.generate();
+                    (direction, mixture.value(&direction))
+                }
+                None => {
+                    let direction = material_pdf.generate();
+                    (direction, material_pdf.value(&direction))
+                }
+            };
+            if direction.norm_squared() == 0.0 {
+                continue;
+            }
+
+            let scattered = Ray::new(rec.p, direction, ray.time);
+            let scattering_pdf = rec.mat.scattering_pdf(ray, rec, &scattered);
+            let weight = if pdf > 0.0 {
+                rec.mat.bsdf_cos(ray, rec, &srec, &scattered) / pdf
+            } else {
+                Color::zeros()
+            };
+            let direction = direction.normalize();
+            samples.push(ScatterSample {
+                direction,
+                local: frame.world_to_local(&direction),
+                pdf,
+                material_pdf: material_pdf.value(&direction),
+                scattering_pdf,
+                weight,
+                specular: false,
+            });
+        }
+    });
+
+    PdfDebugReport {
+        ray: *ray,
+        hit: rec.clone(),
+        material: format!("{:?}", rec.mat),
+        absorbed,
+        samples,
+        frame,
+        lights,
+    }
+}
+
+/// 在像素 (x, y) 中心的主光线的首个交点处采样散射方向，光线未命中任何物体时为None
+pub fn probe_pixel(
+    camera: &Camera,
+    world: &dyn Hittable,
+    lights: Option<Arc<dyn Hittable>>,
+    (x, y): (i32, i32),
+    config: &PdfDebugConfig,
+) -> Option<PdfDebugReport> {
+    let ray = camera.center_ray(x, y);
+    let mut rec = HitRecord::default();
+    if !world.hit(&ray, Interval::new(0.001, f64::INFINITY), &mut rec) {
+        return None;
+    }
+    Some(probe_hit(&ray, &rec, lights, config))
+}
+
+/// 经纬度图中 (x, y) 像素中心的局部方向和像素对应的立体角
+fn latlong_direction(x: u32, y: u32, width: u32, height: u32) -> (Vec3, f64) {
+    let d_phi = 2.0 * PI / width as f64;
+    let theta0 = PI * y as f64 / height as f64;
+    let theta1 = PI * (y + 1) as f64 / height as f64;
+    let theta = 0.5 * (theta0 + theta1);
+    let phi = -PI + (x as f64 + 0.5) * d_phi;
+    let direction = Vec3::new(
+        theta.sin() * phi.cos(),
+        theta.sin() * phi.sin(),
+        theta.cos(),
+    );
+    (direction, (theta0.cos() - theta1.cos()) * d_phi)
+}
+
+/// 局部方向所在的经纬度图像素
+fn latlong_pixel(local: &Vec3, width: u32, height: u32) -> (u32, u32) {
+    let theta = local.z.clamp(-1.0, 1.0).acos();
+    let phi = local.y.atan2(local.x);
+    let x = ((phi + PI) / (2.0 * PI) * width as f64) as u32;
+    let y = (theta / PI * height as f64) as u32;
+    (x.min(width - 1), y.min(height - 1))
+}
+
+/// 黑-红-黄-白热度色图，t 在 [0,1] 内
+pub(crate) fn heat(t: f64) -> Color {
+    let t = t.clamp(0.0, 1.0) * 3.0;
+    Color::new(
+        t.min(1.0),
+        (t - 1.0).clamp(0.0, 1.0),
+        (t - 2.0).clamp(0.0, 1.0),
+    )
+}
+
+impl PdfDebugReport {
+    /// 方向 direction 上渲染时采样所用的PDF（有光源时为光源与材质的混合PDF）
+    fn sampling_pdf(&self, direction: &Vec3) -> f64 {
+        let mut srec = ScatterRecord::new();
+        if !self.hit.mat.scatter(&self.ray, &self.hit, &mut srec) {
+            return 0.0;
+        }
+        let Some(material_pdf) = srec.pdf.as_ref() else {
+            return 0.0;
+        };
+        match &self.lights {
+            Some(lights) => {
+                let light_pdf = HittablePDF::new(lights.as_ref(), &self.hit.p, self.ray.time);
+                MixturePDF::new(&light_pdf, material_pdf).value(direction)
+            }
+            None => material_pdf.value(direction),
+        }
+    }
+
+    /// 方向 direction 上材质的 `scattering_pdf`
+    fn scattering_lobe(&self, direction: &Vec3) -> f64 {
+        let scattered = Ray::new(self.hit.p, *direction, self.ray.time);
+        self.hit
+            .mat
+            .scattering_pdf(&self.ray, &self.hit, &scattered)
+    }
+
+    /// 镜面散射样本的比例
+    pub fn specular_fraction(&self) -> f64 {
+        let total = self.samples.len() + self.absorbed;
+        if total == 0 {
+            return 0.0;
+        }
+        self.samples.iter().filter(|s| s.specular).count() as f64 / total as f64
+    }
+
+    /// 样本的平均路径权重（能量守恒的材质各分量不超过1）
+    pub fn mean_weight(&self) -> Color {
+        let total = self.samples.len() + self.absorbed;
+        if total == 0 {
+            return Color::zeros();
+        }
+        self.samples.iter().map(|s| s.weight).sum::<Color>() / total as f64
+    }
+
+    /// 在经纬度网格上数值积分采样PDF和 `scattering_pdf`，返回 (∫pdf, ∫scattering_pdf)
+    ///
+    /// 第一项应为1（镜面材质为0）；第二项是不计镜面部分时材质的方向反照率上限，超过1说明波瓣未归一化。
+    pub fn pdf_integrals(&self, width: u32) -> (f64, f64) {
+        let width = width.max(2);
+        let height = (width / 2).max(1);
+        with_seeded_stream(0, || {
+            let (mut pdf, mut lobe) = (0.0, 0.0);
+            for y in 0..height {
+                for x in 0..width {
+                    let (local, solid_angle) = latlong_direction(x, y, width, height);
+                    let direction = self.frame.local_to_world(&local);
+                    pdf += self.sampling_pdf(&direction) * solid_angle;
+                    lobe += self.scattering_lobe(&direction) * solid_angle;
+                }
+            }
+            (pdf, lobe)
+        })
+    }
+
+    /// 把样本写入 CSV 文件
+    pub fn write_csv(&self, path: &str) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln!(
+            out,
+            "dir_x,dir_y,dir_z,local_x,local_y,local_z,pdf,material_pdf,scattering_pdf,weight_r,weight_g,weight_b,specular"
+        )?;
+        for s in &self.samples {
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                s.direction.x,
+                s.direction.y,
+                s.direction.z,
+                s.local.x,
+                s.local.y,
+                s.local.z,
+                s.pdf,
+                s.material_pdf,
+                s.scattering_pdf,
+                s.weight.x,
+                s.weight.y,
+                s.weight.z,
+                s.specular as u8
+            )?;
+        }
+        out.flush()
+    }
+
+    /// 三联经纬度密度图：样本直方图 | 采样PDF | scattering_pdf，三幅使用同一色标
+    ///
+    /// 以法线为天顶（图像上半部分为法线一侧的半球），色标上限取采样PDF的99%分位数。
+    pub fn density_image(&self, width: u32) -> FrameBuffer {
+        let width = width.max(2);
+        let height = (width / 2).max(1);
+        let count = (self.samples.len() + self.absorbed).max(1) as f64;
+
+        let mut histogram = vec![0.0; (width * height) as usize];
+        for s in &self.samples {
+            let (x, y) = latlong_pixel(&s.local, width, height);
+            histogram[(y * width + x) as usize] += 1.0;
+        }
+
+        let mut sampled = Vec::with_capacity(histogram.len());
+        let mut pdf = Vec::with_capacity(histogram.len());
+        let mut lobe = Vec::with_capacity(histogram.len());
+        with_seeded_stream(0, || {
+            for y in 0..height {
+                for x in 0..width {
+                    let (local, solid_angle) = latlong_direction(x, y, width, height);
+                    let direction = self.frame.local_to_world(&local);
+                    sampled.push(histogram[(y * width + x) as usize] / (count * solid_angle));
+                    pdf.push(self.sampling_pdf(&direction));
+                    lobe.push(self.scattering_lobe(&direction));
+                }
+            }
+        });
+
+        let mut sorted: Vec<f64> = pdf.iter().copied().filter(|v| v.is_finite()).collect();
+        sorted.sort_by(f64::total_cmp);
+        let scale = sorted
+            .get((sorted.len() as f64 * 0.99) as usize)
+            .copied()
+            .filter(|v| *v > 0.0)
+            .unwrap_or(1.0);
+
+        let panel = |values: &[f64]| {
+            let mut fb = FrameBuffer::new(width, height);
+            for y in 0..height {
+                for x in 0..width {
+                    fb.set(x, y, heat(values[(y * width + x) as usize] / scale));
+                }
+            }
+            fb
+        };
+        side_by_side(&[&panel(&sampled), &panel(&pdf), &panel(&lobe)])
+    }
+
+    /// 保存三联密度图
+    pub fn save_density_image(&self, path: &str, width: u32) -> io::Result<()> {
+        save_framebuffer(
+            &self.density_image(width),
+            path,
+            OutputFormat::from_filename(path),
+        )
+    }
+}
+
+impl std::fmt::Display for PdfDebugReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let weight = self.mean_weight();
+        write!(
+            f,
+            "交点 ({:.3}, {:.3}, {:.3}), 法线 ({:.3}, {:.3}, {:.3}), 材质 {}\n样本 {}, 未散射 {}, 镜面比例 {:.3}, 平均权重 ({:.4}, {:.4}, {:.4}) 亮度 {:.4}",
+            self.hit.p.x,
+            self.hit.p.y,
+            self.hit.p.z,
+            self.hit.normal.x,
+            self.hit.normal.y,
+            self.hit.normal.z,
+            self.material,
+            self.samples.len(),
+            self.absorbed,
+            self.specular_fraction(),
+            weight.x,
+            weight.y,
+            weight.z,
+            luminance(&weight)
+        )?;
+        let (pdf, lobe) = self.pdf_integrals(128);
+        write!(f, "\n∫pdf = {:.4}, ∫scattering_pdf = {:.4}", pdf, lobe)
+    }
+}
@@ -0,0 +1,392 @@
+//! 辐照度探针网格烘焙：在规则网格的探针处积分入射辐射度，投影到二阶球谐函数，供实时引擎使用
+//!
+//! 探针位于包围盒均分后各格子的中心（不会落在包围盒的表面上），每个探针向整个球面分层抽样，
+//! 沿每个方向使用相机的积分器（[`Camera::trace_radiance`]）追踪完整路径，把入射辐射度
+//! 投影到 9 个实球谐基函数上。系数是辐射度本身而不是卷积后的辐照度，
+//! 实时引擎按需乘以余弦核的卷积系数（π, 2π/3, π/4），见 [`ShProbe::irradiance`]。
+//!
+//! 坐标系与渲染器一致（右手系，Y轴向上），颜色为线性 Rec.709。基函数的顺序和归一化与
+//! Sloan《Stupid Spherical Harmonics Tricks》一致：
+//! `Y00, Y1-1(y), Y10(z), Y11(x), Y2-2(xy), Y2-1(yz), Y20(3z²-1), Y21(xz), Y22(x²-y²)`。
+//!
+//! 导出格式：
+//! - JSON：网格范围、分辨率、基函数约定，以及每个探针的位置、9×RGB 系数和背面命中比例；
+//! - 二进制（小端）：`b"SHPG"`、u32 版本号 1、u32 ×3 分辨率、f32 ×3 最小角、f32 ×3 最大角，
+//!   随后每个探针 27 个 f32 系数（按基函数排列的 RGB）和 1 个 f32 背面命中比例。
+//!
+//! 两种格式中探针都按 x 最快、z 最慢的顺序排列：索引为 `i + nx·(j + ny·k)`。
+//! 背面命中比例是探针的首次命中中击中物体背面的比例，接近1说明探针埋在物体内部，
+//! 引擎插值时应降低它的权重。它依赖表面法线的朝向，对朝向不一致的单面四边形只能作为参考。
+
+use super::camera::Camera;
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::json::Json;
+use crate::ray_tracing::utils::random::{hash_seed, random_double, with_seeded_stream};
+use rayon::prelude::*;
+use std::f64::consts::PI;
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// 二阶球谐函数的系数个数
+pub const SH_COEFFICIENTS: usize = 9;
+
+/// 方向 d（单位向量）处 9 个实球谐基函数的值
+pub fn sh_basis(d: &Vec3) -> [f64; SH_COEFFICIENTS] {
+    let (x, y, z) = (d.x, d.y, d.z);
+    [
+        0.282_095,
+        0.488_603 * y,
+        0.488_603 * z,
+        0.488_603 * x,
+        1.092_548 * x * y,
+        1.092_548 * y * z,
+        0.315_392 * (3.0 * z * z - 1.0),
+        1.092_548 * x * z,
+        0.546_274 * (x * x - y * y),
+    ]
+}
+
+/// 余弦核在各阶上的卷积系数（Ramamoorthi & Hanrahan），按系数展开
+const COSINE_LOBE: [f64; SH_COEFFICIENTS] = [
+    PI,
+    2.0 * PI / 3.0,
+    2.0 * PI / 3.0,
+    2.0 * PI / 3.0,
+    PI / 4.0,
+    PI / 4.0,
+    PI / 4.0,
+    PI / 4.0,
+    PI / 4.0,
+];
+
+/// 一个探针：位置、入射辐射度的球谐系数和背面命中比例
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShProbe {
+    pub position: Point3,
+    pub coefficients: [Color; SH_COEFFICIENTS],
+    /// 首次命中中击中物体背面的比例
+    pub backface: f64,
+}
+
+impl ShProbe {
+    /// 由系数重建的方向 d 的入射辐射度（低频近似，可能出现负值和振铃）
+    pub fn radiance(&self, d: &Vec3) -> Color {
+        let basis = sh_basis(&d.normalize());
+        self.coefficients
+            .iter()
+            .zip(basis)
+            .map(|(c, y)| c * y)
+            .sum()
+    }
+
+    /// 法线为 n 的表面接收的辐照度 E（漫反射表面的出射辐射度为 albedo·E/π）
+    pub fn irradiance(&self, n: &Vec3) -> Color {
+        let basis = sh_basis(&n.normalize());
+        self.coefficients
+            .iter()
+            .zip(basis)
+            .zip(COSINE_LOBE)
+            .map(|((c, y), a)| c * (y * a))
+            .sum::<Color>()
+            .map(|c| c.max(0.0))
+    }
+}
+
+/// 探针网格的导出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProbeFormat {
+    #[default]
+    Json,
+    Binary,
+}
+
+impl ProbeFormat {
+    /// 按扩展名推断：`.json` 为 JSON，其余为二进制
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        match path.as_ref().extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::Json,
+            _ => Self::Binary,
+        }
+    }
+}
+
+impl FromStr for ProbeFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "binary" | "bin" => Ok(Self::Binary),
+            _ => Err(format!("未知的探针导出格式 `{}`（可选: json, binary）", s)),
+        }
+    }
+}
+
+impl fmt::Display for ProbeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Json => "json",
+            Self::Binary => "binary",
+        })
+    }
+}
+
+/// 探针网格的烘焙设置
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProbeGridSettings {
+    /// 网格包围盒的最小角
+    pub min: Point3,
+    /// 网格包围盒的最大角
+    pub max: Point3,
+    /// 三个轴向的探针数
+    pub resolution: [u32; 3],
+    /// 每个探针的方向样本数（向下取整为平方数以便分层）
+    pub samples_per_probe: u32,
+}
+
+impl Default for ProbeGridSettings {
+    fn default() -> Self {
+        Self {
+            min: Point3::new(-1.0, -1.0, -1.0),
+            max: Point3::new(1.0, 1.0, 1.0),
+            resolution: [4, 4, 4],
+            samples_per_probe: 1024,
+        }
+    }
+}
+
+impl ProbeGridSettings {
+    /// 覆盖 min 到 max 的网格
+    pub fn new(min: Point3, max: Point3) -> Self {
+        Self {
+            min: Point3::from(min.coords.inf(&max.coords)),
+            max: Point3::from(min.coords.sup(&max.coords)),
+            ..Self::default()
+        }
+    }
+
+    /// 设置三个轴向的探针数
+    pub fn with_resolution(mut self, resolution: [u32; 3]) -> Self {
+        self.resolution = resolution.map(|n| n.max(1));
+        self
+    }
+
+    /// 设置每个探针的方向样本数
+    pub fn with_samples(mut self, samples: u32) -> Self {
+        self.samples_per_probe = samples.max(1);
+        self
+    }
+
+    /// 探针总数
+    pub fn probe_count(&self) -> usize {
+        self.resolution.iter().map(|&n| n as usize).product()
+    }
+
+    /// 探针 (i, j, k) 的位置：对应格子的中心
+    pub fn position(&self, i: u32, j: u32, k: u32) -> Point3 {
+        let t = Vec3::new(
+            (i as f64 + 0.5) / self.resolution[0] as f64,
+            (j as f64 + 0.5) / self.resolution[1] as f64,
+            (k as f64 + 0.5) / self.resolution[2] as f64,
+        );
+        self.min + (self.max - self.min).component_mul(&t)
+    }
+}
+
+/// 烘焙好的探针网格
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeGrid {
+    pub settings: ProbeGridSettings,
+    /// 按 `i + nx·(j + ny·k)` 排列的探针
+    pub probes: Vec<ShProbe>,
+}
+
+impl ProbeGrid {
+    /// 探针 (i, j, k)
+    pub fn probe(&self, i: u32, j: u32, k: u32) -> &ShProbe {
+        let [nx, ny, _] = self.settings.resolution;
+        &self.probes[(i + nx * (j + ny * k)) as usize]
+    }
+
+    /// 背面命中比例超过 threshold 的探针数（可能埋在物体内部）
+    pub fn buried(&self, threshold: f64) -> usize {
+        self.probes
+            .iter()
+            .filter(|p| p.backface > threshold)
+            .count()
+    }
+
+    /// JSON 表示
+    pub(crate) fn to_json(&self) -> Json {
+        let number = |x: f64| Json::Number(x);
+        let triple = |v: &Vec3| Json::Array(vec![number(v.x), number(v.y), number(v.z)]);
+        let point = |p: &Point3| triple(&p.coords);
+        let settings = &self.settings;
+        let probes = self
+            .probes
+            .iter()
+            .map(|probe| {
+                Json::Object(vec![
+                    ("position".into(), point(&probe.position)),
+                    (
+                        "sh".into(),
+                        Json::Array(probe.coefficients.iter().map(triple).collect()),
+                    ),
+                    ("backface".into(), number(probe.backface)),
+                ])
+            })
+            .collect();
+        Json::Object(vec![
+            ("format".into(), Json::String("sh-probe-grid".into())),
+            ("version".into(), number(1.0)),
+            (
+                "basis".into(),
+                Json::String("sloan-l2: Y00 Y1-1(y) Y10(z) Y11(x) Y2-2 Y2-1 Y20 Y21 Y22".into()),
+            ),
+            (
+                "quantity".into(),
+                Json::String("incident radiance, linear rec709, y-up".into()),
+            ),
+            ("min".into(), point(&settings.min)),
+            ("max".into(), point(&settings.max)),
+            (
+                "resolution".into(),
+                Json::Array(
+                    settings
+                        .resolution
+                        .iter()
+                        .map(|&n| number(n as f64))
+                        .collect(),
+                ),
+            ),
+            (
+                "samples_per_probe".into(),
+                number(settings.samples_per_probe as f64),
+            ),
+            ("probes".into(), Json::Array(probes)),
+        ])
+    }
+
+    /// 二进制表示，布局见模块文档
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let settings = &self.settings;
+        let mut bytes = Vec::with_capacity(40 + self.probes.len() * 28 * 4);
+        bytes.extend_from_slice(b"SHPG");
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        for n in settings.resolution {
+            bytes.extend_from_slice(&n.to_le_bytes());
+        }
+        let floats = settings.min.iter().chain(settings.max.iter());
+        for &x in floats {
+            bytes.extend_from_slice(&(x as f32).to_le_bytes());
+        }
+        for probe in &self.probes {
+            for c in &probe.coefficients {
+                for &x in c.iter() {
+                    bytes.extend_from_slice(&(x as f32).to_le_bytes());
+                }
+            }
+            bytes.extend_from_slice(&(probe.backface as f32).to_le_bytes());
+        }
+        bytes
+    }
+
+    /// 按格式保存到文件
+    pub fn save(&self, path: impl AsRef<Path>, format: ProbeFormat) -> io::Result<()> {
+        let mut file = io::BufWriter::new(std::fs::File::create(path)?);
+        match format {
+            ProbeFormat::Json => writeln!(file, "{:#}", self.to_json())?,
+            ProbeFormat::Binary => file.write_all(&self.to_bytes())?,
+        }
+        file.flush()
+    }
+}
+
+/// 在 world 中烘焙探针网格，lights 为用于重要性采样的光源
+///
+/// 相机只提供积分器设置（背景、最大深度、随机种子等），其位置和画面参数不影响结果。
+/// 设置了随机种子时结果与线程数无关。
+pub fn bake_probe_grid(
+    camera: &Camera,
+    world: &dyn Hittable,
+    lights: Option<Arc<dyn Hittable>>,
+    settings: &ProbeGridSettings,
+) -> ProbeGrid {
+    let [nx, ny, _] = settings.resolution;
+    let lights = lights.as_ref();
+    let probes = (0..settings.probe_count())
+        .into_par_iter()
+        .map(|index| {
+            let index = index as u32;
+            let (i, j, k) = (index % nx, (index / nx) % ny, index / (nx * ny));
+            let position = settings.position(i, j, k);
+            let bake = || bake_probe(camera, world, lights, position, settings.samples_per_probe);
+            match camera.seed() {
+                Some(seed) => with_seeded_stream(hash_seed(&[seed, index as u64]), bake),
+                None => bake(),
+            }
+        })
+        .collect();
+    ProbeGrid {
+        settings: *settings,
+        probes,
+    }
+}
+
+/// 对球面分层均匀抽样，把入射辐射度投影到球谐基上
+fn bake_probe(
+    camera: &Camera,
+    world: &dyn Hittable,
+    lights: Option<&Arc<dyn Hittable>>,
+    position: Point3,
+    samples: u32,
+) -> ShProbe {
+    let strata = (samples.max(1) as f64).sqrt() as u32;
+    let count = strata * strata;
+    let mut coefficients = [Color::zeros(); SH_COEFFICIENTS];
+    let mut hits = 0u32;
+    let mut backfaces = 0u32;
+
+    for s in 0..count {
+        // 等面积映射：z 均匀分布在 [-1, 1]，方位角均匀分布
+        let z = 1.0 - 2.0 * ((s / strata) as f64 + random_double()) / strata as f64;
+        let phi = 2.0 * PI * ((s % strata) as f64 + random_double()) / strata as f64;
+        let r = (1.0 - z * z).max(0.0).sqrt();
+        let direction = Vec3::new(r * phi.cos(), r * phi.sin(), z);
+
+        let ray = Ray::new(position, direction, 0.0);
+        let mut rec = HitRecord::default();
+        if world.hit(&ray, Interval::new(0.001, f64::INFINITY), &mut rec) {
+            hits += 1;
+            if !rec.front_face {
+                backfaces += 1;
+            }
+        }
+
+        let radiance = camera.trace_radiance(&ray, world, lights);
+        if !radiance.iter().all(|c| c.is_finite()) {
+            continue;
+        }
+        for (c, y) in coefficients.iter_mut().zip(sh_basis(&direction)) {
+            *c += radiance * y;
+        }
+    }
+
+    // 均匀球面抽样的 pdf 为 1/(4π)
+    let weight = 4.0 * PI / count as f64;
+    ShProbe {
+        position,
+        coefficients: coefficients.map(|c| c * weight),
+        backface: if hits == 0 {
+            0.0
+        } else {
+            backfaces as f64 / hits as f64
+        },
+    }
+}
@@ -0,0 +1,72 @@
+//! 渲染进度回调与取消，供嵌入渲染器的应用（图形界面、服务）使用
+//!
+//! 见 [`Camera::render_with`](super::camera::Camera::render_with)：进度以回调报告，
+//! 取消通过调用方持有的 `AtomicBool` 请求，渲染库本身不向终端输出进度。
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// 渲染进度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Progress {
+    /// 已完成的像素数
+    pub completed: u64,
+    /// 像素总数
+    pub total: u64,
+}
+
+impl Progress {
+    /// 完成比例，[0,1]
+    #[inline]
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.completed as f64 / self.total as f64
+        }
+    }
+
+    /// 是否已全部完成
+    #[inline]
+    pub fn is_done(&self) -> bool {
+        self.completed >= self.total
+    }
+}
+
+/// 一次渲染内共享的进度计数和取消标志
+pub(crate) struct ProgressSink<'a> {
+    callback: &'a (dyn Fn(Progress) + Sync),
+    cancel: &'a AtomicBool,
+    completed: AtomicU64,
+    total: u64,
+}
+
+impl<'a> ProgressSink<'a> {
+    pub(crate) fn new(
+        total: u64,
+        callback: &'a (dyn Fn(Progress) + Sync),
+        cancel: &'a AtomicBool,
+    ) -> Self {
+        Self {
+            callback,
+            cancel,
+            completed: AtomicU64::new(0),
+            total,
+        }
+    }
+
+    /// 记录完成一个像素并回调（可能在多个工作线程上并发调用）
+    #[inline]
+    pub(crate) fn pixel_done(&self) {
+        let completed = self.completed.fetch_add(1, Ordering::Relaxed) + 1;
+        (self.callback)(Progress {
+            completed,
+            total: self.total,
+        });
+    }
+
+    /// 是否已请求取消
+    #[inline]
+    pub(crate) fn cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+}
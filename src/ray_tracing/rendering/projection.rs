@@ -0,0 +1,162 @@
+//! 相机投影：透视、球极平面投影（“小行星”）和移轴镜头
+//!
+//! 移轴镜头按沙姆定律（Scheimpflug）处理镜头倾斜：镜头平面绕相机水平轴倾斜 α 后，
+//! 清晰平面绕“铰链线”转动，铰链线位于镜头中心下方 J = f / sin α 处（f 为焦距），
+//! 清晰平面同时经过铰链线和光轴上对焦距离处的点。很小的倾角就能让清晰平面大幅倾斜，
+//! 配合大光圈得到只有一条窄带清晰的“微缩模型”效果。移轴则平移视口，保持竖直线不汇聚。
+
+use crate::ray_tracing::math::vec3::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// 小行星投影的默认视场角（度）
+pub const DEFAULT_LITTLE_PLANET_FOV: f64 = 300.0;
+
+/// 相机投影方式
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Projection {
+    /// 针孔/薄透镜透视投影
+    #[default]
+    Perspective,
+    /// 球极平面投影：图像宽度方向覆盖 fov 度（可超过 180°），
+    /// 相机朝正下方时地面卷成一颗小行星，天空环绕在外
+    LittlePlanet { fov: f64 },
+    /// 移轴镜头
+    TiltShift(TiltShift),
+}
+
+/// 移轴镜头参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TiltShift {
+    /// 镜头绕相机水平轴的倾角（度），为正时清晰平面向下倾向地面
+    pub tilt: f64,
+    /// 视口平移，分别以视口宽度和高度为单位（向右、向上为正）
+    pub shift: (f64, f64),
+    /// 焦距（场景单位），0 为按场景单位是米、35mm 全画幅等效焦距由视场角推算
+    pub focal_length: f64,
+}
+
+impl Default for TiltShift {
+    fn default() -> Self {
+        Self {
+            tilt: 0.0,
+            shift: (0.0, 0.0),
+            focal_length: 0.0,
+        }
+    }
+}
+
+impl TiltShift {
+    /// 全画幅传感器高度的一半（米）
+    const HALF_SENSOR_HEIGHT: f64 = 0.012;
+
+    /// 只倾斜镜头
+    pub fn tilt(tilt: f64) -> Self {
+        Self {
+            tilt,
+            ..Self::default()
+        }
+    }
+
+    /// 实际使用的焦距，vfov 为相机的垂直视场角（度）
+    pub fn effective_focal_length(&self, vfov: f64) -> f64 {
+        if self.focal_length > 0.0 {
+            self.focal_length
+        } else {
+            Self::HALF_SENSOR_HEIGHT / (vfov.to_radians() / 2.0).tan()
+        }
+    }
+
+    /// 清晰平面（平面上一点和单位法线）
+    ///
+    /// center 为镜头中心，forward/up/right 为相机的视线、上方和右方单位向量。
+    pub fn focus_plane(
+        &self,
+        center: &Point3,
+        forward: &Vec3,
+        up: &Vec3,
+        right: &Vec3,
+        focus_dist: f64,
+        vfov: f64,
+    ) -> (Point3, Vec3) {
+        let on_axis = center + focus_dist * forward;
+        let sin_tilt = self.tilt.to_radians().sin();
+        if sin_tilt.abs() < 1e-12 {
+            return (on_axis, *forward);
+        }
+        // 铰链线：经过镜头中心下方 J 处、平行于水平轴
+        let hinge = center - up * (self.effective_focal_length(vfov) / sin_tilt);
+        let normal = right.cross(&(on_axis - hinge)).normalize();
+        // 法线与视线同向，便于求交时判断前后
+        let normal = if normal.dot(forward) < 0.0 {
+            -normal
+        } else {
+            normal
+        };
+        (on_axis, normal)
+    }
+}
+
+impl Projection {
+    /// 小行星投影：归一化图像坐标 (x, y)（x 向右、y 向下，图像宽度方向为 [-1, 1]）
+    /// 对应的相机空间方向，分量依次为沿视线、向右、向下
+    pub fn stereographic_direction(fov: f64, x: f64, y: f64) -> Vec3 {
+        let scale = 2.0 * (fov.to_radians() / 4.0).tan();
+        let (x, y) = (x * scale, y * scale);
+        let r = x.hypot(y);
+        if r < 1e-12 {
+            return Vec3::new(1.0, 0.0, 0.0);
+        }
+        let theta = 2.0 * (r / 2.0).atan();
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        Vec3::new(cos_theta, sin_theta * x / r, sin_theta * y / r)
+    }
+}
+
+impl FromStr for Projection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let name = parts.next().unwrap_or_default().to_ascii_lowercase();
+        let numbers: Vec<f64> = parts
+            .map(|p| p.parse().map_err(|_| format!("无效的投影参数 `{}`", p)))
+            .collect::<Result<_, _>>()?;
+        match (name.as_str(), numbers.as_slice()) {
+            ("perspective", []) => Ok(Self::Perspective),
+            ("little-planet" | "stereographic", []) => Ok(Self::LittlePlanet {
+                fov: DEFAULT_LITTLE_PLANET_FOV,
+            }),
+            ("little-planet" | "stereographic", &[fov]) if fov > 0.0 && fov < 360.0 => {
+                Ok(Self::LittlePlanet { fov })
+            }
+            ("tilt-shift", &[tilt, ref rest @ ..]) if rest.len() <= 2 && tilt.abs() < 90.0 => {
+                Ok(Self::TiltShift(TiltShift {
+                    tilt,
+                    shift: (0.0, rest.first().copied().unwrap_or(0.0)),
+                    focal_length: rest.get(1).copied().unwrap_or(0.0).max(0.0),
+                }))
+            }
+            _ => Err(format!(
+                "无效的投影 `{}`（可选: perspective, little-planet[:视场角], tilt-shift:倾角[:上移[:焦距]]）",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Projection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Perspective => f.write_str("perspective"),
+            Self::LittlePlanet { fov } => write!(f, "little-planet:{}", fov),
+            Self::TiltShift(lens) => {
+                write!(f, "tilt-shift:{}:{}", lens.tilt, lens.shift.1)?;
+                if lens.focal_length > 0.0 {
+                    write!(f, ":{}", lens.focal_length)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! 机器可读的渲染报告（JSON）
+//!
+//! 渲染结束时写出渲染设置、各阶段耗时、进度记录、材质统计和输出文件的哈希，
+//! 自动化流程和基准测试面板可以直接读取结果，不必解析终端上的进度文本。
+//!
+//! 进度记录按游程压缩：完成百分比每变化一次记一条（百分比, 已用秒数），
+//! 一次渲染最多 101 条，与像素数无关。
+
+use super::stats::MaterialStats;
+use crate::ray_tracing::utils::json::Json;
+use std::cell::RefCell;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// 报告格式版本，字段有不兼容的变化时递增
+pub const REPORT_VERSION: u32 = 1;
+
+/// 一个渲染阶段的耗时
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageTiming {
+    /// 阶段名：caustics、render、denoise、overlay、save
+    pub name: String,
+    pub seconds: f64,
+}
+
+/// 进度记录中的一条：完成百分比首次达到 percent 时已用的秒数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressMark {
+    pub percent: u32,
+    pub seconds: f64,
+}
+
+/// 一次渲染的报告
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenderReport {
+    /// 渲染设置（名称, 值），值以字符串或数字写出
+    pub settings: Vec<(String, ReportValue)>,
+    /// 各阶段耗时，按执行顺序
+    pub stages: Vec<StageTiming>,
+    /// 游程压缩的进度记录
+    pub progress: Vec<ProgressMark>,
+    /// 按材质类型的着色统计（相机未设置统计收集器时为空）
+    pub stats: Vec<MaterialStats>,
+    /// 输出文件路径
+    pub output: Option<String>,
+    /// 输出文件内容的 FNV-1a 64 位哈希，保存失败时为None
+    pub output_hash: Option<u64>,
+    /// 总耗时（秒）
+    pub total_seconds: f64,
+}
+
+/// 报告中的设置值
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReportValue {
+    Number(f64),
+    Text(String),
+    Flag(bool),
+    None,
+}
+
+impl From<f64> for ReportValue {
+    fn from(x: f64) -> Self {
+        Self::Number(x)
+    }
+}
+
+impl From<i32> for ReportValue {
+    fn from(x: i32) -> Self {
+        Self::Number(x as f64)
+    }
+}
+
+impl From<usize> for ReportValue {
+    fn from(x: usize) -> Self {
+        Self::Number(x as f64)
+    }
+}
+
+impl From<bool> for ReportValue {
+    fn from(b: bool) -> Self {
+        Self::Flag(b)
+    }
+}
+
+impl From<String> for ReportValue {
+    fn from(s: String) -> Self {
+        Self::Text(s)
+    }
+}
+
+impl<T: Into<ReportValue>> From<Option<T>> for ReportValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::None, Into::into)
+    }
+}
+
+impl ReportValue {
+    fn to_json(&self) -> Json {
+        match self {
+            Self::Number(x) => Json::Number(*x),
+            Self::Text(s) => Json::String(s.clone()),
+            Self::Flag(b) => Json::Bool(*b),
+            Self::None => Json::Null,
+        }
+    }
+}
+
+impl RenderReport {
+    /// 添加一项设置
+    pub fn setting(&mut self, name: &str, value: impl Into<ReportValue>) {
+        self.settings.push((name.to_string(), value.into()));
+    }
+
+    /// 名为 name 的阶段耗时
+    pub fn stage(&self, name: &str) -> Option<f64> {
+        self.stages
+            .iter()
+            .find(|s| s.name == name)
+            .map(|s| s.seconds)
+    }
+
+    /// 记录输出文件并计算其哈希（文件不可读时哈希为None）
+    pub fn set_output(&mut self, path: &str) {
+        self.output = Some(path.to_string());
+        self.output_hash = std::fs::read(path).ok().map(|bytes| fnv1a64(&bytes));
+    }
+
+    /// 两空格缩进的 JSON 文本
+    pub fn to_json(&self) -> String {
+        let number = |x: f64| Json::Number(x);
+        let text = |s: &str| Json::String(s.to_string());
+
+        let settings = self
+            .settings
+            .iter()
+            .map(|(name, value)| (name.clone(), value.to_json()))
+            .collect();
+        let stages = self
+            .stages
+            .iter()
+            .map(|s| {
+                Json::Object(vec![
+                    ("name".into(), text(&s.name)),
+                    ("seconds".into(), number(round_ms(s.seconds))),
+                ])
+            })
+            .collect();
+        let progress = self
+            .progress
+            .iter()
+            .map(|m| Json::Array(vec![number(m.percent as f64), number(round_ms(m.seconds))]))
+            .collect();
+        let stats = self
+            .stats
+            .iter()
+            .map(|s| {
+                Json::Object(vec![
+                    ("material".into(), text(&s.label)),
+                    ("hits".into(), number(s.hits as f64)),
+                    ("primary_hits".into(), number(s.primary_hits as f64)),
+                    ("scatters".into(), number(s.scatters as f64)),
+                    ("average_bounce".into(), number(s.average_bounce())),
+                ])
+            })
+            .collect();
+
+        let json = Json::Object(vec![
+            ("version".into(), number(REPORT_VERSION as f64)),
+            ("settings".into(), Json::Object(settings)),
+            ("stages".into(), Json::Array(stages)),
+            ("total_seconds".into(), number(round_ms(self.total_seconds))),
+            ("progress".into(), Json::Array(progress)),
+            ("stats".into(), Json::Array(stats)),
+            (
+                "output".into(),
+                self.output.as_deref().map_or(Json::Null, text),
+            ),
+            (
+                "output_hash".into(),
+                self.output_hash
+                    .map_or(Json::Null, |h| text(&format!("fnv1a64:{:016x}", h))),
+            ),
+        ]);
+        format!("{:#}\n", json)
+    }
+
+    /// 写出到文件
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+}
+
+/// 报告文件路径：输出文件名后加 `.json`（如 `final_scene.png.json`）
+pub fn report_path(output_filename: &str) -> String {
+    format!("{}.json", output_filename)
+}
+
+/// FNV-1a 64 位哈希
+pub fn fnv1a64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |h, &b| {
+        (h ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// 保留到毫秒，避免报告中出现无意义的长小数
+fn round_ms(seconds: f64) -> f64 {
+    (seconds * 1000.0).round() / 1000.0
+}
+
+/// 游程压缩的进度记录器，可在工作线程上并发调用
+pub(crate) struct ProgressLog {
+    start: Instant,
+    last_percent: AtomicU64,
+    marks: Mutex<Vec<ProgressMark>>,
+}
+
+impl ProgressLog {
+    pub(crate) fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            last_percent: AtomicU64::new(u64::MAX),
+            marks: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// 记录进度；百分比未变化时只有一次原子读取
+    #[inline]
+    pub(crate) fn record(&self, completed: u64, total: u64) {
+        let percent = (completed * 100).checked_div(total).unwrap_or(100);
+        let last = self.last_percent.load(Ordering::Relaxed);
+        if last != u64::MAX && percent <= last {
+            return;
+        }
+        if self
+            .last_percent
+            .compare_exchange(last, percent, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+        {
+            self.marks.lock().unwrap().push(ProgressMark {
+                percent: percent as u32,
+                seconds: self.start.elapsed().as_secs_f64(),
+            });
+        }
+    }
+
+    /// 按百分比排序的记录（并发写入时顺序可能交错）
+    pub(crate) fn into_marks(self) -> Vec<ProgressMark> {
+        let mut marks = self.marks.into_inner().unwrap();
+        marks.sort_by_key(|m| m.percent);
+        marks
+    }
+}
+
+thread_local! {
+    /// 当前线程上正在收集的阶段耗时，未收集时为None
+    static STAGES: RefCell<Option<Vec<StageTiming>>> = const { RefCell::new(None) };
+}
+
+/// 记录一个阶段的耗时；当前线程没有在收集时不做任何事
+pub(crate) fn record_stage(name: &str, elapsed: Duration) {
+    STAGES.with(|stages| {
+        if let Some(list) = stages.borrow_mut().as_mut() {
+            list.push(StageTiming {
+                name: name.to_string(),
+                seconds: elapsed.as_secs_f64(),
+            });
+        }
+    });
+}
+
+/// 执行 f 并收集其间在当前线程上记录的阶段耗时
+pub(crate) fn collect_stages<R>(f: impl FnOnce() -> R) -> (R, Vec<StageTiming>) {
+    let outer = STAGES.with(|stages| stages.borrow_mut().replace(Vec::new()));
+    let result = f();
+    let collected = STAGES.with(|stages| std::mem::replace(&mut *stages.borrow_mut(), outer));
+    (result, collected.unwrap_or_default())
+}
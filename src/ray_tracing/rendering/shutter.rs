@@ -0,0 +1,212 @@
+//! 快门效率曲线：相机光线的时间按快门在每个时刻透过的光量分布采样
+//!
+//! 真实快门的开合需要时间，运动模糊的拖影在两端逐渐变淡，而不是均匀采样时亮度处处相同的硬边。
+//! 曲线在快门区间 [0, 1] 上定义，只有形状有意义（按面积归一化为时间的概率密度），
+//! 时间按曲线的累积分布逆变换采样，每个样本的权重仍为1。
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// 快门效率曲线
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Shutter {
+    /// 瞬间开合，整个区间内效率相同（均匀采样）
+    #[default]
+    Box,
+    /// 梯形：开启和关闭各用区间的 ramp 比例（0 到 0.5）线性过渡，ramp 为 0.5 时是三角形
+    Trapezoid { ramp: f64 },
+    /// 正弦：效率为 sin(πt)，中间最亮、两端平滑地变暗
+    Sinusoidal,
+    /// 自定义的分段线性曲线
+    Custom(ShutterCurve),
+}
+
+impl Shutter {
+    /// 梯形快门
+    pub fn trapezoid(ramp: f64) -> Self {
+        Self::Trapezoid {
+            ramp: ramp.clamp(0.0, 0.5),
+        }
+    }
+
+    /// 时刻 t（0 到 1）的效率，最大值为1
+    pub fn efficiency(&self, t: f64) -> f64 {
+        if !(0.0..=1.0).contains(&t) {
+            return 0.0;
+        }
+        match self {
+            Self::Box => 1.0,
+            Self::Trapezoid { ramp } if *ramp > 0.0 => (t.min(1.0 - t) / ramp).min(1.0),
+            Self::Trapezoid { .. } => 1.0,
+            Self::Sinusoidal => (std::f64::consts::PI * t).sin(),
+            Self::Custom(curve) => curve.efficiency(t),
+        }
+    }
+
+    /// 按曲线把 [0, 1) 上的均匀随机数 u 映射为快门时间
+    pub fn sample(&self, u: f64) -> f64 {
+        let u = u.clamp(0.0, 1.0);
+        match self {
+            Self::Box => u,
+            Self::Trapezoid { ramp } if *ramp > 0.0 => sample_trapezoid(*ramp, u),
+            Self::Trapezoid { .. } => u,
+            Self::Sinusoidal => (1.0 - 2.0 * u).clamp(-1.0, 1.0).acos() / std::f64::consts::PI,
+            Self::Custom(curve) => curve.sample(u),
+        }
+    }
+
+    /// 快门的等效开启比例：效率曲线的面积（瞬间开合为1）
+    pub fn efficiency_ratio(&self) -> f64 {
+        match self {
+            Self::Box => 1.0,
+            Self::Trapezoid { ramp } => 1.0 - ramp,
+            Self::Sinusoidal => 2.0 / std::f64::consts::PI,
+            Self::Custom(curve) => curve.area / curve.peak(),
+        }
+    }
+}
+
+/// 梯形的逆累积分布：两端的斜坡各占面积 ramp/2，总面积 1 - ramp
+fn sample_trapezoid(ramp: f64, u: f64) -> f64 {
+    let target = u * (1.0 - ramp);
+    let ramp_area = 0.5 * ramp;
+    if target < ramp_area {
+        // 上升段：面积 t²/(2·ramp)
+        (2.0 * ramp * target).sqrt()
+    } else if target <= 1.0 - 1.5 * ramp {
+        target + 0.5 * ramp
+    } else {
+        // 下降段按对称性求解
+        1.0 - (2.0 * ramp * (1.0 - ramp - target).max(0.0)).sqrt()
+    }
+}
+
+/// 在 [0, 1] 上等间距的控制点之间线性插值的快门曲线
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShutterCurve {
+    values: Arc<[f64]>,
+    /// 各控制点处的累积面积
+    cdf: Arc<[f64]>,
+    area: f64,
+}
+
+impl ShutterCurve {
+    /// 由等间距的控制点创建，至少需要两个点、值非负且不全为0
+    pub fn new(values: &[f64]) -> Result<Self, String> {
+        if values.len() < 2 {
+            return Err("快门曲线至少需要两个控制点".to_string());
+        }
+        if values.iter().any(|v| !v.is_finite() || *v < 0.0) {
+            return Err("快门曲线的值必须是非负数".to_string());
+        }
+        let step = 1.0 / (values.len() - 1) as f64;
+        let mut cdf = Vec::with_capacity(values.len());
+        let mut area = 0.0;
+        cdf.push(0.0);
+        for pair in values.windows(2) {
+            area += 0.5 * (pair[0] + pair[1]) * step;
+            cdf.push(area);
+        }
+        if area <= 0.0 {
+            return Err("快门曲线的值不能全为0".to_string());
+        }
+        Ok(Self {
+            values: values.into(),
+            cdf: cdf.into(),
+            area,
+        })
+    }
+
+    /// 控制点
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    fn peak(&self) -> f64 {
+        self.values.iter().copied().fold(0.0, f64::max)
+    }
+
+    fn segments(&self) -> usize {
+        self.values.len() - 1
+    }
+
+    /// 时刻 t 的效率（按最大值归一化）
+    fn efficiency(&self, t: f64) -> f64 {
+        let x = t * self.segments() as f64;
+        let i = (x as usize).min(self.segments() - 1);
+        let f = x - i as f64;
+        ((1.0 - f) * self.values[i] + f * self.values[i + 1]) / self.peak()
+    }
+
+    /// 逆累积分布：先二分查找所在的段，再在段内解线性密度的二次方程
+    fn sample(&self, u: f64) -> f64 {
+        let target = u * self.area;
+        let i = self
+            .cdf
+            .partition_point(|&c| c <= target)
+            .clamp(1, self.segments())
+            - 1;
+        let step = 1.0 / self.segments() as f64;
+        let (a, b) = (self.values[i], self.values[i + 1]);
+        let remaining = (target - self.cdf[i]).max(0.0);
+        // 段内面积 a·x + (b - a)·x²/(2·step) = remaining，x 为段内的偏移
+        let slope = (b - a) / step;
+        let x = if slope.abs() < 1e-12 {
+            if a > 0.0 { remaining / a } else { 0.0 }
+        } else {
+            let discriminant = (a * a + 2.0 * slope * remaining).max(0.0);
+            (discriminant.sqrt() - a) / slope
+        };
+        (i as f64 * step + x.clamp(0.0, step)).min(1.0)
+    }
+}
+
+impl fmt::Display for Shutter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Box => write!(f, "box"),
+            Self::Trapezoid { ramp } => write!(f, "trapezoid:{}", ramp),
+            Self::Sinusoidal => write!(f, "sine"),
+            Self::Custom(curve) => {
+                let values: Vec<String> = curve.values.iter().map(|v| v.to_string()).collect();
+                write!(f, "curve:{}", values.join(","))
+            }
+        }
+    }
+}
+
+impl FromStr for Shutter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, param) = match s.split_once(':') {
+            Some((name, param)) => (name, Some(param)),
+            None => (s, None),
+        };
+        match (name.to_ascii_lowercase().as_str(), param) {
+            ("box" | "uniform", None) => Ok(Self::Box),
+            ("trapezoid", None) => Ok(Self::trapezoid(0.25)),
+            ("trapezoid", Some(ramp)) => match ramp.parse::<f64>() {
+                Ok(ramp) if (0.0..=0.5).contains(&ramp) => Ok(Self::trapezoid(ramp)),
+                _ => Err(format!("无效的梯形快门斜坡比例 `{}`（0 到 0.5）", ramp)),
+            },
+            ("sine" | "sinusoidal", None) => Ok(Self::Sinusoidal),
+            ("curve", Some(values)) => {
+                let values: Vec<f64> = values
+                    .split(',')
+                    .map(|v| {
+                        v.trim()
+                            .parse()
+                            .map_err(|_| format!("无效的快门曲线值 `{}`", v))
+                    })
+                    .collect::<Result<_, _>>()?;
+                ShutterCurve::new(&values).map(Self::Custom)
+            }
+            _ => Err(format!(
+                "未知的快门曲线 `{}`（可选: box | trapezoid[:斜坡比例] | sine | curve:v0,v1,...）",
+                s
+            )),
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! 渲染统计：按材质累计着色次数和反弹深度，找出主导渲染开销的材质
+
+use crate::ray_tracing::materials::material::Material;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// 报告中单独列出的材质实例数
+const TOP_INSTANCES: usize = 10;
+/// 占全部着色次数超过此比例的材质会被提示为主要开销
+const DOMINANT_SHARE: f64 = 0.25;
+/// 平均反弹深度超过此值的材质会被提示为深路径来源
+const DEEP_BOUNCE: f64 = 6.0;
+
+/// 单个材质（或材质类型）的统计
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MaterialStats {
+    /// 材质类型名
+    pub label: String,
+    /// 着色次数（光线命中该材质的表面）
+    pub hits: u64,
+    /// 相机光线直接命中的次数
+    pub primary_hits: u64,
+    /// 命中时所处反弹深度之和（相机光线为0）
+    pub bounce_sum: u64,
+    /// 继续散射（未被吸收）的次数
+    pub scatters: u64,
+}
+
+impl MaterialStats {
+    /// 命中时的平均反弹深度
+    #[inline]
+    pub fn average_bounce(&self) -> f64 {
+        if self.hits == 0 {
+            0.0
+        } else {
+            self.bounce_sum as f64 / self.hits as f64
+        }
+    }
+
+    fn merge(&mut self, other: &MaterialStats) {
+        self.hits += other.hits;
+        self.primary_hits += other.primary_hits;
+        self.bounce_sum += other.bounce_sum;
+        self.scatters += other.scatters;
+    }
+}
+
+/// 渲染统计收集器，设置到相机后在每次着色时记录
+///
+/// 按线程分片加锁，各线程只访问自己的分片，统计开销与线程数无关。
+/// 材质以 `Arc` 指针区分实例，同一材质被多个物体共享时合并计数。
+pub struct RenderStats {
+    shards: Vec<Mutex<HashMap<usize, MaterialStats>>>,
+}
+
+impl RenderStats {
+    /// 创建空的统计收集器
+    pub fn new() -> Self {
+        let shards = (0..rayon::current_num_threads() + 1)
+            .map(|_| Mutex::new(HashMap::new()))
+            .collect();
+        Self { shards }
+    }
+
+    /// 记录一次着色，bounce 为光线的反弹深度（相机光线为0）
+    pub fn record(&self, mat: &Arc<dyn Material>, bounce: u32, scattered: bool) {
+        let shard = rayon::current_thread_index().map_or(0, |i| i + 1) % self.shards.len();
+        let key = Arc::as_ptr(mat) as *const () as usize;
+        let mut map = self.shards[shard].lock().unwrap();
+        let entry = map.entry(key).or_insert_with(|| MaterialStats {
+            label: material_label(mat.as_ref()),
+            ..MaterialStats::default()
+        });
+        entry.hits += 1;
+        entry.bounce_sum += bounce as u64;
+        if bounce == 0 {
+            entry.primary_hits += 1;
+        }
+        if scattered {
+            entry.scatters += 1;
+        }
+    }
+
+    /// 清空已收集的统计
+    pub fn reset(&self) {
+        for shard in &self.shards {
+            shard.lock().unwrap().clear();
+        }
+    }
+
+    /// 按材质实例合并各分片的统计，按着色次数从多到少排序
+    pub fn per_material(&self) -> Vec<MaterialStats> {
+        let mut merged: HashMap<usize, MaterialStats> = HashMap::new();
+        for shard in &self.shards {
+            for (key, stats) in shard.lock().unwrap().iter() {
+                merged
+                    .entry(*key)
+                    .or_insert_with(|| MaterialStats {
+                        label: stats.label.clone(),
+                        ..MaterialStats::default()
+                    })
+                    .merge(stats);
+            }
+        }
+        let mut list: Vec<MaterialStats> = merged.into_values().collect();
+        list.sort_by_key(|s| std::cmp::Reverse(s.hits));
+        list
+    }
+
+    /// 按材质类型合并的统计，按着色次数从多到少排序
+    pub fn per_type(&self) -> Vec<MaterialStats> {
+        let mut merged: HashMap<String, MaterialStats> = HashMap::new();
+        for stats in self.per_material() {
+            merged
+                .entry(stats.label.clone())
+                .or_insert_with(|| MaterialStats {
+                    label: stats.label.clone(),
+                    ..MaterialStats::default()
+                })
+                .merge(&stats);
+        }
+        let mut list: Vec<MaterialStats> = merged.into_values().collect();
+        list.sort_by_key(|s| std::cmp::Reverse(s.hits));
+        list
+    }
+
+    /// 根据统计给出的优化建议
+    pub fn hints(&self) -> Vec<String> {
+        let types = self.per_type();
+        let total: u64 = types.iter().map(|s| s.hits).sum();
+        if total == 0 {
+            return Vec::new();
+        }
+
+        let mut hints = Vec::new();
+        for stats in &types {
+            let share = stats.hits as f64 / total as f64;
+            if share >= DOMINANT_SHARE {
+                hints.push(format!(
+                    "{} 占全部着色的 {:.1}%，是渲染开销的主要来源",
+                    stats.label,
+                    share * 100.0
+                ));
+            }
+            if stats.average_bounce() >= DEEP_BOUNCE {
+                hints.push(format!(
+                    "{} 平均在第 {:.1} 次反弹时被命中，路径很长；可以降低 max_depth，或减小介质密度/提高吸收",
+                    stats.label,
+                    stats.average_bounce()
+                ));
+            }
+        }
+
+        let instances = self.per_material();
+        if let Some(top) = instances.first()
+            && instances.len() > 1
+            && top.hits as f64 / total as f64 >= DOMINANT_SHARE
+        {
+            hints.push(format!(
+                "单个 {} 材质实例占 {:.1}% 的着色，优先简化使用它的物体",
+                top.label,
+                top.hits as f64 / total as f64 * 100.0
+            ));
+        }
+        hints
+    }
+}
+
+impl Default for RenderStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for RenderStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RenderStats")
+            .field("shards", &self.shards.len())
+            .finish()
+    }
+}
+
+impl std::fmt::Display for RenderStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let types = self.per_type();
+        let total: u64 = types.iter().map(|s| s.hits).sum();
+        let share = |hits: u64| hits as f64 / total.max(1) as f64 * 100.0;
+
+        writeln!(f, "渲染统计：共 {} 次着色", total)?;
+        writeln!(f, "按材质类型:")?;
+        for stats in &types {
+            writeln!(
+                f,
+                "  {:<20} 着色 {:>12} ({:>5.1}%)  直接可见 {:>10}  平均反弹 {:>5.2}  散射率 {:>5.1}%",
+                stats.label,
+                stats.hits,
+                share(stats.hits),
+                stats.primary_hits,
+                stats.average_bounce(),
+                stats.scatters as f64 / stats.hits.max(1) as f64 * 100.0
+            )?;
+        }
+
+        let instances = self.per_material();
+        writeln!(f, "着色最多的材质实例（共 {} 个实例）:", instances.len())?;
+        for (rank, stats) in instances.iter().take(TOP_INSTANCES).enumerate() {
+            writeln!(
+                f,
+                "  #{:<3} {:<20} 着色 {:>12} ({:>5.1}%)  平均反弹 {:>5.2}",
+                rank + 1,
+                stats.label,
+                stats.hits,
+                share(stats.hits),
+                stats.average_bounce()
+            )?;
+        }
+
+        let hints = self.hints();
+        if !hints.is_empty() {
+            writeln!(f, "优化建议:")?;
+            for hint in hints {
+                writeln!(f, "  - {}", hint)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 材质的类型名，取自其 Debug 输出的开头
+fn material_label(mat: &dyn Material) -> String {
+    let debug = format!("{:?}", mat);
+    let end = debug
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(debug.len());
+    if end == 0 {
+        "<未命名材质>".to_string()
+    } else {
+        debug[..end].to_string()
+    }
+}
@@ -0,0 +1,101 @@
+//! 路径终止策略：固定最大深度、按路径通量的俄罗斯轮盘赌，或两者结合
+//!
+//! 固定深度会截断长路径（有偏但耗时可控）；轮盘赌按路径通量（已累积的衰减）决定继续的概率，
+//! 存活的路径按 1/概率 补偿，期望值无偏。混合策略在轮盘赌之外保留最大深度作为硬上限。
+
+use crate::ray_tracing::math::vec3::Color;
+use std::fmt;
+use std::str::FromStr;
+
+/// 轮盘赌的最大存活概率，保证通量不衰减的路径（如白炉测试）也能终止
+pub const MAX_SURVIVAL: f64 = 0.95;
+
+/// 纯轮盘赌策略下的安全深度上限，防止极端情况下递归过深
+pub const ROULETTE_DEPTH_LIMIT: i32 = 256;
+
+/// 相机的路径终止策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TerminationPolicy {
+    /// 只在达到最大深度时终止
+    FixedDepth,
+    /// 前 min_bounces 次反弹后按路径通量做轮盘赌，忽略最大深度（仅保留安全上限）
+    Roulette { min_bounces: u32 },
+    /// 轮盘赌，同时在最大深度处截断
+    Hybrid { min_bounces: u32 },
+}
+
+impl Default for TerminationPolicy {
+    fn default() -> Self {
+        Self::Hybrid { min_bounces: 3 }
+    }
+}
+
+impl TerminationPolicy {
+    /// 实际使用的深度上限
+    #[inline]
+    pub fn depth_limit(&self, max_depth: i32) -> i32 {
+        match self {
+            Self::Roulette { .. } => ROULETTE_DEPTH_LIMIT,
+            Self::FixedDepth | Self::Hybrid { .. } => max_depth,
+        }
+    }
+
+    /// 第 bounce 次反弹（从0开始）后路径继续的概率，throughput 为包含本次散射的路径通量
+    #[inline]
+    pub fn survival(&self, bounce: u32, throughput: &Color) -> f64 {
+        match *self {
+            Self::FixedDepth => 1.0,
+            Self::Roulette { min_bounces } | Self::Hybrid { min_bounces } => {
+                if bounce < min_bounces {
+                    1.0
+                } else {
+                    throughput.max().clamp(0.0, MAX_SURVIVAL)
+                }
+            }
+        }
+    }
+}
+
+/// 解析 `fixed`、`roulette`、`hybrid`，后两者可用 `:N` 指定最少反弹次数（如 `roulette:5`）
+impl FromStr for TerminationPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_ascii_lowercase();
+        let (name, min_bounces) = match lower.split_once(':') {
+            Some((name, n)) => {
+                let n = n
+                    .trim()
+                    .parse::<u32>()
+                    .map_err(|_| format!("无效的最少反弹次数 `{}`", n))?;
+                (name.trim(), Some(n))
+            }
+            None => (lower.trim(), None),
+        };
+        let default_min = 3;
+        match (name, min_bounces) {
+            ("fixed", None) => Ok(Self::FixedDepth),
+            ("roulette", n) => Ok(Self::Roulette {
+                min_bounces: n.unwrap_or(default_min),
+            }),
+            ("hybrid", n) => Ok(Self::Hybrid {
+                min_bounces: n.unwrap_or(default_min),
+            }),
+            ("fixed", Some(_)) => Err("`fixed` 不接受最少反弹次数".to_string()),
+            _ => Err(format!(
+                "未知的终止策略 `{}`（可选: fixed, roulette[:N], hybrid[:N]）",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for TerminationPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FixedDepth => f.write_str("fixed"),
+            Self::Roulette { min_bounces } => write!(f, "roulette:{}", min_bounces),
+            Self::Hybrid { min_bounces } => write!(f, "hybrid:{}", min_bounces),
+        }
+    }
+}
@@ -0,0 +1,368 @@
+//! 分块结果缓存：只修改少数材质或裁剪区域时复用未受影响分块的渲染结果，加快大图的调整迭代
+//!
+//! 每个16×16分块记录渲染时光线经过的物体（见 [`TrackedObject`]）作为依赖，连同依赖物体的版本号
+//! 一起保存分块的溅射缓冲区。下次渲染时，分块键（相机设置、场景结构哈希、分块位置）相同且所有依赖物体的
+//! 版本号不变时直接复用缓存，否则重新渲染。
+//!
+//! - 场景结构哈希覆盖物体的标识、包围盒和可见性，光源条目，光源链接和加速结构类型。
+//!   增删物体或物体的包围盒变化时所有分块失效（新的几何可能出现在任何分块中）。
+//! - 物体通过 `Scene::replace` 替换时获得新的版本号。包围盒不变的替换（换材质、调整粗糙度、
+//!   改纹理）只使光线经过其包围盒的分块失效。
+//! - 光线经过包围盒即记为依赖（不要求命中），包围盒内几何的变化也能被正确检测。
+//!
+//! 相机设置的哈希由其 `Debug` 输出计算。通过内部可变性修改物体（不经过 `replace`）不会被察觉；
+//! 启用辐照度缓存或焦散光子图时这些全局结构依赖整个场景，不使用分块缓存。
+//! 缓存只保存在内存中，适合在同一进程内反复修改场景并重新渲染（见 [`Scene::render_cached`]）。
+//!
+//! [`Scene::render_cached`]: crate::ray_tracing::scene::world::Scene::render_cached
+
+use super::camera::Camera;
+use super::filter::SplatTile;
+use super::report::fnv1a64;
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable, UvTriangle};
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::{Color, Point3, Vec3};
+use crate::ray_tracing::scene::world::ObjectId;
+use crate::ray_tracing::utils::random::hash_seed;
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::{self, Write};
+use std::sync::{Arc, Mutex};
+
+/// 图像上的矩形像素区域 [x0, x1) × [y0, y1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crop {
+    pub x0: i32,
+    pub y0: i32,
+    pub x1: i32,
+    pub y1: i32,
+}
+
+impl Crop {
+    /// 创建区域，坐标按大小排序
+    pub fn new(x0: i32, y0: i32, x1: i32, y1: i32) -> Self {
+        Self {
+            x0: x0.min(x1),
+            y0: y0.min(y1),
+            x1: x0.max(x1),
+            y1: y0.max(y1),
+        }
+    }
+
+    /// 是否与 [x0, x1) × [y0, y1) 重叠
+    #[inline]
+    pub fn overlaps(&self, x0: i32, y0: i32, x1: i32, y1: i32) -> bool {
+        self.x0 < x1 && x0 < self.x1 && self.y0 < y1 && y0 < self.y1
+    }
+}
+
+/// 场景的摘要：结构哈希与每个物体的版本号
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SceneSignature {
+    /// 物体集合、包围盒、光源和链接的哈希，变化时所有分块失效
+    pub structure: u64,
+    /// 参与渲染的物体的版本号
+    pub objects: HashMap<ObjectId, u64>,
+}
+
+/// 最近一次缓存渲染的分块统计
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TileCacheStats {
+    /// 直接复用缓存的分块数
+    pub reused: usize,
+    /// 重新渲染的分块数
+    pub rendered: usize,
+    /// 位于裁剪区域之外、没有缓存结果而留空的分块数
+    pub skipped: usize,
+}
+
+/// 分块结果缓存，克隆后共享同一份缓存
+#[derive(Clone, Default)]
+pub struct TileCache {
+    state: Arc<Mutex<CacheState>>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    tiles: HashMap<(i32, i32), CachedTile>,
+    frame: Option<Frame>,
+    stats: TileCacheStats,
+}
+
+/// 当前渲染的相机键、场景摘要和裁剪区域
+struct Frame {
+    key: u64,
+    signature: SceneSignature,
+    crop: Option<Crop>,
+}
+
+struct CachedTile {
+    key: u64,
+    /// 渲染时经过的物体及其版本号
+    dependencies: Vec<(ObjectId, u64)>,
+    tile: SplatTile,
+}
+
+/// 分块的查询结果
+pub(crate) enum TileLookup {
+    /// 缓存有效（或位于裁剪区域之外的旧结果），直接使用
+    Cached(SplatTile),
+    /// 位于裁剪区域之外且没有缓存，留空
+    Skip,
+    /// 需要渲染，渲染后以该键保存
+    Render(u64),
+}
+
+impl TileCache {
+    /// 创建空缓存
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 缓存的分块数
+    pub fn len(&self) -> usize {
+        self.lock().tiles.len()
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 清空缓存
+    pub fn clear(&self) {
+        self.lock().tiles.clear();
+    }
+
+    /// 最近一次渲染的分块统计
+    pub fn stats(&self) -> TileCacheStats {
+        self.lock().stats
+    }
+
+    /// 开始新的一帧：记录相机、场景摘要和裁剪区域，清零统计
+    pub(crate) fn begin_frame(
+        &self,
+        camera: &Camera,
+        signature: SceneSignature,
+        crop: Option<Crop>,
+    ) {
+        let key = hash_seed(&[camera_key(camera), signature.structure]);
+        let mut state = self.lock();
+        state.frame = Some(Frame {
+            key,
+            signature,
+            crop,
+        });
+        state.stats = TileCacheStats::default();
+    }
+
+    /// 查询起点为 (x0, y0)、范围到 (x1, y1) 的分块；extra 为分块内随场景变化的采样参数（如景深网格）的哈希
+    pub(crate) fn lookup(&self, x0: i32, y0: i32, x1: i32, y1: i32, extra: u64) -> TileLookup {
+        let mut state = self.lock();
+        let state = &mut *state;
+        let Some(frame) = &state.frame else {
+            return TileLookup::Render(0);
+        };
+        let key = hash_seed(&[frame.key, x0 as u64, y0 as u64, extra]);
+        let cached = state.tiles.get(&(x0, y0));
+
+        if let Some(crop) = frame.crop
+            && !crop.overlaps(x0, y0, x1, y1)
+        {
+            return match cached {
+                Some(entry) => {
+                    state.stats.reused += 1;
+                    TileLookup::Cached(entry.tile.clone())
+                }
+                None => {
+                    state.stats.skipped += 1;
+                    TileLookup::Skip
+                }
+            };
+        }
+
+        if let Some(entry) = cached
+            && entry.key == key
+            && entry
+                .dependencies
+                .iter()
+                .all(|(id, hash)| frame.signature.objects.get(id) == Some(hash))
+        {
+            state.stats.reused += 1;
+            return TileLookup::Cached(entry.tile.clone());
+        }
+        state.stats.rendered += 1;
+        TileLookup::Render(key)
+    }
+
+    /// 保存渲染完成的分块及其依赖
+    pub(crate) fn store(
+        &self,
+        x0: i32,
+        y0: i32,
+        key: u64,
+        dependencies: BTreeSet<ObjectId>,
+        tile: SplatTile,
+    ) {
+        let mut state = self.lock();
+        let Some(frame) = &state.frame else {
+            return;
+        };
+        let dependencies = dependencies
+            .into_iter()
+            .filter_map(|id| frame.signature.objects.get(&id).map(|hash| (id, *hash)))
+            .collect();
+        state.tiles.insert(
+            (x0, y0),
+            CachedTile {
+                key,
+                dependencies,
+                tile,
+            },
+        );
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl fmt::Debug for TileCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TileCache")
+            .field("tiles", &self.len())
+            .finish()
+    }
+}
+
+/// 把格式化输出直接累积为 FNV-1a 哈希，不分配完整的字符串
+struct HashWriter(u64);
+
+impl Write for HashWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 = s
+            .bytes()
+            .fold(self.0, |h, b| (h ^ b as u64).wrapping_mul(0x0100_0000_01b3));
+        Ok(())
+    }
+}
+
+/// 值的 `Debug` 输出的哈希
+pub fn debug_hash(value: &impl fmt::Debug) -> u64 {
+    let mut writer = HashWriter(fnv1a64(&[]));
+    let _ = write!(writer, "{:?}", value);
+    writer.0
+}
+
+/// 影响像素结果的相机设置的哈希（不含输出文件名、报告和统计等）
+fn camera_key(camera: &Camera) -> u64 {
+    let mut camera = camera.clone();
+    camera.set_output_filename(String::new());
+    camera.set_report(false);
+    camera.set_stats(None);
+    debug_hash(&camera)
+}
+
+thread_local! {
+    /// 当前线程正在渲染的分块经过的物体
+    static DEPENDENCIES: RefCell<Option<BTreeSet<ObjectId>>> = const { RefCell::new(None) };
+}
+
+/// 执行 f 并收集其间当前线程上的光线经过的物体
+pub(crate) fn record_dependencies(f: impl FnOnce()) -> BTreeSet<ObjectId> {
+    let previous = DEPENDENCIES.with(|d| d.borrow_mut().replace(BTreeSet::new()));
+    f();
+    DEPENDENCIES
+        .with(|d| std::mem::replace(&mut *d.borrow_mut(), previous))
+        .unwrap_or_default()
+}
+
+/// 记录光线经过的场景物体的包装：光线与包围盒相交时把物体标识记入当前分块的依赖
+pub struct TrackedObject {
+    id: ObjectId,
+    object: Arc<dyn Hittable>,
+    bbox: Option<Aabb>,
+}
+
+impl TrackedObject {
+    /// 包装物体
+    pub fn new(id: ObjectId, object: Arc<dyn Hittable>) -> Self {
+        let bbox = object.bounding_box();
+        Self { id, object, bbox }
+    }
+
+    /// 被包装的物体
+    #[inline]
+    pub fn object(&self) -> &Arc<dyn Hittable> {
+        &self.object
+    }
+
+    #[inline]
+    fn record(&self, r: &Ray, ray_t: Interval) {
+        if self.bbox.is_none_or(|bbox| bbox.hit(r, ray_t)) {
+            DEPENDENCIES.with(|d| {
+                if let Some(set) = d.borrow_mut().as_mut() {
+                    set.insert(self.id);
+                }
+            });
+        }
+    }
+}
+
+impl Hittable for TrackedObject {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        self.record(r, ray_t);
+        self.object.hit(r, ray_t, rec)
+    }
+
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        self.record(r, ray_t);
+        self.object.hit_any(r, ray_t)
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.bbox
+    }
+
+    #[inline]
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3, time: f64) -> f64 {
+        self.object.pdf_value(origin, direction, time)
+    }
+
+    #[inline]
+    fn random(&self, origin: &Point3, time: f64) -> Vec3 {
+        self.object.random(origin, time)
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        self.object.area()
+    }
+
+    #[inline]
+    fn power(&self) -> Color {
+        self.object.power()
+    }
+
+    #[inline]
+    fn uv_triangles(&self) -> Vec<UvTriangle> {
+        self.object.uv_triangles()
+    }
+
+    fn collect_debug_boxes(&self, depth: usize, leaves_only: bool, boxes: &mut Vec<(Aabb, usize)>) {
+        self.object.collect_debug_boxes(depth, leaves_only, boxes);
+    }
+}
+
+impl fmt::Debug for TrackedObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrackedObject")
+            .field("id", &self.id)
+            .field("object", &self.object)
+            .finish()
+    }
+}
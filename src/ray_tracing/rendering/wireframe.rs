@@ -0,0 +1,73 @@
+use super::color::hsv_to_rgb;
+use super::framebuffer::FrameBuffer;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::vec3::{Color, Point3};
+
+/// 包围盒线框叠加模式，用于检查BVH质量和变换后的包围盒
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BoundsOverlay {
+    /// 不绘制包围盒
+    #[default]
+    Off,
+    /// 仅绘制叶子物体的包围盒
+    Leaves,
+    /// 绘制所有BVH节点及叶子的包围盒
+    All,
+}
+
+/// 返回包围盒的12条边（每条边为两个端点）
+pub fn box_edges(bbox: &Aabb) -> [(Point3, Point3); 12] {
+    let corner = |i: usize| {
+        Point3::new(
+            if i & 1 == 0 { bbox.x.min } else { bbox.x.max },
+            if i & 2 == 0 { bbox.y.min } else { bbox.y.max },
+            if i & 4 == 0 { bbox.z.min } else { bbox.z.max },
+        )
+    };
+
+    [
+        (corner(0), corner(1)),
+        (corner(2), corner(3)),
+        (corner(4), corner(5)),
+        (corner(6), corner(7)),
+        (corner(0), corner(2)),
+        (corner(1), corner(3)),
+        (corner(4), corner(6)),
+        (corner(5), corner(7)),
+        (corner(0), corner(4)),
+        (corner(1), corner(5)),
+        (corner(2), corner(6)),
+        (corner(3), corner(7)),
+    ]
+}
+
+/// 根据层级深度选择线框颜色（色相随深度循环，返回线性颜色）
+pub fn depth_color(depth: usize) -> Color {
+    let (r, g, b) = hsv_to_rgb(depth as f64 * 0.13, 0.9, 1.0);
+    // 转换回线性空间，使伽马校正后显示为饱和色
+    Color::new(r * r, g * g, b * b)
+}
+
+/// 在帧缓冲区上绘制线段（DDA算法，超出图像的部分被忽略）
+pub fn draw_line(fb: &mut FrameBuffer, x0: f64, y0: f64, x1: f64, y1: f64, color: Color) {
+    let dx = x1 - x0;
+    let dy = y1 - y0;
+    let steps = dx.abs().max(dy.abs()).ceil().max(1.0);
+
+    // 避免极长的线段（投影到近平面附近时）拖慢绘制
+    if steps > 100_000.0 {
+        return;
+    }
+
+    let (width, height) = (fb.width() as f64, fb.height() as f64);
+    let steps_count = steps as i64;
+    for s in 0..=steps_count {
+        let t = s as f64 / steps;
+        let x = (x0 + dx * t).floor();
+        let y = (y0 + dy * t).floor();
+        if x >= 0.0 && y >= 0.0 && x < width && y < height {
+            fb.set(x as u32, y as u32, color);
+            fb.set_alpha(x as u32, y as u32, 1.0);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! 蓝噪声遮罩与低差异序列
+//!
+//! 遮罩用 void-and-cluster 算法（Ulichney 1993）生成：相邻像素的取值尽量不同，
+//! 用作每像素的 Cranley-Patterson 旋转时，误差表现为高频噪声，比白噪声在同样样本数下更平滑。
+
+use crate::ray_tracing::utils::random::Pcg32;
+use std::sync::OnceLock;
+
+/// 遮罩边长（平铺使用）
+pub const BLUE_NOISE_SIZE: usize = 64;
+
+/// 能量函数的高斯标准差（像素）
+const SIGMA: f64 = 1.5;
+
+/// 初始二值图案中点的比例
+const INITIAL_DENSITY: f64 = 0.1;
+
+/// 生成遮罩使用的随机种子
+const SEED: u64 = 0x626c_7565;
+
+static MASK: OnceLock<Vec<f32>> = OnceLock::new();
+
+/// 64×64 蓝噪声遮罩，按行存储，取值为 [0,1) 内均匀分布的等级
+pub fn blue_noise_mask() -> &'static [f32] {
+    MASK.get_or_init(generate)
+}
+
+/// 像素 (x, y) 处的蓝噪声值，不同通道按固定偏移平铺遮罩以去相关
+#[inline]
+pub fn blue_noise(x: i32, y: i32, channel: u32) -> f64 {
+    let n = BLUE_NOISE_SIZE as i32;
+    let x = (x + channel as i32 * 29).rem_euclid(n) as usize;
+    let y = (y + channel as i32 * 47).rem_euclid(n) as usize;
+    blue_noise_mask()[y * BLUE_NOISE_SIZE + x] as f64
+}
+
+/// R2 低差异序列的第 index 个点（Roberts 2018，基于广义黄金分割比）
+#[inline]
+pub fn r2(index: u32) -> (f64, f64) {
+    const G: f64 = 1.324_717_957_244_746;
+    let i = index as f64 + 1.0;
+    ((0.5 + i / G).fract(), (0.5 + i / (G * G)).fract())
+}
+
+/// 平铺网格上的高斯能量场
+#[derive(Clone)]
+struct Energy {
+    kernel: Vec<f64>,
+    values: Vec<f64>,
+}
+
+impl Energy {
+    fn new() -> Self {
+        let n = BLUE_NOISE_SIZE;
+        // 环绕距离下的高斯核，kernel[dy * n + dx]
+        let kernel = (0..n * n)
+            .map(|i| {
+                let wrap = |d: usize| d.min(n - d) as f64;
+                let (dx, dy) = (wrap(i % n), wrap(i / n));
+                (-(dx * dx + dy * dy) / (2.0 * SIGMA * SIGMA)).exp()
+            })
+            .collect();
+        Self {
+            kernel,
+            values: vec![0.0; n * n],
+        }
+    }
+
+    /// 在 index 处加入（sign=1）或移除（sign=-1）一个点
+    fn splat(&mut self, index: usize, sign: f64) {
+        let n = BLUE_NOISE_SIZE;
+        let (px, py) = (index % n, index / n);
+        for y in 0..n {
+            let dy = (y + n - py) % n;
+            for x in 0..n {
+                let dx = (x + n - px) % n;
+                self.values[y * n + x] += sign * self.kernel[dy * n + dx];
+            }
+        }
+    }
+
+    /// 最紧密的簇：已占据位置中能量最大的
+    fn tightest_cluster(&self, pattern: &[bool]) -> usize {
+        (0..pattern.len())
+            .filter(|&i| pattern[i])
+            .max_by(|&a, &b| self.values[a].total_cmp(&self.values[b]))
+            .expect("图案中没有点")
+    }
+
+    /// 最大的空隙：空位置中能量最小的
+    fn largest_void(&self, pattern: &[bool]) -> usize {
+        (0..pattern.len())
+            .filter(|&i| !pattern[i])
+            .min_by(|&a, &b| self.values[a].total_cmp(&self.values[b]))
+            .expect("图案已满")
+    }
+}
+
+/// void-and-cluster：先把随机初始图案松弛为均匀分布，再依次移除/加入点得到每个位置的等级
+fn generate() -> Vec<f32> {
+    let count = BLUE_NOISE_SIZE * BLUE_NOISE_SIZE;
+    let mut rng = Pcg32::new(SEED);
+    let mut pattern = vec![false; count];
+    let initial = ((count as f64) * INITIAL_DENSITY) as usize;
+    let mut placed = 0;
+    while placed < initial {
+        let index = rng.next_u32() as usize % count;
+        if !pattern[index] {
+            pattern[index] = true;
+            placed += 1;
+        }
+    }
+
+    let mut energy = Energy::new();
+    for index in (0..count).filter(|&i| pattern[i]) {
+        energy.splat(index, 1.0);
+    }
+
+    // 松弛：把最紧密簇中的点移到最大空隙，直到移除的点就是加入的点（通常远少于 count 次）
+    for _ in 0..count {
+        let cluster = energy.tightest_cluster(&pattern);
+        pattern[cluster] = false;
+        energy.splat(cluster, -1.0);
+        let void = energy.largest_void(&pattern);
+        pattern[void] = true;
+        energy.splat(void, 1.0);
+        if void == cluster {
+            break;
+        }
+    }
+
+    let mut rank = vec![0usize; count];
+
+    // 阶段1：从初始图案中依次移除最紧密的簇，等级从 initial-1 递减
+    let mut removing = pattern.clone();
+    let mut removing_energy = energy.clone();
+    for r in (0..initial).rev() {
+        let cluster = removing_energy.tightest_cluster(&removing);
+        removing[cluster] = false;
+        removing_energy.splat(cluster, -1.0);
+        rank[cluster] = r;
+    }
+
+    // 阶段2：从初始图案开始依次填充最大的空隙，等级递增直到填满
+    for r in initial..count {
+        let void = energy.largest_void(&pattern);
+        pattern[void] = true;
+        energy.splat(void, 1.0);
+        rank[void] = r;
+    }
+
+    rank.into_iter()
+        .map(|r| ((r as f64 + 0.5) / count as f64) as f32)
+        .collect()
+}
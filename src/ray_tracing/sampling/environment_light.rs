@@ -0,0 +1,224 @@
+//! 环境光源：按亮度重要性采样背景（环境贴图、物理天空等）的方向
+//!
+//! 构建时在经纬度网格上对背景求值，按亮度乘 sinθ 建立分段常数的二维分布（边缘分布选行，条件分布选列），
+//! 明亮的太阳或窗户在方向空间中只占很小的立体角，按材质采样很少命中，按此分布采样则集中在这些方向上。
+//! 环境光源只参与采样、不加入场景：朝采样方向发出的光线未命中任何物体时照常取背景颜色，估计无偏。
+//! 它和面光源、点光源放在同一个光源列表中按权重选择（见 [`Scene::add_environment_light`]），
+//! 光源列表的 PDF 是各光源 PDF 按选择概率的加权和，与材质PDF混合后即为多重重要性采样的平衡启发式。
+//!
+//! 方向到纹理坐标的映射与 `Sphere::get_sphere_uv` 一致。
+//!
+//! [`Scene::add_environment_light`]: crate::ray_tracing::scene::world::Scene::add_environment_light
+
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::rendering::background::Background;
+use crate::ray_tracing::rendering::color::luminance;
+use crate::ray_tracing::utils::random::random_double;
+use std::f64::consts::PI;
+
+/// 分布网格的默认宽度（高度为宽度的一半）
+pub const DEFAULT_WIDTH: usize = 512;
+/// 每个网格单元在每个方向上的求值次数，使太阳等小于单元的亮斑也能被发现
+const SUBSAMPLES: usize = 2;
+
+/// 一维分段常数分布
+#[derive(Debug, Clone)]
+struct Distribution1D {
+    /// 各段的函数值
+    func: Vec<f64>,
+    /// 归一化的累积分布，长度为段数 + 1
+    cdf: Vec<f64>,
+    /// 函数在 [0,1] 上的积分
+    integral: f64,
+}
+
+impl Distribution1D {
+    /// 函数值全为零时退化为均匀分布
+    fn new(func: Vec<f64>) -> Self {
+        let n = func.len() as f64;
+        let mut cdf = Vec::with_capacity(func.len() + 1);
+        cdf.push(0.0);
+        for f in &func {
+            cdf.push(cdf.last().unwrap_or(&0.0) + f / n);
+        }
+        let integral = cdf.last().copied().unwrap_or(0.0);
+        if integral > 0.0 {
+            cdf.iter_mut().for_each(|c| *c /= integral);
+        } else {
+            cdf.iter_mut()
+                .enumerate()
+                .for_each(|(i, c)| *c = i as f64 / n);
+        }
+        Self {
+            func,
+            cdf,
+            integral,
+        }
+    }
+
+    #[inline]
+    fn count(&self) -> usize {
+        self.func.len()
+    }
+
+    /// 第 i 段的概率密度（相对于 [0,1] 上的均匀测度）
+    #[inline]
+    fn density(&self, i: usize) -> f64 {
+        if self.integral > 0.0 {
+            self.func[i] / self.integral
+        } else {
+            1.0
+        }
+    }
+
+    /// 采样，返回 ([0,1) 上的连续位置, 所在段)
+    fn sample(&self, xi: f64) -> (f64, usize) {
+        let i = self
+            .cdf
+            .partition_point(|&c| c <= xi)
+            .saturating_sub(1)
+            .min(self.count() - 1);
+        let width = self.cdf[i + 1] - self.cdf[i];
+        let offset = if width > 0.0 {
+            (xi - self.cdf[i]) / width
+        } else {
+            0.5
+        };
+        (
+            ((i as f64 + offset) / self.count() as f64).min(1.0 - 1e-12),
+            i,
+        )
+    }
+}
+
+/// 按亮度重要性采样的环境光源
+#[derive(Debug, Clone)]
+pub struct EnvironmentLight {
+    width: usize,
+    height: usize,
+    /// 各行内按列的条件分布
+    rows: Vec<Distribution1D>,
+    /// 按行的边缘分布
+    marginal: Distribution1D,
+    /// 所有方向上的入射辐射度积分 ∫L dω
+    integrated_radiance: Color,
+    /// 场景包围球半径，用于估计功率
+    scene_radius: f64,
+}
+
+impl EnvironmentLight {
+    /// 使用默认分辨率构建，scene_radius 为场景包围球的半径
+    pub fn new(background: &dyn Background, scene_radius: f64) -> Self {
+        Self::with_resolution(background, scene_radius, DEFAULT_WIDTH)
+    }
+
+    /// 指定分布网格的宽度构建（高度为宽度的一半）
+    pub fn with_resolution(background: &dyn Background, scene_radius: f64, width: usize) -> Self {
+        let width = width.max(2);
+        let height = width / 2;
+        let cell = |i: usize, j: usize| -> Color {
+            let mut sum = Color::zeros();
+            for sy in 0..SUBSAMPLES {
+                for sx in 0..SUBSAMPLES {
+                    let u = (i as f64 + (sx as f64 + 0.5) / SUBSAMPLES as f64) / width as f64;
+                    let v = (j as f64 + (sy as f64 + 0.5) / SUBSAMPLES as f64) / height as f64;
+                    let ray = Ray::new(Point3::origin(), uv_direction(u, v), 0.0);
+                    sum += background.value(&ray).map(|c| c.max(0.0));
+                }
+            }
+            sum / (SUBSAMPLES * SUBSAMPLES) as f64
+        };
+
+        let solid_angle = 2.0 * PI * PI / (width * height) as f64;
+        let mut integrated_radiance = Color::zeros();
+        let rows: Vec<Distribution1D> = (0..height)
+            .map(|j| {
+                let sin_theta = ((j as f64 + 0.5) / height as f64 * PI).sin();
+                let func = (0..width)
+                    .map(|i| {
+                        let radiance = cell(i, j);
+                        integrated_radiance += radiance * sin_theta * solid_angle;
+                        luminance(&radiance).max(0.0) * sin_theta
+                    })
+                    .collect();
+                Distribution1D::new(func)
+            })
+            .collect();
+        let marginal = Distribution1D::new(rows.iter().map(|row| row.integral).collect());
+
+        Self {
+            width,
+            height,
+            rows,
+            marginal,
+            integrated_radiance,
+            scene_radius: scene_radius.max(0.0),
+        }
+    }
+
+    /// 所有方向上的入射辐射度积分 ∫L dω（均匀环境为 4πL）
+    #[inline]
+    pub fn integrated_radiance(&self) -> Color {
+        self.integrated_radiance
+    }
+
+    /// 单位方向的立体角概率密度
+    fn direction_pdf(&self, direction: &Vec3) -> f64 {
+        let (u, v) = direction_uv(direction);
+        let sin_theta = (v * PI).sin();
+        if sin_theta <= 0.0 {
+            return 0.0;
+        }
+        let i = ((u * self.width as f64) as usize).min(self.width - 1);
+        let j = ((v * self.height as f64) as usize).min(self.height - 1);
+        let pdf_uv = self.marginal.density(j) * self.rows[j].density(i);
+        pdf_uv / (2.0 * PI * PI * sin_theta)
+    }
+}
+
+/// 纹理坐标对应的单位方向（`Sphere::get_sphere_uv` 的逆映射）
+fn uv_direction(u: f64, v: f64) -> Vec3 {
+    let theta = v * PI;
+    let phi = u * 2.0 * PI - PI;
+    Vec3::new(
+        theta.sin() * phi.cos(),
+        -theta.cos(),
+        -theta.sin() * phi.sin(),
+    )
+}
+
+/// 单位方向对应的纹理坐标
+fn direction_uv(d: &Vec3) -> (f64, f64) {
+    let theta = (-d.y).clamp(-1.0, 1.0).acos();
+    let phi = (-d.z).atan2(d.x) + PI;
+    ((phi / (2.0 * PI)).clamp(0.0, 1.0), theta / PI)
+}
+
+impl Hittable for EnvironmentLight {
+    /// 环境位于无穷远处，不与任何光线相交
+    #[inline]
+    fn hit(&self, _r: &Ray, _ray_t: Interval, _rec: &mut HitRecord) -> bool {
+        false
+    }
+
+    fn pdf_value(&self, _origin: &Point3, direction: &Vec3, _time: f64) -> f64 {
+        if direction.norm_squared() == 0.0 {
+            return 0.0;
+        }
+        self.direction_pdf(&direction.normalize())
+    }
+
+    fn random(&self, _origin: &Point3, _time: f64) -> Vec3 {
+        let (v, j) = self.marginal.sample(random_double());
+        let (u, _) = self.rows[j].sample(random_double());
+        uv_direction(u, v)
+    }
+
+    /// 照到场景包围球上的总功率 πr²∫L dω，与面光源的发光功率可以直接比较
+    fn power(&self) -> Color {
+        PI * self.scene_radius * self.scene_radius * self.integrated_radiance
+    }
+}
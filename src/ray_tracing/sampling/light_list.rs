@@ -0,0 +1,149 @@
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable, UvTriangle};
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::rendering::color::luminance;
+use crate::ray_tracing::utils::random::random_double;
+use std::sync::Arc;
+
+/// 带重要性权重的光源列表，按权重比例选择光源进行采样
+pub struct LightList {
+    lights: Vec<(Arc<dyn Hittable>, f64)>,
+    total_weight: f64,
+    bbox: Aabb,
+}
+
+impl LightList {
+    /// 创建空列表
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            lights: Vec::new(),
+            total_weight: 0.0,
+            bbox: Aabb::empty(),
+        }
+    }
+
+    /// 添加光源，权重不大于0的光源会被忽略
+    pub fn add(&mut self, light: Arc<dyn Hittable>, weight: f64) {
+        if weight <= 0.0 {
+            return;
+        }
+        if let Some(light_bbox) = light.bounding_box() {
+            self.bbox = self.bbox.merge(&light_bbox);
+        }
+        self.total_weight += weight;
+        self.lights.push((light, weight));
+    }
+
+    /// 按发光功率的亮度作为权重添加光源；功率为0（如未设置发光材质的采样形状）时退回按面积加权
+    pub fn add_by_power(&mut self, light: Arc<dyn Hittable>) {
+        let power = luminance(&light.power());
+        let weight = if power > 0.0 { power } else { light.area() };
+        self.add(light, weight);
+    }
+
+    /// 光源数量
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.lights.len()
+    }
+
+    /// 检查是否为空
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.lights.is_empty()
+    }
+}
+
+impl Default for LightList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hittable for LightList {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let mut hit_anything = false;
+        let mut closest_so_far = ray_t.max;
+        let mut temp_rec = HitRecord::default();
+
+        for (light, _) in &self.lights {
+            if light.hit(r, Interval::new(ray_t.min, closest_so_far), &mut temp_rec) {
+                hit_anything = true;
+                closest_so_far = temp_rec.t;
+                std::mem::swap(rec, &mut temp_rec);
+            }
+        }
+
+        hit_anything
+    }
+
+    fn hit_any(&self, r: &Ray, ray_t: Interval) -> bool {
+        self.lights.iter().any(|(light, _)| light.hit_any(r, ray_t))
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.bbox)
+        }
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3, time: f64) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+
+        self.lights
+            .iter()
+            .map(|(light, weight)| {
+                weight / self.total_weight * light.pdf_value(origin, direction, time)
+            })
+            .sum()
+    }
+
+    fn random(&self, origin: &Point3, time: f64) -> Vec3 {
+        if self.is_empty() {
+            return Vec3::new(1.0, 0.0, 0.0);
+        }
+
+        // 按权重选择光源
+        let mut target = random_double() * self.total_weight;
+        for (light, weight) in &self.lights {
+            if target < *weight {
+                return light.random(origin, time);
+            }
+            target -= weight;
+        }
+        self.lights[self.lights.len() - 1].0.random(origin, time)
+    }
+
+    fn area(&self) -> f64 {
+        self.lights.iter().map(|(light, _)| light.area()).sum()
+    }
+
+    fn power(&self) -> Color {
+        self.lights.iter().map(|(light, _)| light.power()).sum()
+    }
+
+    fn uv_triangles(&self) -> Vec<UvTriangle> {
+        self.lights
+            .iter()
+            .flat_map(|(light, _)| light.uv_triangles())
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for LightList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LightList")
+            .field("lights", &format!("{} lights", self.lights.len()))
+            .field("total_weight", &self.total_weight)
+            .field("bbox", &self.bbox)
+            .finish()
+    }
+}
@@ -1 +1,5 @@
+pub mod blue_noise;
+pub mod environment_light;
+pub mod light_list;
 pub mod pdf;
+pub mod portal;
@@ -0,0 +1,94 @@
+use super::{PDF, ScatterPDF};
+use crate::ray_tracing::materials::microfacet::{sample_ggx_vndf, vndf_pdf};
+use crate::ray_tracing::math::onb::ONB;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::random::random_double;
+
+/// 清漆层材质的方向采样PDF：GGX 反射波瓣与底层材质PDF的混合
+///
+/// 以 coat_probability 的概率按可见法线分布采样清漆层的反射方向，否则使用底层材质的PDF；
+/// 底层为镜面或吸收了光线时只有清漆层波瓣。
+#[derive(Debug, Clone)]
+pub struct ClearCoatPDF {
+    uvw: ONB,
+    wo: Vec3, // 指向入射一侧的单位观察方向
+    alpha: f64,
+    coat_probability: f64,
+    base: Option<Box<ScatterPDF>>,
+}
+
+impl ClearCoatPDF {
+    /// wo 为离开表面指向观察者的方向，normal 为朝向 wo 一侧的法线
+    #[inline]
+    pub fn new(
+        wo: &Vec3,
+        normal: &Vec3,
+        alpha: f64,
+        coat_probability: f64,
+        base: Option<ScatterPDF>,
+    ) -> Self {
+        let coat_probability = if base.is_some() {
+            coat_probability.clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        Self {
+            uvw: ONB::new(normal),
+            wo: wo.normalize(),
+            alpha,
+            coat_probability,
+            base: base.map(Box::new),
+        }
+    }
+
+    /// 底层材质的PDF，None 表示只有清漆层波瓣
+    #[inline]
+    pub fn base(&self) -> Option<&ScatterPDF> {
+        self.base.as_deref()
+    }
+
+    /// 清漆层反射波瓣的密度
+    pub fn coat_value(&self, direction: &Vec3) -> f64 {
+        let n = self.uvw.w();
+        let wi = direction.normalize();
+        if wi.dot(&n) <= 0.0 {
+            return 0.0;
+        }
+        let h = self.wo + wi;
+        if h.norm_squared() < 1e-16 {
+            return 0.0;
+        }
+        let h = h.normalize();
+        let cos_lh = wi.dot(&h);
+        if cos_lh <= 0.0 {
+            return 0.0;
+        }
+        vndf_pdf(&self.wo, &n, &h, self.alpha) / (4.0 * cos_lh)
+    }
+}
+
+impl PDF for ClearCoatPDF {
+    fn value(&self, direction: &Vec3) -> f64 {
+        if direction.norm_squared() == 0.0 {
+            return 0.0;
+        }
+        let coat = self.coat_probability * self.coat_value(direction);
+        match &self.base {
+            Some(base) => coat + (1.0 - self.coat_probability) * base.value(direction),
+            None => coat,
+        }
+    }
+
+    /// 清漆层的反射方向落到表面以下时样本无效，返回零向量
+    fn generate(&self) -> Vec3 {
+        if let Some(base) = &self.base
+            && random_double() >= self.coat_probability
+        {
+            return base.generate();
+        }
+        let n = self.uvw.w();
+        let h = sample_ggx_vndf(&self.uvw, &self.wo, self.alpha);
+        let wi = (-self.wo).reflect(&h);
+        if wi.dot(&n) > 0.0 { wi } else { Vec3::zeros() }
+    }
+}
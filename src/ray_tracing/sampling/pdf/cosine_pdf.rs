@@ -3,7 +3,7 @@ use crate::ray_tracing::math::onb::ONB;
 use crate::ray_tracing::math::vec3::*;
 
 /// 余弦分布PDF，用于漫反射材质
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CosinePDF {
     uvw: ONB,
 }
@@ -0,0 +1,44 @@
+use super::PDF;
+use crate::ray_tracing::math::onb::ONB;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::random::random_double;
+use std::f64::consts::PI;
+
+/// 纤维（毛发）漫反射分布：密度正比于方向与纤维切线夹角的正弦，sin α / π²
+///
+/// 对应 Kajiya-Kay 模型的漫反射项，绕切线旋转对称，在整个球面上散射（光线可以穿过细纤维）。
+#[derive(Debug, Clone)]
+pub struct FiberPDF {
+    uvw: ONB,
+}
+
+impl FiberPDF {
+    /// 从纤维切线方向创建分布
+    #[inline]
+    pub fn new(tangent: &Vec3) -> Self {
+        Self {
+            uvw: ONB::new(tangent),
+        }
+    }
+}
+
+impl PDF for FiberPDF {
+    #[inline]
+    fn value(&self, direction: &Vec3) -> f64 {
+        let cos_alpha = direction.normalize().dot(&self.uvw.w()).clamp(-1.0, 1.0);
+        (1.0 - cos_alpha * cos_alpha).sqrt() / (PI * PI)
+    }
+
+    /// dω = d(cos α) dφ，cos α 的边缘分布正比于 sqrt(1 - cos²α)，恰好是单位圆盘上均匀点的横坐标分布
+    #[inline]
+    fn generate(&self) -> Vec3 {
+        let cos_alpha = Vec3::random_in_unit_disk().x;
+        let sin_alpha = (1.0 - cos_alpha * cos_alpha).max(0.0).sqrt();
+        let phi = 2.0 * PI * random_double();
+        self.uvw.local_to_world(&Vec3::new(
+            sin_alpha * phi.cos(),
+            sin_alpha * phi.sin(),
+            cos_alpha,
+        ))
+    }
+}
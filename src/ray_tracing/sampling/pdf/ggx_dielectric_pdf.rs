@@ -0,0 +1,89 @@
+use super::PDF;
+use crate::ray_tracing::materials::microfacet::{fresnel_dielectric, sample_ggx_vndf, vndf_pdf};
+use crate::ray_tracing::math::onb::ONB;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::random::random_double;
+
+/// 粗糙电介质（GGX 微表面反射 + 透射）的方向采样PDF
+///
+/// 先按可见法线分布采样微表面法线，再按菲涅尔系数在反射和折射之间随机选择（Walter et al. 2007）。
+#[derive(Debug, Clone)]
+pub struct GgxDielectricPDF {
+    uvw: ONB,
+    wo: Vec3, // 指向入射一侧的单位观察方向
+    alpha: f64,
+    eta: f64, // 透射侧/入射侧折射率之比
+}
+
+impl GgxDielectricPDF {
+    /// wo 为离开表面指向观察者的方向，normal 为朝向 wo 一侧的法线
+    #[inline]
+    pub fn new(wo: &Vec3, normal: &Vec3, alpha: f64, eta: f64) -> Self {
+        Self {
+            uvw: ONB::new(normal),
+            wo: wo.normalize(),
+            alpha,
+            eta,
+        }
+    }
+
+    /// 由观察方向和散射方向求半程向量（已朝向法线一侧），返回 (h, 是否为反射)
+    pub fn half_vector(wo: &Vec3, wi: &Vec3, normal: &Vec3, eta: f64) -> Option<(Vec3, bool)> {
+        let reflect = wi.dot(normal) > 0.0;
+        let h = if reflect { wo + wi } else { wo + eta * wi };
+        if h.norm_squared() < 1e-16 {
+            return None;
+        }
+        let h = h.normalize();
+        Some((if h.dot(normal) < 0.0 { -h } else { h }, reflect))
+    }
+}
+
+impl PDF for GgxDielectricPDF {
+    fn value(&self, direction: &Vec3) -> f64 {
+        if direction.norm_squared() == 0.0 {
+            return 0.0;
+        }
+        let n = self.uvw.w();
+        let wi = direction.normalize();
+        let Some((h, reflect)) = Self::half_vector(&self.wo, &wi, &n, self.eta) else {
+            return 0.0;
+        };
+
+        // 两个方向都必须位于微表面的正确一侧：反射时 wi 与 h 同侧，透射时异侧
+        let cos_vh = self.wo.dot(&h);
+        let cos_lh = wi.dot(&h);
+        if cos_vh <= 0.0 || (cos_lh > 0.0) != reflect {
+            return 0.0;
+        }
+
+        let fresnel = fresnel_dielectric(cos_vh, self.eta);
+        let pdf_h = vndf_pdf(&self.wo, &n, &h, self.alpha);
+
+        if reflect {
+            fresnel * pdf_h / (4.0 * cos_lh.abs())
+        } else {
+            let denom = cos_vh + self.eta * cos_lh;
+            (1.0 - fresnel) * pdf_h * self.eta * self.eta * cos_lh.abs() / (denom * denom)
+        }
+    }
+
+    /// 反射方向落到表面以下或透射方向落到表面以上时样本无效，返回零向量
+    fn generate(&self) -> Vec3 {
+        let n = self.uvw.w();
+        let h = sample_ggx_vndf(&self.uvw, &self.wo, self.alpha);
+        let cos_vh = self.wo.dot(&h);
+        let fresnel = fresnel_dielectric(cos_vh, self.eta);
+
+        let (wi, reflect) = if random_double() < fresnel {
+            ((-self.wo).reflect(&h), true)
+        } else {
+            ((-self.wo).refract(&h, 1.0 / self.eta), false)
+        };
+        if (wi.dot(&n) > 0.0) == reflect {
+            wi
+        } else {
+            Vec3::zeros()
+        }
+    }
+}
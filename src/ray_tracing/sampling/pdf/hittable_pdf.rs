@@ -1,42 +1,44 @@
 use super::PDF;
 use crate::ray_tracing::geometry::hittable::Hittable;
 use crate::ray_tracing::math::vec3::*;
-use std::sync::Arc;
 
-/// 基于几何体的PDF，用于光源采样
-pub struct HittablePDF {
-    objects: Arc<dyn Hittable>,
+/// 基于几何体的PDF，用于光源采样（借用光源几何体，构造时不增加引用计数）
+pub struct HittablePDF<'a> {
+    objects: &'a dyn Hittable,
     origin: Point3,
+    time: f64,
 }
 
-impl HittablePDF {
-    /// 创建基于几何体的PDF
+impl<'a> HittablePDF<'a> {
+    /// 创建基于几何体的PDF，time 为光线时刻，使运动光源按该时刻的位置采样
     #[inline]
-    pub fn new(objects: Arc<dyn Hittable>, origin: &Point3) -> Self {
+    pub fn new(objects: &'a dyn Hittable, origin: &Point3, time: f64) -> Self {
         Self {
             objects,
             origin: *origin,
+            time,
         }
     }
 }
 
-impl PDF for HittablePDF {
+impl PDF for HittablePDF<'_> {
     #[inline]
     fn value(&self, direction: &Vec3) -> f64 {
-        self.objects.pdf_value(&self.origin, direction)
+        self.objects.pdf_value(&self.origin, direction, self.time)
     }
 
     #[inline]
     fn generate(&self) -> Vec3 {
-        self.objects.random(&self.origin)
+        self.objects.random(&self.origin, self.time)
     }
 }
 
-impl std::fmt::Debug for HittablePDF {
+impl std::fmt::Debug for HittablePDF<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("HittablePDF")
             .field("objects", &"<Hittable>")
             .field("origin", &self.origin)
+            .field("time", &self.time)
             .finish()
     }
 }
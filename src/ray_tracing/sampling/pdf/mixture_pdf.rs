@@ -4,23 +4,26 @@ use crate::ray_tracing::utils::random::random_double;
 use std::sync::Arc;
 
 /// 混合PDF，组合两种不同的采样策略
-pub struct MixturePDF {
-    pdf1: Arc<dyn PDF>,
-    pdf2: Arc<dyn PDF>,
+///
+/// 两个分量可以是任意实现了 `PDF` 的类型，包括借用（`&dyn PDF`）和共享指针（`Arc<dyn PDF>`），
+/// 积分器在内层循环中用借用的分量在栈上组合光源和材质的PDF。
+pub struct MixturePDF<P1 = Arc<dyn PDF>, P2 = Arc<dyn PDF>> {
+    pdf1: P1,
+    pdf2: P2,
     weight1: f64,
     weight2: f64,
 }
 
-impl MixturePDF {
+impl<P1: PDF, P2: PDF> MixturePDF<P1, P2> {
     /// 创建等权重的混合PDF
     #[inline]
-    pub fn new(pdf1: Arc<dyn PDF>, pdf2: Arc<dyn PDF>) -> Self {
+    pub fn new(pdf1: P1, pdf2: P2) -> Self {
         Self::new_weighted(pdf1, pdf2, 0.5)
     }
 
     /// 创建带权重的混合PDF
     #[inline]
-    pub fn new_weighted(pdf1: Arc<dyn PDF>, pdf2: Arc<dyn PDF>, weight1: f64) -> Self {
+    pub fn new_weighted(pdf1: P1, pdf2: P2, weight1: f64) -> Self {
         let weight1 = weight1.clamp(0.0, 1.0);
         Self {
             pdf1,
@@ -31,7 +34,7 @@ impl MixturePDF {
     }
 }
 
-impl PDF for MixturePDF {
+impl<P1: PDF, P2: PDF> PDF for MixturePDF<P1, P2> {
     #[inline]
     fn value(&self, direction: &Vec3) -> f64 {
         let value =
@@ -52,7 +55,7 @@ impl PDF for MixturePDF {
     }
 }
 
-impl std::fmt::Debug for MixturePDF {
+impl<P1, P2> std::fmt::Debug for MixturePDF<P1, P2> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("MixturePDF")
             .field("pdf1", &"<PDF>")
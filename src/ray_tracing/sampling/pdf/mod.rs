@@ -1,11 +1,21 @@
+pub mod clear_coat_pdf;
 pub mod cosine_pdf;
+pub mod fiber_pdf;
+pub mod ggx_dielectric_pdf;
 pub mod hittable_pdf;
 pub mod mixture_pdf;
+pub mod principled_pdf;
+pub mod scatter_pdf;
 pub mod sphere_pdf;
 
 use crate::ray_tracing::math::vec3::Vec3;
+use std::sync::Arc;
 
 /// 概率密度函数trait，用于重要性采样
+///
+/// `value` 是关于立体角的密度，必须与 `generate` 生成方向的分布一致，通常在整个球面上积分为1。
+/// `generate` 可以返回零向量表示样本无效（如微表面采样得到的方向位于表面错误一侧），
+/// 此时 `value` 为0、渲染器丢弃该样本，`value` 在球面上的积分等于有效样本的比例。
 #[allow(clippy::upper_case_acronyms)]
 pub trait PDF: Send + Sync + std::fmt::Debug {
     /// 计算给定方向的概率密度值
@@ -15,7 +25,36 @@ pub trait PDF: Send + Sync + std::fmt::Debug {
     fn generate(&self) -> Vec3;
 }
 
+impl<T: PDF + ?Sized> PDF for &T {
+    #[inline]
+    fn value(&self, direction: &Vec3) -> f64 {
+        (**self).value(direction)
+    }
+
+    #[inline]
+    fn generate(&self) -> Vec3 {
+        (**self).generate()
+    }
+}
+
+impl<T: PDF + ?Sized> PDF for Arc<T> {
+    #[inline]
+    fn value(&self, direction: &Vec3) -> f64 {
+        (**self).value(direction)
+    }
+
+    #[inline]
+    fn generate(&self) -> Vec3 {
+        (**self).generate()
+    }
+}
+
+pub use clear_coat_pdf::ClearCoatPDF;
 pub use cosine_pdf::CosinePDF;
+pub use fiber_pdf::FiberPDF;
+pub use ggx_dielectric_pdf::GgxDielectricPDF;
 pub use hittable_pdf::HittablePDF;
 pub use mixture_pdf::MixturePDF;
+pub use principled_pdf::PrincipledPDF;
+pub use scatter_pdf::ScatterPDF;
 pub use sphere_pdf::SpherePDF;
@@ -0,0 +1,148 @@
+use super::{CosinePDF, GgxDielectricPDF, PDF};
+use crate::ray_tracing::materials::microfacet::{gtr1_d, sample_ggx_vndf, sample_gtr1, vndf_pdf};
+use crate::ray_tracing::math::onb::ONB;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::random::random_double;
+
+/// Disney 原则化材质各波瓣的选择概率（之和为1）
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LobeWeights {
+    /// 漫反射和光泽层（余弦分布）
+    pub diffuse: f64,
+    /// GGX 镜面反射（可见法线分布）
+    pub specular: f64,
+    /// GTR1 清漆层
+    pub clearcoat: f64,
+    /// GGX 微表面透射
+    pub transmission: f64,
+}
+
+impl LobeWeights {
+    /// 归一化为概率，全为零时只选择漫反射
+    pub fn normalized(self) -> Self {
+        let total = self.diffuse + self.specular + self.clearcoat + self.transmission;
+        if total <= 0.0 || !total.is_finite() {
+            return Self {
+                diffuse: 1.0,
+                ..Self::default()
+            };
+        }
+        Self {
+            diffuse: self.diffuse / total,
+            specular: self.specular / total,
+            clearcoat: self.clearcoat / total,
+            transmission: self.transmission / total,
+        }
+    }
+}
+
+/// Disney 原则化材质的方向采样PDF：漫反射、镜面反射、清漆层和透射四个波瓣按概率混合
+///
+/// 透射波瓣使用 [`GgxDielectricPDF`]（按菲涅尔系数在微表面反射和折射之间选择），
+/// 与镜面反射波瓣重叠的部分只影响方差，不影响无偏性。
+#[derive(Debug, Clone)]
+pub struct PrincipledPDF {
+    uvw: ONB,
+    wo: Vec3, // 指向入射一侧的单位观察方向
+    alpha: f64,
+    clearcoat_alpha: f64,
+    eta: f64, // 透射侧/入射侧折射率之比
+    weights: LobeWeights,
+}
+
+impl PrincipledPDF {
+    /// wo 为离开表面指向观察者的方向，normal 为朝向 wo 一侧的法线，weights 会被归一化
+    #[inline]
+    pub fn new(
+        wo: &Vec3,
+        normal: &Vec3,
+        alpha: f64,
+        clearcoat_alpha: f64,
+        eta: f64,
+        weights: LobeWeights,
+    ) -> Self {
+        Self {
+            uvw: ONB::new(normal),
+            wo: wo.normalize(),
+            alpha,
+            clearcoat_alpha,
+            eta,
+            weights: weights.normalized(),
+        }
+    }
+
+    /// 各波瓣的选择概率
+    #[inline]
+    pub fn weights(&self) -> LobeWeights {
+        self.weights
+    }
+
+    #[inline]
+    fn transmission_pdf(&self) -> GgxDielectricPDF {
+        GgxDielectricPDF::new(&self.wo, &self.uvw.w(), self.alpha, self.eta)
+    }
+
+    /// 反射方向 wi 的半程向量及 wi·h，方向无效时返回 None
+    fn reflection_half_vector(&self, wi: &Vec3) -> Option<(Vec3, f64)> {
+        if wi.dot(&self.uvw.w()) <= 0.0 {
+            return None;
+        }
+        let h = self.wo + wi;
+        if h.norm_squared() < 1e-16 {
+            return None;
+        }
+        let h = h.normalize();
+        let cos_lh = wi.dot(&h);
+        (cos_lh > 0.0).then_some((h, cos_lh))
+    }
+}
+
+impl PDF for PrincipledPDF {
+    fn value(&self, direction: &Vec3) -> f64 {
+        if direction.norm_squared() == 0.0 {
+            return 0.0;
+        }
+        let n = self.uvw.w();
+        let wi = direction.normalize();
+        let w = &self.weights;
+
+        let mut pdf = 0.0;
+        if w.diffuse > 0.0 {
+            pdf += w.diffuse * CosinePDF::new(&n).value(&wi);
+        }
+        if let Some((h, cos_lh)) = self.reflection_half_vector(&wi) {
+            if w.specular > 0.0 {
+                pdf += w.specular * vndf_pdf(&self.wo, &n, &h, self.alpha) / (4.0 * cos_lh);
+            }
+            if w.clearcoat > 0.0 {
+                let cos_h = h.dot(&n);
+                pdf += w.clearcoat * gtr1_d(cos_h, self.clearcoat_alpha) * cos_h / (4.0 * cos_lh);
+            }
+        }
+        if w.transmission > 0.0 {
+            pdf += w.transmission * self.transmission_pdf().value(&wi);
+        }
+        pdf
+    }
+
+    /// 反射波瓣的方向落到表面以下时样本无效，返回零向量
+    fn generate(&self) -> Vec3 {
+        let n = self.uvw.w();
+        let w = &self.weights;
+        let mut x = random_double();
+
+        if x < w.diffuse {
+            return self.uvw.local_to_world(&Vec3::random_cosine_direction());
+        }
+        x -= w.diffuse;
+        let h = if x < w.specular {
+            sample_ggx_vndf(&self.uvw, &self.wo, self.alpha)
+        } else if x - w.specular < w.clearcoat {
+            sample_gtr1(&self.uvw, self.clearcoat_alpha)
+        } else {
+            return self.transmission_pdf().generate();
+        };
+        let wi = (-self.wo).reflect(&h);
+        if wi.dot(&n) > 0.0 { wi } else { Vec3::zeros() }
+    }
+}
@@ -0,0 +1,101 @@
+use super::{ClearCoatPDF, CosinePDF, FiberPDF, GgxDielectricPDF, PDF, PrincipledPDF, SpherePDF};
+use crate::ray_tracing::math::vec3::Vec3;
+use std::sync::Arc;
+
+/// 材质散射使用的PDF，内置的分布按值存放在散射记录中，无需为每次散射分配堆内存
+///
+/// 自定义PDF通过 `Custom` 以trait对象的形式传入。
+#[derive(Debug, Clone)]
+pub enum ScatterPDF {
+    Cosine(CosinePDF),
+    Sphere(SpherePDF),
+    GgxDielectric(GgxDielectricPDF),
+    Fiber(FiberPDF),
+    ClearCoat(ClearCoatPDF),
+    Principled(PrincipledPDF),
+    Custom(Arc<dyn PDF>),
+}
+
+impl PDF for ScatterPDF {
+    #[inline]
+    fn value(&self, direction: &Vec3) -> f64 {
+        match self {
+            Self::Cosine(pdf) => pdf.value(direction),
+            Self::Sphere(pdf) => pdf.value(direction),
+            Self::GgxDielectric(pdf) => pdf.value(direction),
+            Self::Fiber(pdf) => pdf.value(direction),
+            Self::ClearCoat(pdf) => pdf.value(direction),
+            Self::Principled(pdf) => pdf.value(direction),
+            Self::Custom(pdf) => pdf.value(direction),
+        }
+    }
+
+    #[inline]
+    fn generate(&self) -> Vec3 {
+        match self {
+            Self::Cosine(pdf) => pdf.generate(),
+            Self::Sphere(pdf) => pdf.generate(),
+            Self::GgxDielectric(pdf) => pdf.generate(),
+            Self::Fiber(pdf) => pdf.generate(),
+            Self::ClearCoat(pdf) => pdf.generate(),
+            Self::Principled(pdf) => pdf.generate(),
+            Self::Custom(pdf) => pdf.generate(),
+        }
+    }
+}
+
+impl From<CosinePDF> for ScatterPDF {
+    #[inline]
+    fn from(pdf: CosinePDF) -> Self {
+        Self::Cosine(pdf)
+    }
+}
+
+impl From<SpherePDF> for ScatterPDF {
+    #[inline]
+    fn from(pdf: SpherePDF) -> Self {
+        Self::Sphere(pdf)
+    }
+}
+
+impl From<GgxDielectricPDF> for ScatterPDF {
+    #[inline]
+    fn from(pdf: GgxDielectricPDF) -> Self {
+        Self::GgxDielectric(pdf)
+    }
+}
+
+impl From<FiberPDF> for ScatterPDF {
+    #[inline]
+    fn from(pdf: FiberPDF) -> Self {
+        Self::Fiber(pdf)
+    }
+}
+
+impl From<ClearCoatPDF> for ScatterPDF {
+    #[inline]
+    fn from(pdf: ClearCoatPDF) -> Self {
+        Self::ClearCoat(pdf)
+    }
+}
+
+impl From<PrincipledPDF> for ScatterPDF {
+    #[inline]
+    fn from(pdf: PrincipledPDF) -> Self {
+        Self::Principled(pdf)
+    }
+}
+
+impl From<Arc<dyn PDF>> for ScatterPDF {
+    #[inline]
+    fn from(pdf: Arc<dyn PDF>) -> Self {
+        Self::Custom(pdf)
+    }
+}
+
+impl<T: PDF + 'static> From<Arc<T>> for ScatterPDF {
+    #[inline]
+    fn from(pdf: Arc<T>) -> Self {
+        Self::Custom(pdf)
+    }
+}
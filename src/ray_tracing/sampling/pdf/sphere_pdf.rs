@@ -2,7 +2,7 @@ use super::PDF;
 use crate::ray_tracing::math::vec3::*;
 
 /// 均匀球面分布PDF，用于各向同性散射
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct SpherePDF;
 
 impl SpherePDF {
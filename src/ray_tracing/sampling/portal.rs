@@ -0,0 +1,201 @@
+//! 光门（light portal）：把环境光的采样集中到室内场景的开口上
+//!
+//! 室内场景只能透过窗户、门洞等开口看到天空时，按材质采样的光线绝大多数打在墙上，
+//! 渲染结果噪点很多。光门是覆盖开口的不可见四边形，只加入光源采样列表而不加入场景：
+//! 混合PDF的一部分光线朝开口方向发出，穿过开口后照常取背景颜色，估计仍然无偏。
+//!
+//! [`detect_portals`] 在封闭几何的包围盒各面上按网格投射短探测光线，找出没有墙面遮挡的大块区域
+//! 作为光门，例如康奈尔盒缺失的前墙。开口过多（如室外场景）时认为不是室内，不生成光门。
+
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::geometry::quad::Quad;
+use crate::ray_tracing::materials::lambertian::Lambertian;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::{Ray, RayKind};
+use crate::ray_tracing::math::vec3::*;
+use std::sync::Arc;
+
+/// 覆盖开口的光门：只用于采样方向，不可见、不发光
+#[derive(Debug)]
+pub struct Portal {
+    quad: Quad,
+}
+
+impl Portal {
+    /// 以 q 为角点、u 和 v 为两边的矩形光门
+    pub fn new(q: Point3, u: Vec3, v: Vec3) -> Self {
+        Self {
+            quad: Quad::new(q, u, v, Arc::new(Lambertian::new(Color::zeros()))),
+        }
+    }
+
+    /// 光门的几何形状
+    #[inline]
+    pub fn quad(&self) -> &Quad {
+        &self.quad
+    }
+}
+
+impl Hittable for Portal {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        self.quad.hit(r, ray_t, rec)
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.quad.bounding_box()
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3, time: f64) -> f64 {
+        self.quad.pdf_value(origin, direction, time)
+    }
+
+    fn random(&self, origin: &Point3, time: f64) -> Vec3 {
+        self.quad.random(origin, time)
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        self.quad.area()
+    }
+
+    // power 和 uv_triangles 保持默认：光门不发光，焦散光子也不从光门发射
+}
+
+/// 光门检测参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PortalSettings {
+    /// 包围盒每个面上探测网格的边长（格数）
+    pub resolution: usize,
+    /// 开口面积至少占所在面的比例，更小的缝隙忽略
+    pub min_area_fraction: f64,
+    /// 开口占包围盒总表面的比例超过此值时认为场景不封闭，不生成光门
+    pub max_open_fraction: f64,
+    /// 探测光线在面两侧的长度，占包围盒对角线的比例
+    pub probe_depth: f64,
+}
+
+impl Default for PortalSettings {
+    fn default() -> Self {
+        Self {
+            resolution: 32,
+            min_area_fraction: 0.02,
+            max_open_fraction: 0.5,
+            probe_depth: 0.02,
+        }
+    }
+}
+
+/// 在 bounds（通常为场景包围盒）的各面上检测开口，返回覆盖每个开口的光门
+///
+/// 面上每格中心投射一条垂直穿过该面的短光线，未击中任何物体的格子为开口；
+/// 相连的开口格子合并为一个开口，光门取其外接矩形。
+pub fn detect_portals(
+    world: &dyn Hittable,
+    bounds: &Aabb,
+    settings: &PortalSettings,
+) -> Vec<Portal> {
+    let n = settings.resolution.max(1);
+    let (min, max) = (bounds_min(bounds), bounds_max(bounds));
+    let depth = settings.probe_depth.max(0.0) * (max - min).norm();
+
+    let mut openings = Vec::new();
+    let mut open_cells = 0;
+    for axis in 0..3 {
+        let (a, b) = ((axis + 1) % 3, (axis + 2) % 3);
+        for positive in [false, true] {
+            let plane = if positive { max[axis] } else { min[axis] };
+            let mut normal = Vec3::zeros();
+            normal[axis] = if positive { 1.0 } else { -1.0 };
+            let cell_point = |i: usize, j: usize| {
+                let mut p = Point3::origin();
+                p[axis] = plane;
+                p[a] = min[a] + (i as f64 + 0.5) / n as f64 * (max[a] - min[a]);
+                p[b] = min[b] + (j as f64 + 0.5) / n as f64 * (max[b] - min[b]);
+                p
+            };
+
+            let open: Vec<bool> = (0..n * n)
+                .map(|k| {
+                    let p = cell_point(k % n, k / n);
+                    let probe = Ray::new(p - depth * normal, 2.0 * depth * normal, 0.0)
+                        .with_kind(RayKind::Shadow);
+                    !world.hit_any(&probe, Interval::new(0.0, 1.0))
+                })
+                .collect();
+            open_cells += open.iter().filter(|&&o| o).count();
+
+            for Opening { lo, hi, cells } in components(&open, n) {
+                if (cells as f64) < settings.min_area_fraction * (n * n) as f64 {
+                    continue;
+                }
+                let mut q = Point3::origin();
+                q[axis] = plane;
+                q[a] = min[a] + lo.0 as f64 / n as f64 * (max[a] - min[a]);
+                q[b] = min[b] + lo.1 as f64 / n as f64 * (max[b] - min[b]);
+                let mut u = Vec3::zeros();
+                u[a] = (hi.0 + 1 - lo.0) as f64 / n as f64 * (max[a] - min[a]);
+                let mut v = Vec3::zeros();
+                v[b] = (hi.1 + 1 - lo.1) as f64 / n as f64 * (max[b] - min[b]);
+                openings.push(Portal::new(q, u, v));
+            }
+        }
+    }
+
+    if open_cells as f64 > settings.max_open_fraction * (6 * n * n) as f64 {
+        return Vec::new();
+    }
+    openings
+}
+
+fn bounds_min(bounds: &Aabb) -> Point3 {
+    Point3::new(bounds.x.min, bounds.y.min, bounds.z.min)
+}
+
+fn bounds_max(bounds: &Aabb) -> Point3 {
+    Point3::new(bounds.x.max, bounds.y.max, bounds.z.max)
+}
+
+/// 网格上四连通的开口区域
+struct Opening {
+    /// 外接矩形的最小格坐标
+    lo: (usize, usize),
+    /// 外接矩形的最大格坐标
+    hi: (usize, usize),
+    /// 格子数
+    cells: usize,
+}
+
+fn components(open: &[bool], n: usize) -> Vec<Opening> {
+    let mut visited = vec![false; open.len()];
+    let mut result = Vec::new();
+    for start in 0..open.len() {
+        if !open[start] || visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        let mut stack = vec![start];
+        let (mut lo, mut hi, mut cells) = ((n, n), (0, 0), 0);
+        while let Some(k) = stack.pop() {
+            let (i, j) = (k % n, k / n);
+            lo = (lo.0.min(i), lo.1.min(j));
+            hi = (hi.0.max(i), hi.1.max(j));
+            cells += 1;
+            let neighbours = [
+                (i > 0).then(|| k - 1),
+                (i + 1 < n).then(|| k + 1),
+                (j > 0).then(|| k - n),
+                (j + 1 < n).then(|| k + n),
+            ];
+            for next in neighbours.into_iter().flatten() {
+                if open[next] && !visited[next] {
+                    visited[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        result.push(Opening { lo, hi, cells });
+    }
+    result
+}
@@ -0,0 +1,1101 @@
+//! glTF 2.0 场景导入（`.gltf` + 外部/内嵌缓冲区，或二进制 `.glb`）
+//!
+//! 导入内容：
+//! - 网格图元（三角形、三角带、三角扇）按节点的世界变换展开为 [`TriangleMesh`]，
+//!   可按 [`GltfOptions::subdivision`] 做 Loop 细分使低面数模型变得光滑
+//! - 金属度-粗糙度材质及 `KHR_materials_transmission` / `_ior` / `_clearcoat` / `_sheen` / `_specular`
+//!   扩展映射为 [`PrincipledMaterial`]，光滑的完全透射材质映射为 [`Dielectric`]，
+//!   发光材质映射为 [`DiffuseLight`]
+//! - 透视相机转换为 [`GltfCamera`]，可直接生成相机构建器
+//! - `KHR_lights_punctual` 的点光源和聚光灯转换为小的发光球体
+//!
+//! 不支持的内容（正交相机、平行光、稀疏访问器、透明混合等）会被跳过并记录在 `warnings` 中。
+
+use super::world::Scene;
+use crate::ray_tracing::geometry::hittable::Hittable;
+use crate::ray_tracing::geometry::mesh::{MeshData, TriangleMesh};
+use crate::ray_tracing::geometry::sphere::Sphere;
+use crate::ray_tracing::geometry::subdivision::{Subdivision, loop_subdivide};
+use crate::ray_tracing::materials::dielectric::Dielectric;
+use crate::ray_tracing::materials::diffuse_light::DiffuseLight;
+use crate::ray_tracing::materials::material::Material;
+use crate::ray_tracing::materials::principled::PrincipledMaterial;
+use crate::ray_tracing::materials::spot_light::SpotLight;
+use crate::ray_tracing::materials::texture::image::ImageTexture;
+use crate::ray_tracing::materials::texture::{SolidColor, Texture, TexturePtr};
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::rendering::camera::{Camera, CameraBuilder};
+use crate::ray_tracing::rendering::color_space::TextureColorSpace;
+use crate::ray_tracing::utils::json::Json;
+use nalgebra::{Matrix3, Matrix4, Quaternion, UnitQuaternion};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// GLB 文件头魔数 "glTF"
+const GLB_MAGIC: &[u8; 4] = b"glTF";
+/// GLB JSON 块类型
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+/// GLB 二进制块类型
+const CHUNK_BIN: u32 = 0x004E_4942;
+/// 粗糙度低于此值的透射材质按光滑玻璃处理
+const SMOOTH_ROUGHNESS: f64 = 0.05;
+/// 没有缓冲区视图（全零）的访问器允许的最大元素数
+const MAX_ZERO_ACCESSOR_COUNT: usize = 1 << 24;
+
+/// 导入选项
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GltfOptions {
+    /// 点光源和聚光灯转换成的发光球体半径（场景单位）
+    pub light_radius: f64,
+    /// 网格图元的细分级数（默认不细分）
+    pub subdivision: Subdivision,
+}
+
+impl Default for GltfOptions {
+    fn default() -> Self {
+        Self {
+            light_radius: 0.05,
+            subdivision: Subdivision::default(),
+        }
+    }
+}
+
+/// 从 glTF 相机节点得到的透视相机
+#[derive(Debug, Clone, PartialEq)]
+pub struct GltfCamera {
+    pub name: Option<String>,
+    pub lookfrom: Point3,
+    pub lookat: Point3,
+    pub vup: Vec3,
+    /// 垂直视场角（度）
+    pub vfov: f64,
+    /// 文件中给出的宽高比
+    pub aspect_ratio: Option<f64>,
+}
+
+impl GltfCamera {
+    /// 按此相机设置机位、朝向和视场角的相机构建器
+    pub fn builder(&self) -> CameraBuilder {
+        let builder = Camera::builder()
+            .lookfrom(self.lookfrom)
+            .lookat(self.lookat)
+            .vup(self.vup)
+            .vfov(self.vfov);
+        match self.aspect_ratio {
+            Some(aspect) => builder.aspect_ratio(aspect),
+            None => builder,
+        }
+    }
+}
+
+/// 导入结果
+#[derive(Debug)]
+pub struct GltfImport {
+    /// 场景：网格作为普通物体，发光网格和光源同时加入光源列表
+    pub scene: Scene,
+    /// 场景中的相机，按节点遍历顺序
+    pub cameras: Vec<GltfCamera>,
+    /// 被跳过或近似处理的内容
+    pub warnings: Vec<String>,
+    /// 重新加载时复用几何所需的状态
+    reload: ReloadState,
+}
+
+/// 重新加载时文件变化的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GltfChange {
+    /// 网格、节点或缓冲区数据变化，重新构建了全部几何和BVH
+    Geometry,
+    /// 只有材质、纹理、相机或光源变化，复用了已有的网格几何和BVH
+    Materials,
+}
+
+/// 影响几何的顶层字段：这些字段不变时网格的顶点数据和节点变换都不变
+const GEOMETRY_SECTIONS: [&str; 7] = [
+    "scene",
+    "scenes",
+    "nodes",
+    "meshes",
+    "accessors",
+    "bufferViews",
+    "buffers",
+];
+
+#[derive(Debug, Default)]
+struct ReloadState {
+    options: GltfOptions,
+    /// GEOMETRY_SECTIONS 中各字段的内容
+    geometry_sections: Vec<Option<Json>>,
+    /// GLB 二进制块的哈希
+    bin_hash: Option<u64>,
+    /// 本次导入是否复用了上次的几何
+    reused_geometry: bool,
+    /// 按遍历顺序导入的图元几何（空网格为None）
+    meshes: Vec<Option<Arc<TriangleMesh>>>,
+    /// 引用的外部缓冲区文件
+    buffer_files: Vec<PathBuf>,
+    /// 引用的外部图像文件
+    image_files: Vec<PathBuf>,
+}
+
+impl GltfImport {
+    /// 引用的外部缓冲区文件（修改后几何需要重建）
+    pub fn buffer_files(&self) -> &[PathBuf] {
+        &self.reload.buffer_files
+    }
+
+    /// 引用的外部图像文件（修改后只需重建材质）
+    pub fn image_files(&self) -> &[PathBuf] {
+        &self.reload.image_files
+    }
+}
+
+/// 使用默认选项导入 glTF 文件
+pub fn load_gltf(path: impl AsRef<Path>) -> io::Result<GltfImport> {
+    load_gltf_with(path, GltfOptions::default())
+}
+
+/// 导入 glTF 文件（`.gltf` 或 `.glb`）
+pub fn load_gltf_with(path: impl AsRef<Path>, options: GltfOptions) -> io::Result<GltfImport> {
+    import(path.as_ref(), options, None)
+}
+
+/// 重新导入修改过的文件
+///
+/// 影响几何的字段（节点、网格、访问器、缓冲区）和 GLB 二进制块都不变时，复用 previous 中已构建的网格几何和BVH，
+/// 只重建材质、相机和光源；否则完整地重新导入。外部缓冲区文件的内容不在比较范围内，修改后应使用 `load_gltf_with`。
+pub fn reload_gltf(
+    path: impl AsRef<Path>,
+    previous: &GltfImport,
+) -> io::Result<(GltfImport, GltfChange)> {
+    let import = import(
+        path.as_ref(),
+        previous.reload.options,
+        Some(&previous.reload),
+    )?;
+    let change = if import.reload.reused_geometry {
+        GltfChange::Materials
+    } else {
+        GltfChange::Geometry
+    };
+    Ok((import, change))
+}
+
+fn import(
+    path: &Path,
+    options: GltfOptions,
+    previous: Option<&ReloadState>,
+) -> io::Result<GltfImport> {
+    let bytes = std::fs::read(path)?;
+    let source = path.display().to_string();
+    let base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+    let (doc, bin) = if bytes.starts_with(GLB_MAGIC) {
+        let (json, bin) = split_glb(&bytes, &source)?;
+        (Json::parse(json, &source)?, bin)
+    } else {
+        let text = std::str::from_utf8(&bytes).map_err(|_| invalid(&source, "不是有效的 UTF-8"))?;
+        (Json::parse(text, &source)?, None)
+    };
+
+    let version = doc
+        .get("asset")
+        .and_then(|a| a.get("version"))
+        .and_then(Json::as_str)
+        .unwrap_or("");
+    if !version.starts_with('2') {
+        return Err(invalid(
+            &source,
+            &format!("只支持 glTF 2.0（文件版本 {:?}）", version),
+        ));
+    }
+
+    let geometry_sections: Vec<Option<Json>> = GEOMETRY_SECTIONS
+        .iter()
+        .map(|key| doc.get(key).cloned())
+        .collect();
+    let bin_hash = bin.as_ref().map(|bin| {
+        let mut hasher = DefaultHasher::new();
+        bin.hash(&mut hasher);
+        hasher.finish()
+    });
+    // 几何不变时按遍历顺序复用上次的网格
+    let reuse = previous
+        .filter(|p| p.geometry_sections == geometry_sections && p.bin_hash == bin_hash)
+        .map(|p| p.meshes.clone().into_iter());
+
+    let mut importer = Importer {
+        doc: &doc,
+        source,
+        base_dir,
+        options,
+        buffers: Vec::new(),
+        images: HashMap::new(),
+        materials: HashMap::new(),
+        reuse: None,
+        result: GltfImport {
+            scene: Scene::new(),
+            cameras: Vec::new(),
+            warnings: Vec::new(),
+            reload: ReloadState {
+                options,
+                geometry_sections,
+                bin_hash,
+                reused_geometry: reuse.is_some(),
+                ..ReloadState::default()
+            },
+        },
+    };
+    importer.reuse = reuse;
+    importer.load_buffers(bin)?;
+    importer.import_scene()?;
+    Ok(importer.result)
+}
+
+fn invalid(source: &str, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", source, message),
+    )
+}
+
+/// 拆分 GLB 容器，返回 (JSON 文本, 二进制块)
+fn split_glb<'a>(bytes: &'a [u8], source: &str) -> io::Result<(&'a str, Option<Vec<u8>>)> {
+    let read_u32 = |offset: usize| -> io::Result<u32> {
+        bytes
+            .get(offset..offset + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .ok_or_else(|| invalid(source, "GLB 文件被截断"))
+    };
+    if read_u32(4)? != 2 {
+        return Err(invalid(source, "只支持 GLB 版本 2"));
+    }
+    let total = (read_u32(8)? as usize).min(bytes.len());
+
+    let mut json = None;
+    let mut bin = None;
+    let mut offset = 12;
+    while offset + 8 <= total {
+        let length = read_u32(offset)? as usize;
+        let kind = read_u32(offset + 4)?;
+        let data = bytes
+            .get(offset + 8..offset + 8 + length)
+            .ok_or_else(|| invalid(source, "GLB 数据块越界"))?;
+        match kind {
+            CHUNK_JSON if json.is_none() => {
+                json = Some(
+                    std::str::from_utf8(data)
+                        .map_err(|_| invalid(source, "GLB 的 JSON 块不是有效的 UTF-8"))?,
+                );
+            }
+            CHUNK_BIN if bin.is_none() => bin = Some(data.to_vec()),
+            _ => {}
+        }
+        // 数据块按4字节对齐
+        offset += 8 + length.div_ceil(4) * 4;
+    }
+
+    json.map(|json| (json, bin))
+        .ok_or_else(|| invalid(source, "GLB 缺少 JSON 块"))
+}
+
+/// 解码 base64（忽略空白，允许缺少末尾的填充）
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    fn sextet(c: u8) -> Option<u32> {
+        match c {
+            b'A'..=b'Z' => Some((c - b'A') as u32),
+            b'a'..=b'z' => Some((c - b'a') as u32 + 26),
+            b'0'..=b'9' => Some((c - b'0') as u32 + 52),
+            b'+' | b'-' => Some(62),
+            b'/' | b'_' => Some(63),
+            _ => None,
+        }
+    }
+
+    let mut out = Vec::with_capacity(text.len() * 3 / 4);
+    let mut acc = 0u32;
+    let mut bits = 0;
+    for c in text.bytes() {
+        if c == b'=' {
+            break;
+        }
+        if c.is_ascii_whitespace() {
+            continue;
+        }
+        acc = (acc << 6) | sextet(c)?;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    Some(out)
+}
+
+/// 解码 `data:` URI 中的 base64 数据
+fn decode_data_uri(uri: &str) -> Option<Vec<u8>> {
+    let (header, data) = uri.strip_prefix("data:")?.split_once(',')?;
+    header.ends_with(";base64").then(|| decode_base64(data))?
+}
+
+/// 解码 URI 中的百分号转义（外部文件名可能包含空格等字符）
+fn percent_decode(uri: &str) -> String {
+    let bytes = uri.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = uri
+                .get(i + 1..i + 3)
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+        {
+            out.push(byte);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// glTF 节点的局部变换：`matrix` 或 TRS
+fn node_transform(node: &Json) -> Matrix4<f64> {
+    if let Some(m) = node.f64_array::<16>("matrix") {
+        // glTF 矩阵按列主序存储
+        return Matrix4::from_column_slice(&m);
+    }
+
+    let translation = node.f64_array::<3>("translation").unwrap_or([0.0; 3]);
+    let [x, y, z, w] = node
+        .f64_array::<4>("rotation")
+        .unwrap_or([0.0, 0.0, 0.0, 1.0]);
+    let scale = node.f64_array::<3>("scale").unwrap_or([1.0; 3]);
+
+    let rotation = UnitQuaternion::from_quaternion(Quaternion::new(w, x, y, z));
+    Matrix4::new_translation(&Vec3::from(translation))
+        * rotation.to_homogeneous()
+        * Matrix4::new_nonuniform_scaling(&Vec3::from(scale))
+}
+
+#[inline]
+fn transform_point(m: &Matrix4<f64>, p: &Point3) -> Point3 {
+    m.transform_point(p)
+}
+
+#[inline]
+fn transform_vector(m: &Matrix4<f64>, v: &Vec3) -> Vec3 {
+    m.transform_vector(v)
+}
+
+/// glTF 纹理：按 REPEAT 方式环绕纹理坐标（glTF 的 v 轴向下），乘以系数，可选取单个通道
+#[derive(Debug)]
+struct GltfTexture {
+    image: Arc<ImageTexture>,
+    factor: Color,
+    channel: Option<usize>,
+}
+
+impl Texture for GltfTexture {
+    fn value(&self, u: f64, v: f64, p: &Point3) -> Color {
+        // ImageTexture 的 v 轴向上，翻转后与 glTF 的图像坐标一致
+        let color = self
+            .image
+            .value(u.rem_euclid(1.0), 1.0 - v.rem_euclid(1.0), p);
+        match self.channel {
+            Some(channel) => Color::repeat(color[channel]).component_mul(&self.factor),
+            None => color.component_mul(&self.factor),
+        }
+    }
+}
+
+/// 导入过程的状态
+struct Importer<'a> {
+    doc: &'a Json,
+    source: String,
+    base_dir: PathBuf,
+    options: GltfOptions,
+    buffers: Vec<Vec<u8>>,
+    /// 按 (图像索引, 色彩空间) 缓存：同一图像可能同时作为颜色和数据纹理
+    images: HashMap<(usize, TextureColorSpace), Option<Arc<ImageTexture>>>,
+    materials: HashMap<Option<usize>, MaterialEntry>,
+    /// 重新加载时复用的网格（按遍历顺序）
+    reuse: Option<std::vec::IntoIter<Option<Arc<TriangleMesh>>>>,
+    result: GltfImport,
+}
+
+/// 转换后的材质及其是否发光
+#[derive(Clone)]
+struct MaterialEntry {
+    material: Arc<dyn Material>,
+    emissive: bool,
+}
+
+impl Importer<'_> {
+    fn warn(&mut self, message: String) {
+        if !self.result.warnings.contains(&message) {
+            self.result.warnings.push(message);
+        }
+    }
+
+    fn error(&self, message: &str) -> io::Error {
+        invalid(&self.source, message)
+    }
+
+    /// 外部文件 URI 对应的路径，data URI 为None
+    fn external_path(&self, uri: &str) -> Option<PathBuf> {
+        (!uri.starts_with("data:")).then(|| self.base_dir.join(percent_decode(uri)))
+    }
+
+    /// 读取外部文件或 data URI
+    fn read_uri(&self, uri: &str) -> io::Result<Vec<u8>> {
+        if uri.starts_with("data:") {
+            return decode_data_uri(uri)
+                .ok_or_else(|| self.error("无法解码的 data URI（只支持 base64）"));
+        }
+        std::fs::read(self.base_dir.join(percent_decode(uri)))
+    }
+
+    fn load_buffers(&mut self, mut bin: Option<Vec<u8>>) -> io::Result<()> {
+        for (index, buffer) in self.doc.array_field("buffers").iter().enumerate() {
+            let data = match buffer.get("uri").and_then(Json::as_str) {
+                Some(uri) => {
+                    if let Some(file) = self.external_path(uri) {
+                        self.result.reload.buffer_files.push(file);
+                    }
+                    self.read_uri(uri)?
+                }
+                None if index == 0 => bin
+                    .take()
+                    .ok_or_else(|| self.error("缓冲区0没有 uri，且文件中没有 GLB 二进制块"))?,
+                None => return Err(self.error(&format!("缓冲区{}没有 uri", index))),
+            };
+            let declared = buffer.usize_field("byteLength").unwrap_or(data.len());
+            if data.len() < declared {
+                return Err(self.error(&format!("缓冲区{}的数据比声明的长度短", index)));
+            }
+            self.buffers.push(data);
+        }
+        Ok(())
+    }
+
+    /// 读取访问器为 f64 数组，返回 (数据, 每个元素的分量数)
+    fn read_accessor(&self, index: usize) -> io::Result<(Vec<f64>, usize)> {
+        let accessor = self
+            .doc
+            .array_field("accessors")
+            .get(index)
+            .ok_or_else(|| self.error(&format!("访问器{}不存在", index)))?;
+        let count = accessor.usize_field("count").unwrap_or(0);
+        let components = match accessor.get("type").and_then(Json::as_str) {
+            Some("SCALAR") => 1,
+            Some("VEC2") => 2,
+            Some("VEC3") => 3,
+            Some("VEC4") => 4,
+            Some("MAT4") => 16,
+            other => return Err(self.error(&format!("访问器{}的类型 {:?} 不受支持", index, other))),
+        };
+        let component_type = accessor.usize_field("componentType").unwrap_or(0);
+        let component_size = match component_type {
+            5120 | 5121 => 1,
+            5122 | 5123 => 2,
+            5125 | 5126 => 4,
+            other => {
+                return Err(self.error(&format!("访问器{}的分量类型 {} 不受支持", index, other)));
+            }
+        };
+        let normalized = accessor
+            .get("normalized")
+            .and_then(Json::as_bool)
+            .unwrap_or(false);
+        if accessor.get("sparse").is_some() {
+            return Err(self.error(&format!("访问器{}使用了稀疏存储，暂不支持", index)));
+        }
+
+        // 没有缓冲区视图的访问器全部为0，元素数量不受任何数据约束，需单独设上限
+        let Some(view_index) = accessor.usize_field("bufferView") else {
+            if count > MAX_ZERO_ACCESSOR_COUNT {
+                return Err(self.error(&format!(
+                    "访问器{}没有缓冲区视图，元素数量 {} 超过上限 {}",
+                    index, count, MAX_ZERO_ACCESSOR_COUNT
+                )));
+            }
+            return Ok((vec![0.0; count * components], components));
+        };
+        let view = self
+            .doc
+            .array_field("bufferViews")
+            .get(view_index)
+            .ok_or_else(|| self.error(&format!("缓冲区视图{}不存在", view_index)))?;
+        let buffer = view
+            .usize_field("buffer")
+            .and_then(|b| self.buffers.get(b))
+            .ok_or_else(|| self.error(&format!("缓冲区视图{}引用了不存在的缓冲区", view_index)))?;
+        let element_size = component_size * components;
+        let stride = view
+            .usize_field("byteStride")
+            .filter(|&s| s > 0)
+            .unwrap_or(element_size);
+        let view_offset = view.usize_field("byteOffset").unwrap_or(0);
+        let out_of_range = || self.error(&format!("访问器{}超出缓冲区范围", index));
+        let start = view_offset
+            .checked_add(accessor.usize_field("byteOffset").unwrap_or(0))
+            .ok_or_else(out_of_range)?;
+        let view_end = view_offset
+            .checked_add(view.usize_field("byteLength").unwrap_or(0))
+            .ok_or_else(out_of_range)?
+            .min(buffer.len());
+        // 最后一个元素的末尾必须落在视图和缓冲区之内，这同时把 count 限制在缓冲区长度以内
+        if count > 0 {
+            let end = (count - 1)
+                .checked_mul(stride)
+                .and_then(|offset| offset.checked_add(start))
+                .and_then(|last| last.checked_add(element_size))
+                .ok_or_else(out_of_range)?;
+            if end > view_end {
+                return Err(out_of_range());
+            }
+        }
+
+        let mut data = Vec::with_capacity(count * components);
+        for i in 0..count {
+            let base = start + i * stride;
+            for c in 0..components {
+                let at = base + c * component_size;
+                let b = &buffer[at..at + component_size];
+                let value = match component_type {
+                    5120 => {
+                        let x = b[0] as i8 as f64;
+                        if normalized { (x / 127.0).max(-1.0) } else { x }
+                    }
+                    5121 => {
+                        let x = b[0] as f64;
+                        if normalized { x / 255.0 } else { x }
+                    }
+                    5122 => {
+                        let x = i16::from_le_bytes([b[0], b[1]]) as f64;
+                        if normalized {
+                            (x / 32767.0).max(-1.0)
+                        } else {
+                            x
+                        }
+                    }
+                    5123 => {
+                        let x = u16::from_le_bytes([b[0], b[1]]) as f64;
+                        if normalized { x / 65535.0 } else { x }
+                    }
+                    5125 => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                    _ => f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                };
+                data.push(value);
+            }
+        }
+        Ok((data, components))
+    }
+
+    /// 读取顶点属性访问器，要求每个元素至少有 `min_components` 个分量
+    fn read_vectors(
+        &self,
+        index: usize,
+        attribute: &str,
+        min_components: usize,
+    ) -> io::Result<(Vec<f64>, usize)> {
+        let (data, components) = self.read_accessor(index)?;
+        if components < min_components {
+            return Err(self.error(&format!(
+                "{} 访问器{}只有 {} 个分量，至少需要 {} 个",
+                attribute, index, components, min_components
+            )));
+        }
+        Ok((data, components))
+    }
+
+    /// 加载图像（按图像索引和色彩空间缓存，加载失败时记录警告并返回None）
+    fn image(&mut self, index: usize, color_space: TextureColorSpace) -> Option<Arc<ImageTexture>> {
+        if let Some(cached) = self.images.get(&(index, color_space)) {
+            return cached.clone();
+        }
+
+        if let Some(file) = self
+            .doc
+            .array_field("images")
+            .get(index)
+            .and_then(|image| image.get("uri"))
+            .and_then(Json::as_str)
+            .and_then(|uri| self.external_path(uri))
+            && !self.result.reload.image_files.contains(&file)
+        {
+            self.result.reload.image_files.push(file);
+        }
+        let loaded = self.load_image(index);
+        let image = match loaded {
+            Ok(img) => Some(Arc::new(ImageTexture::from_image_with_color_space(
+                img,
+                color_space,
+            ))),
+            Err(e) => {
+                self.warn(format!("无法加载图像{}: {}", index, e));
+                None
+            }
+        };
+        self.images.insert((index, color_space), image.clone());
+        image
+    }
+
+    fn load_image(&self, index: usize) -> io::Result<image::DynamicImage> {
+        let image = self
+            .doc
+            .array_field("images")
+            .get(index)
+            .ok_or_else(|| self.error(&format!("图像{}不存在", index)))?;
+        let bytes = if let Some(uri) = image.get("uri").and_then(Json::as_str) {
+            self.read_uri(uri)?
+        } else {
+            let view = image
+                .usize_field("bufferView")
+                .and_then(|v| self.doc.array_field("bufferViews").get(v))
+                .ok_or_else(|| self.error(&format!("图像{}没有数据来源", index)))?;
+            let buffer = view
+                .usize_field("buffer")
+                .and_then(|b| self.buffers.get(b))
+                .ok_or_else(|| self.error(&format!("图像{}引用了不存在的缓冲区", index)))?;
+            let offset = view.usize_field("byteOffset").unwrap_or(0);
+            let length = view.usize_field("byteLength").unwrap_or(0);
+            buffer
+                .get(offset..offset + length)
+                .ok_or_else(|| self.error(&format!("图像{}超出缓冲区范围", index)))?
+                .to_vec()
+        };
+        image::load_from_memory(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// 纹理信息（`{"index": n, "texCoord": 0}`）对应的纹理，乘以 factor
+    ///
+    /// 按 glTF 规范，颜色纹理（基础色、发光）为 sRGB 编码，取单个通道的数据纹理为线性值。
+    fn texture(
+        &mut self,
+        info: Option<&Json>,
+        factor: Color,
+        channel: Option<usize>,
+    ) -> Option<TexturePtr> {
+        let info = info?;
+        if info.usize_field("texCoord").unwrap_or(0) != 0 {
+            self.warn("只支持第一套纹理坐标（TEXCOORD_0）".to_string());
+        }
+        let source = info
+            .usize_field("index")
+            .and_then(|t| self.doc.array_field("textures").get(t))
+            .and_then(|t| t.usize_field("source"))?;
+        let color_space = match channel {
+            Some(_) => TextureColorSpace::Linear,
+            None => TextureColorSpace::Srgb,
+        };
+        let image = self.image(source, color_space)?;
+        Some(Arc::new(GltfTexture {
+            image,
+            factor,
+            channel,
+        }))
+    }
+
+    /// 金属度-粗糙度材质及其扩展（清漆、光泽层、镜面反射强度）对应的原则化材质
+    fn principled(
+        &mut self,
+        pbr: &Json,
+        extensions: &Json,
+        base_color: Color,
+        roughness: f64,
+        metallic: f64,
+        ior: f64,
+    ) -> PrincipledMaterial {
+        let albedo = self
+            .texture(pbr.get("baseColorTexture"), base_color, None)
+            .unwrap_or_else(|| Arc::new(SolidColor::new(base_color)));
+        // 金属度-粗糙度纹理：G 通道为粗糙度，B 通道为金属度
+        let mr_info = pbr.get("metallicRoughnessTexture");
+        let roughness_tex = self
+            .texture(mr_info, Color::repeat(roughness), Some(1))
+            .unwrap_or_else(|| Arc::new(SolidColor::new(Color::repeat(roughness))));
+        let metallic_tex = self
+            .texture(mr_info, Color::repeat(metallic), Some(2))
+            .unwrap_or_else(|| Arc::new(SolidColor::new(Color::repeat(metallic))));
+        let mut material = PrincipledMaterial::new(albedo, roughness_tex, metallic_tex);
+
+        // 镜面反射强度：垂直入射反射率由折射率决定，再乘以 specularFactor（0.5 对应 4%）
+        let specular_factor = extensions
+            .get("KHR_materials_specular")
+            .and_then(|e| e.f64_field("specularFactor"))
+            .unwrap_or(1.0);
+        let f0 = ((ior - 1.0) / (ior + 1.0)).powi(2);
+        material = material.with_specular(f0 / 0.08 * specular_factor, 0.0);
+
+        if let Some(clearcoat) = extensions.get("KHR_materials_clearcoat") {
+            let factor = clearcoat.f64_field("clearcoatFactor").unwrap_or(0.0);
+            let roughness = clearcoat
+                .f64_field("clearcoatRoughnessFactor")
+                .unwrap_or(0.0);
+            material = material.with_clearcoat(factor, 1.0 - roughness);
+        }
+        // 光泽层颜色取最大分量作为强度，按白色光泽处理
+        if let Some(sheen) = extensions.get("KHR_materials_sheen") {
+            let color = sheen.f64_array::<3>("sheenColorFactor").unwrap_or([0.0; 3]);
+            material = material.with_sheen(color[0].max(color[1]).max(color[2]), 0.0);
+        }
+        material
+    }
+
+    /// 把 glTF 材质转换为本crate的材质（按材质索引缓存，None为默认材质）
+    fn material(&mut self, index: Option<usize>) -> MaterialEntry {
+        if let Some(entry) = self.materials.get(&index) {
+            return entry.clone();
+        }
+        let doc = self.doc;
+        let empty = Json::Object(Vec::new());
+        let mat = index
+            .and_then(|i| doc.array_field("materials").get(i))
+            .unwrap_or(&empty);
+        let name = mat
+            .get("name")
+            .and_then(Json::as_str)
+            .map_or_else(|| format!("{:?}", index), str::to_string);
+        let pbr = mat.get("pbrMetallicRoughness").unwrap_or(&empty);
+        let extensions = mat.get("extensions").unwrap_or(&empty);
+
+        let base = pbr.f64_array::<4>("baseColorFactor").unwrap_or([1.0; 4]);
+        let base_color = Color::new(base[0], base[1], base[2]);
+        let metallic = pbr
+            .f64_field("metallicFactor")
+            .unwrap_or(1.0)
+            .clamp(0.0, 1.0);
+        let roughness = pbr
+            .f64_field("roughnessFactor")
+            .unwrap_or(1.0)
+            .clamp(0.0, 1.0);
+
+        match mat.get("alphaMode").and_then(Json::as_str) {
+            Some("MASK") | Some("BLEND") => {
+                self.warn(format!("材质 {} 的透明模式被忽略，按不透明渲染", name))
+            }
+            _ => {}
+        }
+
+        // 发光：emissiveFactor × KHR_materials_emissive_strength
+        let emissive = mat.f64_array::<3>("emissiveFactor").unwrap_or([0.0; 3]);
+        let strength = extensions
+            .get("KHR_materials_emissive_strength")
+            .and_then(|e| e.f64_field("emissiveStrength"))
+            .unwrap_or(1.0);
+        let emission = Color::from(emissive) * strength;
+        let entry = if emission.max() > 0.0 {
+            let texture = self.texture(mat.get("emissiveTexture"), emission, None);
+            let light = match texture {
+                Some(texture) => DiffuseLight::new(texture),
+                None => DiffuseLight::new_color(emission),
+            };
+            MaterialEntry {
+                material: Arc::new(light),
+                emissive: true,
+            }
+        } else {
+            let transmission = extensions
+                .get("KHR_materials_transmission")
+                .and_then(|t| t.f64_field("transmissionFactor"))
+                .unwrap_or(0.0)
+                .clamp(0.0, 1.0);
+            let ior = extensions
+                .get("KHR_materials_ior")
+                .and_then(|e| e.f64_field("ior"))
+                .unwrap_or(1.5);
+            let material: Arc<dyn Material> = if transmission >= 1.0
+                && roughness <= SMOOTH_ROUGHNESS
+                && pbr.get("baseColorTexture").is_none()
+            {
+                // 光滑的完全透射材质按镜面散射处理，噪声远小于微表面透射
+                Arc::new(Dielectric::new(ior))
+            } else {
+                Arc::new(
+                    self.principled(pbr, extensions, base_color, roughness, metallic, ior)
+                        .with_transmission(transmission, ior),
+                )
+            };
+            MaterialEntry {
+                material,
+                emissive: false,
+            }
+        };
+
+        self.materials.insert(index, entry.clone());
+        entry
+    }
+
+    /// 遍历默认场景（没有场景时遍历所有根节点）
+    fn import_scene(&mut self) -> io::Result<()> {
+        let doc = self.doc;
+        let nodes = doc.array_field("nodes");
+        let roots: Vec<usize> = match doc
+            .usize_field("scene")
+            .or_else(|| (!doc.array_field("scenes").is_empty()).then_some(0))
+            .and_then(|s| doc.array_field("scenes").get(s))
+        {
+            Some(scene) => scene
+                .array_field("nodes")
+                .iter()
+                .filter_map(Json::as_usize)
+                .collect(),
+            None => {
+                let mut is_child = vec![false; nodes.len()];
+                for node in nodes {
+                    for child in node
+                        .array_field("children")
+                        .iter()
+                        .filter_map(Json::as_usize)
+                    {
+                        if let Some(flag) = is_child.get_mut(child) {
+                            *flag = true;
+                        }
+                    }
+                }
+                (0..nodes.len()).filter(|&i| !is_child[i]).collect()
+            }
+        };
+
+        let mut stack: Vec<(usize, Matrix4<f64>, usize)> = roots
+            .into_iter()
+            .rev()
+            .map(|n| (n, Matrix4::identity(), 0))
+            .collect();
+        while let Some((index, parent, depth)) = stack.pop() {
+            let node = nodes
+                .get(index)
+                .ok_or_else(|| self.error(&format!("节点{}不存在", index)))?;
+            if depth > nodes.len() {
+                return Err(self.error("节点层级中存在循环"));
+            }
+            let world = parent * node_transform(node);
+
+            if let Some(mesh) = node.usize_field("mesh") {
+                self.import_mesh(mesh, &world)?;
+            }
+            if let Some(camera) = node.usize_field("camera") {
+                self.import_camera(camera, node, &world);
+            }
+            if let Some(light) = node
+                .get("extensions")
+                .and_then(|e| e.get("KHR_lights_punctual"))
+                .and_then(|l| l.usize_field("light"))
+            {
+                self.import_light(light, &world);
+            }
+
+            for child in node
+                .array_field("children")
+                .iter()
+                .rev()
+                .filter_map(Json::as_usize)
+            {
+                stack.push((child, world, depth + 1));
+            }
+        }
+        Ok(())
+    }
+
+    fn import_mesh(&mut self, index: usize, world: &Matrix4<f64>) -> io::Result<()> {
+        let doc = self.doc;
+        let mesh = doc
+            .array_field("meshes")
+            .get(index)
+            .ok_or_else(|| self.error(&format!("网格{}不存在", index)))?;
+        // 法线按逆转置矩阵变换
+        let normal_matrix: Matrix3<f64> = world
+            .fixed_view::<3, 3>(0, 0)
+            .into_owned()
+            .try_inverse()
+            .map_or_else(Matrix3::identity, |m| m.transpose());
+
+        for primitive in mesh.array_field("primitives") {
+            let mode = primitive.usize_field("mode").unwrap_or(4);
+            if !(4..=6).contains(&mode) {
+                self.warn(format!(
+                    "网格{}包含点或线图元（模式 {}），已跳过",
+                    index, mode
+                ));
+                continue;
+            }
+            let attributes = primitive.get("attributes");
+            let Some(position) = attributes.and_then(|a| a.usize_field("POSITION")) else {
+                self.warn(format!("网格{}的图元没有 POSITION 属性，已跳过", index));
+                continue;
+            };
+
+            let entry = self.material(primitive.usize_field("material"));
+            let geometry = match self.reuse.as_mut().map(Iterator::next) {
+                Some(Some(cached)) => cached,
+                Some(None) => return Err(self.error("重新加载时网格数量与上次不一致")),
+                None => {
+                    let data =
+                        self.primitive_data(primitive, mode, position, world, &normal_matrix)?;
+                    let levels = self.options.subdivision.levels_for(&data);
+                    let data = loop_subdivide(data, levels);
+                    let mesh = TriangleMesh::new(&data, entry.material.clone());
+                    (!mesh.is_empty()).then(|| Arc::new(mesh))
+                }
+            };
+            self.result.reload.meshes.push(geometry.clone());
+            let Some(geometry) = geometry else {
+                continue;
+            };
+
+            let mesh: Arc<dyn Hittable> = Arc::new(geometry.with_material(entry.material));
+            if entry.emissive {
+                self.result.scene.add_emitter(mesh);
+            } else {
+                self.result.scene.add(mesh);
+            }
+        }
+        Ok(())
+    }
+
+    /// 读取图元的顶点数据，变换到世界空间并把三角带、三角扇展开为三角形列表
+    fn primitive_data(
+        &self,
+        primitive: &Json,
+        mode: usize,
+        position: usize,
+        world: &Matrix4<f64>,
+        normal_matrix: &Matrix3<f64>,
+    ) -> io::Result<MeshData> {
+        let attributes = primitive.get("attributes");
+        let mut data = MeshData::default();
+        let (positions, n) = self.read_vectors(position, "POSITION", 3)?;
+        data.positions = positions
+            .chunks_exact(n)
+            .map(|p| transform_point(world, &Point3::new(p[0], p[1], p[2])))
+            .collect();
+        if let Some(normal) = attributes.and_then(|a| a.usize_field("NORMAL")) {
+            let (normals, n) = self.read_vectors(normal, "NORMAL", 3)?;
+            data.normals = normals
+                .chunks_exact(n)
+                .map(|v| normal_matrix * Vec3::new(v[0], v[1], v[2]))
+                .collect();
+        }
+        if let Some(texcoord) = attributes.and_then(|a| a.usize_field("TEXCOORD_0")) {
+            let (uvs, n) = self.read_vectors(texcoord, "TEXCOORD_0", 2)?;
+            data.uvs = uvs.chunks_exact(n).map(|t| (t[0], t[1])).collect();
+        }
+
+        let indices: Vec<usize> = match primitive.usize_field("indices") {
+            Some(accessor) => self
+                .read_accessor(accessor)?
+                .0
+                .into_iter()
+                .map(|i| i as usize)
+                .collect(),
+            None => (0..data.positions.len()).collect(),
+        };
+        data.indices = match mode {
+            4 => indices
+                .chunks_exact(3)
+                .map(|f| [f[0], f[1], f[2]])
+                .collect(),
+            // 三角带：奇数三角形交换顶点顺序以保持朝向一致
+            5 => (2..indices.len())
+                .map(|i| {
+                    if i % 2 == 0 {
+                        [indices[i - 2], indices[i - 1], indices[i]]
+                    } else {
+                        [indices[i - 1], indices[i - 2], indices[i]]
+                    }
+                })
+                .collect(),
+            _ => (2..indices.len())
+                .map(|i| [indices[0], indices[i - 1], indices[i]])
+                .collect(),
+        };
+        Ok(data)
+    }
+
+    fn import_camera(&mut self, index: usize, node: &Json, world: &Matrix4<f64>) {
+        let Some(camera) = self.doc.array_field("cameras").get(index) else {
+            self.warn(format!("相机{}不存在", index));
+            return;
+        };
+        let Some(perspective) = camera.get("perspective") else {
+            self.warn(format!("相机{}不是透视相机，已跳过", index));
+            return;
+        };
+
+        // glTF 相机看向局部 -Z，上方为 +Y
+        let lookfrom = transform_point(world, &Point3::origin());
+        let forward = transform_vector(world, &Vec3::new(0.0, 0.0, -1.0));
+        let up = transform_vector(world, &Vec3::new(0.0, 1.0, 0.0));
+        if forward.norm_squared() == 0.0 || up.norm_squared() == 0.0 {
+            self.warn(format!("相机{}的变换退化，已跳过", index));
+            return;
+        }
+        let yfov = perspective.f64_field("yfov").unwrap_or(PI / 4.0);
+        self.result.cameras.push(GltfCamera {
+            name: node
+                .get("name")
+                .or_else(|| camera.get("name"))
+                .and_then(Json::as_str)
+                .map(str::to_string),
+            lookfrom,
+            lookat: lookfrom + forward.normalize(),
+            vup: up.normalize(),
+            vfov: yfov.to_degrees(),
+            aspect_ratio: perspective.f64_field("aspectRatio"),
+        });
+    }
+
+    fn import_light(&mut self, index: usize, world: &Matrix4<f64>) {
+        let Some(light) = self
+            .doc
+            .get("extensions")
+            .and_then(|e| e.get("KHR_lights_punctual"))
+            .map(|l| l.array_field("lights"))
+            .and_then(|lights| lights.get(index))
+        else {
+            self.warn(format!("光源{}不存在", index));
+            return;
+        };
+        let color = Color::from(light.f64_array::<3>("color").unwrap_or([1.0; 3]));
+        let intensity = light.f64_field("intensity").unwrap_or(1.0);
+
+        // 半径为 r、辐射度为 L 的球体在各方向的发光强度为 π r² L
+        let radius = self.options.light_radius.max(1e-6);
+        let radiance = color * (intensity / (PI * radius * radius));
+        let center = transform_point(world, &Point3::origin());
+
+        let material: Arc<dyn Material> = match light.get("type").and_then(Json::as_str) {
+            Some("point") => Arc::new(DiffuseLight::new_color(radiance)),
+            Some("spot") => {
+                let spot = light.get("spot");
+                let inner = spot
+                    .and_then(|s| s.f64_field("innerConeAngle"))
+                    .unwrap_or(0.0);
+                let outer = spot
+                    .and_then(|s| s.f64_field("outerConeAngle"))
+                    .unwrap_or(PI / 4.0);
+                let direction = transform_vector(world, &Vec3::new(0.0, 0.0, -1.0));
+                Arc::new(SpotLight::new_color(
+                    radiance,
+                    direction,
+                    inner.to_degrees(),
+                    outer.to_degrees(),
+                ))
+            }
+            other => {
+                self.warn(format!("不支持的光源类型 {:?}，已跳过", other));
+                return;
+            }
+        };
+        self.result
+            .scene
+            .add_emitter(Arc::new(Sphere::new(center, radius, material)));
+    }
+}
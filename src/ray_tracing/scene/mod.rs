@@ -0,0 +1,7 @@
+pub mod gltf;
+pub mod point_data;
+pub mod ray_cast;
+pub mod scatter;
+pub mod scene_file;
+pub mod watch;
+pub mod world;
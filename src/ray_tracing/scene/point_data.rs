@@ -0,0 +1,389 @@
+//! 点云数据读取（`.xyz` 文本和 `.ply` 文本/二进制格式）
+//!
+//! - XYZ：每行 `x y z`，可选 `r g b` 颜色和 `nx ny nz` 法线（共3、6或9列），`#` 开头的行为注释；
+//!   任一颜色分量大于1时整个文件的颜色按 0..255 解释
+//! - PLY：读取 `vertex` 元素的 `x y z`、`nx ny nz` 和 `red green blue`（整数类型按最大值归一化），
+//!   其他元素（如面）被跳过
+//!
+//! 颜色与图像纹理一样按原值使用，不做 sRGB 解码。
+
+use crate::ray_tracing::geometry::point_cloud::CloudPoint;
+use crate::ray_tracing::math::vec3::*;
+use std::io;
+use std::path::Path;
+
+/// 没有颜色的点使用的颜色
+const DEFAULT_COLOR: f64 = 0.7;
+
+/// 按扩展名读取点云文件
+pub fn load_points(path: impl AsRef<Path>) -> io::Result<Vec<CloudPoint>> {
+    let path = path.as_ref();
+    let source = path.display().to_string();
+    let bytes = std::fs::read(path)?;
+    let is_ply = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("ply"));
+    if is_ply {
+        parse_ply(&bytes, &source)
+    } else {
+        let text = std::str::from_utf8(&bytes).map_err(|_| invalid(&source, "不是 UTF-8 文本"))?;
+        parse_xyz(text, &source)
+    }
+}
+
+fn invalid(source: &str, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", source, message),
+    )
+}
+
+/// 解析 XYZ 文本
+pub fn parse_xyz(text: &str, source: &str) -> io::Result<Vec<CloudPoint>> {
+    let mut points = Vec::new();
+    let mut max_color: f64 = 0.0;
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+            continue;
+        }
+        let values: Vec<f64> = line
+            .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| invalid(source, &format!("第{}行包含无法解析的数值", index + 1)))?;
+
+        let (color, normal) = match values.len() {
+            3 => (None, None),
+            6 => (Some(Color::new(values[3], values[4], values[5])), None),
+            9 => (
+                Some(Color::new(values[3], values[4], values[5])),
+                Some(Vec3::new(values[6], values[7], values[8])),
+            ),
+            n => {
+                return Err(invalid(
+                    source,
+                    &format!("第{}行有{}列，应为3、6或9列", index + 1, n),
+                ));
+            }
+        };
+        if let Some(color) = color {
+            max_color = max_color.max(color.max());
+        }
+        points.push(CloudPoint {
+            position: Point3::new(values[0], values[1], values[2]),
+            normal,
+            color: color.unwrap_or(Color::repeat(DEFAULT_COLOR)),
+        });
+    }
+
+    if max_color > 1.0 {
+        for point in &mut points {
+            point.color /= 255.0;
+        }
+    }
+    Ok(points)
+}
+
+/// PLY 数据的存储格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlyFormat {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+/// PLY 标量类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlyType {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl PlyType {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "char" | "int8" => Self::I8,
+            "uchar" | "uint8" => Self::U8,
+            "short" | "int16" => Self::I16,
+            "ushort" | "uint16" => Self::U16,
+            "int" | "int32" => Self::I32,
+            "uint" | "uint32" => Self::U32,
+            "float" | "float32" => Self::F32,
+            "double" | "float64" => Self::F64,
+            _ => return None,
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Self::I8 | Self::U8 => 1,
+            Self::I16 | Self::U16 => 2,
+            Self::I32 | Self::U32 | Self::F32 => 4,
+            Self::F64 => 8,
+        }
+    }
+
+    /// 颜色归一化时使用的最大值，浮点类型为1
+    fn color_scale(self) -> f64 {
+        match self {
+            Self::I8 => 127.0,
+            Self::U8 => 255.0,
+            Self::I16 => 32767.0,
+            Self::U16 => 65535.0,
+            Self::I32 => i32::MAX as f64,
+            Self::U32 => u32::MAX as f64,
+            Self::F32 | Self::F64 => 1.0,
+        }
+    }
+}
+
+/// PLY 属性：标量或列表（列表的长度类型和元素类型）
+#[derive(Debug, Clone)]
+struct PlyProperty {
+    name: String,
+    kind: PlyType,
+    list_count: Option<PlyType>,
+}
+
+#[derive(Debug, Clone)]
+struct PlyElement {
+    name: String,
+    count: usize,
+    properties: Vec<PlyProperty>,
+}
+
+impl PlyElement {
+    /// 单个元素实例在数据体中至少占用的字节数（文本格式每个数值至少一个字符加一个分隔符）
+    fn min_row_bytes(&self, format: PlyFormat) -> usize {
+        self.properties
+            .iter()
+            .map(|p| match (format, p.list_count) {
+                (PlyFormat::Ascii, _) => 2,
+                (_, Some(count_type)) => count_type.size(),
+                (_, None) => p.kind.size(),
+            })
+            .sum::<usize>()
+            .max(1)
+    }
+}
+
+/// 逐个读取 PLY 数据体中的数值
+struct PlyReader<'a> {
+    format: PlyFormat,
+    body: &'a [u8],
+    offset: usize,
+    /// 文本格式的数据行
+    lines: std::str::Lines<'a>,
+    /// 文本格式下当前行剩余的字段
+    tokens: std::vec::IntoIter<&'a str>,
+    source: &'a str,
+}
+
+impl<'a> PlyReader<'a> {
+    fn read(&mut self, kind: PlyType) -> io::Result<f64> {
+        if self.format == PlyFormat::Ascii {
+            let token = self
+                .tokens
+                .next()
+                .ok_or_else(|| invalid(self.source, "顶点数据不完整"))?;
+            return token
+                .parse()
+                .map_err(|_| invalid(self.source, &format!("无法解析数值 \"{}\"", token)));
+        }
+
+        let size = kind.size();
+        let bytes = self
+            .body
+            .get(self.offset..self.offset + size)
+            .ok_or_else(|| invalid(self.source, "数据长度不足"))?;
+        self.offset += size;
+        let mut buf = [0u8; 8];
+        buf[..size].copy_from_slice(bytes);
+        if self.format == PlyFormat::BinaryBigEndian {
+            buf[..size].reverse();
+        }
+        Ok(match kind {
+            PlyType::I8 => buf[0] as i8 as f64,
+            PlyType::U8 => buf[0] as f64,
+            PlyType::I16 => i16::from_le_bytes([buf[0], buf[1]]) as f64,
+            PlyType::U16 => u16::from_le_bytes([buf[0], buf[1]]) as f64,
+            PlyType::I32 => i32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64,
+            PlyType::U32 => u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64,
+            PlyType::F32 => f32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64,
+            PlyType::F64 => f64::from_le_bytes(buf),
+        })
+    }
+
+    /// 读取一个元素实例的全部属性，列表属性只读取并丢弃
+    fn read_element(&mut self, element: &PlyElement, values: &mut Vec<f64>) -> io::Result<()> {
+        if self.format == PlyFormat::Ascii {
+            let line = self
+                .lines
+                .find(|line| !line.trim().is_empty())
+                .ok_or_else(|| invalid(self.source, "元素数量多于数据行数"))?;
+            self.tokens = line.split_whitespace().collect::<Vec<_>>().into_iter();
+        }
+
+        values.clear();
+        for property in &element.properties {
+            match property.list_count {
+                Some(count_type) => {
+                    let count = self.read(count_type)? as usize;
+                    for _ in 0..count {
+                        self.read(property.kind)?;
+                    }
+                    values.push(f64::NAN);
+                }
+                None => values.push(self.read(property.kind)?),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 解析 PLY 文件
+pub fn parse_ply(bytes: &[u8], source: &str) -> io::Result<Vec<CloudPoint>> {
+    const END_HEADER: &[u8] = b"end_header";
+    let header_end = bytes
+        .windows(END_HEADER.len())
+        .position(|w| w == END_HEADER)
+        .ok_or_else(|| invalid(source, "缺少 end_header"))?;
+    let body_start = bytes[header_end..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(bytes.len(), |i| header_end + i + 1);
+    let header = std::str::from_utf8(&bytes[..header_end])
+        .map_err(|_| invalid(source, "文件头不是 UTF-8 文本"))?;
+
+    let mut lines = header.lines().map(str::trim);
+    if lines.next() != Some("ply") {
+        return Err(invalid(source, "不是 PLY 文件"));
+    }
+    let mut format = None;
+    let mut elements: Vec<PlyElement> = Vec::new();
+    for line in lines {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["format", name, ..] => {
+                format = Some(match *name {
+                    "ascii" => PlyFormat::Ascii,
+                    "binary_little_endian" => PlyFormat::BinaryLittleEndian,
+                    "binary_big_endian" => PlyFormat::BinaryBigEndian,
+                    other => return Err(invalid(source, &format!("不支持的格式 {}", other))),
+                });
+            }
+            ["element", name, count] => elements.push(PlyElement {
+                name: name.to_string(),
+                count: count
+                    .parse()
+                    .map_err(|_| invalid(source, &format!("元素 {} 的数量无效", name)))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count_type, kind, name] => {
+                let element = elements
+                    .last_mut()
+                    .ok_or_else(|| invalid(source, "属性出现在元素之前"))?;
+                let (Some(count_type), Some(kind)) =
+                    (PlyType::parse(count_type), PlyType::parse(kind))
+                else {
+                    return Err(invalid(source, &format!("属性 {} 的类型无效", name)));
+                };
+                element.properties.push(PlyProperty {
+                    name: name.to_string(),
+                    kind,
+                    list_count: Some(count_type),
+                });
+            }
+            ["property", kind, name] => {
+                let element = elements
+                    .last_mut()
+                    .ok_or_else(|| invalid(source, "属性出现在元素之前"))?;
+                let kind = PlyType::parse(kind)
+                    .ok_or_else(|| invalid(source, &format!("属性 {} 的类型无效", name)))?;
+                element.properties.push(PlyProperty {
+                    name: name.to_string(),
+                    kind,
+                    list_count: None,
+                });
+            }
+            _ => {} // comment、obj_info 等
+        }
+    }
+    let format = format.ok_or_else(|| invalid(source, "缺少 format 行"))?;
+
+    let body = &bytes[body_start..];
+    let text = if format == PlyFormat::Ascii {
+        std::str::from_utf8(body).map_err(|_| invalid(source, "文本数据不是 UTF-8"))?
+    } else {
+        ""
+    };
+    let mut reader = PlyReader {
+        format,
+        body,
+        offset: 0,
+        lines: text.lines(),
+        tokens: Vec::new().into_iter(),
+        source,
+    };
+    let mut values = Vec::new();
+    for element in &elements {
+        if element.name != "vertex" {
+            // 跳过顶点之前的其他元素
+            for _ in 0..element.count {
+                reader.read_element(element, &mut values)?;
+            }
+            continue;
+        }
+
+        let find = |names: &[&str]| {
+            element
+                .properties
+                .iter()
+                .position(|p| p.list_count.is_none() && names.contains(&p.name.as_str()))
+        };
+        let (Some(x), Some(y), Some(z)) = (find(&["x"]), find(&["y"]), find(&["z"])) else {
+            return Err(invalid(source, "vertex 元素缺少 x/y/z 属性"));
+        };
+        let normal = match (find(&["nx"]), find(&["ny"]), find(&["nz"])) {
+            (Some(nx), Some(ny), Some(nz)) => Some([nx, ny, nz]),
+            _ => None,
+        };
+        let color = match (
+            find(&["red", "r", "diffuse_red"]),
+            find(&["green", "g", "diffuse_green"]),
+            find(&["blue", "b", "diffuse_blue"]),
+        ) {
+            (Some(r), Some(g), Some(b)) => Some([r, g, b]),
+            _ => None,
+        };
+
+        // 元素数量来自文件头，预分配时以数据体能容纳的实例数为上限
+        let capacity = element
+            .count
+            .min(body.len() / element.min_row_bytes(reader.format));
+        let mut points = Vec::with_capacity(capacity);
+        for _ in 0..element.count {
+            reader.read_element(element, &mut values)?;
+            points.push(CloudPoint {
+                position: Point3::new(values[x], values[y], values[z]),
+                normal: normal.map(|[nx, ny, nz]| Vec3::new(values[nx], values[ny], values[nz])),
+                color: color.map_or(Color::repeat(DEFAULT_COLOR), |channels| {
+                    Color::from_fn(|i, _| {
+                        let index = channels[i];
+                        values[index] / element.properties[index].kind.color_scale()
+                    })
+                }),
+            });
+        }
+        return Ok(points);
+    }
+    Err(invalid(source, "文件中没有 vertex 元素"))
+}
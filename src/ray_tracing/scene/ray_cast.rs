@@ -0,0 +1,199 @@
+//! 与渲染无关的光线查询：最近交点和遮挡测试
+//!
+//! 用于碰撞检测、激光雷达模拟、可见性预计算等不需要相机和材质着色的场合。
+//! 最近交点查询只考虑几何；遮挡查询与渲染一致地穿过透明度遮罩镂空的部分（树叶贴图的空隙不挡光），
+//! 但玻璃等透明材质仍算遮挡。光线从 t = 0.001 开始，与渲染时的自相交偏移一致。
+//! 透过率查询（[`RayCast::trace_transmittance`]）供直接光照的阴影光线使用（光照探针、相机的直接光照预览和开启透明阴影的路径追踪），
+//! 还穿过电介质等透明材质，只有不透明的表面完全遮挡。
+//!
+//! 场景用 `Scene::ray_caster()` 构建一次加速结构后反复查询；已有的 BVH 等加速结构通过 [`RayCast`] 直接查询。
+//! 用法和性能见 `examples/ray_cast_bench.rs`。
+
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::{Ray, RayKind};
+use crate::ray_tracing::math::vec3::{Color, Point3};
+use crate::ray_tracing::utils::random::random_double;
+use rayon::prelude::*;
+use std::sync::Arc;
+
+/// 查询区间的起点（光线参数 t），避免从表面出发的光线与自身相交
+pub const RAY_EPSILON: f64 = 0.001;
+
+/// 透过率查询最多穿过的透明表面数，超过后视为被遮挡
+const MAX_TRANSPARENT_HITS: u32 = 64;
+/// 透过率低于此值后开始俄罗斯轮盘
+const ROULETTE_THRESHOLD: f64 = 0.25;
+
+/// 光线查询，对所有可被击中的物体（BVH、k-d 树、物体列表等）自动实现
+pub trait RayCast {
+    /// 光线的最近交点，未命中时为None
+    fn trace_nearest(&self, ray: &Ray) -> Option<HitRecord>;
+
+    /// 距离光线起点 max_dist（按世界空间长度计，与方向向量的长度无关）以内是否有遮挡
+    ///
+    /// 透明度遮罩按覆盖比例随机穿过（与渲染时的透明度测试相同），完全镂空的表面不遮挡。
+    fn trace_occluded(&self, ray: &Ray, max_dist: f64) -> bool;
+
+    /// 距离光线起点 max_dist 以内各表面透过率的乘积，被不透明表面挡住时为0
+    ///
+    /// 阴影光线沿直线穿过透明表面（不计折射偏折），每个表面的透过率由材质的
+    /// [`shadow_transmittance`](crate::ray_tracing::materials::material::Material::shadow_transmittance)
+    /// 和透明度遮罩给出。透过率降到较低后按俄罗斯轮盘随机终止，继续的路径除以存活概率，期望值不变。
+    fn trace_transmittance(&self, ray: &Ray, max_dist: f64) -> Color;
+}
+
+impl<T: Hittable + ?Sized> RayCast for T {
+    fn trace_nearest(&self, ray: &Ray) -> Option<HitRecord> {
+        let mut rec = HitRecord::default();
+        self.hit(ray, Interval::new(RAY_EPSILON, f64::INFINITY), &mut rec)
+            .then_some(rec)
+    }
+
+    fn trace_occluded(&self, ray: &Ray, max_dist: f64) -> bool {
+        let length = ray.dir.norm();
+        if length == 0.0 || max_dist <= 0.0 {
+            return false;
+        }
+        let mut ray = ray.with_kind(RayKind::Shadow);
+        let mut t_max = max_dist / length;
+        // 先用任意命中测试排除未遮挡的光线，命中后才逐个检查透明度遮罩
+        if !self.hit_any(&ray, Interval::new(RAY_EPSILON, t_max)) {
+            return false;
+        }
+
+        for _ in 0..MAX_TRANSPARENT_HITS {
+            let mut rec = HitRecord::default();
+            if !self.hit(&ray, Interval::new(RAY_EPSILON, t_max), &mut rec) {
+                return false;
+            }
+            let alpha = rec.mat.alpha(rec.u, rec.v, &rec.p);
+            if alpha >= 1.0 || random_double() < alpha {
+                return true;
+            }
+            ray.orig = rec.p;
+            t_max -= rec.t;
+        }
+        true
+    }
+
+    fn trace_transmittance(&self, ray: &Ray, max_dist: f64) -> Color {
+        let length = ray.dir.norm();
+        if length == 0.0 || max_dist <= 0.0 {
+            return Color::repeat(1.0);
+        }
+        let mut ray = ray.with_kind(RayKind::Shadow);
+        let mut t_max = max_dist / length;
+        let mut transmittance = Color::repeat(1.0);
+
+        for _ in 0..MAX_TRANSPARENT_HITS {
+            let mut rec = HitRecord::default();
+            if !self.hit(&ray, Interval::new(RAY_EPSILON, t_max), &mut rec) {
+                return transmittance;
+            }
+            // 透明度遮罩按覆盖比例混合：未覆盖的部分直接穿过，覆盖的部分按材质透过
+            let alpha = rec.mat.alpha(rec.u, rec.v, &rec.p).clamp(0.0, 1.0);
+            let surface = rec.mat.shadow_transmittance(&ray, &rec);
+            let passed = match surface {
+                Some(t) => Color::repeat(1.0 - alpha) + t * alpha,
+                None if alpha < 1.0 => Color::repeat(1.0 - alpha),
+                None => return Color::zeros(),
+            };
+            transmittance.component_mul_assign(&passed);
+
+            let survival = transmittance.max();
+            if survival <= 0.0 {
+                return Color::zeros();
+            }
+            if survival < ROULETTE_THRESHOLD {
+                if random_double() >= survival {
+                    return Color::zeros();
+                }
+                transmittance /= survival;
+            }
+
+            // 从交点继续，方向不变，剩余区间相应缩短
+            ray.orig = rec.p;
+            t_max -= rec.t;
+        }
+        Color::zeros()
+    }
+}
+
+/// 构建一次加速结构、反复查询的光线投射器，批量查询在线程池中并行执行
+#[derive(Debug, Clone)]
+pub struct RayCaster {
+    world: Arc<dyn Hittable>,
+}
+
+impl RayCaster {
+    /// 包装已构建的加速结构
+    #[inline]
+    pub fn new(world: Arc<dyn Hittable>) -> Self {
+        Self { world }
+    }
+
+    /// 底层的加速结构
+    #[inline]
+    pub fn world(&self) -> &dyn Hittable {
+        self.world.as_ref()
+    }
+
+    /// 光线的最近交点
+    #[inline]
+    pub fn trace_nearest(&self, ray: &Ray) -> Option<HitRecord> {
+        self.world.trace_nearest(ray)
+    }
+
+    /// 距离光线起点 max_dist 以内是否有遮挡
+    #[inline]
+    pub fn trace_occluded(&self, ray: &Ray, max_dist: f64) -> bool {
+        self.world.trace_occluded(ray, max_dist)
+    }
+
+    /// 光线在 max_dist 以内的透过率
+    #[inline]
+    pub fn trace_transmittance(&self, ray: &Ray, max_dist: f64) -> Color {
+        self.world.trace_transmittance(ray, max_dist)
+    }
+
+    /// 两点之间的透过率：透明表面使光线衰减，不透明表面完全遮挡（终点所在的表面不计）
+    pub fn transmittance(&self, from: &Point3, to: &Point3) -> Color {
+        let distance = (to - from).norm();
+        if distance <= 2.0 * RAY_EPSILON {
+            return Color::repeat(1.0);
+        }
+        let ray = Ray::new(*from, (to - from) / distance, 0.0);
+        self.world.trace_transmittance(&ray, distance - RAY_EPSILON)
+    }
+
+    /// 两点之间是否互相可见（透明度遮罩镂空的部分不遮挡）
+    pub fn visible(&self, from: &Point3, to: &Point3) -> bool {
+        let distance = (to - from).norm();
+        if distance <= 2.0 * RAY_EPSILON {
+            return true;
+        }
+        // 终点留出与起点相同的偏移，终点所在的表面不算遮挡
+        let ray = Ray::new(*from, (to - from) / distance, 0.0);
+        !self.world.trace_occluded(&ray, distance - RAY_EPSILON)
+    }
+
+    /// 并行求一组光线的最近交点，结果与输入顺序一致
+    pub fn trace_nearest_batch(&self, rays: &[Ray]) -> Vec<Option<HitRecord>> {
+        rays.par_iter().map(|ray| self.trace_nearest(ray)).collect()
+    }
+
+    /// 并行求一组光线的命中距离（世界空间长度），未命中为None；只需要距离时比 `trace_nearest_batch` 更省内存
+    pub fn trace_distance_batch(&self, rays: &[Ray]) -> Vec<Option<f64>> {
+        rays.par_iter()
+            .map(|ray| self.trace_nearest(ray).map(|rec| rec.t * ray.dir.norm()))
+            .collect()
+    }
+
+    /// 并行做一组遮挡测试，每条光线的最大距离为 max_dist
+    pub fn trace_occluded_batch(&self, rays: &[Ray], max_dist: f64) -> Vec<bool> {
+        rays.par_iter()
+            .map(|ray| self.trace_occluded(ray, max_dist))
+            .collect()
+    }
+}
@@ -0,0 +1,359 @@
+//! 程序化散布：把树木、岩石、楼块等原型物体的实例撒在高度场或物体表面上
+//!
+//! 实例共享原型的几何（`Arc`），每个实例只是一组 [`Scale`]、[`RotateY`]、[`Translate`] 包装，
+//! 因此上万个实例的内存开销很小。原型的局部原点应位于其底部中心，实例始终竖直放置，
+//! 只绕 Y 轴随机旋转。
+//!
+//! 候选点在表面上按面积均匀生成，再按密度图的值（纹理的标量值，[0,1]）随机接受，
+//! 并可剔除过陡的坡面和离已有实例太近的点。相同的种子总是得到相同的布局。
+//!
+//! [`RotateY`]: crate::ray_tracing::geometry::transforms::rotate_y::RotateY
+//! [`Translate`]: crate::ray_tracing::geometry::transforms::translate::Translate
+
+use crate::ray_tracing::geometry::hittable::{Hittable, UvTriangle};
+use crate::ray_tracing::geometry::hittable_list::HittableList;
+use crate::ray_tracing::geometry::scene_graph::Transform;
+use crate::ray_tracing::geometry::transforms::scale::Scale;
+use crate::ray_tracing::materials::texture::Texture;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::random::Pcg32;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 每个目标实例允许的最多候选点数，密度图大部分为零时防止无限循环
+const ATTEMPTS_PER_INSTANCE: usize = 30;
+
+/// 高度函数 y = h(x, z)
+pub type HeightFn = Arc<dyn Fn(f64, f64) -> f64 + Send + Sync>;
+
+/// 散布的目标表面
+#[derive(Clone)]
+pub enum ScatterSurface {
+    /// x ∈ [min.0, max.0]、z ∈ [min.1, max.1] 上的高度场，密度图的纹理坐标为归一化的 (x, z)
+    Heightfield {
+        min: (f64, f64),
+        max: (f64, f64),
+        height: HeightFn,
+    },
+    /// 物体表面，按其表面三角形（[`Hittable::uv_triangles`]）采样，如网格和四边形
+    Surface(Arc<dyn Hittable>),
+}
+
+/// 表面上的采样点
+struct SurfacePoint {
+    p: Point3,
+    normal: Vec3,
+    uv: (f64, f64),
+}
+
+/// 准备好采样的表面
+enum Sampler {
+    Heightfield {
+        min: (f64, f64),
+        max: (f64, f64),
+        height: HeightFn,
+    },
+    Triangles {
+        triangles: Vec<UvTriangle>,
+        cdf: Vec<f64>,
+    },
+}
+
+impl Sampler {
+    fn new(surface: &ScatterSurface) -> Self {
+        match surface {
+            ScatterSurface::Heightfield { min, max, height } => Self::Heightfield {
+                min: *min,
+                max: *max,
+                height: height.clone(),
+            },
+            ScatterSurface::Surface(object) => {
+                let triangles = object.uv_triangles();
+                let mut total = 0.0;
+                let mut cdf: Vec<f64> = triangles
+                    .iter()
+                    .map(|t| {
+                        total += 0.5 * (t.p[1] - t.p[0]).cross(&(t.p[2] - t.p[0])).norm();
+                        total
+                    })
+                    .collect();
+                if total > 0.0 {
+                    cdf.iter_mut().for_each(|c| *c /= total);
+                }
+                Self::Triangles { triangles, cdf }
+            }
+        }
+    }
+
+    fn sample(&self, rng: &mut Pcg32) -> Option<SurfacePoint> {
+        match self {
+            Self::Heightfield { min, max, height } => {
+                let (u, v) = (rng.next_f64(), rng.next_f64());
+                let x = min.0 + u * (max.0 - min.0);
+                let z = min.1 + v * (max.1 - min.1);
+                // 中心差分估计法线
+                let h = 1e-3 * (max.0 - min.0).abs().max((max.1 - min.1).abs()).max(1e-6);
+                let dx = (height(x + h, z) - height(x - h, z)) / (2.0 * h);
+                let dz = (height(x, z + h) - height(x, z - h)) / (2.0 * h);
+                Some(SurfacePoint {
+                    p: Point3::new(x, height(x, z), z),
+                    normal: Vec3::new(-dx, 1.0, -dz).normalize(),
+                    uv: (u, v),
+                })
+            }
+            Self::Triangles { triangles, cdf } => {
+                if triangles.is_empty() || cdf.last().is_none_or(|&c| c <= 0.0) {
+                    return None;
+                }
+                let xi = rng.next_f64();
+                let t = &triangles[cdf.partition_point(|&c| c < xi).min(triangles.len() - 1)];
+                let (mut r1, mut r2) = (rng.next_f64(), rng.next_f64());
+                if r1 + r2 > 1.0 {
+                    (r1, r2) = (1.0 - r1, 1.0 - r2);
+                }
+                let b = [1.0 - r1 - r2, r1, r2];
+                let uv = (
+                    b[0] * t.uv[0].0 + b[1] * t.uv[1].0 + b[2] * t.uv[2].0,
+                    b[0] * t.uv[0].1 + b[1] * t.uv[1].1 + b[2] * t.uv[2].1,
+                );
+                Some(SurfacePoint {
+                    p: t.point(&b),
+                    normal: t.normal(&b),
+                    uv,
+                })
+            }
+        }
+    }
+}
+
+/// 散布参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScatterSettings {
+    /// 目标实例数（密度图很稀疏或最小间距过大时可能达不到）
+    pub count: usize,
+    /// 随机种子
+    pub seed: u64,
+    /// 缩放系数的范围，在其中均匀选取
+    pub scale: (f64, f64),
+    /// 绕 Y 轴随机旋转的范围（度），以 0 为中心；360 为任意朝向
+    pub rotation_jitter: f64,
+    /// 实例之间的最小距离，0 为不限制
+    pub min_distance: f64,
+    /// 允许的最大坡度（度），坡度为表面法线与 +Y 的夹角
+    pub max_slope: f64,
+    /// 实例向下沉入表面的深度（原型单位，随缩放变化），避免坡面上悬空
+    pub sink: f64,
+}
+
+impl Default for ScatterSettings {
+    fn default() -> Self {
+        Self {
+            count: 100,
+            seed: 0,
+            scale: (1.0, 1.0),
+            rotation_jitter: 360.0,
+            min_distance: 0.0,
+            max_slope: 90.0,
+            sink: 0.0,
+        }
+    }
+}
+
+impl ScatterSettings {
+    /// 指定目标实例数和种子
+    pub fn new(count: usize, seed: u64) -> Self {
+        Self {
+            count,
+            seed,
+            ..Self::default()
+        }
+    }
+}
+
+/// 一个实例的放置
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Placement {
+    /// 原型序号
+    pub prototype: usize,
+    /// 原型原点在世界中的位置
+    pub position: Point3,
+    /// 绕 Y 轴的旋转（度）
+    pub rotate_y: f64,
+    /// 缩放系数
+    pub scale: f64,
+}
+
+impl Placement {
+    /// 用共享的原型几何生成实例
+    pub fn instantiate(&self, prototype: Arc<dyn Hittable>) -> Arc<dyn Hittable> {
+        let scaled: Arc<dyn Hittable> = if self.scale == 1.0 {
+            prototype
+        } else {
+            Arc::new(Scale::new(prototype, self.scale))
+        };
+        Transform {
+            rotate_y: self.rotate_y,
+            translate: self.position.coords,
+        }
+        .apply(scaled)
+    }
+}
+
+/// 散布工具：若干按权重选取的原型、可选的密度图和散布参数
+#[derive(Debug, Clone, Default)]
+pub struct Scatter {
+    prototypes: Vec<(Arc<dyn Hittable>, f64)>,
+    density: Option<Arc<dyn Texture>>,
+    settings: ScatterSettings,
+}
+
+impl Scatter {
+    /// 创建散布工具
+    pub fn new(settings: ScatterSettings) -> Self {
+        Self {
+            settings,
+            ..Self::default()
+        }
+    }
+
+    /// 添加原型，weight 为相对的选取概率
+    pub fn with_prototype(mut self, prototype: Arc<dyn Hittable>, weight: f64) -> Self {
+        self.prototypes.push((prototype, weight.max(0.0)));
+        self
+    }
+
+    /// 设置密度图：纹理在表面点处的标量值为接受概率
+    pub fn with_density(mut self, density: Arc<dyn Texture>) -> Self {
+        self.density = Some(density);
+        self
+    }
+
+    /// 散布参数
+    #[inline]
+    pub fn settings(&self) -> &ScatterSettings {
+        &self.settings
+    }
+
+    /// 计算实例的放置，不生成几何
+    pub fn place(&self, surface: &ScatterSurface) -> Vec<Placement> {
+        let s = &self.settings;
+        let total_weight: f64 = self.prototypes.iter().map(|(_, w)| w).sum();
+        if s.count == 0 || total_weight <= 0.0 {
+            return Vec::new();
+        }
+
+        let sampler = Sampler::new(surface);
+        let mut rng = Pcg32::new(s.seed);
+        let min_slope_cos = s.max_slope.clamp(0.0, 180.0).to_radians().cos();
+        let (scale_lo, scale_hi) = (s.scale.0.min(s.scale.1), s.scale.0.max(s.scale.1));
+        let mut grid = SpacingGrid::new(s.min_distance);
+        let mut placements = Vec::with_capacity(s.count);
+
+        for _ in 0..s.count.saturating_mul(ATTEMPTS_PER_INSTANCE) {
+            if placements.len() >= s.count {
+                break;
+            }
+            let Some(point) = sampler.sample(&mut rng) else {
+                break;
+            };
+            // 每个候选点固定消耗同样多的随机数，使布局只取决于种子
+            let (accept, pick, angle, size) = (
+                rng.next_f64(),
+                rng.next_f64(),
+                rng.next_f64(),
+                rng.next_f64(),
+            );
+            if point.normal.y < min_slope_cos {
+                continue;
+            }
+            if let Some(density) = &self.density {
+                let d = density.value_with_normal(point.uv.0, point.uv.1, &point.p, &point.normal);
+                if accept >= ((d.x + d.y + d.z) / 3.0).clamp(0.0, 1.0) {
+                    continue;
+                }
+            }
+            if !grid.try_insert(point.p) {
+                continue;
+            }
+
+            let mut target = pick * total_weight;
+            let prototype = self
+                .prototypes
+                .iter()
+                .position(|(_, w)| {
+                    target -= w;
+                    target < 0.0
+                })
+                .unwrap_or(self.prototypes.len() - 1);
+            let scale = scale_lo + size * (scale_hi - scale_lo);
+            placements.push(Placement {
+                prototype,
+                position: point.p - Vec3::new(0.0, s.sink * scale, 0.0),
+                rotate_y: (angle - 0.5) * s.rotation_jitter,
+                scale,
+            });
+        }
+        placements
+    }
+
+    /// 散布并生成实例列表（通常再交给加速结构）
+    pub fn build(&self, surface: &ScatterSurface) -> HittableList {
+        self.place(surface)
+            .iter()
+            .map(|p| p.instantiate(self.prototypes[p.prototype].0.clone()))
+            .collect()
+    }
+}
+
+/// 检查最小间距的均匀网格，单元边长等于最小距离
+struct SpacingGrid {
+    spacing: f64,
+    cells: HashMap<(i64, i64, i64), Vec<Point3>>,
+}
+
+impl SpacingGrid {
+    fn new(spacing: f64) -> Self {
+        Self {
+            spacing,
+            cells: HashMap::new(),
+        }
+    }
+
+    /// 与已有点的距离都不小于间距时插入并返回 true
+    fn try_insert(&mut self, p: Point3) -> bool {
+        if self.spacing <= 0.0 {
+            return true;
+        }
+        let cell = |c: f64| (c / self.spacing).floor() as i64;
+        let key = (cell(p.x), cell(p.y), cell(p.z));
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let neighbour = (key.0 + dx, key.1 + dy, key.2 + dz);
+                    if self
+                        .cells
+                        .get(&neighbour)
+                        .is_some_and(|points| points.iter().any(|q| (p - q).norm() < self.spacing))
+                    {
+                        return false;
+                    }
+                }
+            }
+        }
+        self.cells.entry(key).or_default().push(p);
+        true
+    }
+}
+
+impl std::fmt::Debug for ScatterSurface {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Heightfield { min, max, .. } => f
+                .debug_struct("Heightfield")
+                .field("min", min)
+                .field("max", max)
+                .finish(),
+            Self::Surface(object) => f.debug_tuple("Surface").field(object).finish(),
+        }
+    }
+}
@@ -0,0 +1,735 @@
+//! 文本场景文件的读取与写出
+//!
+//! 每行一个指令，`#` 开始注释：
+//!
+//! ```text
+//! camera   lookfrom(x y z) lookat(x y z) vfov
+//! texture  名称 solid r g b | checker 边长 偶数格 奇数格 | image 文件名 色彩空间
+//!          | noise 频率 种子 演变速度 [湍流层数] | simplex 种子 缩放 层数 r g b r g b
+//!          | worley 种子 抖动 度量 特征 缩放 增益 反相(0/1) 幂次 r g b r g b
+//!          | triplanar 子纹理 缩放 锐度
+//! material 名称 lambertian 颜色 | metal 颜色 模糊度 | dielectric ior [σr σg σb] | light 颜色
+//!          | blackbody 色温K 亮度cd/m²
+//! sphere   x y z 半径 材质名
+//! quad     Q(x y z) u(x y z) v(x y z) 材质名
+//! ```
+//!
+//! 图像文件名包含 `<UDIM>` 时按 UDIM 多瓦片纹理读取（见 [`UdimTexture`]）。
+//! 颜色参数可以写成 `r g b` 或之前定义的纹理名，模糊度等标量参数可以写成一个数值或纹理名。
+//! 使用 light 或 blackbody 材质的物体读取时通过 [`Scene::add_emitter`] 同时加入光源列表。
+//!
+//! 写出时把列表、平移和Y轴旋转展开成世界坐标下的球体和四边形，材质和纹理按共享关系去重，
+//! 依次命名为 `m0`、`m1`…和 `t0`、`t1`…。数值按最短的可精确还原的十进制写出，
+//! 程序纹理写出全部参数和种子，图像纹理写出加载时给出的文件名（读取时同样按纹理搜索路径查找），
+//! 读回的场景与原场景逐位相同（没有展开变换时渲染结果也逐位相同）。
+//! 程序生成的场景（随机球阵等）按展开后的结果写出，重新读取不需要原来的随机种子。
+//! 格式无法表示的内容（运动球体、未设置种子的噪声纹理、内存中创建的图像纹理、网格、体积等）
+//! 不会写出，而是记录在 [`SceneExport::skipped`] 中；光源的采样权重和启用状态、
+//! 只用于采样的光源形状同样不保存。
+
+use crate::ray_tracing::geometry::hittable::Hittable;
+use crate::ray_tracing::geometry::hittable_list::HittableList;
+use crate::ray_tracing::geometry::quad::Quad;
+use crate::ray_tracing::geometry::sphere::Sphere;
+use crate::ray_tracing::geometry::transforms::rotate_y::RotateY;
+use crate::ray_tracing::geometry::transforms::translate::Translate;
+use crate::ray_tracing::materials::blackbody_light::BlackbodyLight;
+use crate::ray_tracing::materials::dielectric::Dielectric;
+use crate::ray_tracing::materials::diffuse_light::DiffuseLight;
+use crate::ray_tracing::materials::lambertian::Lambertian;
+use crate::ray_tracing::materials::material::Material;
+use crate::ray_tracing::materials::metal::Metal;
+use crate::ray_tracing::materials::texture::checker::CheckerTexture;
+use crate::ray_tracing::materials::texture::image::ImageTexture;
+use crate::ray_tracing::materials::texture::noise::{DEFAULT_TURBULENCE_DEPTH, NoiseTexture};
+use crate::ray_tracing::materials::texture::simplex::SimplexTexture;
+use crate::ray_tracing::materials::texture::triplanar::TriplanarTexture;
+use crate::ray_tracing::materials::texture::udim::UdimTexture;
+use crate::ray_tracing::materials::texture::worley::WorleyTexture;
+use crate::ray_tracing::materials::texture::{SolidColor, TextureKind, TexturePtr};
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::procedural::worley::Worley;
+use crate::ray_tracing::rendering::camera::Camera;
+use crate::ray_tracing::rendering::color_space::TextureColorSpace;
+use crate::ray_tracing::scene::world::Scene;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+/// 场景文件中的相机参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraView {
+    pub lookfrom: Point3,
+    pub lookat: Point3,
+    pub vfov: f64,
+}
+
+impl Default for CameraView {
+    fn default() -> Self {
+        Self {
+            lookfrom: Point3::new(0.0, 0.0, 5.0),
+            lookat: Point3::origin(),
+            vfov: 40.0,
+        }
+    }
+}
+
+impl CameraView {
+    /// 取相机的机位和视场角
+    pub fn of(camera: &Camera) -> Self {
+        Self {
+            lookfrom: camera.lookfrom(),
+            lookat: camera.lookat(),
+            vfov: camera.vfov(),
+        }
+    }
+}
+
+/// 读取的场景文件：场景和相机参数
+#[derive(Debug)]
+pub struct SceneFile {
+    pub scene: Scene,
+    pub view: CameraView,
+}
+
+fn invalid(source: &str, line_no: usize, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{} 第{}行: {}", source, line_no, message),
+    )
+}
+
+fn parse_numbers(fields: &[&str], source: &str, line_no: usize) -> io::Result<Vec<f64>> {
+    fields
+        .iter()
+        .map(|f| {
+            f.parse()
+                .map_err(|_| invalid(source, line_no, &format!("无法解析的数值 `{}`", f)))
+        })
+        .collect()
+}
+
+/// 颜色或标量参数：直接写出的数值，或引用的纹理
+enum Slot {
+    Value(Color),
+    Texture(TexturePtr),
+}
+
+impl Slot {
+    fn into_texture(self) -> TexturePtr {
+        match self {
+            Self::Value(color) => Arc::new(SolidColor::new(color)),
+            Self::Texture(texture) => texture,
+        }
+    }
+}
+
+/// 按顺序读取一行中的参数
+struct Args<'a> {
+    fields: &'a [&'a str],
+    source: &'a str,
+    line_no: usize,
+}
+
+impl<'a> Args<'a> {
+    fn error(&self, message: &str) -> io::Error {
+        invalid(self.source, self.line_no, message)
+    }
+
+    fn word(&mut self) -> io::Result<&'a str> {
+        let (&first, rest) = self
+            .fields
+            .split_first()
+            .ok_or_else(|| self.error("参数不足"))?;
+        self.fields = rest;
+        Ok(first)
+    }
+
+    fn parse<T: std::str::FromStr>(&mut self) -> io::Result<T> {
+        let word = self.word()?;
+        word.parse()
+            .map_err(|_| self.error(&format!("无法解析的数值 `{}`", word)))
+    }
+
+    fn color(&mut self) -> io::Result<Color> {
+        Ok(Color::new(self.parse()?, self.parse()?, self.parse()?))
+    }
+
+    /// 下一个参数是否为数值（否则为纹理名）
+    fn next_is_number(&self) -> bool {
+        self.fields
+            .first()
+            .is_some_and(|f| f.parse::<f64>().is_ok())
+    }
+
+    fn texture(&mut self, textures: &HashMap<String, TexturePtr>) -> io::Result<TexturePtr> {
+        let name = self.word()?;
+        textures
+            .get(name)
+            .cloned()
+            .ok_or_else(|| self.error(&format!("未定义的纹理 `{}`", name)))
+    }
+
+    /// `r g b` 或纹理名
+    fn color_slot(&mut self, textures: &HashMap<String, TexturePtr>) -> io::Result<Slot> {
+        if self.next_is_number() {
+            Ok(Slot::Value(self.color()?))
+        } else {
+            Ok(Slot::Texture(self.texture(textures)?))
+        }
+    }
+
+    /// 一个数值或纹理名
+    fn scalar_slot(&mut self, textures: &HashMap<String, TexturePtr>) -> io::Result<Slot> {
+        if self.next_is_number() {
+            Ok(Slot::Value(Color::repeat(self.parse()?)))
+        } else {
+            Ok(Slot::Texture(self.texture(textures)?))
+        }
+    }
+
+    /// 确认参数已全部读完
+    fn finish(&self) -> io::Result<()> {
+        if self.fields.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error(&format!("多余的参数 `{}`", self.fields.join(" "))))
+        }
+    }
+}
+
+/// 解析纹理定义（类型及其参数）
+fn parse_texture(
+    kind: &str,
+    args: &mut Args<'_>,
+    textures: &HashMap<String, TexturePtr>,
+) -> io::Result<TexturePtr> {
+    let texture: TexturePtr = match kind {
+        "solid" => Arc::new(SolidColor::new(args.color()?)),
+        "checker" => {
+            let scale = args.parse()?;
+            let even = args.color_slot(textures)?.into_texture();
+            let odd = args.color_slot(textures)?.into_texture();
+            Arc::new(CheckerTexture::new(scale, even, odd))
+        }
+        "image" => {
+            let filename = args.word()?;
+            let color_space: TextureColorSpace =
+                args.word()?.parse().map_err(|e: String| args.error(&e))?;
+            if UdimTexture::is_pattern(filename) {
+                Arc::new(UdimTexture::with_color_space(filename, color_space))
+            } else {
+                Arc::new(ImageTexture::with_color_space(filename, color_space))
+            }
+        }
+        "noise" => {
+            let scale = args.parse()?;
+            let seed = args.parse()?;
+            let speed = args.parse()?;
+            let mut texture = NoiseTexture::with_seed(seed, scale).with_speed(speed);
+            if args.next_is_number() {
+                texture = texture.with_turbulence_depth(args.parse()?);
+            }
+            Arc::new(texture)
+        }
+        "simplex" => {
+            let seed = args.parse()?;
+            let scale = args.parse()?;
+            let octaves = args.parse()?;
+            let (low, high) = (args.color()?, args.color()?);
+            Arc::new(SimplexTexture::new(seed, scale, low, high).with_octaves(octaves))
+        }
+        "worley" => {
+            let seed = args.parse()?;
+            let jitter = args.parse()?;
+            let metric = args.word()?.parse().map_err(|e: String| args.error(&e))?;
+            let feature = args.word()?.parse().map_err(|e: String| args.error(&e))?;
+            let scale = args.parse()?;
+            let gain = args.parse()?;
+            let invert = match args.word()? {
+                "0" => false,
+                "1" => true,
+                other => return Err(args.error(&format!("反相应为 0 或 1，而不是 `{}`", other))),
+            };
+            let exponent = args.parse()?;
+            let (low, high) = (args.color()?, args.color()?);
+            let noise = Worley::new(seed).with_jitter(jitter).with_metric(metric);
+            let texture = WorleyTexture::new(noise, feature, scale, low, high)
+                .with_gain(gain)
+                .with_exponent(exponent);
+            Arc::new(if invert { texture.inverted() } else { texture })
+        }
+        "triplanar" => {
+            let inner = args.color_slot(textures)?.into_texture();
+            let scale = args.parse()?;
+            let sharpness = args.parse()?;
+            Arc::new(TriplanarTexture::new(inner, scale, sharpness))
+        }
+        _ => return Err(args.error(&format!("未知的纹理类型 `{}`", kind))),
+    };
+    args.finish()?;
+    Ok(texture)
+}
+
+/// 解析材质定义，返回材质和是否发光
+fn parse_material(
+    kind: &str,
+    args: &mut Args<'_>,
+    textures: &HashMap<String, TexturePtr>,
+) -> io::Result<(Arc<dyn Material>, bool)> {
+    let material: (Arc<dyn Material>, bool) = match kind {
+        "lambertian" => match args.color_slot(textures)? {
+            Slot::Value(albedo) => (Arc::new(Lambertian::new(albedo)), false),
+            Slot::Texture(albedo) => (Arc::new(Lambertian::new_texture(albedo)), false),
+        },
+        "metal" => {
+            let albedo = args.color_slot(textures)?;
+            let fuzz = args.scalar_slot(textures)?;
+            match (albedo, fuzz) {
+                (Slot::Value(albedo), Slot::Value(fuzz)) => {
+                    (Arc::new(Metal::new(albedo, fuzz.x)), false)
+                }
+                (albedo, fuzz) => (
+                    Arc::new(Metal::new_texture(
+                        albedo.into_texture(),
+                        fuzz.into_texture(),
+                    )),
+                    false,
+                ),
+            }
+        }
+        "dielectric" => {
+            let dielectric = Dielectric::new(args.parse()?);
+            // 可选的每单位长度吸收系数
+            let dielectric = if args.next_is_number() {
+                dielectric.with_absorption(args.color()?)
+            } else {
+                dielectric
+            };
+            (Arc::new(dielectric), false)
+        }
+        "light" => match args.color_slot(textures)? {
+            Slot::Value(emission) => (Arc::new(DiffuseLight::new_color(emission)), true),
+            Slot::Texture(emission) => (Arc::new(DiffuseLight::new(emission)), true),
+        },
+        "blackbody" => {
+            let kelvin: f64 = args.parse()?;
+            let nits = args.parse()?;
+            if kelvin <= 0.0 {
+                return Err(args.error("色温必须为正"));
+            }
+            (Arc::new(BlackbodyLight::new(kelvin, nits)), true)
+        }
+        _ => return Err(args.error("无效的材质定义")),
+    };
+    args.finish()?;
+    Ok(material)
+}
+
+impl SceneFile {
+    /// 读取场景文件
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        Self::parse(&text, &path.display().to_string())
+    }
+
+    /// 解析场景文本，source 用于错误信息
+    pub fn parse(text: &str, source: &str) -> io::Result<Self> {
+        let mut materials: HashMap<String, (Arc<dyn Material>, bool)> = HashMap::new();
+        let mut textures: HashMap<String, TexturePtr> = HashMap::new();
+        let mut file = Self {
+            scene: Scene::new(),
+            view: CameraView::default(),
+        };
+
+        for (index, line) in text.lines().enumerate() {
+            let line_no = index + 1;
+            let line = line.split('#').next().unwrap_or("").trim();
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let Some((&command, args)) = fields.split_first() else {
+                continue;
+            };
+
+            match command {
+                "camera" => {
+                    let n = parse_numbers(args, source, line_no)?;
+                    if n.len() != 7 {
+                        return Err(invalid(source, line_no, "camera 需要7个数值"));
+                    }
+                    file.view = CameraView {
+                        lookfrom: Point3::new(n[0], n[1], n[2]),
+                        lookat: Point3::new(n[3], n[4], n[5]),
+                        vfov: n[6],
+                    };
+                }
+                "texture" | "material" => {
+                    let [name, kind, rest @ ..] = args else {
+                        return Err(invalid(
+                            source,
+                            line_no,
+                            &format!("{} 需要名称和类型", command),
+                        ));
+                    };
+                    let mut rest = Args {
+                        fields: rest,
+                        source,
+                        line_no,
+                    };
+                    if command == "texture" {
+                        let texture = parse_texture(kind, &mut rest, &textures)?;
+                        textures.insert(name.to_string(), texture);
+                    } else {
+                        let material = parse_material(kind, &mut rest, &textures)?;
+                        materials.insert(name.to_string(), material);
+                    }
+                }
+                "sphere" | "quad" => {
+                    let Some((name, numbers)) = args.split_last() else {
+                        return Err(invalid(source, line_no, "缺少材质名"));
+                    };
+                    let (material, emissive) = materials.get(*name).cloned().ok_or_else(|| {
+                        invalid(source, line_no, &format!("未定义的材质 `{}`", name))
+                    })?;
+                    let n = parse_numbers(numbers, source, line_no)?;
+                    let object: Arc<dyn Hittable> = match (command, n.as_slice()) {
+                        ("sphere", &[x, y, z, radius]) => {
+                            Arc::new(Sphere::new(Point3::new(x, y, z), radius, material))
+                        }
+                        ("quad", &[qx, qy, qz, ux, uy, uz, vx, vy, vz]) => Arc::new(Quad::new(
+                            Point3::new(qx, qy, qz),
+                            Vec3::new(ux, uy, uz),
+                            Vec3::new(vx, vy, vz),
+                            material,
+                        )),
+                        _ => {
+                            return Err(invalid(
+                                source,
+                                line_no,
+                                &format!("{} 的参数个数不正确", command),
+                            ));
+                        }
+                    };
+                    if emissive {
+                        file.scene.add_emitter(object);
+                    } else {
+                        file.scene.add(object);
+                    }
+                }
+                _ => {
+                    return Err(invalid(source, line_no, &format!("未知指令 `{}`", command)));
+                }
+            }
+        }
+
+        Ok(file)
+    }
+}
+
+/// 写出结果：场景文本和未能写出的物体
+#[derive(Debug, Clone, Default)]
+pub struct SceneExport {
+    pub text: String,
+    /// 格式无法表示而被跳过的物体或材质的说明
+    pub skipped: Vec<String>,
+}
+
+impl SceneExport {
+    /// 保存到文件
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, &self.text)
+    }
+}
+
+/// 把场景写成场景文件文本；view 为 None 时不写相机
+pub fn serialize_scene(scene: &Scene, view: Option<&CameraView>) -> SceneExport {
+    // 被禁用光源隐藏的物体不写出
+    let hidden: Vec<_> = scene
+        .lights()
+        .filter(|(_, entry)| !entry.enabled)
+        .filter_map(|(_, entry)| entry.object)
+        .collect();
+
+    let mut writer = Writer::default();
+    for (id, object) in scene.objects() {
+        if !hidden.contains(&id) {
+            writer.object(object.as_ref(), &mut Vec::new());
+        }
+    }
+
+    let mut text = String::from("# 由 serialize_scene 写出\n\n");
+    if let Some(view) = view {
+        let _ = writeln!(
+            text,
+            "camera {}   {}   {}\n",
+            triple(&view.lookfrom.coords),
+            triple(&view.lookat.coords),
+            view.vfov
+        );
+    }
+    for section in [&writer.textures, &writer.materials] {
+        if section.is_empty() {
+            continue;
+        }
+        for line in section {
+            text.push_str(line);
+            text.push('\n');
+        }
+        text.push('\n');
+    }
+    for line in &writer.objects {
+        text.push_str(line);
+        text.push('\n');
+    }
+
+    SceneExport {
+        text,
+        skipped: writer.skipped,
+    }
+}
+
+/// 从物体局部坐标到世界坐标的一步变换
+#[derive(Clone, Copy)]
+enum Step<'a> {
+    Translate(Vec3),
+    Rotate(&'a RotateY),
+}
+
+/// 依次应用从内到外的变换（steps 由外到内排列）
+fn to_world(steps: &[Step<'_>], p: Point3) -> Point3 {
+    steps.iter().rev().fold(p, |p, step| match step {
+        Step::Translate(offset) => p + offset,
+        Step::Rotate(rotate) => rotate.local_to_world(&p),
+    })
+}
+
+fn to_world_vec(steps: &[Step<'_>], v: Vec3) -> Vec3 {
+    steps.iter().rev().fold(v, |v, step| match step {
+        Step::Translate(_) => v,
+        Step::Rotate(rotate) => rotate.local_to_world_vec(&v),
+    })
+}
+
+fn triple(v: &Vec3) -> String {
+    // 加0把 -0 写成 0
+    format!("{} {} {}", v.x + 0.0, v.y + 0.0, v.z + 0.0)
+}
+
+/// Debug 输出的类型名部分，用于说明被跳过的物体
+fn type_name(value: &impl std::fmt::Debug) -> String {
+    let text = format!("{:?}", value);
+    text.split([' ', '{', '(']).next().unwrap_or("").to_string()
+}
+
+/// 纹理文件名能否原样写出（不含空白和注释符）
+fn is_plain_word(name: &str) -> bool {
+    !name.is_empty() && !name.contains(|c: char| c.is_whitespace() || c == '#')
+}
+
+#[derive(Default)]
+struct Writer {
+    textures: Vec<String>,
+    texture_names: HashMap<*const (), String>,
+    materials: Vec<String>,
+    names: HashMap<*const (), String>,
+    objects: Vec<String>,
+    skipped: Vec<String>,
+}
+
+impl Writer {
+    /// 材质名，首次遇到时写出定义；格式无法表示时返回 None
+    fn material(&mut self, material: &Arc<dyn Material>) -> Option<String> {
+        let key = Arc::as_ptr(material) as *const ();
+        if let Some(name) = self.names.get(&key) {
+            return Some(name.clone());
+        }
+        let spec = self.material_spec(material.as_ref())?;
+        let name = format!("m{}", self.materials.len());
+        self.materials.push(format!("material {} {}", name, spec));
+        self.names.insert(key, name.clone());
+        Some(name)
+    }
+
+    /// 材质定义（不含名称），格式无法表示时返回 None
+    fn material_spec(&mut self, material: &dyn Material) -> Option<String> {
+        if let Some(m) = material.downcast_ref::<Lambertian>() {
+            Some(format!("lambertian {}", self.color_slot(m.albedo())?))
+        } else if let Some(m) = material.downcast_ref::<Metal>() {
+            let albedo = self.color_slot(m.albedo())?;
+            let fuzz = self.scalar_slot(m.fuzz())?;
+            Some(format!("metal {} {}", albedo, fuzz))
+        } else if let Some(m) = material.downcast_ref::<Dielectric>() {
+            let ior = m.refraction_index();
+            Some(if m.absorption() == Color::zeros() {
+                format!("dielectric {}", ior)
+            } else {
+                format!("dielectric {} {}", ior, triple(&m.absorption()))
+            })
+        } else if let Some(m) = material.downcast_ref::<DiffuseLight>() {
+            Some(format!("light {}", self.color_slot(m.emission())?))
+        } else {
+            material
+                .downcast_ref::<BlackbodyLight>()
+                .map(|m| format!("blackbody {} {}", m.kelvin(), m.nits()))
+        }
+    }
+
+    /// 颜色参数：纯色写成 `r g b`，其他纹理写成纹理名
+    fn color_slot(&mut self, texture: &TextureKind) -> Option<String> {
+        match texture {
+            TextureKind::Solid(_) => texture.solid_color().map(|c| triple(&c)),
+            TextureKind::Shared(texture) => self.color_ref(texture),
+        }
+    }
+
+    fn color_ref(&mut self, texture: &TexturePtr) -> Option<String> {
+        match texture.downcast_ref::<SolidColor>() {
+            Some(solid) => Some(triple(&solid.color())),
+            None => self.texture(texture),
+        }
+    }
+
+    /// 标量参数：三个分量相同的纯色写成一个数值，其他写成纹理名
+    fn scalar_slot(&mut self, texture: &TextureKind) -> Option<String> {
+        let solid = match texture {
+            TextureKind::Solid(_) => texture.solid_color(),
+            TextureKind::Shared(texture) => texture.downcast_ref::<SolidColor>().map(|s| s.color()),
+        };
+        match (solid, texture) {
+            (Some(c), _) if c.x == c.y && c.y == c.z => Some(format!("{}", c.x + 0.0)),
+            (_, TextureKind::Shared(texture)) => self.texture(texture),
+            (Some(c), TextureKind::Solid(_)) => {
+                Some(self.define_texture(format!("solid {}", triple(&c))))
+            }
+            (None, TextureKind::Solid(_)) => None,
+        }
+    }
+
+    /// 纹理名，首次遇到时写出定义（引用的子纹理先于它写出）；格式无法表示时返回 None
+    fn texture(&mut self, texture: &TexturePtr) -> Option<String> {
+        let key = Arc::as_ptr(texture) as *const ();
+        if let Some(name) = self.texture_names.get(&key) {
+            return Some(name.clone());
+        }
+        let spec = self.texture_spec(texture)?;
+        let name = self.define_texture(spec);
+        self.texture_names.insert(key, name.clone());
+        Some(name)
+    }
+
+    fn define_texture(&mut self, spec: String) -> String {
+        let name = format!("t{}", self.textures.len());
+        self.textures.push(format!("texture {} {}", name, spec));
+        name
+    }
+
+    /// 纹理定义（不含名称），格式无法表示时返回 None
+    fn texture_spec(&mut self, texture: &TexturePtr) -> Option<String> {
+        if let Some(t) = texture.downcast_ref::<SolidColor>() {
+            Some(format!("solid {}", triple(&t.color())))
+        } else if let Some(t) = texture.downcast_ref::<CheckerTexture>() {
+            let even = self.color_ref(t.even())?;
+            let odd = self.color_ref(t.odd())?;
+            Some(format!("checker {} {} {}", t.scale(), even, odd))
+        } else if let Some(t) = texture.downcast_ref::<ImageTexture>() {
+            let source = t.source().filter(|s| is_plain_word(s))?;
+            Some(format!("image {} {}", source, t.color_space()))
+        } else if let Some(t) = texture.downcast_ref::<UdimTexture>() {
+            let pattern = Some(t.pattern()).filter(|s| is_plain_word(s))?;
+            Some(format!("image {} {}", pattern, t.color_space()))
+        } else if let Some(t) = texture.downcast_ref::<NoiseTexture>() {
+            let mut line = format!("noise {} {} {}", t.scale(), t.seed()?, t.speed());
+            if t.turbulence_depth() != DEFAULT_TURBULENCE_DEPTH {
+                line += &format!(" {}", t.turbulence_depth());
+            }
+            Some(line)
+        } else if let Some(t) = texture.downcast_ref::<SimplexTexture>() {
+            let (low, high) = t.colors();
+            Some(format!(
+                "simplex {} {} {} {}   {}",
+                t.seed(),
+                t.scale(),
+                t.octaves(),
+                triple(&low),
+                triple(&high)
+            ))
+        } else if let Some(t) = texture.downcast_ref::<WorleyTexture>() {
+            let noise = t.noise();
+            let (low, high) = t.colors();
+            Some(format!(
+                "worley {} {} {} {} {} {} {} {} {}   {}",
+                noise.seed(),
+                noise.jitter,
+                noise.metric,
+                t.feature(),
+                t.scale(),
+                t.gain(),
+                u8::from(t.is_inverted()),
+                t.exponent(),
+                triple(&low),
+                triple(&high)
+            ))
+        } else if let Some(t) = texture.downcast_ref::<TriplanarTexture>() {
+            let inner = self.color_ref(t.texture())?;
+            Some(format!(
+                "triplanar {} {} {}",
+                inner,
+                t.scale(),
+                t.sharpness()
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn object<'a>(&mut self, object: &'a dyn Hittable, steps: &mut Vec<Step<'a>>) {
+        if let Some(list) = object.downcast_ref::<HittableList>() {
+            for child in list {
+                self.object(child.as_ref(), steps);
+            }
+        } else if let Some(translate) = object.downcast_ref::<Translate>() {
+            steps.push(Step::Translate(translate.offset()));
+            self.object(translate.object().as_ref(), steps);
+            steps.pop();
+        } else if let Some(rotate) = object.downcast_ref::<RotateY>() {
+            steps.push(Step::Rotate(rotate));
+            self.object(rotate.object().as_ref(), steps);
+            steps.pop();
+        } else if let Some(sphere) = object.downcast_ref::<Sphere>() {
+            if sphere.is_moving() {
+                self.skipped.push("运动球体".to_string());
+                return;
+            }
+            let Some(name) = self.material(sphere.material()) else {
+                self.skipped
+                    .push(format!("球体（材质 {}）", type_name(sphere.material())));
+                return;
+            };
+            let center = to_world(steps, sphere.center(0.0));
+            self.objects.push(format!(
+                "sphere {} {} {}",
+                triple(&center.coords),
+                sphere.radius(),
+                name
+            ));
+        } else if let Some(quad) = object.downcast_ref::<Quad>() {
+            let Some(name) = self.material(quad.material()) else {
+                self.skipped
+                    .push(format!("四边形（材质 {}）", type_name(quad.material())));
+                return;
+            };
+            let q = to_world(steps, quad.q());
+            self.objects.push(format!(
+                "quad {}   {}   {}   {}",
+                triple(&q.coords),
+                triple(&to_world_vec(steps, quad.u())),
+                triple(&to_world_vec(steps, quad.v())),
+                name
+            ));
+        } else {
+            self.skipped.push(type_name(&object));
+        }
+    }
+}
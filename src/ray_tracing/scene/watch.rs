@@ -0,0 +1,89 @@
+//! 场景文件监视：轮询文件修改时间，文件变化时只重建受影响的部分
+//!
+//! 只修改材质、纹理、相机或光源时复用已有的网格几何和BVH（见 [`reload_gltf`]），
+//! 修改节点、网格或外部缓冲区文件时完整地重新导入。
+
+use super::gltf::{GltfChange, GltfImport, GltfOptions, load_gltf_with, reload_gltf};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// glTF 场景监视器
+#[derive(Debug)]
+pub struct SceneWatcher {
+    path: PathBuf,
+    options: GltfOptions,
+    import: GltfImport,
+    /// 场景文件、外部缓冲区和图像文件的修改时间
+    stamps: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl SceneWatcher {
+    /// 导入场景并开始监视
+    pub fn new(path: impl AsRef<Path>, options: GltfOptions) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let import = load_gltf_with(&path, options)?;
+        let mut watcher = Self {
+            path,
+            options,
+            import,
+            stamps: Vec::new(),
+        };
+        watcher.stamps = watcher.current_stamps();
+        Ok(watcher)
+    }
+
+    /// 当前的导入结果
+    #[inline]
+    pub fn import(&self) -> &GltfImport {
+        &self.import
+    }
+
+    /// 检查文件是否变化，变化时重新加载并返回变化类型
+    ///
+    /// 重新加载失败（例如编辑器尚未写完文件）时保留上次的场景并返回错误，文件再次修改后会重试。
+    pub fn poll(&mut self) -> io::Result<Option<GltfChange>> {
+        let stamps = self.current_stamps();
+        if stamps == self.stamps {
+            return Ok(None);
+        }
+
+        let buffers_changed = self
+            .import
+            .buffer_files()
+            .iter()
+            .any(|file| stamp_of(&stamps, file) != stamp_of(&self.stamps, file));
+        self.stamps = stamps;
+
+        let (import, change) = if buffers_changed {
+            (
+                load_gltf_with(&self.path, self.options)?,
+                GltfChange::Geometry,
+            )
+        } else {
+            reload_gltf(&self.path, &self.import)?
+        };
+        self.import = import;
+        // 新文件可能引用了不同的外部文件
+        self.stamps = self.current_stamps();
+        Ok(Some(change))
+    }
+
+    fn current_stamps(&self) -> Vec<(PathBuf, Option<SystemTime>)> {
+        std::iter::once(&self.path)
+            .chain(self.import.buffer_files())
+            .chain(self.import.image_files())
+            .map(|file| {
+                let modified = std::fs::metadata(file).and_then(|m| m.modified()).ok();
+                (file.clone(), modified)
+            })
+            .collect()
+    }
+}
+
+fn stamp_of(stamps: &[(PathBuf, Option<SystemTime>)], file: &Path) -> Option<SystemTime> {
+    stamps
+        .iter()
+        .find(|(path, _)| path == file)
+        .and_then(|(_, modified)| *modified)
+}
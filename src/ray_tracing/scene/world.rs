@@ -0,0 +1,652 @@
+use crate::ray_tracing::acceleration::Accelerator;
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::geometry::hittable_list::HittableList;
+use crate::ray_tracing::geometry::light_link::LightLink;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::{LightLinkTag, Ray};
+use crate::ray_tracing::math::vec3::{Point3, Vec3};
+use crate::ray_tracing::rendering::background::Background;
+use crate::ray_tracing::rendering::camera::Camera;
+use crate::ray_tracing::rendering::color::luminance;
+use crate::ray_tracing::rendering::framebuffer::FrameBuffer;
+use crate::ray_tracing::rendering::tile_cache::{
+    Crop, SceneSignature, TileCache, TrackedObject, debug_hash,
+};
+use crate::ray_tracing::sampling::environment_light::EnvironmentLight;
+use crate::ray_tracing::sampling::light_list::LightList;
+use crate::ray_tracing::sampling::portal::{PortalSettings, detect_portals};
+use crate::ray_tracing::scene::ray_cast::RayCaster;
+use std::collections::HashMap;
+use std::io;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+/// 下一个物体版本号
+static NEXT_REVISION: AtomicU64 = AtomicU64::new(0);
+
+/// 分配进程内唯一的物体版本号
+fn next_revision() -> u64 {
+    NEXT_REVISION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// 场景中物体的稳定标识
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ObjectId(pub usize);
+
+/// 场景中光源的稳定标识
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LightId(pub usize);
+
+/// 光源采样条目
+#[derive(Debug, Clone)]
+pub struct LightEntry {
+    /// 用于重要性采样的光源形状
+    pub shape: Arc<dyn Hittable>,
+    /// 采样重要性倍数，越大越倾向于采样该光源
+    pub weight: f64,
+    /// 是否启用；禁用时既不参与采样，关联的场景物体也不参与渲染
+    pub enabled: bool,
+    /// 关联的发光物体（通过 add_emitter 添加时存在）
+    pub object: Option<ObjectId>,
+}
+
+/// 光源链接规则：只包含或排除指定的标识
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkRule<T> {
+    /// 只与列出的标识交互
+    Only(Vec<T>),
+    /// 与列出的标识以外的所有标识交互
+    Except(Vec<T>),
+}
+
+impl<T: PartialEq> LinkRule<T> {
+    /// 规则是否允许与 id 交互
+    pub fn allows(&self, id: &T) -> bool {
+        match self {
+            Self::Only(ids) => ids.contains(id),
+            Self::Except(ids) => !ids.contains(id),
+        }
+    }
+}
+
+/// 场景：带标识的顶层物体集合与光源列表，提供查询接口
+#[derive(Default)]
+pub struct Scene {
+    objects: Vec<(ObjectId, Arc<dyn Hittable>)>,
+    /// 物体的版本号：添加或替换时分配，进程内全局唯一
+    revisions: HashMap<ObjectId, u64>,
+    lights: Vec<(LightId, LightEntry)>,
+    next_id: usize,
+    next_light_id: usize,
+    accelerator: Accelerator,
+    cameras: Vec<(String, Camera)>,
+    light_links: Vec<(LightId, LinkRule<ObjectId>)>,
+    object_links: Vec<(ObjectId, LinkRule<LightId>)>,
+}
+
+impl Scene {
+    /// 创建空场景
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加物体，返回其标识
+    pub fn add(&mut self, object: Arc<dyn Hittable>) -> ObjectId {
+        let id = ObjectId(self.next_id);
+        self.next_id += 1;
+        self.objects.push((id, object));
+        self.revisions.insert(id, next_revision());
+        id
+    }
+
+    /// 添加用于重要性采样的光源形状
+    pub fn add_light(&mut self, light: Arc<dyn Hittable>) -> LightId {
+        self.push_light(LightEntry {
+            shape: light,
+            weight: 1.0,
+            enabled: true,
+            object: None,
+        })
+    }
+
+    /// 添加发光物体：同时作为场景物体和采样光源，禁用光源时一并隐藏
+    pub fn add_emitter(&mut self, emitter: Arc<dyn Hittable>) -> (ObjectId, LightId) {
+        let object = self.add(emitter.clone());
+        let light = self.push_light(LightEntry {
+            shape: emitter,
+            weight: 1.0,
+            enabled: true,
+            object: Some(object),
+        });
+        (object, light)
+    }
+
+    /// 检测封闭场景包围盒上的开口，把覆盖开口的光门加入光源列表，返回其标识
+    ///
+    /// 光门只参与采样、不加入场景，背景（环境光）从开口照入的室内场景因此收敛得更快。
+    /// 应在场景几何搭建完成后调用；场景不封闭时不添加任何光门。
+    pub fn add_portals(&mut self, settings: &PortalSettings) -> Vec<LightId> {
+        let Some(bounds) = self.bounds() else {
+            return Vec::new();
+        };
+        let world = self.build_world();
+        detect_portals(world.as_ref(), &bounds, settings)
+            .into_iter()
+            .map(|portal| self.add_light(Arc::new(portal)))
+            .collect()
+    }
+
+    /// 把背景作为按亮度重要性采样的环境光源加入光源列表，返回其标识
+    ///
+    /// 环境光源与场景中的面光源、点光源在同一个光源列表中选择；调用 [`Self::set_light_weights_by_power`]
+    /// 后按实测功率分配采样（环境光的功率按场景包围球估计）。应在场景几何搭建完成后调用，
+    /// 背景须与渲染时相机使用的背景相同。
+    pub fn add_environment_light(&mut self, background: &dyn Background) -> LightId {
+        let radius = self.bounds().map_or(1.0, |b| {
+            let corner = Point3::new(b.x.max, b.y.max, b.z.max);
+            (corner - b.center()).norm()
+        });
+        self.add_light(Arc::new(EnvironmentLight::new(background, radius)))
+    }
+
+    fn push_light(&mut self, entry: LightEntry) -> LightId {
+        let id = LightId(self.next_light_id);
+        self.next_light_id += 1;
+        self.lights.push((id, entry));
+        id
+    }
+
+    fn light_mut(&mut self, id: LightId) -> Option<&mut LightEntry> {
+        self.lights
+            .iter_mut()
+            .find(|(light_id, _)| *light_id == id)
+            .map(|(_, entry)| entry)
+    }
+
+    /// 设置光源的采样重要性倍数
+    pub fn set_light_weight(&mut self, id: LightId, weight: f64) {
+        if let Some(entry) = self.light_mut(id) {
+            entry.weight = weight.max(0.0);
+        }
+    }
+
+    /// 把所有光源的采样重要性设为其发光功率的亮度（功率为0时退回表面积），亮的光源被更多地采样
+    pub fn set_light_weights_by_power(&mut self) {
+        for (_, entry) in &mut self.lights {
+            let power = luminance(&entry.shape.power());
+            entry.weight = if power > 0.0 {
+                power
+            } else {
+                entry.shape.area()
+            };
+        }
+    }
+
+    /// 启用或禁用光源
+    pub fn set_light_enabled(&mut self, id: LightId, enabled: bool) {
+        if let Some(entry) = self.light_mut(id) {
+            entry.enabled = enabled;
+        }
+    }
+
+    /// 仅保留指定光源（调试单个光源的贡献）
+    pub fn solo_light(&mut self, id: LightId) {
+        for (light_id, entry) in &mut self.lights {
+            entry.enabled = *light_id == id;
+        }
+    }
+
+    /// 设置光源照亮哪些物体，替换该光源原有的规则
+    ///
+    /// 链接作用于通过 add_emitter 添加的光源：着色点的散射光线命中其发光物体时，
+    /// 不被允许的物体得不到它的光照。相机直接看到的光源和遮挡不受影响。
+    pub fn set_light_links(&mut self, id: LightId, rule: LinkRule<ObjectId>) {
+        self.light_links.retain(|(light, _)| *light != id);
+        self.light_links.push((id, rule));
+    }
+
+    /// 设置物体接受哪些光源的照明，替换该物体原有的规则
+    pub fn set_object_links(&mut self, id: ObjectId, rule: LinkRule<LightId>) {
+        self.object_links.retain(|(object, _)| *object != id);
+        self.object_links.push((id, rule));
+    }
+
+    /// 清除所有光源链接规则
+    pub fn clear_light_links(&mut self) {
+        self.light_links.clear();
+        self.object_links.clear();
+    }
+
+    /// 光源照亮物体是否被链接规则允许
+    pub fn is_linked(&self, light: LightId, object: ObjectId) -> bool {
+        let light_allows = self
+            .light_links
+            .iter()
+            .filter(|(id, _)| *id == light)
+            .all(|(_, rule)| rule.allows(&object));
+        let object_allows = self
+            .object_links
+            .iter()
+            .filter(|(id, _)| *id == object)
+            .all(|(_, rule)| rule.allows(&light));
+        light_allows && object_allows
+    }
+
+    /// 把链接规则编译为每个物体的链接标签（只返回非默认标签）
+    ///
+    /// 受规则限制的发光物体各占一个链接组（1..64，组0留给未链接的物体），
+    /// 接收者的组集合去掉照不到它的光源所在的组。超出组数上限的光源不再受限制。
+    fn link_tags(&self) -> Vec<(ObjectId, LightLinkTag)> {
+        if self.light_links.is_empty() && self.object_links.is_empty() {
+            return Vec::new();
+        }
+        let mut groups: Vec<(LightId, ObjectId, u8)> = Vec::new();
+        for (light, entry) in &self.lights {
+            let Some(emitter) = entry.object else {
+                continue;
+            };
+            if self
+                .objects
+                .iter()
+                .all(|(id, _)| self.is_linked(*light, *id))
+            {
+                continue;
+            }
+            if groups.len() >= 63 {
+                eprintln!(
+                    "光源链接最多支持 63 个受限光源，光源 {:?} 不受链接限制",
+                    light
+                );
+                continue;
+            }
+            groups.push((*light, emitter, groups.len() as u8 + 1));
+        }
+
+        self.objects
+            .iter()
+            .filter_map(|(id, _)| {
+                let mut tag = LightLinkTag::DEFAULT;
+                if let Some((_, _, group)) = groups.iter().find(|(_, e, _)| e == id) {
+                    tag.group = *group;
+                }
+                for (light, _, group) in &groups {
+                    if !self.is_linked(*light, *id) {
+                        tag.links = tag.links.without(*group);
+                    }
+                }
+                (tag != LightLinkTag::DEFAULT).then_some((*id, tag))
+            })
+            .collect()
+    }
+
+    /// 按添加顺序遍历物体
+    pub fn objects(&self) -> impl Iterator<Item = (ObjectId, &Arc<dyn Hittable>)> {
+        self.objects.iter().map(|(id, object)| (*id, object))
+    }
+
+    /// 根据标识获取物体
+    pub fn get(&self, id: ObjectId) -> Option<&Arc<dyn Hittable>> {
+        self.objects
+            .iter()
+            .find(|(object_id, _)| *object_id == id)
+            .map(|(_, object)| object)
+    }
+
+    /// 遍历类型为 T 的顶层物体（不进入嵌套的列表或BVH）
+    pub fn objects_of<T: Hittable>(&self) -> impl Iterator<Item = (ObjectId, &T)> {
+        self.objects
+            .iter()
+            .filter_map(|(id, object)| object.downcast_ref::<T>().map(|o| (*id, o)))
+    }
+
+    /// 移除物体并返回它；通过 add_emitter 与之关联的光源一并移除。其他物体的标识不变
+    pub fn remove(&mut self, id: ObjectId) -> Option<Arc<dyn Hittable>> {
+        let index = self
+            .objects
+            .iter()
+            .position(|(object_id, _)| *object_id == id)?;
+        self.lights.retain(|(_, entry)| entry.object != Some(id));
+        self.object_links.retain(|(object, _)| *object != id);
+        self.revisions.remove(&id);
+        Some(self.objects.remove(index).1)
+    }
+
+    /// 替换物体并返回原物体，标识保持不变；关联的光源形状同时替换为新物体
+    pub fn replace(
+        &mut self,
+        id: ObjectId,
+        object: Arc<dyn Hittable>,
+    ) -> Option<Arc<dyn Hittable>> {
+        let slot = self
+            .objects
+            .iter_mut()
+            .find(|(object_id, _)| *object_id == id)
+            .map(|(_, object)| object)?;
+        let old = std::mem::replace(slot, object.clone());
+        self.revisions.insert(id, next_revision());
+        for (_, entry) in &mut self.lights {
+            if entry.object == Some(id) {
+                entry.shape = object.clone();
+            }
+        }
+        Some(old)
+    }
+
+    /// 移除光源（关联的场景物体保留，不再随光源禁用而隐藏）
+    pub fn remove_light(&mut self, id: LightId) -> Option<LightEntry> {
+        let index = self
+            .lights
+            .iter()
+            .position(|(light_id, _)| *light_id == id)?;
+        self.light_links.retain(|(light, _)| *light != id);
+        Some(self.lights.remove(index).1)
+    }
+
+    /// 物体数量
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// 检查是否为空
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// 遍历光源条目
+    pub fn lights(&self) -> impl Iterator<Item = (LightId, &LightEntry)> {
+        self.lights.iter().map(|(id, entry)| (*id, entry))
+    }
+
+    /// 被禁用光源隐藏的物体
+    fn is_hidden(&self, id: ObjectId) -> bool {
+        self.lights
+            .iter()
+            .any(|(_, entry)| !entry.enabled && entry.object == Some(id))
+    }
+
+    /// 场景整体包围盒
+    pub fn bounds(&self) -> Option<Aabb> {
+        self.objects
+            .iter()
+            .filter_map(|(_, object)| object.bounding_box())
+            .reduce(|a, b| a.merge(&b))
+    }
+
+    /// 场景的包围球（球心, 半径）：球心为整体包围盒的中心，半径取到各物体包围盒最远角点的距离，
+    /// 比整体包围盒的外接球更紧；没有有限大小的物体时为None
+    pub fn bounding_sphere(&self) -> Option<(Point3, f64)> {
+        let boxes: Vec<Aabb> = self
+            .objects
+            .iter()
+            .filter_map(|(_, object)| object.bounding_box())
+            .filter(|b| {
+                [b.x, b.y, b.z]
+                    .iter()
+                    .all(|axis| axis.min.is_finite() && axis.max.is_finite())
+            })
+            .collect();
+        let center = boxes.iter().copied().reduce(|a, b| a.merge(&b))?.center();
+        let radius = boxes
+            .iter()
+            .map(|b| {
+                let far = |min: f64, max: f64, c: f64| (min - c).abs().max((max - c).abs());
+                Vec3::new(
+                    far(b.x.min, b.x.max, center.x),
+                    far(b.y.min, b.y.max, center.y),
+                    far(b.z.min, b.z.max, center.z),
+                )
+                .norm()
+            })
+            .fold(0.0, f64::max);
+        Some((center, radius))
+    }
+
+    /// 拾取：返回光线最先命中的物体标识和命中信息
+    pub fn pick(&self, r: &Ray) -> Option<(ObjectId, HitRecord)> {
+        let mut closest = f64::INFINITY;
+        let mut result = None;
+        let mut rec = HitRecord::default();
+
+        for (id, object) in &self.objects {
+            let ray_t = Interval::new(0.001, closest);
+            if let Some(bbox) = object.bounding_box()
+                && !bbox.hit(r, ray_t)
+            {
+                continue;
+            }
+            if object.hit(r, ray_t, &mut rec) {
+                closest = rec.t;
+                result = Some((*id, rec.clone()));
+            }
+        }
+
+        result
+    }
+
+    /// 返回包围盒与给定区域重叠的物体标识（无包围盒的物体视为无限大）
+    pub fn objects_in(&self, region: &Aabb) -> Vec<ObjectId> {
+        self.objects
+            .iter()
+            .filter(|(_, object)| {
+                object
+                    .bounding_box()
+                    .is_none_or(|bbox| bbox.overlaps(region))
+            })
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// 构建用于渲染的加速结构
+    pub fn build_world(&self) -> Arc<dyn Hittable> {
+        self.build_world_with(|_, object| object)
+    }
+
+    /// 构建加速结构，每个可见物体（带上链接标签后）先经过 wrap 包装
+    fn build_world_with(
+        &self,
+        wrap: impl Fn(ObjectId, Arc<dyn Hittable>) -> Arc<dyn Hittable>,
+    ) -> Arc<dyn Hittable> {
+        let tags = self.link_tags();
+        let list: HittableList = self
+            .objects
+            .iter()
+            .filter(|(id, _)| !self.is_hidden(*id))
+            .map(|(id, o)| {
+                let object = match tags.iter().find(|(tagged, _)| tagged == id) {
+                    Some((_, tag)) => {
+                        Arc::new(LightLink::new(o.clone(), *tag)) as Arc<dyn Hittable>
+                    }
+                    None => o.clone(),
+                };
+                wrap(*id, object)
+            })
+            .collect();
+        self.accelerator.build(&list)
+    }
+
+    /// 场景的摘要，用于判断分块缓存是否仍然有效
+    ///
+    /// 结构哈希覆盖可见物体的标识和包围盒、光源条目、光源链接和加速结构；物体的内容用版本号表示，
+    /// 每次 [`add`](Self::add) 或 [`replace`](Self::replace) 都会分配新的版本号（即使新物体与原物体相同）。
+    /// 与物体关联的光源只计入包围盒和权重，其内容由物体的版本号覆盖；
+    /// 独立的光源形状按其地址和 `Debug` 输出计入结构哈希。
+    pub fn signature(&self) -> SceneSignature {
+        let visible: Vec<_> = self
+            .objects
+            .iter()
+            .filter(|(id, _)| !self.is_hidden(*id))
+            .collect();
+        let bounds: Vec<_> = visible
+            .iter()
+            .map(|(id, object)| (*id, object.bounding_box()))
+            .collect();
+        let lights: Vec<_> = self
+            .lights
+            .iter()
+            .filter(|(_, entry)| entry.enabled)
+            .map(|(id, entry)| {
+                let shape = match entry.object {
+                    Some(_) => debug_hash(&entry.shape.bounding_box()),
+                    None => debug_hash(&(Arc::as_ptr(&entry.shape).cast::<()>(), &entry.shape)),
+                };
+                (*id, entry.weight, entry.object, shape)
+            })
+            .collect();
+        let structure = debug_hash(&(
+            bounds,
+            lights,
+            &self.light_links,
+            &self.object_links,
+            self.accelerator,
+        ));
+        let objects = visible
+            .iter()
+            .filter_map(|(id, _)| self.revisions.get(id).map(|revision| (*id, *revision)))
+            .collect();
+        SceneSignature { structure, objects }
+    }
+
+    /// 使用分块缓存渲染：依赖的物体没有变化的分块直接复用上次的结果
+    ///
+    /// crop 指定时只渲染与之重叠的分块，其余分块使用缓存中的结果（可能已过期），没有缓存时留空。
+    /// 在同一个 `TileCache` 上反复调用，只修改少数物体的材质时只重新渲染光线经过这些物体的分块；
+    /// 复用情况见 [`TileCache::stats`]。
+    pub fn render_cached(
+        &self,
+        camera: &Camera,
+        cache: &TileCache,
+        crop: Option<Crop>,
+    ) -> FrameBuffer {
+        cache.begin_frame(camera, self.signature(), crop);
+        let world = self.build_world_with(|id, object| Arc::new(TrackedObject::new(id, object)));
+        camera
+            .clone()
+            .with_tile_cache(cache.clone())
+            .render_to_buffer(world.as_ref(), self.light_sampler())
+    }
+
+    /// 构建加速结构并返回用于最近交点和遮挡查询的光线投射器（与渲染无关）
+    ///
+    /// 之后对场景的修改不会反映到已返回的投射器中。
+    pub fn ray_caster(&self) -> RayCaster {
+        RayCaster::new(self.build_world())
+    }
+
+    /// 设置 `build_world` 使用的加速结构（默认为BVH）
+    #[inline]
+    pub fn set_accelerator(&mut self, accelerator: Accelerator) {
+        self.accelerator = accelerator;
+    }
+
+    /// `build_world` 使用的加速结构
+    #[inline]
+    pub fn accelerator(&self) -> Accelerator {
+        self.accelerator
+    }
+
+    /// 添加命名相机，同名相机会被替换；每个相机按自己的 output_filename 输出
+    pub fn add_camera(&mut self, name: impl Into<String>, camera: Camera) {
+        let name = name.into();
+        match self.cameras.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, existing)) => *existing = camera,
+            None => self.cameras.push((name, camera)),
+        }
+    }
+
+    /// 根据名称获取相机
+    pub fn camera(&self, name: &str) -> Option<&Camera> {
+        self.cameras
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, camera)| camera)
+    }
+
+    /// 按添加顺序遍历命名相机
+    pub fn cameras(&self) -> impl Iterator<Item = (&str, &Camera)> {
+        self.cameras
+            .iter()
+            .map(|(name, camera)| (name.as_str(), camera))
+    }
+
+    /// 只构建一次加速结构，依次用所有相机（或指定名称的相机）渲染场景
+    ///
+    /// 名称不存在时返回 NotFound 错误并列出可用的相机；返回渲染的相机数。
+    pub fn render_cameras(&self, name: Option<&str>) -> io::Result<usize> {
+        let selected: Vec<&(String, Camera)> = match name {
+            Some(name) => {
+                let found: Vec<_> = self.cameras.iter().filter(|(n, _)| n == name).collect();
+                if found.is_empty() {
+                    let names: Vec<&str> = self.cameras.iter().map(|(n, _)| n.as_str()).collect();
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!(
+                            "场景中没有名为 `{}` 的相机（可用: {}）",
+                            name,
+                            names.join(", ")
+                        ),
+                    ));
+                }
+                found
+            }
+            None => self.cameras.iter().collect(),
+        };
+
+        let build_start = Instant::now();
+        let world = self.build_world();
+        let lights = self.light_sampler();
+        eprintln!(
+            "构建加速结构（{:?}）耗时: {:?}",
+            self.accelerator,
+            build_start.elapsed()
+        );
+
+        for (index, (name, camera)) in selected.iter().enumerate() {
+            let start = Instant::now();
+            eprintln!(
+                "[{}/{}] 渲染相机 `{}` -> {}",
+                index + 1,
+                selected.len(),
+                name,
+                camera.output_filename()
+            );
+            camera.render_frame(world.as_ref(), lights.clone());
+            eprintln!("相机 `{}` 渲染完成，耗时: {:?}", name, start.elapsed());
+        }
+        Ok(selected.len())
+    }
+
+    /// 用于重要性采样的光源，按权重选择且跳过禁用的光源（无可用光源时为None）
+    pub fn light_sampler(&self) -> Option<Arc<dyn Hittable>> {
+        let mut sampler = LightList::new();
+        for (_, entry) in &self.lights {
+            if entry.enabled {
+                sampler.add(entry.shape.clone(), entry.weight);
+            }
+        }
+
+        if sampler.is_empty() {
+            None
+        } else {
+            Some(Arc::new(sampler))
+        }
+    }
+}
+
+impl std::fmt::Debug for Scene {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scene")
+            .field("objects", &format!("{} objects", self.objects.len()))
+            .field("lights", &format!("{} lights", self.lights.len()))
+            .field("accelerator", &self.accelerator)
+            .field(
+                "cameras",
+                &self.cameras.iter().map(|(n, _)| n).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
@@ -0,0 +1,405 @@
+//! 渲染器全局默认配置（raytracer.toml）
+//!
+//! 程序入口用 [`RendererConfig::load_default`] 加载配置并报告错误，再用 [`install`] 安装为全局配置；
+//! 未安装时 [`global`] 只使用默认值，不读取配置文件。
+//!
+//! 支持 TOML 的一个子集：顶层的 `键 = 值`，值可以是字符串、整数、浮点数、布尔值或字符串数组，`#` 开始注释。
+//!
+//! ```toml
+//! output_dir = "renders"
+//! samples_per_pixel = 200
+//! max_depth = 50
+//! threads = 8
+//! texture_paths = ["textures", "/data/textures"]
+//! texture_color_space = "srgb"       # linear | srgb | display-p3
+//! output_color_space = "display-p3"  # gamma2 | srgb | display-p3
+//! termination = "hybrid:3"           # fixed | roulette[:N] | hybrid[:N]
+//! denoise = 1.0                      # 降噪强度，0 为不降噪
+//! caustics = 200000                  # 焦散光子图每轮的光子数，0 为不使用
+//! projection = "little-planet:300"   # perspective | little-planet[:视场角] | tilt-shift:倾角[:上移[:焦距]]
+//! dof_sampling = 1.0                 # 景深感知采样的权重，0 为不使用
+//! shutter = "trapezoid:0.25"         # box | trapezoid[:斜坡比例] | sine | curve:v0,v1,...
+//! report = true                      # 渲染结束时在输出文件旁写出 JSON 渲染报告
+//! time_budget = "10m"                # 渐进渲染的时间预算（秒或带 s/m/h 单位的字符串）
+//! target_noise = 1e-4                # 渐进渲染的目标噪声（每像素亮度估计值方差的平均值）
+//! ```
+
+use crate::ray_tracing::rendering::caustics::CausticSettings;
+use crate::ray_tracing::rendering::color_space::{OutputColorSpace, TextureColorSpace};
+use crate::ray_tracing::rendering::denoise::DenoiseSettings;
+use crate::ray_tracing::rendering::dof_sampling::DofSampling;
+use crate::ray_tracing::rendering::halt::{HaltCondition, parse_duration};
+use crate::ray_tracing::rendering::projection::Projection;
+use crate::ray_tracing::rendering::shutter::Shutter;
+use crate::ray_tracing::rendering::termination::TerminationPolicy;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// 默认配置文件名（在当前工作目录中查找）
+pub const CONFIG_FILENAME: &str = "raytracer.toml";
+
+/// 指定配置文件路径的环境变量
+pub const CONFIG_ENV: &str = "RAYTRACER_CONFIG";
+
+/// 额外纹理目录的环境变量（兼容书中的约定）
+pub const IMAGES_ENV: &str = "RTW_IMAGES";
+
+static GLOBAL: OnceLock<RendererConfig> = OnceLock::new();
+
+/// 渲染器默认配置，未设置的项由各场景自己的默认值决定
+#[derive(Debug, Clone, PartialEq)]
+pub struct RendererConfig {
+    /// 输出目录，相对路径的输出文件名将放在此目录下
+    pub output_dir: Option<PathBuf>,
+    pub samples_per_pixel: Option<i32>,
+    pub max_depth: Option<i32>,
+    /// 渲染线程数
+    pub threads: Option<usize>,
+    /// 纹理搜索目录（按顺序查找）
+    pub texture_paths: Vec<PathBuf>,
+    /// 没有声明色彩空间的图像纹理的默认色彩空间
+    pub texture_color_space: TextureColorSpace,
+    /// 相机的默认输出色彩空间
+    pub output_color_space: OutputColorSpace,
+    /// 相机的默认路径终止策略
+    pub termination: TerminationPolicy,
+    /// 相机的默认降噪参数
+    pub denoise: Option<DenoiseSettings>,
+    /// 相机的默认焦散光子映射参数
+    pub caustics: Option<CausticSettings>,
+    /// 相机的默认投影方式
+    pub projection: Projection,
+    /// 相机的默认景深感知采样参数
+    pub dof_sampling: Option<DofSampling>,
+    /// 相机的默认快门效率曲线
+    pub shutter: Shutter,
+    /// 相机是否默认写出 JSON 渲染报告
+    pub report: bool,
+    /// 相机的默认渐进渲染停止条件
+    pub halt: Option<HaltCondition>,
+}
+
+impl Default for RendererConfig {
+    fn default() -> Self {
+        Self {
+            output_dir: None,
+            samples_per_pixel: None,
+            max_depth: None,
+            threads: None,
+            texture_paths: vec![PathBuf::from("textures"), PathBuf::from("../textures")],
+            texture_color_space: TextureColorSpace::default(),
+            output_color_space: OutputColorSpace::default(),
+            termination: TerminationPolicy::default(),
+            denoise: None,
+            caustics: None,
+            projection: Projection::default(),
+            dof_sampling: None,
+            shutter: Shutter::default(),
+            report: false,
+            halt: None,
+        }
+    }
+}
+
+/// 解析后的配置值
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Bool(bool),
+    Array(Vec<String>),
+}
+
+impl Value {
+    /// 数值（整数或浮点数）
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Integer(n) => Some(*n as f64),
+            Value::Float(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    /// 正整数，超出 i32 范围时为 None
+    pub(crate) fn as_positive_i32(&self) -> Option<i32> {
+        match self {
+            Value::Integer(n) if *n > 0 => i32::try_from(*n).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// 一条 `键 = 值` 记录
+pub(crate) struct Entry {
+    pub line_no: usize,
+    pub key: String,
+    pub value: Value,
+}
+
+/// 按本模块支持的 TOML 子集解析文本，source 用于错误信息
+pub(crate) fn parse_entries(text: &str, source: &str) -> io::Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    for (index, raw_line) in text.lines().enumerate() {
+        let line_no = index + 1;
+        let line = strip_comment(raw_line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('[') {
+            return Err(invalid(source, line_no, "不支持表（[section]）"));
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(invalid(source, line_no, "应为 `键 = 值`"));
+        };
+        let value =
+            parse_value(value.trim()).ok_or_else(|| invalid(source, line_no, "无法解析的值"))?;
+        entries.push(Entry {
+            line_no,
+            key: key.trim().to_string(),
+            value,
+        });
+    }
+    Ok(entries)
+}
+
+/// 配置格式错误
+pub(crate) fn invalid(source: &str, line_no: usize, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{} 第{}行: {}", source, line_no, message),
+    )
+}
+
+impl RendererConfig {
+    /// 从配置文本解析，错误信息中的文件名为 raytracer.toml
+    pub fn parse(text: &str) -> io::Result<Self> {
+        Self::parse_from(text, CONFIG_FILENAME)
+    }
+
+    /// 从配置文本解析，source 为错误信息中的文件名
+    fn parse_from(text: &str, source: &str) -> io::Result<Self> {
+        let mut config = Self::default();
+
+        for Entry {
+            line_no,
+            key,
+            value,
+        } in parse_entries(text, source)?
+        {
+            let key = key.as_str();
+            match (key, value) {
+                ("output_dir", Value::String(s)) => config.output_dir = Some(PathBuf::from(s)),
+                ("samples_per_pixel", value) if value.as_positive_i32().is_some() => {
+                    config.samples_per_pixel = value.as_positive_i32()
+                }
+                ("max_depth", value) if value.as_positive_i32().is_some() => {
+                    config.max_depth = value.as_positive_i32()
+                }
+                ("threads", Value::Integer(n)) if n > 0 => config.threads = Some(n as usize),
+                ("texture_paths", Value::Array(paths)) => {
+                    config.texture_paths = paths.into_iter().map(PathBuf::from).collect()
+                }
+                ("texture_color_space", Value::String(s)) => {
+                    config.texture_color_space = s
+                        .parse()
+                        .map_err(|e: String| invalid(source, line_no, &e))?
+                }
+                ("output_color_space", Value::String(s)) => {
+                    config.output_color_space = s
+                        .parse()
+                        .map_err(|e: String| invalid(source, line_no, &e))?
+                }
+                ("termination", Value::String(s)) => {
+                    config.termination = s
+                        .parse()
+                        .map_err(|e: String| invalid(source, line_no, &e))?
+                }
+                ("denoise", value) if value.as_f64().is_some_and(|s| s >= 0.0) => {
+                    let strength = value.as_f64().unwrap_or_default();
+                    config.denoise =
+                        (strength > 0.0).then(|| DenoiseSettings::with_strength(strength));
+                }
+                ("caustics", Value::Integer(n)) if n >= 0 => {
+                    config.caustics = (n > 0).then(|| CausticSettings::with_photons(n as usize));
+                }
+                ("projection", Value::String(s)) => {
+                    config.projection = s
+                        .parse()
+                        .map_err(|e: String| invalid(source, line_no, &e))?
+                }
+                ("dof_sampling", value) if value.as_f64().is_some_and(|w| w >= 0.0) => {
+                    let weight = value.as_f64().unwrap_or_default();
+                    config.dof_sampling = (weight > 0.0).then(|| DofSampling::with_weight(weight));
+                }
+                ("shutter", Value::String(s)) => {
+                    config.shutter = s
+                        .parse()
+                        .map_err(|e: String| invalid(source, line_no, &e))?
+                }
+                ("report", Value::Bool(b)) => config.report = b,
+                ("time_budget", Value::String(s)) => {
+                    let budget = parse_duration(&s).map_err(|e| invalid(source, line_no, &e))?;
+                    config
+                        .halt
+                        .get_or_insert_with(HaltCondition::default)
+                        .time_budget = Some(budget);
+                }
+                ("time_budget", value) if value.as_f64().is_some_and(|s| s > 0.0) => {
+                    let seconds = value.as_f64().unwrap_or_default();
+                    config
+                        .halt
+                        .get_or_insert_with(HaltCondition::default)
+                        .time_budget = Some(std::time::Duration::from_secs_f64(seconds));
+                }
+                ("target_noise", value) if value.as_f64().is_some_and(|n| n > 0.0) => {
+                    config
+                        .halt
+                        .get_or_insert_with(HaltCondition::default)
+                        .target_noise = value.as_f64();
+                }
+                (
+                    "output_dir"
+                    | "samples_per_pixel"
+                    | "max_depth"
+                    | "threads"
+                    | "texture_paths"
+                    | "texture_color_space"
+                    | "output_color_space"
+                    | "termination"
+                    | "denoise"
+                    | "caustics"
+                    | "projection"
+                    | "dof_sampling"
+                    | "shutter"
+                    | "report"
+                    | "time_budget"
+                    | "target_noise",
+                    _,
+                ) => {
+                    return Err(invalid(
+                        source,
+                        line_no,
+                        &format!("`{}` 的值类型或范围无效", key),
+                    ));
+                }
+                _ => eprintln!("警告: {} 第{}行的未知键 `{}` 已忽略", source, line_no, key),
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// 从文件加载配置，错误信息中包含文件路径
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        Self::parse_from(&text, &path.display().to_string())
+    }
+
+    /// 加载默认配置：优先使用环境变量指定的文件，其次是当前目录下的 raytracer.toml，都不存在时使用默认值
+    ///
+    /// 设置了 RTW_IMAGES 时，其目录追加到纹理搜索路径末尾。
+    pub fn load_default() -> io::Result<Self> {
+        let mut config = match std::env::var_os(CONFIG_ENV) {
+            Some(path) => Self::load(path)?,
+            None if Path::new(CONFIG_FILENAME).exists() => Self::load(CONFIG_FILENAME)?,
+            None => Self::default(),
+        };
+
+        config.append_images_env();
+        Ok(config)
+    }
+
+    /// 设置了 RTW_IMAGES 时，把其目录追加到纹理搜索路径末尾
+    fn append_images_env(&mut self) {
+        if let Some(image_dir) = std::env::var_os(IMAGES_ENV) {
+            self.texture_paths.push(PathBuf::from(image_dir));
+        }
+    }
+
+    /// 在当前目录和纹理搜索目录中查找纹理文件
+    pub fn resolve_texture(&self, filename: &str) -> Option<PathBuf> {
+        let direct = PathBuf::from(filename);
+        if direct.exists() {
+            return Some(direct);
+        }
+        self.texture_paths
+            .iter()
+            .map(|dir| dir.join(filename))
+            .find(|path| path.exists())
+    }
+
+    /// 将相对输出文件名放到输出目录下（绝对路径保持不变）
+    pub fn output_path(&self, filename: &str) -> String {
+        match &self.output_dir {
+            Some(dir) if Path::new(filename).is_relative() => {
+                dir.join(filename).to_string_lossy().into_owned()
+            }
+            _ => filename.to_string(),
+        }
+    }
+}
+
+/// 安装全局配置，只能在首次访问前调用一次；已安装时返回false
+pub fn install(config: RendererConfig) -> bool {
+    GLOBAL.set(config).is_ok()
+}
+
+/// 全局配置；未安装时为默认值（纹理搜索路径包含 RTW_IMAGES），不读取配置文件
+pub fn global() -> &'static RendererConfig {
+    GLOBAL.get_or_init(|| {
+        let mut config = RendererConfig::default();
+        config.append_images_env();
+        config
+    })
+}
+
+/// 去掉不在字符串内的 `#` 注释
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_string(s: &str) -> Option<String> {
+    let inner = s.strip_prefix('"')?.strip_suffix('"')?;
+    (!inner.contains('"')).then(|| inner.replace("\\\\", "\\"))
+}
+
+fn parse_value(s: &str) -> Option<Value> {
+    if let Some(inner) = s.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+        let items = inner
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(parse_string)
+            .collect::<Option<Vec<_>>>()?;
+        return Some(Value::Array(items));
+    }
+    if s.starts_with('"') {
+        return parse_string(s).map(Value::String);
+    }
+    match s {
+        "true" => return Some(Value::Bool(true)),
+        "false" => return Some(Value::Bool(false)),
+        _ => {}
+    }
+    let s = s.replace('_', "");
+    if let Ok(n) = s.parse() {
+        return Some(Value::Integer(n));
+    }
+    s.parse()
+        .ok()
+        .filter(|x: &f64| x.is_finite())
+        .map(Value::Float)
+}
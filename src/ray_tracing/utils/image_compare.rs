@@ -0,0 +1,533 @@
+//! 图像比较指标：MSE、PSNR、SSIM、FLIP，用于回归测试和评估采样器/积分器的改动
+
+use crate::ray_tracing::math::vec3::Color;
+use crate::ray_tracing::rendering::color::luminance;
+use crate::ray_tracing::rendering::framebuffer::FrameBuffer;
+use image::{Rgb, Rgb32FImage};
+use std::f64::consts::PI;
+use std::io;
+use std::path::Path;
+
+/// SSIM 高斯窗口半径（11×11 窗口）
+const SSIM_RADIUS: i32 = 5;
+/// SSIM 高斯窗口标准差
+const SSIM_SIGMA: f64 = 1.5;
+/// FLIP 的观察条件：每视角度对应的像素数（0.7 m 外观看 0.7 m 宽、3840 像素的显示器）
+pub const FLIP_PIXELS_PER_DEGREE: f64 = 67.0;
+
+/// 两幅图像的比较结果（像素值按 [0,1] 的显示空间计算）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageMetrics {
+    /// 均方误差（RGB三个通道平均）
+    pub mse: f64,
+    /// 峰值信噪比（dB），图像完全相同时为无穷大
+    pub psnr: f64,
+    /// 结构相似性（亮度通道，1表示完全相同）
+    pub ssim: f64,
+    /// LDR-FLIP 平均感知误差（0表示完全相同，1为最大差异）
+    pub flip: f64,
+}
+
+impl std::fmt::Display for ImageMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "MSE: {:.6e}, PSNR: {:.2} dB, SSIM: {:.4}, FLIP: {:.4}",
+            self.mse, self.psnr, self.ssim, self.flip
+        )
+    }
+}
+
+/// 均方误差
+pub fn mse(a: &Rgb32FImage, b: &Rgb32FImage) -> f64 {
+    let n = a.as_raw().len().max(1) as f64;
+    a.as_raw()
+        .iter()
+        .zip(b.as_raw())
+        .map(|(&x, &y)| {
+            let d = x as f64 - y as f64;
+            d * d
+        })
+        .sum::<f64>()
+        / n
+}
+
+/// 由均方误差计算峰值信噪比（峰值为1）
+#[inline]
+pub fn psnr(mse: f64) -> f64 {
+    if mse <= 0.0 {
+        f64::INFINITY
+    } else {
+        -10.0 * mse.log10()
+    }
+}
+
+/// 平均结构相似性（Wang et al. 2004），在亮度通道上使用 11×11 高斯窗口，边缘按复制处理
+pub fn ssim(a: &Rgb32FImage, b: &Rgb32FImage) -> f64 {
+    const C1: f64 = 0.01 * 0.01;
+    const C2: f64 = 0.03 * 0.03;
+
+    let (width, height) = (a.width() as usize, a.height() as usize);
+    if width == 0 || height == 0 {
+        return 1.0;
+    }
+
+    let luma = |img: &Rgb32FImage| -> Vec<f64> {
+        img.pixels()
+            .map(|p| 0.299 * p[0] as f64 + 0.587 * p[1] as f64 + 0.114 * p[2] as f64)
+            .collect()
+    };
+    let x = luma(a);
+    let y = luma(b);
+    let xx: Vec<f64> = x.iter().map(|v| v * v).collect();
+    let yy: Vec<f64> = y.iter().map(|v| v * v).collect();
+    let xy: Vec<f64> = x.iter().zip(&y).map(|(u, v)| u * v).collect();
+
+    let kernel = gaussian_kernel();
+    let blur = |data: &[f64]| gaussian_blur(data, width, height, &kernel);
+    let (mu_x, mu_y) = (blur(&x), blur(&y));
+    let (e_xx, e_yy, e_xy) = (blur(&xx), blur(&yy), blur(&xy));
+
+    let total: f64 = (0..width * height)
+        .map(|i| {
+            let (mx, my) = (mu_x[i], mu_y[i]);
+            let var_x = e_xx[i] - mx * mx;
+            let var_y = e_yy[i] - my * my;
+            let cov = e_xy[i] - mx * my;
+            ((2.0 * mx * my + C1) * (2.0 * cov + C2))
+                / ((mx * mx + my * my + C1) * (var_x + var_y + C2))
+        })
+        .sum();
+    total / (width * height) as f64
+}
+
+/// 线性 sRGB → XYZ（D65），与 FLIP 参考实现使用相同的系数
+const LINEAR_RGB_TO_XYZ: [[f64; 3]; 3] = [
+    [
+        10135552.0 / 24577794.0,
+        8788810.0 / 24577794.0,
+        4435075.0 / 24577794.0,
+    ],
+    [
+        2613072.0 / 12288897.0,
+        8788810.0 / 12288897.0,
+        887015.0 / 12288897.0,
+    ],
+    [
+        1425312.0 / 73733382.0,
+        8788810.0 / 73733382.0,
+        70074185.0 / 73733382.0,
+    ],
+];
+/// XYZ → 线性 sRGB
+const XYZ_TO_LINEAR_RGB: [[f64; 3]; 3] = [
+    [3.241003232976358, -1.537398969488785, -0.498615881996363],
+    [-0.969224252202516, 1.875929983695176, 0.041554226340085],
+    [0.055639419851975, -0.204011206123910, 1.057148977187533],
+];
+
+fn mat3_mul(m: &[[f64; 3]; 3], v: [f64; 3]) -> [f64; 3] {
+    m.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+/// 参考白点（线性 RGB 白色的 XYZ）
+fn reference_white() -> [f64; 3] {
+    mat3_mul(&LINEAR_RGB_TO_XYZ, [1.0; 3])
+}
+
+fn srgb_to_linear(c: f64) -> f64 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// XYZ → YyCxCz 对立色空间（CIELAB 去掉立方根的线性版本）
+fn xyz_to_ycxcz(xyz: [f64; 3]) -> [f64; 3] {
+    let w = reference_white();
+    let (x, y, z) = (xyz[0] / w[0], xyz[1] / w[1], xyz[2] / w[2]);
+    [116.0 * y - 16.0, 500.0 * (x - y), 200.0 * (y - z)]
+}
+
+fn ycxcz_to_xyz(c: [f64; 3]) -> [f64; 3] {
+    let w = reference_white();
+    let y = (c[0] + 16.0) / 116.0;
+    let x = y + c[1] / 500.0;
+    let z = y - c[2] / 200.0;
+    [x * w[0], y * w[1], z * w[2]]
+}
+
+/// 线性 RGB → Hunt 调整后的 L*a*b*（a、b 按 0.01·L 缩放）
+fn linear_rgb_to_hunt_lab(rgb: [f64; 3]) -> [f64; 3] {
+    let w = reference_white();
+    let xyz = mat3_mul(&LINEAR_RGB_TO_XYZ, rgb);
+    let delta: f64 = 6.0 / 29.0;
+    let f = |t: f64| {
+        if t > delta.powi(3) {
+            t.cbrt()
+        } else {
+            t / (3.0 * delta * delta) + 4.0 / 29.0
+        }
+    };
+    let (fx, fy, fz) = (f(xyz[0] / w[0]), f(xyz[1] / w[1]), f(xyz[2] / w[2]));
+    let l = 116.0 * fy - 16.0;
+    let a = 500.0 * (fx - fy);
+    let b = 200.0 * (fy - fz);
+    [l, 0.01 * l * a, 0.01 * l * b]
+}
+
+/// HyAB 色差：亮度差取绝对值，色度差取欧氏距离
+fn hyab(a: [f64; 3], b: [f64; 3]) -> f64 {
+    (a[0] - b[0]).abs() + (a[1] - b[1]).hypot(a[2] - b[2])
+}
+
+/// 对比敏感度函数的空间滤波器，每个通道是至多两个高斯之和，
+/// 返回各高斯分量的（权重，归一化一维核），二维滤波器为 Σ 权重·核⊗核
+fn csf_filters(pixels_per_degree: f64) -> [Vec<(f64, Vec<f64>)>; 3] {
+    // 亮度、红绿、黄蓝通道的 (a1, b1, a2, b2)
+    const PARAMETERS: [[f64; 4]; 3] = [
+        [1.0, 0.0047, 0.0, 1e-5],
+        [1.0, 0.0053, 0.0, 1e-5],
+        [34.1, 0.04, 13.5, 0.025],
+    ];
+    let max_b = 0.04_f64;
+    let radius = (3.0 * (max_b / (2.0 * PI * PI)).sqrt() * pixels_per_degree).ceil() as i32;
+
+    PARAMETERS.map(|[a1, b1, a2, b2]| {
+        let components: Vec<(f64, Vec<f64>)> = [(a1, b1), (a2, b2)]
+            .into_iter()
+            .filter(|&(a, _)| a > 0.0)
+            .map(|(a, b)| {
+                let kernel: Vec<f64> = (-radius..=radius)
+                    .map(|i| {
+                        let x = i as f64 / pixels_per_degree;
+                        (-PI * PI * x * x / b).exp()
+                    })
+                    .collect();
+                let sum: f64 = kernel.iter().sum();
+                let weight = a * (PI / b).sqrt() * sum * sum;
+                (weight, kernel.into_iter().map(|k| k / sum).collect())
+            })
+            .collect();
+        let total: f64 = components.iter().map(|(w, _)| w).sum();
+        components
+            .into_iter()
+            .map(|(w, kernel)| (w / total, kernel))
+            .collect()
+    })
+}
+
+/// 边缘（一阶导数）和点（二阶导数）检测滤波器的一维部分：(导数核, 平滑核)
+///
+/// 正负权重分别归一化为 ±1，与 FLIP 参考实现一致。
+fn feature_filters(pixels_per_degree: f64) -> [(Vec<f64>, Vec<f64>); 2] {
+    let sigma = 0.5 * 0.082 * pixels_per_degree;
+    let radius = (3.0 * sigma).ceil() as i32;
+    let gaussian: Vec<f64> = (-radius..=radius)
+        .map(|i| (-((i * i) as f64) / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let sum: f64 = gaussian.iter().sum();
+    let smooth: Vec<f64> = gaussian.iter().map(|g| g / sum).collect();
+
+    let normalize = |kernel: Vec<f64>| {
+        let positive: f64 = kernel.iter().filter(|&&k| k > 0.0).sum();
+        let negative: f64 = -kernel.iter().filter(|&&k| k < 0.0).sum::<f64>();
+        kernel
+            .into_iter()
+            .map(|k| if k > 0.0 { k / positive } else { k / negative })
+            .collect::<Vec<f64>>()
+    };
+    let edge = normalize(
+        (-radius..=radius)
+            .zip(&gaussian)
+            .map(|(i, g)| -(i as f64) * g)
+            .collect(),
+    );
+    let point = normalize(
+        (-radius..=radius)
+            .zip(&gaussian)
+            .map(|(i, g)| ((i * i) as f64 / (sigma * sigma) - 1.0) * g)
+            .collect(),
+    );
+    [(edge, smooth.clone()), (point, smooth)]
+}
+
+/// LDR-FLIP 平均误差（Andersson et al. 2020），输入按 sRGB 编码的 [0,1] 显示值解释
+///
+/// 颜色管线在 YyCxCz 空间按对比敏感度函数滤波后用 HyAB 色差度量，
+/// 特征管线比较亮度的边缘和点特征，每像素误差为 ΔE_c^(1-ΔE_f)。
+pub fn flip(reference: &Rgb32FImage, test: &Rgb32FImage, pixels_per_degree: f64) -> f64 {
+    const QC: f64 = 0.7;
+    const PC: f64 = 0.4;
+    const PT: f64 = 0.95;
+    const QF: f64 = 0.5;
+
+    let (width, height) = (reference.width() as usize, reference.height() as usize);
+    if width == 0 || height == 0 {
+        return 0.0;
+    }
+
+    let to_ycxcz = |img: &Rgb32FImage| -> [Vec<f64>; 3] {
+        let mut channels = [vec![], vec![], vec![]];
+        for p in img.pixels() {
+            let rgb = [0, 1, 2].map(|i| srgb_to_linear((p[i] as f64).clamp(0.0, 1.0)));
+            let c = xyz_to_ycxcz(mat3_mul(&LINEAR_RGB_TO_XYZ, rgb));
+            for (channel, v) in channels.iter_mut().zip(c) {
+                channel.push(v);
+            }
+        }
+        channels
+    };
+    let (ref_ycxcz, test_ycxcz) = (to_ycxcz(reference), to_ycxcz(test));
+
+    // 颜色管线
+    let filters = csf_filters(pixels_per_degree);
+    let filtered_lab = |ycxcz: &[Vec<f64>; 3]| -> Vec<[f64; 3]> {
+        let filtered: Vec<Vec<f64>> = ycxcz
+            .iter()
+            .zip(&filters)
+            .map(|(channel, components)| {
+                let mut sum = vec![0.0; channel.len()];
+                for (weight, kernel) in components {
+                    let blurred = convolve_separable(channel, width, height, kernel, kernel);
+                    for (s, b) in sum.iter_mut().zip(blurred) {
+                        *s += weight * b;
+                    }
+                }
+                sum
+            })
+            .collect();
+        (0..width * height)
+            .map(|i| {
+                let xyz = ycxcz_to_xyz([filtered[0][i], filtered[1][i], filtered[2][i]]);
+                let rgb = mat3_mul(&XYZ_TO_LINEAR_RGB, xyz).map(|c| c.clamp(0.0, 1.0));
+                linear_rgb_to_hunt_lab(rgb)
+            })
+            .collect()
+    };
+    let (ref_lab, test_lab) = (filtered_lab(&ref_ycxcz), filtered_lab(&test_ycxcz));
+    let max_error = hyab(
+        linear_rgb_to_hunt_lab([0.0, 1.0, 0.0]),
+        linear_rgb_to_hunt_lab([0.0, 0.0, 1.0]),
+    )
+    .powf(QC);
+    let color_error = |i: usize| {
+        let e = hyab(ref_lab[i], test_lab[i]).powf(QC);
+        if e < PC * max_error {
+            PT / (PC * max_error) * e
+        } else {
+            PT + (e - PC * max_error) / (max_error - PC * max_error) * (1.0 - PT)
+        }
+    };
+
+    // 特征管线：在归一化亮度 Y/Yn 上检测边缘和点
+    let [edge, point] = feature_filters(pixels_per_degree);
+    let features = |ycxcz: &[Vec<f64>; 3]| -> [Vec<f64>; 2] {
+        let y: Vec<f64> = ycxcz[0].iter().map(|l| (l + 16.0) / 116.0).collect();
+        [&edge, &point].map(|(derivative, smooth)| {
+            let dx = convolve_separable(&y, width, height, derivative, smooth);
+            let dy = convolve_separable(&y, width, height, smooth, derivative);
+            dx.iter().zip(&dy).map(|(x, y)| x.hypot(*y)).collect()
+        })
+    };
+    let [ref_edges, ref_points] = features(&ref_ycxcz);
+    let [test_edges, test_points] = features(&test_ycxcz);
+    let feature_error = |i: usize| {
+        let d = (ref_edges[i] - test_edges[i])
+            .abs()
+            .max((ref_points[i] - test_points[i]).abs());
+        (d / 2f64.sqrt()).powf(QF)
+    };
+
+    let total: f64 = (0..width * height)
+        .map(|i| color_error(i).powf(1.0 - feature_error(i)))
+        .sum();
+    total / (width * height) as f64
+}
+
+/// 比较两幅尺寸相同的图像
+pub fn compare(a: &Rgb32FImage, b: &Rgb32FImage) -> io::Result<ImageMetrics> {
+    if a.dimensions() != b.dimensions() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "图像尺寸不一致: {}x{} 与 {}x{}",
+                a.width(),
+                a.height(),
+                b.width(),
+                b.height()
+            ),
+        ));
+    }
+
+    let mse = mse(a, b);
+    Ok(ImageMetrics {
+        mse,
+        psnr: psnr(mse),
+        ssim: ssim(a, b),
+        flip: flip(a, b, FLIP_PIXELS_PER_DEGREE),
+    })
+}
+
+/// 读取两个图像文件并比较
+pub fn compare_files(a: impl AsRef<Path>, b: impl AsRef<Path>) -> io::Result<ImageMetrics> {
+    let load = |path: &Path| {
+        image::open(path)
+            .map(|img| img.to_rgb32f())
+            .map_err(io::Error::other)
+    };
+    compare(&load(a.as_ref())?, &load(b.as_ref())?)
+}
+
+/// 帧缓冲区按其输出色彩空间编码后的显示图像，用于计算比较指标
+pub fn display_image(fb: &FrameBuffer) -> Rgb32FImage {
+    Rgb32FImage::from_fn(fb.width(), fb.height(), |x, y| {
+        let c = fb.color_space().encode(&fb.get(x, y));
+        Rgb([c.x as f32, c.y as f32, c.z as f32])
+    })
+}
+
+/// 把高度相同的若干帧缓冲区从左到右拼接为一幅图像（高度不同时以最高者为准，空白处为黑色）
+pub fn side_by_side(images: &[&FrameBuffer]) -> FrameBuffer {
+    let width = images.iter().map(|fb| fb.width()).sum();
+    let height = images.iter().map(|fb| fb.height()).max().unwrap_or(0);
+    let mut out = FrameBuffer::new(width, height);
+    if let Some(first) = images.first() {
+        out.set_color_space(first.color_space());
+    }
+
+    let mut x0 = 0;
+    for fb in images {
+        for y in 0..fb.height() {
+            for x in 0..fb.width() {
+                out.set(x0 + x, y, fb.get(x, y));
+            }
+        }
+        x0 += fb.width();
+    }
+    out
+}
+
+/// 把若干帧缓冲区按行排成每行 columns 幅的网格（印样），格子之间留 gap 像素的黑色间隔
+///
+/// 每个格子的尺寸取所有图像中的最大宽度和高度，较小的图像放在格子左上角。
+pub fn contact_sheet(images: &[&FrameBuffer], columns: usize, gap: u32) -> FrameBuffer {
+    let columns = columns.clamp(1, images.len().max(1));
+    let rows = images.len().div_ceil(columns);
+    let cell_width = images.iter().map(|fb| fb.width()).max().unwrap_or(0);
+    let cell_height = images.iter().map(|fb| fb.height()).max().unwrap_or(0);
+    let span = |count: usize, cell: u32| (count as u32 * (cell + gap)).saturating_sub(gap);
+    let mut out = FrameBuffer::new(span(columns, cell_width), span(rows, cell_height));
+    if let Some(first) = images.first() {
+        out.set_color_space(first.color_space());
+    }
+
+    for (index, fb) in images.iter().enumerate() {
+        let x0 = (index % columns) as u32 * (cell_width + gap);
+        let y0 = (index / columns) as u32 * (cell_height + gap);
+        for y in 0..fb.height() {
+            for x in 0..fb.width() {
+                out.set(x0 + x, y0 + y, fb.get(x, y));
+            }
+        }
+    }
+    out
+}
+
+/// 像素亮度的中位数（不受光源等少量极亮像素影响），用于归一化不同积分器的结果
+pub fn median_luminance(fb: &FrameBuffer) -> f64 {
+    let mut values: Vec<f64> = fb
+        .pixels()
+        .iter()
+        .map(luminance)
+        .filter(|l| l.is_finite())
+        .collect();
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mid = values.len() / 2;
+    *values.select_nth_unstable_by(mid, f64::total_cmp).1
+}
+
+/// 按亮度中位数归一化后的有符号亮度差异图：b 比 a 亮的区域为红色，暗的区域为蓝色
+///
+/// 差异按 scale 缩放后截断到 [0,1]；两幅图像尺寸须相同。
+pub fn luminance_difference(a: &FrameBuffer, b: &FrameBuffer, scale: f64) -> FrameBuffer {
+    let (median_a, median_b) = (
+        median_luminance(a).max(1e-12),
+        median_luminance(b).max(1e-12),
+    );
+
+    let mut out = FrameBuffer::new(a.width(), a.height());
+    out.set_color_space(a.color_space());
+    for y in 0..a.height().min(b.height()) {
+        for x in 0..a.width().min(b.width()) {
+            let d = luminance(&b.get(x, y)) / median_b - luminance(&a.get(x, y)) / median_a;
+            let d = (d * scale).clamp(-1.0, 1.0);
+            let color = if d >= 0.0 {
+                Color::new(d, 0.0, 0.0)
+            } else {
+                Color::new(0.0, 0.0, -d)
+            };
+            out.set(x, y, color);
+        }
+    }
+    out
+}
+
+/// 归一化的一维高斯核
+fn gaussian_kernel() -> Vec<f64> {
+    let kernel: Vec<f64> = (-SSIM_RADIUS..=SSIM_RADIUS)
+        .map(|i| (-((i * i) as f64) / (2.0 * SSIM_SIGMA * SSIM_SIGMA)).exp())
+        .collect();
+    let sum: f64 = kernel.iter().sum();
+    kernel.into_iter().map(|k| k / sum).collect()
+}
+
+/// 可分离高斯模糊（边缘像素复制）
+fn gaussian_blur(data: &[f64], width: usize, height: usize, kernel: &[f64]) -> Vec<f64> {
+    convolve_separable(data, width, height, kernel, kernel)
+}
+
+/// 可分离卷积：先沿水平方向应用 horizontal，再沿竖直方向应用 vertical（奇数长度、中心对齐，边缘像素复制）
+fn convolve_separable(
+    data: &[f64],
+    width: usize,
+    height: usize,
+    horizontal: &[f64],
+    vertical: &[f64],
+) -> Vec<f64> {
+    let clamp = |v: i32, max: usize| v.clamp(0, max as i32 - 1) as usize;
+
+    let h_radius = (horizontal.len() / 2) as i32;
+    let mut pass = vec![0.0; data.len()];
+    for row in 0..height {
+        for col in 0..width {
+            pass[row * width + col] = horizontal
+                .iter()
+                .enumerate()
+                .map(|(k, w)| {
+                    let c = clamp(col as i32 + k as i32 - h_radius, width);
+                    w * data[row * width + c]
+                })
+                .sum();
+        }
+    }
+
+    let v_radius = (vertical.len() / 2) as i32;
+    let mut out = vec![0.0; data.len()];
+    for row in 0..height {
+        for col in 0..width {
+            out[row * width + col] = vertical
+                .iter()
+                .enumerate()
+                .map(|(k, w)| {
+                    let r = clamp(row as i32 + k as i32 - v_radius, height);
+                    w * pass[r * width + col]
+                })
+                .sum();
+        }
+    }
+    out
+}
@@ -0,0 +1,392 @@
+//! 最小的 JSON 解析器和写出器，用于读取 glTF 等基于 JSON 的场景格式和写出渲染报告
+//!
+//! 支持完整的 JSON 语法（包括 `\uXXXX` 转义和代理对），数字统一按 f64 保存。
+
+use std::fmt::{self, Write};
+use std::io;
+
+/// JSON 值，对象保留键的原始顺序
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// 解析 JSON 文本，source 用于错误信息
+    pub(crate) fn parse(text: &str, source: &str) -> io::Result<Json> {
+        let mut parser = Parser {
+            bytes: text.as_bytes(),
+            pos: 0,
+            source,
+        };
+        parser.skip_whitespace();
+        let value = parser.value(0)?;
+        parser.skip_whitespace();
+        if parser.pos != parser.bytes.len() {
+            return Err(parser.error("值之后有多余的内容"));
+        }
+        Ok(value)
+    }
+
+    /// 对象的字段，不是对象或没有该字段时为None
+    pub(crate) fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    /// 非负整数
+    #[inline]
+    pub(crate) fn as_usize(&self) -> Option<usize> {
+        match self {
+            Json::Number(x) if *x >= 0.0 && x.fract() == 0.0 => Some(*x as usize),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn as_bool(&self) -> Option<bool> {
+        match self {
+            Json::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// 数组形式的字段（缺失时为空）
+    pub(crate) fn array_field(&self, key: &str) -> &[Json] {
+        self.get(key).and_then(Json::as_array).unwrap_or(&[])
+    }
+
+    /// 数值字段
+    pub(crate) fn f64_field(&self, key: &str) -> Option<f64> {
+        self.get(key).and_then(Json::as_f64)
+    }
+
+    /// 非负整数字段
+    pub(crate) fn usize_field(&self, key: &str) -> Option<usize> {
+        self.get(key).and_then(Json::as_usize)
+    }
+
+    /// 数值数组字段，长度必须为 N
+    pub(crate) fn f64_array<const N: usize>(&self, key: &str) -> Option<[f64; N]> {
+        let items = self.get(key)?.as_array()?;
+        if items.len() != N {
+            return None;
+        }
+        let mut out = [0.0; N];
+        for (slot, item) in out.iter_mut().zip(items) {
+            *slot = item.as_f64()?;
+        }
+        Some(out)
+    }
+}
+
+impl Json {
+    fn is_scalar(&self) -> bool {
+        !matches!(self, Json::Array(_) | Json::Object(_))
+    }
+}
+
+impl fmt::Display for Json {
+    /// 紧凑格式；`{:#}` 输出两空格缩进的多行格式
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_value(f, self, f.alternate(), 0)
+    }
+}
+
+fn write_value(
+    f: &mut fmt::Formatter<'_>,
+    value: &Json,
+    pretty: bool,
+    indent: usize,
+) -> fmt::Result {
+    let newline = |f: &mut fmt::Formatter<'_>, level: usize| -> fmt::Result {
+        if pretty {
+            f.write_char('\n')?;
+            for _ in 0..level {
+                f.write_str("  ")?;
+            }
+        }
+        Ok(())
+    };
+    match value {
+        Json::Null => f.write_str("null"),
+        Json::Bool(b) => write!(f, "{}", b),
+        // JSON 没有 NaN 和无穷大
+        Json::Number(x) if !x.is_finite() => f.write_str("null"),
+        Json::Number(x) if x.fract() == 0.0 && x.abs() < 1e15 => write!(f, "{}", *x as i64),
+        Json::Number(x) => write!(f, "{}", x),
+        Json::String(s) => write_string(f, s),
+        Json::Array(items) if items.is_empty() => f.write_str("[]"),
+        // 只含标量的数组即使在多行格式下也写在一行
+        Json::Array(items) if pretty && items.iter().all(Json::is_scalar) => {
+            write_value(f, value, false, indent)
+        }
+        Json::Array(items) => {
+            f.write_char('[')?;
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    f.write_char(',')?;
+                }
+                newline(f, indent + 1)?;
+                write_value(f, item, pretty, indent + 1)?;
+            }
+            newline(f, indent)?;
+            f.write_char(']')
+        }
+        Json::Object(fields) if fields.is_empty() => f.write_str("{}"),
+        Json::Object(fields) => {
+            f.write_char('{')?;
+            for (i, (key, item)) in fields.iter().enumerate() {
+                if i > 0 {
+                    f.write_char(',')?;
+                }
+                newline(f, indent + 1)?;
+                write_string(f, key)?;
+                f.write_str(if pretty { ": " } else { ":" })?;
+                write_value(f, item, pretty, indent + 1)?;
+            }
+            newline(f, indent)?;
+            f.write_char('}')
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+/// 嵌套层数上限，防止恶意输入导致栈溢出
+const MAX_DEPTH: usize = 256;
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    source: &'a str,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} 第{}字节: {}", self.source, self.pos, message),
+        )
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.bytes.get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> io::Result<()> {
+        if self.bytes.get(self.pos) == Some(&byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("应为 '{}'", byte as char)))
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Json) -> io::Result<Json> {
+        if self.bytes[self.pos..].starts_with(word.as_bytes()) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            Err(self.error("无法识别的值"))
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> io::Result<Json> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("嵌套层数过多"));
+        }
+        match self.bytes.get(self.pos) {
+            Some(b'{') => self.object(depth),
+            Some(b'[') => self.array(depth),
+            Some(b'"') => self.string().map(Json::String),
+            Some(b't') => self.literal("true", Json::Bool(true)),
+            Some(b'f') => self.literal("false", Json::Bool(false)),
+            Some(b'n') => self.literal("null", Json::Null),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.error("无法识别的值")),
+            None => Err(self.error("意外的文件结尾")),
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> io::Result<Json> {
+        self.expect(b'{')?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) == Some(&b'}') {
+            self.pos += 1;
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(b':')?;
+            self.skip_whitespace();
+            fields.push((key, self.value(depth + 1)?));
+            self.skip_whitespace();
+            match self.bytes.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(fields));
+                }
+                _ => return Err(self.error("应为 ',' 或 '}'")),
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> io::Result<Json> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) == Some(&b']') {
+            self.pos += 1;
+            return Ok(Json::Array(items));
+        }
+        loop {
+            self.skip_whitespace();
+            items.push(self.value(depth + 1)?);
+            self.skip_whitespace();
+            match self.bytes.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(items));
+                }
+                _ => return Err(self.error("应为 ',' 或 ']'")),
+            }
+        }
+    }
+
+    fn number(&mut self) -> io::Result<Json> {
+        let start = self.pos;
+        while let Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') = self.bytes.get(self.pos) {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos])
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .map(Json::Number)
+            .ok_or_else(|| self.error("无效的数字"))
+    }
+
+    fn hex4(&mut self) -> io::Result<u32> {
+        let digits = self
+            .bytes
+            .get(self.pos..self.pos + 4)
+            .and_then(|d| std::str::from_utf8(d).ok())
+            .and_then(|d| u32::from_str_radix(d, 16).ok())
+            .ok_or_else(|| self.error("无效的 \\u 转义"))?;
+        self.pos += 4;
+        Ok(digits)
+    }
+
+    fn string(&mut self) -> io::Result<String> {
+        self.expect(b'"')?;
+        let mut out = String::new();
+        loop {
+            // 连续的普通字符整段复制
+            let start = self.pos;
+            while let Some(&b) = self.bytes.get(self.pos) {
+                if b == b'"' || b == b'\\' || b < 0x20 {
+                    break;
+                }
+                self.pos += 1;
+            }
+            out.push_str(
+                std::str::from_utf8(&self.bytes[start..self.pos])
+                    .map_err(|_| self.error("字符串不是有效的 UTF-8"))?,
+            );
+
+            match self.bytes.get(self.pos) {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let escape = *self
+                        .bytes
+                        .get(self.pos)
+                        .ok_or_else(|| self.error("意外的文件结尾"))?;
+                    self.pos += 1;
+                    match escape {
+                        b'"' => out.push('"'),
+                        b'\\' => out.push('\\'),
+                        b'/' => out.push('/'),
+                        b'b' => out.push('\u{8}'),
+                        b'f' => out.push('\u{c}'),
+                        b'n' => out.push('\n'),
+                        b'r' => out.push('\r'),
+                        b't' => out.push('\t'),
+                        b'u' => {
+                            let mut code = self.hex4()?;
+                            // UTF-16 代理对
+                            if (0xD800..0xDC00).contains(&code)
+                                && self.bytes[self.pos..].starts_with(b"\\u")
+                            {
+                                self.pos += 2;
+                                let low = self.hex4()?;
+                                code = 0x10000
+                                    + ((code - 0xD800) << 10)
+                                    + (low.wrapping_sub(0xDC00) & 0x3FF);
+                            }
+                            out.push(char::from_u32(code).unwrap_or('\u{FFFD}'));
+                        }
+                        _ => return Err(self.error("无效的转义字符")),
+                    }
+                }
+                Some(_) => return Err(self.error("字符串中有未转义的控制字符")),
+                None => return Err(self.error("字符串没有结束")),
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! 只读的文件内存映射
+//!
+//! Unix 上用 `mmap` 映射整个文件，页面在首次访问时才由操作系统读入，内存紧张时可被回收，
+//! 因此可以打开比物理内存更大的文件。其他平台退化为一次性读入内存。
+//!
+//! 映射期间文件不能被其他进程截断：访问超出新文件末尾的页面时内核发送 SIGBUS，进程直接终止，
+//! 无法作为错误返回。原地改写文件内容同样会被读到（MAP_PRIVATE 不会为未写入的页面保留快照），
+//! 违反了 `&[u8]` 内容不变的保证。这些前提无法由本模块检查，因此 [`MappedFile::open`] 是 `unsafe` 的。
+
+use std::io;
+use std::ops::Deref;
+use std::path::Path;
+
+/// 只读映射的文件内容
+pub struct MappedFile {
+    storage: Storage,
+}
+
+enum Storage {
+    #[cfg(unix)]
+    Mapped {
+        ptr: *const u8,
+        len: usize,
+    },
+    Owned(Vec<u8>),
+}
+
+// 映射区域只读且在 Drop 前一直有效，可以在线程间共享
+unsafe impl Send for MappedFile {}
+unsafe impl Sync for MappedFile {}
+
+impl MappedFile {
+    /// 映射整个文件
+    ///
+    /// # Safety
+    ///
+    /// 返回值存在期间，文件不能被本进程或其他进程截断或改写。截断后读取越过新末尾的页面会触发
+    /// SIGBUS，改写会使已借出的切片内容发生变化，两者都是未定义行为。
+    #[cfg(unix)]
+    pub unsafe fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        use std::os::fd::AsRawFd;
+
+        let file = std::fs::File::open(path)?;
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "文件超出地址空间"))?;
+        if len == 0 {
+            return Ok(Self {
+                storage: Storage::Owned(Vec::new()),
+            });
+        }
+        // SAFETY: 以只读、私有方式映射有效的文件描述符；映射在关闭描述符后仍然有效。
+        // 之后读取映射的安全性依赖调用者保证文件不被截断或改写（见本函数的 Safety 一节）。
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self {
+            storage: Storage::Mapped {
+                ptr: ptr as *const u8,
+                len,
+            },
+        })
+    }
+
+    /// 读入整个文件
+    ///
+    /// # Safety
+    ///
+    /// 与 Unix 版本相同：返回值存在期间，文件不能被本进程或其他进程截断或改写。截断后读取越过新末尾的页面会触发
+    /// SIGBUS，改写会使已借出的切片内容发生变化，两者都是未定义行为。
+    #[cfg(not(unix))]
+    pub unsafe fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self {
+            storage: Storage::Owned(std::fs::read(path)?),
+        })
+    }
+
+    /// 是否为真正的内存映射（否则文件已整体读入内存）
+    #[inline]
+    pub fn is_mapped(&self) -> bool {
+        !matches!(self.storage, Storage::Owned(_))
+    }
+}
+
+impl Deref for MappedFile {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        match &self.storage {
+            // SAFETY: ptr 指向长度为 len 的只读映射，生命周期不超过 self；
+            // 文件在映射期间不被截断或改写由 `open` 的调用者保证
+            #[cfg(unix)]
+            Storage::Mapped { ptr, len } => unsafe { std::slice::from_raw_parts(*ptr, *len) },
+            Storage::Owned(bytes) => bytes,
+        }
+    }
+}
+
+impl Drop for MappedFile {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Storage::Mapped { ptr, len } = self.storage {
+            // SAFETY: 解除 open 中建立的映射，之后不再访问
+            unsafe {
+                libc::munmap(ptr as *mut libc::c_void, len);
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for MappedFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MappedFile")
+            .field("len", &self.len())
+            .field("mapped", &self.is_mapped())
+            .finish()
+    }
+}
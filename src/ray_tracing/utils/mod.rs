@@ -1 +1,5 @@
+pub mod config;
+pub mod image_compare;
+pub mod json;
+pub mod mapped_file;
 pub mod random;
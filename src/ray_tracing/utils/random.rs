@@ -1,4 +1,5 @@
 use rand::Rng;
+use std::cell::RefCell;
 use std::f64::consts::PI;
 
 #[inline]
@@ -6,20 +7,90 @@ pub fn degrees_to_radians(degrees: f64) -> f64 {
     degrees * PI / 180.0
 }
 
+/// 可复现的伪随机数生成器（PCG-XSH-RR，32位输出）
+#[derive(Debug, Clone)]
+pub struct Pcg32 {
+    state: u64,
+    inc: u64,
+}
+
+impl Pcg32 {
+    const MULTIPLIER: u64 = 6364136223846793005;
+
+    /// 从种子创建生成器，相同种子在任何平台上产生相同序列
+    pub fn new(seed: u64) -> Self {
+        let mut rng = Self {
+            state: 0,
+            inc: (hash_seed(&[seed, 0x9e37_79b9]) << 1) | 1,
+        };
+        rng.next_u32();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.next_u32();
+        rng
+    }
+
+    #[inline]
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.state = old.wrapping_mul(Self::MULTIPLIER).wrapping_add(self.inc);
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rot = (old >> 59) as u32;
+        xorshifted.rotate_right(rot)
+    }
+
+    /// [0, 1) 区间的均匀分布浮点数（53位精度）
+    #[inline]
+    pub fn next_f64(&mut self) -> f64 {
+        let bits = ((self.next_u32() as u64) << 21) ^ (self.next_u32() as u64 >> 11);
+        bits as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+}
+
+/// 将多个整数混合为一个种子（SplitMix64 终结函数）
+pub fn hash_seed(values: &[u64]) -> u64 {
+    let mut h = 0x243f_6a88_85a3_08d3u64;
+    for &v in values {
+        h ^= v
+            .wrapping_add(0x9e37_79b9_7f4a_7c15)
+            .wrapping_add(h << 6)
+            .wrapping_add(h >> 2);
+        h = (h ^ (h >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        h = (h ^ (h >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        h ^= h >> 31;
+    }
+    h
+}
+
+thread_local! {
+    // 当前线程的可复现随机数流，None 时使用系统线程随机数
+    static STREAM: RefCell<Option<Pcg32>> = const { RefCell::new(None) };
+}
+
+/// 在给定种子的随机数流下执行闭包，期间本线程的所有随机函数都从该流取值
+///
+/// 按 (像素, 样本) 哈希种子即可保证结果与线程数和任务调度顺序无关。
+pub fn with_seeded_stream<R>(seed: u64, f: impl FnOnce() -> R) -> R {
+    let previous = STREAM.with(|s| s.borrow_mut().replace(Pcg32::new(seed)));
+    let result = f();
+    STREAM.with(|s| *s.borrow_mut() = previous);
+    result
+}
+
 #[inline]
 pub fn random_double() -> f64 {
-    let mut rng = rand::rng();
-    rng.random()
+    STREAM.with(|s| match s.borrow_mut().as_mut() {
+        Some(stream) => stream.next_f64(),
+        None => rand::rng().random(),
+    })
 }
 
 #[inline]
 pub fn random_double_range(min: f64, max: f64) -> f64 {
-    let mut rng = rand::rng();
-    rng.random_range(min..max)
+    min + (max - min) * random_double()
 }
 
 #[inline]
 pub fn random_int_range(min: i32, max: i32) -> i32 {
-    let mut rng = rand::rng();
-    rng.random_range(min..=max)
+    let span = (max - min + 1) as f64;
+    (min + (random_double() * span) as i32).min(max)
 }
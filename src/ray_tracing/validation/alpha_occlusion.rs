@@ -0,0 +1,94 @@
+//! 透明度遮罩的遮挡检查：完全镂空的四边形不遮挡可见性测试和环境光遮蔽，半透明的遮罩按覆盖比例遮挡
+//!
+//! 遮挡查询只做几何求交时，树叶、铁丝网等镂空贴图在渲染中看不见，却仍然投下完整的阴影。
+
+use super::check::CheckResult;
+use crate::ray_tracing::geometry::hittable::Hittable;
+use crate::ray_tracing::geometry::hittable_list::HittableList;
+use crate::ray_tracing::geometry::quad::Quad;
+use crate::ray_tracing::materials::alpha_mask::AlphaMask;
+use crate::ray_tracing::materials::lambertian::Lambertian;
+use crate::ray_tracing::materials::texture::SolidColor;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::rendering::integrator::ambient_occlusion;
+use crate::ray_tracing::scene::ray_cast::{RayCast, RayCaster};
+use crate::ray_tracing::utils::random::with_seeded_stream;
+use std::sync::Arc;
+
+/// 可见性测试的次数
+const SAMPLES: usize = 20_000;
+/// 环境光遮蔽的采样数
+const AO_SAMPLES: u32 = 256;
+/// 半透明遮罩可见比例的容差（相对误差）
+const TOLERANCE: f64 = 0.05;
+
+/// y = 0 处的地面
+fn floor() -> Arc<dyn Hittable> {
+    Arc::new(Quad::new(
+        Point3::new(-1.0, 0.0, -1.0),
+        Vec3::new(2.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 2.0),
+        Arc::new(Lambertian::new(Color::repeat(0.5))),
+    ))
+}
+
+/// 地面上方 y = 0.5 处、不透明度为 alpha 的遮罩四边形
+fn cover(alpha: f64) -> Arc<dyn Hittable> {
+    let base = Arc::new(Lambertian::new(Color::repeat(0.5)));
+    let mask = Arc::new(SolidColor::new(Color::repeat(alpha)));
+    Arc::new(Quad::new(
+        Point3::new(-2.0, 0.5, -2.0),
+        Vec3::new(4.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 4.0),
+        Arc::new(AlphaMask::new(base, mask)),
+    ))
+}
+
+/// 穿过不透明度为 alpha 的遮罩时的可见比例和地面中心的环境光遮蔽
+fn measure(alpha: f64) -> (f64, f64) {
+    let mut world = HittableList::new();
+    world.add(floor());
+    world.add(cover(alpha));
+    let world: Arc<dyn Hittable> = Arc::new(world);
+    let caster = RayCaster::new(world.clone());
+
+    let below = Point3::new(0.0, 0.1, 0.0);
+    let above = Point3::new(0.0, 1.0, 0.0);
+    let visible = with_seeded_stream(alpha.to_bits(), || {
+        (0..SAMPLES)
+            .filter(|_| caster.visible(&below, &above))
+            .count()
+    });
+
+    // 只对地面求交得到着色点，遮罩四边形不会挡住这条光线
+    let down = Ray::new(above, Vec3::new(0.0, -1.0, 0.0), 0.0);
+    let rec = floor().trace_nearest(&down).expect("光线应命中地面");
+    let occlusion = with_seeded_stream(alpha.to_bits() ^ 1, || {
+        ambient_occlusion(world.as_ref(), &rec, 0.0, 10.0, AO_SAMPLES, (0, 0), 0)
+    });
+    (visible as f64 / SAMPLES as f64, occlusion)
+}
+
+/// 运行遮罩完全镂空、半透明和不透明三种情况的检查
+pub fn run() -> Vec<CheckResult> {
+    let (clear_visible, clear_ao) = measure(0.0);
+    let (half_visible, _) = measure(0.5);
+    let (opaque_visible, opaque_ao) = measure(1.0);
+    vec![
+        CheckResult {
+            name: "透明度遮挡: 完全镂空".to_string(),
+            passed: clear_visible == 1.0 && clear_ao == 1.0,
+            detail: format!("可见比例 {:.4}, 环境光遮蔽 {:.4}", clear_visible, clear_ao),
+        },
+        CheckResult::compare("透明度遮挡: 半透明可见比例", half_visible, 0.5, TOLERANCE),
+        CheckResult {
+            name: "透明度遮挡: 不透明".to_string(),
+            passed: opaque_visible == 0.0 && opaque_ao < 0.5,
+            detail: format!(
+                "可见比例 {:.4}, 环境光遮蔽 {:.4}",
+                opaque_visible, opaque_ao
+            ),
+        },
+    ]
+}
@@ -0,0 +1,52 @@
+//! 渲染器自检：用蒙特卡洛统计验证采样（方向PDF、快门时间、镜头网格）和变换的正确性，检查四边形接缝的密封性、透明度遮罩的遮挡、紧凑网格的求交精度和场景文件的往返一致性
+
+use super::{
+    alpha_occlusion, furnace, lens_grid, light_pdfs, light_transforms, media, mesh_precision,
+    pdf_chi2, quad_seams, scene_roundtrip, shutter,
+};
+
+/// 单项检查的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckResult {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+impl CheckResult {
+    /// 比较估计值与参考值的相对误差是否在容差内
+    pub fn compare(name: impl Into<String>, value: f64, reference: f64, tolerance: f64) -> Self {
+        let relative = (value - reference).abs() / reference.abs().max(1e-12);
+        Self {
+            name: name.into(),
+            passed: relative.is_finite() && relative <= tolerance,
+            detail: format!(
+                "估计值 {:.6}, 参考值 {:.6}, 相对误差 {:.4} (容差 {:.4})",
+                value, reference, relative, tolerance
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for CheckResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = if self.passed { "通过" } else { "失败" };
+        write!(f, "[{}] {}: {}", status, self.name, self.detail)
+    }
+}
+
+/// 运行全部自检
+pub fn run_all() -> Vec<CheckResult> {
+    let mut results = light_transforms::run();
+    results.extend(light_pdfs::run());
+    results.extend(pdf_chi2::run());
+    results.extend(shutter::run());
+    results.extend(lens_grid::run());
+    results.extend(furnace::run());
+    results.extend(media::run());
+    results.extend(quad_seams::run());
+    results.extend(alpha_occlusion::run());
+    results.extend(mesh_precision::run());
+    results.extend(scene_roundtrip::run());
+    results
+}
@@ -0,0 +1,58 @@
+//! 白炉测试：反照率为1的球体在均匀环境光中的平均亮度应等于环境，有损材质应等于各自的参考值（见 `FurnaceMaterial::reference`）
+
+use super::check::CheckResult;
+use crate::ray_tracing::geometry::sphere::Sphere;
+use crate::ray_tracing::materials::material::Material;
+use crate::ray_tracing::math::vec3::Point3;
+use crate::ray_tracing::rendering::color::luminance;
+use crate::scenes::furnace::{
+    FURNACE_ENVIRONMENT, FurnaceConfig, furnace_camera, furnace_materials,
+};
+use std::sync::Arc;
+
+/// 测试图像宽度
+const IMAGE_WIDTH: i32 = 24;
+/// 每像素采样数
+const SAMPLES_PER_PIXEL: i32 = 64;
+/// 相对误差容差
+const TOLERANCE: f64 = 0.01;
+
+/// 渲染画面完全被单位球填满的图像，返回平均亮度与环境亮度之比
+pub fn furnace_ratio(material: Arc<dyn Material>) -> f64 {
+    let config = FurnaceConfig {
+        image_width: IMAGE_WIDTH,
+        samples_per_pixel: SAMPLES_PER_PIXEL,
+        max_depth: 50,
+        ..FurnaceConfig::default()
+    };
+    // 距球心3处单位球的视半角约19.5°，视场对角线约14°，所有像素都命中球体
+    let camera = furnace_camera(&config)
+        .aspect_ratio(1.0)
+        .vfov(20.0)
+        .lookfrom(Point3::new(0.0, 0.0, 3.0))
+        .lookat(Point3::new(0.0, 0.0, 0.0))
+        .seed(1)
+        .build();
+    let sphere = Sphere::new(Point3::new(0.0, 0.0, 0.0), 1.0, material);
+
+    let fb = camera.render_to_buffer(&sphere, None);
+    let pixels = fb.pixels();
+    let mean = pixels.iter().map(luminance).sum::<f64>() / pixels.len() as f64;
+    mean / FURNACE_ENVIRONMENT
+}
+
+/// 对所有材质运行白炉测试
+pub fn run() -> Vec<CheckResult> {
+    furnace_materials()
+        .into_iter()
+        .map(|entry| {
+            let ratio = furnace_ratio(entry.material);
+            CheckResult::compare(
+                format!("白炉测试: {}", entry.name),
+                ratio,
+                entry.reference,
+                TOLERANCE,
+            )
+        })
+        .collect()
+}
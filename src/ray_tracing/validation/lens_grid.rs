@@ -0,0 +1,51 @@
+//! 镜头分层网格：样本序号经步长置换后应恰好覆盖每个网格单元一次，采样点都落在单位圆盘内（包括每像素样本数很大的情况）
+
+use super::check::CheckResult;
+use crate::ray_tracing::rendering::camera::Camera;
+use crate::ray_tracing::utils::random::with_seeded_stream;
+
+/// 检查的网格边长：小网格、每像素 65536 样本（i32 乘积会溢出的规模）和景深感知采样可能用到的更大网格
+const GRID_SIZES: [i32; 3] = [16, 256, 1024];
+
+/// 检查一个网格边长下的置换和采样点
+fn check_grid(sqrt_n: i32) -> CheckResult {
+    let total = sqrt_n * sqrt_n;
+    let stride = Camera::coprime_stride(total);
+    let mut visited = vec![false; total as usize];
+    let mut duplicates = 0usize;
+    let mut out_of_range = 0usize;
+    for sample_idx in 0..total {
+        let (u, v) = Camera::lens_grid_cell(sample_idx, sqrt_n, stride);
+        if !(0..sqrt_n).contains(&u) || !(0..sqrt_n).contains(&v) {
+            out_of_range += 1;
+            continue;
+        }
+        let cell = &mut visited[(u * sqrt_n + v) as usize];
+        if *cell {
+            duplicates += 1;
+        }
+        *cell = true;
+    }
+
+    // 末尾的样本序号最大，最容易在乘法中溢出
+    let outside_disk = with_seeded_stream(sqrt_n as u64, || {
+        (total.saturating_sub(4096)..total)
+            .map(|i| Camera::sample_lens_grid(i, sqrt_n, stride))
+            .filter(|p| p.norm_squared() > 1.0 + 1e-12)
+            .count()
+    });
+
+    CheckResult {
+        name: format!("镜头网格: {}×{}", sqrt_n, sqrt_n),
+        passed: duplicates == 0 && out_of_range == 0 && outside_disk == 0,
+        detail: format!(
+            "步长 {}, 重复单元 {}, 越界单元 {}, 圆盘外采样点 {}",
+            stride, duplicates, out_of_range, outside_disk
+        ),
+    }
+}
+
+/// 对各网格边长运行检查
+pub fn run() -> Vec<CheckResult> {
+    GRID_SIZES.iter().map(|&n| check_grid(n)).collect()
+}
@@ -0,0 +1,158 @@
+//! 光源采样PDF与解析面积、功率的一致性
+
+use super::check::CheckResult;
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::geometry::polygon::Polygon;
+use crate::ray_tracing::geometry::quad::Quad;
+use crate::ray_tracing::geometry::sphere::Sphere;
+use crate::ray_tracing::geometry::transforms::rotate_y::RotateY;
+use crate::ray_tracing::geometry::triangle::Triangle;
+use crate::ray_tracing::materials::diffuse_light::DiffuseLight;
+use crate::ray_tracing::materials::material::NoMaterial;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::random::with_seeded_stream;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// 每项检查的采样数
+const SAMPLES: usize = 200_000;
+/// 相对误差容差
+const TOLERANCE: f64 = 0.02;
+
+/// 在整个方向球面上均匀积分 pdf，归一化的PDF（光源完全可见时）结果为1
+fn pdf_integral(light: &dyn Hittable, p: &Point3, seed: u64) -> f64 {
+    with_seeded_stream(seed, || {
+        let mut sum = 0.0;
+        for _ in 0..SAMPLES {
+            let direction = Vec3::random_unit_vector();
+            sum += light.pdf_value(p, &direction, 0.0);
+        }
+        sum / SAMPLES as f64 * 4.0 * PI
+    })
+}
+
+/// 用光源采样估计从 p 可见的光源面积：A = E[d²/(|cos|·pdf)]
+fn visible_area(light: &dyn Hittable, p: &Point3, seed: u64) -> f64 {
+    with_seeded_stream(seed, || {
+        let mut sum = 0.0;
+        for _ in 0..SAMPLES {
+            let direction = light.random(p, 0.0);
+            let pdf = light.pdf_value(p, &direction, 0.0);
+            let unit = direction.normalize();
+
+            // 沿采样方向求交点处的法线和距离
+            let mut rec = HitRecord::default();
+            let ray = Ray::new(*p, unit, 0.0);
+            if pdf <= 0.0 || !light.hit(&ray, Interval::new(0.001, f64::INFINITY), &mut rec) {
+                continue;
+            }
+            let cos = unit.dot(&rec.normal).abs();
+            if cos > 1e-8 {
+                sum += rec.t * rec.t / (cos * pdf);
+            }
+        }
+        sum / SAMPLES as f64
+    })
+}
+
+/// 运行所有光源PDF检查
+pub fn run() -> Vec<CheckResult> {
+    let p = Point3::new(0.2, 0.0, 0.1);
+    let mut results = Vec::new();
+
+    // 四边形：PDF 归一化，且重建的面积等于解析面积
+    let quad = Quad::new(
+        Point3::new(-0.5, 2.0, -0.25),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.3, 0.5),
+        Arc::new(NoMaterial),
+    );
+    results.push(CheckResult::compare(
+        "四边形光源PDF归一化",
+        pdf_integral(&quad, &p, 11),
+        1.0,
+        TOLERANCE,
+    ));
+    results.push(CheckResult::compare(
+        "四边形光源面积",
+        visible_area(&quad, &p, 12),
+        quad.area(),
+        TOLERANCE,
+    ));
+
+    // 旋转后的四边形：面积不变
+    let rotated = RotateY::new(Arc::new(quad), 40.0);
+    results.push(CheckResult::compare(
+        "旋转四边形光源面积",
+        visible_area(&rotated, &p, 13),
+        rotated.area(),
+        TOLERANCE,
+    ));
+
+    // 三角形与正六边形：按面积采样，重建的面积等于解析面积
+    let triangle = Triangle::new(
+        Point3::new(-0.5, 2.0, -0.3),
+        Point3::new(0.6, 2.2, -0.2),
+        Point3::new(0.0, 1.9, 0.6),
+        Arc::new(NoMaterial),
+    );
+    results.push(CheckResult::compare(
+        "三角形光源面积",
+        visible_area(&triangle, &p, 16),
+        triangle.area(),
+        TOLERANCE,
+    ));
+    let hexagon = Polygon::regular(
+        Point3::new(0.0, 1.2, 0.0),
+        Vec3::new(0.2, -1.0, 0.1),
+        0.9,
+        6,
+        Arc::new(NoMaterial),
+    );
+    results.push(CheckResult::compare(
+        "六边形光源PDF归一化",
+        pdf_integral(&hexagon, &p, 17),
+        1.0,
+        TOLERANCE,
+    ));
+    results.push(CheckResult::compare(
+        "六边形光源面积",
+        visible_area(&hexagon, &p, 18),
+        1.5 * 3.0_f64.sqrt() * 0.9 * 0.9,
+        TOLERANCE,
+    ));
+
+    // 球体：从距球心 D 处可见的球冠面积为 A·(1 - r/D)/2
+    let (center, radius) = (Point3::new(0.0, 2.5, 0.0), 0.5);
+    let sphere = Sphere::new(center, radius, Arc::new(NoMaterial));
+    let distance = (center - p).norm();
+    results.push(CheckResult::compare(
+        "球形光源PDF归一化",
+        pdf_integral(&sphere, &p, 14),
+        1.0,
+        TOLERANCE,
+    ));
+    results.push(CheckResult::compare(
+        "球形光源可见面积",
+        visible_area(&sphere, &p, 15),
+        sphere.area() * (1.0 - radius / distance) / 2.0,
+        TOLERANCE,
+    ));
+
+    // 功率：均匀发光的球体 Φ = π·A·L
+    let emitter = Sphere::new(
+        center,
+        radius,
+        Arc::new(DiffuseLight::new_color(Color::new(2.0, 2.0, 2.0))),
+    );
+    results.push(CheckResult::compare(
+        "球形光源功率",
+        emitter.power().x,
+        PI * emitter.area() * 2.0,
+        1e-9,
+    ));
+
+    results
+}
@@ -0,0 +1,120 @@
+//! 变换后的光源与等价的未变换光源应给出相同的光源采样结果
+
+use super::check::CheckResult;
+use crate::ray_tracing::geometry::hittable::Hittable;
+use crate::ray_tracing::geometry::quad::Quad;
+use crate::ray_tracing::geometry::scene_graph::Transform;
+use crate::ray_tracing::geometry::sphere::Sphere;
+use crate::ray_tracing::geometry::transforms::rotate_y::RotateY;
+use crate::ray_tracing::geometry::transforms::translate::Translate;
+use crate::ray_tracing::materials::material::NoMaterial;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::random::{degrees_to_radians, with_seeded_stream};
+use std::sync::Arc;
+
+/// 每项检查的采样数
+const SAMPLES: usize = 200_000;
+/// 相对误差容差
+const TOLERANCE: f64 = 0.02;
+
+/// 用光源采样估计点 p（法线 n）处来自单位辐射度光源的辐照度
+///
+/// 采样方向必须落在光源上（pdf > 0），否则说明 random 与 pdf_value 不一致，计为0。
+pub fn light_sampled_irradiance(light: &dyn Hittable, p: &Point3, n: &Vec3, seed: u64) -> f64 {
+    with_seeded_stream(seed, || {
+        let mut sum = 0.0;
+        for _ in 0..SAMPLES {
+            let direction = light.random(p, 0.0);
+            let pdf = light.pdf_value(p, &direction, 0.0);
+            let cos_theta = direction.normalize().dot(n);
+            if pdf > 0.0 && cos_theta > 0.0 {
+                sum += cos_theta / pdf;
+            }
+        }
+        sum / SAMPLES as f64
+    })
+}
+
+/// 绕Y轴旋转向量（与 RotateY 的局部到世界方向一致）
+fn rotate_y(v: &Vec3, angle: f64) -> Vec3 {
+    let (sin_theta, cos_theta) = degrees_to_radians(angle).sin_cos();
+    Vec3::new(
+        cos_theta * v.x + sin_theta * v.z,
+        v.y,
+        -sin_theta * v.x + cos_theta * v.z,
+    )
+}
+
+/// 运行所有变换光源检查
+pub fn run() -> Vec<CheckResult> {
+    let q = Point3::new(-0.5, 2.0, -0.25);
+    let u = Vec3::new(1.0, 0.0, 0.0);
+    let v = Vec3::new(0.0, 0.0, 0.5);
+    let offset = Vec3::new(0.7, 0.3, -0.4);
+    let angle = 35.0;
+    let quad = || -> Arc<dyn Hittable> { Arc::new(Quad::new(q, u, v, Arc::new(NoMaterial))) };
+
+    let p = Point3::new(0.2, 0.0, 0.1);
+    let n = Vec3::new(0.0, 1.0, 0.0);
+    let mut results = Vec::new();
+
+    // 平移
+    let translated = Translate::new(quad(), offset);
+    let reference = Quad::new(q + offset, u, v, Arc::new(NoMaterial));
+    results.push(CheckResult::compare(
+        "平移四边形光源",
+        light_sampled_irradiance(&translated, &p, &n, 1),
+        light_sampled_irradiance(&reference, &p, &n, 2),
+        TOLERANCE,
+    ));
+
+    // 旋转
+    let rotated = RotateY::new(quad(), angle);
+    let reference = Quad::new(
+        Point3::from(rotate_y(&q.coords, angle)),
+        rotate_y(&u, angle),
+        rotate_y(&v, angle),
+        Arc::new(NoMaterial),
+    );
+    results.push(CheckResult::compare(
+        "旋转四边形光源",
+        light_sampled_irradiance(&rotated, &p, &n, 3),
+        light_sampled_irradiance(&reference, &p, &n, 4),
+        TOLERANCE,
+    ));
+
+    // 场景图变换（先旋转再平移）
+    let transform = Transform {
+        rotate_y: angle,
+        translate: offset,
+    };
+    let transformed = transform.apply(quad());
+    let reference = Quad::new(
+        Point3::from(rotate_y(&q.coords, angle) + offset),
+        rotate_y(&u, angle),
+        rotate_y(&v, angle),
+        Arc::new(NoMaterial),
+    );
+    results.push(CheckResult::compare(
+        "场景图变换四边形光源",
+        light_sampled_irradiance(transformed.as_ref(), &p, &n, 5),
+        light_sampled_irradiance(&reference, &p, &n, 6),
+        TOLERANCE,
+    ));
+
+    // 平移球形光源
+    let center = Point3::new(0.0, 2.5, 0.0);
+    let translated = Translate::new(
+        Arc::new(Sphere::new(center, 0.5, Arc::new(NoMaterial))),
+        offset,
+    );
+    let reference = Sphere::new(center + offset, 0.5, Arc::new(NoMaterial));
+    results.push(CheckResult::compare(
+        "平移球形光源",
+        light_sampled_irradiance(&translated, &p, &n, 7),
+        light_sampled_irradiance(&reference, &p, &n, 8),
+        TOLERANCE,
+    ));
+
+    results
+}
@@ -0,0 +1,73 @@
+//! 有界面介质的检查：外部光线总是先命中外壳，内部光线的散射概率符合 Beer-Lambert 定律
+
+use super::check::CheckResult;
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::geometry::sphere::Sphere;
+use crate::ray_tracing::materials::dielectric::Dielectric;
+use crate::ray_tracing::materials::material::{Material, NoMaterial};
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::random::with_seeded_stream;
+use crate::ray_tracing::volumes::medium_shell::MediumShell;
+use std::sync::Arc;
+
+/// 每项检查的光线数
+const SAMPLES: usize = 100_000;
+/// 外壳半径
+const RADIUS: f64 = 2.0;
+/// 介质密度，从球心到外壳的光学厚度为 1
+const DENSITY: f64 = 0.5;
+
+/// 运行全部检查
+pub fn run() -> Vec<CheckResult> {
+    let interface: Arc<dyn Material> = Arc::new(Dielectric::new(1.5));
+    let shell = MediumShell::new_color(
+        Arc::new(Sphere::new(Point3::origin(), RADIUS, Arc::new(NoMaterial))),
+        interface.clone(),
+        DENSITY,
+        Color::new(0.5, 0.5, 0.5),
+    );
+    let is_interface = |rec: &HitRecord| Arc::ptr_eq(&rec.mat, &interface);
+
+    // 从外部射向外壳的光线：交点必须是外壳正面，不能在折射之前进入介质
+    let mut early = 0usize;
+    with_seeded_stream(0x3ed1a, || {
+        for _ in 0..SAMPLES {
+            let origin = Point3::from(Vec3::random_unit_vector() * (RADIUS * 3.0));
+            let target = Point3::from(Vec3::random_unit_vector() * (RADIUS * 0.5));
+            let ray = Ray::new(origin, target - origin, 0.0);
+            let mut rec = HitRecord::default();
+            let hit = shell.hit(&ray, Interval::new(0.001, f64::INFINITY), &mut rec);
+            if !hit || !is_interface(&rec) || !rec.front_face {
+                early += 1;
+            }
+        }
+    });
+
+    // 从球心出发的光线：在到达外壳之前散射的比例为 1 - e^(-σR)
+    let mut scattered = 0usize;
+    with_seeded_stream(0x3ed1b, || {
+        for _ in 0..SAMPLES {
+            let ray = Ray::new(Point3::origin(), Vec3::random_unit_vector(), 0.0);
+            let mut rec = HitRecord::default();
+            if shell.hit(&ray, Interval::new(0.0, f64::INFINITY), &mut rec) && !is_interface(&rec) {
+                scattered += 1;
+            }
+        }
+    });
+
+    vec![
+        CheckResult {
+            name: "有界面介质: 外部光线先命中外壳".to_string(),
+            passed: early == 0,
+            detail: format!("{} 条光线中 {} 条没有先命中外壳正面", SAMPLES, early),
+        },
+        CheckResult::compare(
+            "有界面介质: 内部散射概率",
+            scattered as f64 / SAMPLES as f64,
+            1.0 - (-DENSITY * RADIUS).exp(),
+            0.02,
+        ),
+    ]
+}
@@ -0,0 +1,101 @@
+//! 紧凑网格精度检查：f64 和 f32 的紧凑网格与 TriangleMesh 对同一批光线的求交结果应当一致
+//!
+//! 光线从地形上方射向地形内部的随机点，必然命中；f64 的交点应与 TriangleMesh 相同，
+//! f32 的交点误差应在坐标量级乘以 f32 精度的范围内，且 BVH 包围盒的舍入不能漏掉任何交点。
+
+use super::check::CheckResult;
+use crate::ray_tracing::geometry::compact_mesh::CompactMesh;
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::geometry::mesh::{MeshData, TriangleMesh};
+use crate::ray_tracing::materials::material::{Material, NoMaterial};
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::scalar::Scalar;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::random::{random_double_range, with_seeded_stream};
+use std::sync::Arc;
+
+/// 光线数
+const SAMPLES: usize = 50_000;
+/// 地形每边格数和边长
+const CELLS: usize = 64;
+const SIZE: f64 = 40.0;
+
+fn terrain() -> MeshData {
+    let mut data = MeshData::default();
+    let step = SIZE / CELLS as f64;
+    for j in 0..=CELLS {
+        for i in 0..=CELLS {
+            let (x, z) = (i as f64 * step - SIZE / 2.0, j as f64 * step - SIZE / 2.0);
+            let y = 2.0 * (x * 0.3).sin() * (z * 0.2).cos();
+            data.positions.push(Point3::new(x, y, z));
+        }
+    }
+    let row = CELLS + 1;
+    for j in 0..CELLS {
+        for i in 0..CELLS {
+            let v = j * row + i;
+            data.indices.push([v, v + row, v + 1]);
+            data.indices.push([v + 1, v + row, v + row + 1]);
+        }
+    }
+    data
+}
+
+/// 与参考网格比较，返回 (未命中的光线数, 交点的最大距离)
+fn compare<S: Scalar>(mesh: &CompactMesh<S>, reference: &TriangleMesh, seed: u64) -> (usize, f64) {
+    let (mut missed, mut max_error) = (0, 0.0f64);
+    with_seeded_stream(seed, || {
+        let half = SIZE / 2.0 - 0.5;
+        for _ in 0..SAMPLES {
+            let origin = Point3::new(
+                random_double_range(-half, half),
+                10.0,
+                random_double_range(-half, half),
+            );
+            let target = Point3::new(
+                random_double_range(-half, half),
+                -5.0,
+                random_double_range(-half, half),
+            );
+            let ray = Ray::new(origin, target - origin, 0.0);
+            let interval = Interval::new(1e-6, f64::INFINITY);
+            let (mut expected, mut actual) = (HitRecord::default(), HitRecord::default());
+            if !reference.hit(&ray, interval, &mut expected) {
+                continue;
+            }
+            if !mesh.hit(&ray, interval, &mut actual) || !mesh.hit_any(&ray, interval) {
+                missed += 1;
+                continue;
+            }
+            max_error = max_error.max((actual.p - expected.p).norm());
+        }
+    });
+    (missed, max_error)
+}
+
+/// 运行全部检查
+pub fn run() -> Vec<CheckResult> {
+    let mat: Arc<dyn Material> = Arc::new(NoMaterial);
+    let data = terrain();
+    let reference = TriangleMesh::new(&data, mat.clone());
+
+    let mut results = Vec::new();
+    let mut check = |name: &str, (missed, error): (usize, f64), tolerance: f64| {
+        results.push(CheckResult {
+            name: format!("紧凑网格精度: {}", name),
+            passed: missed == 0 && error <= tolerance,
+            detail: format!(
+                "{} 条光线中 {} 条漏判, 交点最大偏差 {:.3e} (容差 {:.1e})",
+                SAMPLES, missed, error, tolerance
+            ),
+        });
+    };
+    if let Some(mesh) = CompactMesh::<f64>::new(&data, mat.clone()) {
+        check("f64", compare(&mesh, &reference, 0x3211), 1e-9);
+    }
+    if let Some(mesh) = CompactMesh::<f32>::new(&data, mat) {
+        check("f32", compare(&mesh, &reference, 0x3212), 1e-3);
+    }
+    results
+}
@@ -0,0 +1,12 @@
+pub mod alpha_occlusion;
+pub mod check;
+pub mod furnace;
+pub mod lens_grid;
+pub mod light_pdfs;
+pub mod light_transforms;
+pub mod media;
+pub mod mesh_precision;
+pub mod pdf_chi2;
+pub mod quad_seams;
+pub mod scene_roundtrip;
+pub mod shutter;
@@ -0,0 +1,241 @@
+//! PDF 采样一致性的卡方检验
+//!
+//! 把方向球面按 (cosθ, φ) 等立体角划分为网格，统计 `generate` 的样本落入各格的次数，
+//! 与对 `value` 数值积分得到的期望次数比较。期望次数过少的格合并为一格，
+//! 检验统计量的 p 值低于显著性水平时说明采样与密度不一致（会导致渲染结果有偏）。
+
+use super::check::CheckResult;
+use crate::ray_tracing::geometry::hittable::Hittable;
+use crate::ray_tracing::geometry::quad::Quad;
+use crate::ray_tracing::geometry::sphere::Sphere;
+use crate::ray_tracing::materials::material::NoMaterial;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::rendering::background::PhysicalSky;
+use crate::ray_tracing::rendering::color::luminance;
+use crate::ray_tracing::sampling::environment_light::EnvironmentLight;
+use crate::ray_tracing::sampling::light_list::LightList;
+use crate::ray_tracing::sampling::pdf::principled_pdf::LobeWeights;
+use crate::ray_tracing::sampling::pdf::{
+    CosinePDF, FiberPDF, GgxDielectricPDF, HittablePDF, MixturePDF, PDF, PrincipledPDF, SpherePDF,
+};
+use crate::ray_tracing::utils::random::with_seeded_stream;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// cosθ 方向的格数
+const THETA_BINS: usize = 16;
+/// φ 方向的格数
+const PHI_BINS: usize = 32;
+/// 每格数值积分的子格数（每个方向）
+const SUBDIVISIONS: usize = 40;
+/// 每项检验的样本数
+const SAMPLES: usize = 200_000;
+/// 期望次数低于此值的格合并
+const MIN_EXPECTED: f64 = 5.0;
+/// 显著性水平（对全部检验总体而言）
+const SIGNIFICANCE: f64 = 0.01;
+/// 允许落在 value 为0处的样本比例（数值误差）
+const MAX_ZERO_PDF_FRACTION: f64 = 1e-4;
+
+/// 方向所在的格，方向含非有限值时返回None
+fn bin_index(direction: &Vec3) -> Option<usize> {
+    let d = direction.normalize();
+    if !d.iter().all(|x| x.is_finite()) {
+        return None;
+    }
+    let theta = (((d.z + 1.0) * 0.5 * THETA_BINS as f64) as usize).min(THETA_BINS - 1);
+    let phi = ((d.y.atan2(d.x) + PI) / (2.0 * PI) * PHI_BINS as f64) as usize;
+    Some(theta * PHI_BINS + phi.min(PHI_BINS - 1))
+}
+
+/// 对每格数值积分 value 得到期望次数
+fn expected_counts(pdf: &dyn PDF) -> Vec<f64> {
+    let d_cos = 2.0 / (THETA_BINS * SUBDIVISIONS) as f64;
+    let d_phi = 2.0 * PI / (PHI_BINS * SUBDIVISIONS) as f64;
+
+    let mut counts = vec![0.0; THETA_BINS * PHI_BINS];
+    for i in 0..THETA_BINS * SUBDIVISIONS {
+        let cos_theta = -1.0 + (i as f64 + 0.5) * d_cos;
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        for j in 0..PHI_BINS * SUBDIVISIONS {
+            let phi = -PI + (j as f64 + 0.5) * d_phi;
+            let direction = Vec3::new(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta);
+            let bin = (i / SUBDIVISIONS) * PHI_BINS + j / SUBDIVISIONS;
+            counts[bin] += pdf.value(&direction) * d_cos * d_phi * SAMPLES as f64;
+        }
+    }
+    counts
+}
+
+/// 自由度为 dof 的卡方分布的上尾概率（Wilson–Hilferty 正态近似）
+pub(super) fn chi_square_p_value(statistic: f64, dof: usize) -> f64 {
+    let k = dof as f64;
+    let variance = 2.0 / (9.0 * k);
+    let z = ((statistic / k).cbrt() - (1.0 - variance)) / variance.sqrt();
+    0.5 * erfc(z / std::f64::consts::SQRT_2)
+}
+
+/// 互补误差函数（Abramowitz & Stegun 7.1.26，绝对误差 < 1.5e-7）
+fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.3275911 * x.abs());
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let value = poly * (-x * x).exp();
+    if x >= 0.0 { value } else { 2.0 - value }
+}
+
+/// 对单个PDF做卡方检验，alpha 为该项检验的显著性水平
+pub fn chi_square_test(name: &str, pdf: &dyn PDF, seed: u64, alpha: f64) -> CheckResult {
+    let expected = expected_counts(pdf);
+    let integral = expected.iter().sum::<f64>() / SAMPLES as f64;
+
+    let mut observed = vec![0.0; expected.len()];
+    let (mut rejected, mut invalid, mut zero_pdf) = (0usize, 0usize, 0usize);
+    with_seeded_stream(seed, || {
+        for _ in 0..SAMPLES {
+            let direction = pdf.generate();
+            // 零向量是 generate 明确丢弃的样本，计入积分不足1的部分
+            if direction.norm_squared() == 0.0 {
+                rejected += 1;
+                continue;
+            }
+            match bin_index(&direction) {
+                Some(bin) => {
+                    observed[bin] += 1.0;
+                    if pdf.value(&direction) <= 0.0 {
+                        zero_pdf += 1;
+                    }
+                }
+                None => invalid += 1,
+            }
+        }
+    });
+
+    // 合并期望次数过少的格
+    let (mut statistic, mut dof) = (0.0, 0usize);
+    let (mut pooled_expected, mut pooled_observed) = (0.0, 0.0);
+    for (&e, &o) in expected.iter().zip(&observed) {
+        if e < MIN_EXPECTED {
+            pooled_expected += e;
+            pooled_observed += o;
+        } else {
+            statistic += (o - e) * (o - e) / e;
+            dof += 1;
+        }
+    }
+    if pooled_expected >= MIN_EXPECTED {
+        statistic += (pooled_observed - pooled_expected).powi(2) / pooled_expected;
+        dof += 1;
+    }
+    let dof = dof.saturating_sub(1).max(1);
+    let p_value = chi_square_p_value(statistic, dof);
+
+    let zero_fraction = zero_pdf as f64 / SAMPLES as f64;
+    CheckResult {
+        name: format!("PDF卡方检验: {}", name),
+        passed: invalid == 0 && zero_fraction <= MAX_ZERO_PDF_FRACTION && p_value >= alpha,
+        detail: format!(
+            "χ² = {:.1}, 自由度 {}, p = {:.4} (显著性 {:.4}), ∫pdf = {:.4}, 丢弃样本 {}, 无效样本 {}, pdf为0的样本 {}",
+            statistic, dof, p_value, alpha, integral, rejected, invalid, zero_pdf
+        ),
+    }
+}
+
+/// 运行所有PDF卡方检验
+pub fn run() -> Vec<CheckResult> {
+    let tilted = Vec3::new(0.3, -0.5, 0.8).normalize();
+    let origin = Point3::new(0.2, 0.0, 0.1);
+    let quad: Arc<dyn Hittable> = Arc::new(Quad::new(
+        Point3::new(-0.5, -0.25, 1.0),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.5, 0.3),
+        Arc::new(NoMaterial),
+    ));
+    let sphere: Arc<dyn Hittable> = Arc::new(Sphere::new(
+        Point3::new(1.0, 0.5, 0.5),
+        0.6,
+        Arc::new(NoMaterial),
+    ));
+    let wo = Vec3::new(-0.4, 0.2, 0.6).normalize();
+    let sky: Arc<dyn Hittable> = Arc::new(EnvironmentLight::with_resolution(
+        &PhysicalSky::new(Vec3::new(0.4, 0.6, -0.3), 1.0, 3.0),
+        2.0,
+        64,
+    ));
+    // 环境光与面光源按功率选择
+    let mut unified = LightList::new();
+    unified.add_by_power(sky.clone());
+    unified.add(quad.clone(), luminance(&sky.power()));
+
+    let pdfs: Vec<(&str, Arc<dyn PDF + '_>)> = vec![
+        ("SpherePDF", Arc::new(SpherePDF)),
+        ("CosinePDF", Arc::new(CosinePDF::new(&tilted))),
+        ("FiberPDF", Arc::new(FiberPDF::new(&tilted))),
+        (
+            "HittablePDF(四边形)",
+            Arc::new(HittablePDF::new(quad.as_ref(), &origin, 0.0)),
+        ),
+        (
+            "HittablePDF(球体)",
+            Arc::new(HittablePDF::new(sphere.as_ref(), &origin, 0.0)),
+        ),
+        (
+            "HittablePDF(环境光)",
+            Arc::new(HittablePDF::new(sky.as_ref(), &origin, 0.0)),
+        ),
+        (
+            "HittablePDF(环境光 + 四边形)",
+            Arc::new(HittablePDF::new(&unified, &origin, 0.0)),
+        ),
+        (
+            "MixturePDF(余弦 + 四边形)",
+            Arc::new(MixturePDF::new_weighted(
+                Arc::new(CosinePDF::new(&Vec3::new(0.0, 0.0, 1.0))),
+                Arc::new(HittablePDF::new(quad.as_ref(), &origin, 0.0)),
+                0.3,
+            )),
+        ),
+        (
+            "GgxDielectricPDF(进入)",
+            Arc::new(GgxDielectricPDF::new(
+                &wo,
+                &Vec3::new(0.0, 0.0, 1.0),
+                0.4,
+                1.5,
+            )),
+        ),
+        (
+            "GgxDielectricPDF(离开)",
+            Arc::new(GgxDielectricPDF::new(
+                &wo,
+                &Vec3::new(0.0, 0.0, 1.0),
+                0.4,
+                1.0 / 1.5,
+            )),
+        ),
+        (
+            "PrincipledPDF(四个波瓣)",
+            Arc::new(PrincipledPDF::new(
+                &wo,
+                &Vec3::new(0.0, 0.0, 1.0),
+                0.3,
+                0.05,
+                1.5,
+                LobeWeights {
+                    diffuse: 0.4,
+                    specular: 0.2,
+                    clearcoat: 0.1,
+                    transmission: 0.3,
+                },
+            )),
+        ),
+    ];
+
+    // Bonferroni 校正：整组检验的误报率不超过 SIGNIFICANCE
+    let alpha = SIGNIFICANCE / pdfs.len() as f64;
+    pdfs.iter()
+        .enumerate()
+        .map(|(i, (name, pdf))| chi_square_test(name, pdf.as_ref(), 100 + i as u64, alpha))
+        .collect()
+}
@@ -0,0 +1,124 @@
+//! 四边形接缝检查：从封闭盒子内部射向棱边的光线都必须命中盒子的某个面
+//!
+//! 相邻的两个面在共享的棱边上各自判断交点是否在内部，舍入误差使两者同时拒绝时光线从缝隙漏出，
+//! 渲染中表现为墙角处的亮点或暗点（康奈尔盒子的墙角最明显）。
+
+use super::check::CheckResult;
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::geometry::hittable_list::HittableList;
+use crate::ray_tracing::geometry::quad::{Quad, box_new};
+use crate::ray_tracing::materials::material::{Material, NoMaterial};
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::onb::ONB;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::random::{random_double, random_double_range, with_seeded_stream};
+use std::sync::Arc;
+
+/// 每个盒子的光线数
+const SAMPLES: usize = 200_000;
+
+/// 以 corner 为顶点、三条棱为 a、b、c 的平行六面体（六个四边形面）
+fn parallelepiped(
+    corner: Point3,
+    a: Vec3,
+    b: Vec3,
+    c: Vec3,
+    mat: Arc<dyn Material>,
+) -> HittableList {
+    let mut sides = HittableList::new();
+    let far = corner + a + b + c;
+    for (q, u, v) in [
+        (corner, a, b),
+        (corner, b, c),
+        (corner, c, a),
+        (far, -a, -b),
+        (far, -b, -c),
+        (far, -c, -a),
+    ] {
+        sides.add(Arc::new(Quad::new(q, u, v, mat.clone())));
+    }
+    sides
+}
+
+/// 从盒子内部随机点射向随机棱边上的点，返回漏出的光线数
+fn leaks(sides: &HittableList, corner: Point3, edges: [Vec3; 3], seed: u64) -> usize {
+    let mut leaked = 0;
+    with_seeded_stream(seed, || {
+        for _ in 0..SAMPLES {
+            let inside = corner
+                + edges
+                    .iter()
+                    .map(|e| random_double_range(0.2, 0.8) * e)
+                    .sum::<Vec3>();
+            // 棱边：沿一条棱方向任意位置，另外两条棱方向各取 0 或 1
+            let axis = (random_double() * 3.0) as usize % 3;
+            let mut target = corner + random_double() * edges[axis];
+            for (k, edge) in edges.iter().enumerate() {
+                if k != axis && random_double() < 0.5 {
+                    target += edge;
+                }
+            }
+            let ray = Ray::new(inside, target - inside, 0.0);
+            let mut rec = HitRecord::default();
+            if !sides.hit(&ray, Interval::new(1e-9, f64::INFINITY), &mut rec) {
+                leaked += 1;
+            }
+        }
+    });
+    leaked
+}
+
+/// 运行全部检查
+pub fn run() -> Vec<CheckResult> {
+    let mat: Arc<dyn Material> = Arc::new(NoMaterial);
+
+    // 康奈尔盒子尺度的轴对齐盒子
+    let cornell = box_new(
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(555.0, 555.0, 555.0),
+        mat.clone(),
+    );
+    let cornell_edges = [
+        Vec3::new(555.0, 0.0, 0.0),
+        Vec3::new(0.0, 555.0, 0.0),
+        Vec3::new(0.0, 0.0, 555.0),
+    ];
+
+    // 远离原点、任意朝向的斜盒子
+    let frame = ONB::new(&Vec3::new(0.3, 0.7, -0.4).normalize());
+    let skew_corner = Point3::new(1234.5, -678.25, 4321.0);
+    let skew_edges = [
+        frame.u() * 37.0,
+        frame.v() * 12.5 + frame.u() * 3.0,
+        frame.w() * 80.0,
+    ];
+    let skewed = parallelepiped(
+        skew_corner,
+        skew_edges[0],
+        skew_edges[1],
+        skew_edges[2],
+        mat,
+    );
+
+    [
+        (
+            "康奈尔盒子",
+            &cornell,
+            Point3::origin(),
+            cornell_edges,
+            0x5ea1,
+        ),
+        ("远离原点的斜盒子", &skewed, skew_corner, skew_edges, 0x5ea2),
+    ]
+    .into_iter()
+    .map(|(name, sides, corner, edges, seed)| {
+        let leaked = leaks(sides, corner, edges, seed);
+        CheckResult {
+            name: format!("四边形接缝: {}", name),
+            passed: leaked == 0,
+            detail: format!("{} 条射向棱边的光线中 {} 条从缝隙漏出", SAMPLES, leaked),
+        }
+    })
+    .collect()
+}
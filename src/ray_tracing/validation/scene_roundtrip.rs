@@ -0,0 +1,202 @@
+//! 场景文件往返：构建 → 写出 → 读回 → 渲染，读回的场景应渲染出逐位相同的图像
+//!
+//! 测试场景覆盖场景文件能表示的全部纹理（嵌套的棋盘格、带种子的 Perlin/单形/Worley 噪声、
+//! 三平面映射的图像纹理）和共享材质，不使用变换，避免展开到世界坐标时的舍入误差。
+
+use super::check::CheckResult;
+use crate::ray_tracing::geometry::hittable::Hittable;
+use crate::ray_tracing::geometry::quad::Quad;
+use crate::ray_tracing::geometry::sphere::Sphere;
+use crate::ray_tracing::materials::dielectric::Dielectric;
+use crate::ray_tracing::materials::diffuse_light::DiffuseLight;
+use crate::ray_tracing::materials::lambertian::Lambertian;
+use crate::ray_tracing::materials::material::Material;
+use crate::ray_tracing::materials::metal::Metal;
+use crate::ray_tracing::materials::texture::checker::CheckerTexture;
+use crate::ray_tracing::materials::texture::image::ImageTexture;
+use crate::ray_tracing::materials::texture::noise::NoiseTexture;
+use crate::ray_tracing::materials::texture::simplex::SimplexTexture;
+use crate::ray_tracing::materials::texture::triplanar::TriplanarTexture;
+use crate::ray_tracing::materials::texture::worley::WorleyTexture;
+use crate::ray_tracing::materials::texture::{SolidColor, TexturePtr};
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::procedural::worley::{DistanceMetric, Worley, WorleyFeature};
+use crate::ray_tracing::rendering::camera::Camera;
+use crate::ray_tracing::rendering::color_space::TextureColorSpace;
+use crate::ray_tracing::rendering::framebuffer::FrameBuffer;
+use crate::ray_tracing::rendering::report::fnv1a64;
+use crate::ray_tracing::scene::scene_file::{CameraView, SceneFile, serialize_scene};
+use crate::ray_tracing::scene::world::Scene;
+use std::path::Path;
+use std::sync::Arc;
+
+/// 写出 4×4 的 sRGB 测试图像，供图像纹理引用
+fn write_test_image(path: &Path) -> image::ImageResult<()> {
+    let img = image::RgbImage::from_fn(4, 4, |x, y| {
+        image::Rgb([
+            (x * 60 + 20) as u8,
+            (y * 60 + 20) as u8,
+            ((x ^ y) * 50) as u8,
+        ])
+    });
+    img.save(path)
+}
+
+/// 构建测试场景，image 为图像纹理的文件名
+fn build_scene(image: &str) -> Scene {
+    let simplex: TexturePtr = Arc::new(
+        SimplexTexture::new(7, 3.0, Color::new(0.2, 0.3, 0.1), Color::new(0.6, 0.5, 0.4))
+            .with_octaves(3),
+    );
+    let checker: TexturePtr = Arc::new(CheckerTexture::new(
+        0.7,
+        simplex.clone(),
+        Arc::new(SolidColor::new(Color::new(0.9, 0.9, 0.85))),
+    ));
+    let marble: TexturePtr = Arc::new(NoiseTexture::with_seed(42, 4.0).with_speed(0.1));
+    let cells: TexturePtr = Arc::new(
+        WorleyTexture::new(
+            Worley::new(11)
+                .with_jitter(0.8)
+                .with_metric(DistanceMetric::Manhattan),
+            WorleyFeature::F2MinusF1,
+            5.0,
+            Color::new(0.8, 0.6, 0.2),
+            Color::new(0.1, 0.1, 0.1),
+        )
+        .with_gain(2.5)
+        .inverted()
+        .with_exponent(1.5),
+    );
+    let photo: TexturePtr = Arc::new(TriplanarTexture::new(
+        Arc::new(ImageTexture::with_color_space(
+            image,
+            TextureColorSpace::Srgb,
+        )),
+        1.3,
+        4.0,
+    ));
+
+    let ground: Arc<dyn Material> = Arc::new(Lambertian::new_texture(checker));
+    let shared: Arc<dyn Material> = Arc::new(Lambertian::new_texture(marble));
+    let brushed: Arc<dyn Material> = Arc::new(Metal::new_texture(cells, simplex));
+    let light: Arc<dyn Material> = Arc::new(DiffuseLight::new_color(Color::new(6.0, 5.5, 5.0)));
+
+    let mut scene = Scene::new();
+    scene.add(Arc::new(Quad::new(
+        Point3::new(-4.0, 0.0, -4.0),
+        Vec3::new(8.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 8.0),
+        ground,
+    )));
+    let spheres: [(Point3, f64, Arc<dyn Material>); 5] = [
+        (Point3::new(-1.6, 0.6, 0.0), 0.6, shared.clone()),
+        (Point3::new(1.6, 0.4, 0.8), 0.4, shared),
+        (Point3::new(0.0, 0.8, -0.6), 0.8, brushed),
+        (
+            Point3::new(0.6, 0.35, 1.2),
+            0.35,
+            Arc::new(Lambertian::new_texture(photo)),
+        ),
+        (
+            Point3::new(-0.5, 0.3, 1.4),
+            0.3,
+            Arc::new(Dielectric::new(1.5).with_absorption(Color::new(0.2, 0.9, 1.4))),
+        ),
+    ];
+    for (center, radius, material) in spheres {
+        scene.add(Arc::new(Sphere::new(center, radius, material)));
+    }
+    let lamp: Arc<dyn Hittable> = Arc::new(Quad::new(
+        Point3::new(-1.0, 3.0, -1.0),
+        Vec3::new(2.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 2.0),
+        light,
+    ));
+    scene.add_emitter(lamp);
+    scene
+}
+
+fn render(scene: &Scene, camera: &Camera) -> FrameBuffer {
+    camera.render_to_buffer(scene.build_world().as_ref(), scene.light_sampler())
+}
+
+/// 像素值的哈希（按 f64 的位模式，逐位相同才相等）
+fn image_hash(fb: &FrameBuffer) -> u64 {
+    let bytes: Vec<u8> = fb
+        .pixels()
+        .iter()
+        .flat_map(|c| [c.x, c.y, c.z])
+        .flat_map(f64::to_le_bytes)
+        .collect();
+    fnv1a64(&bytes)
+}
+
+/// 运行往返检查
+pub fn run() -> Vec<CheckResult> {
+    let dir = std::env::temp_dir().join(format!("scene_roundtrip_{}", std::process::id()));
+    let image = dir.join("checker.png");
+    let prepared = std::fs::create_dir_all(&dir)
+        .map_err(|e| e.to_string())
+        .and_then(|_| write_test_image(&image).map_err(|e| e.to_string()));
+    if let Err(e) = prepared {
+        return vec![CheckResult {
+            name: "场景文件往返".to_string(),
+            passed: false,
+            detail: format!("无法写出测试图像: {}", e),
+        }];
+    }
+
+    let camera = Camera::builder()
+        .image_width(48)
+        .aspect_ratio(4.0 / 3.0)
+        .samples_per_pixel(16)
+        .max_depth(8)
+        .background_color(Color::new(0.1, 0.12, 0.15))
+        .vfov(35.0)
+        .lookfrom(Point3::new(0.0, 2.5, 7.0))
+        .lookat(Point3::new(0.0, 0.5, 0.0))
+        .seed(5)
+        .build();
+
+    let scene = build_scene(&image.to_string_lossy());
+    let export = serialize_scene(&scene, Some(&CameraView::of(&camera)));
+    let loaded = SceneFile::parse(&export.text, "往返场景");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let loaded = match loaded {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            return vec![CheckResult {
+                name: "场景文件往返".to_string(),
+                passed: false,
+                detail: format!("无法读回写出的场景: {}", e),
+            }];
+        }
+    };
+
+    let reexport = serialize_scene(&loaded.scene, Some(&loaded.view));
+    let original = image_hash(&render(&scene, &camera));
+    let reloaded = image_hash(&render(&loaded.scene, &camera));
+
+    vec![
+        CheckResult {
+            name: "场景文件往返: 写出".to_string(),
+            passed: export.skipped.is_empty() && reexport.text == export.text,
+            detail: format!(
+                "跳过 {} 项，再次写出{}",
+                export.skipped.len(),
+                if reexport.text == export.text {
+                    "的文本相同"
+                } else {
+                    "的文本不同"
+                }
+            ),
+        },
+        CheckResult {
+            name: "场景文件往返: 渲染".to_string(),
+            passed: original == reloaded,
+            detail: format!("原场景 {:016x}, 读回 {:016x}", original, reloaded),
+        },
+    ]
+}
@@ -0,0 +1,93 @@
+//! 快门曲线采样的卡方检验：`Shutter::sample` 给出的时间分布应与效率曲线按面积归一化后的密度一致
+
+use super::check::CheckResult;
+use super::pdf_chi2::chi_square_p_value;
+use crate::ray_tracing::rendering::shutter::{Shutter, ShutterCurve};
+use crate::ray_tracing::utils::random::{random_double, with_seeded_stream};
+
+/// 快门区间的格数
+const BINS: usize = 64;
+/// 每格数值积分的子格数
+const SUBDIVISIONS: usize = 64;
+/// 每项检验的样本数
+const SAMPLES: usize = 200_000;
+/// 期望次数低于此值的格合并
+const MIN_EXPECTED: f64 = 5.0;
+/// 显著性水平（对全部检验总体而言）
+const SIGNIFICANCE: f64 = 0.01;
+
+/// 对单条快门曲线做卡方检验
+fn chi_square_test(shutter: &Shutter, seed: u64, alpha: f64) -> CheckResult {
+    let dt = 1.0 / (BINS * SUBDIVISIONS) as f64;
+    let mut expected = vec![0.0; BINS];
+    for i in 0..BINS * SUBDIVISIONS {
+        expected[i / SUBDIVISIONS] += shutter.efficiency((i as f64 + 0.5) * dt) * dt;
+    }
+    let area: f64 = expected.iter().sum();
+    expected
+        .iter_mut()
+        .for_each(|e| *e *= SAMPLES as f64 / area);
+
+    let mut observed = vec![0.0; BINS];
+    let mut outside = 0usize;
+    with_seeded_stream(seed, || {
+        for _ in 0..SAMPLES {
+            let t = shutter.sample(random_double());
+            if (0.0..=1.0).contains(&t) {
+                observed[((t * BINS as f64) as usize).min(BINS - 1)] += 1.0;
+            } else {
+                outside += 1;
+            }
+        }
+    });
+
+    let (mut statistic, mut dof) = (0.0, 0usize);
+    let (mut pooled_expected, mut pooled_observed) = (0.0, 0.0);
+    for (&e, &o) in expected.iter().zip(&observed) {
+        if e < MIN_EXPECTED {
+            pooled_expected += e;
+            pooled_observed += o;
+        } else {
+            statistic += (o - e) * (o - e) / e;
+            dof += 1;
+        }
+    }
+    if pooled_expected >= MIN_EXPECTED {
+        statistic += (pooled_observed - pooled_expected).powi(2) / pooled_expected;
+        dof += 1;
+    } else {
+        // 期望几乎为0的格（曲线为0处）不应有样本
+        statistic += pooled_observed;
+    }
+    let dof = dof.saturating_sub(1).max(1);
+    let p_value = chi_square_p_value(statistic, dof);
+
+    CheckResult {
+        name: format!("快门曲线卡方检验: {}", shutter),
+        passed: outside == 0 && p_value >= alpha,
+        detail: format!(
+            "χ² = {:.1}, 自由度 {}, p = {:.4} (显著性 {:.4}), 区间外样本 {}",
+            statistic, dof, p_value, alpha, outside
+        ),
+    }
+}
+
+/// 运行全部快门曲线检验
+pub fn run() -> Vec<CheckResult> {
+    let mut shutters = vec![
+        Shutter::trapezoid(0.25),
+        Shutter::trapezoid(0.5),
+        Shutter::Sinusoidal,
+    ];
+    // 含平台、零值段和不对称斜坡的自定义曲线
+    if let Ok(curve) = ShutterCurve::new(&[0.0, 1.0, 1.0, 0.0, 0.0, 0.5, 2.0, 0.2]) {
+        shutters.push(Shutter::Custom(curve));
+    }
+
+    let alpha = SIGNIFICANCE / shutters.len() as f64;
+    shutters
+        .iter()
+        .enumerate()
+        .map(|(i, shutter)| chi_square_test(shutter, 0x5407 + i as u64, alpha))
+        .collect()
+}
@@ -1,7 +1,8 @@
 use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
 use crate::ray_tracing::materials::isotropic::Isotropic;
 use crate::ray_tracing::materials::material::Material;
-use crate::ray_tracing::materials::texture::TexturePtr;
+use crate::ray_tracing::materials::scaled::Weighted;
+use crate::ray_tracing::materials::texture::{SolidColor, TexturePtr};
 use crate::ray_tracing::math::aabb::Aabb;
 use crate::ray_tracing::math::interval::Interval;
 use crate::ray_tracing::math::ray::Ray;
@@ -13,29 +14,128 @@ use std::sync::Arc;
 pub struct ConstantMedium {
     boundary: Arc<dyn Hittable>,
     phase_function: Arc<dyn Material>,
+    weighted_phase: Arc<dyn Material>, // 引导距离采样时使用，按命中记录中的权重缩放相函数
     neg_inv_density: f64,
+    light: Option<Arc<dyn Hittable>>, // 等角采样的目标光源
 }
 
 impl ConstantMedium {
     /// 从纹理创建常密度介质
     #[inline]
     pub fn new(boundary: Arc<dyn Hittable>, density: f64, texture: TexturePtr) -> Self {
-        Self {
-            boundary,
-            neg_inv_density: -1.0 / density,
-            phase_function: Arc::new(Isotropic::new(texture)),
-        }
+        Self::with_phase_function(boundary, density, Arc::new(Isotropic::new(texture)))
     }
 
     /// 从颜色创建常密度介质
     #[inline]
     pub fn new_color(boundary: Arc<dyn Hittable>, density: f64, color: Color) -> Self {
+        Self::with_phase_function(boundary, density, Arc::new(Isotropic::new_color(color)))
+    }
+
+    /// 创建发光介质（发光雾、火焰等）
+    ///
+    /// 发射辐射度在采样到的散射点处累加，期望贡献随光学厚度增加，即 L_e·(1 - e^(-σd))。
+    #[inline]
+    pub fn new_emissive(
+        boundary: Arc<dyn Hittable>,
+        density: f64,
+        albedo: TexturePtr,
+        emit: TexturePtr,
+    ) -> Self {
+        Self::with_phase_function(
+            boundary,
+            density,
+            Arc::new(Isotropic::new_emissive(albedo, emit)),
+        )
+    }
+
+    fn with_phase_function(
+        boundary: Arc<dyn Hittable>,
+        density: f64,
+        phase_function: Arc<dyn Material>,
+    ) -> Self {
         Self {
             boundary,
+            weighted_phase: Arc::new(Weighted::new(phase_function.clone())),
+            phase_function,
             neg_inv_density: -1.0 / density,
-            phase_function: Arc::new(Isotropic::new_color(color)),
+            light: None,
         }
     }
+
+    /// 启用朝向光源的等角采样（体积光/丁达尔效应）
+    ///
+    /// 与距离采样各以一半概率组合（单样本MIS），显著降低光源位于介质内部或附近时的方差。
+    #[inline]
+    pub fn with_equiangular_light(mut self, light: Arc<dyn Hittable>) -> Self {
+        self.light = Some(light);
+        self
+    }
+
+    /// 在光源包围盒内均匀选取一个点作为等角采样的锚点
+    fn sample_light_point(light: &dyn Hittable) -> Option<Point3> {
+        let bbox = light.bounding_box()?;
+        Some(Point3::new(
+            bbox.x.min + random_double() * bbox.x.size(),
+            bbox.y.min + random_double() * bbox.y.size(),
+            bbox.z.min + random_double() * bbox.z.size(),
+        ))
+    }
+
+    /// 在已确定发生散射的区间 [0, d) 内采样散射距离，返回 (距离, 估计权重)
+    fn sample_guided_distance(
+        &self,
+        light: &dyn Hittable,
+        origin: &Point3,
+        dir: &Vec3,
+        d: f64,
+    ) -> (f64, f64) {
+        let sigma = -1.0 / self.neg_inv_density;
+        let transmittance = (-sigma * d).exp();
+
+        // 截断指数分布的PDF（已归一化到 [0, d)）
+        let exp_pdf = |s: f64| sigma * (-sigma * s).exp() / (1.0 - transmittance);
+
+        let Some(anchor) = Self::sample_light_point(light) else {
+            let s = -(1.0 - random_double() * (1.0 - transmittance)).ln() / sigma;
+            return (s.min(d), 1.0);
+        };
+
+        // 等角采样参数：锚点在光线上的投影距离 delta 与垂直距离 h
+        let delta = (anchor - origin).dot(dir);
+        let h = ((anchor - origin) - delta * dir).norm().max(1e-6);
+        let theta_a = (-delta / h).atan();
+        let theta_b = ((d - delta) / h).atan();
+        let equiangular_pdf =
+            |s: f64| h / ((theta_b - theta_a) * (h * h + (s - delta) * (s - delta)));
+
+        let s = if random_double() < 0.5 {
+            let theta = theta_a + random_double() * (theta_b - theta_a);
+            (delta + h * theta.tan()).clamp(0.0, d)
+        } else {
+            (-(1.0 - random_double() * (1.0 - transmittance)).ln() / sigma).min(d)
+        };
+
+        let p_exp = exp_pdf(s);
+        let p_mix = 0.5 * p_exp + 0.5 * equiangular_pdf(s);
+        (s, p_exp / p_mix)
+    }
+
+    /// 从颜色创建发光介质
+    #[inline]
+    pub fn new_emissive_color(
+        boundary: Arc<dyn Hittable>,
+        density: f64,
+        albedo: Color,
+        emit: Color,
+    ) -> Self {
+        Self::new_emissive(
+            boundary,
+            density,
+            Arc::new(SolidColor::new(albedo)),
+            Arc::new(SolidColor::new(emit)),
+        )
+    }
 }
 
 impl Hittable for ConstantMedium {
@@ -71,12 +171,25 @@ impl Hittable for ConstantMedium {
         let distance_inside_boundary = (rec2.t - rec1.t) * ray_length;
 
         // 根据介质密度随机确定散射点
-        let hit_distance = self.neg_inv_density * random_double().ln();
+        let mut hit_distance = self.neg_inv_density * random_double().ln();
 
         if hit_distance > distance_inside_boundary {
             return false;
         }
 
+        // 已确定发生散射时，改用距离采样与等角采样的混合重新选择散射位置
+        let mut weight = 1.0;
+        if let Some(light) = &self.light {
+            let origin = r.at(rec1.t);
+            let dir = r.dir / ray_length;
+            (hit_distance, weight) = self.sample_guided_distance(
+                light.as_ref(),
+                &origin,
+                &dir,
+                distance_inside_boundary,
+            );
+        }
+
         // 设置散射点信息
         rec.t = rec1.t + hit_distance / ray_length;
         rec.p = r.at(rec.t);
@@ -84,7 +197,14 @@ impl Hittable for ConstantMedium {
         // 设置法线（对体积散射来说法线是任意的）
         rec.normal = Vec3::new(1.0, 0.0, 0.0);
         rec.front_face = true;
-        rec.mat = self.phase_function.clone();
+        rec.dpdu = Vec3::zeros();
+        rec.dpdv = Vec3::zeros();
+        rec.weight = weight;
+        rec.mat = if weight == 1.0 {
+            self.phase_function.clone()
+        } else {
+            self.weighted_phase.clone()
+        };
 
         true
     }
@@ -101,6 +221,7 @@ impl std::fmt::Debug for ConstantMedium {
             .field("boundary", &"<Hittable>")
             .field("phase_function", &"<Material>")
             .field("neg_inv_density", &self.neg_inv_density)
+            .field("light", &self.light.as_ref().map(|_| "<Hittable>"))
             .finish()
     }
 }
@@ -0,0 +1,128 @@
+//! 有界面的介质：折射外壳包围的散射介质（玻璃球中的烟雾、杯中的有色液体）
+//!
+//! 把外壳与介质分别作为两个物体加入场景时（`ConstantMedium` 与同一个边界），结果依赖两者在
+//! 同一位置的交点由哪一个先返回、以及介质用无限区间重新求边界交点的方式，属于巧合的行为。
+//! [`MediumShell`] 把两者作为一个物体，按明确的规则处理：
+//!
+//! - 光线沿途下一次穿过边界时若为正面，起点在外壳之外：返回外壳的交点（使用界面材质），
+//!   外部的光线不会在折射之前就进入介质。
+//! - 下一次穿过边界为背面时，起点在外壳之内：介质占据从起点到该交点的区间，按密度采样散射距离，
+//!   在区间内散射时返回介质事件，否则返回外壳内侧的交点，由界面材质决定折射出去还是反射回来。
+//!
+//! 判断内外只看光线前方的第一个交点，不需要在光线上记录经过的界面，
+//! 因此边界必须是封闭的、法线朝外的形状（球体、`box_new` 等）。与场景中其他介质（如大范围的环境雾）
+//! 重叠时，各介质独立采样，重叠区域的密度相加。
+
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::materials::isotropic::Isotropic;
+use crate::ray_tracing::materials::material::Material;
+use crate::ray_tracing::materials::texture::TexturePtr;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::ray::Ray;
+use crate::ray_tracing::math::vec3::*;
+use crate::ray_tracing::utils::random::random_double;
+use std::sync::Arc;
+
+/// 折射外壳包围的常密度介质
+pub struct MediumShell {
+    boundary: Arc<dyn Hittable>,
+    interface: Arc<dyn Material>,
+    phase_function: Arc<dyn Material>,
+    density: f64,
+}
+
+impl MediumShell {
+    /// 从纹理创建：interface 为外壳的材质（通常是电介质），内部介质的反照率由纹理给出
+    #[inline]
+    pub fn new(
+        boundary: Arc<dyn Hittable>,
+        interface: Arc<dyn Material>,
+        density: f64,
+        albedo: TexturePtr,
+    ) -> Self {
+        Self {
+            boundary,
+            interface,
+            phase_function: Arc::new(Isotropic::new(albedo)),
+            density: density.max(0.0),
+        }
+    }
+
+    /// 从颜色创建
+    #[inline]
+    pub fn new_color(
+        boundary: Arc<dyn Hittable>,
+        interface: Arc<dyn Material>,
+        density: f64,
+        albedo: Color,
+    ) -> Self {
+        Self {
+            boundary,
+            interface,
+            phase_function: Arc::new(Isotropic::new_color(albedo)),
+            density: density.max(0.0),
+        }
+    }
+
+    /// 介质密度
+    #[inline]
+    pub fn density(&self) -> f64 {
+        self.density
+    }
+
+    /// 外壳的材质
+    #[inline]
+    pub fn interface(&self) -> &Arc<dyn Material> {
+        &self.interface
+    }
+}
+
+impl Hittable for MediumShell {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let mut crossing = HitRecord::default();
+        if !self.boundary.hit(r, ray_t, &mut crossing) {
+            return false;
+        }
+
+        if !crossing.front_face && self.density > 0.0 {
+            // 起点在外壳内：先在到达外壳之前的区间内采样散射
+            let length = r.dir.norm();
+            let start = ray_t.min.max(0.0);
+            let inside = (crossing.t - start) * length;
+            let distance = -random_double().ln() / self.density;
+            if distance < inside {
+                rec.t = start + distance / length;
+                rec.p = r.at(rec.t);
+                // 体积散射的法线是任意的
+                rec.normal = Vec3::new(1.0, 0.0, 0.0);
+                rec.front_face = true;
+                rec.u = 0.0;
+                rec.v = 0.0;
+                rec.dpdu = Vec3::zeros();
+                rec.dpdv = Vec3::zeros();
+                rec.mat = self.phase_function.clone();
+                return true;
+            }
+        }
+
+        *rec = crossing;
+        rec.mat = self.interface.clone();
+        true
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.boundary.bounding_box()
+    }
+}
+
+impl std::fmt::Debug for MediumShell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MediumShell")
+            .field("boundary", &"<Hittable>")
+            .field("interface", &self.interface)
+            .field("density", &self.density)
+            .finish()
+    }
+}
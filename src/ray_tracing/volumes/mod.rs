@@ -1 +1,2 @@
 pub mod constant_medium;
+pub mod medium_shell;
@@ -0,0 +1,586 @@
+//! 相机动画批量渲染：动画描述文件、逐帧可复现渲染和断点续渲
+//!
+//! 动画描述使用与 raytracer.toml 相同的 TOML 子集：
+//!
+//! ```toml
+//! scene = "cornell"          # cornell | final
+//! frames = 120               # 总帧数
+//! output = "anim/frame_####.png"  # 连续的 # 替换为补零的帧号
+//! image_width = 400
+//! samples_per_pixel = 200
+//! max_depth = 50
+//! seed = 1
+//! orbit_degrees = 360        # 整段动画中相机绕观察点水平旋转的角度
+//! elevation_degrees = 0      # 整段动画中仰角的变化量
+//! dolly = 1.0                # 最后一帧相机距离相对第一帧的倍数
+//! ```
+//!
+//! 也可以用关键帧定义相机路径，设置后 orbit_degrees、elevation_degrees 和 dolly 不再生效。
+//! 首帧位于第一个关键帧，末帧位于最后一个关键帧：
+//!
+//! ```toml
+//! path_lookfrom = ["278 278 -800", "-200 400 -500", "278 600 -300"]
+//! path_lookat = ["278 278 0"]          # 一个目标表示全程注视同一点，否则与 path_lookfrom 一一对应
+//! path_times = ["0", "0.3", "1"]       # 可选，关键帧时间，默认均匀分布
+//! path_spline = "catmull-rom"          # catmull-rom | bezier
+//! path_easing = "ease-in-out"          # linear | ease-in | ease-out | ease-in-out
+//! ```
+//!
+//! 光源和材质参数同样可以设置关键帧（时间在 [0,1] 内，每项为 `时间 值`，也可以直接写一个数值表示全程不变），
+//! 关键帧之间线性插值，可选地经过缓动函数：
+//!
+//! ```toml
+//! light_intensity = ["0 1", "0.5 0.1", "1 1"]   # 场景光源辐射度的倍数
+//! light_color = ["0 1 0.6 0.3", "1 1 1 1"]       # 与场景光源颜色逐分量相乘
+//! light_flicker = 0.3        # 烛光式随机闪烁的幅度，按种子和帧号确定
+//! light_flicker_rate = 0.5   # 每帧经过的闪烁噪声格数，越小变化越缓慢
+//! sphere_roughness = ["0 0", "1 0.5"]  # 康奈尔盒玻璃球（磨砂）/最终场景金属球的粗糙度
+//! channel_easing = "ease-in-out"        # 关键帧之间的缓动函数
+//! ```
+//!
+//! 渲染每帧前把全局帧时间设为帧号，设置了演变速度的噪声纹理因此逐帧变化。
+//!
+//! 每帧先渲染到临时文件，完成后再原子地重命名为最终文件名，因此已存在的帧文件总是完整的，
+//! 中断后重新运行时可以跳过。
+
+use crate::ray_tracing::geometry::hittable::Hittable;
+use crate::ray_tracing::geometry::hittable_list::HittableList;
+use crate::ray_tracing::math::vec3::{Color, Point3};
+use crate::ray_tracing::procedural::noise::set_frame_time;
+use crate::ray_tracing::rendering::camera::Camera;
+use crate::ray_tracing::rendering::camera_path::{CameraKey, CameraPath, Easing, SplineKind};
+use crate::ray_tracing::rendering::framebuffer::FrameBuffer;
+use crate::ray_tracing::rendering::keyframes::{Channel, Flicker, Key, Keyable};
+use crate::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use crate::ray_tracing::rendering::tile_cache::Crop;
+use crate::ray_tracing::utils::config::{self, Entry, Value, invalid, parse_entries};
+use crate::ray_tracing::utils::random::{hash_seed, with_seeded_stream};
+use crate::scenes::cornell_box::{
+    CornellBoxConfig, CornellBoxParams, CornellContents, build_cornell_box, cornell_box_camera,
+};
+use crate::scenes::final_scene::{
+    FinalSceneConfig, FinalSceneParams, build_final_scene_with, final_scene_camera,
+};
+use std::io;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// 动画使用的场景
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationScene {
+    /// 康奈尔盒 + 玻璃球
+    Cornell,
+    /// 《下一周》最终场景
+    Final,
+}
+
+impl AnimationScene {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "cornell" => Some(Self::Cornell),
+            "final" => Some(Self::Final),
+            _ => None,
+        }
+    }
+}
+
+/// 动画描述
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationSpec {
+    pub scene: AnimationScene,
+    pub frames: u32,
+    /// 帧文件名模板，相对路径放在全局配置的输出目录下
+    pub output: String,
+    pub image_width: i32,
+    /// 未设置时依次使用全局配置和场景默认值
+    pub samples_per_pixel: Option<i32>,
+    pub max_depth: Option<i32>,
+    /// 动画种子：场景中的随机物体和每帧的采样都由它确定
+    pub seed: u64,
+    pub orbit_degrees: f64,
+    pub elevation_degrees: f64,
+    pub dolly: f64,
+    /// 相机路径，设置后取代环绕和推拉参数
+    pub path: Option<CameraPath>,
+    /// 光源和材质参数的关键帧
+    pub channels: SceneChannels,
+}
+
+/// 随动画变化的光源和材质参数，未设置的项保持场景的默认值
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SceneChannels {
+    /// 光源辐射度的倍数
+    pub light_intensity: Option<Channel<f64>>,
+    /// 与光源颜色逐分量相乘的颜色
+    pub light_color: Option<Channel<Color>>,
+    /// 光源的随机闪烁
+    pub light_flicker: Option<Flicker>,
+    /// 球体粗糙度
+    pub sphere_roughness: Option<Channel<f64>>,
+}
+
+impl SceneChannels {
+    /// 时刻 t（第 frame 帧）的光源辐射度，base 为场景的默认值
+    pub fn light_emission(&self, base: Color, t: f64, frame: u32) -> Color {
+        let mut emission = base;
+        if let Some(intensity) = &self.light_intensity {
+            emission *= intensity.evaluate(t).max(0.0);
+        }
+        if let Some(color) = &self.light_color {
+            emission.component_mul_assign(&color.evaluate(t).map(|c| c.max(0.0)));
+        }
+        if let Some(flicker) = &self.light_flicker {
+            emission *= flicker.factor(frame as f64);
+        }
+        emission
+    }
+
+    /// 时刻 t 的球体粗糙度，base 为场景的默认值
+    pub fn sphere_roughness(&self, base: f64, t: f64) -> f64 {
+        self.sphere_roughness
+            .as_ref()
+            .map_or(base, |c| c.evaluate(t).clamp(0.0, 1.0))
+    }
+}
+
+impl Default for AnimationSpec {
+    fn default() -> Self {
+        Self {
+            scene: AnimationScene::Cornell,
+            frames: 60,
+            output: "frame_####.png".to_string(),
+            image_width: 400,
+            samples_per_pixel: None,
+            max_depth: None,
+            seed: 1,
+            orbit_degrees: 360.0,
+            elevation_degrees: 0.0,
+            dolly: 1.0,
+            path: None,
+            channels: SceneChannels::default(),
+        }
+    }
+}
+
+impl AnimationSpec {
+    /// 从描述文本解析，source 用于错误信息
+    pub fn parse(text: &str, source: &str) -> io::Result<Self> {
+        let mut spec = Self::default();
+        let mut path = PathEntries::default();
+        let mut channels = ChannelEntries::default();
+
+        for Entry {
+            line_no,
+            key,
+            value,
+        } in parse_entries(text, source)?
+        {
+            let number = value.as_f64();
+            let ok = match (key.as_str(), &value) {
+                ("scene", Value::String(name)) => AnimationScene::from_name(name)
+                    .map(|scene| spec.scene = scene)
+                    .is_some(),
+                ("output", Value::String(pattern)) => {
+                    spec.output = pattern.clone();
+                    true
+                }
+                ("frames", Value::Integer(n)) if *n > 0 => {
+                    u32::try_from(*n).map(|n| spec.frames = n).is_ok()
+                }
+                ("image_width", Value::Integer(_)) => value
+                    .as_positive_i32()
+                    .map(|n| spec.image_width = n)
+                    .is_some(),
+                ("samples_per_pixel", Value::Integer(_)) => value
+                    .as_positive_i32()
+                    .map(|n| spec.samples_per_pixel = Some(n))
+                    .is_some(),
+                ("max_depth", Value::Integer(_)) => value
+                    .as_positive_i32()
+                    .map(|n| spec.max_depth = Some(n))
+                    .is_some(),
+                ("seed", Value::Integer(n)) if *n >= 0 => {
+                    spec.seed = *n as u64;
+                    true
+                }
+                ("orbit_degrees", _) => number.map(|x| spec.orbit_degrees = x).is_some(),
+                ("elevation_degrees", _) => number.map(|x| spec.elevation_degrees = x).is_some(),
+                ("dolly", _) => number
+                    .filter(|&x| x > 0.0)
+                    .map(|x| spec.dolly = x)
+                    .is_some(),
+                ("path_lookfrom", Value::Array(items)) => {
+                    path.line_no = line_no;
+                    parse_list(items, parse_point)
+                        .map(|points| path.lookfrom = points)
+                        .is_some()
+                }
+                ("path_lookat", Value::Array(items)) => parse_list(items, parse_point)
+                    .map(|points| path.lookat = points)
+                    .is_some(),
+                ("path_times", Value::Array(items)) => {
+                    parse_list(items, |s| s.trim().parse::<f64>().ok())
+                        .map(|times| path.times = Some(times))
+                        .is_some()
+                }
+                ("path_spline", Value::String(name)) => name
+                    .parse::<SplineKind>()
+                    .map(|kind| path.spline = kind)
+                    .is_ok(),
+                ("path_easing", Value::String(name)) => name
+                    .parse::<Easing>()
+                    .map(|easing| path.easing = easing)
+                    .is_ok(),
+                ("light_intensity", value) => parse_channel(value, parse_scalar)
+                    .map(|c| channels.light_intensity = Some(c))
+                    .is_some(),
+                ("light_color", value) => {
+                    parse_channel(value, |s| parse_point(s).map(|p| p.coords))
+                        .map(|c| channels.light_color = Some(c))
+                        .is_some()
+                }
+                ("sphere_roughness", value) => parse_channel(value, parse_scalar)
+                    .map(|c| channels.sphere_roughness = Some(c))
+                    .is_some(),
+                ("light_flicker", _) => number
+                    .filter(|&x| x >= 0.0)
+                    .map(|x| channels.flicker = Some(x))
+                    .is_some(),
+                ("light_flicker_rate", _) => number
+                    .filter(|&x| x > 0.0)
+                    .map(|x| channels.flicker_rate = x)
+                    .is_some(),
+                ("channel_easing", Value::String(name)) => name
+                    .parse::<Easing>()
+                    .map(|easing| channels.easing = easing)
+                    .is_ok(),
+                (
+                    "scene" | "output" | "frames" | "image_width" | "samples_per_pixel"
+                    | "max_depth" | "seed" | "path_lookfrom" | "path_lookat" | "path_times"
+                    | "path_spline" | "path_easing" | "channel_easing",
+                    _,
+                ) => false,
+                _ => {
+                    eprintln!("警告: {} 第{}行的未知键 `{}` 已忽略", source, line_no, key);
+                    true
+                }
+            };
+            if !ok {
+                return Err(invalid(
+                    source,
+                    line_no,
+                    &format!("`{}` 的值类型或范围无效", key),
+                ));
+            }
+        }
+
+        spec.path = path.build(source)?;
+        spec.channels = channels.build(spec.seed);
+        Ok(spec)
+    }
+
+    /// 从文件加载动画描述
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        Self::parse(&std::fs::read_to_string(path)?, &path.display().to_string())
+    }
+
+    /// 第 frame 帧的输出路径
+    pub fn frame_path(&self, frame: u32) -> PathBuf {
+        PathBuf::from(config::global().output_path(&expand_pattern(&self.output, frame)))
+    }
+
+    /// 范围内尚未渲染完成（输出文件不存在）的帧
+    pub fn pending_frames(&self, range: Range<u32>) -> Vec<u32> {
+        range
+            .filter(|&frame| !self.frame_path(frame).exists())
+            .collect()
+    }
+
+    /// 渲染单帧并写入输出文件，返回输出路径
+    ///
+    /// 同一描述和帧号的结果可复现，与渲染进程和线程数无关。
+    pub fn render_frame(&self, frame: u32) -> io::Result<PathBuf> {
+        let (world, lights, camera) = self.frame_setup(frame)?;
+        let fb = camera.render_to_buffer(&world, Some(lights));
+        let path = self.frame_path(frame);
+        save_complete(&fb, &path)?;
+        Ok(path)
+    }
+
+    /// 只渲染单帧中的一个区域（区域外的像素留空），与整帧渲染的对应像素完全相同
+    pub fn render_region(&self, frame: u32, region: Crop) -> io::Result<FrameBuffer> {
+        let (world, lights, camera) = self.frame_setup(frame)?;
+        Ok(camera.render_region(&world, Some(lights), region))
+    }
+
+    /// 输出图像的尺寸（宽, 高）
+    pub fn image_size(&self) -> io::Result<(u32, u32)> {
+        let (_, _, camera) = self.frame_setup(0)?;
+        Ok((camera.image_width() as u32, camera.image_height() as u32))
+    }
+
+    /// 第 frame 帧的场景、光源和相机
+    fn frame_setup(&self, frame: u32) -> io::Result<(HittableList, Arc<dyn Hittable>, Camera)> {
+        if frame >= self.frames {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("帧号 {} 超出范围（共 {} 帧）", frame, self.frames),
+            ));
+        }
+        let defaults = config::global();
+        // 动画噪声纹理按帧号演变
+        set_frame_time(frame as f64);
+        // 光源和材质参数的关键帧时间，末帧为1
+        let t = frame as f64 / (self.frames - 1).max(1) as f64;
+        let (world, lights, mut camera) = with_seeded_stream(self.seed, || match self.scene {
+            AnimationScene::Cornell => {
+                let config = CornellBoxConfig {
+                    image_width: self.image_width,
+                    samples_per_pixel: self
+                        .samples_per_pixel
+                        .or(defaults.samples_per_pixel)
+                        .unwrap_or(200),
+                    max_depth: self.max_depth.or(defaults.max_depth).unwrap_or(50),
+                    ..CornellBoxConfig::default()
+                };
+                let mut params = CornellBoxParams::with_contents(&[
+                    CornellContents::TallBox,
+                    CornellContents::GlassSphere,
+                ]);
+                params.light_emission =
+                    self.channels
+                        .light_emission(params.light_emission, t, frame);
+                params.sphere_roughness =
+                    self.channels.sphere_roughness(params.sphere_roughness, t);
+                let (world, lights) = build_cornell_box(&params);
+                (world, lights, cornell_box_camera(&config, &params).build())
+            }
+            AnimationScene::Final => {
+                let config = FinalSceneConfig {
+                    image_width: self.image_width,
+                    samples_per_pixel: self
+                        .samples_per_pixel
+                        .or(defaults.samples_per_pixel)
+                        .unwrap_or(500),
+                    max_depth: self.max_depth.or(defaults.max_depth).unwrap_or(50),
+                    ..FinalSceneConfig::default()
+                };
+                let defaults = FinalSceneParams::default();
+                let params = FinalSceneParams {
+                    light_emission: self
+                        .channels
+                        .light_emission(defaults.light_emission, t, frame),
+                    metal_fuzz: self.channels.sphere_roughness(defaults.metal_fuzz, t),
+                };
+                let (world, lights) = build_final_scene_with(&params);
+                (world, lights, final_scene_camera(&config).build())
+            }
+        });
+
+        camera.set_seed(Some(hash_seed(&[self.seed, frame as u64])));
+        if let Some(path) = &self.path {
+            // 沿路径移动相机，末帧正好到达最后一个关键帧
+            path.apply(&mut camera, t);
+        } else {
+            // 按帧在动画中的位置移动相机（首帧为场景的标准机位）
+            let t = frame as f64 / self.frames as f64;
+            let (azimuth, elevation, distance) = camera.spherical_coords();
+            camera.orbit(
+                azimuth + t * self.orbit_degrees,
+                elevation + t * self.elevation_degrees,
+                distance * (1.0 + t * (self.dolly - 1.0)),
+            );
+        }
+
+        let lights: Arc<dyn Hittable> = Arc::new(lights);
+        Ok((world, lights, camera))
+    }
+}
+
+/// 先写临时文件再重命名为 path，保证最终文件名只对应完整的图像
+pub(crate) fn save_complete(fb: &FrameBuffer, path: &Path) -> io::Result<()> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let partial = partial_path(path);
+    let filename = partial.to_string_lossy();
+    save_framebuffer(fb, &filename, OutputFormat::from_filename(&filename))?;
+    std::fs::rename(&partial, path)
+}
+
+/// 描述文件中的相机路径相关项，全部读完后再组合为路径
+#[derive(Default)]
+struct PathEntries {
+    /// path_lookfrom 所在的行，用于错误信息
+    line_no: usize,
+    lookfrom: Vec<Point3>,
+    lookat: Vec<Point3>,
+    times: Option<Vec<f64>>,
+    spline: SplineKind,
+    easing: Easing,
+}
+
+impl PathEntries {
+    /// 组合为相机路径；没有 path_lookfrom 时为None
+    fn build(self, source: &str) -> io::Result<Option<CameraPath>> {
+        if self.lookfrom.is_empty() {
+            return Ok(None);
+        }
+        let count = self.lookfrom.len();
+        let error = |message: &str| invalid(source, self.line_no, message);
+        let lookat = match self.lookat.len() {
+            1 => vec![self.lookat[0]; count],
+            n if n == count => self.lookat,
+            _ => {
+                return Err(error(
+                    "path_lookat 应只有一个目标，或与 path_lookfrom 数量相同",
+                ));
+            }
+        };
+
+        let views: Vec<(Point3, Point3)> = self.lookfrom.into_iter().zip(lookat).collect();
+        let path = match self.times {
+            None => CameraPath::uniform(self.spline, &views),
+            Some(times) if times.len() == count => {
+                if times.windows(2).any(|w| w[1] <= w[0]) {
+                    return Err(error("path_times 应严格递增"));
+                }
+                let keys = times
+                    .into_iter()
+                    .zip(views)
+                    .map(|(time, (lookfrom, lookat))| CameraKey {
+                        time,
+                        lookfrom,
+                        lookat,
+                    })
+                    .collect();
+                CameraPath::new(self.spline, keys)
+            }
+            Some(_) => return Err(error("path_times 应与 path_lookfrom 数量相同")),
+        };
+        Ok(Some(path.with_easing(self.easing)))
+    }
+}
+
+/// 描述文件中的参数通道相关项，全部读完后再加上缓动函数和闪烁种子
+#[derive(Default)]
+struct ChannelEntries {
+    light_intensity: Option<Channel<f64>>,
+    light_color: Option<Channel<Color>>,
+    sphere_roughness: Option<Channel<f64>>,
+    flicker: Option<f64>,
+    flicker_rate: f64,
+    easing: Easing,
+}
+
+impl ChannelEntries {
+    fn build(self, seed: u64) -> SceneChannels {
+        let easing = self.easing;
+        let flicker_rate = if self.flicker_rate > 0.0 {
+            self.flicker_rate
+        } else {
+            1.0
+        };
+        SceneChannels {
+            light_intensity: self.light_intensity.map(|c| c.with_easing(easing)),
+            light_color: self.light_color.map(|c| c.with_easing(easing)),
+            light_flicker: self.flicker.filter(|&a| a > 0.0).map(|amplitude| {
+                Flicker::new(amplitude)
+                    .with_rate(flicker_rate)
+                    .with_seed(seed)
+            }),
+            sphere_roughness: self.sphere_roughness.map(|c| c.with_easing(easing)),
+        }
+    }
+}
+
+/// 解析参数通道：单个数值为常数，字符串数组的每项为 `时间 值`
+fn parse_channel<T: Keyable>(
+    value: &Value,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Option<Channel<T>> {
+    match value {
+        Value::Array(items) if !items.is_empty() => {
+            let keys = parse_list(items, |item| {
+                let item = item.trim();
+                let (time, rest) = item.split_once(char::is_whitespace)?;
+                let time: f64 = time.parse().ok()?;
+                (0.0..=1.0).contains(&time).then_some(Key {
+                    time,
+                    value: parse(rest)?,
+                })
+            })?;
+            if keys.windows(2).any(|w| w[1].time <= w[0].time) {
+                return None;
+            }
+            Some(Channel::new(keys))
+        }
+        Value::String(s) => parse(s).map(Channel::constant),
+        Value::Integer(_) | Value::Float(_) => {
+            let text = value.as_f64()?.to_string();
+            parse(&text).map(Channel::constant)
+        }
+        _ => None,
+    }
+}
+
+fn parse_scalar(s: &str) -> Option<f64> {
+    s.trim().parse().ok().filter(|x: &f64| x.is_finite())
+}
+
+/// 逐项解析字符串数组，任一项无效时为None
+fn parse_list<T>(items: &[String], parse: impl Fn(&str) -> Option<T>) -> Option<Vec<T>> {
+    items.iter().map(|s| parse(s)).collect()
+}
+
+/// 解析 `x y z`（也可用逗号分隔）
+pub(crate) fn parse_point(s: &str) -> Option<Point3> {
+    let values: Vec<f64> = s
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse().ok())
+        .collect::<Option<_>>()?;
+    match values[..] {
+        [x, y, z] => Some(Point3::new(x, y, z)),
+        _ => None,
+    }
+}
+
+/// 把模板中第一段连续的 `#` 替换为补零到相同宽度的帧号；没有 `#` 时在扩展名前追加 `_帧号`
+fn expand_pattern(pattern: &str, frame: u32) -> String {
+    if let Some(start) = pattern.find('#') {
+        let width = pattern[start..].chars().take_while(|&c| c == '#').count();
+        return format!(
+            "{}{:0width$}{}",
+            &pattern[..start],
+            frame,
+            &pattern[start + width..],
+            width = width
+        );
+    }
+
+    let path = Path::new(pattern);
+    match (path.file_stem(), path.extension()) {
+        (Some(stem), Some(ext)) => path
+            .with_file_name(format!(
+                "{}_{:04}.{}",
+                stem.to_string_lossy(),
+                frame,
+                ext.to_string_lossy()
+            ))
+            .to_string_lossy()
+            .into_owned(),
+        _ => format!("{}_{:04}", pattern, frame),
+    }
+}
+
+/// 渲染中的临时文件名（保留扩展名以便推断输出格式）
+fn partial_path(path: &Path) -> PathBuf {
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "png".to_string());
+    path.with_extension(format!("partial.{}", ext))
+}
@@ -0,0 +1,209 @@
+//! 环境光遮蔽与全局光照的对比：用同一相机分别渲染 AO 和路径追踪结果，输出并排图或差异图
+//!
+//! 差异图按亮度中位数归一化：红色表示全局光照比 AO 预测的更亮（间接光、颜色渗透或直接光照占主导），
+//! 蓝色表示更暗（AO 无法表现的阴影或远处的遮挡）。
+
+use crate::ray_tracing::geometry::hittable::Hittable;
+use crate::ray_tracing::geometry::hittable_list::HittableList;
+use crate::ray_tracing::math::vec3::Color;
+use crate::ray_tracing::rendering::camera::CameraBuilder;
+use crate::ray_tracing::rendering::color::luminance;
+use crate::ray_tracing::rendering::framebuffer::FrameBuffer;
+use crate::ray_tracing::rendering::integrator::Integrator;
+use crate::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use crate::ray_tracing::utils::image_compare::{
+    ImageMetrics, compare, display_image, luminance_difference, median_luminance, side_by_side,
+};
+use crate::scenes::cornell_box::{
+    CornellBoxConfig, CornellBoxParams, CornellContents, build_cornell_box, cornell_box_camera,
+};
+use crate::scenes::final_scene::{FinalSceneConfig, build_final_scene, final_scene_camera};
+use std::io;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// 参与对比的场景
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CompareScene {
+    /// 康奈尔盒（高盒 + 玻璃球）
+    #[default]
+    Cornell,
+    /// 最终场景
+    Final,
+}
+
+impl FromStr for CompareScene {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cornell" => Ok(Self::Cornell),
+            "final" => Ok(Self::Final),
+            _ => Err(format!("未知的对比场景 `{}`（可选: cornell, final）", s)),
+        }
+    }
+}
+
+/// 输出布局
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CompareLayout {
+    /// AO | 全局光照 | 差异图 三联图
+    #[default]
+    SideBySide,
+    /// 只输出差异图
+    Difference,
+}
+
+/// AO/全局光照对比配置
+pub struct AoCompareConfig {
+    pub scene: CompareScene,
+    pub image_width: i32,
+    /// 全局光照的每像素采样数
+    pub samples_per_pixel: i32,
+    /// AO 的每像素相机样本数（每个样本再发出 ao_samples 条遮挡光线）
+    pub ao_samples_per_pixel: i32,
+    pub max_depth: i32,
+    /// 遮挡距离，None 时使用场景的默认值
+    pub ao_distance: Option<f64>,
+    /// 每个相机样本的遮挡光线数
+    pub ao_samples: u32,
+    /// 差异图的放大倍数
+    pub difference_scale: f64,
+    pub layout: CompareLayout,
+    pub output_filename: String,
+}
+
+impl Default for AoCompareConfig {
+    fn default() -> Self {
+        Self {
+            scene: CompareScene::Cornell,
+            image_width: 400,
+            samples_per_pixel: 256,
+            ao_samples_per_pixel: 16,
+            max_depth: 30,
+            ao_distance: None,
+            ao_samples: 4,
+            difference_scale: 0.5,
+            layout: CompareLayout::SideBySide,
+            output_filename: "ao_compare.png".to_string(),
+        }
+    }
+}
+
+/// 用同一相机渲染 AO 和全局光照，按布局保存对比图，返回两者（GI 按亮度中位数与 AO 对齐后）的比较指标
+///
+/// 未指定遮挡距离时取场景包围盒对角线的 10%。
+pub fn render_ao_comparison(
+    world: &dyn Hittable,
+    lights: Option<Arc<dyn Hittable>>,
+    camera: CameraBuilder,
+    config: &AoCompareConfig,
+) -> io::Result<ImageMetrics> {
+    let distance = config.ao_distance.unwrap_or_else(|| {
+        world.bounding_box().map_or(1.0, |b| {
+            let diagonal = (b.x.size().powi(2) + b.y.size().powi(2) + b.z.size().powi(2)).sqrt();
+            0.1 * diagonal
+        })
+    });
+
+    let start = Instant::now();
+    eprintln!(
+        "渲染环境光遮蔽... 遮挡距离: {:.3}, 采样数: {}×{}",
+        distance, config.ao_samples_per_pixel, config.ao_samples
+    );
+    let ao = camera
+        .clone()
+        .samples_per_pixel(config.ao_samples_per_pixel)
+        .integrator(Integrator::AmbientOcclusion {
+            distance,
+            samples: config.ao_samples,
+        })
+        .build()
+        .render_to_buffer(world, None);
+    eprintln!("AO 完成，耗时: {:?}", start.elapsed());
+
+    let start = Instant::now();
+    eprintln!(
+        "渲染全局光照... 采样数: {}, 反射深度: {}",
+        config.samples_per_pixel, config.max_depth
+    );
+    let gi = camera
+        .samples_per_pixel(config.samples_per_pixel)
+        .max_depth(config.max_depth)
+        .integrator(Integrator::PathTracing)
+        .build()
+        .render_to_buffer(world, lights);
+    eprintln!("全局光照完成，耗时: {:?}", start.elapsed());
+
+    let difference = luminance_difference(&ao, &gi, config.difference_scale);
+    let output = match config.layout {
+        CompareLayout::SideBySide => side_by_side(&[&ao, &gi, &difference]),
+        CompareLayout::Difference => difference,
+    };
+    save_framebuffer(
+        &output,
+        &config.output_filename,
+        OutputFormat::from_filename(&config.output_filename),
+    )?;
+    eprintln!("对比图已保存为 {}", config.output_filename);
+
+    // 指标在灰度上计算：GI 亮度按中位数缩放到与 AO 相同
+    let gray = |fb: &FrameBuffer, scale: f64| {
+        let mut out = fb.clone();
+        for y in 0..fb.height() {
+            for x in 0..fb.width() {
+                let l = luminance(&fb.get(x, y));
+                out.set(x, y, Color::repeat(l * scale));
+            }
+        }
+        display_image(&out)
+    };
+    let scale = median_luminance(&ao) / median_luminance(&gi).max(1e-12);
+    compare(&gray(&ao, 1.0), &gray(&gi, scale))
+}
+
+/// 构建所选场景，返回 (物体, 光源, 图像宽度为 image_width 的相机)
+pub fn build_compare_scene(
+    scene: CompareScene,
+    image_width: i32,
+) -> (HittableList, HittableList, CameraBuilder) {
+    match scene {
+        CompareScene::Cornell => {
+            let params = CornellBoxParams::with_contents(&[
+                CornellContents::TallBox,
+                CornellContents::GlassSphere,
+            ]);
+            let (world, lights) = build_cornell_box(&params);
+            let camera_config = CornellBoxConfig {
+                image_width,
+                ..CornellBoxConfig::default()
+            };
+            (world, lights, cornell_box_camera(&camera_config, &params))
+        }
+        CompareScene::Final => {
+            let (world, lights) = build_final_scene();
+            let camera_config = FinalSceneConfig {
+                image_width,
+                ..FinalSceneConfig::default()
+            };
+            (world, lights, final_scene_camera(&camera_config))
+        }
+    }
+}
+
+/// 构建所选场景并输出 AO/全局光照对比图
+pub fn ao_compare_scene(config: AoCompareConfig) -> io::Result<ImageMetrics> {
+    let (world, lights, camera) = build_compare_scene(config.scene, config.image_width);
+    let distance = match config.scene {
+        // 盒子边长的 1/5
+        CompareScene::Cornell => CornellBoxParams::default().size * 0.2,
+        // 包围盒被半径5000的环境雾主导，按主要物体的尺度取值
+        CompareScene::Final => 100.0,
+    };
+    let config = AoCompareConfig {
+        ao_distance: config.ao_distance.or(Some(distance)),
+        ..config
+    };
+    render_ao_comparison(&world, Some(Arc::new(lights)), camera, &config)
+}
@@ -1,3 +1,4 @@
+use crate::ray_tracing::geometry::hittable::Hittable;
 use crate::ray_tracing::geometry::hittable_list::HittableList;
 use crate::ray_tracing::geometry::quad::{Quad, box_new};
 use crate::ray_tracing::geometry::sphere::Sphere;
@@ -6,9 +7,15 @@ use crate::ray_tracing::geometry::transforms::translate::Translate;
 use crate::ray_tracing::materials::dielectric::Dielectric;
 use crate::ray_tracing::materials::diffuse_light::DiffuseLight;
 use crate::ray_tracing::materials::lambertian::Lambertian;
-use crate::ray_tracing::materials::material::NoMaterial;
+use crate::ray_tracing::materials::material::{Material, NoMaterial};
+use crate::ray_tracing::materials::metal::Metal;
+use crate::ray_tracing::materials::rough_dielectric::RoughDielectric;
 use crate::ray_tracing::math::vec3::{Color, Point3, Vec3};
-use crate::ray_tracing::rendering::camera::Camera;
+use crate::ray_tracing::rendering::camera::{Camera, CameraBuilder};
+use crate::ray_tracing::rendering::irradiance_cache::IrradianceCacheSettings;
+use crate::ray_tracing::rendering::stats::RenderStats;
+use crate::ray_tracing::rendering::wireframe::BoundsOverlay;
+use crate::ray_tracing::volumes::constant_medium::ConstantMedium;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -18,6 +25,12 @@ pub struct CornellBoxConfig {
     pub samples_per_pixel: i32,
     pub max_depth: i32,
     pub output_filename: String,
+    pub bounds_overlay: BoundsOverlay,
+    pub transparent_background: bool,
+    /// 使用辐照度缓存快速预览（有偏）
+    pub irradiance_cache: bool,
+    /// 渲染结束后输出按材质统计的着色开销报告
+    pub stats: bool,
 }
 
 impl Default for CornellBoxConfig {
@@ -27,126 +40,278 @@ impl Default for CornellBoxConfig {
             samples_per_pixel: 1000,
             max_depth: 50,
             output_filename: "cornell_box.png".to_string(),
+            bounds_overlay: BoundsOverlay::Off,
+            transparent_background: false,
+            irradiance_cache: false,
+            stats: false,
         }
     }
 }
 
+/// 康奈尔盒内放置的物体
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CornellContents {
+    /// 高白盒（旋转15°）
+    TallBox,
+    /// 矮白盒（旋转-18°）
+    ShortBox,
+    /// 书中经典的高矮两个白盒
+    Boxes,
+    /// 玻璃球（同时加入光源列表用于重要性采样）
+    GlassSphere,
+    /// 光滑金属球
+    MetalSphere,
+    /// 两个盒子形状的烟雾（黑、白各一）
+    Smoke,
+    /// 后墙替换为镜面
+    MirrorWall,
+}
+
+/// 康奈尔盒场景参数，默认值与书中的场景一致
+#[derive(Debug, Clone, PartialEq)]
+pub struct CornellBoxParams {
+    /// 盒子边长
+    pub size: f64,
+    pub left_color: Color,
+    pub right_color: Color,
+    pub white_color: Color,
+    /// 光源在 x、z 方向上的尺寸
+    pub light_size: (f64, f64),
+    /// 光源辐射度
+    pub light_emission: Color,
+    /// 玻璃球和金属球的粗糙度，0 为光滑（玻璃球大于0时改用磨砂玻璃）
+    pub sphere_roughness: f64,
+    pub contents: Vec<CornellContents>,
+}
+
+impl Default for CornellBoxParams {
+    fn default() -> Self {
+        Self {
+            size: 555.0,
+            left_color: Color::new(0.65, 0.05, 0.05),
+            right_color: Color::new(0.12, 0.45, 0.15),
+            white_color: Color::new(0.73, 0.73, 0.73),
+            light_size: (130.0, 105.0),
+            light_emission: Color::new(15.0, 15.0, 15.0),
+            sphere_roughness: 0.0,
+            contents: Vec::new(),
+        }
+    }
+}
+
+impl CornellBoxParams {
+    /// 指定盒内物体，其余参数取默认值
+    pub fn with_contents(contents: &[CornellContents]) -> Self {
+        Self {
+            contents: contents.to_vec(),
+            ..Self::default()
+        }
+    }
+
+    #[inline]
+    fn has(&self, item: CornellContents) -> bool {
+        self.contents.contains(&item)
+    }
+}
+
 /// 构建基础康奈尔盒场景
 pub fn build_cornell_box_scene() -> (HittableList, HittableList) {
+    build_cornell_box(&CornellBoxParams::default())
+}
+
+/// 按参数构建康奈尔盒场景，返回 (场景物体, 光源列表)
+pub fn build_cornell_box(params: &CornellBoxParams) -> (HittableList, HittableList) {
     let mut world = HittableList::new();
     let mut lights = HittableList::new();
 
+    let size = params.size;
+    // 书中场景的坐标按边长555设计，盒内物体按比例缩放
+    let scale = size / 555.0;
+
     // 创建材质
-    let red = Arc::new(Lambertian::new(Color::new(0.65, 0.05, 0.05)));
-    let white = Arc::new(Lambertian::new(Color::new(0.73, 0.73, 0.73)));
-    let green = Arc::new(Lambertian::new(Color::new(0.12, 0.45, 0.15)));
-    let light = Arc::new(DiffuseLight::new_color(Color::new(15.0, 15.0, 15.0)));
+    let red = Arc::new(Lambertian::new(params.left_color));
+    let white = Arc::new(Lambertian::new(params.white_color));
+    let green = Arc::new(Lambertian::new(params.right_color));
+    let light = Arc::new(DiffuseLight::new_color(params.light_emission));
+    let back: Arc<dyn Material> = if params.has(CornellContents::MirrorWall) {
+        Arc::new(Metal::new(Color::new(0.9, 0.9, 0.9), 0.0))
+    } else {
+        white.clone()
+    };
 
     // 康奈尔盒的六个面
     // 右面（绿色）
     world.add(Arc::new(Quad::new(
-        Point3::new(555.0, 0.0, 0.0),
-        Vec3::new(0.0, 555.0, 0.0),
-        Vec3::new(0.0, 0.0, 555.0),
+        Point3::new(size, 0.0, 0.0),
+        Vec3::new(0.0, size, 0.0),
+        Vec3::new(0.0, 0.0, size),
         green,
     )));
 
     // 左面（红色）
     world.add(Arc::new(Quad::new(
         Point3::new(0.0, 0.0, 0.0),
-        Vec3::new(0.0, 555.0, 0.0),
-        Vec3::new(0.0, 0.0, 555.0),
+        Vec3::new(0.0, size, 0.0),
+        Vec3::new(0.0, 0.0, size),
         red,
     )));
 
     // 顶面（白色）
     world.add(Arc::new(Quad::new(
-        Point3::new(0.0, 555.0, 0.0),
-        Vec3::new(555.0, 0.0, 0.0),
-        Vec3::new(0.0, 0.0, 555.0),
+        Point3::new(0.0, size, 0.0),
+        Vec3::new(size, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, size),
         white.clone(),
     )));
 
     // 底面（白色）
     world.add(Arc::new(Quad::new(
         Point3::new(0.0, 0.0, 0.0),
-        Vec3::new(555.0, 0.0, 0.0),
-        Vec3::new(0.0, 0.0, 555.0),
+        Vec3::new(size, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, size),
         white.clone(),
     )));
 
-    // 后面（白色）
+    // 后面（白色或镜面）
     world.add(Arc::new(Quad::new(
-        Point3::new(0.0, 0.0, 555.0),
-        Vec3::new(555.0, 0.0, 0.0),
-        Vec3::new(0.0, 555.0, 0.0),
-        white.clone(),
+        Point3::new(0.0, 0.0, size),
+        Vec3::new(size, 0.0, 0.0),
+        Vec3::new(0.0, size, 0.0),
+        back,
     )));
 
-    // 光源
-    let light_quad = Arc::new(Quad::new(
-        Point3::new(213.0, 554.0, 227.0),
-        Vec3::new(130.0, 0.0, 0.0),
-        Vec3::new(0.0, 0.0, 105.0),
-        light,
-    ));
-    world.add(light_quad.clone());
+    // 光源（位于顶面下方，中心与书中场景一致）
+    let (light_w, light_d) = params.light_size;
+    let light_corner = Point3::new(
+        278.0 * scale - light_w / 2.0,
+        size - 1.0,
+        279.5 * scale - light_d / 2.0,
+    );
+    let light_u = Vec3::new(light_w, 0.0, 0.0);
+    let light_v = Vec3::new(0.0, 0.0, light_d);
+    world.add(Arc::new(Quad::new(light_corner, light_u, light_v, light)));
 
     // 光源列表（用于重要性采样）
     lights.add(Arc::new(Quad::new(
-        Point3::new(213.0, 554.0, 227.0),
-        Vec3::new(130.0, 0.0, 0.0),
-        Vec3::new(0.0, 0.0, 105.0),
+        light_corner,
+        light_u,
+        light_v,
         Arc::new(NoMaterial),
     )));
 
+    // 盒内物体
+    let tall_box = || -> Arc<dyn Hittable> {
+        let b = box_new(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(165.0, 330.0, 165.0) * scale,
+            white.clone(),
+        );
+        let rotated = Arc::new(RotateY::new(Arc::new(b), 15.0));
+        Arc::new(Translate::new(
+            rotated,
+            Vec3::new(265.0, 0.0, 295.0) * scale,
+        ))
+    };
+    let short_box = || -> Arc<dyn Hittable> {
+        let b = box_new(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(165.0, 165.0, 165.0) * scale,
+            white.clone(),
+        );
+        let rotated = Arc::new(RotateY::new(Arc::new(b), -18.0));
+        Arc::new(Translate::new(rotated, Vec3::new(130.0, 0.0, 65.0) * scale))
+    };
+    let sphere_center = Point3::new(190.0, 90.0, 190.0) * scale;
+    let sphere_radius = 90.0 * scale;
+
+    for item in &params.contents {
+        match item {
+            CornellContents::TallBox => world.add(tall_box()),
+            CornellContents::ShortBox => world.add(short_box()),
+            CornellContents::Boxes => {
+                world.add(tall_box());
+                world.add(short_box());
+            }
+            CornellContents::GlassSphere => {
+                let glass: Arc<dyn Material> = if params.sphere_roughness > 0.0 {
+                    Arc::new(RoughDielectric::new(1.5, params.sphere_roughness))
+                } else {
+                    Arc::new(Dielectric::new(1.5))
+                };
+                world.add(Arc::new(Sphere::new(sphere_center, sphere_radius, glass)));
+                // 将玻璃球也加入光源列表（用于重要性采样）
+                lights.add(Arc::new(Sphere::new(
+                    sphere_center,
+                    sphere_radius,
+                    Arc::new(NoMaterial),
+                )));
+            }
+            CornellContents::MetalSphere => world.add(Arc::new(Sphere::new(
+                sphere_center,
+                sphere_radius,
+                Arc::new(Metal::new(
+                    Color::new(0.8, 0.85, 0.88),
+                    params.sphere_roughness,
+                )),
+            ))),
+            CornellContents::Smoke => {
+                world.add(Arc::new(ConstantMedium::new_color(
+                    tall_box(),
+                    0.01 / scale,
+                    Color::new(0.0, 0.0, 0.0),
+                )));
+                world.add(Arc::new(ConstantMedium::new_color(
+                    short_box(),
+                    0.01 / scale,
+                    Color::new(1.0, 1.0, 1.0),
+                )));
+            }
+            CornellContents::MirrorWall => {}
+        }
+    }
+
     (world, lights)
 }
 
 /// 康奈尔盒 + 玻璃球场景
 pub fn cornell_box_with_glass_sphere(config: CornellBoxConfig) {
-    let (mut world, mut lights) = build_cornell_box_scene();
+    let params =
+        CornellBoxParams::with_contents(&[CornellContents::TallBox, CornellContents::GlassSphere]);
+    render_cornell_box(config, &params);
+}
 
-    // 添加白色盒子
-    let white = Arc::new(Lambertian::new(Color::new(0.73, 0.73, 0.73)));
-    let box1 = box_new(
-        Point3::new(0.0, 0.0, 0.0),
-        Point3::new(165.0, 330.0, 165.0),
-        white,
-    );
-    let box1_rotated = Arc::new(RotateY::new(Arc::new(box1), 15.0));
-    let box1_translated = Arc::new(Translate::new(box1_rotated, Vec3::new(265.0, 0.0, 295.0)));
-    world.add(box1_translated);
-
-    // 添加玻璃球
-    let glass_sphere = Arc::new(Sphere::new(
-        Point3::new(190.0, 90.0, 190.0),
-        90.0,
-        Arc::new(Dielectric::new(1.5)),
-    ));
-    world.add(glass_sphere.clone());
-
-    // 将玻璃球也加入光源列表（用于重要性采样）
-    lights.add(Arc::new(Sphere::new(
-        Point3::new(190.0, 90.0, 190.0),
-        90.0,
-        Arc::new(NoMaterial),
-    )));
+/// 康奈尔盒的标准相机（从盒子开口正前方观察）
+pub fn cornell_box_camera(config: &CornellBoxConfig, params: &CornellBoxParams) -> CameraBuilder {
+    let size = params.size;
+    let mut builder = Camera::builder()
+        .aspect_ratio(1.0)
+        .image_width(config.image_width)
+        .samples_per_pixel(config.samples_per_pixel)
+        .max_depth(config.max_depth)
+        .background_color(Color::zeros()) // 黑色背景
+        .vfov(40.0)
+        .lookfrom(Point3::new(278.0, 278.0, -800.0) * (size / 555.0))
+        .lookat(Point3::new(278.0, 278.0, 0.0) * (size / 555.0))
+        .vup(Vec3::new(0.0, 1.0, 0.0))
+        .defocus_angle(0.0)
+        .output_filename(config.output_filename.clone())
+        .bounds_overlay(config.bounds_overlay)
+        .transparent_background(config.transparent_background);
+    if config.irradiance_cache {
+        builder = builder.irradiance_cache(IrradianceCacheSettings::default());
+    }
+    builder
+}
 
-    // 配置相机
-    let mut camera = Camera::new();
-    camera.aspect_ratio = 1.0;
-    camera.image_width = config.image_width;
-    camera.samples_per_pixel = config.samples_per_pixel;
-    camera.max_depth = config.max_depth;
-    camera.background = Color::zeros(); // 黑色背景
-
-    camera.vfov = 40.0;
-    camera.lookfrom = Point3::new(278.0, 278.0, -800.0);
-    camera.lookat = Point3::new(278.0, 278.0, 0.0);
-    camera.vup = Vec3::new(0.0, 1.0, 0.0);
-    camera.defocus_angle = 0.0;
-    camera.output_filename = config.output_filename;
+/// 按参数构建并渲染康奈尔盒场景
+pub fn render_cornell_box(config: CornellBoxConfig, params: &CornellBoxParams) {
+    let (world, lights) = build_cornell_box(params);
+    let stats = config.stats.then(|| Arc::new(RenderStats::new()));
+    let mut builder = cornell_box_camera(&config, params);
+    if let Some(stats) = &stats {
+        builder = builder.stats(stats.clone());
+    }
+    let camera = builder.build();
 
     // 渲染
     let start = Instant::now();
@@ -156,8 +321,11 @@ pub fn cornell_box_with_glass_sphere(config: CornellBoxConfig) {
         config.image_width, config.image_width, config.samples_per_pixel, config.max_depth
     );
 
-    camera.render(&world, Some(Arc::new(lights)));
+    camera.render_frame(&world, Some(Arc::new(lights)));
 
     let duration = start.elapsed();
     eprintln!("渲染完成！总耗时: {:?}", duration);
+    if let Some(stats) = stats {
+        eprint!("{}", stats);
+    }
 }
@@ -12,18 +12,32 @@ use crate::ray_tracing::materials::metal::Metal;
 use crate::ray_tracing::materials::texture::image::ImageTexture;
 use crate::ray_tracing::materials::texture::noise::NoiseTexture;
 use crate::ray_tracing::math::vec3::{Color, Point3, Vec3, Vec3Ext};
-use crate::ray_tracing::rendering::camera::Camera;
+use crate::ray_tracing::rendering::camera::{Camera, CameraBuilder};
+use crate::ray_tracing::rendering::stats::RenderStats;
+use crate::ray_tracing::rendering::wireframe::BoundsOverlay;
+use crate::ray_tracing::scene::world::Scene;
 use crate::ray_tracing::utils::random::random_double_range;
 use crate::ray_tracing::volumes::constant_medium::ConstantMedium;
+use crate::ray_tracing::volumes::medium_shell::MediumShell;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Instant;
 
+/// 最终场景标准相机的名称，其输出文件名即配置中的 output_filename
+pub const MAIN_CAMERA: &str = "main";
+
 /// 最终场景配置
 pub struct FinalSceneConfig {
     pub image_width: i32,
     pub samples_per_pixel: i32,
     pub max_depth: i32,
     pub output_filename: String,
+    pub bounds_overlay: BoundsOverlay,
+    pub transparent_background: bool,
+    /// 渲染结束后输出按材质统计的着色开销报告
+    pub stats: bool,
+    /// 要渲染的相机名称（见 `final_scene_cameras`），None 时渲染全部相机
+    pub camera: Option<String>,
 }
 
 impl Default for FinalSceneConfig {
@@ -33,12 +47,41 @@ impl Default for FinalSceneConfig {
             samples_per_pixel: 5000,
             max_depth: 75,
             output_filename: "final_scene.png".to_string(),
+            bounds_overlay: BoundsOverlay::Off,
+            transparent_background: false,
+            stats: false,
+            camera: Some(MAIN_CAMERA.to_string()),
         }
     }
 }
 
-/// 构建最终复杂场景
-pub fn final_scene_next_week(config: FinalSceneConfig) {
+/// 最终场景中可调节的参数，默认值与书中的场景一致
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FinalSceneParams {
+    /// 顶部光源的辐射度
+    pub light_emission: Color,
+    /// 金属球的模糊度
+    pub metal_fuzz: f64,
+}
+
+impl Default for FinalSceneParams {
+    fn default() -> Self {
+        Self {
+            light_emission: Color::new(7.0, 7.0, 7.0),
+            metal_fuzz: 1.0,
+        }
+    }
+}
+
+/// 构建最终复杂场景，返回 (场景, 光源列表)
+///
+/// 场景包含随机生成的盒子和小球，需要可复现时在 `with_seeded_stream` 中调用。
+pub fn build_final_scene() -> (HittableList, HittableList) {
+    build_final_scene_with(&FinalSceneParams::default())
+}
+
+/// 按参数构建最终场景，随机物体与 [`build_final_scene`] 相同
+pub fn build_final_scene_with(params: &FinalSceneParams) -> (HittableList, HittableList) {
     let mut world = HittableList::new();
 
     // 地面材质
@@ -70,7 +113,7 @@ pub fn final_scene_next_week(config: FinalSceneConfig) {
     world.add(Arc::new(BvhNode::new(&boxes1)));
 
     // 添加光源
-    let light = Arc::new(DiffuseLight::new_color(Color::new(7.0, 7.0, 7.0)));
+    let light = Arc::new(DiffuseLight::new_color(params.light_emission));
     world.add(Arc::new(Quad::new(
         Point3::new(123.0, 554.0, 147.0),
         Vec3::new(300.0, 0.0, 0.0),
@@ -100,18 +143,18 @@ pub fn final_scene_next_week(config: FinalSceneConfig) {
     world.add(Arc::new(Sphere::new(
         Point3::new(0.0, 150.0, 145.0),
         50.0,
-        Arc::new(Metal::new(Color::new(0.8, 0.8, 0.9), 1.0)),
+        Arc::new(Metal::new(Color::new(0.8, 0.8, 0.9), params.metal_fuzz)),
     )));
 
-    // 蓝色烟雾球
+    // 蓝色烟雾球：玻璃外壳包围的介质，光线先在外壳折射再进入烟雾
     let boundary = Arc::new(Sphere::new(
         Point3::new(360.0, 150.0, 145.0),
         70.0,
-        Arc::new(Dielectric::new(1.5)),
+        Arc::new(NoMaterial),
     ));
-    world.add(boundary.clone());
-    world.add(Arc::new(ConstantMedium::new_color(
+    world.add(Arc::new(MediumShell::new_color(
         boundary,
+        Arc::new(Dielectric::new(1.5)),
         0.2,
         Color::new(0.2, 0.4, 0.9),
     )));
@@ -180,20 +223,96 @@ pub fn final_scene_next_week(config: FinalSceneConfig) {
         Arc::new(NoMaterial),
     )));
 
-    // 配置相机
-    let mut camera = Camera::new();
-    camera.aspect_ratio = 1.0;
-    camera.image_width = config.image_width;
-    camera.samples_per_pixel = config.samples_per_pixel;
-    camera.max_depth = config.max_depth;
-    camera.background = Color::zeros(); // 黑色背景
-
-    camera.vfov = 40.0;
-    camera.lookfrom = Point3::new(478.0, 278.0, -600.0);
-    camera.lookat = Point3::new(278.0, 278.0, 0.0);
-    camera.vup = Vec3::new(0.0, 1.0, 0.0);
-    camera.defocus_angle = 0.0;
-    camera.output_filename = config.output_filename;
+    (world, lights)
+}
+
+/// 最终场景的标准相机
+pub fn final_scene_camera(config: &FinalSceneConfig) -> CameraBuilder {
+    Camera::builder()
+        .aspect_ratio(1.0)
+        .image_width(config.image_width)
+        .samples_per_pixel(config.samples_per_pixel)
+        .max_depth(config.max_depth)
+        .background_color(Color::zeros()) // 黑色背景
+        .vfov(40.0)
+        .lookfrom(Point3::new(478.0, 278.0, -600.0))
+        .lookat(Point3::new(278.0, 278.0, 0.0))
+        .vup(Vec3::new(0.0, 1.0, 0.0))
+        .defocus_angle(0.0)
+        .output_filename(config.output_filename.clone())
+        .bounds_overlay(config.bounds_overlay)
+        .transparent_background(config.transparent_background)
+}
+
+/// 最终场景的全部命名相机：标准视角以及俯视、侧面和小球群特写
+///
+/// 非标准相机的输出文件名在 output_filename 的主干后追加相机名称，如 `final_scene_top.png`。
+pub fn final_scene_cameras(config: &FinalSceneConfig) -> Vec<(&'static str, CameraBuilder)> {
+    let views = [
+        (
+            "top",
+            Point3::new(278.0, 900.0, -500.0),
+            Point3::new(278.0, 150.0, 200.0),
+            45.0,
+        ),
+        (
+            "side",
+            Point3::new(-650.0, 300.0, 278.0),
+            Point3::new(278.0, 250.0, 278.0),
+            40.0,
+        ),
+        (
+            "cluster",
+            Point3::new(200.0, 450.0, -100.0),
+            Point3::new(-20.0, 350.0, 470.0),
+            35.0,
+        ),
+    ];
+
+    let mut cameras = vec![(MAIN_CAMERA, final_scene_camera(config))];
+    cameras.extend(views.into_iter().map(|(name, lookfrom, lookat, vfov)| {
+        let builder = final_scene_camera(config)
+            .lookfrom(lookfrom)
+            .lookat(lookat)
+            .vfov(vfov)
+            .output_filename(camera_output_filename(&config.output_filename, name));
+        (name, builder)
+    }));
+    cameras
+}
+
+/// 在文件名主干后追加相机名称：`out/final.png` -> `out/final_top.png`
+fn camera_output_filename(filename: &str, camera: &str) -> String {
+    let path = Path::new(filename);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("final_scene");
+    let name = match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{}_{}.{}", stem, camera, ext),
+        None => format!("{}_{}", stem, camera),
+    };
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
+
+/// 构建并渲染最终复杂场景：场景和加速结构只构建一次，依次渲染选中的相机
+pub fn final_scene_next_week(config: FinalSceneConfig) {
+    let (world, lights) = build_final_scene();
+    let mut scene = Scene::new();
+    for object in world.objects {
+        scene.add(object);
+    }
+    for light in lights.objects {
+        scene.add_light(light);
+    }
+
+    let stats = config.stats.then(|| Arc::new(RenderStats::new()));
+    for (name, mut builder) in final_scene_cameras(&config) {
+        if let Some(stats) = &stats {
+            builder = builder.stats(stats.clone());
+        }
+        scene.add_camera(name, builder.build());
+    }
 
     // 渲染
     let start = Instant::now();
@@ -203,8 +322,14 @@ pub fn final_scene_next_week(config: FinalSceneConfig) {
         config.image_width, config.image_width, config.samples_per_pixel, config.max_depth
     );
 
-    camera.render(&world, Some(Arc::new(lights)));
+    if let Err(e) = scene.render_cameras(config.camera.as_deref()) {
+        eprintln!("渲染最终场景时出错: {}", e);
+        return;
+    }
 
     let duration = start.elapsed();
     eprintln!("渲染完成！总耗时: {:?}", duration);
+    if let Some(stats) = stats {
+        eprint!("{}", stats);
+    }
 }
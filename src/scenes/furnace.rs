@@ -0,0 +1,161 @@
+//! 白炉测试场景：反照率为1的物体置于均匀环境光中
+//!
+//! 能量守恒且不吸收的材质渲染结果应处处等于环境颜色；微表面等单次散射模型会损失部分能量而偏暗，
+//! 但任何材质都不应比环境更亮。新增材质时把它加入 `furnace_materials` 即可同时出现在场景和自检中。
+
+use crate::ray_tracing::acceleration::bvh::BvhNode;
+use crate::ray_tracing::geometry::hittable_list::HittableList;
+use crate::ray_tracing::geometry::sphere::Sphere;
+use crate::ray_tracing::materials::dielectric::Dielectric;
+use crate::ray_tracing::materials::hair::HairMaterial;
+use crate::ray_tracing::materials::lambertian::Lambertian;
+use crate::ray_tracing::materials::material::Material;
+use crate::ray_tracing::materials::metal::Metal;
+use crate::ray_tracing::materials::pbr::PbrMaterial;
+use crate::ray_tracing::materials::principled::PrincipledMaterial;
+use crate::ray_tracing::materials::rough_dielectric::RoughDielectric;
+use crate::ray_tracing::math::vec3::{Color, Point3, Vec3};
+use crate::ray_tracing::rendering::camera::{Camera, CameraBuilder};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// 白炉的环境颜色
+pub const FURNACE_ENVIRONMENT: f64 = 0.5;
+
+/// 参与白炉测试的材质
+pub struct FurnaceMaterial {
+    pub name: &'static str,
+    pub material: Arc<dyn Material>,
+    /// 是否应无损（结果等于环境颜色）；否则只要求不超过环境颜色
+    pub lossless: bool,
+    /// 自检视角下（只看到球体中央、入射角不超过约46°的部分）亮度与环境之比的参考值，
+    /// 无损材质为1，其余由 4096 spp 的渲染得到
+    pub reference: f64,
+}
+
+/// 所有反照率为1的材质
+pub fn furnace_materials() -> Vec<FurnaceMaterial> {
+    let white = Color::new(1.0, 1.0, 1.0);
+    vec![
+        FurnaceMaterial {
+            name: "Lambertian",
+            material: Arc::new(Lambertian::new(white)),
+            lossless: true,
+            reference: 1.0,
+        },
+        FurnaceMaterial {
+            name: "Metal(镜面)",
+            material: Arc::new(Metal::new(white, 0.0)),
+            lossless: true,
+            reference: 1.0,
+        },
+        FurnaceMaterial {
+            name: "Metal(模糊)",
+            material: Arc::new(Metal::new(white, 0.5)),
+            lossless: false,
+            reference: 1.0,
+        },
+        FurnaceMaterial {
+            name: "Dielectric",
+            material: Arc::new(Dielectric::new(1.5)),
+            lossless: true,
+            reference: 1.0,
+        },
+        FurnaceMaterial {
+            name: "RoughDielectric",
+            material: Arc::new(RoughDielectric::new(1.5, 0.5)),
+            lossless: false,
+            reference: 0.8775,
+        },
+        FurnaceMaterial {
+            name: "PbrMaterial",
+            material: Arc::new(PbrMaterial::new_constant(white, 0.4, 0.5)),
+            lossless: false,
+            reference: 1.0,
+        },
+        FurnaceMaterial {
+            name: "PrincipledMaterial",
+            material: Arc::new(
+                PrincipledMaterial::new_constant(white, 0.4, 0.3)
+                    .with_sheen(0.5, 0.0)
+                    .with_clearcoat(1.0, 0.8),
+            ),
+            lossless: false,
+            reference: 0.9972,
+        },
+        FurnaceMaterial {
+            name: "HairMaterial",
+            material: Arc::new(HairMaterial::new(white, 0.3, 0.2)),
+            lossless: true,
+            reference: 1.0,
+        },
+    ]
+}
+
+/// 白炉场景配置
+pub struct FurnaceConfig {
+    pub image_width: i32,
+    pub samples_per_pixel: i32,
+    pub max_depth: i32,
+    pub output_filename: String,
+}
+
+impl Default for FurnaceConfig {
+    fn default() -> Self {
+        Self {
+            image_width: 800,
+            samples_per_pixel: 256,
+            max_depth: 50,
+            output_filename: "furnace.png".to_string(),
+        }
+    }
+}
+
+/// 白炉相机：均匀环境光背景，不使用光源采样
+pub fn furnace_camera(config: &FurnaceConfig) -> CameraBuilder {
+    Camera::builder()
+        .image_width(config.image_width)
+        .samples_per_pixel(config.samples_per_pixel)
+        .max_depth(config.max_depth)
+        .background_color(Color::repeat(FURNACE_ENVIRONMENT))
+        .vup(Vec3::new(0.0, 1.0, 0.0))
+        .defocus_angle(0.0)
+        .output_filename(config.output_filename.clone())
+}
+
+/// 渲染白炉场景：所有材质的球体排成一行，与背景融为一体的球体是无损的
+pub fn render_furnace(config: FurnaceConfig) {
+    let materials = furnace_materials();
+    let spacing = 2.5;
+    let half_width = (materials.len() - 1) as f64 * spacing / 2.0;
+
+    let mut world = HittableList::new();
+    for (i, entry) in materials.iter().enumerate() {
+        let center = Point3::new(i as f64 * spacing - half_width, 0.0, 0.0);
+        world.add(Arc::new(Sphere::new(center, 1.0, entry.material.clone())));
+    }
+    let world = BvhNode::new(&world);
+
+    let camera = furnace_camera(&config)
+        .aspect_ratio(3.0)
+        .vfov(20.0)
+        .lookfrom(Point3::new(0.0, 0.0, 15.0))
+        .lookat(Point3::new(0.0, 0.0, 0.0))
+        .build();
+
+    let start = Instant::now();
+    eprintln!("开始渲染白炉场景（从左到右）:");
+    for entry in &materials {
+        eprintln!(
+            "  {}{}",
+            entry.name,
+            if entry.lossless {
+                ""
+            } else {
+                "（允许能量损失）"
+            }
+        );
+    }
+    camera.render_frame(&world, None);
+    eprintln!("渲染完成！总耗时: {:?}", start.elapsed());
+}
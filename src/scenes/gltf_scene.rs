@@ -0,0 +1,303 @@
+//! 渲染导入的 glTF 场景：使用文件中的第一个相机，没有相机时自动从包围盒前方取景
+//!
+//! 也可以把场景中某个网格的光照烘焙为光照贴图，或监视场景文件并在修改后自动重新渲染。
+//! 低面数的网格可以在导入时细分，细分级数固定或按网格在屏幕上的大小自适应选择。
+
+use crate::ray_tracing::geometry::subdivision::{ScreenSizeTarget, Subdivision};
+use crate::ray_tracing::math::vec3::{Color, Point3, Vec3};
+use crate::ray_tracing::rendering::bake::{BakeSettings, bake_lightmap};
+use crate::ray_tracing::rendering::camera::{Camera, CameraBuilder};
+use crate::ray_tracing::rendering::framebuffer::FrameBuffer;
+use crate::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use crate::ray_tracing::rendering::stats::RenderStats;
+use crate::ray_tracing::rendering::wireframe::BoundsOverlay;
+use crate::ray_tracing::scene::gltf::{GltfImport, GltfOptions, load_gltf, load_gltf_with};
+use crate::ray_tracing::scene::watch::SceneWatcher;
+use crate::ray_tracing::scene::world::ObjectId;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 监视模式下检查文件变化的间隔
+const WATCH_INTERVAL: Duration = Duration::from_millis(300);
+
+/// glTF 场景渲染配置
+pub struct GltfSceneConfig {
+    pub image_width: i32,
+    pub samples_per_pixel: i32,
+    pub max_depth: i32,
+    pub output_filename: String,
+    pub bounds_overlay: BoundsOverlay,
+    pub transparent_background: bool,
+    /// 渲染结束后输出按材质统计的着色开销报告
+    pub stats: bool,
+    /// 网格的 Loop 细分级数（自适应细分时为最大级数）
+    pub subdivision: u32,
+    /// 自适应细分：细分到网格的边投影到屏幕上不超过该像素数
+    pub subdivision_edge_pixels: Option<f64>,
+}
+
+impl Default for GltfSceneConfig {
+    fn default() -> Self {
+        Self {
+            image_width: 800,
+            samples_per_pixel: 256,
+            max_depth: 30,
+            output_filename: "gltf_scene.png".to_string(),
+            bounds_overlay: BoundsOverlay::Off,
+            transparent_background: false,
+            stats: false,
+            subdivision: 0,
+            subdivision_edge_pixels: None,
+        }
+    }
+}
+
+/// 导入场景的相机：优先使用文件中的第一个相机，否则沿 -Z 方向自动取景整个场景
+pub fn gltf_camera(import: &GltfImport, config: &GltfSceneConfig) -> CameraBuilder {
+    let builder = match import.cameras.first() {
+        Some(camera) => camera.builder(),
+        None => Camera::builder()
+            .aspect_ratio(16.0 / 9.0)
+            .vfov(40.0)
+            .lookfrom(Point3::new(0.0, 0.0, 1.0))
+            .lookat(Point3::origin())
+            .vup(Vec3::new(0.0, 1.0, 0.0))
+            .frame_scene(&import.scene, 0.0),
+    };
+
+    // 没有光源的场景用灰色环境光照明，否则只靠场景中的光源
+    let background = if import.scene.light_sampler().is_some() {
+        Color::zeros()
+    } else {
+        Color::repeat(0.7)
+    };
+    builder
+        .image_width(config.image_width)
+        .samples_per_pixel(config.samples_per_pixel)
+        .max_depth(config.max_depth)
+        .background_color(background)
+        .defocus_angle(0.0)
+        .output_filename(config.output_filename.clone())
+        .bounds_overlay(config.bounds_overlay)
+        .transparent_background(config.transparent_background)
+}
+
+/// 按配置得到导入选项：自适应细分需要知道相机位置，先不细分地导入一次确定相机
+fn import_options(path: &Path, config: &GltfSceneConfig) -> io::Result<GltfOptions> {
+    let subdivision = match config.subdivision_edge_pixels {
+        None => Subdivision::Levels(config.subdivision),
+        Some(max_edge_pixels) => {
+            let camera = gltf_camera(&load_gltf(path)?, config).build();
+            Subdivision::Adaptive(ScreenSizeTarget {
+                lookfrom: camera.lookfrom(),
+                vfov: camera.vfov(),
+                image_height: camera.image_height().max(1) as u32,
+                max_edge_pixels,
+                max_levels: config.subdivision,
+            })
+        }
+    };
+    Ok(GltfOptions {
+        subdivision,
+        ..GltfOptions::default()
+    })
+}
+
+/// 导入并渲染 glTF 文件，导入时的警告输出到标准错误
+pub fn render_gltf_scene(path: impl AsRef<Path>, config: GltfSceneConfig) -> io::Result<()> {
+    let path = path.as_ref();
+    let import = load_gltf_with(path, import_options(path, &config)?)?;
+    for warning in &import.warnings {
+        eprintln!("警告: {}", warning);
+    }
+    eprintln!(
+        "已导入 {}: {} 个物体, {} 个光源, {} 个相机",
+        path.display(),
+        import.scene.len(),
+        import.scene.lights().count(),
+        import.cameras.len()
+    );
+    if import.scene.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: 场景中没有可渲染的网格", path.display()),
+        ));
+    }
+
+    let stats = config.stats.then(|| Arc::new(RenderStats::new()));
+    let mut builder = gltf_camera(&import, &config);
+    if let Some(stats) = &stats {
+        builder = builder.stats(stats.clone());
+    }
+    let camera = builder.build();
+
+    let start = Instant::now();
+    eprintln!(
+        "开始渲染 glTF 场景... 采样数: {}, 反射深度: {}",
+        config.samples_per_pixel, config.max_depth
+    );
+    let world = import.scene.build_world();
+    camera.render_frame(world.as_ref(), import.scene.light_sampler());
+    eprintln!("渲染完成！总耗时: {:?}", start.elapsed());
+    if let Some(stats) = stats {
+        eprint!("{}", stats);
+    }
+    Ok(())
+}
+
+/// 导入 glTF 文件并烘焙第 object 个物体（按导入顺序）的光照贴图
+pub fn bake_gltf_lightmap(
+    path: impl AsRef<Path>,
+    object: usize,
+    config: &GltfSceneConfig,
+    settings: &BakeSettings,
+) -> io::Result<()> {
+    let path = path.as_ref();
+    let import = load_gltf_with(path, import_options(path, config)?)?;
+    for warning in &import.warnings {
+        eprintln!("警告: {}", warning);
+    }
+    let target = import.scene.get(ObjectId(object)).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "{}: 物体{}不存在（共 {} 个物体）",
+                path.display(),
+                object,
+                import.scene.len()
+            ),
+        )
+    })?;
+
+    let camera = gltf_camera(&import, config).build();
+    let world = import.scene.build_world();
+    let start = Instant::now();
+    eprintln!(
+        "开始烘焙物体{}的光照贴图: {}x{}, 每纹素采样数: {}, 模式: {:?}",
+        object, settings.width, settings.height, settings.samples_per_texel, settings.mode
+    );
+    let lightmap = bake_lightmap(
+        &camera,
+        target.as_ref(),
+        world.as_ref(),
+        import.scene.light_sampler(),
+        settings,
+    )
+    .ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("物体{}没有可烘焙的纹理坐标", object),
+        )
+    })?;
+
+    let format = OutputFormat::from_filename(&config.output_filename);
+    save_framebuffer(&lightmap.image, &config.output_filename, format)?;
+    eprintln!(
+        "光照贴图已保存为 {}（覆盖 {} 个纹素），耗时: {:?}",
+        config.output_filename,
+        lightmap.covered_texels,
+        start.elapsed()
+    );
+    Ok(())
+}
+
+/// 监视 glTF 文件，每次修改后重新加载并重新开始渐进渲染（不会返回，按 Ctrl-C 退出）
+///
+/// 每一轮的采样数是上一轮的4倍（1, 4, 16, ...），累计到 `samples_per_pixel` 为止，
+/// 每轮结束后保存当前的平均结果，用能自动刷新的图像查看器打开输出文件即可预览。
+/// 每轮之间检查文件变化，因此修改后最多等待当前这一轮结束。
+pub fn watch_gltf_scene(path: impl AsRef<Path>, config: GltfSceneConfig) -> io::Result<()> {
+    let path = path.as_ref();
+    let mut watcher = SceneWatcher::new(path, import_options(path, &config)?)?;
+    eprintln!(
+        "正在监视 {}，修改后自动重新渲染（Ctrl-C 退出）",
+        path.display()
+    );
+    let format = OutputFormat::from_filename(&config.output_filename);
+
+    loop {
+        let import = watcher.import();
+        for warning in &import.warnings {
+            eprintln!("警告: {}", warning);
+        }
+        let world = import.scene.build_world();
+        let lights = import.scene.light_sampler();
+        let builder = gltf_camera(import, &config);
+
+        let mut accumulated: Option<FrameBuffer> = None;
+        let mut total_spp = 0;
+        let mut pass = 0;
+        let start = Instant::now();
+        let changed = loop {
+            // 采样数取平方数，与相机的分层采样一致
+            let remaining = config.samples_per_pixel - total_spp;
+            if remaining <= 0 {
+                break false;
+            }
+            let sqrt_spp = (1 << pass).min(((remaining as f64).sqrt() as i32).max(1));
+            let pass_spp = sqrt_spp * sqrt_spp;
+            let camera = builder
+                .clone()
+                .samples_per_pixel(pass_spp)
+                .seed(pass as u64)
+                .build();
+            let frame = camera.render_to_buffer(world.as_ref(), lights.clone());
+            accumulated = Some(match accumulated {
+                Some(previous) => blend(&previous, total_spp, &frame, pass_spp),
+                None => frame,
+            });
+            total_spp += pass_spp;
+            pass += 1;
+
+            if let Some(image) = &accumulated {
+                save_framebuffer(image, &config.output_filename, format)?;
+            }
+            eprintln!(
+                "已保存 {}（{} spp，{:?}）",
+                config.output_filename,
+                total_spp,
+                start.elapsed()
+            );
+            if poll(&mut watcher) {
+                break true;
+            }
+        };
+
+        // 渲染完成后等待文件变化
+        if !changed {
+            while !poll(&mut watcher) {
+                std::thread::sleep(WATCH_INTERVAL);
+            }
+        }
+    }
+}
+
+/// 检查文件变化，返回是否需要重新渲染；加载失败时只输出错误，继续使用上次的场景
+fn poll(watcher: &mut SceneWatcher) -> bool {
+    match watcher.poll() {
+        Ok(Some(change)) => {
+            eprintln!("检测到文件变化（{:?}），重新开始渲染", change);
+            true
+        }
+        Ok(None) => false,
+        Err(e) => {
+            eprintln!("重新加载失败，保留上次的场景: {}", e);
+            false
+        }
+    }
+}
+
+/// 按采样数加权平均两帧
+fn blend(a: &FrameBuffer, a_spp: i32, b: &FrameBuffer, b_spp: i32) -> FrameBuffer {
+    let total = (a_spp + b_spp) as f64;
+    let (wa, wb) = (a_spp as f64 / total, b_spp as f64 / total);
+    let mut out = a.clone();
+    for y in 0..a.height() {
+        for x in 0..a.width() {
+            out.set(x, y, a.get(x, y) * wa + b.get(x, y) * wb);
+        }
+    }
+    out
+}
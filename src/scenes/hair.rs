@@ -0,0 +1,169 @@
+//! 毛球场景：球体表面长出数万根贝塞尔曲线毛发，测试曲线求交和曲线集合的BVH
+
+use crate::ray_tracing::acceleration::bvh::BvhNode;
+use crate::ray_tracing::geometry::curve::{Curve, CurveType};
+use crate::ray_tracing::geometry::curve_set::CurveSet;
+use crate::ray_tracing::geometry::hittable::Hittable;
+use crate::ray_tracing::geometry::hittable_list::HittableList;
+use crate::ray_tracing::geometry::quad::Quad;
+use crate::ray_tracing::geometry::sphere::Sphere;
+use crate::ray_tracing::materials::diffuse_light::DiffuseLight;
+use crate::ray_tracing::materials::hair::HairMaterial;
+use crate::ray_tracing::materials::lambertian::Lambertian;
+use crate::ray_tracing::materials::material::Material;
+use crate::ray_tracing::math::onb::ONB;
+use crate::ray_tracing::math::vec3::{Color, Point3, Vec3, Vec3Ext};
+use crate::ray_tracing::rendering::camera::Camera;
+use crate::ray_tracing::rendering::stats::RenderStats;
+use crate::ray_tracing::rendering::wireframe::BoundsOverlay;
+use crate::ray_tracing::utils::random::{random_double, with_seeded_stream};
+use std::f64::consts::PI;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// 毛发生成使用的随机种子，保证每次渲染的毛发形状相同
+const STRAND_SEED: u64 = 0x4841_4952;
+
+/// 毛球场景配置
+pub struct HairSceneConfig {
+    pub image_width: i32,
+    pub samples_per_pixel: i32,
+    pub max_depth: i32,
+    pub output_filename: String,
+    /// 毛发根数
+    pub strands: usize,
+    /// 毛发横截面形状
+    pub curve_type: CurveType,
+    pub bounds_overlay: BoundsOverlay,
+    pub transparent_background: bool,
+    /// 渲染结束后输出按材质统计的着色开销报告
+    pub stats: bool,
+}
+
+impl Default for HairSceneConfig {
+    fn default() -> Self {
+        Self {
+            image_width: 600,
+            samples_per_pixel: 64,
+            max_depth: 12,
+            output_filename: "hair.png".to_string(),
+            strands: 30000,
+            curve_type: CurveType::Cylinder,
+            bounds_overlay: BoundsOverlay::Off,
+            transparent_background: false,
+            stats: false,
+        }
+    }
+}
+
+/// 在球面上均匀生长毛发：每根毛发沿法线伸出后在重力和随机扰动下弯曲，从根部到末端逐渐变细
+pub fn fur_ball(
+    center: Point3,
+    radius: f64,
+    strands: usize,
+    length: f64,
+    width: f64,
+    kind: CurveType,
+    mat: Arc<dyn Material>,
+) -> CurveSet {
+    let curves = with_seeded_stream(STRAND_SEED, || {
+        (0..strands)
+            .map(|_| {
+                let normal = Vec3::random_unit_vector();
+                let uvw = ONB::new(&normal);
+                let root = center + normal * radius;
+                let length = length * (0.7 + 0.6 * random_double());
+                // 毛发的弯曲：一个随机的侧向方向加上向下的重力
+                let phi = 2.0 * PI * random_double();
+                let side = uvw.local_to_world(&Vec3::new(phi.cos(), phi.sin(), 0.0));
+                let droop = Vec3::new(0.0, -0.5, 0.0);
+                let bend = (side * 0.4 + droop) * length;
+
+                let cp = [
+                    root,
+                    root + normal * (length / 3.0),
+                    root + normal * (2.0 * length / 3.0) + bend * 0.3,
+                    root + normal * length + bend,
+                ];
+                Curve::new(cp, width, width * 0.2, kind, mat.clone())
+            })
+            .collect()
+    });
+    CurveSet::new(curves)
+}
+
+/// 渲染毛球场景
+pub fn render_hair_scene(config: HairSceneConfig) {
+    let mut world = HittableList::new();
+
+    let ground = Arc::new(Lambertian::new(Color::new(0.45, 0.45, 0.5)));
+    world.add(Arc::new(Quad::new(
+        Point3::new(-10.0, -1.0, -10.0),
+        Vec3::new(20.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 20.0),
+        ground,
+    )));
+
+    let skin = Arc::new(Lambertian::new(Color::new(0.35, 0.2, 0.1)));
+    world.add(Arc::new(Sphere::new(Point3::origin(), 0.6, skin)));
+
+    let fur: Arc<dyn Material> =
+        Arc::new(HairMaterial::new(Color::new(0.75, 0.5, 0.25), 0.2, 0.15));
+    let build_start = Instant::now();
+    let hair = fur_ball(
+        Point3::origin(),
+        0.6,
+        config.strands,
+        0.35,
+        0.012,
+        config.curve_type,
+        fur,
+    );
+    eprintln!(
+        "已生成 {} 根毛发（{:?}），构建BVH耗时: {:?}",
+        hair.len(),
+        config.curve_type,
+        build_start.elapsed()
+    );
+    world.add(Arc::new(hair));
+
+    let light: Arc<dyn Hittable> = Arc::new(Quad::new(
+        Point3::new(-1.5, 3.0, -0.5),
+        Vec3::new(3.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 2.0),
+        Arc::new(DiffuseLight::new_color(Color::new(6.0, 6.0, 6.0))),
+    ));
+    world.add(light.clone());
+    let world = BvhNode::new(&world);
+
+    let stats = config.stats.then(|| Arc::new(RenderStats::new()));
+    let mut builder = Camera::builder()
+        .aspect_ratio(1.0)
+        .image_width(config.image_width)
+        .samples_per_pixel(config.samples_per_pixel)
+        .max_depth(config.max_depth)
+        .background_color(Color::new(0.2, 0.25, 0.35))
+        .vfov(30.0)
+        .lookfrom(Point3::new(0.0, 1.0, 4.5))
+        .lookat(Point3::new(0.0, -0.1, 0.0))
+        .vup(Vec3::new(0.0, 1.0, 0.0))
+        .defocus_angle(0.0)
+        .output_filename(config.output_filename.clone())
+        .bounds_overlay(config.bounds_overlay)
+        .transparent_background(config.transparent_background);
+    if let Some(stats) = &stats {
+        builder = builder.stats(stats.clone());
+    }
+    let camera = builder.build();
+
+    let start = Instant::now();
+    eprintln!(
+        "开始渲染毛球场景... 采样数: {}, 反射深度: {}",
+        config.samples_per_pixel, config.max_depth
+    );
+    camera.render_frame(&world, Some(light));
+    eprintln!("渲染完成！总耗时: {:?}", start.elapsed());
+    if let Some(stats) = stats {
+        eprint!("{}", stats);
+    }
+}
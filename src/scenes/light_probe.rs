@@ -0,0 +1,72 @@
+//! 在示例场景中比较候选光源位置的直接光照贡献，输出并排的热度图
+
+use crate::ray_tracing::math::vec3::Point3;
+use crate::ray_tracing::rendering::light_probe::{
+    LightContribution, LightProbeSettings, rank_positions,
+};
+use crate::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use crate::ray_tracing::utils::image_compare::side_by_side;
+use crate::scenes::animation::parse_point;
+use crate::scenes::ao_compare::{CompareScene, build_compare_scene};
+use std::io;
+
+/// 光源摆放预估配置
+pub struct LightProbeSceneConfig {
+    pub scene: CompareScene,
+    /// 候选光源位置
+    pub positions: Vec<Point3>,
+    /// 每幅热度图的宽度
+    pub image_width: i32,
+    pub settings: LightProbeSettings,
+    pub output_filename: String,
+}
+
+impl Default for LightProbeSceneConfig {
+    fn default() -> Self {
+        Self {
+            scene: CompareScene::Cornell,
+            positions: Vec::new(),
+            image_width: 300,
+            settings: LightProbeSettings::default(),
+            output_filename: "light_probe.png".to_string(),
+        }
+    }
+}
+
+/// 解析候选位置 `x,y,z`
+pub fn parse_position(s: &str) -> Result<Point3, String> {
+    parse_point(s).ok_or_else(|| format!("无效的光源位置 `{}`（格式: x,y,z）", s))
+}
+
+/// 构建场景并估计各候选位置的贡献，按平均贡献从大到小返回
+///
+/// 热度图按候选位置的输入顺序并排保存，使用相同的亮度刻度以便直接比较。
+pub fn light_probe_scene(config: &LightProbeSceneConfig) -> io::Result<Vec<LightContribution>> {
+    if config.positions.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "至少需要一个候选光源位置",
+        ));
+    }
+    let (world, _, camera) = build_compare_scene(config.scene, config.image_width);
+    let camera = camera.build();
+
+    let ranked = rank_positions(&camera, &world, &config.positions, &config.settings);
+    let scale = ranked
+        .iter()
+        .map(LightContribution::max)
+        .fold(0.0, f64::max);
+    let panels: Vec<_> = config
+        .positions
+        .iter()
+        .filter_map(|p| ranked.iter().find(|c| c.position == *p))
+        .map(|c| c.heatmap(Some(scale)))
+        .collect();
+    save_framebuffer(
+        &side_by_side(&panels.iter().collect::<Vec<_>>()),
+        &config.output_filename,
+        OutputFormat::Png8,
+    )?;
+    eprintln!("热度图已保存为 {}", config.output_filename);
+    Ok(ranked)
+}
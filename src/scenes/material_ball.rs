@@ -0,0 +1,336 @@
+//! 材质测试球（shader ball）场景：在相同的影棚布光下渲染任意材质，便于开发新材质时对比
+//!
+//! 测试球是一个开了环形窗口的空心球壳，窗口内露出中性灰的内芯，可以同时看到材质在凸面、
+//! 凹面和薄边上的表现。球放在灰色底座上，背后是棋盘格的地面和背景板（用于观察反射和折射的扭曲），
+//! 由主光、补光和轮廓光三盏面光源照明。除测试材质外场景完全固定，不同材质的结果可以直接比较。
+
+use crate::ray_tracing::acceleration::bvh::BvhNode;
+use crate::ray_tracing::geometry::hittable::Hittable;
+use crate::ray_tracing::geometry::hittable_list::HittableList;
+use crate::ray_tracing::geometry::quad::{Quad, box_new};
+use crate::ray_tracing::geometry::sdf::{DistanceField, SdfObject};
+use crate::ray_tracing::geometry::sphere::Sphere;
+use crate::ray_tracing::materials::clear_coat::ClearCoat;
+use crate::ray_tracing::materials::diffuse_light::DiffuseLight;
+use crate::ray_tracing::materials::lambertian::Lambertian;
+use crate::ray_tracing::materials::material::{Material, NoMaterial};
+use crate::ray_tracing::materials::metal::Metal;
+use crate::ray_tracing::materials::principled::PrincipledMaterial;
+use crate::ray_tracing::materials::rough_dielectric::RoughDielectric;
+use crate::ray_tracing::materials::texture::checker::CheckerTexture;
+use crate::ray_tracing::materials::texture::noise::NoiseTexture;
+use crate::ray_tracing::math::aabb::Aabb;
+use crate::ray_tracing::math::vec3::{Color, Point3, Vec3};
+use crate::ray_tracing::rendering::camera::{Camera, CameraBuilder};
+use crate::ray_tracing::rendering::framebuffer::FrameBuffer;
+use crate::ray_tracing::utils::image_compare::{contact_sheet, side_by_side};
+use std::f64::consts::PI;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// 测试球球心和半径
+const BALL_CENTER: Point3 = Point3::new(0.0, 1.3, 0.0);
+const BALL_RADIUS: f64 = 1.0;
+/// 球壳厚度（相对半径）
+const SHELL_THICKNESS: f64 = 0.1;
+/// 内芯半径（相对半径）
+const CORE_RADIUS: f64 = 0.8;
+
+/// 测试球场景的渲染参数
+#[derive(Debug, Clone)]
+pub struct MaterialBallConfig {
+    pub image_width: i32,
+    pub samples_per_pixel: i32,
+    pub max_depth: i32,
+    /// 随机种子，固定后不同材质的噪声图案相同，差异只来自材质
+    pub seed: u64,
+}
+
+impl Default for MaterialBallConfig {
+    fn default() -> Self {
+        Self {
+            image_width: 300,
+            samples_per_pixel: 256,
+            max_depth: 16,
+            seed: 1,
+        }
+    }
+}
+
+/// 开了环形窗口的空心球壳
+#[derive(Debug, Clone, Copy)]
+struct ShaderBallShell {
+    center: Point3,
+    radius: f64,
+}
+
+impl DistanceField for ShaderBallShell {
+    fn distance(&self, p: &Point3) -> f64 {
+        let local = (p - self.center) / self.radius;
+        let r = local.norm();
+        let shell = (r - 1.0).max(1.0 - SHELL_THICKNESS - r);
+        // 窗口：朝向相机、略高于赤道的一圈水平带，并向右上斜切
+        let band = (local.y - 0.05 - 0.25 * local.x).abs() - 0.22;
+        let window = band.max(0.15 - local.z);
+        shell.max(-window) * self.radius
+    }
+
+    fn bounds(&self) -> Aabb {
+        let r = Vec3::repeat(self.radius * 1.001);
+        Aabb::new_point(self.center - r, self.center + r)
+    }
+
+    /// 球面经纬度作为纹理坐标，带纹理的材质在球上按经纬度展开
+    fn coloring(&self, p: &Point3) -> (f64, f64) {
+        let d = (p - self.center).normalize();
+        let theta = (-d.y).acos();
+        let phi = (-d.z).atan2(d.x) + PI;
+        (phi / (2.0 * PI), theta / PI)
+    }
+}
+
+/// 构建测试球场景，返回 (世界, 光源列表)
+pub fn build_material_ball(material: Arc<dyn Material>) -> (HittableList, HittableList) {
+    let mut world = HittableList::new();
+    let neutral: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.18, 0.18, 0.18)));
+    let backdrop: Arc<dyn Material> = Arc::new(Lambertian::new_texture(Arc::new(
+        CheckerTexture::new_colors(
+            0.5,
+            Color::new(0.55, 0.55, 0.55),
+            Color::new(0.35, 0.35, 0.35),
+        ),
+    )));
+
+    // 地面和背景板
+    world.add(Arc::new(Quad::new(
+        Point3::new(-8.0, 0.0, -3.0),
+        Vec3::new(0.0, 0.0, 10.0),
+        Vec3::new(16.0, 0.0, 0.0),
+        backdrop.clone(),
+    )));
+    world.add(Arc::new(Quad::new(
+        Point3::new(-8.0, 0.0, -3.0),
+        Vec3::new(16.0, 0.0, 0.0),
+        Vec3::new(0.0, 8.0, 0.0),
+        backdrop,
+    )));
+
+    // 底座、内芯和测试球壳
+    world.add(Arc::new(box_new(
+        Point3::new(-0.4, 0.0, -0.4),
+        Point3::new(0.4, BALL_CENTER.y - BALL_RADIUS * 0.92, 0.4),
+        Arc::new(Lambertian::new(Color::new(0.4, 0.4, 0.4))),
+    )));
+    world.add(Arc::new(Sphere::new(
+        BALL_CENTER,
+        BALL_RADIUS * CORE_RADIUS,
+        neutral,
+    )));
+    world.add(Arc::new(SdfObject::new(
+        Arc::new(ShaderBallShell {
+            center: BALL_CENTER,
+            radius: BALL_RADIUS,
+        }),
+        material,
+    )));
+
+    // 主光（左前上方）、补光（右前方，较暗）和轮廓光（后上方）
+    let mut lights = HittableList::new();
+    for (q, u, v, radiance) in [
+        (
+            Point3::new(-3.5, 4.5, 1.5),
+            Vec3::new(1.6, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.6),
+            Color::new(9.0, 8.6, 8.0),
+        ),
+        (
+            Point3::new(2.5, 3.0, 2.5),
+            Vec3::new(1.2, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.2),
+            Color::new(2.6, 2.8, 3.2),
+        ),
+        (
+            Point3::new(-0.8, 5.0, -2.4),
+            Vec3::new(1.6, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 0.6),
+            Color::new(8.0, 8.0, 8.0),
+        ),
+    ] {
+        world.add(Arc::new(Quad::new(
+            q,
+            u,
+            v,
+            Arc::new(DiffuseLight::new_color(radiance)),
+        )));
+        lights.add(Arc::new(Quad::new(q, u, v, Arc::new(NoMaterial))));
+    }
+
+    (world, lights)
+}
+
+/// 测试球场景的固定相机
+pub fn material_ball_camera(config: &MaterialBallConfig) -> CameraBuilder {
+    Camera::builder()
+        .aspect_ratio(1.0)
+        .image_width(config.image_width)
+        .samples_per_pixel(config.samples_per_pixel)
+        .max_depth(config.max_depth)
+        .background_color(Color::new(0.03, 0.03, 0.03))
+        .vfov(32.0)
+        .lookfrom(Point3::new(0.0, 2.4, 6.0))
+        .lookat(Point3::new(0.0, 1.15, 0.0))
+        .seed(config.seed)
+}
+
+/// 渲染材质的测试球
+pub fn render_material_ball(
+    material: Arc<dyn Material>,
+    config: &MaterialBallConfig,
+) -> FrameBuffer {
+    let (world, lights) = build_material_ball(material);
+    let lights: Arc<dyn Hittable> = Arc::new(lights);
+    material_ball_camera(config)
+        .build()
+        .render_to_buffer(&BvhNode::new(&world), Some(lights))
+}
+
+/// 依次渲染多个材质的测试球并从左到右拼接
+pub fn material_ball_sheet(
+    materials: &[Arc<dyn Material>],
+    config: &MaterialBallConfig,
+) -> FrameBuffer {
+    let panels: Vec<FrameBuffer> = materials
+        .iter()
+        .map(|material| render_material_ball(material.clone(), config))
+        .collect();
+    side_by_side(&panels.iter().collect::<Vec<_>>())
+}
+
+/// 参数扫描：对两个参数的每种组合渲染一个测试球，排成 rows.len() 行 × columns.len() 列的印样
+///
+/// make(行参数, 列参数) 创建每个格子的材质，纹理参数可以通过以该纹理为颜色的材质扫描。
+/// 行从上到下、列从左到右依次对应 rows 和 columns 中的取值。
+pub fn material_ball_sweep<F>(
+    rows: &[f64],
+    columns: &[f64],
+    make: F,
+    config: &MaterialBallConfig,
+) -> FrameBuffer
+where
+    F: Fn(f64, f64) -> Arc<dyn Material>,
+{
+    let panels: Vec<FrameBuffer> = rows
+        .iter()
+        .flat_map(|&row| columns.iter().map(move |&column| (row, column)))
+        .map(|(row, column)| render_material_ball(make(row, column), config))
+        .collect();
+    contact_sheet(&panels.iter().collect::<Vec<_>>(), columns.len(), 4)
+}
+
+/// 区间 [start, end] 上等间距的 count 个取值（count 为 1 时取 start），用作扫描的参数
+pub fn sweep_values(start: f64, end: f64, count: usize) -> Vec<f64> {
+    if count <= 1 {
+        return vec![start; count];
+    }
+    (0..count)
+        .map(|i| start + (end - start) * i as f64 / (count - 1) as f64)
+        .collect()
+}
+
+/// 预设的参数扫描
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MaterialSweep {
+    /// 金色金属上的清漆层：行为清漆折射率，列为金属模糊度
+    #[default]
+    Coat,
+    /// 粗糙玻璃：行为折射率，列为表面粗糙度
+    Glass,
+    /// 大理石噪声纹理：行为条纹频率，列为湍流层数
+    Noise,
+    /// 橙色原则化材质：行为金属度，列为粗糙度
+    Principled,
+}
+
+impl FromStr for MaterialSweep {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "coat" => Ok(Self::Coat),
+            "glass" => Ok(Self::Glass),
+            "noise" => Ok(Self::Noise),
+            _ => Err(format!(
+                "未知的参数扫描 `{}`（可选: coat, glass, noise）",
+                s
+            )),
+        }
+    }
+}
+
+impl MaterialSweep {
+    /// 行参数和列参数的名称
+    pub fn axis_names(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Coat => ("清漆折射率", "金属模糊度"),
+            Self::Glass => ("折射率", "粗糙度"),
+            Self::Noise => ("条纹频率", "湍流层数"),
+            Self::Principled => ("金属度", "粗糙度"),
+        }
+    }
+
+    /// 行数和列数分别为 rows、columns 时两个参数的取值
+    pub fn values(self, rows: usize, columns: usize) -> (Vec<f64>, Vec<f64>) {
+        match self {
+            Self::Coat => (
+                sweep_values(1.2, 2.0, rows),
+                sweep_values(0.0, 1.0, columns),
+            ),
+            Self::Glass => (
+                sweep_values(1.1, 2.4, rows),
+                sweep_values(0.0, 0.5, columns),
+            ),
+            Self::Noise => {
+                let depths = sweep_values(1.0, 7.0, columns);
+                (
+                    sweep_values(1.0, 8.0, rows),
+                    depths.into_iter().map(f64::round).collect(),
+                )
+            }
+            Self::Principled => (
+                sweep_values(0.0, 1.0, rows),
+                sweep_values(0.0, 1.0, columns),
+            ),
+        }
+    }
+
+    /// 参数组合 (row, column) 的材质
+    pub fn material(self, row: f64, column: f64) -> Arc<dyn Material> {
+        match self {
+            Self::Coat => {
+                let gold: Arc<dyn Material> =
+                    Arc::new(Metal::new(Color::new(0.9, 0.65, 0.25), column));
+                Arc::new(ClearCoat::new(gold, row, 0.0))
+            }
+            Self::Glass => Arc::new(RoughDielectric::new(row, column)),
+            Self::Noise => Arc::new(Lambertian::new_texture(Arc::new(
+                NoiseTexture::with_seed(7, row).with_turbulence_depth(column as i32),
+            ))),
+            Self::Principled => Arc::new(PrincipledMaterial::new_constant(
+                Color::new(0.9, 0.45, 0.1),
+                column,
+                row,
+            )),
+        }
+    }
+
+    /// 按预设渲染 rows × columns 的参数扫描印样
+    pub fn render(self, rows: usize, columns: usize, config: &MaterialBallConfig) -> FrameBuffer {
+        let (row_values, column_values) = self.values(rows, columns);
+        material_ball_sweep(
+            &row_values,
+            &column_values,
+            |row, column| self.material(row, column),
+            config,
+        )
+    }
+}
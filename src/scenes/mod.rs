@@ -1,2 +1,14 @@
+pub mod animation;
+pub mod ao_compare;
 pub mod cornell_box;
 pub mod final_scene;
+pub mod furnace;
+pub mod gltf_scene;
+pub mod hair;
+pub mod light_probe;
+pub mod material_ball;
+pub mod onb_debug;
+pub mod pdf_debug;
+pub mod point_cloud;
+pub mod probe_grid;
+pub mod render_farm;
@@ -0,0 +1,91 @@
+//! 正交基底调试：在示例场景中按交点处的 ONB 着色，检查翻转或退化的基底
+//!
+//! 输出 u | v | w | 检查 四联图，并统计主光线交点中各类基底问题的像素数。
+//! 修改 `ONB::new` 的构建方式后运行一次，检查视图应保持全绿，w 视图应与法线一致。
+
+use crate::ray_tracing::geometry::hittable::{HitRecord, Hittable};
+use crate::ray_tracing::math::interval::Interval;
+use crate::ray_tracing::math::onb::ONB;
+use crate::ray_tracing::rendering::camera::Camera;
+use crate::ray_tracing::rendering::integrator::{BasisIssue, BasisView, Integrator, basis_issue};
+use crate::ray_tracing::rendering::output::{OutputFormat, save_framebuffer};
+use crate::ray_tracing::utils::image_compare::side_by_side;
+use crate::scenes::ao_compare::{CompareScene, build_compare_scene};
+use std::io;
+
+/// 基底调试配置
+pub struct OnbDebugConfig {
+    pub scene: CompareScene,
+    /// 每幅视图的宽度
+    pub image_width: i32,
+    /// 每像素样本数（只用于抗锯齿）
+    pub samples_per_pixel: i32,
+    pub output_filename: String,
+}
+
+impl Default for OnbDebugConfig {
+    fn default() -> Self {
+        Self {
+            scene: CompareScene::Cornell,
+            image_width: 300,
+            samples_per_pixel: 4,
+            output_filename: "onb_debug.png".to_string(),
+        }
+    }
+}
+
+/// 统计每个像素中心的主光线交点处的基底问题，返回 (命中像素数, 各类问题的像素数)
+pub fn count_basis_issues(
+    camera: &Camera,
+    world: &dyn Hittable,
+) -> (usize, Vec<(BasisIssue, usize)>) {
+    let mut hits = 0;
+    let mut counts: Vec<(BasisIssue, usize)> = BasisIssue::ALL.iter().map(|&i| (i, 0)).collect();
+    for j in 0..camera.image_height() {
+        for i in 0..camera.image_width() {
+            let mut rec = HitRecord::default();
+            if !world.hit(
+                &camera.center_ray(i, j),
+                Interval::new(0.001, f64::INFINITY),
+                &mut rec,
+            ) {
+                continue;
+            }
+            hits += 1;
+            if let Some(issue) = basis_issue(&ONB::new(&rec.normal), &rec.normal)
+                && let Some(slot) = counts.iter_mut().find(|(i, _)| *i == issue)
+            {
+                slot.1 += 1;
+            }
+        }
+    }
+    (hits, counts)
+}
+
+/// 构建场景，保存四联调试图并返回问题统计
+pub fn onb_debug_scene(config: &OnbDebugConfig) -> io::Result<(usize, Vec<(BasisIssue, usize)>)> {
+    let (world, _, camera) = build_compare_scene(config.scene, config.image_width);
+    let camera = camera.samples_per_pixel(config.samples_per_pixel);
+
+    let panels: Vec<_> = [BasisView::U, BasisView::V, BasisView::W, BasisView::Check]
+        .into_iter()
+        .map(|view| {
+            camera
+                .clone()
+                .integrator(Integrator::Basis(view))
+                .build()
+                .render_to_buffer(&world, None)
+        })
+        .collect();
+    save_framebuffer(
+        &side_by_side(&panels.iter().collect::<Vec<_>>()),
+        &config.output_filename,
+        OutputFormat::Png8,
+    )?;
+    eprintln!(
+        "基底调试图已保存为 {}（u | v | w | 检查）",
+        config.output_filename
+    );
+
+    Ok(count_basis_issues(&camera.build(), &world))
+}
@@ -0,0 +1,74 @@
+//! 在示例场景的某个像素处检查材质的散射采样，输出 CSV 和经纬度密度图
+
+use crate::ray_tracing::geometry::hittable::Hittable;
+use crate::ray_tracing::rendering::pdf_debug::{PdfDebugConfig, PdfDebugReport, probe_pixel};
+use crate::scenes::ao_compare::{CompareScene, build_compare_scene};
+use std::io;
+use std::sync::Arc;
+
+/// 场景PDF调试配置
+pub struct PdfDebugSceneConfig {
+    pub scene: CompareScene,
+    /// 要检查的像素（相机图像坐标）
+    pub pixel: (i32, i32),
+    /// 相机图像宽度，决定像素坐标的范围
+    pub image_width: i32,
+    /// 是否与渲染一样混合光源采样；关闭时只检查材质自身的PDF
+    pub light_sampling: bool,
+    pub debug: PdfDebugConfig,
+    pub csv_filename: String,
+    pub image_filename: String,
+}
+
+impl Default for PdfDebugSceneConfig {
+    fn default() -> Self {
+        Self {
+            scene: CompareScene::Cornell,
+            pixel: (300, 300),
+            image_width: 600,
+            light_sampling: false,
+            debug: PdfDebugConfig::default(),
+            csv_filename: "pdf_debug.csv".to_string(),
+            image_filename: "pdf_debug.png".to_string(),
+        }
+    }
+}
+
+/// 构建场景并检查指定像素的首个交点，保存 CSV 和密度图
+pub fn pdf_debug_scene(config: &PdfDebugSceneConfig) -> io::Result<PdfDebugReport> {
+    let (world, lights, camera) = build_compare_scene(config.scene, config.image_width);
+    let camera = camera.build();
+    let (x, y) = config.pixel;
+    if x < 0 || y < 0 || x >= config.image_width || y >= camera.image_height() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "像素 ({}, {}) 超出图像范围 {}×{}",
+                x,
+                y,
+                config.image_width,
+                camera.image_height()
+            ),
+        ));
+    }
+
+    let lights: Option<Arc<dyn Hittable>> = config
+        .light_sampling
+        .then(|| Arc::new(lights) as Arc<dyn Hittable>);
+    let report =
+        probe_pixel(&camera, &world, lights, config.pixel, &config.debug).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("像素 ({}, {}) 的光线没有命中任何物体", x, y),
+            )
+        })?;
+
+    report.write_csv(&config.csv_filename)?;
+    eprintln!("样本已保存为 {}", config.csv_filename);
+    report.save_density_image(&config.image_filename, config.debug.image_width)?;
+    eprintln!(
+        "密度图已保存为 {}（样本直方图 | 采样PDF | scattering_pdf）",
+        config.image_filename
+    );
+    Ok(report)
+}
@@ -0,0 +1,112 @@
+//! 渲染点云文件：每个点绘制为带颜色的小圆盘或小球，在均匀环境光下从包围盒前方取景
+
+use crate::ray_tracing::geometry::hittable::Hittable;
+use crate::ray_tracing::geometry::point_cloud::{PointCloud, SplatShape};
+use crate::ray_tracing::math::vec3::{Color, Point3, Vec3};
+use crate::ray_tracing::rendering::camera::Camera;
+use crate::ray_tracing::rendering::stats::RenderStats;
+use crate::ray_tracing::rendering::wireframe::BoundsOverlay;
+use crate::ray_tracing::scene::point_data::load_points;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// 点云场景配置
+pub struct PointCloudSceneConfig {
+    pub image_width: i32,
+    pub samples_per_pixel: i32,
+    pub max_depth: i32,
+    pub output_filename: String,
+    /// 点的半径，None 时由点数和包围盒估计
+    pub radius: Option<f64>,
+    pub shape: SplatShape,
+    pub bounds_overlay: BoundsOverlay,
+    pub transparent_background: bool,
+    /// 渲染结束后输出按材质统计的着色开销报告
+    pub stats: bool,
+}
+
+impl Default for PointCloudSceneConfig {
+    fn default() -> Self {
+        Self {
+            image_width: 800,
+            samples_per_pixel: 64,
+            max_depth: 10,
+            output_filename: "point_cloud.png".to_string(),
+            radius: None,
+            shape: SplatShape::Disk,
+            bounds_overlay: BoundsOverlay::Off,
+            transparent_background: false,
+            stats: false,
+        }
+    }
+}
+
+/// 读取并渲染点云文件（`.xyz` 或 `.ply`）
+pub fn render_point_cloud(path: impl AsRef<Path>, config: PointCloudSceneConfig) -> io::Result<()> {
+    let path = path.as_ref();
+    let points = load_points(path)?;
+    if points.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: 文件中没有点", path.display()),
+        ));
+    }
+
+    let radius = config
+        .radius
+        .unwrap_or_else(|| PointCloud::suggested_radius(&points));
+    let with_normals = points.iter().filter(|p| p.normal.is_some()).count();
+    let build_start = Instant::now();
+    let cloud = PointCloud::lambertian(&points, radius, config.shape);
+    eprintln!(
+        "已读取 {}: {} 个点（{} 个带法线），半径 {:.4}，构建BVH耗时: {:?}",
+        path.display(),
+        cloud.len(),
+        with_normals,
+        radius,
+        build_start.elapsed()
+    );
+
+    // 沿 -Z 方向看向包围盒中心
+    let (center, extent) = cloud.bounding_box().map_or((Point3::origin(), 1.0), |b| {
+        let center = b.center();
+        let corner = Point3::new(b.x.max, b.y.max, b.z.max);
+        (center, (corner - center).norm().max(1e-3))
+    });
+    let vfov: f64 = 40.0;
+    let distance = extent / (vfov.to_radians() / 2.0).sin();
+
+    let stats = config.stats.then(|| Arc::new(RenderStats::new()));
+    let mut builder = Camera::builder()
+        .aspect_ratio(16.0 / 9.0)
+        .image_width(config.image_width)
+        .samples_per_pixel(config.samples_per_pixel)
+        .max_depth(config.max_depth)
+        .background_color(Color::repeat(0.7))
+        .vfov(vfov)
+        .lookfrom(center + Vec3::new(0.0, 0.0, distance))
+        .lookat(center)
+        .vup(Vec3::new(0.0, 1.0, 0.0))
+        .defocus_angle(0.0)
+        .output_filename(config.output_filename.clone())
+        .bounds_overlay(config.bounds_overlay)
+        .transparent_background(config.transparent_background);
+    if let Some(stats) = &stats {
+        builder = builder.stats(stats.clone());
+    }
+    let camera = builder.build();
+
+    let start = Instant::now();
+    eprintln!(
+        "开始渲染点云... 采样数: {}, 反射深度: {}",
+        config.samples_per_pixel, config.max_depth
+    );
+    camera.render_frame(&cloud, None);
+    eprintln!("渲染完成！总耗时: {:?}", start.elapsed());
+    if let Some(stats) = stats {
+        eprint!("{}", stats);
+    }
+    Ok(())
+}
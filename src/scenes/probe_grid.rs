@@ -0,0 +1,97 @@
+//! 在示例场景中烘焙辐照度探针网格，导出球谐系数供实时引擎使用
+
+use crate::ray_tracing::geometry::hittable::Hittable;
+use crate::ray_tracing::math::vec3::Point3;
+use crate::ray_tracing::rendering::probe_grid::{
+    ProbeFormat, ProbeGrid, ProbeGridSettings, bake_probe_grid,
+};
+use crate::scenes::animation::parse_point;
+use crate::scenes::ao_compare::{CompareScene, build_compare_scene};
+use std::io;
+use std::sync::Arc;
+
+/// 探针网格烘焙配置
+pub struct ProbeGridSceneConfig {
+    pub scene: CompareScene,
+    /// 网格范围，None 时使用场景的默认范围
+    pub bounds: Option<(Point3, Point3)>,
+    pub resolution: [u32; 3],
+    pub samples_per_probe: u32,
+    pub max_depth: i32,
+    pub seed: u64,
+    pub format: ProbeFormat,
+    pub output_filename: String,
+}
+
+impl Default for ProbeGridSceneConfig {
+    fn default() -> Self {
+        Self {
+            scene: CompareScene::Cornell,
+            bounds: None,
+            resolution: [4, 4, 4],
+            samples_per_probe: 1024,
+            max_depth: 20,
+            seed: 0,
+            format: ProbeFormat::Json,
+            output_filename: "probes.json".to_string(),
+        }
+    }
+}
+
+/// 场景的默认网格范围：康奈尔盒的内部；最终场景去掉地面和远处环境雾后的主要物体区域
+pub fn default_bounds(scene: CompareScene) -> (Point3, Point3) {
+    match scene {
+        CompareScene::Cornell => (Point3::new(1.0, 1.0, 1.0), Point3::new(554.0, 554.0, 554.0)),
+        CompareScene::Final => (
+            Point3::new(0.0, 110.0, 0.0),
+            Point3::new(560.0, 540.0, 560.0),
+        ),
+    }
+}
+
+/// 解析每轴探针数：`N`（三轴相同）或 `NX,NY,NZ`
+pub fn parse_resolution(s: &str) -> Result<[u32; 3], String> {
+    let values: Option<Vec<u32>> = s
+        .split(',')
+        .map(|part| part.trim().parse().ok().filter(|&n| n > 0))
+        .collect();
+    match values.as_deref() {
+        Some(&[n]) => Ok([n; 3]),
+        Some(&[x, y, z]) => Ok([x, y, z]),
+        _ => Err(format!("无效的探针数 `{}`（格式: N 或 NX,NY,NZ）", s)),
+    }
+}
+
+/// 解析网格的角点 `x,y,z`
+pub fn parse_corner(s: &str) -> Result<Point3, String> {
+    parse_point(s).ok_or_else(|| format!("无效的网格角点 `{}`（格式: x,y,z）", s))
+}
+
+/// 构建场景，烘焙探针网格并保存
+pub fn probe_grid_scene(config: &ProbeGridSceneConfig) -> io::Result<ProbeGrid> {
+    let (world, lights, camera) = build_compare_scene(config.scene, 100);
+    let camera = camera.max_depth(config.max_depth).seed(config.seed).build();
+    let (min, max) = config
+        .bounds
+        .unwrap_or_else(|| default_bounds(config.scene));
+    let settings = ProbeGridSettings::new(min, max)
+        .with_resolution(config.resolution)
+        .with_samples(config.samples_per_probe);
+
+    eprintln!(
+        "烘焙 {} 个探针（{}×{}×{}），每个探针 {} 个方向样本",
+        settings.probe_count(),
+        settings.resolution[0],
+        settings.resolution[1],
+        settings.resolution[2],
+        settings.samples_per_probe
+    );
+    let lights = (!lights.is_empty()).then(|| Arc::new(lights) as Arc<dyn Hittable>);
+    let grid = bake_probe_grid(&camera, &world, lights, &settings);
+    grid.save(&config.output_filename, config.format)?;
+    eprintln!(
+        "探针网格已保存为 {}（{}）",
+        config.output_filename, config.format
+    );
+    Ok(grid)
+}
@@ -0,0 +1,447 @@
+//! 分布式渲染任务清单：把动画按帧段和图像行带切分为任务，分发到几台机器上渲染后再拼合
+//!
+//! 不需要专门的渲染农场软件，只要求各机器能以相同的路径访问动画描述和输出目录（共享文件系统）：
+//!
+//! 1. `farm-export <动画描述> --frames-per-job N --bands B` 写出 JSON 任务清单。清单记录动画描述和
+//!    输出文件的绝对路径、覆盖的采样参数、图像尺寸，以及每个任务的帧段和行带。
+//! 2. 每台机器运行 `farm-worker <清单>`：依次认领尚未完成的任务（在清单旁的 `.claims` 目录中
+//!    原子地创建认领文件），渲染后继续认领下一个。`--job ID` 只运行指定的任务，便于交给已有的调度器。
+//! 3. `farm-merge <清单>` 把各行带拼合为完整的帧并删除行带文件，列出尚未完成的帧。
+//!
+//! 只按帧切分（B = 1）时任务直接写出最终的帧文件，不需要拼合。行带文件保存未经编码的线性颜色，
+//! 设置了种子时每个像素的样本与整帧渲染相同，拼合结果与单机渲染逐像素一致。
+//! 降噪需要整帧的辅助缓冲，分带渲染时不进行。
+//!
+//! 已存在的帧和行带文件视为完成，任务可以重复运行；认领后崩溃的任务需要删除 `.claims` 目录中
+//! 对应的文件才会被再次认领。各机器的 raytracer.toml 和命令行渲染参数应保持一致。
+
+use crate::ray_tracing::math::vec3::Color;
+use crate::ray_tracing::rendering::color_space::OutputColorSpace;
+use crate::ray_tracing::rendering::framebuffer::FrameBuffer;
+use crate::ray_tracing::rendering::tile_cache::Crop;
+use crate::ray_tracing::utils::config;
+use crate::ray_tracing::utils::json::Json;
+use crate::scenes::animation::{AnimationSpec, save_complete};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// 清单格式标识
+const MANIFEST_FORMAT: &str = "ray_tracing_rust/render-farm";
+/// 清单格式版本
+const MANIFEST_VERSION: u32 = 1;
+/// 行带文件的标识
+const BAND_MAGIC: &str = "RTBAND";
+
+/// 一个渲染任务：一段连续的帧中的同一行带
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FarmJob {
+    pub id: usize,
+    pub frames: Range<u32>,
+    /// 行带序号
+    pub band: u32,
+    /// 行带覆盖的图像行 [start, end)
+    pub rows: Range<u32>,
+}
+
+/// 任务清单
+#[derive(Debug, Clone, PartialEq)]
+pub struct FarmManifest {
+    /// 动画描述的路径
+    pub spec: PathBuf,
+    /// 帧文件名模板（已加上输出目录）
+    pub output: String,
+    pub samples_per_pixel: Option<i32>,
+    pub max_depth: Option<i32>,
+    pub image_width: u32,
+    pub image_height: u32,
+    /// 每帧的行带数
+    pub bands: u32,
+    pub frames: Range<u32>,
+    pub jobs: Vec<FarmJob>,
+}
+
+/// 拼合的结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergeSummary {
+    /// 本次拼合的帧
+    pub merged: Vec<u32>,
+    /// 此前已完成的帧数
+    pub complete: usize,
+    /// 尚未完成的帧及其缺少的行带
+    pub incomplete: Vec<(u32, Vec<u32>)>,
+}
+
+impl FarmManifest {
+    /// 为动画的 frames 范围规划任务：每个任务渲染最多 frames_per_job 帧中的一个行带
+    ///
+    /// spec_path 和帧文件名模板转换为绝对路径，其他机器上的工作进程按相同的路径读写。
+    pub fn plan(
+        spec_path: impl AsRef<Path>,
+        spec: &AnimationSpec,
+        frames: Range<u32>,
+        frames_per_job: u32,
+        bands: u32,
+    ) -> io::Result<Self> {
+        let (image_width, image_height) = spec.image_size()?;
+        let bands = bands.clamp(1, image_height.max(1));
+        let frames_per_job = frames_per_job.max(1);
+        let frames = frames.start.min(spec.frames)..frames.end.min(spec.frames);
+        let output = std::path::absolute(config::global().output_path(&spec.output))?
+            .to_string_lossy()
+            .into_owned();
+
+        let mut jobs = Vec::new();
+        let mut start = frames.start;
+        while start < frames.end {
+            let end = (start + frames_per_job).min(frames.end);
+            for band in 0..bands {
+                jobs.push(FarmJob {
+                    id: jobs.len(),
+                    frames: start..end,
+                    band,
+                    rows: band_rows(image_height, bands, band),
+                });
+            }
+            start = end;
+        }
+
+        Ok(Self {
+            spec: std::path::absolute(spec_path)?,
+            output,
+            samples_per_pixel: spec.samples_per_pixel,
+            max_depth: spec.max_depth,
+            image_width,
+            image_height,
+            bands,
+            frames,
+            jobs,
+        })
+    }
+
+    /// 序列化为 JSON
+    pub fn to_json(&self) -> String {
+        let number = |x: f64| Json::Number(x);
+        let range =
+            |r: &Range<u32>| Json::Array(vec![number(r.start as f64), number(r.end as f64)]);
+        let optional = |x: Option<i32>| x.map_or(Json::Null, |x| number(x as f64));
+        let jobs = self
+            .jobs
+            .iter()
+            .map(|job| {
+                Json::Object(vec![
+                    ("id".into(), number(job.id as f64)),
+                    ("frames".into(), range(&job.frames)),
+                    ("band".into(), number(job.band as f64)),
+                    ("rows".into(), range(&job.rows)),
+                ])
+            })
+            .collect();
+        let json = Json::Object(vec![
+            ("format".into(), Json::String(MANIFEST_FORMAT.into())),
+            ("version".into(), number(MANIFEST_VERSION as f64)),
+            (
+                "spec".into(),
+                Json::String(self.spec.to_string_lossy().into_owned()),
+            ),
+            ("output".into(), Json::String(self.output.clone())),
+            ("samples_per_pixel".into(), optional(self.samples_per_pixel)),
+            ("max_depth".into(), optional(self.max_depth)),
+            ("image_width".into(), number(self.image_width as f64)),
+            ("image_height".into(), number(self.image_height as f64)),
+            ("bands".into(), number(self.bands as f64)),
+            ("frames".into(), range(&self.frames)),
+            ("jobs".into(), Json::Array(jobs)),
+        ]);
+        format!("{:#}\n", json)
+    }
+
+    /// 从 JSON 文本解析，source 用于错误信息
+    pub fn parse(text: &str, source: &str) -> io::Result<Self> {
+        let json = Json::parse(text, source)?;
+        let error = |message: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", source, message),
+            )
+        };
+        if json.get("format").and_then(Json::as_str) != Some(MANIFEST_FORMAT) {
+            return Err(error("不是渲染任务清单"));
+        }
+        let version = json.usize_field("version").unwrap_or(0);
+        if version != MANIFEST_VERSION as usize {
+            return Err(error(&format!("不支持的清单版本 {}", version)));
+        }
+        let range = |value: &Json| -> Option<Range<u32>> {
+            let [start, end] = [value.as_array()?.first()?, value.as_array()?.get(1)?]
+                .map(|x| x.as_usize().and_then(|x| u32::try_from(x).ok()));
+            Some(start?..end?)
+        };
+        let optional = |key: &str| json.usize_field(key).map(|x| x as i32);
+        let field = |key: &str| -> io::Result<u32> {
+            json.usize_field(key)
+                .and_then(|x| u32::try_from(x).ok())
+                .ok_or_else(|| error(&format!("缺少或无效的字段 `{}`", key)))
+        };
+
+        let jobs = json
+            .array_field("jobs")
+            .iter()
+            .map(|job| {
+                Some(FarmJob {
+                    id: job.usize_field("id")?,
+                    frames: range(job.get("frames")?)?,
+                    band: u32::try_from(job.usize_field("band")?).ok()?,
+                    rows: range(job.get("rows")?)?,
+                })
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| error("任务列表无效"))?;
+
+        Ok(Self {
+            spec: json
+                .get("spec")
+                .and_then(Json::as_str)
+                .map(PathBuf::from)
+                .ok_or_else(|| error("缺少动画描述路径"))?,
+            output: json
+                .get("output")
+                .and_then(Json::as_str)
+                .map(String::from)
+                .ok_or_else(|| error("缺少帧文件名模板"))?,
+            samples_per_pixel: optional("samples_per_pixel"),
+            max_depth: optional("max_depth"),
+            image_width: field("image_width")?,
+            image_height: field("image_height")?,
+            bands: field("bands")?.max(1),
+            frames: json
+                .get("frames")
+                .and_then(range)
+                .ok_or_else(|| error("缺少帧范围"))?,
+            jobs,
+        })
+    }
+
+    /// 从文件加载清单
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        Self::parse(&std::fs::read_to_string(path)?, &path.display().to_string())
+    }
+
+    /// 写出到文件
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+
+    /// 加载动画描述并应用清单中记录的输出模板和采样参数
+    pub fn animation(&self) -> io::Result<AnimationSpec> {
+        let mut spec = AnimationSpec::load(&self.spec)?;
+        spec.output = self.output.clone();
+        spec.samples_per_pixel = self.samples_per_pixel;
+        spec.max_depth = self.max_depth;
+        Ok(spec)
+    }
+
+    /// 第 frame 帧第 band 个行带的文件路径
+    pub fn band_path(&self, spec: &AnimationSpec, frame: u32, band: u32) -> PathBuf {
+        spec.frame_path(frame)
+            .with_extension(format!("band{:02}.rtband", band))
+    }
+
+    /// 任务的输出是否都已存在
+    pub fn is_done(&self, spec: &AnimationSpec, job: &FarmJob) -> bool {
+        job.frames.clone().all(|frame| {
+            spec.frame_path(frame).exists()
+                || (self.bands > 1 && self.band_path(spec, frame, job.band).exists())
+        })
+    }
+
+    /// 运行一个任务，跳过已完成的帧，返回实际渲染的帧数
+    pub fn run_job(&self, spec: &AnimationSpec, job: &FarmJob) -> io::Result<usize> {
+        let mut rendered = 0;
+        for frame in job.frames.clone() {
+            if spec.frame_path(frame).exists() {
+                continue;
+            }
+            if self.bands == 1 {
+                spec.render_frame(frame)?;
+            } else {
+                let path = self.band_path(spec, frame, job.band);
+                if path.exists() {
+                    continue;
+                }
+                let region = Crop::new(
+                    0,
+                    job.rows.start as i32,
+                    self.image_width as i32,
+                    job.rows.end as i32,
+                );
+                let fb = spec.render_region(frame, region)?;
+                write_band(&fb, job.rows.clone(), &path)?;
+            }
+            rendered += 1;
+        }
+        Ok(rendered)
+    }
+
+    /// 认领文件所在的目录：清单文件名加上 `.claims`
+    pub fn claims_dir(manifest_path: &Path) -> PathBuf {
+        manifest_path.with_extension("claims")
+    }
+
+    /// 原子地认领任务，已被其他工作进程认领时返回false
+    pub fn claim(manifest_path: &Path, job: &FarmJob) -> io::Result<bool> {
+        let dir = Self::claims_dir(manifest_path);
+        std::fs::create_dir_all(&dir)?;
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(dir.join(format!("job_{}", job.id)))
+        {
+            Ok(mut file) => {
+                writeln!(file, "pid {}", std::process::id())?;
+                Ok(true)
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 释放认领（任务失败时调用，使其他工作进程可以重试）
+    pub fn release(manifest_path: &Path, job: &FarmJob) -> io::Result<()> {
+        std::fs::remove_file(Self::claims_dir(manifest_path).join(format!("job_{}", job.id)))
+    }
+
+    /// 把行带齐全的帧拼合为最终的帧文件，keep_bands 为假时删除已拼合的行带文件
+    pub fn merge(&self, spec: &AnimationSpec, keep_bands: bool) -> io::Result<MergeSummary> {
+        let mut summary = MergeSummary::default();
+        for frame in self.frames.clone() {
+            let path = spec.frame_path(frame);
+            if path.exists() {
+                summary.complete += 1;
+                continue;
+            }
+            if self.bands == 1 {
+                summary.incomplete.push((frame, Vec::new()));
+                continue;
+            }
+            let bands: Vec<PathBuf> = (0..self.bands)
+                .map(|band| self.band_path(spec, frame, band))
+                .collect();
+            let missing: Vec<u32> = (0..self.bands)
+                .filter(|&band| !bands[band as usize].exists())
+                .collect();
+            if !missing.is_empty() {
+                summary.incomplete.push((frame, missing));
+                continue;
+            }
+
+            let mut fb = FrameBuffer::new(self.image_width, self.image_height);
+            for band in &bands {
+                read_band(band, &mut fb)?;
+            }
+            save_complete(&fb, &path)?;
+            if !keep_bands {
+                for band in &bands {
+                    std::fs::remove_file(band)?;
+                }
+            }
+            summary.merged.push(frame);
+        }
+        Ok(summary)
+    }
+}
+
+/// 把 height 行均分为 bands 个行带，返回第 band 个
+fn band_rows(height: u32, bands: u32, band: u32) -> Range<u32> {
+    let edge = |k: u32| (height as u64 * k as u64 / bands as u64) as u32;
+    edge(band)..edge(band + 1)
+}
+
+/// 写出行带文件：一行文本头（标识、图像尺寸、行范围、透明度、色彩空间），之后是小端 f64 的线性颜色
+///
+/// 先写临时文件再重命名，已存在的行带文件总是完整的。
+fn write_band(fb: &FrameBuffer, rows: Range<u32>, path: &Path) -> io::Result<()> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let partial = path.with_extension("partial");
+    let mut out = BufWriter::new(File::create(&partial)?);
+    writeln!(
+        out,
+        "{} {} {} {} {} {} {}",
+        BAND_MAGIC,
+        fb.width(),
+        fb.height(),
+        rows.start,
+        rows.end,
+        fb.has_alpha() as u8,
+        fb.color_space()
+    )?;
+    for y in rows {
+        for x in 0..fb.width() {
+            let c = fb.get(x, y);
+            for value in [c.x, c.y, c.z] {
+                out.write_all(&value.to_le_bytes())?;
+            }
+            if fb.has_alpha() {
+                out.write_all(&fb.alpha(x, y).to_le_bytes())?;
+            }
+        }
+    }
+    out.flush()?;
+    drop(out);
+    std::fs::rename(&partial, path)
+}
+
+/// 读取行带文件并写入帧缓冲区的对应行
+fn read_band(path: &Path, fb: &mut FrameBuffer) -> io::Result<()> {
+    let invalid = |message: &str| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {}", path.display(), message),
+        )
+    };
+    let mut input = BufReader::new(File::open(path)?);
+    let mut header = Vec::new();
+    let mut byte = [0u8];
+    while input.read(&mut byte)? == 1 && byte[0] != b'\n' {
+        header.push(byte[0]);
+    }
+    let header = String::from_utf8_lossy(&header);
+    let fields: Vec<&str> = header.split_whitespace().collect();
+    let [magic, width, height, y0, y1, alpha, color_space] = fields[..] else {
+        return Err(invalid("文件头无效"));
+    };
+    let number = |s: &str| s.parse::<u32>().map_err(|_| invalid("文件头无效"));
+    let (width, height, y0, y1) = (number(width)?, number(height)?, number(y0)?, number(y1)?);
+    if magic != BAND_MAGIC {
+        return Err(invalid("不是行带文件"));
+    }
+    if (width, height) != (fb.width(), fb.height()) || y0 > y1 || y1 > height {
+        return Err(invalid("图像尺寸或行范围与清单不符"));
+    }
+    let alpha = alpha == "1";
+    let color_space: OutputColorSpace = color_space.parse().map_err(|e: String| invalid(&e))?;
+    fb.set_color_space(color_space);
+    if alpha {
+        fb.enable_alpha();
+    }
+
+    let mut value = || -> io::Result<f64> {
+        let mut bytes = [0u8; 8];
+        input.read_exact(&mut bytes)?;
+        Ok(f64::from_le_bytes(bytes))
+    };
+    for y in y0..y1 {
+        for x in 0..width {
+            let color = Color::new(value()?, value()?, value()?);
+            fb.set(x, y, color);
+            if alpha {
+                fb.set_alpha(x, y, value()?);
+            }
+        }
+    }
+    Ok(())
+}
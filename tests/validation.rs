@@ -0,0 +1,73 @@
+//! 在 `cargo test` 下运行渲染器自检（与 `cargo run --release -- validate` 相同的检查）
+
+use ray_tracing_rust::ray_tracing::validation::check::CheckResult;
+use ray_tracing_rust::ray_tracing::validation::{
+    alpha_occlusion, furnace, lens_grid, light_pdfs, light_transforms, media, mesh_precision,
+    pdf_chi2, quad_seams, scene_roundtrip, shutter,
+};
+
+/// 所有检查都应通过，失败时列出失败项
+fn assert_passed(results: Vec<CheckResult>) {
+    assert!(!results.is_empty(), "没有运行任何检查");
+    let failed: Vec<String> = results
+        .iter()
+        .filter(|r| !r.passed)
+        .map(ToString::to_string)
+        .collect();
+    assert!(failed.is_empty(), "{}", failed.join("\n"));
+}
+
+#[test]
+fn light_transforms() {
+    assert_passed(light_transforms::run());
+}
+
+#[test]
+fn light_pdfs() {
+    assert_passed(light_pdfs::run());
+}
+
+#[test]
+fn pdf_chi2() {
+    assert_passed(pdf_chi2::run());
+}
+
+#[test]
+fn shutter() {
+    assert_passed(shutter::run());
+}
+
+#[test]
+fn lens_grid() {
+    assert_passed(lens_grid::run());
+}
+
+#[test]
+fn furnace() {
+    assert_passed(furnace::run());
+}
+
+#[test]
+fn media() {
+    assert_passed(media::run());
+}
+
+#[test]
+fn quad_seams() {
+    assert_passed(quad_seams::run());
+}
+
+#[test]
+fn alpha_occlusion() {
+    assert_passed(alpha_occlusion::run());
+}
+
+#[test]
+fn mesh_precision() {
+    assert_passed(mesh_precision::run());
+}
+
+#[test]
+fn scene_roundtrip() {
+    assert_passed(scene_roundtrip::run());
+}